@@ -33,8 +33,8 @@ fn create_test_character_data(
     max_hp: i32,
 ) -> mimir_dm_core::models::character::CharacterData {
     use mimir_dm_core::models::character::{
-        AbilityScores, CharacterData, ClassLevel, Currency, EquippedItems, Personality,
-        Proficiencies, SpellData,
+        AbilityScores, CharacterData, ClassLevel, Currency, DeathSaves, EquippedItems,
+        Personality, Proficiencies, SpellData,
     };
 
     CharacterData {
@@ -66,6 +66,11 @@ fn create_test_character_data(
         },
         max_hp,
         current_hp: max_hp,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![],
@@ -76,7 +81,9 @@ fn create_test_character_data(
             languages: vec!["Common".to_string()],
         },
         class_features: vec![],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![],
         currency: Currency::default(),
@@ -268,6 +275,7 @@ impl AgentTestExecutor {
                     ollama_config: Some(OllamaConfig {
                         base_url: ollama_url.to_string(),
                         model: Some(model_name),
+                        embedding_model: None,
                     }),
                     groq_config: None,
                     tool_confirmation_timeout_secs: 30,
@@ -0,0 +1,5 @@
+-- Drop combat encounter tables
+DROP INDEX IF EXISTS idx_combat_combatants_encounter_id;
+DROP INDEX IF EXISTS idx_combat_encounters_campaign_id;
+DROP TABLE IF EXISTS combat_combatants;
+DROP TABLE IF EXISTS combat_encounters;
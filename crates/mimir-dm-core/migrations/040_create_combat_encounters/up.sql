@@ -0,0 +1,41 @@
+-- Create combat encounter tables for the initiative tracker
+-- An encounter holds round/turn state; combatants hold per-creature initiative and HP
+CREATE TABLE combat_encounters (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    campaign_id INTEGER NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    module_id INTEGER REFERENCES modules(id) ON DELETE SET NULL,
+    map_id INTEGER REFERENCES maps(id) ON DELETE SET NULL,
+    name TEXT NOT NULL,
+    encounter_tag TEXT,  -- Module encounter tag this combat was started from
+    status TEXT NOT NULL DEFAULT 'active',  -- 'active', 'completed'
+    round INTEGER NOT NULL DEFAULT 1,
+    current_combatant_id INTEGER,  -- Whose turn it is (NULL before the first turn)
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE combat_combatants (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    encounter_id INTEGER NOT NULL REFERENCES combat_encounters(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    combatant_type TEXT NOT NULL DEFAULT 'monster',  -- 'monster', 'pc', 'npc'
+    monster_name TEXT,  -- Catalog monster name (for monster combatants)
+    monster_source TEXT,  -- Catalog monster source
+    character_id INTEGER REFERENCES characters(id) ON DELETE SET NULL,
+    token_id INTEGER REFERENCES tokens(id) ON DELETE SET NULL,
+    initiative INTEGER NOT NULL DEFAULT 0,
+    initiative_modifier INTEGER NOT NULL DEFAULT 0,
+    current_hp INTEGER,
+    max_hp INTEGER,
+    armor_class INTEGER,
+    is_active INTEGER NOT NULL DEFAULT 1,  -- 0 once defeated or removed from the turn order
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Index for fast lookup by campaign (resuming the active encounter)
+CREATE INDEX idx_combat_encounters_campaign_id ON combat_encounters(campaign_id);
+
+-- Index for loading an encounter's combatants
+CREATE INDEX idx_combat_combatants_encounter_id ON combat_combatants(encounter_id);
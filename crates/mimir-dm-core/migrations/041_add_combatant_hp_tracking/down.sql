@@ -0,0 +1,7 @@
+-- Drop combatant HP tracking tables
+DROP INDEX IF EXISTS idx_combat_hp_events_combatant_id;
+DROP INDEX IF EXISTS idx_combat_conditions_combatant_id;
+DROP TABLE IF EXISTS combat_hp_events;
+DROP TABLE IF EXISTS combat_conditions;
+
+ALTER TABLE combat_combatants DROP COLUMN temp_hp;
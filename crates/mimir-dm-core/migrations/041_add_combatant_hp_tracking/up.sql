@@ -0,0 +1,36 @@
+-- Per-combatant temporary HP, applied conditions and HP change history
+
+-- Temporary HP absorbs damage before current HP
+ALTER TABLE combat_combatants ADD COLUMN temp_hp INTEGER NOT NULL DEFAULT 0;
+
+-- Conditions applied to a combatant, referencing catalog_conditions by name/source
+CREATE TABLE combat_conditions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    combatant_id INTEGER NOT NULL REFERENCES combat_combatants(id) ON DELETE CASCADE,
+    condition_name TEXT NOT NULL,
+    condition_source TEXT NOT NULL,
+    applied_round INTEGER NOT NULL DEFAULT 1,
+    duration_rounds INTEGER,  -- NULL for conditions that last until removed
+    notes TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Damage, healing and temp HP history for each combatant
+CREATE TABLE combat_hp_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    combatant_id INTEGER NOT NULL REFERENCES combat_combatants(id) ON DELETE CASCADE,
+    round INTEGER NOT NULL DEFAULT 1,
+    event_type TEXT NOT NULL,  -- 'damage', 'healing', 'temp_hp'
+    amount INTEGER NOT NULL,  -- Amount as rolled, before resistances
+    damage_type TEXT,  -- e.g. 'fire', 'slashing' (damage only)
+    applied_amount INTEGER NOT NULL,  -- Amount after resistances/immunities/vulnerabilities
+    defense TEXT,  -- 'resistance', 'immunity', 'vulnerability' if one applied
+    hp_before INTEGER,
+    hp_after INTEGER,
+    temp_hp_before INTEGER NOT NULL DEFAULT 0,
+    temp_hp_after INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_combat_conditions_combatant_id ON combat_conditions(combatant_id);
+CREATE INDEX idx_combat_hp_events_combatant_id ON combat_hp_events(combatant_id);
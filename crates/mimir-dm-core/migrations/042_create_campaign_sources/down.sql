@@ -0,0 +1,3 @@
+-- Drop campaign source settings
+DROP INDEX IF EXISTS idx_campaign_sources_campaign_id;
+DROP TABLE IF EXISTS campaign_sources;
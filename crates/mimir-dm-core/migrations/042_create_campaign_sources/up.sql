@@ -0,0 +1,11 @@
+-- Source books enabled for a campaign
+-- A campaign with no rows here allows every imported source
+CREATE TABLE campaign_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    campaign_id INTEGER NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    source TEXT NOT NULL,  -- Source book abbreviation, e.g. 'MM', 'VGM'
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(campaign_id, source)
+);
+
+CREATE INDEX idx_campaign_sources_campaign_id ON campaign_sources(campaign_id);
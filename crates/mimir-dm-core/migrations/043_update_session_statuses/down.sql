@@ -0,0 +1,41 @@
+-- Restore the original session statuses
+
+CREATE TABLE sessions_old (
+    id INTEGER PRIMARY KEY,
+    campaign_id INTEGER NOT NULL,
+    module_id INTEGER,
+    session_number INTEGER NOT NULL,
+    status TEXT NOT NULL CHECK(status IN ('next_week', 'prep_needed', 'in_prep', 'ready', 'complete')),
+    scheduled_date TEXT,  -- ISO date format YYYY-MM-DD
+    prep_started_at TEXT,
+    prep_completed_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (campaign_id) REFERENCES campaigns(id) ON DELETE CASCADE,
+    FOREIGN KEY (module_id) REFERENCES modules(id) ON DELETE SET NULL
+);
+
+INSERT INTO sessions_old (
+    id, campaign_id, module_id, session_number, status, scheduled_date,
+    prep_started_at, prep_completed_at, completed_at, created_at
+)
+SELECT
+    id, campaign_id, module_id, session_number,
+    CASE status
+        WHEN 'prepping' THEN 'in_prep'
+        WHEN 'ready' THEN 'ready'
+        WHEN 'played' THEN 'complete'
+        ELSE 'prep_needed'
+    END,
+    scheduled_date, prep_started_at, prep_completed_at, completed_at, created_at
+FROM sessions;
+
+DROP TABLE sessions;
+
+ALTER TABLE sessions_old RENAME TO sessions;
+
+CREATE INDEX idx_sessions_campaign ON sessions(campaign_id);
+CREATE INDEX idx_sessions_module ON sessions(module_id);
+CREATE INDEX idx_sessions_status ON sessions(status);
+CREATE INDEX idx_sessions_scheduled ON sessions(scheduled_date);
+CREATE UNIQUE INDEX idx_sessions_campaign_number ON sessions(campaign_id, session_number);
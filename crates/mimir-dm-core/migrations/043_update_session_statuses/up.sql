@@ -0,0 +1,46 @@
+-- Move sessions onto the session board stages (planned -> prepping -> ready -> played)
+-- SQLite requires table recreation to change a CHECK constraint
+
+CREATE TABLE sessions_new (
+    id INTEGER PRIMARY KEY,
+    campaign_id INTEGER NOT NULL,
+    module_id INTEGER,
+    session_number INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'planned' CHECK(status IN ('planned', 'prepping', 'ready', 'played')),
+    scheduled_date TEXT,  -- ISO date format YYYY-MM-DD
+    prep_started_at TEXT,
+    prep_completed_at TEXT,
+    completed_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (campaign_id) REFERENCES campaigns(id) ON DELETE CASCADE,
+    FOREIGN KEY (module_id) REFERENCES modules(id) ON DELETE SET NULL
+);
+
+-- Copy existing data, mapping the old statuses onto board stages
+INSERT INTO sessions_new (
+    id, campaign_id, module_id, session_number, status, scheduled_date,
+    prep_started_at, prep_completed_at, completed_at, created_at
+)
+SELECT
+    id, campaign_id, module_id, session_number,
+    CASE status
+        WHEN 'in_prep' THEN 'prepping'
+        WHEN 'ready' THEN 'ready'
+        WHEN 'complete' THEN 'played'
+        ELSE 'planned'
+    END,
+    scheduled_date, prep_started_at, prep_completed_at, completed_at, created_at
+FROM sessions;
+
+-- Drop old table
+DROP TABLE sessions;
+
+-- Rename new table
+ALTER TABLE sessions_new RENAME TO sessions;
+
+-- Recreate indexes
+CREATE INDEX idx_sessions_campaign ON sessions(campaign_id);
+CREATE INDEX idx_sessions_module ON sessions(module_id);
+CREATE INDEX idx_sessions_status ON sessions(status);
+CREATE INDEX idx_sessions_scheduled ON sessions(scheduled_date);
+CREATE UNIQUE INDEX idx_sessions_campaign_number ON sessions(campaign_id, session_number);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS lore_vectors;
+DROP INDEX IF EXISTS idx_lore_chunks_source_id;
+DROP INDEX IF EXISTS idx_lore_sources_campaign_id;
+DROP TABLE IF EXISTS lore_index_info;
+DROP TABLE IF EXISTS lore_chunks;
+DROP TABLE IF EXISTS lore_sources;
@@ -0,0 +1,36 @@
+-- Semantic search index over book content and campaign documents
+-- Embeddings live in the lore_vectors vec0 table, keyed by lore_chunks.id.
+-- That table is created on first index since its width depends on the model.
+
+-- One row per indexed book or document
+CREATE TABLE lore_sources (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    source_type TEXT NOT NULL CHECK (source_type IN ('book', 'document')),
+    source_key TEXT NOT NULL,  -- Book ID (e.g. 'PHB') or document ID
+    campaign_id INTEGER REFERENCES campaigns(id) ON DELETE CASCADE,  -- NULL for books
+    title TEXT NOT NULL,
+    content_hash TEXT NOT NULL,  -- SHA-256 of the indexed text, for staleness checks
+    chunk_count INTEGER NOT NULL DEFAULT 0,
+    indexed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(source_type, source_key)
+);
+
+-- Text chunks that were embedded
+CREATE TABLE lore_chunks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    source_id INTEGER NOT NULL REFERENCES lore_sources(id) ON DELETE CASCADE,
+    chunk_index INTEGER NOT NULL,
+    heading TEXT,  -- Nearest section heading, if any
+    content TEXT NOT NULL
+);
+
+-- Embedding model the index was built with (single row)
+CREATE TABLE lore_index_info (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    embedding_model TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_lore_sources_campaign_id ON lore_sources(campaign_id);
+CREATE INDEX idx_lore_chunks_source_id ON lore_chunks(source_id);
@@ -0,0 +1,10 @@
+-- Full-text index over catalog names and entry text.
+-- Rows are written by CatalogSearchService when a book's catalogs are imported.
+CREATE VIRTUAL TABLE catalog_search USING fts5(
+    catalog_type UNINDEXED,
+    catalog_id UNINDEXED,
+    source UNINDEXED,
+    name,
+    content,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
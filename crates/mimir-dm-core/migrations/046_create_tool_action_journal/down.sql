@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_tool_action_journal_session_id;
+DROP TABLE IF EXISTS tool_action_journal;
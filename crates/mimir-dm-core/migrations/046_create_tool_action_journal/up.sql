@@ -0,0 +1,13 @@
+-- Journal of mutating LLM tool calls, so assistant actions can be undone per chat session
+CREATE TABLE tool_action_journal (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    session_id TEXT NOT NULL,  -- Chat session the tool was called from
+    tool_name TEXT NOT NULL,
+    arguments TEXT NOT NULL,  -- Tool arguments as JSON
+    summary TEXT NOT NULL,  -- Human-readable description of the action
+    revert_action TEXT NOT NULL,  -- JSON-encoded RevertAction with the before-state
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    undone_at TEXT  -- Set once the action has been reverted
+);
+
+CREATE INDEX idx_tool_action_journal_session_id ON tool_action_journal(session_id);
@@ -0,0 +1,5 @@
+-- Drop wall and door tables and indexes
+DROP INDEX IF EXISTS idx_map_doors_map_id;
+DROP TABLE IF EXISTS map_doors;
+DROP INDEX IF EXISTS idx_map_walls_map_id;
+DROP TABLE IF EXISTS map_walls;
@@ -0,0 +1,30 @@
+-- Create wall and door tables for map line-of-sight geometry
+-- Segments are stored in pixel coordinates of the stored map image
+CREATE TABLE map_walls (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    map_id INTEGER NOT NULL REFERENCES maps(id) ON DELETE CASCADE,
+    x1 REAL NOT NULL,
+    y1 REAL NOT NULL,
+    x2 REAL NOT NULL,
+    y2 REAL NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Index for fast lookup by map
+CREATE INDEX idx_map_walls_map_id ON map_walls(map_id);
+
+-- Doors block sight while closed or locked
+CREATE TABLE map_doors (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    map_id INTEGER NOT NULL REFERENCES maps(id) ON DELETE CASCADE,
+    x1 REAL NOT NULL,
+    y1 REAL NOT NULL,
+    x2 REAL NOT NULL,
+    y2 REAL NOT NULL,
+    state TEXT NOT NULL DEFAULT 'closed',  -- 'open', 'closed', 'locked'
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Index for fast lookup by map
+CREATE INDEX idx_map_doors_map_id ON map_doors(map_id);
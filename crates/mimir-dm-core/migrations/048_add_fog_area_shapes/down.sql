@@ -0,0 +1,10 @@
+-- Remove fog area shapes and sources
+DROP INDEX IF EXISTS idx_fog_revealed_areas_map_source;
+
+-- Explored and non-rectangular areas cannot be represented without these columns
+DELETE FROM fog_revealed_areas WHERE source = 'explored' OR shape = 'polygon';
+
+ALTER TABLE fog_revealed_areas DROP COLUMN source;
+ALTER TABLE fog_revealed_areas DROP COLUMN clip_points;
+ALTER TABLE fog_revealed_areas DROP COLUMN points;
+ALTER TABLE fog_revealed_areas DROP COLUMN shape;
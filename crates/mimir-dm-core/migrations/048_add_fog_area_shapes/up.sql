@@ -0,0 +1,14 @@
+-- Fog revealed areas can be circles and polygons as well as rectangles,
+-- and are either revealed by the DM or explored by PC tokens.
+-- x, y, width and height remain the bounding box of every shape.
+
+-- 'rect', 'circle' or 'polygon'
+ALTER TABLE fog_revealed_areas ADD COLUMN shape TEXT NOT NULL DEFAULT 'rect';
+-- Polygon vertices as a JSON array of {"x", "y"} points
+ALTER TABLE fog_revealed_areas ADD COLUMN points TEXT;
+-- Optional second polygon limiting the area (e.g. the line of sight a lit area was seen through)
+ALTER TABLE fog_revealed_areas ADD COLUMN clip_points TEXT;
+-- 'manual' (revealed by the DM) or 'explored' (seen by PC tokens)
+ALTER TABLE fog_revealed_areas ADD COLUMN source TEXT NOT NULL DEFAULT 'manual';
+
+CREATE INDEX idx_fog_revealed_areas_map_source ON fog_revealed_areas(map_id, source);
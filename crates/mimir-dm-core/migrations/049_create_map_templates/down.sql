@@ -0,0 +1,5 @@
+-- Remove map templates and the campaign diagonal rule
+ALTER TABLE campaigns DROP COLUMN diagonal_rule;
+
+DROP INDEX IF EXISTS idx_map_templates_map_id;
+DROP TABLE IF EXISTS map_templates;
@@ -0,0 +1,28 @@
+-- Area of effect templates placed on maps (spell cones, spheres, lines, ...)
+CREATE TABLE map_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    map_id INTEGER NOT NULL REFERENCES maps(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    shape TEXT NOT NULL,  -- 'cone', 'cube', 'sphere', 'line', 'cylinder'
+    -- Point of origin in pixel coordinates
+    x REAL NOT NULL,
+    y REAL NOT NULL,
+    -- Direction in degrees clockwise from east (ignored for spheres and cylinders)
+    direction_deg REAL NOT NULL DEFAULT 0.0,
+    -- Cone/line length, cube side or sphere/cylinder radius in feet
+    size_ft REAL NOT NULL,
+    -- Line width in feet
+    width_ft REAL NOT NULL DEFAULT 5.0,
+    -- Catalog spell the template was made from, if any
+    spell_name TEXT,
+    spell_source TEXT,
+    color TEXT,
+    visible_to_players INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_map_templates_map_id ON map_templates(map_id);
+
+-- How grid distances count diagonals: 'alternating' (5/10/5) or 'euclidean'
+ALTER TABLE campaigns ADD COLUMN diagonal_rule TEXT NOT NULL DEFAULT 'alternating';
@@ -4,12 +4,35 @@ use diesel::prelude::*;
 use diesel::sqlite::SqliteConnection;
 
 use crate::error::Result;
+use std::sync::Once;
+
+static VECTOR_EXTENSION: Once = Once::new();
+
+/// Register the sqlite-vec extension for every connection opened afterwards.
+///
+/// SQLite auto-extensions are process wide, so this only needs to run once
+/// before the first connection is established. Safe to call repeatedly.
+pub fn register_vector_extension() {
+    VECTOR_EXTENSION.call_once(|| {
+        // SAFETY: sqlite3_vec_init has the signature SQLite expects for an
+        // extension entry point; sqlite-vec exports it without the type so it
+        // can be linked against any libsqlite3 binding.
+        #[allow(clippy::missing_transmute_annotations)]
+        unsafe {
+            libsqlite3_sys::sqlite3_auto_extension(Some(std::mem::transmute(
+                sqlite_vec::sqlite3_vec_init as *const (),
+            )));
+        }
+    });
+}
 
 /// Type alias for our database connection
 pub type DbConnection = SqliteConnection;
 
 /// Establish a connection to the SQLite database
 pub fn establish_connection(database_url: &str) -> Result<DbConnection> {
+    register_vector_extension();
+
     let mut conn = DbConnection::establish(database_url)?;
 
     // Enable foreign key constraints
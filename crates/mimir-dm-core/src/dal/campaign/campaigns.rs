@@ -3,7 +3,7 @@
 use crate::connection::DbConnection;
 use crate::error::Result;
 use crate::models::campaign::campaigns::{Campaign, NewCampaign, UpdateCampaign};
-use crate::schema::campaigns;
+use crate::schema::{campaign_sources, campaigns};
 use chrono::Utc;
 use diesel::prelude::*;
 
@@ -122,4 +122,33 @@ impl<'a> CampaignRepository<'a> {
 
         self.update(id, update)
     }
+
+    /// List the source books enabled for a campaign
+    pub fn list_sources(&mut self, id: i32) -> Result<Vec<String>> {
+        campaign_sources::table
+            .filter(campaign_sources::campaign_id.eq(id))
+            .select(campaign_sources::source)
+            .order_by(campaign_sources::source.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Replace the source books enabled for a campaign
+    pub fn set_sources(&mut self, id: i32, sources: &[String]) -> Result<Vec<String>> {
+        self.conn.transaction(|conn| {
+            diesel::delete(campaign_sources::table.filter(campaign_sources::campaign_id.eq(id)))
+                .execute(conn)?;
+            for source in sources {
+                diesel::insert_or_ignore_into(campaign_sources::table)
+                    .values((
+                        campaign_sources::campaign_id.eq(id),
+                        campaign_sources::source.eq(source),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok::<_, diesel::result::Error>(())
+        })?;
+
+        self.list_sources(id)
+    }
 }
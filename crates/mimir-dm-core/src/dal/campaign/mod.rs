@@ -6,5 +6,6 @@ pub mod campaigns;
 pub mod documents;
 pub mod module_monsters;
 pub mod modules;
+pub mod sessions;
 pub mod template_documents;
 pub mod workflow_cards;
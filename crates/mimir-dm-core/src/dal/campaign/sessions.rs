@@ -0,0 +1,95 @@
+//! Session data access layer
+
+use crate::connection::DbConnection;
+use crate::error::Result;
+use crate::models::campaign::sessions::{NewSession, Session, UpdateSession};
+use crate::schema::sessions;
+use diesel::prelude::*;
+
+/// Repository for session operations
+pub struct SessionRepository<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> SessionRepository<'a> {
+    /// Create a new session repository
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a new session
+    pub fn create(&mut self, new_session: NewSession) -> Result<Session> {
+        diesel::insert_into(sessions::table)
+            .values(&new_session)
+            .returning(Session::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Find a session by ID
+    pub fn find_by_id(&mut self, id: i32) -> Result<Option<Session>> {
+        sessions::table
+            .find(id)
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Update a session
+    pub fn update(&mut self, id: i32, update: UpdateSession) -> Result<Session> {
+        diesel::update(sessions::table.find(id))
+            .set(&update)
+            .returning(Session::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Delete a session
+    pub fn delete(&mut self, id: i32) -> Result<()> {
+        diesel::delete(sessions::table.find(id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// List all sessions for a campaign
+    pub fn list_by_campaign(&mut self, campaign_id: i32) -> Result<Vec<Session>> {
+        sessions::table
+            .filter(sessions::campaign_id.eq(campaign_id))
+            .order_by(sessions::session_number)
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// List all sessions for a module
+    pub fn list_by_module(&mut self, module_id: i32) -> Result<Vec<Session>> {
+        sessions::table
+            .filter(sessions::module_id.eq(module_id))
+            .order_by(sessions::session_number)
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// List sessions by status for a campaign
+    pub fn list_by_campaign_and_status(
+        &mut self,
+        campaign_id: i32,
+        status: &str,
+    ) -> Result<Vec<Session>> {
+        sessions::table
+            .filter(sessions::campaign_id.eq(campaign_id))
+            .filter(sessions::status.eq(status))
+            .order_by(sessions::session_number)
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get the next session number for a campaign
+    pub fn get_next_session_number(&mut self, campaign_id: i32) -> Result<i32> {
+        let max_number = sessions::table
+            .filter(sessions::campaign_id.eq(campaign_id))
+            .select(diesel::dsl::max(sessions::session_number))
+            .first::<Option<i32>>(self.conn)?
+            .unwrap_or(0);
+
+        Ok(max_number + 1)
+    }
+}
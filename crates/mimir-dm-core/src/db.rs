@@ -18,6 +18,8 @@ pub struct DatabaseService {
 impl DatabaseService {
     /// Create a new DatabaseService with connection pooling
     pub fn new(database_url: &str, is_memory_db: bool) -> Result<Self> {
+        crate::connection::register_vector_extension();
+
         let manager = ConnectionManager::<SqliteConnection>::new(database_url);
 
         let mut pool_builder = Pool::builder();
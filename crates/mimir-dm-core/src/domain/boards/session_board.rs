@@ -0,0 +1,139 @@
+//! Session board definition.
+
+use super::{BoardDefinition, StageMetadata};
+
+/// Session workflow board with progression stages.
+pub struct SessionBoard;
+
+impl Default for SessionBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionBoard {
+    /// Creates a new session board.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl BoardDefinition for SessionBoard {
+    fn board_type(&self) -> &str {
+        "session"
+    }
+
+    fn stages(&self) -> Vec<&str> {
+        vec!["planned", "prepping", "ready", "played"]
+    }
+
+    fn can_transition(&self, from: &str, to: &str) -> bool {
+        match (from, to) {
+            // Forward progression
+            ("planned", "prepping") => true,
+            ("prepping", "ready") => true,
+            ("ready", "played") => true,
+
+            // Allow moving back
+            ("prepping", "planned") => true,
+            ("ready", "prepping") => true,
+
+            _ => false,
+        }
+    }
+
+    fn required_documents(&self, stage: &str) -> Vec<&str> {
+        match stage {
+            "prepping" => vec!["session_outline"],
+            "played" => vec!["session_notes"],
+            _ => vec![],
+        }
+    }
+
+    fn optional_documents(&self, _stage: &str) -> Vec<&str> {
+        vec![]
+    }
+
+    fn next_stage(&self, current: &str) -> Option<&str> {
+        match current {
+            "planned" => Some("prepping"),
+            "prepping" => Some("ready"),
+            "ready" => Some("played"),
+            _ => None,
+        }
+    }
+
+    fn stage_metadata(&self, stage: &str) -> StageMetadata {
+        match stage {
+            "planned" => StageMetadata {
+                display_name: "Planned".to_string(),
+                description: "Session is on the calendar but prep hasn't started".to_string(),
+                completion_message: None,
+                transition_prompt: Some(
+                    "Ready to start prepping? This will create the session outline."
+                        .to_string(),
+                ),
+                help_text: None,
+                content: Some(
+                    r#"<h3>Planned: Pick a Date and a Direction</h3>
+<p>Schedule the session and note where the last one left off. A single sentence about what the players are likely to do next is enough to start prep.</p>"#
+                        .to_string(),
+                ),
+            },
+            "prepping" => StageMetadata {
+                display_name: "Prepping".to_string(),
+                description: "Outlining scenes, NPCs and encounters".to_string(),
+                completion_message: Some(
+                    "Session outline is done! The session is ready to run.".to_string(),
+                ),
+                transition_prompt: Some(
+                    "Have you finished the session outline? Marking it ready locks in your prep."
+                        .to_string(),
+                ),
+                help_text: None,
+                content: Some(
+                    r#"<h3>Prepping: Build the Session Outline</h3>
+<p>Work through the session outline: a strong opening, 3-5 potential scenes, the NPCs likely to appear and the encounters you want on hand.</p>
+
+<p class="integration-note">Prep situations, not plots. Players will surprise you, and the outline should make it easy to improvise.</p>"#
+                        .to_string(),
+                ),
+            },
+            "ready" => StageMetadata {
+                display_name: "Ready".to_string(),
+                description: "Prep is complete and the session is ready to run".to_string(),
+                completion_message: None,
+                transition_prompt: Some(
+                    "Finished playing? Marking the session played will create session notes."
+                        .to_string(),
+                ),
+                help_text: None,
+                content: Some(
+                    r#"<h3>Ready: Run the Session</h3>
+<p>Everything is prepared. Review the outline before play and keep notes on decisions, NPCs met and loose threads as you go.</p>"#
+                        .to_string(),
+                ),
+            },
+            "played" => StageMetadata {
+                display_name: "Played".to_string(),
+                description: "Session has been played".to_string(),
+                completion_message: None,
+                transition_prompt: None,
+                help_text: None,
+                content: Some(
+                    r#"<h3>Played: Capture What Happened</h3>
+<p>Fill in the session notes while the session is fresh. They feed the campaign summary and are the best reference for future sessions.</p>"#
+                        .to_string(),
+                ),
+            },
+            _ => StageMetadata {
+                display_name: stage.to_string(),
+                description: format!("Session in {} stage", stage),
+                completion_message: None,
+                transition_prompt: None,
+                help_text: None,
+                content: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,770 @@
+//! Dice expression parsing and rolling
+//!
+//! Supports standard dice notation used across the app and by the chat agent:
+//! - `1d20`, `d20`, `d%` (d100)
+//! - Keep/drop: `4d6kh3` (keep highest 3), `2d20kl1` (keep lowest 1), `4d6dl1` (drop lowest 1)
+//! - Exploding dice: `3d6!` (roll again on the maximum)
+//! - Constants and ability modifiers: `1d8+3`, `1d8+STR`, `1d20-DEX`
+//!
+//! A [`DiceRoller`] can be seeded for deterministic tests and keeps a log of
+//! every roll it makes.
+
+use crate::error::{DbError, Result};
+use crate::models::character::data::AbilityScores;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Maximum number of dice in a single term
+const MAX_DICE: u32 = 1000;
+/// Maximum number of sides on a die
+const MAX_SIDES: u32 = 1000;
+/// Maximum number of extra rolls a single exploding die can add
+const MAX_EXPLOSIONS: u32 = 100;
+
+/// Which dice of a pool count toward the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeepRule {
+    /// Keep the N highest dice (`kh`)
+    KeepHighest(u32),
+    /// Keep the N lowest dice (`kl`)
+    KeepLowest(u32),
+    /// Drop the N highest dice (`dh`)
+    DropHighest(u32),
+    /// Drop the N lowest dice (`dl`)
+    DropLowest(u32),
+}
+
+impl fmt::Display for KeepRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeepRule::KeepHighest(n) => write!(f, "kh{}", n),
+            KeepRule::KeepLowest(n) => write!(f, "kl{}", n),
+            KeepRule::DropHighest(n) => write!(f, "dh{}", n),
+            KeepRule::DropLowest(n) => write!(f, "dl{}", n),
+        }
+    }
+}
+
+/// A pool of identical dice, e.g. `4d6kh3`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DicePool {
+    /// Number of dice
+    pub count: u32,
+    /// Sides per die
+    pub sides: u32,
+    /// Keep/drop rule applied after rolling
+    pub keep: Option<KeepRule>,
+    /// Whether dice showing their maximum are rolled again and added
+    pub exploding: bool,
+}
+
+impl fmt::Display for DicePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+        if self.exploding {
+            write!(f, "!")?;
+        }
+        if let Some(keep) = self.keep {
+            write!(f, "{}", keep)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single term of a dice expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiceTerm {
+    /// Rolled dice
+    Dice(DicePool),
+    /// A flat number
+    Constant(i32),
+    /// An ability modifier (`STR`, `DEX`, `CON`, `INT`, `WIS`, `CHA`)
+    Ability(String),
+}
+
+impl fmt::Display for DiceTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiceTerm::Dice(pool) => write!(f, "{}", pool),
+            DiceTerm::Constant(n) => write!(f, "{}", n),
+            DiceTerm::Ability(ability) => write!(f, "{}", ability),
+        }
+    }
+}
+
+/// A parsed dice expression: signed terms added together
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiceExpression {
+    /// Terms with their sign (1 or -1)
+    pub terms: Vec<(i32, DiceTerm)>,
+}
+
+impl DiceExpression {
+    /// Parse a dice expression such as `4d6kh3`, `2d20kl1+5` or `1d8+STR`.
+    ///
+    /// # Arguments
+    /// * `expression` - The dice notation to parse (case and whitespace insensitive)
+    ///
+    /// # Returns
+    /// * `Ok(DiceExpression)` - The parsed expression
+    /// * `Err(DbError::InvalidData)` - If the notation is malformed or out of range
+    pub fn parse(expression: &str) -> Result<Self> {
+        let compact: String = expression
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        if compact.is_empty() {
+            return Err(invalid(expression, "expression is empty"));
+        }
+
+        let chars: Vec<char> = compact.chars().collect();
+        let mut pos = 0;
+        let mut terms = Vec::new();
+
+        while pos < chars.len() {
+            let sign = match chars[pos] {
+                '+' => {
+                    pos += 1;
+                    1
+                }
+                '-' => {
+                    pos += 1;
+                    -1
+                }
+                _ if terms.is_empty() => 1,
+                c => return Err(invalid(expression, &format!("unexpected '{}'", c))),
+            };
+
+            let start = pos;
+            while pos < chars.len() && chars[pos] != '+' && chars[pos] != '-' {
+                pos += 1;
+            }
+            let text: String = chars[start..pos].iter().collect();
+            if text.is_empty() {
+                return Err(invalid(expression, "missing term after sign"));
+            }
+
+            terms.push((
+                sign,
+                parse_term(&text).map_err(|e| invalid(expression, &e))?,
+            ));
+        }
+
+        Ok(Self { terms })
+    }
+
+    /// Check if the expression uses ability modifiers
+    pub fn uses_abilities(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|(_, term)| matches!(term, DiceTerm::Ability(_)))
+    }
+}
+
+impl fmt::Display for DiceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (sign, term)) in self.terms.iter().enumerate() {
+            match (index, *sign < 0) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            write!(f, "{}", term)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(expression: &str, reason: &str) -> DbError {
+    DbError::InvalidData(format!(
+        "Invalid dice expression '{}': {}",
+        expression, reason
+    ))
+}
+
+fn parse_term(text: &str) -> std::result::Result<DiceTerm, String> {
+    if let Some(ability) = normalize_ability(text) {
+        return Ok(DiceTerm::Ability(ability.to_string()));
+    }
+
+    let Some(d_pos) = text.find('d') else {
+        return text
+            .parse::<i32>()
+            .map(DiceTerm::Constant)
+            .map_err(|_| format!("'{}' is not a number, dice or ability", text));
+    };
+
+    let count = match &text[..d_pos] {
+        "" => 1,
+        n => n
+            .parse::<u32>()
+            .map_err(|_| format!("invalid dice count '{}'", n))?,
+    };
+
+    let rest = &text[d_pos + 1..];
+    let sides_len = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '%')
+        .unwrap_or(rest.len());
+    let sides = match &rest[..sides_len] {
+        "%" => 100,
+        "" => return Err("missing number of sides".to_string()),
+        n => n
+            .parse::<u32>()
+            .map_err(|_| format!("invalid number of sides '{}'", n))?,
+    };
+
+    let mut pool = DicePool {
+        count,
+        sides,
+        keep: None,
+        exploding: false,
+    };
+
+    let mut modifiers = &rest[sides_len..];
+    while !modifiers.is_empty() {
+        if let Some(after) = modifiers.strip_prefix('!') {
+            pool.exploding = true;
+            modifiers = after;
+            continue;
+        }
+
+        let (rule, after): (fn(u32) -> KeepRule, &str) =
+            if let Some(a) = modifiers.strip_prefix("kh") {
+                (KeepRule::KeepHighest, a)
+            } else if let Some(a) = modifiers.strip_prefix("kl") {
+                (KeepRule::KeepLowest, a)
+            } else if let Some(a) = modifiers.strip_prefix("dh") {
+                (KeepRule::DropHighest, a)
+            } else if let Some(a) = modifiers.strip_prefix("dl") {
+                (KeepRule::DropLowest, a)
+            } else if let Some(a) = modifiers.strip_prefix('k') {
+                (KeepRule::KeepHighest, a)
+            } else {
+                return Err(format!("unknown modifier '{}'", modifiers));
+            };
+
+        if pool.keep.is_some() {
+            return Err("only one keep/drop modifier is allowed".to_string());
+        }
+        let digits = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        let n = match &after[..digits] {
+            "" => 1,
+            n => n
+                .parse::<u32>()
+                .map_err(|_| format!("invalid keep count '{}'", n))?,
+        };
+        pool.keep = Some(rule(n));
+        modifiers = &after[digits..];
+    }
+
+    if pool.count == 0 || pool.count > MAX_DICE {
+        return Err(format!("dice count must be between 1 and {}", MAX_DICE));
+    }
+    if pool.sides == 0 || pool.sides > MAX_SIDES {
+        return Err(format!("dice sides must be between 1 and {}", MAX_SIDES));
+    }
+    if pool.exploding && pool.sides == 1 {
+        return Err("a d1 cannot explode".to_string());
+    }
+
+    Ok(DiceTerm::Dice(pool))
+}
+
+fn normalize_ability(text: &str) -> Option<&'static str> {
+    match text {
+        "str" | "strength" => Some("STR"),
+        "dex" | "dexterity" => Some("DEX"),
+        "con" | "constitution" => Some("CON"),
+        "int" | "intelligence" => Some("INT"),
+        "wis" | "wisdom" => Some("WIS"),
+        "cha" | "charisma" => Some("CHA"),
+        _ => None,
+    }
+}
+
+fn ability_modifier(abilities: &AbilityScores, ability: &str) -> i32 {
+    match ability {
+        "STR" => abilities.str_modifier(),
+        "DEX" => abilities.dex_modifier(),
+        "CON" => abilities.con_modifier(),
+        "INT" => abilities.int_modifier(),
+        "WIS" => abilities.wis_modifier(),
+        _ => abilities.cha_modifier(),
+    }
+}
+
+/// A single die rolled as part of a term
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DieRoll {
+    /// Face rolled
+    pub value: u32,
+    /// Whether the die counts toward the total (false if dropped)
+    pub kept: bool,
+    /// Whether this die was an extra roll from an explosion
+    pub exploded: bool,
+}
+
+/// The result of one term of an expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermResult {
+    /// The term as written, e.g. "4d6kh3" or "STR"
+    pub term: String,
+    /// 1 or -1
+    pub sign: i32,
+    /// Individual dice (empty for constants and abilities)
+    pub rolls: Vec<DieRoll>,
+    /// Signed contribution of this term to the total
+    pub value: i32,
+}
+
+/// The result of rolling a dice expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollResult {
+    /// The normalized expression that was rolled
+    pub expression: String,
+    /// Per-term results
+    pub terms: Vec<TermResult>,
+    /// Final total
+    pub total: i32,
+    /// Human-readable breakdown, e.g. "4d6kh3 [6, 5, 4, (2)] + 3 (STR) = 18"
+    pub breakdown: String,
+}
+
+/// Rolls dice expressions and keeps a log of every roll
+pub struct DiceRoller {
+    rng: StdRng,
+    log: Vec<RollResult>,
+}
+
+impl DiceRoller {
+    /// Create a roller seeded from system entropy.
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            log: Vec::new(),
+        }
+    }
+
+    /// Create a roller with a fixed seed, producing the same rolls every time.
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            log: Vec::new(),
+        }
+    }
+
+    /// Roll a single die.
+    pub fn roll_die(&mut self, sides: u32) -> u32 {
+        self.rng.gen_range(1..=sides.max(1))
+    }
+
+    /// Parse and roll an expression that doesn't use ability modifiers.
+    ///
+    /// # Arguments
+    /// * `expression` - Dice notation, e.g. `2d20kl1+5`
+    ///
+    /// # Returns
+    /// * `Ok(RollResult)` - The rolled result with breakdown
+    pub fn roll(&mut self, expression: &str) -> Result<RollResult> {
+        let parsed = DiceExpression::parse(expression)?;
+        self.roll_expression(&parsed, None)
+    }
+
+    /// Parse and roll an expression, resolving ability modifiers from the given scores.
+    ///
+    /// # Arguments
+    /// * `expression` - Dice notation, e.g. `1d8+STR`
+    /// * `abilities` - Ability scores used for `STR`, `DEX`, etc.
+    ///
+    /// # Returns
+    /// * `Ok(RollResult)` - The rolled result with breakdown
+    pub fn roll_with_abilities(
+        &mut self,
+        expression: &str,
+        abilities: &AbilityScores,
+    ) -> Result<RollResult> {
+        let parsed = DiceExpression::parse(expression)?;
+        self.roll_expression(&parsed, Some(abilities))
+    }
+
+    /// Roll a parsed expression and add it to the log.
+    ///
+    /// # Arguments
+    /// * `expression` - The parsed expression
+    /// * `abilities` - Ability scores for ability terms (required if the expression uses them)
+    ///
+    /// # Returns
+    /// * `Ok(RollResult)` - The rolled result with breakdown
+    /// * `Err(DbError::InvalidData)` - If the expression uses abilities and none were given,
+    ///   or the total overflows
+    pub fn roll_expression(
+        &mut self,
+        expression: &DiceExpression,
+        abilities: Option<&AbilityScores>,
+    ) -> Result<RollResult> {
+        let mut terms = Vec::with_capacity(expression.terms.len());
+
+        for (sign, term) in &expression.terms {
+            let (rolls, value) = match term {
+                DiceTerm::Dice(pool) => {
+                    let rolls = self.roll_pool(pool);
+                    let value =
+                        checked_sum(rolls.iter().filter(|r| r.kept).map(|r| r.value as i32))
+                            .ok_or_else(|| out_of_range(expression))?;
+                    (rolls, value)
+                }
+                DiceTerm::Constant(n) => (Vec::new(), *n),
+                DiceTerm::Ability(ability) => {
+                    let abilities = abilities.ok_or_else(|| {
+                        DbError::InvalidData(format!(
+                            "Dice expression '{}' uses {} but no ability scores were provided",
+                            expression, ability
+                        ))
+                    })?;
+                    (Vec::new(), ability_modifier(abilities, ability))
+                }
+            };
+
+            terms.push(TermResult {
+                term: term.to_string(),
+                sign: *sign,
+                rolls,
+                value: sign * value,
+            });
+        }
+
+        let total =
+            checked_sum(terms.iter().map(|t| t.value)).ok_or_else(|| out_of_range(expression))?;
+        let result = RollResult {
+            expression: expression.to_string(),
+            breakdown: format_breakdown(&terms, total),
+            terms,
+            total,
+        };
+        self.log.push(result.clone());
+        Ok(result)
+    }
+
+    /// Roll a standard set of six ability scores (4d6, drop the lowest) in
+    /// STR, DEX, CON, INT, WIS, CHA order.
+    pub fn roll_ability_scores(&mut self) -> Result<AbilityScores> {
+        let mut scores = [0; 6];
+        for score in scores.iter_mut() {
+            *score = self.roll("4d6kh3")?.total;
+        }
+        Ok(AbilityScores {
+            strength: scores[0],
+            dexterity: scores[1],
+            constitution: scores[2],
+            intelligence: scores[3],
+            wisdom: scores[4],
+            charisma: scores[5],
+        })
+    }
+
+    /// All rolls made by this roller, oldest first.
+    pub fn log(&self) -> &[RollResult] {
+        &self.log
+    }
+
+    /// Clear the roll log.
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+
+    fn roll_pool(&mut self, pool: &DicePool) -> Vec<DieRoll> {
+        let mut rolls = Vec::with_capacity(pool.count as usize);
+        for _ in 0..pool.count {
+            let mut value = self.roll_die(pool.sides);
+            rolls.push(DieRoll {
+                value,
+                kept: true,
+                exploded: false,
+            });
+
+            let mut explosions = 0;
+            while pool.exploding && value == pool.sides && explosions < MAX_EXPLOSIONS {
+                value = self.roll_die(pool.sides);
+                rolls.push(DieRoll {
+                    value,
+                    kept: true,
+                    exploded: true,
+                });
+                explosions += 1;
+            }
+        }
+
+        if let Some(rule) = pool.keep {
+            apply_keep_rule(&mut rolls, rule);
+        }
+        rolls
+    }
+}
+
+impl Default for DiceRoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Mark dice as dropped according to a keep/drop rule.
+fn apply_keep_rule(rolls: &mut [DieRoll], rule: KeepRule) {
+    let total = rolls.len();
+    let mut order: Vec<usize> = (0..total).collect();
+    // Stable ascending sort: of two tied dice, kh/dl drop the earlier one
+    // and kl/dh drop the later one
+    order.sort_by_key(|&i| rolls[i].value);
+
+    let drop: Vec<usize> = match rule {
+        KeepRule::KeepHighest(n) => order[..total.saturating_sub(n as usize)].to_vec(),
+        KeepRule::KeepLowest(n) => order[(n as usize).min(total)..].to_vec(),
+        KeepRule::DropHighest(n) => order[total.saturating_sub(n as usize)..].to_vec(),
+        KeepRule::DropLowest(n) => order[..(n as usize).min(total)].to_vec(),
+    };
+    for index in drop {
+        rolls[index].kept = false;
+    }
+}
+
+/// Add term values, or `None` if the total doesn't fit in an `i32`
+fn checked_sum(mut values: impl Iterator<Item = i32>) -> Option<i32> {
+    values.try_fold(0i32, |total, value| total.checked_add(value))
+}
+
+fn out_of_range(expression: &DiceExpression) -> DbError {
+    DbError::InvalidData(format!(
+        "Dice expression '{}' total is out of range",
+        expression
+    ))
+}
+
+fn format_breakdown(terms: &[TermResult], total: i32) -> String {
+    let mut out = String::new();
+    for (index, term) in terms.iter().enumerate() {
+        match (index, term.sign < 0) {
+            (0, true) => out.push('-'),
+            (0, false) => {}
+            (_, true) => out.push_str(" - "),
+            (_, false) => out.push_str(" + "),
+        }
+
+        if term.rolls.is_empty() {
+            if term.term.parse::<i32>().is_ok() {
+                out.push_str(&term.term);
+            } else {
+                // The modifier itself, under the sign written in the formula
+                out.push_str(&format!("{} ({})", term.sign * term.value, term.term));
+            }
+        } else {
+            let faces: Vec<String> = term
+                .rolls
+                .iter()
+                .map(|r| {
+                    let face = if r.exploded {
+                        format!("{}!", r.value)
+                    } else {
+                        r.value.to_string()
+                    };
+                    if r.kept {
+                        face
+                    } else {
+                        format!("({})", face)
+                    }
+                })
+                .collect();
+            out.push_str(&format!("{} [{}]", term.term, faces.join(", ")));
+        }
+    }
+    out.push_str(&format!(" = {}", total));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abilities() -> AbilityScores {
+        AbilityScores {
+            strength: 16,
+            dexterity: 8,
+            constitution: 14,
+            intelligence: 10,
+            wisdom: 12,
+            charisma: 18,
+        }
+    }
+
+    #[test]
+    fn test_parse_notation() {
+        let expr = DiceExpression::parse("4d6kh3").unwrap();
+        assert_eq!(
+            expr.terms,
+            vec![(
+                1,
+                DiceTerm::Dice(DicePool {
+                    count: 4,
+                    sides: 6,
+                    keep: Some(KeepRule::KeepHighest(3)),
+                    exploding: false,
+                })
+            )]
+        );
+
+        let expr = DiceExpression::parse("d20 - 1 + STR").unwrap();
+        assert_eq!(expr.to_string(), "1d20 - 1 + STR");
+        assert!(expr.uses_abilities());
+
+        let expr = DiceExpression::parse("d%").unwrap();
+        assert_eq!(expr.to_string(), "1d100");
+
+        let expr = DiceExpression::parse("3d6!").unwrap();
+        assert_eq!(expr.to_string(), "3d6!");
+
+        assert_eq!(
+            DiceExpression::parse("2D20KL1").unwrap().to_string(),
+            "2d20kl1"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(DiceExpression::parse("").is_err());
+        assert!(DiceExpression::parse("d").is_err());
+        assert!(DiceExpression::parse("0d6").is_err());
+        assert!(DiceExpression::parse("2d6x").is_err());
+        assert!(DiceExpression::parse("1d6+").is_err());
+        assert!(DiceExpression::parse("4d6kh3kl1").is_err());
+        assert!(DiceExpression::parse("1d1!").is_err());
+        assert!(DiceExpression::parse("5000d6").is_err());
+        assert!(DiceExpression::parse("fireball").is_err());
+    }
+
+    #[test]
+    fn test_seeded_rolls_are_deterministic() {
+        let mut a = DiceRoller::seeded(42);
+        let mut b = DiceRoller::seeded(42);
+        for _ in 0..20 {
+            assert_eq!(a.roll("3d8+2").unwrap(), b.roll("3d8+2").unwrap());
+        }
+    }
+
+    #[test]
+    fn test_roll_totals_in_range() {
+        let mut roller = DiceRoller::seeded(7);
+        for _ in 0..200 {
+            let total = roller.roll("2d6+3").unwrap().total;
+            assert!((5..=15).contains(&total));
+
+            let total = roller.roll("1d4-10").unwrap().total;
+            assert!((-9..=-6).contains(&total));
+        }
+    }
+
+    #[test]
+    fn test_keep_highest_drops_lowest_die() {
+        let mut roller = DiceRoller::seeded(3);
+        for _ in 0..50 {
+            let result = roller.roll("4d6kh3").unwrap();
+            let rolls = &result.terms[0].rolls;
+            assert_eq!(rolls.len(), 4);
+            assert_eq!(rolls.iter().filter(|r| r.kept).count(), 3);
+
+            let dropped = rolls.iter().find(|r| !r.kept).unwrap().value;
+            assert!(rolls.iter().filter(|r| r.kept).all(|r| r.value >= dropped));
+            assert!((3..=18).contains(&result.total));
+        }
+    }
+
+    #[test]
+    fn test_keep_lowest_disadvantage() {
+        let mut roller = DiceRoller::seeded(11);
+        for _ in 0..50 {
+            let result = roller.roll("2d20kl1").unwrap();
+            let rolls = &result.terms[0].rolls;
+            let lowest = rolls.iter().map(|r| r.value).min().unwrap();
+            assert_eq!(result.total, lowest as i32);
+        }
+    }
+
+    #[test]
+    fn test_exploding_dice() {
+        let mut roller = DiceRoller::seeded(5);
+        let mut saw_explosion = false;
+        for _ in 0..200 {
+            let result = roller.roll("1d2!").unwrap();
+            let rolls = &result.terms[0].rolls;
+            // Every die except the last one must have been a max roll
+            for pair in rolls.windows(2) {
+                assert_eq!(pair[0].value, 2);
+                assert!(pair[1].exploded);
+            }
+            saw_explosion |= rolls.len() > 1;
+        }
+        assert!(saw_explosion);
+    }
+
+    #[test]
+    fn test_ability_modifiers() {
+        let mut roller = DiceRoller::seeded(1);
+        let result = roller.roll_with_abilities("1d8+STR", &abilities()).unwrap();
+        let die = result.terms[0].value;
+        assert_eq!(result.total, die + 3);
+        assert_eq!(result.terms[1].term, "STR");
+
+        let result = roller.roll_with_abilities("10-dex", &abilities()).unwrap();
+        assert_eq!(result.total, 11);
+
+        // Negative modifiers keep their own sign in the breakdown
+        let result = roller.roll_with_abilities("1d8+DEX", &abilities()).unwrap();
+        assert_eq!(result.total, result.terms[0].value - 1);
+        assert!(result.breakdown.contains(" + -1 (DEX) = "));
+
+        // Abilities without scores is an error
+        assert!(roller.roll("1d20+CHA").is_err());
+    }
+
+    #[test]
+    fn test_breakdown_and_log() {
+        let mut roller = DiceRoller::seeded(9);
+        let result = roller
+            .roll_with_abilities("4d6dl1 + 2 + CHA", &abilities())
+            .unwrap();
+        assert!(result.breakdown.starts_with("4d6dl1 ["));
+        assert!(result.breakdown.contains(" + 2 + 4 (CHA)"));
+        assert!(result.breakdown.ends_with(&format!("= {}", result.total)));
+
+        roller.roll("1d20").unwrap();
+        assert_eq!(roller.log().len(), 2);
+
+        // Totals that overflow are rejected instead of panicking
+        assert!(roller.roll("2147483647+2147483647").is_err());
+        assert_eq!(roller.log().len(), 2);
+        roller.clear_log();
+        assert!(roller.log().is_empty());
+    }
+
+    #[test]
+    fn test_roll_ability_scores() {
+        let mut roller = DiceRoller::seeded(21);
+        let scores = roller.roll_ability_scores().unwrap();
+        for score in [
+            scores.strength,
+            scores.dexterity,
+            scores.constitution,
+            scores.intelligence,
+            scores.wisdom,
+            scores.charisma,
+        ] {
+            assert!((3..=18).contains(&score));
+        }
+        assert_eq!(roller.log().len(), 6);
+    }
+}
@@ -0,0 +1,390 @@
+//! Encounter difficulty rules
+//!
+//! Implements the Dungeon Master's Guide encounter building math: per-level
+//! XP thresholds, XP by challenge rating, the multiplier for the number of
+//! monsters (adjusted for party size) and the resulting difficulty rating.
+
+use serde::{Deserialize, Serialize};
+
+/// XP thresholds per character level (easy, medium, hard, deadly), levels 1-20
+const XP_THRESHOLDS: [(i32, i32, i32, i32); 20] = [
+    (25, 50, 75, 100),
+    (50, 100, 150, 200),
+    (75, 150, 225, 400),
+    (125, 250, 375, 500),
+    (250, 500, 750, 1100),
+    (300, 600, 900, 1400),
+    (350, 750, 1100, 1700),
+    (450, 900, 1400, 2100),
+    (550, 1100, 1600, 2400),
+    (600, 1200, 1900, 2800),
+    (800, 1600, 2400, 3600),
+    (1000, 2000, 3000, 4500),
+    (1100, 2200, 3400, 5100),
+    (1250, 2500, 3800, 5700),
+    (1400, 2800, 4300, 6400),
+    (1600, 3200, 4800, 7200),
+    (2000, 3900, 5900, 8800),
+    (2100, 4200, 6300, 9500),
+    (2400, 4900, 7300, 10900),
+    (2800, 5700, 8500, 12700),
+];
+
+/// XP awarded for challenge ratings 1-30
+const XP_BY_CR: [i32; 30] = [
+    200, 450, 700, 1100, 1800, 2300, 2900, 3900, 5000, 5900, 7200, 8400, 10000, 11500, 13000,
+    15000, 18000, 20000, 22000, 25000, 33000, 41000, 50000, 62000, 75000, 90000, 105000, 120000,
+    135000, 155000,
+];
+
+/// Encounter multipliers, ordered so party size can shift one step either way
+const MULTIPLIERS: [f64; 8] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0];
+
+/// Difficulty rating of an encounter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Below the party's easy threshold
+    Trivial,
+    /// At or above the easy threshold
+    Easy,
+    /// At or above the medium threshold
+    Medium,
+    /// At or above the hard threshold
+    Hard,
+    /// At or above the deadly threshold
+    Deadly,
+}
+
+impl Difficulty {
+    /// Lowercase name of the rating
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Trivial => "trivial",
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Deadly => "deadly",
+        }
+    }
+}
+
+/// XP thresholds for a character or a whole party
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XpThresholds {
+    /// Easy threshold
+    pub easy: i32,
+    /// Medium threshold
+    pub medium: i32,
+    /// Hard threshold
+    pub hard: i32,
+    /// Deadly threshold
+    pub deadly: i32,
+}
+
+impl XpThresholds {
+    /// Thresholds for a single character (levels are clamped to 1-20)
+    pub fn for_level(level: i32) -> Self {
+        let (easy, medium, hard, deadly) = XP_THRESHOLDS[(level.clamp(1, 20) - 1) as usize];
+        Self {
+            easy,
+            medium,
+            hard,
+            deadly,
+        }
+    }
+
+    /// Summed thresholds for a party
+    pub fn for_party(levels: &[i32]) -> Self {
+        levels
+            .iter()
+            .map(|&level| Self::for_level(level))
+            .fold(Self::default(), |total, t| Self {
+                easy: total.easy + t.easy,
+                medium: total.medium + t.medium,
+                hard: total.hard + t.hard,
+                deadly: total.deadly + t.deadly,
+            })
+    }
+
+    /// Rate an adjusted XP total against these thresholds
+    pub fn rate(&self, adjusted_xp: i32) -> Difficulty {
+        if adjusted_xp >= self.deadly {
+            Difficulty::Deadly
+        } else if adjusted_xp >= self.hard {
+            Difficulty::Hard
+        } else if adjusted_xp >= self.medium {
+            Difficulty::Medium
+        } else if adjusted_xp >= self.easy {
+            Difficulty::Easy
+        } else {
+            Difficulty::Trivial
+        }
+    }
+
+    /// Inclusive range of adjusted XP that rates as the given difficulty.
+    ///
+    /// Deadly has no upper threshold, so it is capped at one and a half
+    /// times the deadly threshold.
+    pub fn xp_range(&self, difficulty: Difficulty) -> (i32, i32) {
+        match difficulty {
+            Difficulty::Trivial => (1, self.easy - 1),
+            Difficulty::Easy => (self.easy, self.medium - 1),
+            Difficulty::Medium => (self.medium, self.hard - 1),
+            Difficulty::Hard => (self.hard, self.deadly - 1),
+            Difficulty::Deadly => (self.deadly, self.deadly * 3 / 2),
+        }
+    }
+}
+
+/// Get the XP for a challenge rating string ("0", "1/8", "1/4", "1/2", "1" - "30").
+pub fn xp_for_cr(cr: &str) -> Option<i32> {
+    match cr.trim() {
+        "0" => Some(10),
+        "1/8" => Some(25),
+        "1/4" => Some(50),
+        "1/2" => Some(100),
+        other => {
+            let cr: usize = other.parse().ok()?;
+            XP_BY_CR.get(cr.checked_sub(1)?).copied()
+        }
+    }
+}
+
+/// Get the encounter multiplier for a number of monsters and party size.
+///
+/// Parties of fewer than three characters use the next higher multiplier;
+/// parties of six or more use the next lower one.
+pub fn encounter_multiplier(monster_count: i32, party_size: usize) -> f64 {
+    let index: usize = match monster_count {
+        i32::MIN..=1 => 1,
+        2 => 2,
+        3..=6 => 3,
+        7..=10 => 4,
+        11..=14 => 5,
+        _ => 6,
+    };
+    let index = match party_size {
+        0 => index,
+        1 | 2 => index + 1,
+        3..=5 => index,
+        _ => index - 1,
+    };
+    MULTIPLIERS[index]
+}
+
+/// A group of identical monsters in an encounter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterMonster {
+    /// Monster name
+    pub name: String,
+    /// Source book abbreviation
+    pub source: String,
+    /// Number of this monster in the encounter
+    pub quantity: i32,
+    /// Challenge rating, if known
+    pub cr: Option<String>,
+    /// XP for a single monster (0 if the CR is unknown)
+    pub xp: i32,
+}
+
+impl EncounterMonster {
+    /// Build from 5etools monster data, reading `cr` as a string or
+    /// `{ "cr": "...", "xp": ... }` object.
+    pub fn from_monster_json(
+        name: impl Into<String>,
+        source: impl Into<String>,
+        quantity: i32,
+        data: Option<&serde_json::Value>,
+    ) -> Self {
+        let cr_value = data.and_then(|d| d.get("cr"));
+        let cr = cr_value.and_then(|v| match v {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Object(obj) => {
+                obj.get("cr").and_then(|c| c.as_str()).map(String::from)
+            }
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        });
+        let xp = cr_value
+            .and_then(|v| v.get("xp"))
+            .and_then(|xp| xp.as_i64())
+            .map(|xp| xp as i32)
+            .or_else(|| cr.as_deref().and_then(xp_for_cr))
+            .unwrap_or(0);
+
+        Self {
+            name: name.into(),
+            source: source.into(),
+            quantity,
+            cr,
+            xp,
+        }
+    }
+}
+
+/// Difficulty breakdown for an encounter against a party
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterDifficulty {
+    /// Level of each party member
+    pub party_levels: Vec<i32>,
+    /// Summed party thresholds
+    pub thresholds: XpThresholds,
+    /// Monsters in the encounter with their XP
+    pub monsters: Vec<EncounterMonster>,
+    /// Total number of monsters (sum of quantities)
+    pub monster_count: i32,
+    /// Unadjusted XP of all monsters (what the party is awarded)
+    pub base_xp: i32,
+    /// Multiplier for the number of monsters, adjusted for party size
+    pub multiplier: f64,
+    /// Base XP times the multiplier, compared against the thresholds
+    pub adjusted_xp: i32,
+    /// Difficulty rating, `None` when there is no party to compare against
+    pub difficulty: Option<Difficulty>,
+    /// Base XP each party member earns
+    pub xp_per_character: i32,
+}
+
+impl EncounterDifficulty {
+    /// Calculate the difficulty of an encounter for a party.
+    ///
+    /// # Arguments
+    /// * `party_levels` - Level of each party member
+    /// * `monsters` - Monsters in the encounter
+    pub fn calculate(party_levels: &[i32], monsters: Vec<EncounterMonster>) -> Self {
+        let thresholds = XpThresholds::for_party(party_levels);
+        let monster_count: i32 = monsters.iter().map(|m| m.quantity.max(0)).sum();
+        let base_xp: i32 = monsters.iter().map(|m| m.xp * m.quantity.max(0)).sum();
+        let multiplier = if monster_count == 0 {
+            1.0
+        } else {
+            encounter_multiplier(monster_count, party_levels.len())
+        };
+        let adjusted_xp = (base_xp as f64 * multiplier).round() as i32;
+        let difficulty = if party_levels.is_empty() {
+            None
+        } else {
+            Some(thresholds.rate(adjusted_xp))
+        };
+        let xp_per_character = if party_levels.is_empty() {
+            0
+        } else {
+            base_xp / party_levels.len() as i32
+        };
+
+        Self {
+            party_levels: party_levels.to_vec(),
+            thresholds,
+            monsters,
+            monster_count,
+            base_xp,
+            multiplier,
+            adjusted_xp,
+            difficulty,
+            xp_per_character,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn monster(cr: &str, quantity: i32) -> EncounterMonster {
+        EncounterMonster::from_monster_json("Monster", "MM", quantity, Some(&json!({ "cr": cr })))
+    }
+
+    #[test]
+    fn test_xp_for_cr() {
+        assert_eq!(xp_for_cr("0"), Some(10));
+        assert_eq!(xp_for_cr("1/4"), Some(50));
+        assert_eq!(xp_for_cr("5"), Some(1800));
+        assert_eq!(xp_for_cr("30"), Some(155000));
+        assert_eq!(xp_for_cr("31"), None);
+        assert_eq!(xp_for_cr("Unknown"), None);
+    }
+
+    #[test]
+    fn test_party_thresholds() {
+        let thresholds = XpThresholds::for_party(&[3, 3, 3, 2]);
+        assert_eq!(thresholds.easy, 275);
+        assert_eq!(thresholds.medium, 550);
+        assert_eq!(thresholds.hard, 825);
+        assert_eq!(thresholds.deadly, 1400);
+    }
+
+    #[test]
+    fn test_xp_range_matches_rating() {
+        let thresholds = XpThresholds::for_party(&[5, 5, 5, 5]);
+        for difficulty in [
+            Difficulty::Trivial,
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Deadly,
+        ] {
+            let (min, max) = thresholds.xp_range(difficulty);
+            assert_eq!(thresholds.rate(min), difficulty);
+            assert_eq!(thresholds.rate(max), difficulty);
+        }
+    }
+
+    #[test]
+    fn test_multiplier_party_size_adjustment() {
+        assert_eq!(encounter_multiplier(1, 4), 1.0);
+        assert_eq!(encounter_multiplier(4, 4), 2.0);
+        assert_eq!(encounter_multiplier(15, 4), 4.0);
+        assert_eq!(encounter_multiplier(1, 2), 1.5);
+        assert_eq!(encounter_multiplier(15, 1), 5.0);
+        assert_eq!(encounter_multiplier(1, 6), 0.5);
+    }
+
+    #[test]
+    fn test_dmg_example_encounter() {
+        // DMG example: four 3rd-level characters vs. a bugbear and three hobgoblins
+        let difficulty =
+            EncounterDifficulty::calculate(&[3, 3, 3, 3], vec![monster("1", 1), monster("1/2", 3)]);
+
+        assert_eq!(difficulty.monster_count, 4);
+        assert_eq!(difficulty.base_xp, 500);
+        assert_eq!(difficulty.multiplier, 2.0);
+        assert_eq!(difficulty.adjusted_xp, 1000);
+        assert_eq!(difficulty.difficulty, Some(Difficulty::Hard));
+        assert_eq!(difficulty.xp_per_character, 125);
+    }
+
+    #[test]
+    fn test_cr_object_and_unknown_cr() {
+        let lair = EncounterMonster::from_monster_json(
+            "Dragon",
+            "MM",
+            1,
+            Some(&json!({ "cr": { "cr": "17", "lair": "18" } })),
+        );
+        assert_eq!(lair.cr.as_deref(), Some("17"));
+        assert_eq!(lair.xp, 18000);
+
+        let unknown = EncounterMonster::from_monster_json("Homebrew", "HB", 2, None);
+        assert_eq!(unknown.cr, None);
+        assert_eq!(unknown.xp, 0);
+    }
+
+    #[test]
+    fn test_no_party_has_no_rating() {
+        let difficulty = EncounterDifficulty::calculate(&[], vec![monster("1", 2)]);
+        assert_eq!(difficulty.base_xp, 400);
+        assert_eq!(difficulty.difficulty, None);
+    }
+
+    #[test]
+    fn test_trivial_and_deadly() {
+        let party = [5, 5, 5, 5];
+        let trivial = EncounterDifficulty::calculate(&party, vec![monster("1/8", 1)]);
+        assert_eq!(trivial.difficulty, Some(Difficulty::Trivial));
+
+        let deadly = EncounterDifficulty::calculate(&party, vec![monster("5", 3)]);
+        assert_eq!(deadly.difficulty, Some(Difficulty::Deadly));
+    }
+}
@@ -0,0 +1,281 @@
+//! Encounter token placement
+//!
+//! Works out the size and vision of monster tokens from catalog data, and
+//! lays a group of tokens out in a formation around a point on a map.
+//! Positions are token centers in map pixels.
+
+use crate::domain::visibility::Point;
+use crate::models::campaign::{TokenSize, VisionType};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// How a group of tokens is arranged around the chosen point
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Formation {
+    /// Rows filling a roughly square block
+    #[default]
+    Cluster,
+    /// A single row
+    Line,
+    /// Evenly spaced on a circle around the point
+    Ring,
+}
+
+impl Formation {
+    /// Database/API string for this formation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Formation::Cluster => "cluster",
+            Formation::Line => "line",
+            Formation::Ring => "ring",
+        }
+    }
+
+    /// Parse a formation, falling back to a cluster
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "line" => Formation::Line,
+            "ring" => Formation::Ring,
+            _ => Formation::Cluster,
+        }
+    }
+}
+
+/// The grid tokens are placed on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacementGrid {
+    /// Size of one grid square in pixels
+    pub size_px: f32,
+    /// Offset of the grid's first line from the map origin
+    pub offset: Point,
+    /// Whether tokens should line up with grid squares
+    pub snap: bool,
+}
+
+impl PlacementGrid {
+    fn snap(&self, value: f32, offset: f32) -> f32 {
+        if self.snap {
+            ((value - offset) / self.size_px).round() * self.size_px + offset
+        } else {
+            value
+        }
+    }
+}
+
+/// Token size for a catalog monster's JSON data (defaults to Medium)
+pub fn token_size_from_monster_json(data: Option<&serde_json::Value>) -> TokenSize {
+    data.and_then(|d| d.get("size"))
+        .and_then(|size| size.get(0))
+        .and_then(|size| size.as_str())
+        .map(TokenSize::from_str)
+        .unwrap_or_default()
+}
+
+/// Token vision for a catalog monster's JSON data, from its senses
+pub fn vision_from_monster_json(data: Option<&serde_json::Value>) -> (VisionType, Option<f32>) {
+    let senses: Vec<&str> = data
+        .and_then(|d| d.get("senses"))
+        .and_then(|senses| senses.as_array())
+        .map(|senses| senses.iter().filter_map(|s| s.as_str()).collect())
+        .unwrap_or_default();
+    match vision_from_senses(&senses) {
+        Some((vision_type, range_ft)) => (vision_type, Some(range_ft)),
+        None => (VisionType::Normal, None),
+    }
+}
+
+/// Pick the best special sense from a monster's senses.
+///
+/// The longest range wins; on a tie, senses that work regardless of light
+/// beat darkvision.
+///
+/// # Returns
+/// The vision type and its range in feet, or `None` for normal vision
+pub fn vision_from_senses(senses: &[&str]) -> Option<(VisionType, f32)> {
+    senses
+        .iter()
+        .flat_map(|sense| sense.split(','))
+        .filter_map(parse_sense)
+        .max_by(|(a_type, a_range), (b_type, b_range)| {
+            a_range
+                .total_cmp(b_range)
+                .then(a_type.ignores_light().cmp(&b_type.ignores_light()))
+        })
+}
+
+/// Parse a sense such as "darkvision 60 ft." or "{@sense blindsight} 30 ft."
+fn parse_sense(sense: &str) -> Option<(VisionType, f32)> {
+    let sense = sense.to_lowercase();
+    ["blindsight", "darkvision", "tremorsense", "truesight"]
+        .into_iter()
+        .find_map(|name| {
+            let rest = &sense[sense.find(name)? + name.len()..];
+            let digits: String = rest
+                .chars()
+                .skip_while(|c| !c.is_ascii_digit())
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            Some((VisionType::from_str(name), digits.parse().ok()?))
+        })
+}
+
+/// Lay out tokens of the given sizes around `center`.
+///
+/// # Returns
+/// One token center per size, in the same order
+pub fn layout_formation(
+    formation: Formation,
+    sizes: &[TokenSize],
+    center: Point,
+    grid: &PlacementGrid,
+) -> Vec<Point> {
+    let widths: Vec<f32> = sizes
+        .iter()
+        .map(|size| size.grid_squares() * grid.size_px)
+        .collect();
+    match formation {
+        Formation::Cluster => {
+            let per_row = (widths.len() as f32).sqrt().ceil() as usize;
+            layout_rows(&widths, per_row, center, grid)
+        }
+        Formation::Line => layout_rows(&widths, widths.len(), center, grid),
+        Formation::Ring => layout_ring(&widths, center),
+    }
+}
+
+/// Fill rows left to right, top to bottom, centering the block on `center`
+fn layout_rows(widths: &[f32], per_row: usize, center: Point, grid: &PlacementGrid) -> Vec<Point> {
+    if widths.is_empty() {
+        return Vec::new();
+    }
+    let rows: Vec<&[f32]> = widths.chunks(per_row.max(1)).collect();
+    let block_width = rows
+        .iter()
+        .map(|row| row.iter().sum::<f32>())
+        .fold(0.0, f32::max);
+    let block_height: f32 = rows
+        .iter()
+        .map(|row| row.iter().copied().fold(0.0, f32::max))
+        .sum();
+    let left = grid.snap(center.x - block_width / 2.0, grid.offset.x);
+    let mut top = grid.snap(center.y - block_height / 2.0, grid.offset.y);
+
+    let mut positions = Vec::with_capacity(widths.len());
+    for row in rows {
+        let mut x = left;
+        for width in row {
+            positions.push(Point::new(x + width / 2.0, top + width / 2.0));
+            x += width;
+        }
+        top += row.iter().copied().fold(0.0, f32::max);
+    }
+    positions
+}
+
+/// Space tokens evenly on a circle just large enough that they don't overlap
+fn layout_ring(widths: &[f32], center: Point) -> Vec<Point> {
+    if widths.len() < 2 {
+        return widths.iter().map(|_| center).collect();
+    }
+    let circumference: f32 = widths.iter().sum();
+    let largest = widths.iter().copied().fold(0.0, f32::max);
+    let radius = (circumference / (2.0 * PI)).max(largest);
+    (0..widths.len())
+        .map(|i| {
+            let angle = 2.0 * PI * i as f32 / widths.len() as f32 - PI / 2.0;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const GRID: PlacementGrid = PlacementGrid {
+        size_px: 50.0,
+        offset: Point { x: 0.0, y: 0.0 },
+        snap: true,
+    };
+
+    #[test]
+    fn test_vision_from_senses() {
+        assert_eq!(
+            vision_from_senses(&["darkvision 60 ft."]),
+            Some((VisionType::Darkvision, 60.0))
+        );
+        assert_eq!(
+            vision_from_senses(&["blindsight 10 ft.", "darkvision 120 ft."]),
+            Some((VisionType::Darkvision, 120.0))
+        );
+        assert_eq!(
+            vision_from_senses(&[
+                "darkvision 60 ft.",
+                "{@sense blindsight|XPHB} 60 ft. (blind beyond this radius)"
+            ]),
+            Some((VisionType::Blindsight, 60.0))
+        );
+        assert_eq!(
+            vision_from_senses(&["tremorsense 60 ft., blindsight 30 ft."]),
+            Some((VisionType::Tremorsense, 60.0))
+        );
+        assert_eq!(vision_from_senses(&["passive Perception 10"]), None);
+    }
+
+    #[test]
+    fn test_monster_json_size_and_vision() {
+        let owlbear = json!({"size": ["L"], "senses": ["darkvision 60 ft."]});
+        assert_eq!(
+            token_size_from_monster_json(Some(&owlbear)),
+            TokenSize::Large
+        );
+        assert_eq!(
+            vision_from_monster_json(Some(&owlbear)),
+            (VisionType::Darkvision, Some(60.0))
+        );
+        assert_eq!(token_size_from_monster_json(None), TokenSize::Medium);
+        assert_eq!(vision_from_monster_json(None), (VisionType::Normal, None));
+    }
+
+    #[test]
+    fn test_cluster_lines_up_with_grid() {
+        let sizes = [TokenSize::Large, TokenSize::Medium, TokenSize::Medium];
+        let positions =
+            layout_formation(Formation::Cluster, &sizes, Point::new(510.0, 490.0), &GRID);
+
+        // Two per row: the ogre and a goblin on top, the second goblin below
+        assert_eq!(
+            positions,
+            vec![
+                Point::new(500.0, 450.0),
+                Point::new(575.0, 425.0),
+                Point::new(475.0, 525.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_and_ring() {
+        let sizes = [TokenSize::Medium; 4];
+        let line = layout_formation(Formation::Line, &sizes, Point::new(500.0, 500.0), &GRID);
+        assert!(line.iter().all(|p| p.y == 525.0));
+        assert_eq!(line[0].x, 425.0);
+        assert_eq!(line[3].x, 575.0);
+
+        let center = Point::new(500.0, 500.0);
+        let ring = layout_formation(Formation::Ring, &sizes, center, &GRID);
+        assert_eq!(ring.len(), 4);
+        for (i, a) in ring.iter().enumerate() {
+            assert!((a.distance_to(center) - 50.0).abs() < 0.01);
+            for b in &ring[i + 1..] {
+                assert!(a.distance_to(*b) >= 50.0);
+            }
+        }
+    }
+}
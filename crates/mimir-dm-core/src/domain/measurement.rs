@@ -0,0 +1,456 @@
+//! Distance measurement and area of effect templates
+//!
+//! Distances are counted on the map's grid, where one square or hex is five
+//! feet. A token is caught by a template when the template covers the center
+//! of any grid square the token occupies.
+
+use crate::domain::uvtt::FEET_PER_GRID;
+use crate::domain::visibility::{feet_to_pixels, Point};
+use crate::models::campaign::{DiagonalRule, GridType, Map, TemplateShape, Token, TokenSize};
+use crate::models::catalog::{Spell, SpellRange};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Slack for square centers that fall exactly on a template's edge
+const EDGE_TOLERANCE_PX: f32 = 0.5;
+
+/// Width of a line when the spell doesn't say
+const DEFAULT_LINE_WIDTH_FT: f32 = 5.0;
+
+/// "20-foot-radius sphere", "15-foot cone", "5-foot-radius, 40-foot-high cylinder"
+static AREA_TEXT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(\d+)-foot(?:[- ](radius|diameter))?[^.]{0,30}?\b(sphere|cube|cone|line|cylinder|emanation)\b",
+    )
+    .unwrap()
+});
+
+/// "a line 100 feet long and 5 feet wide"
+static LINE_TEXT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)line (\d+) feet long(?: and (\d+) feet wide)?").unwrap());
+
+/// How distances are measured on a map
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridMeasure {
+    /// Kind of grid the map uses
+    pub grid_type: GridType,
+    /// Size of one square (or hex, flat side to flat side) in pixels
+    pub grid_size_px: f32,
+    /// How diagonals count on square grids
+    pub diagonal_rule: DiagonalRule,
+}
+
+/// The space a token takes up on the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Footprint {
+    /// Token center
+    pub center: Point,
+    /// Token size
+    pub size: TokenSize,
+}
+
+impl Footprint {
+    /// The footprint of a token on a map
+    pub fn of(token: &Token) -> Self {
+        Self {
+            center: Point::new(token.x, token.y),
+            size: token.size_enum(),
+        }
+    }
+
+    /// Squares along each side (tiny creatures still fill one square)
+    fn squares(&self) -> f32 {
+        self.size.grid_squares().max(1.0)
+    }
+}
+
+impl GridMeasure {
+    /// Measure on a map's grid with the campaign's diagonal rule
+    pub fn for_map(map: &Map, diagonal_rule: DiagonalRule) -> Self {
+        Self {
+            grid_type: map.grid_type_enum(),
+            grid_size_px: map.grid_size_px_or_default(),
+            diagonal_rule,
+        }
+    }
+
+    /// Convert a distance in feet to pixels
+    pub fn feet_to_px(&self, feet: f32) -> f32 {
+        feet_to_pixels(feet, self.grid_size_px)
+    }
+
+    /// Distance in feet between two points
+    pub fn distance_ft(&self, from: Point, to: Point) -> f32 {
+        self.measure(from, to, 0.0)
+    }
+
+    /// Distance in feet between two tokens, from the nearest squares they occupy
+    pub fn token_distance_ft(&self, a: &Footprint, b: &Footprint) -> f32 {
+        let reach = (a.squares() - 1.0) / 2.0 + (b.squares() - 1.0) / 2.0;
+        self.measure(a.center, b.center, reach)
+    }
+
+    /// Centers of the grid squares a token occupies
+    pub fn footprint_squares(&self, footprint: &Footprint) -> Vec<Point> {
+        let n = footprint.squares().ceil() as usize;
+        let start = -(n as f32 - 1.0) / 2.0;
+        (0..n)
+            .flat_map(|row| (0..n).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                Point::new(
+                    footprint.center.x + (start + col as f32) * self.grid_size_px,
+                    footprint.center.y + (start + row as f32) * self.grid_size_px,
+                )
+            })
+            .collect()
+    }
+
+    /// Distance in feet, less `reach` squares taken up by the things measured
+    fn measure(&self, from: Point, to: Point, reach: f32) -> f32 {
+        let dx = ((to.x - from.x).abs() / self.grid_size_px - reach).max(0.0);
+        let dy = ((to.y - from.y).abs() / self.grid_size_px - reach).max(0.0);
+        let squares = match (self.grid_type, self.diagonal_rule) {
+            (GridType::None, _) | (_, DiagonalRule::Euclidean) => dx.hypot(dy),
+            (GridType::Square, DiagonalRule::Alternating) => {
+                let (dx, dy) = (dx.round(), dy.round());
+                let diagonals = dx.min(dy);
+                let straight = dx.max(dy) - diagonals;
+                straight + diagonals + (diagonals / 2.0).floor()
+            }
+            (GridType::Hex, DiagonalRule::Alternating) => (hex_steps(from, to, self.grid_size_px)
+                - reach)
+                .max(0.0)
+                .round(),
+        };
+        squares * FEET_PER_GRID
+    }
+}
+
+/// Hexes between two points on a pointy-top grid `size_px` wide
+fn hex_steps(from: Point, to: Point, size_px: f32) -> f32 {
+    let dx = (to.x - from.x) / size_px;
+    let dy = (to.y - from.y) / size_px;
+    // Axial coordinates: rows are sqrt(3)/2 hex widths apart
+    let r = dy * 2.0 / 3f32.sqrt();
+    let q = dx - r / 2.0;
+    (q.abs() + r.abs() + (q + r).abs()) / 2.0
+}
+
+/// An area of effect placed on a map
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AreaTemplate {
+    /// Shape of the area
+    pub shape: TemplateShape,
+    /// Point of origin
+    pub origin: Point,
+    /// Direction the area extends in, degrees clockwise from east
+    pub direction_deg: f32,
+    /// Cone/line length, cube side or sphere/cylinder radius in feet
+    pub size_ft: f32,
+    /// Line width in feet
+    pub width_ft: f32,
+}
+
+impl AreaTemplate {
+    /// Whether a point is inside the area
+    pub fn contains(&self, point: Point, grid: &GridMeasure) -> bool {
+        let size = grid.feet_to_px(self.size_ft);
+        let dx = point.x - self.origin.x;
+        let dy = point.y - self.origin.y;
+        if !self.shape.is_directional() {
+            return dx.hypot(dy) <= size + EDGE_TOLERANCE_PX;
+        }
+
+        // Distance along the template's direction and to either side of it
+        let (sin, cos) = self.direction_deg.to_radians().sin_cos();
+        let along = dx * cos + dy * sin;
+        let across = (dy * cos - dx * sin).abs();
+        let half_width = match self.shape {
+            // A cone is as wide as it is far from the origin
+            TemplateShape::Cone if along <= 0.0 => return false,
+            TemplateShape::Cone => along / 2.0,
+            TemplateShape::Line => grid.feet_to_px(self.width_ft) / 2.0,
+            _ => size / 2.0,
+        };
+        along >= -EDGE_TOLERANCE_PX
+            && along <= size + EDGE_TOLERANCE_PX
+            && across <= half_width + EDGE_TOLERANCE_PX
+    }
+
+    /// Whether the area covers any grid square a token occupies
+    pub fn covers(&self, footprint: &Footprint, grid: &GridMeasure) -> bool {
+        grid.footprint_squares(footprint)
+            .into_iter()
+            .any(|square| self.contains(square, grid))
+    }
+}
+
+/// The area of effect a spell creates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpellArea {
+    /// Shape of the area
+    pub shape: TemplateShape,
+    /// Cone/line length, cube side or sphere/cylinder radius in feet
+    pub size_ft: f32,
+    /// Line width in feet
+    pub width_ft: f32,
+}
+
+/// Work out a spell's area of effect from its range and description.
+///
+/// Spells with a self-centered area ("15-foot cone") carry it in their
+/// range; others describe it in their text ("20-foot-radius sphere").
+///
+/// # Returns
+/// The area, or `None` for spells without one
+pub fn spell_area(spell: &Spell) -> Option<SpellArea> {
+    let mut text = String::new();
+    for entry in &spell.entries {
+        collect_text(entry, &mut text);
+    }
+
+    if let SpellRange::Point {
+        range_type,
+        distance,
+    } = &spell.range
+    {
+        let shape = match range_type.as_str() {
+            "cone" => Some(TemplateShape::Cone),
+            "cube" => Some(TemplateShape::Cube),
+            "line" => Some(TemplateShape::Line),
+            "radius" | "sphere" | "hemisphere" | "emanation" => Some(TemplateShape::Sphere),
+            "cylinder" => Some(TemplateShape::Cylinder),
+            _ => None,
+        };
+        if let (Some(shape), Some(amount), "feet") =
+            (shape, distance.amount, distance.distance_type.as_str())
+        {
+            let width_ft = LINE_TEXT
+                .captures(&text)
+                .and_then(|caps| caps.get(2))
+                .and_then(|width| width.as_str().parse().ok())
+                .unwrap_or(DEFAULT_LINE_WIDTH_FT);
+            return Some(SpellArea {
+                shape,
+                size_ft: amount as f32,
+                width_ft,
+            });
+        }
+    }
+
+    if let Some(caps) = LINE_TEXT.captures(&text) {
+        return Some(SpellArea {
+            shape: TemplateShape::Line,
+            size_ft: caps[1].parse().ok()?,
+            width_ft: caps
+                .get(2)
+                .and_then(|width| width.as_str().parse().ok())
+                .unwrap_or(DEFAULT_LINE_WIDTH_FT),
+        });
+    }
+
+    let caps = AREA_TEXT.captures(&text)?;
+    let feet: f32 = caps[1].parse().ok()?;
+    let is_diameter = caps
+        .get(2)
+        .is_some_and(|m| m.as_str().eq_ignore_ascii_case("diameter"));
+    let shape = match caps[3].to_lowercase().as_str() {
+        "emanation" => TemplateShape::Sphere,
+        shape => TemplateShape::from_str(shape),
+    };
+    Some(SpellArea {
+        shape,
+        size_ft: if is_diameter { feet / 2.0 } else { feet },
+        width_ft: DEFAULT_LINE_WIDTH_FT,
+    })
+}
+
+/// Append the text of a spell entry, including nested entries and lists
+fn collect_text(entry: &serde_json::Value, text: &mut String) {
+    match entry {
+        serde_json::Value::String(s) => {
+            text.push_str(s);
+            text.push('\n');
+        }
+        serde_json::Value::Array(entries) => {
+            for entry in entries {
+                collect_text(entry, text);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for key in ["entries", "items"] {
+                if let Some(entries) = obj.get(key) {
+                    collect_text(entries, text);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn square_grid(diagonal_rule: DiagonalRule) -> GridMeasure {
+        GridMeasure {
+            grid_type: GridType::Square,
+            grid_size_px: 50.0,
+            diagonal_rule,
+        }
+    }
+
+    fn spell(range: serde_json::Value, text: &str) -> Spell {
+        serde_json::from_value(json!({
+            "name": "Test Spell",
+            "source": "PHB",
+            "level": 3,
+            "school": "V",
+            "time": [{"number": 1, "unit": "action"}],
+            "range": range,
+            "components": {"v": true},
+            "duration": [{"type": "instant"}],
+            "entries": [text]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_diagonal_rules() {
+        let from = Point::new(25.0, 25.0);
+        let three_diagonal = Point::new(175.0, 175.0);
+        let knight = Point::new(125.0, 75.0);
+
+        let alternating = square_grid(DiagonalRule::Alternating);
+        assert_eq!(alternating.distance_ft(from, three_diagonal), 20.0);
+        assert_eq!(alternating.distance_ft(from, knight), 10.0);
+        assert_eq!(alternating.distance_ft(from, Point::new(225.0, 25.0)), 20.0);
+
+        let euclidean = square_grid(DiagonalRule::Euclidean);
+        assert!((euclidean.distance_ft(from, three_diagonal) - 21.21).abs() < 0.01);
+
+        let hex = GridMeasure {
+            grid_type: GridType::Hex,
+            ..alternating
+        };
+        // Two hexes east, then two down-right
+        let row = 50.0 * 3f32.sqrt() / 2.0;
+        assert_eq!(hex.distance_ft(from, Point::new(125.0, 25.0)), 10.0);
+        assert_eq!(
+            hex.distance_ft(from, Point::new(175.0, 25.0 + 2.0 * row)),
+            20.0
+        );
+    }
+
+    #[test]
+    fn test_token_distance_uses_nearest_squares() {
+        let grid = square_grid(DiagonalRule::Alternating);
+        let goblin = Footprint {
+            center: Point::new(25.0, 25.0),
+            size: TokenSize::Small,
+        };
+        let neighbor = Footprint {
+            center: Point::new(75.0, 75.0),
+            size: TokenSize::Medium,
+        };
+        // An ogre filling the 2x2 block two squares east of the goblin
+        let ogre = Footprint {
+            center: Point::new(150.0, 50.0),
+            size: TokenSize::Large,
+        };
+        assert_eq!(grid.token_distance_ft(&goblin, &neighbor), 5.0);
+        assert_eq!(grid.token_distance_ft(&goblin, &ogre), 10.0);
+        assert_eq!(grid.footprint_squares(&ogre).len(), 4);
+    }
+
+    #[test]
+    fn test_template_shapes() {
+        let grid = square_grid(DiagonalRule::Alternating);
+        let origin = Point::new(100.0, 100.0);
+        let template = |shape, size_ft| AreaTemplate {
+            shape,
+            origin,
+            direction_deg: 0.0,
+            size_ft,
+            width_ft: 5.0,
+        };
+
+        let sphere = template(TemplateShape::Sphere, 20.0);
+        assert!(sphere.contains(Point::new(100.0, 300.0), &grid));
+        assert!(!sphere.contains(Point::new(250.0, 250.0), &grid));
+
+        // 15 ft cone pointing east: 150px long, 150px wide at the end
+        let cone = template(TemplateShape::Cone, 15.0);
+        assert!(cone.contains(Point::new(250.0, 175.0), &grid));
+        assert!(!cone.contains(Point::new(150.0, 175.0), &grid));
+        assert!(!cone.contains(Point::new(75.0, 100.0), &grid));
+
+        let cube = template(TemplateShape::Cube, 10.0);
+        assert!(cube.contains(Point::new(200.0, 150.0), &grid));
+        assert!(!cube.contains(Point::new(200.0, 175.0), &grid));
+
+        let line = AreaTemplate {
+            direction_deg: 90.0,
+            ..template(TemplateShape::Line, 100.0)
+        };
+        assert!(line.contains(Point::new(120.0, 1000.0), &grid));
+        assert!(!line.contains(Point::new(130.0, 500.0), &grid));
+        assert!(!line.contains(Point::new(100.0, 50.0), &grid));
+
+        // A large token is caught when the sphere reaches one of its squares
+        let ogre = Footprint {
+            center: Point::new(300.0, 150.0),
+            size: TokenSize::Large,
+        };
+        assert!(!sphere.contains(ogre.center, &grid));
+        assert!(sphere.covers(&ogre, &grid));
+    }
+
+    #[test]
+    fn test_spell_area() {
+        let point = json!({"type": "point", "distance": {"type": "feet", "amount": 150}});
+        let fireball = spell(
+            point.clone(),
+            "Each creature in a 20-foot-radius sphere centered on that point must make a save.",
+        );
+        assert_eq!(
+            spell_area(&fireball),
+            Some(SpellArea {
+                shape: TemplateShape::Sphere,
+                size_ft: 20.0,
+                width_ft: 5.0
+            })
+        );
+
+        let burning_hands = spell(
+            json!({"type": "cone", "distance": {"type": "feet", "amount": 15}}),
+            "Each creature in a 15-foot cone must make a Dexterity saving throw.",
+        );
+        assert_eq!(
+            spell_area(&burning_hands).map(|a| (a.shape, a.size_ft)),
+            Some((TemplateShape::Cone, 15.0))
+        );
+
+        let lightning_bolt = spell(
+            json!({"type": "line", "distance": {"type": "feet", "amount": 100}}),
+            "A stroke of lightning forming a line 100 feet long and 5 feet wide blasts out.",
+        );
+        assert_eq!(
+            spell_area(&lightning_bolt).map(|a| (a.shape, a.size_ft, a.width_ft)),
+            Some((TemplateShape::Line, 100.0, 5.0))
+        );
+
+        let moonbeam = spell(
+            point.clone(),
+            "A silvery beam of pale light shines down in a 5-foot-radius, 40-foot-high cylinder.",
+        );
+        assert_eq!(
+            spell_area(&moonbeam).map(|a| (a.shape, a.size_ft)),
+            Some((TemplateShape::Cylinder, 5.0))
+        );
+
+        let magic_missile = spell(point, "You create three glowing darts of magical force.");
+        assert_eq!(spell_area(&magic_missile), None);
+    }
+}
@@ -2,15 +2,37 @@
 //!
 //! This module contains the business domain logic including:
 //! - Board workflow definitions
+//! - Dice expression rolling
+//! - Encounter difficulty rules
+//! - Encounter token placement
 //! - Business rules
 //! - Domain services
 //! - Template information
+//! - Universal VTT map import
+//! - Line of sight and token visibility
+//! - Distance measurement and area of effect templates
 
 pub mod boards;
+pub mod dice;
+pub mod encounter;
+pub mod formation;
+pub mod measurement;
 pub mod template_info;
+pub mod uvtt;
+pub mod visibility;
 
 // Re-export commonly used types
 pub use boards::campaign_board::CampaignBoard;
 pub use boards::module_board::ModuleBoard;
+pub use boards::session_board::SessionBoard;
 pub use boards::{BoardCompletionStatus, BoardDefinition, BoardRegistry, StageMetadata};
+pub use dice::{DiceExpression, DiceRoller, RollResult};
+pub use encounter::{Difficulty, EncounterDifficulty, XpThresholds};
+pub use formation::{Formation, PlacementGrid};
+pub use measurement::{spell_area, AreaTemplate, Footprint, GridMeasure, SpellArea};
 pub use template_info::{TemplateInfo, TemplateVariable};
+pub use uvtt::{Segment, UvttDoor, UvttLightPlacement, UvttMap};
+pub use visibility::{
+    LightLevel, LitArea, MapVisibility, Point, SeenRegion, SightArea, SightBlockers,
+    TokenVisibility,
+};
@@ -0,0 +1,344 @@
+//! Universal VTT map format
+//!
+//! Parses the Universal VTT JSON format (`.dd2vtt`, `.uvtt`) exported by
+//! Dungeondraft and other map makers. The file carries the map image along
+//! with its grid resolution, line-of-sight walls, doors ("portals") and
+//! lights. All positions in the file are in grid units; the helpers here
+//! convert them to pixel coordinates of the stored map image.
+
+use crate::models::campaign::AmbientLight;
+use serde::{Deserialize, Serialize};
+
+/// Distance covered by one grid square, in feet
+pub const FEET_PER_GRID: f32 = 5.0;
+
+/// A point in grid units
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct UvttPoint {
+    /// Horizontal position in grid squares
+    pub x: f64,
+    /// Vertical position in grid squares
+    pub y: f64,
+}
+
+/// Grid resolution of the map image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvttResolution {
+    /// Grid position of the image's top-left corner
+    #[serde(default)]
+    pub map_origin: UvttPoint,
+    /// Map size in grid squares
+    pub map_size: UvttPoint,
+    /// Image pixels per grid square
+    pub pixels_per_grid: f64,
+}
+
+/// A door, window or other opening in the walls
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvttPortal {
+    /// Center of the opening
+    pub position: UvttPoint,
+    /// End points of the opening
+    pub bounds: Vec<UvttPoint>,
+    /// Rotation in radians
+    #[serde(default)]
+    pub rotation: f64,
+    /// Whether the door is shut
+    #[serde(default)]
+    pub closed: bool,
+    /// Whether the door stands on its own rather than in a wall
+    #[serde(default)]
+    pub freestanding: bool,
+}
+
+/// A light placed on the map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvttLight {
+    /// Center of the light
+    pub position: UvttPoint,
+    /// Light radius in grid squares
+    pub range: f64,
+    /// Brightness multiplier
+    #[serde(default)]
+    pub intensity: f64,
+    /// Color as ARGB hex (e.g. "ffeccd8b")
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Whether walls cast shadows from this light
+    #[serde(default)]
+    pub shadows: bool,
+}
+
+/// Global lighting of the map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvttEnvironment {
+    /// Whether lighting is already painted into the image
+    #[serde(default)]
+    pub baked_lighting: bool,
+    /// Ambient light color as ARGB hex
+    #[serde(default)]
+    pub ambient_light: Option<String>,
+}
+
+/// A Universal VTT map file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UvttMap {
+    /// Format version (e.g. 0.3)
+    #[serde(default)]
+    pub format: f64,
+    /// Grid resolution of the image
+    pub resolution: UvttResolution,
+    /// Wall polylines
+    #[serde(default)]
+    pub line_of_sight: Vec<Vec<UvttPoint>>,
+    /// Polylines around sight-blocking objects (pillars, furniture)
+    #[serde(default)]
+    pub objects_line_of_sight: Vec<Vec<UvttPoint>>,
+    /// Doors and other openings
+    #[serde(default)]
+    pub portals: Vec<UvttPortal>,
+    /// Lights placed on the map
+    #[serde(default)]
+    pub lights: Vec<UvttLight>,
+    /// Global lighting
+    #[serde(default)]
+    pub environment: Option<UvttEnvironment>,
+    /// Base64-encoded map image
+    pub image: String,
+}
+
+/// A line segment in pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    /// Start X
+    pub x1: f32,
+    /// Start Y
+    pub y1: f32,
+    /// End X
+    pub x2: f32,
+    /// End Y
+    pub y2: f32,
+}
+
+/// A door in pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvttDoor {
+    /// Where the door sits
+    pub segment: Segment,
+    /// Whether the door is shut
+    pub closed: bool,
+}
+
+/// A light in pixel coordinates with its radius in feet
+#[derive(Debug, Clone, PartialEq)]
+pub struct UvttLightPlacement {
+    /// X coordinate (pixel position)
+    pub x: f32,
+    /// Y coordinate (pixel position)
+    pub y: f32,
+    /// Light radius in feet
+    pub radius_ft: f32,
+    /// Color as "#rrggbb"
+    pub color: Option<String>,
+}
+
+impl UvttMap {
+    /// Parse a Universal VTT file.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Size of the embedded image in pixels, as described by the resolution
+    pub fn image_size_px(&self) -> (f64, f64) {
+        let resolution = &self.resolution;
+        (
+            resolution.map_size.x * resolution.pixels_per_grid,
+            resolution.map_size.y * resolution.pixels_per_grid,
+        )
+    }
+
+    /// Convert a grid position to pixels of an image scaled by `scale`
+    pub fn to_pixels(&self, point: UvttPoint, scale: f64) -> (f32, f32) {
+        let resolution = &self.resolution;
+        let factor = resolution.pixels_per_grid * scale;
+        (
+            ((point.x - resolution.map_origin.x) * factor) as f32,
+            ((point.y - resolution.map_origin.y) * factor) as f32,
+        )
+    }
+
+    /// Wall and object outlines split into individual segments.
+    pub fn wall_segments(&self, scale: f64) -> Vec<Segment> {
+        self.line_of_sight
+            .iter()
+            .chain(&self.objects_line_of_sight)
+            .flat_map(|polyline| polyline.windows(2))
+            .map(|pair| self.segment(pair[0], pair[1], scale))
+            .collect()
+    }
+
+    /// Doors, from the first to the last point of each portal's bounds.
+    ///
+    /// Portals without at least two bound points are skipped.
+    pub fn doors(&self, scale: f64) -> Vec<UvttDoor> {
+        self.portals
+            .iter()
+            .filter_map(|portal| {
+                let (first, last) = (portal.bounds.first()?, portal.bounds.last()?);
+                (portal.bounds.len() >= 2).then(|| UvttDoor {
+                    segment: self.segment(*first, *last, scale),
+                    closed: portal.closed,
+                })
+            })
+            .collect()
+    }
+
+    /// Lights with positions in pixels and radii in feet.
+    pub fn light_placements(&self, scale: f64) -> Vec<UvttLightPlacement> {
+        self.lights
+            .iter()
+            .map(|light| {
+                let (x, y) = self.to_pixels(light.position, scale);
+                UvttLightPlacement {
+                    x,
+                    y,
+                    radius_ft: light.range as f32 * FEET_PER_GRID,
+                    color: light.color.as_deref().and_then(argb_to_hex),
+                }
+            })
+            .collect()
+    }
+
+    /// Ambient light level, judged by the brightness of the ambient color.
+    ///
+    /// Maps without environment data are treated as brightly lit.
+    pub fn ambient_light(&self) -> AmbientLight {
+        let Some((r, g, b)) = self
+            .environment
+            .as_ref()
+            .and_then(|env| env.ambient_light.as_deref())
+            .and_then(argb_to_rgb)
+        else {
+            return AmbientLight::Bright;
+        };
+
+        let luminance = (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) / 255.0;
+        if luminance >= 0.75 {
+            AmbientLight::Bright
+        } else if luminance >= 0.25 {
+            AmbientLight::Dim
+        } else {
+            AmbientLight::Darkness
+        }
+    }
+
+    fn segment(&self, start: UvttPoint, end: UvttPoint, scale: f64) -> Segment {
+        let (x1, y1) = self.to_pixels(start, scale);
+        let (x2, y2) = self.to_pixels(end, scale);
+        Segment { x1, y1, x2, y2 }
+    }
+}
+
+/// Parse "aarrggbb" or "rrggbb" into its color channels
+fn argb_to_rgb(color: &str) -> Option<(u8, u8, u8)> {
+    let color = color.trim_start_matches('#');
+    if !color.is_ascii() {
+        return None;
+    }
+    let rgb = match color.len() {
+        8 => &color[2..],
+        6 => color,
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(&rgb[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Convert "aarrggbb" or "rrggbb" to "#rrggbb"
+fn argb_to_hex(color: &str) -> Option<String> {
+    let (r, g, b) = argb_to_rgb(color)?;
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "format": 0.3,
+        "resolution": {
+            "map_origin": {"x": 0, "y": 0},
+            "map_size": {"x": 10, "y": 8},
+            "pixels_per_grid": 100
+        },
+        "line_of_sight": [
+            [{"x": 1, "y": 1}, {"x": 5, "y": 1}, {"x": 5, "y": 4}]
+        ],
+        "objects_line_of_sight": [
+            [{"x": 7, "y": 7}, {"x": 8, "y": 7}]
+        ],
+        "portals": [
+            {
+                "position": {"x": 3, "y": 1},
+                "bounds": [{"x": 2.5, "y": 1}, {"x": 3.5, "y": 1}],
+                "rotation": 0,
+                "closed": true,
+                "freestanding": false
+            }
+        ],
+        "environment": {"baked_lighting": true, "ambient_light": "ff202020"},
+        "lights": [
+            {"position": {"x": 4, "y": 2}, "range": 6, "intensity": 1, "color": "ffeccd8b", "shadows": true}
+        ],
+        "image": ""
+    }"#;
+
+    #[test]
+    fn test_parse_and_convert_geometry() {
+        let map = UvttMap::from_json(SAMPLE).unwrap();
+        assert_eq!(map.image_size_px(), (1000.0, 800.0));
+
+        // Three points make two wall segments, plus one object segment
+        let walls = map.wall_segments(1.0);
+        assert_eq!(walls.len(), 3);
+        assert_eq!(
+            walls[0],
+            Segment {
+                x1: 100.0,
+                y1: 100.0,
+                x2: 500.0,
+                y2: 100.0
+            }
+        );
+
+        // A half-size image halves every coordinate
+        let doors = map.doors(0.5);
+        assert_eq!(doors.len(), 1);
+        assert!(doors[0].closed);
+        assert_eq!(doors[0].segment.x1, 125.0);
+        assert_eq!(doors[0].segment.x2, 175.0);
+    }
+
+    #[test]
+    fn test_lights_and_ambient() {
+        let map = UvttMap::from_json(SAMPLE).unwrap();
+
+        let lights = map.light_placements(1.0);
+        assert_eq!(lights.len(), 1);
+        assert_eq!((lights[0].x, lights[0].y), (400.0, 200.0));
+        assert_eq!(lights[0].radius_ft, 30.0);
+        assert_eq!(lights[0].color.as_deref(), Some("#eccd8b"));
+
+        assert_eq!(map.ambient_light(), AmbientLight::Darkness);
+    }
+
+    #[test]
+    fn test_map_origin_offsets_positions() {
+        let mut map = UvttMap::from_json(SAMPLE).unwrap();
+        map.resolution.map_origin = UvttPoint { x: 1.0, y: 1.0 };
+        map.environment = None;
+
+        assert_eq!(map.to_pixels(UvttPoint { x: 1.0, y: 1.0 }, 1.0), (0.0, 0.0));
+        assert_eq!(map.ambient_light(), AmbientLight::Bright);
+    }
+}
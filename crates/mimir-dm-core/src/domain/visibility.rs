@@ -0,0 +1,702 @@
+//! Line of sight and token visibility
+//!
+//! Computes what a token can see on a map. Walls and closed doors block
+//! sight; within line of sight, what the token actually sees depends on its
+//! vision type and range, the map's ambient light and the light sources on
+//! the map. All positions and distances are in map pixels.
+//!
+//! Sight is returned as polygons so the player display can draw them:
+//! a token sees its `sight` areas, plus the parts of each lit area that fall
+//! inside its `line_of_sight`.
+
+use crate::domain::uvtt::{Segment, FEET_PER_GRID};
+use crate::models::campaign::{AmbientLight, VisionType};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+/// Angular offset of the extra rays cast on either side of a wall corner
+const CORNER_EPSILON: f64 = 1e-4;
+
+/// Number of rays used to trace the edge of a limited sight radius
+const ARC_STEPS: usize = 72;
+
+/// A point in pixel coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    /// X coordinate (pixel position)
+    pub x: f32,
+    /// Y coordinate (pixel position)
+    pub y: f32,
+}
+
+impl Point {
+    /// Create a point
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Straight-line distance to another point in pixels
+    pub fn distance_to(&self, other: Point) -> f32 {
+        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
+    }
+}
+
+/// How well lit a visible area is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LightLevel {
+    /// Lightly obscured; Perception checks that rely on sight have disadvantage
+    Dim,
+    /// Seen clearly
+    Bright,
+}
+
+/// Convert a distance in feet to pixels for a grid of `grid_size_px`
+pub fn feet_to_pixels(feet: f32, grid_size_px: f32) -> f32 {
+    feet / FEET_PER_GRID * grid_size_px
+}
+
+/// The segments that block sight on a map, including the map's edges
+#[derive(Debug, Clone, PartialEq)]
+pub struct SightBlockers {
+    segments: Vec<Segment>,
+}
+
+impl SightBlockers {
+    /// Create blockers for a map of the given size with no walls yet
+    pub fn new(width_px: f32, height_px: f32) -> Self {
+        let edges = [
+            (0.0, 0.0, width_px, 0.0),
+            (width_px, 0.0, width_px, height_px),
+            (width_px, height_px, 0.0, height_px),
+            (0.0, height_px, 0.0, 0.0),
+        ];
+        Self {
+            segments: edges
+                .into_iter()
+                .map(|(x1, y1, x2, y2)| Segment { x1, y1, x2, y2 })
+                .collect(),
+        }
+    }
+
+    /// Add walls (or closed doors) that block sight
+    pub fn with_segments(mut self, segments: impl IntoIterator<Item = Segment>) -> Self {
+        self.segments.extend(segments);
+        self
+    }
+
+    /// Compute the line-of-sight polygon from a point.
+    ///
+    /// Rays are cast towards every wall end point (and just past it, to see
+    /// around corners). With a `radius`, rays stop at that distance and extra
+    /// rays trace the edge of the circle.
+    ///
+    /// # Returns
+    /// The polygon's vertices in angular order around `origin`
+    pub fn line_of_sight(&self, origin: Point, radius: Option<f32>) -> Vec<Point> {
+        let (ox, oy) = (origin.x as f64, origin.y as f64);
+        let mut angles = Vec::with_capacity(self.segments.len() * 6 + ARC_STEPS);
+        for segment in &self.segments {
+            for (x, y) in [(segment.x1, segment.y1), (segment.x2, segment.y2)] {
+                let angle = (y as f64 - oy).atan2(x as f64 - ox);
+                angles.extend([angle - CORNER_EPSILON, angle, angle + CORNER_EPSILON]);
+            }
+        }
+        if radius.is_some() {
+            angles.extend((0..ARC_STEPS).map(|i| -PI + 2.0 * PI * i as f64 / ARC_STEPS as f64));
+        }
+        angles.sort_by(|a, b| a.total_cmp(b));
+        angles.dedup();
+
+        let max_distance = radius.map_or(f64::INFINITY, |r| r as f64);
+        angles
+            .into_iter()
+            .filter_map(|angle| {
+                let (dx, dy) = (angle.cos(), angle.sin());
+                let distance = self.nearest_hit(ox, oy, dx, dy).min(max_distance);
+                distance
+                    .is_finite()
+                    .then(|| Point::new((ox + dx * distance) as f32, (oy + dy * distance) as f32))
+            })
+            .collect()
+    }
+
+    /// Whether nothing blocks sight between two points
+    pub fn has_line_of_sight(&self, from: Point, to: Point) -> bool {
+        let (ox, oy) = (from.x as f64, from.y as f64);
+        let (dx, dy) = (to.x as f64 - ox, to.y as f64 - oy);
+        self.segments
+            .iter()
+            .all(|segment| ray_hit(ox, oy, dx, dy, segment).is_none_or(|t| t >= 1.0))
+    }
+
+    /// Distance along a unit ray to the nearest blocking segment
+    fn nearest_hit(&self, ox: f64, oy: f64, dx: f64, dy: f64) -> f64 {
+        self.segments
+            .iter()
+            .filter_map(|segment| ray_hit(ox, oy, dx, dy, segment))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Where a ray from (ox, oy) along (dx, dy) crosses a segment, as a multiple
+/// of the ray direction
+fn ray_hit(ox: f64, oy: f64, dx: f64, dy: f64, segment: &Segment) -> Option<f64> {
+    let (px, py) = (segment.x1 as f64, segment.y1 as f64);
+    let (sx, sy) = (segment.x2 as f64 - px, segment.y2 as f64 - py);
+    let denom = dx * sy - dy * sx;
+    if denom.abs() < 1e-12 {
+        return None;
+    }
+    let (wx, wy) = (px - ox, py - oy);
+    let t = (wx * sy - wy * sx) / denom;
+    let u = (wx * dy - wy * dx) / denom;
+    (t >= 0.0 && (0.0..=1.0).contains(&u)).then_some(t)
+}
+
+/// Whether a point lies inside a polygon or within `tolerance` pixels of
+/// its outline
+fn polygon_near(polygon: &[Point], point: Point, tolerance: f32) -> bool {
+    if polygon_contains(polygon, point) {
+        return true;
+    }
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        if segment_distance(polygon[j], polygon[i], point) <= tolerance {
+            return true;
+        }
+        j = i;
+    }
+    false
+}
+
+/// Distance from a point to the segment between `a` and `b`
+fn segment_distance(a: Point, b: Point, point: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return a.distance_to(point);
+    }
+    let t = (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_sq).clamp(0.0, 1.0);
+    Point::new(a.x + t * dx, a.y + t * dy).distance_to(point)
+}
+
+/// Whether a point lies inside a polygon (even-odd rule)
+pub fn polygon_contains(polygon: &[Point], point: Point) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// A token whose visibility is being computed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewer {
+    /// Database ID of the token
+    pub token_id: i32,
+    /// Token center
+    pub position: Point,
+    /// How the token perceives its surroundings
+    pub vision_type: VisionType,
+    /// Range of the token's special vision in pixels
+    pub vision_range_px: f32,
+}
+
+/// A light source on the map
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Light {
+    /// Database ID of the light source
+    pub light_source_id: i32,
+    /// Light center
+    pub position: Point,
+    /// Bright light radius in pixels
+    pub bright_radius_px: f32,
+    /// Dim light radius in pixels
+    pub dim_radius_px: f32,
+}
+
+/// An area a token sees regardless of light sources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SightArea {
+    /// Outline of the area
+    pub polygon: Vec<Point>,
+    /// How well the token sees within it
+    pub light: LightLevel,
+}
+
+/// The area lit by a light source, with walls casting shadows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LitArea {
+    /// Database ID of the light source
+    pub light_source_id: i32,
+    /// Light center
+    pub center: Point,
+    /// Bright light radius in pixels
+    pub bright_radius_px: f32,
+    /// Dim light radius in pixels
+    pub dim_radius_px: f32,
+    /// Outline of the lit area, out to the dim radius
+    pub polygon: Vec<Point>,
+}
+
+impl LitArea {
+    /// Light level this source gives a point, if it reaches it
+    pub fn light_at(&self, point: Point) -> Option<LightLevel> {
+        if !polygon_contains(&self.polygon, point) {
+            return None;
+        }
+        let distance = self.center.distance_to(point);
+        if distance <= self.bright_radius_px {
+            Some(LightLevel::Bright)
+        } else if distance <= self.dim_radius_px {
+            Some(LightLevel::Dim)
+        } else {
+            None
+        }
+    }
+}
+
+/// What a single token can see
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenVisibility {
+    /// Database ID of the token
+    pub token_id: i32,
+    /// Everything not blocked by walls, whether lit or not
+    pub line_of_sight: Vec<Point>,
+    /// Areas seen without help from light sources, dimmest first
+    pub sight: Vec<SightArea>,
+}
+
+impl TokenVisibility {
+    /// How well this token sees a point, given the map's lit areas
+    pub fn light_level_at(&self, point: Point, lit_areas: &[LitArea]) -> Option<LightLevel> {
+        let from_sight = self
+            .sight
+            .iter()
+            .filter(|area| polygon_contains(&area.polygon, point))
+            .map(|area| area.light)
+            .max();
+        let from_lights = if polygon_contains(&self.line_of_sight, point) {
+            lit_areas
+                .iter()
+                .filter_map(|area| area.light_at(point))
+                .max()
+        } else {
+            None
+        };
+        from_sight.max(from_lights)
+    }
+}
+
+/// Visibility of a group of tokens on one map
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapVisibility {
+    /// Database ID of the map
+    pub map_id: i32,
+    /// Areas lit by active light sources
+    pub lit_areas: Vec<LitArea>,
+    /// What each token can see
+    pub tokens: Vec<TokenVisibility>,
+}
+
+impl MapVisibility {
+    /// How well the best-placed token sees a point, if any token does
+    pub fn light_level_at(&self, point: Point) -> Option<LightLevel> {
+        self.tokens
+            .iter()
+            .filter_map(|token| token.light_level_at(point, &self.lit_areas))
+            .max()
+    }
+
+    /// The regions the tokens currently see, for remembering explored areas.
+    ///
+    /// Each token contributes its widest sight area. Unless that already
+    /// covers its whole line of sight, every lit area the token can see
+    /// into is added, limited to its line of sight. Duplicate regions are
+    /// only returned once.
+    pub fn seen_regions(&self) -> Vec<SeenRegion> {
+        let mut regions: Vec<SeenRegion> = Vec::new();
+        for token in &self.tokens {
+            let widest = token.sight.first();
+            if let Some(area) = widest {
+                push_unique(
+                    &mut regions,
+                    SeenRegion {
+                        polygon: area.polygon.clone(),
+                        clip: None,
+                    },
+                );
+            }
+            if widest.is_some_and(|area| area.polygon == token.line_of_sight) {
+                continue;
+            }
+            for lit in &self.lit_areas {
+                let seen = std::iter::once(&lit.center)
+                    .chain(&lit.polygon)
+                    .any(|p| polygon_contains(&token.line_of_sight, *p));
+                if seen {
+                    push_unique(
+                        &mut regions,
+                        SeenRegion {
+                            polygon: lit.polygon.clone(),
+                            clip: Some(token.line_of_sight.clone()),
+                        },
+                    );
+                }
+            }
+        }
+        regions
+    }
+}
+
+fn push_unique(regions: &mut Vec<SeenRegion>, region: SeenRegion) {
+    if !regions.contains(&region) {
+        regions.push(region);
+    }
+}
+
+/// An area a token has seen: a polygon, optionally limited to the part of it
+/// inside a second polygon (such as a lit area seen through a doorway)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeenRegion {
+    /// Outline of the area
+    pub polygon: Vec<Point>,
+    /// Only the part of `polygon` inside this outline was seen
+    pub clip: Option<Vec<Point>>,
+}
+
+impl SeenRegion {
+    /// Whether a point lies within the region
+    pub fn contains(&self, point: Point) -> bool {
+        polygon_contains(&self.polygon, point)
+            && self
+                .clip
+                .as_ref()
+                .is_none_or(|clip| polygon_contains(clip, point))
+    }
+
+    /// Whether a point lies within the region or within `tolerance` pixels
+    /// of it
+    pub fn contains_near(&self, point: Point, tolerance: f32) -> bool {
+        polygon_near(&self.polygon, point, tolerance)
+            && self
+                .clip
+                .as_ref()
+                .is_none_or(|clip| polygon_near(clip, point, tolerance))
+    }
+
+    /// Points just inside the region's outline: every corner and edge
+    /// midpoint of the polygon and clip, nudged a pixel toward the middle of
+    /// their outline, that falls within the region.
+    ///
+    /// Another region close to all of them covers this one, give or take
+    /// the space between outline points.
+    pub fn sample_points(&self) -> Vec<Point> {
+        std::iter::once(&self.polygon)
+            .chain(&self.clip)
+            .flat_map(|outline| outline_samples(outline))
+            .filter(|point| self.contains(*point))
+            .collect()
+    }
+}
+
+fn outline_samples(outline: &[Point]) -> Vec<Point> {
+    if outline.is_empty() {
+        return Vec::new();
+    }
+    let count = outline.len() as f32;
+    let middle = Point::new(
+        outline.iter().map(|p| p.x).sum::<f32>() / count,
+        outline.iter().map(|p| p.y).sum::<f32>() / count,
+    );
+    let nudge = |point: Point| {
+        let distance = point.distance_to(middle);
+        if distance <= 1.0 {
+            return middle;
+        }
+        Point::new(
+            point.x + (middle.x - point.x) / distance,
+            point.y + (middle.y - point.y) / distance,
+        )
+    };
+
+    let mut samples = vec![middle];
+    let mut j = outline.len() - 1;
+    for i in 0..outline.len() {
+        let (a, b) = (outline[j], outline[i]);
+        samples.push(nudge(b));
+        samples.push(nudge(Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)));
+        j = i;
+    }
+    samples
+}
+
+/// Compute the area lit by a light source.
+pub fn lit_area(blockers: &SightBlockers, light: &Light) -> LitArea {
+    LitArea {
+        light_source_id: light.light_source_id,
+        center: light.position,
+        bright_radius_px: light.bright_radius_px,
+        dim_radius_px: light.dim_radius_px,
+        polygon: blockers.line_of_sight(light.position, Some(light.dim_radius_px)),
+    }
+}
+
+/// Compute what a token sees on a map.
+///
+/// Vision follows the same rules as the player display: in bright ambient
+/// light everything in line of sight is seen clearly. In dim light normal
+/// vision sees dimly, while special vision sees clearly within its range.
+/// In darkness, darkvision sees dimly within its range, other special senses
+/// see clearly within their range, and normal vision relies on lights alone.
+pub fn token_visibility(
+    blockers: &SightBlockers,
+    viewer: &Viewer,
+    ambient_light: AmbientLight,
+) -> TokenVisibility {
+    let line_of_sight = blockers.line_of_sight(viewer.position, None);
+    let range = viewer.vision_range_px;
+    let has_range = viewer.vision_type.sees_in_darkness() && range > 0.0;
+
+    let mut ranges = Vec::new();
+    match ambient_light {
+        AmbientLight::Bright => ranges.push((None, LightLevel::Bright)),
+        AmbientLight::Dim => {
+            ranges.push((None, LightLevel::Dim));
+            if has_range {
+                ranges.push((Some(range), LightLevel::Bright));
+            }
+        }
+        AmbientLight::Darkness => {
+            if has_range {
+                let light = if viewer.vision_type == VisionType::Darkvision {
+                    LightLevel::Dim
+                } else {
+                    LightLevel::Bright
+                };
+                ranges.push((Some(range), light));
+            }
+        }
+    }
+
+    let sight = ranges
+        .into_iter()
+        .map(|(radius, light)| SightArea {
+            polygon: match radius {
+                Some(_) => blockers.line_of_sight(viewer.position, radius),
+                None => line_of_sight.clone(),
+            },
+            light,
+        })
+        .collect();
+
+    TokenVisibility {
+        token_id: viewer.token_id,
+        line_of_sight,
+        sight,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewer(x: f32, y: f32, vision_type: VisionType, range: f32) -> Viewer {
+        Viewer {
+            token_id: 1,
+            position: Point::new(x, y),
+            vision_type,
+            vision_range_px: range,
+        }
+    }
+
+    /// A 1000x1000 map split by a wall at x = 500, with a gap from y = 400 to 600
+    fn split_room() -> SightBlockers {
+        SightBlockers::new(1000.0, 1000.0).with_segments([
+            Segment {
+                x1: 500.0,
+                y1: 0.0,
+                x2: 500.0,
+                y2: 400.0,
+            },
+            Segment {
+                x1: 500.0,
+                y1: 600.0,
+                x2: 500.0,
+                y2: 1000.0,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_walls_block_line_of_sight() {
+        let blockers = split_room();
+        let from = Point::new(250.0, 500.0);
+
+        assert!(blockers.has_line_of_sight(from, Point::new(750.0, 500.0)));
+        assert!(!blockers.has_line_of_sight(from, Point::new(750.0, 100.0)));
+
+        let polygon = blockers.line_of_sight(from, None);
+        assert!(polygon_contains(&polygon, Point::new(750.0, 500.0)));
+        assert!(polygon_contains(&polygon, Point::new(100.0, 100.0)));
+        assert!(!polygon_contains(&polygon, Point::new(750.0, 100.0)));
+        assert!(!polygon_contains(&polygon, Point::new(900.0, 950.0)));
+    }
+
+    #[test]
+    fn test_radius_limits_line_of_sight() {
+        let blockers = SightBlockers::new(1000.0, 1000.0);
+        let polygon = blockers.line_of_sight(Point::new(500.0, 500.0), Some(100.0));
+
+        assert!(polygon_contains(&polygon, Point::new(590.0, 500.0)));
+        assert!(!polygon_contains(&polygon, Point::new(610.0, 500.0)));
+        assert!(polygon
+            .iter()
+            .all(|p| p.distance_to(Point::new(500.0, 500.0)) <= 100.01));
+    }
+
+    #[test]
+    fn test_bright_ambient_sees_everything_in_line_of_sight() {
+        let blockers = split_room();
+        let visibility = token_visibility(
+            &blockers,
+            &viewer(250.0, 500.0, VisionType::Normal, 0.0),
+            AmbientLight::Bright,
+        );
+
+        assert_eq!(
+            visibility.light_level_at(Point::new(900.0, 500.0), &[]),
+            Some(LightLevel::Bright)
+        );
+        assert_eq!(
+            visibility.light_level_at(Point::new(900.0, 100.0), &[]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_darkness_needs_darkvision_or_light() {
+        let blockers = split_room();
+        let torch = lit_area(
+            &blockers,
+            &Light {
+                light_source_id: 7,
+                position: Point::new(800.0, 500.0),
+                bright_radius_px: 100.0,
+                dim_radius_px: 200.0,
+            },
+        );
+        let lit_areas = [torch];
+
+        let human = token_visibility(
+            &blockers,
+            &viewer(250.0, 500.0, VisionType::Normal, 0.0),
+            AmbientLight::Darkness,
+        );
+        assert!(human.sight.is_empty());
+        assert_eq!(
+            human.light_level_at(Point::new(300.0, 500.0), &lit_areas),
+            None
+        );
+        assert_eq!(
+            human.light_level_at(Point::new(800.0, 550.0), &lit_areas),
+            Some(LightLevel::Bright)
+        );
+        assert_eq!(
+            human.light_level_at(Point::new(950.0, 500.0), &lit_areas),
+            Some(LightLevel::Dim)
+        );
+
+        let dwarf = token_visibility(
+            &blockers,
+            &viewer(250.0, 500.0, VisionType::Darkvision, 120.0),
+            AmbientLight::Darkness,
+        );
+        assert_eq!(
+            dwarf.light_level_at(Point::new(300.0, 500.0), &lit_areas),
+            Some(LightLevel::Dim)
+        );
+        assert_eq!(
+            dwarf.light_level_at(Point::new(100.0, 500.0), &lit_areas),
+            None
+        );
+    }
+
+    #[test]
+    fn test_light_behind_wall_is_not_seen() {
+        let blockers = split_room();
+        let torch = lit_area(
+            &blockers,
+            &Light {
+                light_source_id: 1,
+                position: Point::new(800.0, 100.0),
+                bright_radius_px: 100.0,
+                dim_radius_px: 200.0,
+            },
+        );
+        let visibility = MapVisibility {
+            map_id: 1,
+            lit_areas: vec![torch],
+            tokens: vec![token_visibility(
+                &blockers,
+                &viewer(250.0, 100.0, VisionType::Normal, 0.0),
+                AmbientLight::Darkness,
+            )],
+        };
+
+        assert_eq!(visibility.light_level_at(Point::new(800.0, 150.0)), None);
+    }
+
+    #[test]
+    fn test_seen_regions_include_visible_lights() {
+        let blockers = split_room();
+        let light = |id, x, y| {
+            lit_area(
+                &blockers,
+                &Light {
+                    light_source_id: id,
+                    position: Point::new(x, y),
+                    bright_radius_px: 100.0,
+                    dim_radius_px: 200.0,
+                },
+            )
+        };
+        let token = token_visibility(
+            &blockers,
+            &viewer(100.0, 500.0, VisionType::Darkvision, 120.0),
+            AmbientLight::Darkness,
+        );
+        let visibility = MapVisibility {
+            map_id: 1,
+            lit_areas: vec![light(1, 800.0, 500.0), light(2, 700.0, 100.0)],
+            tokens: vec![token.clone(), token],
+        };
+
+        // Darkvision radius plus the torch seen through the gap, once each;
+        // the torch behind the wall is not seen
+        let regions = visibility.seen_regions();
+        assert_eq!(regions.len(), 2);
+        assert!(regions[0].clip.is_none());
+        assert!(regions[0].contains(Point::new(150.0, 500.0)));
+        assert!(!regions[0].contains(Point::new(100.0, 300.0)));
+        assert!(regions[1].contains(Point::new(700.0, 500.0)));
+        assert!(!regions[1].contains(Point::new(800.0, 310.0)));
+
+        // A region covers itself, but not one that reaches further
+        let samples = regions[0].sample_points();
+        assert!(!samples.is_empty());
+        assert!(samples.iter().all(|p| regions[0].contains_near(*p, 0.0)));
+        assert!(!regions[1]
+            .sample_points()
+            .iter()
+            .all(|p| regions[0].contains_near(*p, 25.0)));
+    }
+}
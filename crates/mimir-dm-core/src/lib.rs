@@ -15,6 +15,7 @@ pub mod models;
 pub mod schema;
 pub mod seed;
 pub mod services;
+pub mod text;
 
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
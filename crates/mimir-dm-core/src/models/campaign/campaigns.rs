@@ -4,6 +4,34 @@ use crate::schema::campaigns;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// How grid distances count diagonal moves on the campaign's maps
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagonalRule {
+    /// Every second diagonal costs 10 ft (5/10/5)
+    #[default]
+    Alternating,
+    /// Straight-line distance, ignoring the grid
+    Euclidean,
+}
+
+impl DiagonalRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagonalRule::Alternating => "alternating",
+            DiagonalRule::Euclidean => "euclidean",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "euclidean" => DiagonalRule::Euclidean,
+            _ => DiagonalRule::Alternating,
+        }
+    }
+}
+
 /// Database model for campaigns
 #[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
 #[diesel(table_name = campaigns)]
@@ -17,6 +45,7 @@ pub struct Campaign {
     pub first_session_date: Option<String>,
     pub last_activity_at: String,
     pub archived_at: Option<String>,
+    pub diagonal_rule: String,
 }
 
 /// New campaign for insertion
@@ -39,8 +68,14 @@ pub struct UpdateCampaign {
     pub first_session_date: Option<Option<String>>,
     pub last_activity_at: Option<String>,
     pub archived_at: Option<Option<String>>,
+    pub diagonal_rule: Option<String>,
 }
 
 impl Campaign {
     // Transition validation is handled by BoardDefinition in the service layer
+
+    /// Get the diagonal rule enum
+    pub fn diagonal_rule_enum(&self) -> DiagonalRule {
+        DiagonalRule::from_str(&self.diagonal_rule)
+    }
 }
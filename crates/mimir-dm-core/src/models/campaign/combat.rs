@@ -0,0 +1,708 @@
+//! Combat encounter database models for the initiative tracker
+//!
+//! An encounter holds the round and turn state of a fight; combatants are the
+//! individual creatures taking part, each with their own initiative and HP.
+//! Conditions and HP events (damage, healing, temp HP) hang off combatants.
+
+use crate::schema::{combat_combatants, combat_conditions, combat_encounters, combat_hp_events};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Encounter status - whether the fight is still running
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CombatStatus {
+    #[default]
+    Active,
+    Completed,
+}
+
+impl CombatStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CombatStatus::Active => "active",
+            CombatStatus::Completed => "completed",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "completed" => CombatStatus::Completed,
+            _ => CombatStatus::Active,
+        }
+    }
+}
+
+/// Combatant type - what kind of creature is taking part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CombatantType {
+    #[default]
+    Monster,
+    PC,
+    NPC,
+}
+
+impl CombatantType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CombatantType::Monster => "monster",
+            CombatantType::PC => "pc",
+            CombatantType::NPC => "npc",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "pc" => CombatantType::PC,
+            "npc" => CombatantType::NPC,
+            _ => CombatantType::Monster,
+        }
+    }
+}
+
+/// How monster max HP is determined when a combatant is created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HpMode {
+    /// Use the catalog's average HP
+    #[default]
+    Average,
+    /// Roll the catalog's hit dice formula
+    Rolled,
+}
+
+impl HpMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HpMode::Average => "average",
+            HpMode::Rolled => "rolled",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "rolled" => HpMode::Rolled,
+            _ => HpMode::Average,
+        }
+    }
+}
+
+/// Database model for combat encounters
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = combat_encounters)]
+pub struct CombatEncounter {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub module_id: Option<i32>,
+    pub map_id: Option<i32>,
+    pub name: String,
+    /// Module encounter tag this combat was started from
+    pub encounter_tag: Option<String>,
+    pub status: String,
+    pub round: i32,
+    /// Combatant whose turn it currently is
+    pub current_combatant_id: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CombatEncounter {
+    /// Get the status enum
+    pub fn status_enum(&self) -> CombatStatus {
+        CombatStatus::from_str(&self.status)
+    }
+
+    /// Check if this encounter is still running
+    pub fn is_active(&self) -> bool {
+        self.status_enum() == CombatStatus::Active
+    }
+}
+
+/// New combat encounter for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = combat_encounters)]
+pub struct NewCombatEncounter {
+    pub campaign_id: i32,
+    pub module_id: Option<i32>,
+    pub map_id: Option<i32>,
+    pub name: String,
+    pub encounter_tag: Option<String>,
+    pub status: String,
+    pub round: i32,
+}
+
+impl NewCombatEncounter {
+    /// Create a new active encounter starting at round 1
+    pub fn new(campaign_id: i32, name: String) -> Self {
+        Self {
+            campaign_id,
+            module_id: None,
+            map_id: None,
+            name,
+            encounter_tag: None,
+            status: CombatStatus::Active.as_str().to_string(),
+            round: 1,
+        }
+    }
+
+    pub fn with_module(mut self, module_id: i32, encounter_tag: Option<String>) -> Self {
+        self.module_id = Some(module_id);
+        self.encounter_tag = encounter_tag;
+        self
+    }
+
+    pub fn with_map(mut self, map_id: i32) -> Self {
+        self.map_id = Some(map_id);
+        self
+    }
+}
+
+/// Combat encounter update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = combat_encounters)]
+pub struct UpdateCombatEncounter {
+    pub name: Option<String>,
+    pub status: Option<String>,
+    pub round: Option<i32>,
+    pub current_combatant_id: Option<Option<i32>>,
+    pub updated_at: Option<String>,
+}
+
+impl UpdateCombatEncounter {
+    /// Create an update that moves the turn marker
+    pub fn turn(round: i32, current_combatant_id: Option<i32>) -> Self {
+        Self {
+            round: Some(round),
+            current_combatant_id: Some(current_combatant_id),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    /// Create an update for the encounter status
+    pub fn status(status: CombatStatus) -> Self {
+        Self {
+            status: Some(status.as_str().to_string()),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Database model for combatants
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = combat_combatants)]
+pub struct Combatant {
+    pub id: i32,
+    pub encounter_id: i32,
+    pub name: String,
+    pub combatant_type: String,
+    pub monster_name: Option<String>,
+    pub monster_source: Option<String>,
+    pub character_id: Option<i32>,
+    pub token_id: Option<i32>,
+    pub initiative: i32,
+    pub initiative_modifier: i32,
+    pub current_hp: Option<i32>,
+    pub max_hp: Option<i32>,
+    pub armor_class: Option<i32>,
+    /// False once the combatant is defeated or taken out of the turn order
+    pub is_active: bool,
+    pub notes: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Temporary HP, lost before current HP
+    pub temp_hp: i32,
+}
+
+impl Combatant {
+    /// Get the combatant type enum
+    pub fn combatant_type_enum(&self) -> CombatantType {
+        CombatantType::from_str(&self.combatant_type)
+    }
+
+    /// Check if this combatant is at 0 HP
+    pub fn is_down(&self) -> bool {
+        matches!(self.current_hp, Some(hp) if hp <= 0)
+    }
+}
+
+/// New combatant for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = combat_combatants)]
+pub struct NewCombatant {
+    pub encounter_id: i32,
+    pub name: String,
+    pub combatant_type: String,
+    pub monster_name: Option<String>,
+    pub monster_source: Option<String>,
+    pub character_id: Option<i32>,
+    pub token_id: Option<i32>,
+    pub initiative: i32,
+    pub initiative_modifier: i32,
+    pub current_hp: Option<i32>,
+    pub max_hp: Option<i32>,
+    pub armor_class: Option<i32>,
+    pub is_active: bool,
+    pub notes: Option<String>,
+}
+
+impl NewCombatant {
+    /// Create a combatant with no catalog or character link
+    pub fn new(encounter_id: i32, name: String, combatant_type: CombatantType) -> Self {
+        Self {
+            encounter_id,
+            name,
+            combatant_type: combatant_type.as_str().to_string(),
+            monster_name: None,
+            monster_source: None,
+            character_id: None,
+            token_id: None,
+            initiative: 0,
+            initiative_modifier: 0,
+            current_hp: None,
+            max_hp: None,
+            armor_class: None,
+            is_active: true,
+            notes: None,
+        }
+    }
+
+    /// Create a monster combatant linked to a catalog monster
+    pub fn monster(encounter_id: i32, name: String, monster_name: String, monster_source: String) -> Self {
+        Self {
+            monster_name: Some(monster_name),
+            monster_source: Some(monster_source),
+            ..Self::new(encounter_id, name, CombatantType::Monster)
+        }
+    }
+
+    /// Create a character combatant (PC or NPC) linked to a character
+    pub fn character(encounter_id: i32, name: String, character_id: i32, is_pc: bool) -> Self {
+        let combatant_type = if is_pc { CombatantType::PC } else { CombatantType::NPC };
+        Self {
+            character_id: Some(character_id),
+            ..Self::new(encounter_id, name, combatant_type)
+        }
+    }
+
+    pub fn with_token(mut self, token_id: i32) -> Self {
+        self.token_id = Some(token_id);
+        self
+    }
+
+    pub fn with_initiative_modifier(mut self, modifier: i32) -> Self {
+        self.initiative_modifier = modifier;
+        self
+    }
+
+    pub fn with_hp(mut self, current_hp: i32, max_hp: i32) -> Self {
+        self.current_hp = Some(current_hp);
+        self.max_hp = Some(max_hp);
+        self
+    }
+
+    pub fn with_armor_class(mut self, armor_class: i32) -> Self {
+        self.armor_class = Some(armor_class);
+        self
+    }
+}
+
+/// Combatant update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = combat_combatants)]
+pub struct UpdateCombatant {
+    pub name: Option<String>,
+    pub initiative: Option<i32>,
+    pub initiative_modifier: Option<i32>,
+    pub current_hp: Option<Option<i32>>,
+    pub max_hp: Option<Option<i32>>,
+    pub armor_class: Option<Option<i32>>,
+    pub is_active: Option<bool>,
+    pub notes: Option<Option<String>>,
+    pub updated_at: Option<String>,
+    pub temp_hp: Option<i32>,
+}
+
+impl UpdateCombatant {
+    /// Create an update for just the initiative roll
+    pub fn initiative(initiative: i32) -> Self {
+        Self {
+            initiative: Some(initiative),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    /// Create an update for current HP
+    pub fn hp(current_hp: i32) -> Self {
+        Self {
+            current_hp: Some(Some(current_hp)),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    /// Create an update for whether the combatant is still in the turn order
+    pub fn active(is_active: bool) -> Self {
+        Self {
+            is_active: Some(is_active),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Database model for a condition applied to a combatant
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = combat_conditions)]
+pub struct CombatCondition {
+    pub id: i32,
+    pub combatant_id: i32,
+    /// Catalog condition name (e.g. "Prone")
+    pub condition_name: String,
+    pub condition_source: String,
+    /// Round the condition was applied in
+    pub applied_round: i32,
+    /// Number of rounds the condition lasts (`None` until removed)
+    pub duration_rounds: Option<i32>,
+    pub notes: Option<String>,
+    pub created_at: String,
+}
+
+impl CombatCondition {
+    /// Check if the condition has run out by the given round
+    pub fn is_expired(&self, round: i32) -> bool {
+        matches!(self.duration_rounds, Some(duration) if round >= self.applied_round + duration)
+    }
+}
+
+/// New combat condition for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = combat_conditions)]
+pub struct NewCombatCondition {
+    pub combatant_id: i32,
+    pub condition_name: String,
+    pub condition_source: String,
+    pub applied_round: i32,
+    pub duration_rounds: Option<i32>,
+    pub notes: Option<String>,
+}
+
+impl NewCombatCondition {
+    pub fn new(combatant_id: i32, condition_name: String, condition_source: String) -> Self {
+        Self {
+            combatant_id,
+            condition_name,
+            condition_source,
+            applied_round: 1,
+            duration_rounds: None,
+            notes: None,
+        }
+    }
+
+    pub fn with_duration(mut self, duration_rounds: i32) -> Self {
+        self.duration_rounds = Some(duration_rounds);
+        self
+    }
+
+    pub fn with_notes(mut self, notes: String) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+}
+
+/// Kind of HP change recorded in the HP log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HpEventType {
+    Damage,
+    Healing,
+    TempHp,
+}
+
+impl HpEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HpEventType::Damage => "damage",
+            HpEventType::Healing => "healing",
+            HpEventType::TempHp => "temp_hp",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "healing" => HpEventType::Healing,
+            "temp_hp" => HpEventType::TempHp,
+            _ => HpEventType::Damage,
+        }
+    }
+}
+
+/// Damage defense that changed the amount of damage taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DamageDefense {
+    Resistance,
+    Immunity,
+    Vulnerability,
+}
+
+impl DamageDefense {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DamageDefense::Resistance => "resistance",
+            DamageDefense::Immunity => "immunity",
+            DamageDefense::Vulnerability => "vulnerability",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "resistance" => Some(DamageDefense::Resistance),
+            "immunity" => Some(DamageDefense::Immunity),
+            "vulnerability" => Some(DamageDefense::Vulnerability),
+            _ => None,
+        }
+    }
+
+    /// Apply this defense to a damage amount
+    pub fn apply(&self, amount: i32) -> i32 {
+        match self {
+            DamageDefense::Resistance => amount / 2,
+            DamageDefense::Immunity => 0,
+            DamageDefense::Vulnerability => amount * 2,
+        }
+    }
+}
+
+/// Database model for an HP log entry
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = combat_hp_events)]
+pub struct HpEvent {
+    pub id: i32,
+    pub combatant_id: i32,
+    pub round: i32,
+    pub event_type: String,
+    /// Amount as rolled, before any defenses
+    pub amount: i32,
+    pub damage_type: Option<String>,
+    /// Amount actually applied after defenses
+    pub applied_amount: i32,
+    pub defense: Option<String>,
+    pub hp_before: Option<i32>,
+    pub hp_after: Option<i32>,
+    pub temp_hp_before: i32,
+    pub temp_hp_after: i32,
+    pub created_at: String,
+}
+
+impl HpEvent {
+    /// Get the event type enum
+    pub fn event_type_enum(&self) -> HpEventType {
+        HpEventType::from_str(&self.event_type)
+    }
+
+    /// Get the defense that applied, if any
+    pub fn defense_enum(&self) -> Option<DamageDefense> {
+        self.defense.as_deref().and_then(DamageDefense::from_str)
+    }
+}
+
+/// New HP log entry for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = combat_hp_events)]
+pub struct NewHpEvent {
+    pub combatant_id: i32,
+    pub round: i32,
+    pub event_type: String,
+    pub amount: i32,
+    pub damage_type: Option<String>,
+    pub applied_amount: i32,
+    pub defense: Option<String>,
+    pub hp_before: Option<i32>,
+    pub hp_after: Option<i32>,
+    pub temp_hp_before: i32,
+    pub temp_hp_after: i32,
+}
+
+/// Damage resistances, immunities and vulnerabilities parsed from monster JSON
+///
+/// Entries are either plain damage type strings or 5etools objects such as
+/// `{"resist": ["bludgeoning", "piercing", "slashing"], "note": "from nonmagical attacks", "cond": true}`.
+/// Conditional entries that only cover nonmagical attacks are ignored for magical damage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DamageDefenses {
+    pub resistances: Vec<DefenseEntry>,
+    pub immunities: Vec<DefenseEntry>,
+    pub vulnerabilities: Vec<DefenseEntry>,
+    /// Condition names the creature cannot be affected by (lowercase)
+    pub condition_immunities: Vec<String>,
+}
+
+/// A single damage type covered by a defense
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DefenseEntry {
+    /// Lowercase damage type
+    pub damage_type: String,
+    /// Whether this only applies to nonmagical attacks
+    pub nonmagical_only: bool,
+}
+
+impl DamageDefenses {
+    /// Parse defenses from a monster's full 5etools JSON
+    pub fn from_monster_json(data: &Value) -> Self {
+        Self {
+            resistances: parse_defense_entries(data.get("resist"), "resist"),
+            immunities: parse_defense_entries(data.get("immune"), "immune"),
+            vulnerabilities: parse_defense_entries(data.get("vulnerable"), "vulnerable"),
+            condition_immunities: parse_defense_entries(data.get("conditionImmune"), "conditionImmune")
+                .into_iter()
+                .map(|e| e.damage_type)
+                .collect(),
+        }
+    }
+
+    /// Find the defense that applies to a damage type.
+    ///
+    /// Immunity takes precedence over resistance, which takes precedence over vulnerability.
+    pub fn defense_for(&self, damage_type: &str, magical: bool) -> Option<DamageDefense> {
+        let damage_type = damage_type.to_lowercase();
+        let covers = |entries: &[DefenseEntry]| {
+            entries
+                .iter()
+                .any(|e| e.damage_type == damage_type && !(magical && e.nonmagical_only))
+        };
+
+        if covers(&self.immunities) {
+            Some(DamageDefense::Immunity)
+        } else if covers(&self.resistances) {
+            Some(DamageDefense::Resistance)
+        } else if covers(&self.vulnerabilities) {
+            Some(DamageDefense::Vulnerability)
+        } else {
+            None
+        }
+    }
+
+    /// Check if the creature is immune to a condition
+    pub fn is_immune_to_condition(&self, condition_name: &str) -> bool {
+        self.condition_immunities.contains(&condition_name.to_lowercase())
+    }
+}
+
+fn parse_defense_entries(value: Option<&Value>, key: &str) -> Vec<DefenseEntry> {
+    let mut entries = Vec::new();
+    let Some(values) = value.and_then(|v| v.as_array()) else {
+        return entries;
+    };
+
+    for value in values {
+        if let Some(s) = value.as_str() {
+            entries.push(DefenseEntry {
+                damage_type: s.to_lowercase(),
+                nonmagical_only: false,
+            });
+        } else if let Some(obj) = value.as_object() {
+            let nonmagical_only = obj
+                .get("note")
+                .and_then(|n| n.as_str())
+                .map(|n| n.to_lowercase().contains("nonmagical"))
+                .unwrap_or(false);
+            // Nested groups share the outer note
+            for mut entry in parse_defense_entries(obj.get(key), key) {
+                entry.nonmagical_only |= nonmagical_only;
+                entries.push(entry);
+            }
+        }
+    }
+
+    entries
+}
+
+/// Full combat state: the encounter plus its combatants in turn order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatState {
+    pub encounter: CombatEncounter,
+    /// Combatants sorted by initiative (highest first)
+    pub combatants: Vec<Combatant>,
+    /// Conditions currently applied to the combatants
+    pub conditions: Vec<CombatCondition>,
+}
+
+impl CombatState {
+    /// Get the combatant whose turn it currently is
+    pub fn current_combatant(&self) -> Option<&Combatant> {
+        self.encounter
+            .current_combatant_id
+            .and_then(|id| self.combatants.iter().find(|c| c.id == id))
+    }
+
+    /// Get the conditions applied to a combatant
+    pub fn conditions_for(&self, combatant_id: i32) -> Vec<&CombatCondition> {
+        self.conditions
+            .iter()
+            .filter(|c| c.combatant_id == combatant_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_damage_defenses_from_monster_json() {
+        let data = json!({
+            "resist": [
+                "cold",
+                {"resist": ["bludgeoning", "piercing", "slashing"], "note": "from nonmagical attacks", "cond": true}
+            ],
+            "immune": ["poison"],
+            "vulnerable": ["Fire"],
+            "conditionImmune": ["poisoned", {"conditionImmune": ["charmed"]}]
+        });
+        let defenses = DamageDefenses::from_monster_json(&data);
+
+        assert_eq!(defenses.defense_for("cold", false), Some(DamageDefense::Resistance));
+        assert_eq!(defenses.defense_for("slashing", false), Some(DamageDefense::Resistance));
+        assert_eq!(defenses.defense_for("slashing", true), None);
+        assert_eq!(defenses.defense_for("POISON", true), Some(DamageDefense::Immunity));
+        assert_eq!(defenses.defense_for("fire", false), Some(DamageDefense::Vulnerability));
+        assert_eq!(defenses.defense_for("acid", false), None);
+        assert!(defenses.is_immune_to_condition("Poisoned"));
+        assert!(defenses.is_immune_to_condition("charmed"));
+        assert!(!defenses.is_immune_to_condition("prone"));
+    }
+
+    #[test]
+    fn test_damage_defense_apply() {
+        assert_eq!(DamageDefense::Resistance.apply(7), 3);
+        assert_eq!(DamageDefense::Immunity.apply(7), 0);
+        assert_eq!(DamageDefense::Vulnerability.apply(7), 14);
+    }
+
+    #[test]
+    fn test_condition_expiry() {
+        let condition = CombatCondition {
+            id: 1,
+            combatant_id: 1,
+            condition_name: "Stunned".to_string(),
+            condition_source: "PHB".to_string(),
+            applied_round: 2,
+            duration_rounds: Some(1),
+            notes: None,
+            created_at: String::new(),
+        };
+        assert!(!condition.is_expired(2));
+        assert!(condition.is_expired(3));
+    }
+}
@@ -1,36 +1,154 @@
 //! Fog of War database models for Visual Display System
 //!
-//! Tracks revealed areas on maps for the fog of war feature.
+//! Tracks revealed areas on maps for the fog of war feature. Areas are
+//! either revealed by the DM or explored by PC tokens as they move.
 
+use crate::domain::visibility::{polygon_contains, Point, SeenRegion};
 use crate::schema::fog_revealed_areas;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Shape of a revealed area
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FogAreaShape {
+    #[default]
+    Rect,
+    /// Circle filling the bounding box
+    Circle,
+    /// Outline stored in `points`
+    Polygon,
+}
+
+impl FogAreaShape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FogAreaShape::Rect => "rect",
+            FogAreaShape::Circle => "circle",
+            FogAreaShape::Polygon => "polygon",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "circle" => FogAreaShape::Circle,
+            "polygon" => FogAreaShape::Polygon,
+            _ => FogAreaShape::Rect,
+        }
+    }
+}
+
+/// How an area came to be revealed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FogAreaSource {
+    /// Revealed by the DM
+    #[default]
+    Manual,
+    /// Seen by PC tokens
+    Explored,
+}
+
+impl FogAreaSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FogAreaSource::Manual => "manual",
+            FogAreaSource::Explored => "explored",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "explored" => FogAreaSource::Explored,
+            _ => FogAreaSource::Manual,
+        }
+    }
+}
+
 /// Database model for fog revealed areas
-/// Each record represents a rectangular region that has been revealed on a map
+/// Each record represents a rectangle, circle or polygon that has been revealed on a map
 #[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
 #[diesel(table_name = fog_revealed_areas)]
 pub struct FogRevealedArea {
     pub id: i32,
     pub map_id: i32,
-    /// X coordinate (pixel position)
+    /// X coordinate of the bounding box (pixel position)
     pub x: f32,
-    /// Y coordinate (pixel position)
+    /// Y coordinate of the bounding box (pixel position)
     pub y: f32,
-    /// Width of revealed area (pixels)
+    /// Width of the bounding box (pixels)
     pub width: f32,
-    /// Height of revealed area (pixels)
+    /// Height of the bounding box (pixels)
     pub height: f32,
     pub created_at: String,
+    /// "rect", "circle" or "polygon"
+    pub shape: String,
+    /// Polygon outline as a JSON array of points
+    pub points: Option<String>,
+    /// JSON array of points limiting the polygon to where it overlaps this outline
+    pub clip_points: Option<String>,
+    /// "manual" or "explored"
+    pub source: String,
 }
 
 impl FogRevealedArea {
+    pub fn shape_enum(&self) -> FogAreaShape {
+        FogAreaShape::from_str(&self.shape)
+    }
+
+    pub fn source_enum(&self) -> FogAreaSource {
+        FogAreaSource::from_str(&self.source)
+    }
+
+    /// Outline of a polygon area
+    pub fn polygon(&self) -> Option<Vec<Point>> {
+        self.points
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok())
+    }
+
+    /// Outline limiting a polygon area, if any
+    pub fn clip_polygon(&self) -> Option<Vec<Point>> {
+        self.clip_points
+            .as_deref()
+            .and_then(|s| serde_json::from_str(s).ok())
+    }
+
+    /// The polygon and clip outline of a polygon area
+    pub fn seen_region(&self) -> Option<SeenRegion> {
+        Some(SeenRegion {
+            polygon: self.polygon()?,
+            clip: self.clip_polygon(),
+        })
+    }
+
     /// Check if a point is within this revealed area
     pub fn contains_point(&self, px: f32, py: f32) -> bool {
-        px >= self.x && px <= self.x + self.width && py >= self.y && py <= self.y + self.height
+        let in_bounds =
+            px >= self.x && px <= self.x + self.width && py >= self.y && py <= self.y + self.height;
+        if !in_bounds {
+            return false;
+        }
+        let point = Point::new(px, py);
+        match self.shape_enum() {
+            FogAreaShape::Rect => true,
+            FogAreaShape::Circle => {
+                let center = Point::new(self.x + self.width / 2.0, self.y + self.height / 2.0);
+                center.distance_to(point) <= self.width / 2.0
+            }
+            FogAreaShape::Polygon => {
+                self.polygon()
+                    .is_some_and(|polygon| polygon_contains(&polygon, point))
+                    && self
+                        .clip_polygon()
+                        .is_none_or(|clip| polygon_contains(&clip, point))
+            }
+        }
     }
 
-    /// Check if this area overlaps with another rectangle
+    /// Check if this area's bounding box overlaps with another rectangle
     pub fn overlaps(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
         self.x < x + width && self.x + self.width > x && self.y < y + height && self.y + self.height > y
     }
@@ -45,10 +163,14 @@ pub struct NewFogRevealedArea {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    pub shape: String,
+    pub points: Option<String>,
+    pub clip_points: Option<String>,
+    pub source: String,
 }
 
 impl NewFogRevealedArea {
-    /// Create a new revealed area
+    /// Create a new rectangular revealed area
     pub fn new(map_id: i32, x: f32, y: f32, width: f32, height: f32) -> Self {
         Self {
             map_id,
@@ -56,17 +178,61 @@ impl NewFogRevealedArea {
             y,
             width,
             height,
+            shape: FogAreaShape::Rect.as_str().to_string(),
+            points: None,
+            clip_points: None,
+            source: FogAreaSource::Manual.as_str().to_string(),
         }
     }
 
-    /// Create a revealed area from a center point and radius (circular reveal stored as bounding box)
+    /// Create a circular revealed area from a center point and radius
     pub fn from_circle(map_id: i32, center_x: f32, center_y: f32, radius: f32) -> Self {
         Self {
-            map_id,
-            x: center_x - radius,
-            y: center_y - radius,
-            width: radius * 2.0,
-            height: radius * 2.0,
+            shape: FogAreaShape::Circle.as_str().to_string(),
+            ..Self::new(
+                map_id,
+                center_x - radius,
+                center_y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            )
+        }
+    }
+
+    /// Create a polygon revealed area from its outline
+    pub fn from_polygon(map_id: i32, polygon: &[Point]) -> Self {
+        let (min_x, min_y, max_x, max_y) = polygon.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(min_x, min_y, max_x, max_y), p| {
+                (
+                    min_x.min(p.x),
+                    min_y.min(p.y),
+                    max_x.max(p.x),
+                    max_y.max(p.y),
+                )
+            },
+        );
+        Self {
+            shape: FogAreaShape::Polygon.as_str().to_string(),
+            points: serde_json::to_string(polygon).ok(),
+            ..Self::new(map_id, min_x, min_y, max_x - min_x, max_y - min_y)
+        }
+    }
+
+    /// Create an explored area from a region seen by PC tokens
+    pub fn explored(map_id: i32, region: &SeenRegion) -> Self {
+        Self {
+            clip_points: region
+                .clip
+                .as_ref()
+                .and_then(|clip| serde_json::to_string(clip).ok()),
+            source: FogAreaSource::Explored.as_str().to_string(),
+            ..Self::from_polygon(map_id, &region.polygon)
         }
     }
 }
@@ -80,6 +246,12 @@ pub struct FogRevealedAreaSummary {
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    pub shape: FogAreaShape,
+    /// Polygon outline, for polygon areas
+    pub points: Option<Vec<Point>>,
+    /// Outline limiting the polygon, if any
+    pub clip_points: Option<Vec<Point>>,
+    pub source: FogAreaSource,
 }
 
 impl From<FogRevealedArea> for FogRevealedAreaSummary {
@@ -91,6 +263,10 @@ impl From<FogRevealedArea> for FogRevealedAreaSummary {
             y: area.y,
             width: area.width,
             height: area.height,
+            shape: area.shape_enum(),
+            points: area.polygon(),
+            clip_points: area.clip_polygon(),
+            source: area.source_enum(),
         }
     }
 }
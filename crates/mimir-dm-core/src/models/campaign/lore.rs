@@ -0,0 +1,154 @@
+//! Lore index models for semantic search
+//!
+//! Book content and campaign documents are split into chunks and embedded.
+//! The vectors themselves live in the `lore_vectors` sqlite-vec table; these
+//! models cover the relational side of the index.
+
+use crate::schema::{lore_chunks, lore_index_info, lore_sources};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kind of content a lore source was built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoreSourceType {
+    Book,
+    Document,
+}
+
+impl LoreSourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoreSourceType::Book => "book",
+            LoreSourceType::Document => "document",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "book" => Some(LoreSourceType::Book),
+            "document" => Some(LoreSourceType::Document),
+            _ => None,
+        }
+    }
+}
+
+/// An indexed book or campaign document
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = lore_sources)]
+pub struct LoreSource {
+    pub id: i32,
+    pub source_type: String,
+    /// Book ID (e.g. "PHB") or document ID
+    pub source_key: String,
+    /// Owning campaign for documents; None for books
+    pub campaign_id: Option<i32>,
+    pub title: String,
+    pub content_hash: String,
+    pub chunk_count: i32,
+    pub indexed_at: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = lore_sources)]
+pub struct NewLoreSource {
+    pub source_type: String,
+    pub source_key: String,
+    pub campaign_id: Option<i32>,
+    pub title: String,
+    pub content_hash: String,
+    pub chunk_count: i32,
+}
+
+impl NewLoreSource {
+    pub fn new(
+        source_type: LoreSourceType,
+        source_key: impl Into<String>,
+        campaign_id: Option<i32>,
+        title: impl Into<String>,
+        content_hash: impl Into<String>,
+    ) -> Self {
+        Self {
+            source_type: source_type.as_str().to_string(),
+            source_key: source_key.into(),
+            campaign_id,
+            title: title.into(),
+            content_hash: content_hash.into(),
+            chunk_count: 0,
+        }
+    }
+}
+
+/// A chunk of source text whose embedding is stored in `lore_vectors`
+#[derive(
+    Debug, Clone, Queryable, Selectable, Identifiable, Associations, Serialize, Deserialize,
+)]
+#[diesel(table_name = lore_chunks)]
+#[diesel(belongs_to(LoreSource, foreign_key = source_id))]
+pub struct LoreChunk {
+    pub id: i32,
+    pub source_id: i32,
+    pub chunk_index: i32,
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = lore_chunks)]
+pub struct NewLoreChunk {
+    pub source_id: i32,
+    pub chunk_index: i32,
+    pub heading: Option<String>,
+    pub content: String,
+}
+
+/// A chunk ready to be stored, with its embedding
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedChunk {
+    pub heading: Option<String>,
+    pub content: String,
+    pub embedding: Vec<f32>,
+}
+
+/// The embedding model the index was built with
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = lore_index_info)]
+pub struct LoreIndexInfo {
+    pub id: i32,
+    pub embedding_model: String,
+    pub dimensions: i32,
+    pub created_at: String,
+}
+
+/// Optional restrictions applied to a similarity search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoreSearchFilter {
+    /// Only return books and this campaign's documents
+    pub campaign_id: Option<i32>,
+    /// Only return chunks from this kind of source
+    pub source_type: Option<LoreSourceType>,
+}
+
+/// A chunk matched by a similarity search
+#[derive(Debug, Clone, QueryableByName, Serialize, Deserialize)]
+pub struct LoreSearchResult {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub chunk_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    pub source_id: i32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub source_type: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub source_key: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub title: String,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Integer>)]
+    pub campaign_id: Option<i32>,
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    pub heading: Option<String>,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub content: String,
+    /// Distance from the query vector; smaller is more similar
+    #[diesel(sql_type = diesel::sql_types::Double)]
+    pub distance: f64,
+}
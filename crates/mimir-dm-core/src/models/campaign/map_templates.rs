@@ -0,0 +1,198 @@
+//! Area of effect template database models for Visual Display System
+//!
+//! Templates are spell areas (cones, cubes, spheres, lines and cylinders)
+//! placed on a map so the DM and players can see who they cover.
+
+use crate::domain::measurement::AreaTemplate;
+use crate::domain::visibility::Point;
+use crate::schema::map_templates;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Shape of an area of effect
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemplateShape {
+    /// Widens from the origin; as wide as it is long at any distance
+    Cone,
+    /// Square with the origin at the middle of one side
+    Cube,
+    /// Circle of the given radius around the origin
+    #[default]
+    Sphere,
+    /// Rectangle of the given length and width starting at the origin
+    Line,
+    /// Circle of the given radius around the origin (seen from above)
+    Cylinder,
+}
+
+impl TemplateShape {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateShape::Cone => "cone",
+            TemplateShape::Cube => "cube",
+            TemplateShape::Sphere => "sphere",
+            TemplateShape::Line => "line",
+            TemplateShape::Cylinder => "cylinder",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "cone" => TemplateShape::Cone,
+            "cube" => TemplateShape::Cube,
+            "line" => TemplateShape::Line,
+            "cylinder" => TemplateShape::Cylinder,
+            _ => TemplateShape::Sphere,
+        }
+    }
+
+    /// Whether the template points in a direction
+    pub fn is_directional(&self) -> bool {
+        matches!(
+            self,
+            TemplateShape::Cone | TemplateShape::Cube | TemplateShape::Line
+        )
+    }
+}
+
+/// Database model for map templates
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = map_templates)]
+pub struct MapTemplate {
+    pub id: i32,
+    pub map_id: i32,
+    pub name: String,
+    pub shape: String,
+    /// Point of origin (pixel position)
+    pub x: f32,
+    pub y: f32,
+    /// Degrees clockwise from east
+    pub direction_deg: f32,
+    pub size_ft: f32,
+    pub width_ft: f32,
+    pub spell_name: Option<String>,
+    pub spell_source: Option<String>,
+    pub color: Option<String>,
+    pub visible_to_players: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl MapTemplate {
+    /// Get the shape enum
+    pub fn shape_enum(&self) -> TemplateShape {
+        TemplateShape::from_str(&self.shape)
+    }
+
+    /// The area this template covers
+    pub fn area(&self) -> AreaTemplate {
+        AreaTemplate {
+            shape: self.shape_enum(),
+            origin: Point::new(self.x, self.y),
+            direction_deg: self.direction_deg,
+            size_ft: self.size_ft,
+            width_ft: self.width_ft,
+        }
+    }
+}
+
+/// New map template for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = map_templates)]
+pub struct NewMapTemplate {
+    pub map_id: i32,
+    pub name: String,
+    pub shape: String,
+    pub x: f32,
+    pub y: f32,
+    pub direction_deg: f32,
+    pub size_ft: f32,
+    pub width_ft: f32,
+    pub spell_name: Option<String>,
+    pub spell_source: Option<String>,
+    pub color: Option<String>,
+    pub visible_to_players: bool,
+}
+
+impl NewMapTemplate {
+    /// Create a template with its origin at a position
+    pub fn new(
+        map_id: i32,
+        name: String,
+        shape: TemplateShape,
+        x: f32,
+        y: f32,
+        size_ft: f32,
+    ) -> Self {
+        Self {
+            map_id,
+            name,
+            shape: shape.as_str().to_string(),
+            x,
+            y,
+            direction_deg: 0.0,
+            size_ft,
+            width_ft: 5.0,
+            spell_name: None,
+            spell_source: None,
+            color: None,
+            visible_to_players: true,
+        }
+    }
+
+    pub fn with_direction(mut self, direction_deg: f32) -> Self {
+        self.direction_deg = direction_deg;
+        self
+    }
+
+    pub fn with_width(mut self, width_ft: f32) -> Self {
+        self.width_ft = width_ft;
+        self
+    }
+
+    pub fn with_spell(mut self, name: String, source: String) -> Self {
+        self.spell_name = Some(name);
+        self.spell_source = Some(source);
+        self
+    }
+
+    pub fn with_color(mut self, color: String) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_visibility(mut self, visible: bool) -> Self {
+        self.visible_to_players = visible;
+        self
+    }
+}
+
+/// Map template update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = map_templates)]
+pub struct UpdateMapTemplate {
+    pub name: Option<String>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub direction_deg: Option<f32>,
+    pub size_ft: Option<f32>,
+    pub width_ft: Option<f32>,
+    pub color: Option<Option<String>>,
+    pub visible_to_players: Option<bool>,
+    pub updated_at: Option<String>,
+}
+
+impl UpdateMapTemplate {
+    /// Create an update that moves and turns a template
+    pub fn placement(x: f32, y: f32, direction_deg: f32) -> Self {
+        Self {
+            x: Some(x),
+            y: Some(y),
+            direction_deg: Some(direction_deg),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+}
@@ -4,6 +4,9 @@ use crate::schema::maps;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// Grid size assumed for maps without one, matching the map viewers
+const DEFAULT_GRID_SIZE_PX: f32 = 70.0;
+
 /// Grid type for map overlay
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -109,6 +112,14 @@ impl Map {
     pub fn is_campaign_level(&self) -> bool {
         self.module_id.is_none()
     }
+
+    /// Grid square size in pixels, assuming 70px (as the map viewers do)
+    /// when the map has no grid size
+    pub fn grid_size_px_or_default(&self) -> f32 {
+        self.grid_size_px
+            .filter(|size| *size > 0)
+            .map_or(DEFAULT_GRID_SIZE_PX, |size| size as f32)
+    }
 }
 
 /// New map for insertion
@@ -4,28 +4,49 @@
 //! These represent the story management layer, not game mechanics.
 
 pub mod campaigns;
+pub mod combat;
 pub mod documents;
 pub mod fog;
 pub mod light_sources;
+pub mod lore;
+pub mod map_templates;
 pub mod maps;
 pub mod module_monsters;
 pub mod modules;
+pub mod sessions;
 pub mod template_documents;
 pub mod template_frontmatter;
 pub mod tokens;
+pub mod walls;
 pub mod workflow_cards;
 
 // Re-export commonly used types
-pub use campaigns::{Campaign, NewCampaign};
+pub use campaigns::{Campaign, DiagonalRule, NewCampaign};
+pub use combat::{
+    CombatCondition, CombatEncounter, CombatState, CombatStatus, Combatant, CombatantType,
+    DamageDefense, DamageDefenses, DefenseEntry, HpEvent, HpEventType, HpMode, NewCombatCondition,
+    NewCombatEncounter, NewCombatant, NewHpEvent, UpdateCombatEncounter, UpdateCombatant,
+};
 pub use documents::{Document, NewDocument};
-pub use fog::{FogRevealedArea, FogRevealedAreaSummary, NewFogRevealedArea};
+pub use fog::{
+    FogAreaShape, FogAreaSource, FogRevealedArea, FogRevealedAreaSummary, NewFogRevealedArea,
+};
 pub use light_sources::{LightSource, LightSourceSummary, LightType, NewLightSource, UpdateLightSource};
+pub use lore::{
+    EmbeddedChunk, LoreChunk, LoreIndexInfo, LoreSearchFilter, LoreSearchResult, LoreSource,
+    LoreSourceType, NewLoreChunk, NewLoreSource,
+};
+pub use map_templates::{MapTemplate, NewMapTemplate, TemplateShape, UpdateMapTemplate};
 pub use maps::{AmbientLight, GridType, Map, MapSummary, NewMap, UpdateMap};
 pub use module_monsters::{
     EncounterGroup, ModuleMonster, ModuleMonsterWithData, NewModuleMonster, UpdateModuleMonster,
 };
 pub use modules::{Module, NewModule};
+pub use sessions::{NewSession, Session, UpdateSession};
 pub use template_documents::TemplateDocument;
 pub use template_frontmatter::TemplateFrontmatter;
 pub use tokens::{NewToken, Token, TokenSize, TokenSummary, TokenType, UpdateToken, VisionType};
+pub use walls::{
+    DoorState, MapDoor, MapWall, NewMapDoor, NewMapWall, UpdateMapDoor, UpdateMapWall,
+};
 pub use workflow_cards::{NewWorkflowCard, WorkflowCard};
@@ -3,6 +3,7 @@
 //! Links monsters from the catalog to specific modules, with optional
 //! encounter grouping for organization during play.
 
+use crate::domain::encounter::EncounterDifficulty;
 use crate::schema::module_monsters;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -74,3 +75,10 @@ pub struct EncounterGroup {
     pub encounter_tag: Option<String>,
     pub monsters: Vec<ModuleMonsterWithData>,
 }
+
+/// Difficulty of one of a module's encounters against the campaign's party
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleEncounterDifficulty {
+    pub encounter_tag: Option<String>,
+    pub difficulty: EncounterDifficulty,
+}
@@ -0,0 +1,53 @@
+//! Session database models and operations
+
+use crate::schema::sessions;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Database model for game sessions
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Associations)]
+#[diesel(table_name = sessions)]
+#[diesel(belongs_to(crate::models::campaign::campaigns::Campaign))]
+pub struct Session {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub module_id: Option<i32>,
+    pub session_number: i32,
+    pub status: String,
+    pub scheduled_date: Option<String>,
+    pub prep_started_at: Option<String>,
+    pub prep_completed_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub created_at: String,
+}
+
+/// New session for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = sessions)]
+pub struct NewSession {
+    pub campaign_id: i32,
+    pub module_id: Option<i32>,
+    pub session_number: i32,
+    pub status: String,
+    pub scheduled_date: Option<String>,
+}
+
+/// Session update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = sessions)]
+pub struct UpdateSession {
+    pub status: Option<String>,
+    pub scheduled_date: Option<Option<String>>,
+    pub prep_started_at: Option<Option<String>>,
+    pub prep_completed_at: Option<Option<String>>,
+    pub completed_at: Option<Option<String>>,
+}
+
+impl Session {
+    /// Check if the session has been played
+    pub fn is_played(&self) -> bool {
+        self.status == "played"
+    }
+
+    // Transition validation is handled by BoardDefinition in the service layer
+}
@@ -0,0 +1,181 @@
+//! Wall and door database models for Vision and Lighting System
+//!
+//! Walls and doors are line segments on a map that block line of sight.
+//! Doors only block sight while closed or locked.
+
+use crate::schema::{map_doors, map_walls};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Door state - whether a door currently blocks sight and movement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoorState {
+    Open,
+    #[default]
+    Closed,
+    Locked,
+}
+
+impl DoorState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DoorState::Open => "open",
+            DoorState::Closed => "closed",
+            DoorState::Locked => "locked",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "open" => DoorState::Open,
+            "locked" => DoorState::Locked,
+            _ => DoorState::Closed,
+        }
+    }
+
+    /// Whether a door in this state blocks line of sight
+    pub fn blocks_sight(&self) -> bool {
+        !matches!(self, DoorState::Open)
+    }
+}
+
+/// Database model for wall segments
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = map_walls)]
+pub struct MapWall {
+    pub id: i32,
+    pub map_id: i32,
+    /// Start point (pixel position)
+    pub x1: f32,
+    pub y1: f32,
+    /// End point (pixel position)
+    pub x2: f32,
+    pub y2: f32,
+    pub created_at: String,
+}
+
+/// New wall segment for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = map_walls)]
+pub struct NewMapWall {
+    pub map_id: i32,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+impl NewMapWall {
+    /// Create a new wall segment between two points
+    pub fn new(map_id: i32, x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self {
+            map_id,
+            x1,
+            y1,
+            x2,
+            y2,
+        }
+    }
+}
+
+/// Wall update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = map_walls)]
+pub struct UpdateMapWall {
+    pub x1: Option<f32>,
+    pub y1: Option<f32>,
+    pub x2: Option<f32>,
+    pub y2: Option<f32>,
+}
+
+impl UpdateMapWall {
+    /// Create an update that moves both end points
+    pub fn endpoints(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self {
+            x1: Some(x1),
+            y1: Some(y1),
+            x2: Some(x2),
+            y2: Some(y2),
+        }
+    }
+}
+
+/// Database model for doors
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize, Identifiable)]
+#[diesel(table_name = map_doors)]
+pub struct MapDoor {
+    pub id: i32,
+    pub map_id: i32,
+    /// Start point (pixel position)
+    pub x1: f32,
+    pub y1: f32,
+    /// End point (pixel position)
+    pub x2: f32,
+    pub y2: f32,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl MapDoor {
+    /// Get the door state enum
+    pub fn state_enum(&self) -> DoorState {
+        DoorState::from_str(&self.state)
+    }
+}
+
+/// New door for insertion
+#[derive(Debug, Clone, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = map_doors)]
+pub struct NewMapDoor {
+    pub map_id: i32,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub state: String,
+}
+
+impl NewMapDoor {
+    /// Create a new closed door between two points
+    pub fn new(map_id: i32, x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self {
+            map_id,
+            x1,
+            y1,
+            x2,
+            y2,
+            state: DoorState::default().as_str().to_string(),
+        }
+    }
+
+    pub fn with_state(mut self, state: DoorState) -> Self {
+        self.state = state.as_str().to_string();
+        self
+    }
+}
+
+/// Door update structure
+#[derive(Debug, Clone, Default, AsChangeset, Serialize, Deserialize)]
+#[diesel(table_name = map_doors)]
+pub struct UpdateMapDoor {
+    pub x1: Option<f32>,
+    pub y1: Option<f32>,
+    pub x2: Option<f32>,
+    pub y2: Option<f32>,
+    pub state: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+impl UpdateMapDoor {
+    /// Create an update for just the door state
+    pub fn state(state: DoorState) -> Self {
+        Self {
+            state: Some(state.as_str().to_string()),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..Default::default()
+        }
+    }
+}
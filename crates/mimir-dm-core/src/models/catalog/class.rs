@@ -103,6 +103,9 @@ pub struct StartingEquipment {
     pub default: Option<Vec<String>>,
     #[serde(default)]
     pub gold_alternative: Option<String>,
+    /// Structured choice groups (`{"a": [...], "b": [...]}` or `{"_": [...]}`)
+    #[serde(default)]
+    pub default_data: Option<Vec<serde_json::Value>>,
 }
 
 /// Container for class data from JSON files
@@ -146,6 +149,8 @@ pub struct Subclass {
     #[serde(default)]
     pub additional_spells: Option<Vec<serde_json::Value>>,
     #[serde(default)]
+    pub optionalfeature_progression: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
     pub fluff: Option<SubclassFluff>,
     #[serde(default)]
     pub intro_description: Option<String>,
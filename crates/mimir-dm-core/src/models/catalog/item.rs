@@ -32,12 +32,35 @@ pub struct Item {
     pub range: Option<String>,
     #[serde(default)]
     pub reload: Option<u8>,
-    #[serde(default, deserialize_with = "deserialize_attunement")]
+    #[serde(default)]
+    pub weapon_category: Option<String>,
+    #[serde(default)]
+    pub bonus_ac: Option<String>,
+    #[serde(default)]
+    pub bonus_weapon: Option<String>,
+    #[serde(default)]
+    pub bonus_weapon_attack: Option<String>,
+    #[serde(default)]
+    pub bonus_weapon_damage: Option<String>,
+    #[serde(
+        default,
+        alias = "reqAttune",
+        deserialize_with = "deserialize_attunement"
+    )]
     pub requires_attunement: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_charges")]
+    pub charges: Option<u32>,
+    #[serde(default)]
+    pub recharge: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_recharge_amount")]
+    pub recharge_amount: Option<String>,
     #[serde(default)]
     pub entries: Option<Vec<serde_json::Value>>,
     #[serde(default, deserialize_with = "deserialize_srd")]
     pub srd: Option<String>,
+    /// Contents of an equipment pack, as 5etools item references
+    #[serde(default)]
+    pub pack_contents: Option<Vec<serde_json::Value>>,
 }
 
 /// Container for item data from JSON files
@@ -287,6 +310,48 @@ where
     }
 }
 
+/// Custom deserializer for charges that can be a number or a numeric string.
+/// Anything else (e.g. a dice expression) is treated as untracked.
+fn deserialize_charges<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde_json::Value;
+
+    let value = Option::<Value>::deserialize(deserializer)?;
+
+    Ok(match value {
+        Some(Value::Number(n)) => n.as_u64().map(|n| n as u32),
+        Some(Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+/// Custom deserializer for rechargeAmount that can be:
+/// - a number: a fixed amount of charges regained
+/// - a string: usually a dice tag such as "{@dice 1d6 + 1}"
+fn deserialize_recharge_amount<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde_json::Value;
+
+    let value = Option::<Value>::deserialize(deserializer)?;
+
+    Ok(match value {
+        Some(Value::Number(n)) => Some(n.to_string()),
+        Some(Value::String(s)) => {
+            let dice = s
+                .trim()
+                .trim_start_matches("{@dice")
+                .trim_end_matches('}')
+                .replace(' ', "");
+            Some(dice)
+        }
+        _ => None,
+    })
+}
+
 /// Custom deserializer for srd field that can be boolean or string
 /// - true (boolean): item is in SRD with same name
 /// - false (boolean): item is not in SRD  
@@ -405,6 +470,38 @@ mod tests {
         assert_eq!(attune_value.as_str().unwrap(), "by a spellcaster");
     }
 
+    #[test]
+    fn test_deserialize_magic_item_state() {
+        let json = r#"{
+            "name": "Wand of Web",
+            "source": "DMG",
+            "reqAttune": "by a spellcaster",
+            "charges": 7,
+            "recharge": "dawn",
+            "rechargeAmount": "{@dice 1d6 + 1}"
+        }"#;
+        let item: Item = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            item.requires_attunement.as_deref(),
+            Some("by a spellcaster")
+        );
+        assert_eq!(item.charges, Some(7));
+        assert_eq!(item.recharge.as_deref(), Some("dawn"));
+        assert_eq!(item.recharge_amount.as_deref(), Some("1d6+1"));
+
+        // Stored JSON round-trips through the serialized field names
+        let stored: Item = serde_json::from_str(&serde_json::to_string(&item).unwrap()).unwrap();
+        assert_eq!(stored.requires_attunement, item.requires_attunement);
+        assert_eq!(stored.recharge_amount, item.recharge_amount);
+
+        let fixed: Item =
+            serde_json::from_str(r#"{"name": "Staff", "source": "DMG", "rechargeAmount": 4}"#)
+                .unwrap();
+        assert_eq!(fixed.recharge_amount.as_deref(), Some("4"));
+        assert_eq!(fixed.charges, None);
+    }
+
     #[test]
     fn test_item_summary_conversion() {
         let item = Item {
@@ -422,11 +519,20 @@ mod tests {
             property: Some(vec!["V".to_string()]),
             range: None,
             reload: None,
+            weapon_category: None,
+            bonus_ac: None,
+            bonus_weapon: None,
+            bonus_weapon_attack: None,
+            bonus_weapon_damage: None,
             requires_attunement: None,
+            charges: None,
+            recharge: None,
+            recharge_amount: None,
             entries: Some(vec![serde_json::Value::String(
                 "A versatile weapon".to_string(),
             )]),
             srd: Some("true".to_string()),
+            pack_contents: None,
         };
 
         let summary = ItemSummary::from(&item);
@@ -459,9 +565,18 @@ mod tests {
             property: None,
             range: None,
             reload: None,
+            weapon_category: None,
+            bonus_ac: None,
+            bonus_weapon: None,
+            bonus_weapon_attack: None,
+            bonus_weapon_damage: None,
             requires_attunement: Some("true".to_string()),
+            charges: None,
+            recharge: None,
+            recharge_amount: None,
             entries: None,
             srd: Some("false".to_string()),
+            pack_contents: None,
         };
 
         let catalog_item = NewCatalogItem::from(&item);
@@ -19,6 +19,7 @@ pub mod optionalfeature;
 pub mod psionic;
 pub mod race;
 pub mod reward;
+pub mod search;
 pub mod spell;
 pub mod table;
 pub mod trap;
@@ -108,3 +109,5 @@ pub use psionic::{
     CatalogPsionic, ConcentrationDuration, NewCatalogPsionic, Psionic, PsionicCost, PsionicFilters,
     PsionicMode, PsionicSummary,
 };
+
+pub use search::{CatalogKind, CatalogSearchFilter, CatalogSearchHit};
@@ -92,7 +92,8 @@ impl From<&OptionalFeature> for OptionalFeatureSummary {
     }
 }
 
-fn format_feature_types(types: &[String]) -> String {
+/// Display names for 5etools optional feature type codes, comma separated
+pub fn format_feature_types(types: &[String]) -> String {
     let formatted: Vec<String> = types
         .iter()
         .map(|t| {
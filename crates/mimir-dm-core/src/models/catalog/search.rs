@@ -0,0 +1,180 @@
+//! Full-text catalog search models
+//!
+//! Every catalog table is mirrored into the `catalog_search` FTS5 table so
+//! that names and entry text can be searched across all kinds of content at
+//! once.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of catalog entry a search hit points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogKind {
+    Action,
+    Background,
+    Class,
+    Subclass,
+    ClassFeature,
+    SubclassFeature,
+    Condition,
+    Cult,
+    Deity,
+    Feat,
+    Item,
+    Language,
+    Monster,
+    Object,
+    OptionalFeature,
+    Psionic,
+    Race,
+    Reward,
+    Spell,
+    Table,
+    Trap,
+    VariantRule,
+    Vehicle,
+}
+
+impl CatalogKind {
+    /// Every catalog kind, in indexing order.
+    pub const ALL: [CatalogKind; 23] = [
+        CatalogKind::Action,
+        CatalogKind::Background,
+        CatalogKind::Class,
+        CatalogKind::Subclass,
+        CatalogKind::ClassFeature,
+        CatalogKind::SubclassFeature,
+        CatalogKind::Condition,
+        CatalogKind::Cult,
+        CatalogKind::Deity,
+        CatalogKind::Feat,
+        CatalogKind::Item,
+        CatalogKind::Language,
+        CatalogKind::Monster,
+        CatalogKind::Object,
+        CatalogKind::OptionalFeature,
+        CatalogKind::Psionic,
+        CatalogKind::Race,
+        CatalogKind::Reward,
+        CatalogKind::Spell,
+        CatalogKind::Table,
+        CatalogKind::Trap,
+        CatalogKind::VariantRule,
+        CatalogKind::Vehicle,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CatalogKind::Action => "action",
+            CatalogKind::Background => "background",
+            CatalogKind::Class => "class",
+            CatalogKind::Subclass => "subclass",
+            CatalogKind::ClassFeature => "class_feature",
+            CatalogKind::SubclassFeature => "subclass_feature",
+            CatalogKind::Condition => "condition",
+            CatalogKind::Cult => "cult",
+            CatalogKind::Deity => "deity",
+            CatalogKind::Feat => "feat",
+            CatalogKind::Item => "item",
+            CatalogKind::Language => "language",
+            CatalogKind::Monster => "monster",
+            CatalogKind::Object => "object",
+            CatalogKind::OptionalFeature => "optional_feature",
+            CatalogKind::Psionic => "psionic",
+            CatalogKind::Race => "race",
+            CatalogKind::Reward => "reward",
+            CatalogKind::Spell => "spell",
+            CatalogKind::Table => "table",
+            CatalogKind::Trap => "trap",
+            CatalogKind::VariantRule => "variant_rule",
+            CatalogKind::Vehicle => "vehicle",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.to_lowercase().replace(['-', ' '], "_");
+        Self::ALL.into_iter().find(|kind| kind.as_str() == s)
+    }
+
+    /// Catalog table holding entries of this kind
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            CatalogKind::Action => "catalog_actions",
+            CatalogKind::Background => "catalog_backgrounds",
+            CatalogKind::Class => "catalog_classes",
+            CatalogKind::Subclass => "catalog_subclasses",
+            CatalogKind::ClassFeature => "catalog_class_features",
+            CatalogKind::SubclassFeature => "catalog_subclass_features",
+            CatalogKind::Condition => "catalog_conditions",
+            CatalogKind::Cult => "catalog_cults",
+            CatalogKind::Deity => "catalog_deities",
+            CatalogKind::Feat => "catalog_feats",
+            CatalogKind::Item => "catalog_items",
+            CatalogKind::Language => "catalog_languages",
+            CatalogKind::Monster => "catalog_monsters",
+            CatalogKind::Object => "catalog_objects",
+            CatalogKind::OptionalFeature => "catalog_optional_features",
+            CatalogKind::Psionic => "catalog_psionics",
+            CatalogKind::Race => "catalog_races",
+            CatalogKind::Reward => "catalog_rewards",
+            CatalogKind::Spell => "catalog_spells",
+            CatalogKind::Table => "catalog_tables",
+            CatalogKind::Trap => "catalog_traps",
+            CatalogKind::VariantRule => "catalog_variant_rules",
+            CatalogKind::Vehicle => "catalog_vehicles",
+        }
+    }
+
+    /// Column holding the entry's full 5etools JSON
+    pub fn json_column(&self) -> &'static str {
+        match self {
+            CatalogKind::Action => "full_action_json",
+            CatalogKind::Background => "full_background_json",
+            CatalogKind::Class => "full_class_json",
+            CatalogKind::Subclass => "full_subclass_json",
+            CatalogKind::ClassFeature | CatalogKind::SubclassFeature => "full_feature_json",
+            CatalogKind::Condition => "full_condition_json",
+            CatalogKind::Cult => "full_cult_json",
+            CatalogKind::Deity => "full_deity_json",
+            CatalogKind::Feat => "full_feat_json",
+            CatalogKind::Item => "full_item_json",
+            CatalogKind::Language => "full_language_json",
+            CatalogKind::Monster => "full_monster_json",
+            CatalogKind::Object => "full_object_json",
+            CatalogKind::OptionalFeature => "full_optional_feature_json",
+            CatalogKind::Psionic => "full_psionic_json",
+            CatalogKind::Race => "full_race_json",
+            CatalogKind::Reward => "full_reward_json",
+            CatalogKind::Spell => "full_spell_json",
+            CatalogKind::Table => "full_table_json",
+            CatalogKind::Trap => "full_trap_json",
+            CatalogKind::VariantRule => "full_variant_rule_json",
+            CatalogKind::Vehicle => "full_vehicle_json",
+        }
+    }
+}
+
+/// Optional restrictions applied to a catalog search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CatalogSearchFilter {
+    /// Only return these kinds of entries; empty means all kinds
+    #[serde(default)]
+    pub kinds: Vec<CatalogKind>,
+    /// Only return entries from these sources; empty means all sources
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
+/// A catalog entry matched by a full-text search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogSearchHit {
+    pub kind: CatalogKind,
+    /// Row ID in the kind's catalog table
+    pub catalog_id: i32,
+    pub name: String,
+    pub source: String,
+    /// Matching excerpt of the entry text, with matches wrapped in `**`
+    pub snippet: String,
+    /// BM25 score; lower is a better match
+    pub rank: f64,
+}
@@ -104,6 +104,131 @@ fn categorize_table(name: &str) -> String {
     }
 }
 
+impl Table {
+    /// Get the dice expression in the roll column label (e.g. "d100" or "{@dice 2d6}").
+    ///
+    /// Returns `None` if the first column isn't a dice column.
+    pub fn roll_column_dice(&self) -> Option<String> {
+        let label = self.col_labels.as_ref()?.first()?;
+        let label = label
+            .trim()
+            .trim_start_matches("{@dice")
+            .trim_end_matches('}')
+            .trim();
+        let mut chars = label.chars();
+        let looks_like_dice = match chars.next()? {
+            'd' | 'D' => true,
+            c if c.is_ascii_digit() => label.contains(['d', 'D']),
+            _ => false,
+        };
+        if looks_like_dice {
+            Some(label.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Parse the roll range of every row from the first column.
+    ///
+    /// Returns `None` if any row doesn't have a recognisable range.
+    pub fn row_ranges(&self) -> Option<Vec<RollRange>> {
+        self.rows
+            .iter()
+            .map(|row| row.first().and_then(RollRange::from_cell))
+            .collect()
+    }
+}
+
+/// The range of die results that select a table row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl RollRange {
+    /// Parse a roll cell: "5", "01-05", "96–00" ("00" is 100), "11+",
+    /// or a 5etools cell object with `roll.exact` / `roll.min` and `roll.max`.
+    pub fn from_cell(cell: &serde_json::Value) -> Option<Self> {
+        if let Some(roll) = cell.get("roll") {
+            if let Some(exact) = roll.get("exact").and_then(|v| v.as_i64()) {
+                return Some(Self {
+                    min: exact as i32,
+                    max: exact as i32,
+                });
+            }
+            let min = roll.get("min").and_then(|v| v.as_i64())?;
+            let max = roll.get("max").and_then(|v| v.as_i64())?;
+            return Some(Self {
+                min: min as i32,
+                max: max as i32,
+            });
+        }
+
+        let text = cell.as_str()?.trim();
+        if let Some(min) = text.strip_suffix('+') {
+            return Some(Self {
+                min: parse_roll_number(min)?,
+                max: i32::MAX,
+            });
+        }
+
+        match text.split_once(['-', '–', '—']) {
+            Some((min, max)) => {
+                let min = parse_roll_number(min)?;
+                let mut max = parse_roll_number(max)?;
+                // "96-00" means 96 through 100
+                if max == 0 && min > 0 {
+                    max = 100;
+                }
+                Some(Self { min, max })
+            }
+            None => {
+                let value = parse_roll_number(text)?;
+                let value = if value == 0 && text.len() > 1 {
+                    100
+                } else {
+                    value
+                };
+                Some(Self {
+                    min: value,
+                    max: value,
+                })
+            }
+        }
+    }
+
+    /// Check if a roll falls within this range
+    pub fn contains(&self, roll: i32) -> bool {
+        roll >= self.min && roll <= self.max
+    }
+}
+
+fn parse_roll_number(text: &str) -> Option<i32> {
+    text.trim().parse().ok()
+}
+
+/// The outcome of rolling on a table, including any nested table rolls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableRollResult {
+    pub table_name: String,
+    pub source: String,
+    /// Dice rolled to pick the row (`None` if the row was picked uniformly)
+    pub dice: Option<String>,
+    /// The die total, or the 1-based row number for uniform picks
+    pub roll: i32,
+    /// Index of the selected row
+    pub row_index: usize,
+    /// Labels of the result columns (roll column excluded)
+    pub columns: Vec<String>,
+    /// Text of the selected row's result cells (roll column excluded)
+    pub result: Vec<String>,
+    /// Rolls made on tables referenced by the result
+    pub nested: Vec<TableRollResult>,
+    /// Table references that couldn't be found in the catalog
+    pub unresolved: Vec<String>,
+}
+
 // Container for JSON parsing
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TableData {
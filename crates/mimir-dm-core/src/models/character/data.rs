@@ -2,6 +2,8 @@
 //!
 //! These structures represent the complete character state stored in character_data column.
 
+use crate::domain::dice::DiceRoller;
+use crate::models::catalog::Item;
 use serde::{Deserialize, Serialize};
 
 /// Default humanoid walking speed in feet
@@ -106,6 +108,126 @@ impl SpellSlots {
     }
 }
 
+/// Warlock pact magic slots, which are all of one level and recover on a short rest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PactSlots {
+    /// Spell level every pact slot is cast at
+    pub slot_level: i32,
+    pub max: i32,
+    pub current: i32,
+}
+
+impl PactSlots {
+    pub fn new(slot_level: i32, max: i32) -> Self {
+        Self {
+            slot_level,
+            max,
+            current: max,
+        }
+    }
+
+    pub fn expend(&mut self, count: i32) -> bool {
+        if self.current >= count {
+            self.current -= count;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn recover_all(&mut self) {
+        self.current = self.max;
+    }
+}
+
+/// Kind of rest, which decides what a character recovers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestType {
+    /// Short rest - typically 1 hour
+    Short,
+    /// Long rest - typically 8 hours
+    Long,
+}
+
+/// Limited-use class feature such as Ki, Rage or Channel Divinity
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClassResource {
+    /// Resource name (e.g., "Ki Points", "Rage")
+    pub name: String,
+    /// Class that grants the resource
+    pub class_name: String,
+    pub max: i32,
+    pub current: i32,
+    /// Shortest rest that restores the resource; long rests restore everything
+    pub recovery: RestType,
+}
+
+impl ClassResource {
+    pub fn expend(&mut self, count: i32) -> bool {
+        if self.current >= count {
+            self.current -= count;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn recover(&mut self, count: i32) {
+        self.current = (self.current + count).min(self.max);
+    }
+
+    pub fn recover_all(&mut self) {
+        self.current = self.max;
+    }
+}
+
+/// Highest exhaustion level; a creature at this level dies
+pub const MAX_EXHAUSTION_LEVEL: i32 = 6;
+
+/// Death saving throws made while at 0 HP
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct DeathSaves {
+    #[serde(default)]
+    pub successes: i32,
+    #[serde(default)]
+    pub failures: i32,
+}
+
+impl DeathSaves {
+    /// Three successes leave the character stable
+    pub fn is_stable(&self) -> bool {
+        self.successes >= 3
+    }
+
+    /// Three failures mean the character has died
+    pub fn is_dead(&self) -> bool {
+        self.failures >= 3
+    }
+
+    pub fn reset(&mut self) {
+        self.successes = 0;
+        self.failures = 0;
+    }
+}
+
+/// What happened when damage was applied to a character
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DamageOutcome {
+    /// Damage soaked up by temporary HP
+    pub temp_hp_absorbed: i32,
+    /// Damage taken from current HP
+    pub hp_lost: i32,
+    /// The damage took the character from above 0 HP down to 0
+    pub dropped_to_zero: bool,
+    /// Massive damage or a third failed death save killed the character
+    pub killed: bool,
+    /// DC of the Constitution save needed to keep concentrating, if any
+    pub concentration_save_dc: Option<i32>,
+    /// Spell whose concentration ended because the character fell unconscious
+    pub concentration_ended: Option<String>,
+}
+
 /// Reference to a spell with source information for unambiguous lookup
 #[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
 pub struct SpellReference {
@@ -311,6 +433,22 @@ impl FeatureReference {
     }
 }
 
+/// An optional class feature chosen from `catalog_optional_features`
+/// (Eldritch Invocations, Fighting Styles, Metamagic, Maneuvers, ...)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OptionalFeatureChoice {
+    /// Feature name as it appears in the source
+    pub name: String,
+    /// Source book code (e.g., "PHB", "TCE")
+    pub source: String,
+    /// 5etools feature type code the choice fills (e.g., "EI", "FS:F", "MV:B")
+    pub feature_type: String,
+    /// Class that granted the choice
+    pub class_name: String,
+    /// Class level at which the choice was made
+    pub level: i32,
+}
+
 /// Spell data for spellcasting characters
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct SpellData {
@@ -322,6 +460,9 @@ pub struct SpellData {
     pub cantrips: Vec<SpellReference>,
     #[serde(default)]
     pub spell_slots: std::collections::HashMap<i32, SpellSlots>,
+    /// Warlock pact magic, kept apart from multiclass spellcasting slots
+    #[serde(default)]
+    pub pact_slots: Option<PactSlots>,
 }
 
 /// Currency tracking for D&D denominations
@@ -339,6 +480,113 @@ pub struct Currency {
     pub platinum: i32,
 }
 
+/// Maximum number of magic items a character can be attuned to at once
+pub const MAX_ATTUNED_ITEMS: usize = 3;
+
+/// When a magic item regains expended charges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemRecharge {
+    Dawn,
+    Dusk,
+    Midnight,
+    ShortRest,
+    LongRest,
+    /// Recharges under conditions described in the item text; never automatic
+    Special,
+}
+
+impl ItemRecharge {
+    /// Parse the catalog `recharge` value ("dawn", "restLong", ...)
+    pub fn from_catalog(value: &str) -> Option<Self> {
+        match value {
+            "dawn" => Some(Self::Dawn),
+            "dusk" => Some(Self::Dusk),
+            "midnight" => Some(Self::Midnight),
+            "restShort" => Some(Self::ShortRest),
+            "restLong" => Some(Self::LongRest),
+            "special" => Some(Self::Special),
+            _ => None,
+        }
+    }
+
+    /// Whether this recharge happens during the given rest
+    ///
+    /// A long rest spans a night, so dawn, dusk and midnight recharges are
+    /// applied on long rests as well.
+    pub fn applies_to(&self, rest_type: RestType) -> bool {
+        match self {
+            Self::ShortRest => true,
+            Self::LongRest | Self::Dawn | Self::Dusk | Self::Midnight => {
+                rest_type == RestType::Long
+            }
+            Self::Special => false,
+        }
+    }
+}
+
+/// Charges on a magic item such as a wand or staff
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemCharges {
+    pub current: i32,
+    pub max: i32,
+    #[serde(default)]
+    pub recharge: Option<ItemRecharge>,
+    /// Dice expression for charges regained (e.g. "1d6+1"); `None` regains all
+    #[serde(default)]
+    pub recharge_amount: Option<String>,
+}
+
+impl ItemCharges {
+    /// Build charge tracking from a catalog item, starting fully charged
+    pub fn from_catalog(item: &Item) -> Option<Self> {
+        let max = item.charges? as i32;
+        Some(Self {
+            current: max,
+            max,
+            recharge: item
+                .recharge
+                .as_deref()
+                .and_then(ItemRecharge::from_catalog),
+            recharge_amount: item.recharge_amount.clone(),
+        })
+    }
+
+    pub fn expend(&mut self, count: i32) -> bool {
+        if self.current >= count {
+            self.current -= count;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn recover(&mut self, count: i32) {
+        self.current = (self.current + count).min(self.max);
+    }
+
+    /// Regain charges for a rest, rolling the recharge amount if there is one
+    ///
+    /// Returns the number of charges regained.
+    pub fn recharge(&mut self, rest_type: RestType, roller: &mut DiceRoller) -> i32 {
+        if !self.recharge.is_some_and(|r| r.applies_to(rest_type)) {
+            return 0;
+        }
+
+        let before = self.current;
+        let rolled = self
+            .recharge_amount
+            .as_deref()
+            .and_then(|amount| roller.roll(amount).ok())
+            .map(|roll| roll.total);
+        match rolled {
+            Some(amount) => self.recover(amount.max(0)),
+            None => self.current = self.max,
+        }
+        self.current - before
+    }
+}
+
 /// Inventory item
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct InventoryItem {
@@ -351,6 +599,13 @@ pub struct InventoryItem {
     #[serde(default)]
     pub value: f64,
     pub notes: Option<String>,
+    /// The item's catalog entry requires attunement (`reqAttune`)
+    #[serde(default)]
+    pub requires_attunement: bool,
+    #[serde(default)]
+    pub attuned: bool,
+    #[serde(default)]
+    pub charges: Option<ItemCharges>,
 }
 
 /// Equipped items in specific slots
@@ -425,6 +680,19 @@ pub struct CharacterData {
     pub max_hp: i32,
     pub current_hp: i32,
 
+    // Combat State
+    #[serde(default)]
+    pub temp_hp: i32,
+    #[serde(default)]
+    pub death_saves: DeathSaves,
+    #[serde(default)]
+    pub exhaustion_level: i32,
+    #[serde(default)]
+    pub conditions: Vec<String>,
+    /// Spell the character is currently concentrating on
+    #[serde(default)]
+    pub concentration: Option<String>,
+
     // Movement
     #[serde(default = "default_speed")]
     pub speed: i32,
@@ -436,10 +704,18 @@ pub struct CharacterData {
     #[serde(default)]
     pub class_features: Vec<FeatureReference>,
 
+    // Class Resources
+    #[serde(default)]
+    pub class_resources: Vec<ClassResource>,
+
     // Feats
     #[serde(default)]
     pub feats: Vec<String>,
 
+    // Optional class features (invocations, fighting styles, metamagic, maneuvers)
+    #[serde(default)]
+    pub optional_features: Vec<OptionalFeatureChoice>,
+
     // Spells
     #[serde(default)]
     pub spells: SpellData,
@@ -542,6 +818,218 @@ impl CharacterData {
     pub fn has_class(&self, class_name: &str) -> bool {
         self.classes.iter().any(|c| c.class_name == class_name)
     }
+
+    /// Find a class resource by name (case-insensitive)
+    pub fn get_resource_mut(&mut self, name: &str) -> Option<&mut ClassResource> {
+        self.class_resources
+            .iter_mut()
+            .find(|r| r.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Spend a spell slot of the given level
+    ///
+    /// Regular slots are used first, then pact magic slots of that level.
+    /// Returns false if no slot of that level is left.
+    pub fn expend_spell_slot(&mut self, spell_level: i32) -> bool {
+        if let Some(slots) = self.spells.spell_slots.get_mut(&spell_level) {
+            if slots.expend(1) {
+                return true;
+            }
+        }
+        match &mut self.spells.pact_slots {
+            Some(pact) if pact.slot_level == spell_level => pact.expend(1),
+            _ => false,
+        }
+    }
+
+    /// Check if the character has any slots (spent or not) of a spell level
+    pub fn has_spell_slot_level(&self, spell_level: i32) -> bool {
+        self.spells.spell_slots.contains_key(&spell_level)
+            || self
+                .spells
+                .pact_slots
+                .as_ref()
+                .is_some_and(|pact| pact.slot_level == spell_level)
+    }
+
+    /// Restore spell slots and class resources for a rest
+    ///
+    /// A short rest restores pact magic slots and short-rest resources; a long
+    /// rest restores every slot and resource, ends temporary HP and
+    /// concentration, resets death saves and removes one level of exhaustion.
+    pub fn recover_resources(&mut self, rest_type: RestType) {
+        if let Some(pact) = &mut self.spells.pact_slots {
+            pact.recover_all();
+        }
+
+        match rest_type {
+            RestType::Short => {
+                for resource in &mut self.class_resources {
+                    if resource.recovery == RestType::Short {
+                        resource.recover_all();
+                    }
+                }
+            }
+            RestType::Long => {
+                for slots in self.spells.spell_slots.values_mut() {
+                    slots.recover_all();
+                }
+                for resource in &mut self.class_resources {
+                    resource.recover_all();
+                }
+                self.temp_hp = 0;
+                self.concentration = None;
+                self.death_saves.reset();
+                self.exhaustion_level = (self.exhaustion_level - 1).max(0);
+            }
+        }
+
+        let mut roller = DiceRoller::new();
+        for item in &mut self.inventory {
+            if let Some(charges) = &mut item.charges {
+                charges.recharge(rest_type, &mut roller);
+            }
+        }
+    }
+
+    /// Number of items the character is currently attuned to
+    pub fn attuned_count(&self) -> usize {
+        self.inventory.iter().filter(|item| item.attuned).count()
+    }
+
+    /// Find an inventory item by name (case-insensitive)
+    pub fn get_item_mut(&mut self, name: &str) -> Option<&mut InventoryItem> {
+        self.inventory
+            .iter_mut()
+            .find(|item| item.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Check if the character has a condition (case-insensitive)
+    pub fn has_condition(&self, condition: &str) -> bool {
+        self.conditions
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(condition))
+    }
+
+    /// Add a condition, returning false if it was already present
+    pub fn add_condition(&mut self, condition: &str) -> bool {
+        if self.has_condition(condition) {
+            return false;
+        }
+        self.conditions.push(condition.to_string());
+        true
+    }
+
+    /// Remove a condition, returning false if it wasn't present
+    pub fn remove_condition(&mut self, condition: &str) -> bool {
+        let before = self.conditions.len();
+        self.conditions
+            .retain(|c| !c.eq_ignore_ascii_case(condition));
+        self.conditions.len() != before
+    }
+
+    /// Grant temporary HP
+    ///
+    /// Temporary HP doesn't stack; the character keeps whichever amount is
+    /// higher. Returns false if the existing temporary HP was kept.
+    pub fn grant_temp_hp(&mut self, amount: i32) -> bool {
+        if amount <= self.temp_hp {
+            return false;
+        }
+        self.temp_hp = amount;
+        true
+    }
+
+    /// Set the exhaustion level, clamped to 0-6
+    pub fn set_exhaustion(&mut self, level: i32) {
+        self.exhaustion_level = level.clamp(0, MAX_EXHAUSTION_LEVEL);
+    }
+
+    /// Apply damage, taking it from temporary HP first
+    ///
+    /// Damage at 0 HP counts as a failed death save, and damage that reduces
+    /// the character to 0 HP with at least max HP left over kills outright.
+    /// A concentrating character must make a Constitution save (DC 10 or half
+    /// the damage, whichever is higher), unless they fall unconscious, which
+    /// ends concentration.
+    pub fn take_damage(&mut self, amount: i32) -> DamageOutcome {
+        let mut outcome = DamageOutcome::default();
+        if amount <= 0 {
+            return outcome;
+        }
+
+        outcome.temp_hp_absorbed = amount.min(self.temp_hp);
+        self.temp_hp -= outcome.temp_hp_absorbed;
+        let remaining = amount - outcome.temp_hp_absorbed;
+
+        if remaining > 0 {
+            let was_at_zero = self.current_hp == 0;
+            outcome.hp_lost = remaining.min(self.current_hp);
+            self.current_hp -= outcome.hp_lost;
+            let overflow = remaining - outcome.hp_lost;
+
+            if overflow >= self.max_hp {
+                outcome.killed = true;
+            } else if was_at_zero {
+                // A stable character who takes damage is dying again
+                if self.death_saves.is_stable() {
+                    self.death_saves.successes = 0;
+                }
+                self.death_saves.failures += 1;
+                outcome.killed = self.death_saves.is_dead();
+            } else if self.current_hp == 0 {
+                outcome.dropped_to_zero = true;
+                self.death_saves.reset();
+            }
+
+            if outcome.killed {
+                self.death_saves.failures = 3;
+            }
+            if self.current_hp == 0 {
+                self.add_condition("Unconscious");
+            }
+        }
+
+        if self.current_hp == 0 {
+            outcome.concentration_ended = self.concentration.take();
+        } else if self.concentration.is_some() {
+            outcome.concentration_save_dc = Some((amount / 2).max(10));
+        }
+
+        outcome
+    }
+
+    /// Restore HP up to max HP
+    ///
+    /// Healing a character at 0 HP brings them back to consciousness and
+    /// resets their death saves.
+    pub fn heal(&mut self, amount: i32) {
+        if amount <= 0 || self.death_saves.is_dead() {
+            return;
+        }
+        if self.current_hp == 0 {
+            self.death_saves.reset();
+            self.remove_condition("Unconscious");
+        }
+        self.current_hp = (self.current_hp + amount).min(self.max_hp);
+    }
+
+    /// Record a death saving throw from its d20 roll
+    ///
+    /// A 1 counts as two failures, 10 or higher is a success, and a 20
+    /// restores 1 HP. Ignored unless the character is dying: at 0 HP and
+    /// neither stable nor dead.
+    pub fn record_death_save(&mut self, roll: i32) {
+        if self.current_hp > 0 || self.death_saves.is_stable() || self.death_saves.is_dead() {
+            return;
+        }
+        match roll {
+            20 => self.heal(1),
+            10..=19 => self.death_saves.successes = (self.death_saves.successes + 1).min(3),
+            1 => self.death_saves.failures = (self.death_saves.failures + 2).min(3),
+            _ => self.death_saves.failures = (self.death_saves.failures + 1).min(3),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -608,10 +1096,17 @@ mod tests {
             },
             max_hp: 12,
             current_hp: 12,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             speed: 30,
             proficiencies: Proficiencies::default(),
             class_features: Vec::new(),
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: Vec::new(),
             currency: Currency::default(),
@@ -664,6 +1159,245 @@ mod tests {
         assert_eq!(slots.current, 4);
     }
 
+    /// Warlock/Cleric multiclass with regular and pact slots and two resources
+    fn create_test_caster() -> CharacterData {
+        let mut spells = SpellData::default();
+        spells.spell_slots.insert(1, SpellSlots::new(2));
+        spells.pact_slots = Some(PactSlots::new(2, 2));
+
+        CharacterData {
+            character_name: "Test".to_string(),
+            player_id: None,
+            level: 4,
+            experience_points: 0,
+            version: 1,
+            snapshot_reason: None,
+            created_at: "2025-01-01".to_string(),
+            race: "Human".to_string(),
+            subrace: None,
+            classes: Vec::new(),
+            background: "Acolyte".to_string(),
+            alignment: None,
+            abilities: AbilityScores {
+                strength: 10,
+                dexterity: 10,
+                constitution: 10,
+                intelligence: 10,
+                wisdom: 14,
+                charisma: 16,
+            },
+            max_hp: 24,
+            current_hp: 24,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
+            speed: 30,
+            proficiencies: Proficiencies::default(),
+            class_features: Vec::new(),
+            class_resources: vec![
+                ClassResource {
+                    name: "Channel Divinity".to_string(),
+                    class_name: "Cleric".to_string(),
+                    max: 1,
+                    current: 1,
+                    recovery: RestType::Short,
+                },
+                ClassResource {
+                    name: "Divine Sense".to_string(),
+                    class_name: "Paladin".to_string(),
+                    max: 4,
+                    current: 4,
+                    recovery: RestType::Long,
+                },
+            ],
+            feats: Vec::new(),
+            optional_features: Vec::new(),
+            spells,
+            inventory: Vec::new(),
+            currency: Currency::default(),
+            equipped: EquippedItems::default(),
+            personality: Personality::default(),
+            npc_role: None,
+            npc_location: None,
+            npc_faction: None,
+            npc_notes: None,
+            legendary_actions: Vec::new(),
+            legendary_action_count: None,
+        }
+    }
+
+    #[test]
+    fn test_short_rest_recovers_pact_slots_and_short_resources() {
+        let mut character = create_test_caster();
+
+        // Regular slots are spent before pact slots of the same level
+        assert!(character.expend_spell_slot(1));
+        assert!(character.expend_spell_slot(1));
+        assert!(!character.expend_spell_slot(1));
+        assert!(character.expend_spell_slot(2));
+        assert!(character.expend_spell_slot(2));
+        assert!(!character.expend_spell_slot(2));
+        assert!(character.has_spell_slot_level(2));
+        assert!(!character.has_spell_slot_level(3));
+
+        assert!(character
+            .get_resource_mut("channel divinity")
+            .unwrap()
+            .expend(1));
+        assert!(character
+            .get_resource_mut("Divine Sense")
+            .unwrap()
+            .expend(2));
+
+        character.recover_resources(RestType::Short);
+        assert_eq!(character.spells.spell_slots[&1].current, 0);
+        assert_eq!(character.spells.pact_slots.as_ref().unwrap().current, 2);
+        assert_eq!(character.class_resources[0].current, 1);
+        assert_eq!(character.class_resources[1].current, 2);
+
+        character.recover_resources(RestType::Long);
+        assert_eq!(character.spells.spell_slots[&1].current, 2);
+        assert_eq!(character.class_resources[1].current, 4);
+    }
+
+    #[test]
+    fn test_damage_uses_temp_hp_and_prompts_concentration() {
+        let mut character = create_test_caster();
+        character.concentration = Some("Bless".to_string());
+
+        assert!(character.grant_temp_hp(5));
+        assert!(!character.grant_temp_hp(3)); // temp HP doesn't stack
+
+        let outcome = character.take_damage(8);
+        assert_eq!(outcome.temp_hp_absorbed, 5);
+        assert_eq!(outcome.hp_lost, 3);
+        assert_eq!(character.temp_hp, 0);
+        assert_eq!(character.current_hp, 21);
+        assert_eq!(outcome.concentration_save_dc, Some(10));
+
+        let outcome = character.take_damage(30);
+        assert!(outcome.dropped_to_zero);
+        assert!(!outcome.killed);
+        assert_eq!(outcome.concentration_save_dc, None);
+        assert_eq!(outcome.concentration_ended.as_deref(), Some("Bless"));
+        assert!(character.concentration.is_none());
+        assert!(character.has_condition("unconscious"));
+    }
+
+    #[test]
+    fn test_death_saves_and_healing() {
+        let mut character = create_test_caster();
+        character.take_damage(24);
+        assert_eq!(character.current_hp, 0);
+
+        character.record_death_save(12);
+        character.record_death_save(1);
+        assert_eq!(character.death_saves.successes, 1);
+        assert_eq!(character.death_saves.failures, 2);
+
+        // Damage while dying is another failure
+        let outcome = character.take_damage(3);
+        assert!(outcome.killed);
+        assert!(character.death_saves.is_dead());
+
+        // Healing brings a dying character back and clears their saves
+        let mut character = create_test_caster();
+        character.take_damage(24);
+        character.record_death_save(5);
+        character.heal(6);
+        assert_eq!(character.current_hp, 6);
+        assert_eq!(character.death_saves, DeathSaves::default());
+        assert!(!character.has_condition("Unconscious"));
+
+        // A natural 20 restores 1 HP
+        character.take_damage(6);
+        character.record_death_save(20);
+        assert_eq!(character.current_hp, 1);
+
+        // Massive damage kills outright
+        let mut character = create_test_caster();
+        assert!(character.take_damage(48).killed);
+    }
+
+    #[test]
+    fn test_damage_while_stable_resumes_death_saves() {
+        let mut character = create_test_caster();
+        character.take_damage(24);
+        for _ in 0..3 {
+            character.record_death_save(15);
+        }
+        assert!(character.death_saves.is_stable());
+
+        let outcome = character.take_damage(2);
+        assert!(!outcome.killed);
+        assert!(!character.death_saves.is_stable());
+        assert_eq!(character.death_saves.successes, 0);
+        assert_eq!(character.death_saves.failures, 1);
+
+        // Saves count again now that the character is dying
+        character.record_death_save(12);
+        assert_eq!(character.death_saves.successes, 1);
+    }
+
+    #[test]
+    fn test_long_rest_clears_combat_state() {
+        let mut character = create_test_caster();
+        character.temp_hp = 4;
+        character.concentration = Some("Hex".to_string());
+        character.set_exhaustion(8);
+        assert_eq!(character.exhaustion_level, MAX_EXHAUSTION_LEVEL);
+        character.add_condition("Poisoned");
+
+        character.recover_resources(RestType::Short);
+        assert_eq!(character.temp_hp, 4);
+        assert_eq!(character.exhaustion_level, 6);
+
+        character.recover_resources(RestType::Long);
+        assert_eq!(character.temp_hp, 0);
+        assert!(character.concentration.is_none());
+        assert_eq!(character.exhaustion_level, 5);
+        assert!(character.has_condition("Poisoned"));
+        assert!(character.remove_condition("poisoned"));
+        assert!(!character.remove_condition("Poisoned"));
+    }
+
+    #[test]
+    fn test_item_charges_recharge_by_rest_type() {
+        let mut roller = DiceRoller::seeded(7);
+        let mut wand = ItemCharges {
+            current: 0,
+            max: 7,
+            recharge: Some(ItemRecharge::Dawn),
+            recharge_amount: Some("1d6+1".to_string()),
+        };
+
+        assert_eq!(wand.recharge(RestType::Short, &mut roller), 0);
+        let regained = wand.recharge(RestType::Long, &mut roller);
+        assert!((2..=7).contains(&regained));
+        assert_eq!(wand.current, regained);
+
+        // Without a recharge amount every charge comes back
+        let mut boots = ItemCharges {
+            current: 1,
+            max: 3,
+            recharge: Some(ItemRecharge::ShortRest),
+            recharge_amount: None,
+        };
+        assert_eq!(boots.recharge(RestType::Short, &mut roller), 2);
+        assert_eq!(boots.current, 3);
+
+        let mut relic = ItemCharges {
+            current: 0,
+            max: 1,
+            recharge: Some(ItemRecharge::Special),
+            recharge_amount: None,
+        };
+        assert_eq!(relic.recharge(RestType::Long, &mut roller), 0);
+        assert!(!relic.expend(1));
+    }
+
     #[test]
     fn test_yaml_serialization() {
         let character = CharacterData {
@@ -695,6 +1429,11 @@ mod tests {
             },
             max_hp: 28,
             current_hp: 28,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             speed: 25, // Dwarf speed
             proficiencies: Proficiencies {
                 skills: vec!["Athletics".to_string(), "Intimidation".to_string()],
@@ -720,7 +1459,9 @@ mod tests {
                     level: 1,
                 },
             ],
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: vec![InventoryItem {
                 name: "Rations".to_string(),
@@ -729,6 +1470,9 @@ mod tests {
                 weight: 20.0,
                 value: 5.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             }],
             currency: Currency::default(),
             equipped: EquippedItems {
@@ -10,9 +10,10 @@ use serde::{Deserialize, Serialize};
 
 // Re-export commonly used data types
 pub use data::{
-    AbilityScores, CharacterData, ClassLevel, Currency, EquippedItems, FeatureReference,
-    InventoryItem, LegendaryAction, Personality, Proficiencies, SpellData, SpellReference,
-    SpellSlots,
+    AbilityScores, CharacterData, ClassLevel, ClassResource, Currency, DamageOutcome, DeathSaves,
+    EquippedItems, FeatureReference, InventoryItem, ItemCharges, ItemRecharge, LegendaryAction,
+    OptionalFeatureChoice, PactSlots, Personality, Proficiencies, RestType, SpellData,
+    SpellReference, SpellSlots,
 };
 
 /// Database model for characters (metadata only)
@@ -0,0 +1,94 @@
+//! Chat models
+//!
+//! Mutating tool calls made by the assistant are journaled together with the
+//! state needed to revert them, so a chat session can undo its last actions.
+
+use crate::schema::tool_action_journal;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How to put things back the way they were before a tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RevertAction {
+    /// Restore a character to the version it had before the call
+    RestoreCharacterVersion {
+        character_id: i32,
+        version_number: i32,
+    },
+    /// Delete a character the call created
+    DeleteCharacter { character_id: i32 },
+    /// Delete a module the call created
+    DeleteModule { module_id: i32 },
+    /// Put a module back in its previous stage
+    RestoreModuleStatus {
+        module_id: i32,
+        status: String,
+        started_at: Option<String>,
+        completed_at: Option<String>,
+    },
+    /// Write back the previous contents of a file, or remove it if it did not exist
+    RestoreFile {
+        path: String,
+        previous_contents: Option<String>,
+    },
+}
+
+/// A journaled tool call
+#[derive(Debug, Clone, Queryable, Selectable, Identifiable, Serialize, Deserialize)]
+#[diesel(table_name = tool_action_journal)]
+pub struct ToolAction {
+    pub id: i32,
+    /// Chat session the tool was called from
+    pub session_id: String,
+    pub tool_name: String,
+    /// Tool arguments as JSON
+    pub arguments: String,
+    /// Human-readable description of the action
+    pub summary: String,
+    /// JSON-encoded [`RevertAction`]
+    pub revert_action: String,
+    pub created_at: String,
+    /// When the action was reverted, if it has been
+    pub undone_at: Option<String>,
+}
+
+impl ToolAction {
+    /// Decode the stored revert action
+    pub fn revert(&self) -> Result<RevertAction, serde_json::Error> {
+        serde_json::from_str(&self.revert_action)
+    }
+
+    /// Whether the action has already been reverted
+    pub fn is_undone(&self) -> bool {
+        self.undone_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = tool_action_journal)]
+pub struct NewToolAction {
+    pub session_id: String,
+    pub tool_name: String,
+    pub arguments: String,
+    pub summary: String,
+    pub revert_action: String,
+}
+
+impl NewToolAction {
+    pub fn new(
+        session_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        arguments: &serde_json::Value,
+        summary: impl Into<String>,
+        revert: &RevertAction,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            session_id: session_id.into(),
+            tool_name: tool_name.into(),
+            arguments: serde_json::to_string(arguments)?,
+            summary: summary.into(),
+            revert_action: serde_json::to_string(revert)?,
+        })
+    }
+}
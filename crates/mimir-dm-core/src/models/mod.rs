@@ -5,6 +5,7 @@
 //! - `campaign`: Campaign management and story organization
 //! - `player`: Player management and campaign associations
 //! - `character`: Character data and version tracking
+//! - `chat`: Journal of assistant tool actions for undo
 
 // Data models have many fields - documenting each would be verbose and redundant.
 // Field names are chosen to be self-documenting.
@@ -13,4 +14,5 @@
 pub mod campaign;
 pub mod catalog;
 pub mod character;
+pub mod chat;
 pub mod player;
@@ -26,6 +26,7 @@ diesel::table! {
         first_session_date -> Nullable<Text>,
         last_activity_at -> Text,
         archived_at -> Nullable<Text>,
+        diagonal_rule -> Text,
     }
 }
 
@@ -113,6 +114,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    lore_sources (id) {
+        id -> Integer,
+        source_type -> Text,
+        source_key -> Text,
+        campaign_id -> Nullable<Integer>,
+        title -> Text,
+        content_hash -> Text,
+        chunk_count -> Integer,
+        indexed_at -> Text,
+    }
+}
+
+diesel::table! {
+    lore_chunks (id) {
+        id -> Integer,
+        source_id -> Integer,
+        chunk_index -> Integer,
+        heading -> Nullable<Text>,
+        content -> Text,
+    }
+}
+
+diesel::table! {
+    lore_index_info (id) {
+        id -> Integer,
+        embedding_model -> Text,
+        dimensions -> Integer,
+        created_at -> Text,
+    }
+}
+
 diesel::table! {
     documents (id) {
         id -> Integer,
@@ -149,6 +182,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    campaign_sources (id) {
+        id -> Integer,
+        campaign_id -> Integer,
+        source -> Text,
+        created_at -> Text,
+    }
+}
+
 diesel::table! {
     characters (id) {
         id -> Integer,
@@ -575,6 +617,10 @@ diesel::table! {
         width -> Float,
         height -> Float,
         created_at -> Text,
+        shape -> Text,
+        points -> Nullable<Text>,
+        clip_points -> Nullable<Text>,
+        source -> Text,
     }
 }
 
@@ -596,6 +642,135 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    map_walls (id) {
+        id -> Integer,
+        map_id -> Integer,
+        x1 -> Float,
+        y1 -> Float,
+        x2 -> Float,
+        y2 -> Float,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    map_templates (id) {
+        id -> Integer,
+        map_id -> Integer,
+        name -> Text,
+        shape -> Text,
+        x -> Float,
+        y -> Float,
+        direction_deg -> Float,
+        size_ft -> Float,
+        width_ft -> Float,
+        spell_name -> Nullable<Text>,
+        spell_source -> Nullable<Text>,
+        color -> Nullable<Text>,
+        visible_to_players -> Bool,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    map_doors (id) {
+        id -> Integer,
+        map_id -> Integer,
+        x1 -> Float,
+        y1 -> Float,
+        x2 -> Float,
+        y2 -> Float,
+        state -> Text,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    combat_encounters (id) {
+        id -> Integer,
+        campaign_id -> Integer,
+        module_id -> Nullable<Integer>,
+        map_id -> Nullable<Integer>,
+        name -> Text,
+        encounter_tag -> Nullable<Text>,
+        status -> Text,
+        round -> Integer,
+        current_combatant_id -> Nullable<Integer>,
+        created_at -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    combat_combatants (id) {
+        id -> Integer,
+        encounter_id -> Integer,
+        name -> Text,
+        combatant_type -> Text,
+        monster_name -> Nullable<Text>,
+        monster_source -> Nullable<Text>,
+        character_id -> Nullable<Integer>,
+        token_id -> Nullable<Integer>,
+        initiative -> Integer,
+        initiative_modifier -> Integer,
+        current_hp -> Nullable<Integer>,
+        max_hp -> Nullable<Integer>,
+        armor_class -> Nullable<Integer>,
+        is_active -> Bool,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+        updated_at -> Text,
+        temp_hp -> Integer,
+    }
+}
+
+diesel::table! {
+    combat_conditions (id) {
+        id -> Integer,
+        combatant_id -> Integer,
+        condition_name -> Text,
+        condition_source -> Text,
+        applied_round -> Integer,
+        duration_rounds -> Nullable<Integer>,
+        notes -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    combat_hp_events (id) {
+        id -> Integer,
+        combatant_id -> Integer,
+        round -> Integer,
+        event_type -> Text,
+        amount -> Integer,
+        damage_type -> Nullable<Text>,
+        applied_amount -> Integer,
+        defense -> Nullable<Text>,
+        hp_before -> Nullable<Integer>,
+        hp_after -> Nullable<Integer>,
+        temp_hp_before -> Integer,
+        temp_hp_after -> Integer,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    tool_action_journal (id) {
+        id -> Integer,
+        session_id -> Text,
+        tool_name -> Text,
+        arguments -> Text,
+        summary -> Text,
+        revert_action -> Text,
+        created_at -> Text,
+        undone_at -> Nullable<Text>,
+    }
+}
+
 diesel::joinable!(maps -> campaigns (campaign_id));
 diesel::joinable!(modules -> campaigns (campaign_id));
 diesel::joinable!(module_monsters -> modules (module_id));
@@ -604,18 +779,32 @@ diesel::joinable!(tokens -> catalog_monsters (monster_id));
 diesel::joinable!(tokens -> characters (character_id));
 diesel::joinable!(fog_revealed_areas -> maps (map_id));
 diesel::joinable!(light_sources -> maps (map_id));
+diesel::joinable!(map_walls -> maps (map_id));
+diesel::joinable!(map_doors -> maps (map_id));
+diesel::joinable!(map_templates -> maps (map_id));
 diesel::joinable!(light_sources -> tokens (token_id));
+diesel::joinable!(combat_encounters -> campaigns (campaign_id));
+diesel::joinable!(combat_encounters -> modules (module_id));
+diesel::joinable!(combat_encounters -> maps (map_id));
+diesel::joinable!(combat_combatants -> combat_encounters (encounter_id));
+diesel::joinable!(combat_combatants -> characters (character_id));
+diesel::joinable!(combat_combatants -> tokens (token_id));
+diesel::joinable!(combat_conditions -> combat_combatants (combatant_id));
+diesel::joinable!(combat_hp_events -> combat_combatants (combatant_id));
 diesel::joinable!(sessions -> campaigns (campaign_id));
 diesel::joinable!(sessions -> modules (module_id));
 diesel::joinable!(workflow_cards -> campaigns (campaign_id));
 diesel::joinable!(workflow_cards -> modules (module_id));
 diesel::joinable!(workflow_cards -> sessions (session_id));
 diesel::joinable!(workflow_card_tags -> workflow_cards (card_id));
+diesel::joinable!(lore_sources -> campaigns (campaign_id));
+diesel::joinable!(lore_chunks -> lore_sources (source_id));
 diesel::joinable!(documents -> campaigns (campaign_id));
 diesel::joinable!(documents -> modules (module_id));
 diesel::joinable!(documents -> sessions (session_id));
 diesel::joinable!(campaign_players -> campaigns (campaign_id));
 diesel::joinable!(campaign_players -> players (player_id));
+diesel::joinable!(campaign_sources -> campaigns (campaign_id));
 diesel::joinable!(characters -> campaigns (campaign_id));
 diesel::joinable!(characters -> players (player_id));
 diesel::joinable!(character_versions -> characters (character_id));
@@ -623,6 +812,7 @@ diesel::joinable!(character_versions -> characters (character_id));
 diesel::allow_tables_to_appear_in_same_query!(
     campaigns,
     campaign_players,
+    campaign_sources,
     maps,
     modules,
     module_monsters,
@@ -630,13 +820,24 @@ diesel::allow_tables_to_appear_in_same_query!(
     tokens,
     fog_revealed_areas,
     light_sources,
+    map_walls,
+    map_doors,
+    map_templates,
+    combat_encounters,
+    combat_combatants,
+    combat_conditions,
+    combat_hp_events,
     workflow_cards,
     workflow_card_tags,
     template_documents,
     documents,
+    lore_sources,
+    lore_chunks,
+    lore_index_info,
     players,
     characters,
     character_versions,
+    tool_action_journal,
     catalog_actions,
     catalog_backgrounds,
     catalog_classes,
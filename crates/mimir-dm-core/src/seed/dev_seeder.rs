@@ -9,8 +9,9 @@ use crate::dal::campaign::campaigns::CampaignRepository;
 use crate::error::Result;
 use crate::models::character::CharacterData;
 use crate::models::character::{
-    AbilityScores, ClassLevel, Currency, EquippedItems, FeatureReference, InventoryItem,
-    LegendaryAction, Personality, Proficiencies, SpellData, SpellReference, SpellSlots,
+    AbilityScores, ClassLevel, Currency, DeathSaves, EquippedItems, FeatureReference,
+    InventoryItem, LegendaryAction, Personality, Proficiencies, SpellData, SpellReference,
+    SpellSlots,
 };
 use crate::services::{
     CampaignService, CampaignSummaryService, CharacterService, DocumentService,
@@ -683,7 +684,9 @@ fn seed_session_notes(
     campaign_id: i32,
     campaign_directory: &str,
 ) -> Result<()> {
+    use crate::dal::campaign::sessions::SessionRepository;
     use crate::models::campaign::documents::NewDocument;
+    use crate::models::campaign::sessions::{NewSession, UpdateSession};
     use std::path::PathBuf;
 
     let session_notes = vec![
@@ -828,13 +831,30 @@ Full health, well-rested after a night at Stonehill Inn.
         // Write the file
         std::fs::write(&full_path, content)?;
 
+        // Create a played session record for the notes to belong to
+        let session = {
+            let mut session_repo = SessionRepository::new(conn);
+            let session = session_repo.create(NewSession {
+                campaign_id,
+                module_id: None,
+                session_number,
+                status: "played".to_string(),
+                scheduled_date: None,
+            })?;
+            session_repo.update(
+                session.id,
+                UpdateSession {
+                    completed_at: Some(Some(Utc::now().to_rfc3339())),
+                    ..Default::default()
+                },
+            )?
+        };
+
         // Create document record
-        // Note: session_id is None because we don't have actual session records,
-        // just session notes documents. The document_type identifies these as session notes.
         let new_doc = NewDocument {
             campaign_id,
             module_id: None,
-            session_id: None,
+            session_id: Some(session.id),
             template_id: format!("session_notes_{}", session_number),
             document_type: "session_notes".to_string(),
             title: title.to_string(),
@@ -1045,6 +1065,11 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
         },
         max_hp: 49,
         current_hp: 49,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 25,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1071,7 +1096,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
             FeatureReference::with_subclass("Improved Critical", "Fighter", "Champion", "PHB", 3),
             FeatureReference::new("Extra Attack", "Fighter", "PHB", 5),
         ],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![
             InventoryItem {
@@ -1081,6 +1108,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 55.0,
                 value: 75.0,
                 notes: Some("AC 16".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Battleaxe".to_string(),
@@ -1089,6 +1119,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 4.0,
                 value: 10.0,
                 notes: Some("1d8 slashing, versatile (1d10)".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Shield".to_string(),
@@ -1097,6 +1130,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 6.0,
                 value: 10.0,
                 notes: Some("+2 AC".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Handaxe".to_string(),
@@ -1105,6 +1141,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 2.0,
                 value: 5.0,
                 notes: Some("1d6 slashing, light, thrown".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Explorer's Pack".to_string(),
@@ -1113,6 +1152,9 @@ fn create_thorin(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 59.0,
                 value: 10.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
@@ -1179,6 +1221,11 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
         },
         max_hp: 27,
         current_hp: 27,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1213,7 +1260,9 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
             FeatureReference::with_subclass("Evocation Savant", "Wizard", "Evocation", "PHB", 2),
             FeatureReference::with_subclass("Sculpt Spells", "Wizard", "Evocation", "PHB", 2),
         ],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData {
             cantrips: vec![
                 SpellReference::new("Fire Bolt", "PHB"),
@@ -1242,6 +1291,7 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
                 SpellReference::new("Counterspell", "PHB"),
             ],
             spell_slots,
+            pact_slots: None,
         },
         inventory: vec![
             InventoryItem {
@@ -1251,6 +1301,9 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 4.0,
                 value: 0.2,
                 notes: Some("Arcane focus".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Spellbook".to_string(),
@@ -1259,6 +1312,9 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 3.0,
                 value: 50.0,
                 notes: Some("Contains all known spells".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Component Pouch".to_string(),
@@ -1267,6 +1323,9 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 2.0,
                 value: 25.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Scholar's Pack".to_string(),
@@ -1275,6 +1334,9 @@ fn create_elara(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 10.0,
                 value: 40.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
@@ -1336,6 +1398,11 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
         },
         max_hp: 9,
         current_hp: 9,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 25,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1365,7 +1432,9 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
             FeatureReference::new("Sneak Attack", "Rogue", "PHB", 1),
             FeatureReference::new("Thieves' Cant", "Rogue", "PHB", 1),
         ],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![
             InventoryItem {
@@ -1375,6 +1444,9 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 10.0,
                 value: 10.0,
                 notes: Some("AC 11 + Dex".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Shortsword".to_string(),
@@ -1383,6 +1455,9 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 2.0,
                 value: 10.0,
                 notes: Some("1d6 piercing, finesse, light".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Thieves' Tools".to_string(),
@@ -1391,6 +1466,9 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 1.0,
                 value: 25.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Burglar's Pack".to_string(),
@@ -1399,6 +1477,9 @@ fn create_finn(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 44.0,
                 value: 16.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
@@ -1475,6 +1556,11 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
         },
         max_hp: 73,
         current_hp: 73,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1507,7 +1593,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
             FeatureReference::new("Destroy Undead", "Cleric", "PHB", 5),
             FeatureReference::new("Divine Intervention", "Cleric", "PHB", 10),
         ],
+        class_resources: vec![],
         feats: vec!["War Caster".to_string()],
+        optional_features: vec![],
         spells: SpellData {
             cantrips: vec![
                 SpellReference::new("Guidance", "PHB"),
@@ -1554,6 +1642,7 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 SpellReference::new("Holy Weapon", "XGE"),
             ],
             spell_slots,
+            pact_slots: None,
         },
         inventory: vec![
             InventoryItem {
@@ -1563,6 +1652,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 65.0,
                 value: 1500.0,
                 notes: Some("AC 18".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Shield".to_string(),
@@ -1571,6 +1663,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 6.0,
                 value: 10.0,
                 notes: Some("+2 AC, holy symbol emblazoned".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Mace".to_string(),
@@ -1579,6 +1674,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 4.0,
                 value: 5.0,
                 notes: Some("1d6 bludgeoning".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Holy Symbol".to_string(),
@@ -1587,6 +1685,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 0.0,
                 value: 5.0,
                 notes: Some("Amulet of Lathander".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Priest's Pack".to_string(),
@@ -1595,6 +1696,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 24.0,
                 value: 19.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Diamond".to_string(),
@@ -1603,6 +1707,9 @@ fn create_helena(player_id: i32, created_at: &str) -> CharacterData {
                 weight: 0.0,
                 value: 300.0,
                 notes: Some("For Revivify spell component".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
@@ -1664,6 +1771,11 @@ fn create_sildar(created_at: &str) -> CharacterData {
         },
         max_hp: 42,
         current_hp: 42,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1683,7 +1795,9 @@ fn create_sildar(created_at: &str) -> CharacterData {
             FeatureReference::new("Action Surge", "Fighter", "PHB", 2),
             FeatureReference::new("Extra Attack", "Fighter", "PHB", 5),
         ],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![
             InventoryItem {
@@ -1693,6 +1807,9 @@ fn create_sildar(created_at: &str) -> CharacterData {
                 weight: 3.0,
                 value: 15.0,
                 notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Chain Mail".to_string(),
@@ -1701,6 +1818,9 @@ fn create_sildar(created_at: &str) -> CharacterData {
                 weight: 55.0,
                 value: 75.0,
                 notes: Some("AC 16".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
@@ -1762,6 +1882,11 @@ fn create_gundren(created_at: &str) -> CharacterData {
         },
         max_hp: 22,
         current_hp: 22,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 25,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1776,7 +1901,9 @@ fn create_gundren(created_at: &str) -> CharacterData {
             languages: vec!["Common".to_string(), "Dwarvish".to_string(), "Gnomish".to_string()],
         },
         class_features: vec![],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![],
         currency: Currency {
@@ -1833,6 +1960,11 @@ fn create_toblen(created_at: &str) -> CharacterData {
         },
         max_hp: 8,
         current_hp: 8,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec!["Insight".to_string(), "Persuasion".to_string()],
@@ -1843,7 +1975,9 @@ fn create_toblen(created_at: &str) -> CharacterData {
             languages: vec!["Common".to_string()],
         },
         class_features: vec![],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![],
         currency: Currency {
@@ -1905,6 +2039,11 @@ fn create_iarno(created_at: &str) -> CharacterData {
         },
         max_hp: 22,
         current_hp: 22,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![
@@ -1940,7 +2079,9 @@ fn create_iarno(created_at: &str) -> CharacterData {
                 2,
             ),
         ],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData {
             cantrips: vec![
                 SpellReference::new("Fire Bolt", "PHB"),
@@ -1958,6 +2099,7 @@ fn create_iarno(created_at: &str) -> CharacterData {
                 SpellReference::new("Suggestion", "PHB"),
             ],
             spell_slots,
+            pact_slots: None,
         },
         inventory: vec![
             InventoryItem {
@@ -1970,6 +2112,9 @@ fn create_iarno(created_at: &str) -> CharacterData {
                     "Glass staff that can cast Shield (1 charge) and Mage Armor (2 charges)"
                         .to_string(),
                 ),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
             InventoryItem {
                 name: "Spellbook".to_string(),
@@ -1978,6 +2123,9 @@ fn create_iarno(created_at: &str) -> CharacterData {
                 weight: 3.0,
                 value: 50.0,
                 notes: Some("Contains all prepared spells plus Detect Magic, Identify, Sleep, Scorching Ray".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             },
         ],
         currency: Currency {
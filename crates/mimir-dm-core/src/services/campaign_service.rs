@@ -12,7 +12,7 @@ use crate::{
     dal::campaign::template_documents::TemplateRepository,
     domain::{BoardCompletionStatus, BoardRegistry},
     error::{DbError, Result},
-    models::campaign::campaigns::{Campaign, NewCampaign},
+    models::campaign::campaigns::{Campaign, DiagonalRule, NewCampaign, UpdateCampaign},
     models::campaign::documents::NewDocument,
 };
 use std::fs;
@@ -170,6 +170,71 @@ impl<'a> CampaignService<'a> {
         repo.find_by_id(campaign_id)
     }
 
+    /// Get the source books enabled for a campaign.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Enabled source abbreviations; empty means every source is allowed
+    pub fn get_enabled_sources(&mut self, campaign_id: i32) -> Result<Vec<String>> {
+        let mut repo = CampaignRepository::new(self.conn);
+        repo.list_sources(campaign_id)
+    }
+
+    /// Set the source books enabled for a campaign.
+    ///
+    /// Catalog lookups made for the campaign (such as encounter generation)
+    /// only use these sources. Pass an empty list to allow every source.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    /// * `sources` - Source book abbreviations (e.g., "MM", "VGM")
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The enabled sources after the update
+    /// * `Err(DbError::NotFound)` - If the campaign doesn't exist
+    pub fn set_enabled_sources(
+        &mut self,
+        campaign_id: i32,
+        sources: &[String],
+    ) -> Result<Vec<String>> {
+        let mut repo = CampaignRepository::new(self.conn);
+        if repo.find_by_id(campaign_id)?.is_none() {
+            return Err(DbError::NotFound {
+                entity_type: "Campaign".to_string(),
+                id: campaign_id.to_string(),
+            });
+        }
+        repo.set_sources(campaign_id, sources)
+    }
+
+    /// Set how grid distances count diagonals on the campaign's maps.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    /// * `rule` - 5/10/5 alternating diagonals or straight-line distance
+    ///
+    /// # Returns
+    /// * `Ok(Campaign)` - The updated campaign
+    /// * `Err(DbError::NotFound)` - If the campaign doesn't exist
+    pub fn set_diagonal_rule(&mut self, campaign_id: i32, rule: DiagonalRule) -> Result<Campaign> {
+        let mut repo = CampaignRepository::new(self.conn);
+        if repo.find_by_id(campaign_id)?.is_none() {
+            return Err(DbError::NotFound {
+                entity_type: "Campaign".to_string(),
+                id: campaign_id.to_string(),
+            });
+        }
+        repo.update(
+            campaign_id,
+            UpdateCampaign {
+                diagonal_rule: Some(rule.as_str().to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
     /// Archive a campaign.
     ///
     /// Sets the archived_at timestamp to mark the campaign as archived.
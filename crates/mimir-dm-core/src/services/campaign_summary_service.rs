@@ -1,12 +1,13 @@
 //! Campaign Summary Service
 //!
 //! Manages cached AI-generated summaries of campaign story progress.
-//! Summaries are built from session notes and module information.
+//! Summaries are built from session records, session notes and module information.
 
 use crate::connection::DbConnection;
 use crate::dal::campaign::documents::DocumentRepository;
 use crate::error::{DbError, Result};
-use crate::services::{CampaignService, ModuleService};
+use crate::services::{CampaignService, ModuleService, SessionService};
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
@@ -32,6 +33,8 @@ pub struct SummarySourceMaterial {
     pub campaign_name: String,
     /// Module summaries (name + status)
     pub modules: Vec<ModuleSummaryInfo>,
+    /// Sessions on the session board, in play order
+    pub sessions: Vec<SessionSummaryInfo>,
     /// Session notes content (title + content)
     pub session_notes: Vec<SessionNoteInfo>,
 }
@@ -47,6 +50,21 @@ pub struct ModuleSummaryInfo {
     pub module_number: i32,
 }
 
+/// Session record information for summary generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummaryInfo {
+    /// Session number in play order
+    pub session_number: i32,
+    /// Name of the module the session belongs to, if any
+    pub module_name: Option<String>,
+    /// Current board stage (planned, prepping, ready, played)
+    pub status: String,
+    /// Scheduled date (YYYY-MM-DD) if set
+    pub scheduled_date: Option<String>,
+    /// ISO8601 timestamp when the session was played
+    pub completed_at: Option<String>,
+}
+
 /// Session note information for summary generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionNoteInfo {
@@ -159,35 +177,66 @@ impl<'a> CampaignSummaryService<'a> {
         };
 
         // Get modules
-        let modules = {
+        let campaign_modules = {
             let mut service = ModuleService::new(self.conn);
             service
                 .list_campaign_modules(campaign_id)
                 .unwrap_or_default()
-                .into_iter()
-                .map(|m| ModuleSummaryInfo {
-                    name: m.name,
-                    status: m.status,
-                    module_number: m.module_number,
-                })
-                .collect()
         };
+        let module_names: HashMap<i32, String> = campaign_modules
+            .iter()
+            .map(|m| (m.id, m.name.clone()))
+            .collect();
+        let modules = campaign_modules
+            .into_iter()
+            .map(|m| ModuleSummaryInfo {
+                name: m.name,
+                status: m.status,
+                module_number: m.module_number,
+            })
+            .collect();
+
+        // Get session records
+        let session_records = {
+            let mut service = SessionService::new(self.conn);
+            service.list_campaign_sessions(campaign_id)?
+        };
+        let session_numbers: HashMap<i32, i32> = session_records
+            .iter()
+            .map(|s| (s.id, s.session_number))
+            .collect();
+        let sessions = session_records
+            .into_iter()
+            .map(|s| SessionSummaryInfo {
+                session_number: s.session_number,
+                module_name: s.module_id.and_then(|id| module_names.get(&id).cloned()),
+                status: s.status,
+                scheduled_date: s.scheduled_date,
+                completed_at: s.completed_at,
+            })
+            .collect();
 
         // Get session notes documents
-        let session_notes = self.gather_session_notes(campaign_id, campaign_dir)?;
+        let session_notes =
+            self.gather_session_notes(campaign_id, campaign_dir, &session_numbers)?;
 
         Ok(SummarySourceMaterial {
             campaign_name,
             modules,
+            sessions,
             session_notes,
         })
     }
 
     /// Gather session notes from documents
+    ///
+    /// `session_numbers` maps session IDs to session numbers so notes tied to
+    /// a session record are ordered by when they were played.
     fn gather_session_notes(
         &mut self,
         campaign_id: i32,
         campaign_dir: &str,
+        session_numbers: &HashMap<i32, i32>,
     ) -> Result<Vec<SessionNoteInfo>> {
         // Find documents that are session notes
         let documents = DocumentRepository::find_by_campaign(self.conn, campaign_id)?;
@@ -210,7 +259,9 @@ impl<'a> CampaignSummaryService<'a> {
                 if let Ok(content) = fs::read_to_string(&file_path) {
                     notes.push(SessionNoteInfo {
                         title: doc.title,
-                        session_number: doc.session_id, // session_id maps to session number
+                        session_number: doc
+                            .session_id
+                            .and_then(|id| session_numbers.get(&id).copied()),
                         content,
                     });
                 }
@@ -263,6 +314,26 @@ pub fn format_source_for_llm(source: &SummarySourceMaterial) -> String {
         prompt.push('\n');
     }
 
+    // Add session log
+    let played: Vec<_> = source
+        .sessions
+        .iter()
+        .filter(|s| s.status == "played")
+        .collect();
+    if !played.is_empty() {
+        prompt.push_str("## Sessions Played:\n");
+        for s in played {
+            match &s.module_name {
+                Some(module) => prompt.push_str(&format!(
+                    "- Session {} ({})\n",
+                    s.session_number, module
+                )),
+                None => prompt.push_str(&format!("- Session {}\n", s.session_number)),
+            }
+        }
+        prompt.push('\n');
+    }
+
     // Add session notes
     if !source.session_notes.is_empty() {
         prompt.push_str("## Session Notes:\n\n");
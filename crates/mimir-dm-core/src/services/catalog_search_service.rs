@@ -0,0 +1,454 @@
+//! Full-text search across every catalog table.
+//!
+//! Catalog services only match on names, so phrases buried in a monster's
+//! traits or a spell's description can't be found through them. This service
+//! mirrors each catalog entry's name and flattened entry text into the
+//! `catalog_search` FTS5 table and runs ranked queries over it.
+
+use crate::connection::DbConnection;
+use crate::error::{DbError, Result};
+use crate::models::catalog::{CatalogKind, CatalogSearchFilter, CatalogSearchHit};
+use crate::text::strip_tags;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Integer, Text};
+use serde_json::Value;
+use tracing::info;
+
+/// Largest number of hits returned by one search
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+/// BM25 column weights: catalog_type, catalog_id, source, name, content.
+/// Name matches count for much more than matches in the body text.
+const RANK_WEIGHTS: &str = "0.0, 0.0, 0.0, 10.0, 1.0";
+
+/// JSON keys that carry bookkeeping rather than readable text
+///
+/// "type" is mostly entry structure ("entries", "list") or item codes; a
+/// monster's creature type is indexed separately by `collect_creature_type`.
+const SKIPPED_KEYS: &[&str] = &[
+    "source",
+    "page",
+    "type",
+    "style",
+    "srd",
+    "basicRules",
+    "otherSources",
+    "additionalSources",
+    "reprintedAs",
+    "hasFluff",
+    "hasFluffImages",
+];
+
+#[derive(QueryableByName)]
+struct CatalogRow {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Text)]
+    source: String,
+    #[diesel(sql_type = Text)]
+    data: String,
+}
+
+#[derive(QueryableByName)]
+struct HitRow {
+    #[diesel(sql_type = Text)]
+    catalog_type: String,
+    #[diesel(sql_type = Integer)]
+    catalog_id: i32,
+    #[diesel(sql_type = Text)]
+    name: String,
+    #[diesel(sql_type = Text)]
+    source: String,
+    #[diesel(sql_type = Text)]
+    snippet: String,
+    #[diesel(sql_type = Double)]
+    rank: f64,
+}
+
+#[derive(QueryableByName)]
+struct CountRow {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+/// Service for maintaining and querying the catalog full-text index
+pub struct CatalogSearchService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> CatalogSearchService<'a> {
+    /// Create a new catalog search service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Re-index every catalog entry from one source.
+    ///
+    /// Called after a book's catalogs are imported. Existing index rows for
+    /// the source are replaced.
+    ///
+    /// # Arguments
+    /// * `source` - Source code the entries were imported under (the book ID)
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of entries indexed
+    pub fn index_source(&mut self, source: &str) -> Result<usize> {
+        let indexed = self.conn.transaction::<_, DbError, _>(|conn| {
+            delete_rows(conn, Some(source))?;
+            index_rows(conn, Some(source))
+        })?;
+        info!("Indexed {} catalog entries from source {}", indexed, source);
+        Ok(indexed)
+    }
+
+    /// Remove every index row from one source.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of entries removed
+    pub fn remove_source(&mut self, source: &str) -> Result<usize> {
+        delete_rows(self.conn, Some(source))
+    }
+
+    /// Drop the whole index and rebuild it from the catalog tables.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of entries indexed
+    pub fn rebuild(&mut self) -> Result<usize> {
+        let indexed = self.conn.transaction::<_, DbError, _>(|conn| {
+            delete_rows(conn, None)?;
+            index_rows(conn, None)
+        })?;
+        info!("Rebuilt catalog search index with {} entries", indexed);
+        Ok(indexed)
+    }
+
+    /// Number of entries in the index.
+    pub fn indexed_count(&mut self) -> Result<i64> {
+        let row: CountRow = diesel::sql_query("SELECT COUNT(*) AS count FROM catalog_search")
+            .get_result(self.conn)?;
+        Ok(row.count)
+    }
+
+    /// Build the index if it is empty but catalog content exists.
+    ///
+    /// Books imported before the index existed are picked up this way.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the index was rebuilt
+    pub fn ensure_index(&mut self) -> Result<bool> {
+        if self.indexed_count()? > 0 {
+            return Ok(false);
+        }
+
+        let catalog_rows = CatalogKind::ALL
+            .iter()
+            .map(|kind| format!("SELECT 1 FROM {}", kind.table_name()))
+            .collect::<Vec<_>>()
+            .join(" UNION ALL ");
+        let row: CountRow = diesel::sql_query(format!(
+            "SELECT COUNT(*) AS count FROM ({} LIMIT 1)",
+            catalog_rows
+        ))
+        .get_result(self.conn)?;
+        if row.count == 0 {
+            return Ok(false);
+        }
+
+        self.rebuild()?;
+        Ok(true)
+    }
+
+    /// Search names and entry text across all catalogs.
+    ///
+    /// Words in the query must all appear in an entry; double-quoted parts
+    /// match as phrases and the last word also matches as a prefix.
+    ///
+    /// # Arguments
+    /// * `query` - Free text typed by the user
+    /// * `filter` - Optional kind and source restrictions
+    /// * `limit` - Maximum number of hits to return
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CatalogSearchHit>)` - Hits ordered from best to worst match
+    pub fn search(
+        &mut self,
+        query: &str,
+        filter: &CatalogSearchFilter,
+        limit: i64,
+    ) -> Result<Vec<CatalogSearchHit>> {
+        let Some(match_query) = build_match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut sql = format!(
+            "SELECT catalog_type, CAST(catalog_id AS INTEGER) AS catalog_id, name, source,
+                    snippet(catalog_search, 4, '**', '**', '…', 24) AS snippet,
+                    bm25(catalog_search, {}) AS rank
+             FROM catalog_search
+             WHERE catalog_search MATCH ?",
+            RANK_WEIGHTS
+        );
+        if !filter.kinds.is_empty() {
+            sql.push_str(&format!(
+                " AND catalog_type IN ({})",
+                placeholders(filter.kinds.len())
+            ));
+        }
+        if !filter.sources.is_empty() {
+            sql.push_str(&format!(
+                " AND source IN ({})",
+                placeholders(filter.sources.len())
+            ));
+        }
+        sql.push_str(" ORDER BY rank LIMIT ?");
+
+        let mut statement = diesel::sql_query(sql)
+            .into_boxed()
+            .bind::<Text, _>(match_query);
+        for kind in &filter.kinds {
+            statement = statement.bind::<Text, _>(kind.as_str());
+        }
+        for source in &filter.sources {
+            statement = statement.bind::<Text, _>(source.clone());
+        }
+        let rows: Vec<HitRow> = statement
+            .bind::<BigInt, _>(limit.clamp(1, MAX_SEARCH_LIMIT))
+            .load(self.conn)?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(CatalogSearchHit {
+                    kind: CatalogKind::parse(&row.catalog_type)?,
+                    catalog_id: row.catalog_id,
+                    name: row.name,
+                    source: row.source,
+                    snippet: row.snippet,
+                    rank: row.rank,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Delete index rows, either for one source or all of them.
+fn delete_rows(conn: &mut DbConnection, source: Option<&str>) -> Result<usize> {
+    let deleted = match source {
+        Some(source) => diesel::sql_query("DELETE FROM catalog_search WHERE source = ?")
+            .bind::<Text, _>(source)
+            .execute(conn)?,
+        None => diesel::sql_query("DELETE FROM catalog_search").execute(conn)?,
+    };
+    Ok(deleted)
+}
+
+/// Copy catalog entries into the index, either for one source or all of them.
+fn index_rows(conn: &mut DbConnection, source: Option<&str>) -> Result<usize> {
+    let mut indexed = 0;
+
+    for kind in CatalogKind::ALL {
+        let sql = format!(
+            "SELECT id, name, source, {} AS data FROM {}",
+            kind.json_column(),
+            kind.table_name()
+        );
+        let rows: Vec<CatalogRow> = match source {
+            Some(source) => diesel::sql_query(format!("{} WHERE source = ?", sql))
+                .bind::<Text, _>(source)
+                .load(conn)?,
+            None => diesel::sql_query(sql).load(conn)?,
+        };
+
+        for row in rows {
+            diesel::sql_query(
+                "INSERT INTO catalog_search(catalog_type, catalog_id, source, name, content)
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind::<Text, _>(kind.as_str())
+            .bind::<Integer, _>(row.id)
+            .bind::<Text, _>(&row.source)
+            .bind::<Text, _>(&row.name)
+            .bind::<Text, _>(searchable_text(kind, &row.data))
+            .execute(conn)?;
+            indexed += 1;
+        }
+    }
+
+    Ok(indexed)
+}
+
+/// Flatten an entry's JSON into plain text for indexing.
+///
+/// The top-level name is indexed in its own column, so it's left out here.
+/// Nested names (trait and action titles) are kept, as is a monster's
+/// creature type.
+fn searchable_text(kind: CatalogKind, json: &str) -> String {
+    let Ok(value) = serde_json::from_str::<Value>(json) else {
+        return strip_tags(json);
+    };
+
+    let mut parts = Vec::new();
+    match &value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                if key == "type" && kind == CatalogKind::Monster {
+                    collect_creature_type(child, &mut parts);
+                } else if key != "name" {
+                    collect_text(key, child, &mut parts);
+                }
+            }
+        }
+        other => collect_text("", other, &mut parts),
+    }
+    parts.join(" ")
+}
+
+fn collect_text(key: &str, value: &Value, parts: &mut Vec<String>) {
+    if SKIPPED_KEYS.contains(&key) {
+        return;
+    }
+    match value {
+        Value::String(text) => {
+            let text = strip_tags(text);
+            if !text.trim().is_empty() {
+                parts.push(text);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_text("", item, parts);
+            }
+        }
+        Value::Object(map) => {
+            for (child_key, child) in map {
+                collect_text(child_key, child, parts);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Creature type and tags, e.g. `"beast"` or
+/// `{"type": "humanoid", "tags": ["goblinoid"]}`
+fn collect_creature_type(value: &Value, parts: &mut Vec<String>) {
+    match value {
+        Value::String(creature_type) => parts.push(creature_type.clone()),
+        Value::Object(map) => {
+            if let Some(creature_type) = map.get("type") {
+                collect_creature_type(creature_type, parts);
+            }
+            // Either of several types, e.g. {"choose": ["beast", "monstrosity"]}
+            if let Some(Value::Array(choices)) = map.get("choose") {
+                for choice in choices {
+                    collect_creature_type(choice, parts);
+                }
+            }
+            if let Some(Value::Array(tags)) = map.get("tags") {
+                for tag in tags {
+                    match tag {
+                        Value::String(tag) => parts.push(tag.clone()),
+                        Value::Object(tag) => {
+                            if let Some(Value::String(tag)) = tag.get("tag") {
+                                parts.push(tag.clone());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Turn free text into an FTS5 query that can't fail to parse.
+///
+/// Every word is quoted so FTS5 operators in user input are treated as
+/// plain text. Returns `None` if the query has nothing searchable.
+fn build_match_query(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+
+    for (i, part) in query.split('"').enumerate() {
+        let words: Vec<&str> = part
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.is_empty() {
+            continue;
+        }
+        // Odd segments were inside double quotes
+        if i % 2 == 1 {
+            terms.push(format!("\"{}\"", words.join(" ")));
+        } else {
+            terms.extend(words.iter().map(|w| format!("\"{}\"", w)));
+        }
+    }
+
+    // A trailing space or quote means the last word is finished
+    let ends_in_word = query.chars().last().is_some_and(|c| c.is_alphanumeric());
+    if ends_in_word {
+        if let Some(last) = terms.last_mut() {
+            last.push('*');
+        }
+    }
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_query_quotes_terms() {
+        assert_eq!(
+            build_match_query("fireball damage").as_deref(),
+            Some("\"fireball\" \"damage\"*")
+        );
+        assert_eq!(
+            build_match_query("\"pack tactics\" wolf ").as_deref(),
+            Some("\"pack tactics\" \"wolf\"")
+        );
+        assert_eq!(
+            build_match_query("NOT AND(*)").as_deref(),
+            Some("\"NOT\" \"AND\"")
+        );
+        assert_eq!(build_match_query("  -- "), None);
+    }
+
+    #[test]
+    fn test_searchable_text_strips_tags_and_metadata() {
+        let json = r#"{"name":"Wolf","source":"MM","page":341,"trait":[{"name":"Pack Tactics","entries":["The wolf has advantage on an attack roll against {@creature goblin|mm|a goblin} if at least one of the wolf's allies is within 5 feet."]}]}"#;
+        let text = searchable_text(CatalogKind::Monster, json);
+
+        assert!(text.contains("Pack Tactics"));
+        assert!(text.contains("against a goblin if"));
+        assert!(!text.contains("MM"));
+        assert!(!text.contains("341"));
+        assert!(!text.starts_with("Wolf"));
+    }
+
+    #[test]
+    fn test_searchable_text_indexes_creature_type() {
+        let json = r#"{"name":"Goblin","type":{"type":"humanoid","tags":["goblinoid"]},"entries":[{"type":"list","items":["Nimble Escape"]}]}"#;
+        let text = searchable_text(CatalogKind::Monster, json);
+        assert!(text.contains("humanoid"));
+        assert!(text.contains("goblinoid"));
+        assert!(text.contains("Nimble Escape"));
+        assert!(!text.contains("list"));
+
+        let json = r#"{"name":"Mimic","type":{"type":{"choose":["monstrosity","beast"]}}}"#;
+        let text = searchable_text(CatalogKind::Monster, json);
+        assert_eq!(text, "monstrosity beast");
+
+        // Item type codes stay out of the index
+        let json = r#"{"name":"Longsword","type":"M","entries":["A blade."]}"#;
+        assert_eq!(searchable_text(CatalogKind::Item, json), "A blade.");
+    }
+}
@@ -6,15 +6,21 @@
 #![allow(missing_docs)]
 
 use crate::connection::DbConnection;
+use crate::domain::dice::DiceRoller;
 use crate::error::{DbError, Result};
 use crate::models::catalog::{Background, Race};
 use crate::models::character::data::{
-    AbilityScores, CharacterData, ClassLevel, Currency, EquippedItems, FeatureReference,
-    InventoryItem, Personality, Proficiencies, SpellData,
+    AbilityScores, CharacterData, ClassLevel, Currency, DeathSaves, EquippedItems,
+    FeatureReference, InventoryItem, Personality, Proficiencies, SpellData,
 };
 use crate::services::{BackgroundService, ClassService, RaceService};
 use serde::{Deserialize, Serialize};
 
+use super::starting_equipment::{
+    resolve_starting_equipment, starting_equipment_options, ResolvedStartingEquipment,
+    StartingEquipmentChoice, StartingEquipmentOptions,
+};
+
 /// Method for determining ability scores
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AbilityScoreMethod {
@@ -82,6 +88,7 @@ pub struct CharacterBuilder<'a> {
     // Derived/calculated fields
     proficiencies: Proficiencies,
     starting_equipment: Vec<InventoryItem>,
+    starting_choice: Option<ResolvedStartingEquipment>,
 }
 
 impl<'a> CharacterBuilder<'a> {
@@ -105,6 +112,7 @@ impl<'a> CharacterBuilder<'a> {
             personality: Personality::default(),
             proficiencies: Proficiencies::default(),
             starting_equipment: Vec::new(),
+            starting_choice: None,
         }
     }
 
@@ -260,6 +268,51 @@ impl<'a> CharacterBuilder<'a> {
         self
     }
 
+    /// Starting equipment offered by the chosen class and background
+    ///
+    /// Requires the class to be set; background equipment is included once
+    /// the background is set.
+    pub fn starting_equipment_options(&mut self) -> Result<StartingEquipmentOptions> {
+        let (class, class_source) = match (&self.class, &self.class_source) {
+            (Some(class), Some(source)) => (class.clone(), source.clone()),
+            _ => {
+                return Err(DbError::InvalidData(
+                    "Class must be set before listing starting equipment".to_string(),
+                ))
+            }
+        };
+        let background = self
+            .background_name
+            .clone()
+            .zip(self.background_source.clone());
+
+        starting_equipment_options(
+            self.conn,
+            &class,
+            &class_source,
+            background.as_ref().map(|(n, s)| (n.as_str(), s.as_str())),
+        )
+    }
+
+    /// Choose starting equipment packages or take starting gold instead
+    ///
+    /// Resolves the chosen class and background options into inventory
+    /// items (expanding equipment packs) and coins. Class and background
+    /// must be set first. Choosing again replaces the previous selection.
+    pub fn choose_starting_equipment(mut self, choice: StartingEquipmentChoice) -> Result<Self> {
+        if self.background_name.is_none() {
+            return Err(DbError::InvalidData(
+                "Background must be set before choosing starting equipment".to_string(),
+            ));
+        }
+        let options = self.starting_equipment_options()?;
+        let resolved =
+            resolve_starting_equipment(self.conn, &options, &choice, &mut DiceRoller::new())?;
+        self.starting_choice = Some(resolved);
+
+        Ok(self)
+    }
+
     /// Build the final CharacterData
     pub fn build(mut self) -> Result<CharacterData> {
         // Validate required fields
@@ -412,6 +465,22 @@ impl<'a> CharacterBuilder<'a> {
             }
         }
 
+        // Merge the chosen starting equipment into any manually added items
+        let mut inventory = self.starting_equipment;
+        let mut currency = Currency::default();
+        if let Some(chosen) = self.starting_choice {
+            for item in chosen.items {
+                match inventory
+                    .iter_mut()
+                    .find(|i| i.name.eq_ignore_ascii_case(&item.name))
+                {
+                    Some(existing) => existing.quantity += item.quantity,
+                    None => inventory.push(item),
+                }
+            }
+            currency = chosen.currency;
+        }
+
         let mut character_data = CharacterData {
             character_name,
             player_id,
@@ -434,13 +503,20 @@ impl<'a> CharacterBuilder<'a> {
             abilities: base_abilities,
             max_hp,
             current_hp: max_hp,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             speed,
             proficiencies: self.proficiencies,
             class_features,
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
-            inventory: self.starting_equipment,
-            currency: Currency::default(),
+            inventory,
+            currency,
             equipped: EquippedItems::default(),
             personality: self.personality,
             // NPC fields default to None
@@ -452,12 +528,21 @@ impl<'a> CharacterBuilder<'a> {
             legendary_action_count: None,
         };
 
-        // Initialize spell slots from class data
+        // Initialize spell slots, pact magic and class resources from class data
         if let Ok(spell_slots) =
             super::spell_management::calculate_spell_slots(self.conn, &character_data)
         {
             character_data.spells.spell_slots = spell_slots;
         }
+        if let Ok(pact_slots) = super::resources::calculate_pact_slots(self.conn, &character_data)
+        {
+            character_data.spells.pact_slots = pact_slots;
+        }
+        if let Ok(resources) =
+            super::resources::calculate_class_resources(self.conn, &character_data)
+        {
+            character_data.class_resources = resources;
+        }
 
         Ok(character_data)
     }
@@ -1110,4 +1195,275 @@ mod tests {
 
         assert_eq!(wizard.max_hp, 9); // 6 + 3
     }
+
+    fn insert_test_item(conn: &mut DbConnection, item_json: &str) {
+        let item: crate::models::catalog::Item =
+            serde_json::from_str(item_json).expect("Invalid test item");
+        diesel::insert_into(crate::schema::catalog_items::table)
+            .values((
+                crate::schema::catalog_items::name.eq(&item.name),
+                crate::schema::catalog_items::source.eq(&item.source),
+                crate::schema::catalog_items::full_item_json.eq(item_json),
+            ))
+            .execute(conn)
+            .expect("Failed to insert test item");
+    }
+
+    fn seed_starting_equipment(conn: &mut DbConnection) {
+        let class_json = r#"{
+            "name": "Paladin",
+            "source": "PHB",
+            "hd": {"number": 1, "faces": 10},
+            "startingEquipment": {
+                "additionalFromBackground": true,
+                "default": ["(a) {@item chain mail|phb} or (b) a {@item longsword|phb}"],
+                "goldAlternative": "{@dice 5d4 × 10|5d4 × 10|Starting Gold}",
+                "defaultData": [
+                    {"a": ["chain mail|phb"], "b": ["longsword|phb"]},
+                    {"a": [{"equipmentType": "weaponMartial"}], "b": ["explorer's pack|phb"]},
+                    {"_": [{"item": "torch|phb", "quantity": 2}]}
+                ]
+            }
+        }"#;
+        diesel::insert_into(crate::schema::catalog_classes::table)
+            .values((
+                crate::schema::catalog_classes::name.eq("Paladin"),
+                crate::schema::catalog_classes::source.eq("PHB"),
+                crate::schema::catalog_classes::hit_dice.eq("d10"),
+                crate::schema::catalog_classes::primary_ability.eq("Strength"),
+                crate::schema::catalog_classes::full_class_json.eq(class_json),
+            ))
+            .execute(conn)
+            .expect("Failed to insert test class");
+
+        let background_json = r#"{
+            "name": "Acolyte",
+            "source": "PHB",
+            "startingEquipment": [
+                {"_": [{"special": "prayer book"}, {"item": "pouch|phb", "containsValue": 1550}]}
+            ]
+        }"#;
+        diesel::insert_into(crate::schema::catalog_backgrounds::table)
+            .values((
+                crate::schema::catalog_backgrounds::name.eq("Acolyte"),
+                crate::schema::catalog_backgrounds::source.eq("PHB"),
+                crate::schema::catalog_backgrounds::skills.eq("Insight, Religion"),
+                crate::schema::catalog_backgrounds::languages.eq(""),
+                crate::schema::catalog_backgrounds::tools.eq(""),
+                crate::schema::catalog_backgrounds::feature.eq("Shelter of the Faithful"),
+                crate::schema::catalog_backgrounds::full_background_json.eq(background_json),
+            ))
+            .execute(conn)
+            .expect("Failed to insert test background");
+
+        insert_test_item(
+            conn,
+            r#"{"name": "Chain Mail", "source": "PHB", "type": "HA", "weight": 55, "value": 7500, "ac": 16}"#,
+        );
+        insert_test_item(
+            conn,
+            r#"{"name": "Longsword", "source": "PHB", "type": "M", "weight": 3, "value": 1500}"#,
+        );
+        insert_test_item(
+            conn,
+            r#"{"name": "Torch", "source": "PHB", "type": "G", "weight": 1, "value": 1}"#,
+        );
+        insert_test_item(
+            conn,
+            r#"{"name": "Backpack", "source": "PHB", "type": "G", "weight": 5, "value": 200}"#,
+        );
+        insert_test_item(
+            conn,
+            r#"{"name": "Explorer's Pack", "source": "PHB", "type": "G", "weight": 59, "value": 1000,
+                "packContents": ["backpack|phb", {"item": "torch|phb", "quantity": 10}]}"#,
+        );
+    }
+
+    fn starting_builder(conn: &mut DbConnection) -> CharacterBuilder<'_> {
+        CharacterBuilder::new(conn)
+            .set_identity("Equipped".to_string(), Some(1))
+            .set_race("Human", "PHB", None)
+            .expect("Failed to set race")
+            .set_class("Paladin", "PHB", None)
+            .expect("Failed to set class")
+            .set_background("Acolyte", "PHB")
+            .expect("Failed to set background")
+            .set_ability_scores(AbilityScoreMethod::StandardArray {
+                strength: 15,
+                dexterity: 10,
+                constitution: 13,
+                intelligence: 8,
+                wisdom: 12,
+                charisma: 14,
+            })
+            .expect("Failed to set abilities")
+    }
+
+    #[test]
+    fn test_starting_equipment_options_and_choices() {
+        let mut conn = setup_test_db();
+        insert_test_race(&mut conn);
+        seed_starting_equipment(&mut conn);
+
+        let mut builder = starting_builder(&mut conn);
+        let options = builder
+            .starting_equipment_options()
+            .expect("Failed to load options");
+        assert_eq!(options.class_choices.len(), 2);
+        assert_eq!(options.class_fixed.len(), 1);
+        assert_eq!(options.background_fixed.len(), 2);
+        assert_eq!(options.gold_alternative.as_deref(), Some("5d4×10"));
+        assert_eq!(
+            options.class_description,
+            vec!["(a) chain mail or (b) a longsword"]
+        );
+
+        // Option (b) twice: a longsword and an explorer's pack, expanded into its contents
+        let character = builder
+            .choose_starting_equipment(StartingEquipmentChoice::Equipment {
+                class_choices: vec!["b".to_string(), "b".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            })
+            .expect("Failed to choose equipment")
+            .build()
+            .expect("Failed to build character");
+
+        let quantity = |name: &str| {
+            character
+                .inventory
+                .iter()
+                .find(|i| i.name == name)
+                .map(|i| i.quantity)
+        };
+        assert_eq!(quantity("Longsword"), Some(1));
+        assert_eq!(quantity("Backpack"), Some(1));
+        assert_eq!(quantity("Torch"), Some(12));
+        assert_eq!(quantity("Prayer Book"), Some(1));
+        assert_eq!(quantity("Pouch"), Some(1));
+        assert_eq!(quantity("Explorer's Pack"), None);
+
+        let longsword = character
+            .inventory
+            .iter()
+            .find(|i| i.name == "Longsword")
+            .unwrap();
+        assert_eq!(longsword.weight, 3.0);
+        assert_eq!(longsword.source.as_deref(), Some("PHB"));
+
+        // 1550 cp from the pouch
+        assert_eq!(character.currency.gold, 15);
+        assert_eq!(character.currency.silver, 5);
+        assert_eq!(character.currency.copper, 0);
+    }
+
+    #[test]
+    fn test_starting_equipment_picks_and_validation() {
+        let mut conn = setup_test_db();
+        insert_test_race(&mut conn);
+        seed_starting_equipment(&mut conn);
+
+        // An "any martial weapon" entry needs a pick
+        let result = starting_builder(&mut conn).choose_starting_equipment(
+            StartingEquipmentChoice::Equipment {
+                class_choices: vec!["a".to_string(), "a".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            },
+        );
+        assert!(result.is_err());
+
+        // Wrong number of choices
+        let result = starting_builder(&mut conn).choose_starting_equipment(
+            StartingEquipmentChoice::Equipment {
+                class_choices: vec!["a".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            },
+        );
+        assert!(result.is_err());
+
+        let character = starting_builder(&mut conn)
+            .choose_starting_equipment(StartingEquipmentChoice::Equipment {
+                class_choices: vec!["(A)".to_string(), "a".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec!["longsword|phb".to_string()],
+            })
+            .expect("Failed to choose equipment")
+            .build()
+            .expect("Failed to build character");
+        assert!(character.inventory.iter().any(|i| i.name == "Chain Mail"));
+        assert!(character.inventory.iter().any(|i| i.name == "Longsword"));
+    }
+
+    #[test]
+    fn test_starting_gold_instead_of_equipment() {
+        let mut conn = setup_test_db();
+        insert_test_race(&mut conn);
+        seed_starting_equipment(&mut conn);
+
+        let character = starting_builder(&mut conn)
+            .choose_starting_equipment(StartingEquipmentChoice::Gold { gold: None })
+            .expect("Failed to take starting gold")
+            .build()
+            .expect("Failed to build character");
+
+        assert!(character.inventory.is_empty());
+        assert!((50..=200).contains(&character.currency.gold));
+
+        let character = starting_builder(&mut conn)
+            .choose_starting_equipment(StartingEquipmentChoice::Gold { gold: Some(120) })
+            .expect("Failed to take starting gold")
+            .build()
+            .expect("Failed to build character");
+        assert_eq!(character.currency.gold, 120);
+    }
+
+    #[test]
+    fn test_choosing_starting_equipment_again_replaces_selection() {
+        let mut conn = setup_test_db();
+        insert_test_race(&mut conn);
+        seed_starting_equipment(&mut conn);
+
+        let character = starting_builder(&mut conn)
+            .choose_starting_equipment(StartingEquipmentChoice::Equipment {
+                class_choices: vec!["b".to_string(), "b".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            })
+            .expect("Failed to choose equipment")
+            .choose_starting_equipment(StartingEquipmentChoice::Equipment {
+                class_choices: vec!["b".to_string(), "b".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            })
+            .expect("Failed to choose equipment again")
+            .build()
+            .expect("Failed to build character");
+        let quantity = |name: &str| {
+            character
+                .inventory
+                .iter()
+                .find(|i| i.name == name)
+                .map(|i| i.quantity)
+        };
+        assert_eq!(quantity("Longsword"), Some(1));
+        assert_eq!(quantity("Torch"), Some(12));
+        assert_eq!(character.currency.gold, 15);
+
+        // Switching to starting gold drops the equipment entirely
+        let character = starting_builder(&mut conn)
+            .choose_starting_equipment(StartingEquipmentChoice::Equipment {
+                class_choices: vec!["b".to_string(), "b".to_string()],
+                background_choices: vec![],
+                equipment_picks: vec![],
+            })
+            .expect("Failed to choose equipment")
+            .choose_starting_equipment(StartingEquipmentChoice::Gold { gold: Some(120) })
+            .expect("Failed to take starting gold")
+            .build()
+            .expect("Failed to build character");
+        assert!(character.inventory.is_empty());
+        assert_eq!(character.currency.gold, 120);
+    }
 }
@@ -0,0 +1,813 @@
+//! Derived armor class, attack and damage calculation
+//!
+//! Equipped item names are resolved against `catalog_items` so armor type,
+//! base AC, weapon damage dice, weapon properties and magic bonuses come from
+//! the catalog instead of being guessed from the item name. Items that cannot
+//! be resolved fall back to conservative defaults (unarmored AC, +2 shield).
+
+use crate::connection::DbConnection;
+use crate::error::DbError;
+use crate::models::catalog::Item;
+use crate::models::character::data::CharacterData;
+use crate::services::ItemService;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, DbError>;
+
+/// Sources tried when an equipped item has no matching inventory entry
+const FALLBACK_SOURCES: [&str; 2] = ["PHB", "XPHB"];
+
+/// Maximum DEX bonus to AC while wearing medium armor
+const MEDIUM_ARMOR_DEX_CAP: i32 = 2;
+
+/// AC granted by a shield that is not in the catalog
+const DEFAULT_SHIELD_AC: i32 = 2;
+
+/// Combat numbers derived from a character's abilities and equipped items
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DerivedStats {
+    /// Total armor class
+    pub armor_class: i32,
+    /// Human-readable parts that add up to the armor class
+    pub ac_breakdown: Vec<String>,
+    /// Attack lines for equipped weapons
+    pub attacks: Vec<AttackLine>,
+}
+
+/// A single weapon attack line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttackLine {
+    /// Weapon name as equipped
+    pub name: String,
+    /// Total bonus to hit
+    pub attack_bonus: i32,
+    /// Damage dice, modifier and type (e.g. "1d8+3 slashing")
+    pub damage: String,
+    /// Two-handed damage for versatile weapons when the other hand is free
+    pub versatile_damage: Option<String>,
+    /// Normal/long range in feet for ranged and thrown weapons
+    pub range: Option<String>,
+    /// Weapon property names (Finesse, Light, ...)
+    pub properties: Vec<String>,
+    /// Whether the weapon was found in the catalog
+    pub from_catalog: bool,
+}
+
+/// Resolve the character's equipped items against the item catalog.
+///
+/// The source recorded on a matching inventory entry is tried first, then the
+/// core rulebooks. Magic weapons and armor written as "+1 Longsword" or
+/// "Chain Mail +1" are resolved to their base item when no exact entry exists.
+///
+/// # Returns
+/// Item details keyed by `"name:source"`, the same shape
+/// [`MarkdownRenderer`](super::MarkdownRenderer) accepts for inventory details.
+pub fn resolve_equipped_items(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+) -> Result<HashMap<String, Item>> {
+    let mut service = ItemService::new(conn);
+    let mut resolved = HashMap::new();
+
+    for name in equipped_names(character) {
+        let (base_name, _) = split_magic_bonus(name);
+        let inventory_source = character
+            .inventory
+            .iter()
+            .find(|item| item.name.eq_ignore_ascii_case(name))
+            .and_then(|item| item.source.as_deref());
+
+        let sources: Vec<&str> = inventory_source
+            .into_iter()
+            .chain(FALLBACK_SOURCES)
+            .collect();
+
+        'lookup: for candidate in [name, base_name] {
+            for source in &sources {
+                if let Some(item) = service.get_item_by_name_and_source(candidate, source)? {
+                    resolved.insert(format!("{}:{}", item.name, item.source), item);
+                    break 'lookup;
+                }
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Calculate AC and attack lines from already-resolved item details.
+///
+/// `item_details` is matched by item name (case-insensitive), so the map
+/// returned by [`resolve_equipped_items`] or a renderer's inventory details
+/// can be passed directly.
+pub fn calculate_derived_stats(
+    character: &CharacterData,
+    item_details: &HashMap<String, Item>,
+) -> DerivedStats {
+    let equipped = &character.equipped;
+    let armor = equipped
+        .armor
+        .as_deref()
+        .map(|name| (name, find_item(item_details, name)));
+    let shield = equipped
+        .shield
+        .as_deref()
+        .map(|name| (name, find_item(item_details, name)));
+    let off_hand = equipped
+        .off_hand
+        .as_deref()
+        .map(|name| (name, find_item(item_details, name)));
+
+    // A shield carried in the off hand counts as the shield
+    let off_hand_is_shield = off_hand.is_some_and(|(name, item)| is_shield(name, item));
+    let shield = shield.or(if off_hand_is_shield { off_hand } else { None });
+    let off_hand_weapon = if off_hand_is_shield { None } else { off_hand };
+
+    let (armor_class, ac_breakdown) = calculate_armor_class(character, armor, shield);
+
+    let mut attacks = Vec::new();
+    let single_weapon = off_hand_weapon.is_none();
+    let hand_free = shield.is_none() && single_weapon;
+    if let Some(name) = equipped.main_hand.as_deref() {
+        let item = find_item(item_details, name);
+        attacks.push(attack_line(
+            character,
+            name,
+            item,
+            false,
+            hand_free,
+            single_weapon,
+        ));
+    }
+    if let Some((name, item)) = off_hand_weapon {
+        attacks.push(attack_line(character, name, item, true, false, false));
+    }
+
+    DerivedStats {
+        armor_class,
+        ac_breakdown,
+        attacks,
+    }
+}
+
+/// Resolve equipped items and calculate derived stats in one step.
+pub fn derive_character_stats(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+) -> Result<DerivedStats> {
+    let items = resolve_equipped_items(conn, character)?;
+    Ok(calculate_derived_stats(character, &items))
+}
+
+fn equipped_names(character: &CharacterData) -> Vec<&str> {
+    let equipped = &character.equipped;
+    [
+        &equipped.armor,
+        &equipped.shield,
+        &equipped.main_hand,
+        &equipped.off_hand,
+    ]
+    .into_iter()
+    .filter_map(|slot| slot.as_deref())
+    .collect()
+}
+
+fn find_item<'a>(item_details: &'a HashMap<String, Item>, name: &str) -> Option<&'a Item> {
+    let (base_name, _) = split_magic_bonus(name);
+    [name, base_name].into_iter().find_map(|candidate| {
+        item_details
+            .values()
+            .find(|item| item.name.eq_ignore_ascii_case(candidate))
+    })
+}
+
+/// Split a "+1 Longsword" / "Longsword +1" / "Shield, +1" style name into the
+/// base item name and its bonus.
+fn split_magic_bonus(name: &str) -> (&str, i32) {
+    let trimmed = name.trim();
+
+    if let Some(rest) = trimmed.strip_prefix('+') {
+        if let Some((number, base)) = rest.split_once(' ') {
+            if let Ok(bonus) = number.parse::<i32>() {
+                return (base.trim(), bonus);
+            }
+        }
+    }
+
+    if let Some((base, number)) = trimmed.rsplit_once('+') {
+        if let Ok(bonus) = number.trim().parse::<i32>() {
+            return (base.trim().trim_end_matches(',').trim(), bonus);
+        }
+    }
+
+    (trimmed, 0)
+}
+
+/// Parse a catalog bonus string such as "+1"
+fn parse_bonus(bonus: Option<&String>) -> i32 {
+    bonus
+        .and_then(|b| b.trim().trim_start_matches('+').parse().ok())
+        .unwrap_or(0)
+}
+
+/// Catalog type code without the source suffix ("M|XPHB" -> "M")
+fn type_code(item: &Item) -> &str {
+    item.item_type
+        .as_deref()
+        .map(|t| t.split('|').next().unwrap_or(t))
+        .unwrap_or("")
+}
+
+fn has_property(item: &Item, code: &str) -> bool {
+    item.property
+        .iter()
+        .flatten()
+        .any(|p| p.split('|').next() == Some(code))
+}
+
+fn is_shield(name: &str, item: Option<&Item>) -> bool {
+    match item {
+        Some(item) => type_code(item) == "S",
+        None => name.to_lowercase().contains("shield"),
+    }
+}
+
+/// Whether a fighting style was chosen, either as an optional feature or as a
+/// "Fighting Style (X)" class feature
+fn has_fighting_style(character: &CharacterData, style: &str) -> bool {
+    character
+        .optional_features
+        .iter()
+        .any(|f| f.feature_type.starts_with("FS") && f.name.eq_ignore_ascii_case(style))
+        || character.class_features.iter().any(|f| {
+            let name = f.name.to_lowercase();
+            name.contains("fighting style") && name.contains(&style.to_lowercase())
+        })
+}
+
+fn calculate_armor_class(
+    character: &CharacterData,
+    armor: Option<(&str, Option<&Item>)>,
+    shield: Option<(&str, Option<&Item>)>,
+) -> (i32, Vec<String>) {
+    let abilities = &character.abilities;
+    let dex_mod = abilities.dex_modifier();
+    let mut breakdown = Vec::new();
+
+    let mut ac = match armor {
+        Some((name, Some(item))) => {
+            let (_, name_bonus) = split_magic_bonus(name);
+            let base = item.ac.map(i32::from).unwrap_or(10);
+            let bonus = parse_bonus(item.bonus_ac.as_ref()).max(name_bonus);
+            let dex = match type_code(item) {
+                "HA" => 0,
+                "MA" => dex_mod.min(MEDIUM_ARMOR_DEX_CAP),
+                _ => dex_mod,
+            };
+            breakdown.push(format!("{} {}", name, base + bonus));
+            if dex != 0 {
+                breakdown.push(format!("DEX {:+}", dex));
+            }
+            base + bonus + dex
+        }
+        Some((name, None)) => {
+            breakdown.push(format!("{} (not in catalog) 10", name));
+            breakdown.push(format!("DEX {:+}", dex_mod));
+            10 + dex_mod
+        }
+        None if character.has_class("Monk") && shield.is_none() => {
+            let wis_mod = abilities.wis_modifier();
+            breakdown.push("Unarmored Defense 10".to_string());
+            breakdown.push(format!("DEX {:+}", dex_mod));
+            breakdown.push(format!("WIS {:+}", wis_mod));
+            10 + dex_mod + wis_mod
+        }
+        None if character.has_class("Barbarian") => {
+            let con_mod = abilities.con_modifier();
+            breakdown.push("Unarmored Defense 10".to_string());
+            breakdown.push(format!("DEX {:+}", dex_mod));
+            breakdown.push(format!("CON {:+}", con_mod));
+            10 + dex_mod + con_mod
+        }
+        None => {
+            breakdown.push("Unarmored 10".to_string());
+            breakdown.push(format!("DEX {:+}", dex_mod));
+            10 + dex_mod
+        }
+    };
+
+    if let Some((name, item)) = shield {
+        let (_, name_bonus) = split_magic_bonus(name);
+        let bonus = match item {
+            Some(item) => {
+                item.ac.map(i32::from).unwrap_or(DEFAULT_SHIELD_AC)
+                    + parse_bonus(item.bonus_ac.as_ref()).max(name_bonus)
+            }
+            None => DEFAULT_SHIELD_AC + name_bonus,
+        };
+        breakdown.push(format!("{} {:+}", name, bonus));
+        ac += bonus;
+    }
+
+    if armor.is_some() && has_fighting_style(character, "Defense") {
+        breakdown.push("Defense +1".to_string());
+        ac += 1;
+    }
+
+    (ac, breakdown)
+}
+
+fn attack_line(
+    character: &CharacterData,
+    name: &str,
+    item: Option<&Item>,
+    off_hand: bool,
+    hand_free: bool,
+    single_weapon: bool,
+) -> AttackLine {
+    let abilities = &character.abilities;
+    let str_mod = abilities.str_modifier();
+    let dex_mod = abilities.dex_modifier();
+    let prof_bonus = character.proficiency_bonus();
+    let (_, name_bonus) = split_magic_bonus(name);
+
+    let Some(item) = item else {
+        // Without catalog data only the attack bonus can be estimated
+        return AttackLine {
+            name: name.to_string(),
+            attack_bonus: str_mod + prof_bonus + name_bonus,
+            damage: "—".to_string(),
+            versatile_damage: None,
+            range: None,
+            properties: Vec::new(),
+            from_catalog: false,
+        };
+    };
+
+    let ranged = type_code(item) == "R";
+    let ability_mod = if ranged {
+        dex_mod
+    } else if has_property(item, "F") {
+        str_mod.max(dex_mod)
+    } else {
+        str_mod
+    };
+
+    let magic_bonus = parse_bonus(item.bonus_weapon.as_ref()).max(name_bonus);
+    let attack_magic = magic_bonus + parse_bonus(item.bonus_weapon_attack.as_ref());
+    let damage_magic = magic_bonus + parse_bonus(item.bonus_weapon_damage.as_ref());
+
+    let mut attack_bonus = ability_mod + attack_magic;
+    if is_proficient(character, name, item) {
+        attack_bonus += prof_bonus;
+    }
+    if ranged && has_fighting_style(character, "Archery") {
+        attack_bonus += 2;
+    }
+
+    // Off-hand attacks don't add a positive ability modifier to damage
+    let damage_mod = if off_hand {
+        ability_mod.min(0)
+    } else {
+        ability_mod
+    } + damage_magic;
+    // Dueling only needs a one-handed weapon and no second weapon; a shield
+    // in the other hand is fine, but it doesn't apply to a two-handed grip
+    let dueling_bonus = if !ranged
+        && single_weapon
+        && !has_property(item, "2H")
+        && has_fighting_style(character, "Dueling")
+    {
+        2
+    } else {
+        0
+    };
+
+    let damage_type = item.dmg_type.as_deref().map(damage_type_name);
+    let damage = item
+        .dmg1
+        .as_deref()
+        .map(|dice| format_damage(dice, damage_mod + dueling_bonus, damage_type))
+        .unwrap_or_else(|| "—".to_string());
+    let versatile_damage = if has_property(item, "V") && hand_free && !off_hand {
+        item.dmg2
+            .as_deref()
+            .map(|dice| format_damage(dice, damage_mod, damage_type))
+    } else {
+        None
+    };
+
+    AttackLine {
+        name: name.to_string(),
+        attack_bonus,
+        damage,
+        versatile_damage,
+        range: item.range.as_ref().map(|r| format!("{} ft", r)),
+        properties: item
+            .property
+            .iter()
+            .flatten()
+            .filter_map(|p| property_name(p.split('|').next().unwrap_or(p)))
+            .map(str::to_string)
+            .collect(),
+        from_catalog: true,
+    }
+}
+
+/// Weapon proficiency by category ("Martial weapons") or by weapon name.
+/// Characters without any recorded weapon proficiencies are treated as
+/// proficient so older character data keeps its attack bonuses.
+fn is_proficient(character: &CharacterData, name: &str, item: &Item) -> bool {
+    let weapons = &character.proficiencies.weapons;
+    if weapons.is_empty() {
+        return true;
+    }
+
+    let (base_name, _) = split_magic_bonus(name);
+    let base_name = base_name.to_lowercase();
+    let item_name = item.name.to_lowercase();
+    let category = item.weapon_category.as_deref().map(str::to_lowercase);
+
+    weapons.iter().any(|prof| {
+        let prof = prof.to_lowercase();
+        let singular = prof.trim_end_matches('s');
+        category.as_deref().is_some_and(|c| prof.starts_with(c))
+            || singular == base_name
+            || singular == item_name
+    })
+}
+
+fn format_damage(dice: &str, modifier: i32, damage_type: Option<&str>) -> String {
+    let mut damage = if modifier == 0 {
+        dice.to_string()
+    } else {
+        format!("{}{:+}", dice, modifier)
+    };
+    if let Some(damage_type) = damage_type {
+        damage.push(' ');
+        damage.push_str(damage_type);
+    }
+    damage
+}
+
+fn damage_type_name(code: &str) -> &str {
+    match code {
+        "A" => "acid",
+        "B" => "bludgeoning",
+        "C" => "cold",
+        "F" => "fire",
+        "O" => "force",
+        "L" => "lightning",
+        "N" => "necrotic",
+        "P" => "piercing",
+        "I" => "poison",
+        "Y" => "psychic",
+        "R" => "radiant",
+        "S" => "slashing",
+        "T" => "thunder",
+        other => other,
+    }
+}
+
+fn property_name(code: &str) -> Option<&'static str> {
+    match code {
+        "A" => Some("Ammunition"),
+        "F" => Some("Finesse"),
+        "H" => Some("Heavy"),
+        "L" => Some("Light"),
+        "LD" => Some("Loading"),
+        "R" => Some("Reach"),
+        "S" => Some("Special"),
+        "T" => Some("Thrown"),
+        "2H" => Some("Two-Handed"),
+        "V" => Some("Versatile"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::character::data::{
+        AbilityScores, ClassLevel, Currency, DeathSaves, EquippedItems, FeatureReference,
+        OptionalFeatureChoice, Personality, Proficiencies, SpellData,
+    };
+
+    fn item(name: &str, item_type: &str) -> Item {
+        Item {
+            name: name.to_string(),
+            source: "PHB".to_string(),
+            page: None,
+            item_type: Some(item_type.to_string()),
+            rarity: None,
+            weight: None,
+            value: None,
+            ac: None,
+            dmg1: None,
+            dmg2: None,
+            dmg_type: None,
+            property: None,
+            range: None,
+            reload: None,
+            weapon_category: None,
+            bonus_ac: None,
+            bonus_weapon: None,
+            bonus_weapon_attack: None,
+            bonus_weapon_damage: None,
+            requires_attunement: None,
+            charges: None,
+            recharge: None,
+            recharge_amount: None,
+            entries: None,
+            srd: None,
+            pack_contents: None,
+        }
+    }
+
+    fn armor(name: &str, item_type: &str, ac: u8) -> Item {
+        Item {
+            ac: Some(ac),
+            ..item(name, item_type)
+        }
+    }
+
+    fn weapon(name: &str, dice: &str, dmg_type: &str, properties: &[&str]) -> Item {
+        Item {
+            dmg1: Some(dice.to_string()),
+            dmg_type: Some(dmg_type.to_string()),
+            weapon_category: Some("martial".to_string()),
+            property: Some(properties.iter().map(|p| p.to_string()).collect()),
+            ..item(name, "M")
+        }
+    }
+
+    fn catalog(items: Vec<Item>) -> HashMap<String, Item> {
+        items
+            .into_iter()
+            .map(|i| (format!("{}:{}", i.name, i.source), i))
+            .collect()
+    }
+
+    fn create_test_character(class_name: &str, equipped: EquippedItems) -> CharacterData {
+        CharacterData {
+            character_name: "Test".to_string(),
+            player_id: None,
+            level: 5,
+            experience_points: 6500,
+            version: 1,
+            snapshot_reason: None,
+            created_at: "2025-01-01".to_string(),
+            race: "Human".to_string(),
+            subrace: None,
+            classes: vec![ClassLevel {
+                class_name: class_name.to_string(),
+                level: 5,
+                subclass: None,
+                hit_dice_type: "d10".to_string(),
+                hit_dice_remaining: 5,
+            }],
+            background: "Soldier".to_string(),
+            alignment: None,
+            abilities: AbilityScores {
+                strength: 16,
+                dexterity: 14,
+                constitution: 14,
+                intelligence: 10,
+                wisdom: 12,
+                charisma: 8,
+            },
+            max_hp: 40,
+            current_hp: 40,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
+            speed: 30,
+            proficiencies: Proficiencies {
+                weapons: vec!["Simple weapons".to_string(), "Martial weapons".to_string()],
+                ..Default::default()
+            },
+            class_features: Vec::new(),
+            class_resources: Vec::new(),
+            feats: Vec::new(),
+            optional_features: Vec::new(),
+            spells: SpellData::default(),
+            inventory: Vec::new(),
+            currency: Currency::default(),
+            equipped,
+            personality: Personality::default(),
+            npc_role: None,
+            npc_location: None,
+            npc_faction: None,
+            npc_notes: None,
+            legendary_actions: Vec::new(),
+            legendary_action_count: None,
+        }
+    }
+
+    #[test]
+    fn test_armor_types_apply_dex_rules() {
+        let items = catalog(vec![
+            armor("Leather Armor", "LA", 11),
+            armor("Scale Mail", "MA", 14),
+            armor("Plate Armor", "HA", 18),
+            armor("Shield", "S", 2),
+        ]);
+        let mut character = create_test_character("Fighter", EquippedItems::default());
+        character.abilities.dexterity = 18;
+
+        character.equipped.armor = Some("Leather Armor".to_string());
+        assert_eq!(calculate_derived_stats(&character, &items).armor_class, 15);
+
+        character.equipped.armor = Some("Scale Mail".to_string());
+        assert_eq!(calculate_derived_stats(&character, &items).armor_class, 16);
+
+        character.equipped.armor = Some("Plate Armor".to_string());
+        character.equipped.shield = Some("Shield".to_string());
+        let stats = calculate_derived_stats(&character, &items);
+        assert_eq!(stats.armor_class, 20);
+        assert_eq!(stats.ac_breakdown, vec!["Plate Armor 18", "Shield +2"]);
+    }
+
+    #[test]
+    fn test_unarmored_defense_and_fallbacks() {
+        let items = HashMap::new();
+
+        let monk = create_test_character("Monk", EquippedItems::default());
+        assert_eq!(calculate_derived_stats(&monk, &items).armor_class, 13);
+
+        let barbarian = create_test_character("Barbarian", EquippedItems::default());
+        assert_eq!(calculate_derived_stats(&barbarian, &items).armor_class, 14);
+
+        // Unknown armor is treated as unarmored, unknown shields as +2
+        let fighter = create_test_character(
+            "Fighter",
+            EquippedItems {
+                armor: Some("Homebrew Mail".to_string()),
+                shield: Some("Shield".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(calculate_derived_stats(&fighter, &items).armor_class, 14);
+    }
+
+    #[test]
+    fn test_magic_bonuses_and_defense_style() {
+        let items = catalog(vec![
+            armor("Plate Armor", "HA", 18),
+            weapon("Longsword", "1d8", "S", &["V"]),
+        ]);
+
+        let mut character = create_test_character(
+            "Fighter",
+            EquippedItems {
+                armor: Some("Plate Armor +1".to_string()),
+                main_hand: Some("+2 Longsword".to_string()),
+                ..Default::default()
+            },
+        );
+        character.class_features.push(FeatureReference {
+            name: "Fighting Style (Defense)".to_string(),
+            class_name: "Fighter".to_string(),
+            subclass_name: None,
+            source: "PHB".to_string(),
+            level: 1,
+        });
+
+        let stats = calculate_derived_stats(&character, &items);
+        assert_eq!(stats.armor_class, 20);
+
+        let attack = &stats.attacks[0];
+        // STR +3, proficiency +3, magic +2
+        assert_eq!(attack.attack_bonus, 8);
+        assert_eq!(attack.damage, "1d8+5 slashing");
+        assert!(attack.from_catalog);
+
+        // A Defense style picked as an optional feature counts the same way
+        character.class_features.clear();
+        character.optional_features.push(OptionalFeatureChoice {
+            name: "Defense".to_string(),
+            source: "PHB".to_string(),
+            feature_type: "FS:F".to_string(),
+            class_name: "Fighter".to_string(),
+            level: 1,
+        });
+        assert_eq!(calculate_derived_stats(&character, &items).armor_class, 20);
+    }
+
+    #[test]
+    fn test_weapon_properties_drive_attacks() {
+        let mut longsword = weapon("Longsword", "1d8", "S", &["V"]);
+        longsword.dmg2 = Some("1d10".to_string());
+        let mut longbow = weapon("Longbow", "1d8", "P", &["A", "H", "2H"]);
+        longbow.item_type = Some("R".to_string());
+        longbow.range = Some("150/600".to_string());
+        let items = catalog(vec![
+            longsword,
+            longbow,
+            weapon("Rapier", "1d8", "P", &["F"]),
+            weapon("Dagger", "1d4", "P", &["F", "L", "T"]),
+        ]);
+
+        let mut character = create_test_character(
+            "Fighter",
+            EquippedItems {
+                main_hand: Some("Longsword".to_string()),
+                ..Default::default()
+            },
+        );
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.damage, "1d8+3 slashing");
+        assert_eq!(attack.versatile_damage.as_deref(), Some("1d10+3 slashing"));
+        assert_eq!(attack.properties, vec!["Versatile"]);
+
+        // Holding a dagger in the off hand removes the versatile grip
+        character.equipped.off_hand = Some("Dagger".to_string());
+        let stats = calculate_derived_stats(&character, &items);
+        assert_eq!(stats.attacks[0].versatile_damage, None);
+        assert_eq!(stats.attacks[1].attack_bonus, 6);
+        assert_eq!(stats.attacks[1].damage, "1d4 piercing");
+
+        // Finesse picks the better of STR and DEX, ranged always uses DEX
+        character.abilities.dexterity = 18;
+        character.equipped.off_hand = None;
+        character.equipped.main_hand = Some("Rapier".to_string());
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.attack_bonus, 7);
+        assert_eq!(attack.damage, "1d8+4 piercing");
+
+        character.abilities.dexterity = 10;
+        character.equipped.main_hand = Some("Longbow".to_string());
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.attack_bonus, 3);
+        assert_eq!(attack.damage, "1d8 piercing");
+        assert_eq!(attack.range.as_deref(), Some("150/600 ft"));
+    }
+
+    #[test]
+    fn test_dueling_applies_with_shield() {
+        let mut longsword = weapon("Longsword", "1d8", "S", &["V"]);
+        longsword.dmg2 = Some("1d10".to_string());
+        let items = catalog(vec![
+            longsword,
+            armor("Shield", "S", 2),
+            weapon("Dagger", "1d4", "P", &["F", "L", "T"]),
+        ]);
+
+        let mut character = create_test_character(
+            "Fighter",
+            EquippedItems {
+                main_hand: Some("Longsword".to_string()),
+                shield: Some("Shield".to_string()),
+                ..Default::default()
+            },
+        );
+        character.class_features.push(FeatureReference {
+            name: "Fighting Style (Dueling)".to_string(),
+            class_name: "Fighter".to_string(),
+            subclass_name: None,
+            source: "PHB".to_string(),
+            level: 1,
+        });
+
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.damage, "1d8+5 slashing");
+        assert_eq!(attack.versatile_damage, None);
+
+        // Wielded two-handed the versatile grip gets no Dueling bonus
+        character.equipped.shield = None;
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.damage, "1d8+5 slashing");
+        assert_eq!(attack.versatile_damage.as_deref(), Some("1d10+3 slashing"));
+
+        // A second weapon rules Dueling out
+        character.equipped.off_hand = Some("Dagger".to_string());
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.damage, "1d8+3 slashing");
+    }
+
+    #[test]
+    fn test_missing_weapon_proficiency() {
+        let items = catalog(vec![weapon("Longsword", "1d8", "S", &["V"])]);
+        let mut character = create_test_character(
+            "Wizard",
+            EquippedItems {
+                main_hand: Some("Longsword".to_string()),
+                ..Default::default()
+            },
+        );
+        character.proficiencies.weapons = vec!["Daggers".to_string()];
+
+        let attack = &calculate_derived_stats(&character, &items).attacks[0];
+        assert_eq!(attack.attack_bonus, 3);
+    }
+
+    #[test]
+    fn test_split_magic_bonus() {
+        assert_eq!(split_magic_bonus("+1 Longsword"), ("Longsword", 1));
+        assert_eq!(split_magic_bonus("Shield, +2"), ("Shield", 2));
+        assert_eq!(split_magic_bonus("Chain Mail"), ("Chain Mail", 0));
+    }
+}
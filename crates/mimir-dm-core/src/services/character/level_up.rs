@@ -6,8 +6,10 @@
 #![allow(missing_docs)]
 
 use crate::connection::DbConnection;
+use crate::domain::dice::DiceRoller;
 use crate::error::{DbError, Result};
 use crate::models::character::data::AbilityScores;
+use crate::services::character::optional_features::OptionalFeatureSelection;
 use crate::services::ClassService;
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +32,11 @@ pub struct LevelUpOptions {
     /// Subclass choice (if this is the level where subclass is chosen)
     pub subclass_choice: Option<String>,
 
+    /// Optional features chosen at this level (invocations, fighting styles,
+    /// metamagic, maneuvers)
+    #[serde(default)]
+    pub optional_features: Vec<OptionalFeatureSelection>,
+
     /// Reason for this level up (e.g., "Leveled up after defeating dragon")
     pub snapshot_reason: Option<String>,
 }
@@ -46,6 +53,9 @@ pub enum HpGainMethod {
 
     /// Take the average (rounded up)
     Average,
+
+    /// Have the dice engine roll the hit die
+    RollHitDie,
 }
 
 /// Ability Score Improvement or Feat selection
@@ -100,7 +110,7 @@ impl LevelUpOptions {
                     )));
                 }
             }
-            HpGainMethod::Average => {}
+            HpGainMethod::Average | HpGainMethod::RollHitDie => {}
         }
         Ok(())
     }
@@ -288,6 +298,15 @@ impl ClassInfo {
     pub fn average_hp_gain(&self) -> i32 {
         (self.hit_die_value / 2) + 1
     }
+
+    /// Calculate the hit die portion of an HP gain (before CON modifier)
+    pub fn hp_gain(&self, method: &HpGainMethod) -> i32 {
+        match method {
+            HpGainMethod::Roll(value) => *value,
+            HpGainMethod::Average => self.average_hp_gain(),
+            HpGainMethod::RollHitDie => DiceRoller::new().roll_die(self.hit_die_value as u32) as i32,
+        }
+    }
 }
 
 impl MulticlassPrerequisites {
@@ -444,6 +463,7 @@ mod tests {
             hp_method: HpGainMethod::Roll(10),
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -456,6 +476,7 @@ mod tests {
             hp_method: HpGainMethod::Roll(11),
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -475,6 +496,7 @@ mod tests {
                 increase2: None,
             }),
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -491,6 +513,7 @@ mod tests {
                 increase2: Some(1),
             }),
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -544,6 +567,11 @@ mod tests {
         let fighter = ClassInfo::get(&mut conn, "Fighter", "PHB").expect("Failed to get Fighter");
         assert_eq!(fighter.hit_die_value, 10);
         assert_eq!(fighter.average_hp_gain(), 6);
+        assert_eq!(fighter.hp_gain(&HpGainMethod::Roll(3)), 3);
+        assert_eq!(fighter.hp_gain(&HpGainMethod::Average), 6);
+        for _ in 0..20 {
+            assert!((1..=10).contains(&fighter.hp_gain(&HpGainMethod::RollHitDie)));
+        }
 
         let wizard = ClassInfo::get(&mut conn, "Wizard", "PHB").expect("Failed to get Wizard");
         assert_eq!(wizard.hit_die_value, 6);
@@ -11,30 +11,61 @@
 //! - [`level_up`] - Level up types and utilities
 //! - [`creation`] - Character builder
 //! - [`spell_management`] - Spell slot calculations
+//! - [`resources`] - Pact magic and class resource calculations
+//! - [`derived_stats`] - Armor class, attack and damage from equipped catalog items
+//! - [`optional_features`] - Invocations, fighting styles, metamagic and maneuvers
+//! - [`starting_equipment`] - Class and background starting equipment choices
+//! - [`validator`] - Rules linter for whole characters
+//! - [`version_diff`] - Structured comparison of character versions
 
 pub mod creation;
+pub mod derived_stats;
 pub mod level_up;
+pub mod optional_features;
 pub mod progression;
 pub mod renderer;
+pub mod resources;
 pub mod spell_management;
 pub mod spells;
+pub mod starting_equipment;
+pub mod validator;
+pub mod version_diff;
 
 pub use creation::{AbilityScoreMethod, CharacterBuilder};
+pub use derived_stats::{
+    calculate_derived_stats, derive_character_stats, resolve_equipped_items, AttackLine,
+    DerivedStats,
+};
 pub use level_up::{AsiOrFeat, ClassInfo, HpGainMethod, LevelUpOptions, MulticlassPrerequisites};
+pub use optional_features::{
+    apply_optional_feature_choices, optional_feature_gains, OptionalFeatureGain,
+    OptionalFeatureSelection,
+};
 pub use progression::CharacterProgressionService;
 pub use renderer::{CharacterRenderer, MarkdownRenderer};
+pub use resources::{calculate_class_resources, calculate_pact_slots, refresh_resources};
 pub use spell_management::{
-    calculate_spell_attack_bonus, calculate_spell_save_dc, calculate_spell_slots, RestType,
+    calculate_spell_attack_bonus, calculate_spell_save_dc, calculate_spell_slots,
+    requires_concentration, RestType,
 };
 pub use spells::CharacterSpellService;
+pub use starting_equipment::{
+    starting_equipment_options, EquipmentChoiceGroup, EquipmentEntry, EquipmentOption,
+    StartingEquipmentChoice, StartingEquipmentOptions,
+};
+pub use validator::{CharacterValidator, FindingSeverity, ValidationFinding, ValidationReport};
+pub use version_diff::{
+    diff_character_data, CharacterDiff, FieldChange, InventoryChange, InventoryChangeKind,
+    ListChange, SpellSlotChange,
+};
 
 use crate::{
     connection::DbConnection,
     dal::character::{CharacterRepository, CharacterVersionRepository},
     error::{DbError, Result},
     models::character::{
-        Character, CharacterData, CharacterVersion, NewCharacter, NewCharacterVersion,
-        UpdateCharacter,
+        data::MAX_ATTUNED_ITEMS, Character, CharacterData, CharacterVersion, ClassLevel,
+        DamageOutcome, ItemCharges, NewCharacter, NewCharacterVersion, UpdateCharacter,
     },
 };
 use std::fs;
@@ -381,6 +412,26 @@ impl<'a> CharacterService<'a> {
         char_repo.list_pcs(campaign_id)
     }
 
+    /// Get the level of each player character in a campaign.
+    ///
+    /// Levels are summed across a character's classes, falling back to the
+    /// stored level if the character data can't be read.
+    pub fn get_party_levels(&mut self, campaign_id: i32) -> Result<Vec<i32>> {
+        let pcs = self.list_pcs_for_campaign(campaign_id)?;
+
+        let mut levels = Vec::with_capacity(pcs.len());
+        for pc in pcs {
+            let level = match self.get_character(pc.id) {
+                Ok((_, data)) if !data.classes.is_empty() => {
+                    data.classes.iter().map(|c| c.level).sum()
+                }
+                _ => pc.current_level,
+            };
+            levels.push(level);
+        }
+        Ok(levels)
+    }
+
     /// Get all versions for a character
     pub fn get_character_versions(&mut self, character_id: i32) -> Result<Vec<CharacterVersion>> {
         let mut ver_repo = CharacterVersionRepository::new(self.conn);
@@ -407,6 +458,35 @@ impl<'a> CharacterService<'a> {
         Ok(character_data)
     }
 
+    /// Compare two versions of a character.
+    ///
+    /// `from_version` is treated as the older snapshot, so values added in
+    /// `to_version` are reported as additions.
+    pub fn diff_character_versions(
+        &mut self,
+        character_id: i32,
+        from_version: i32,
+        to_version: i32,
+    ) -> Result<CharacterDiff> {
+        let before = self.get_character_version(character_id, from_version)?;
+        let after = self.get_character_version(character_id, to_version)?;
+        Ok(diff_character_data(&before, &after))
+    }
+
+    /// Restore a character to an earlier version.
+    ///
+    /// History is never rewritten: the old snapshot is saved as a new
+    /// version, so the restore itself can be undone.
+    pub fn restore_character_version(
+        &mut self,
+        character_id: i32,
+        version_number: i32,
+    ) -> Result<CharacterVersion> {
+        let character_data = self.get_character_version(character_id, version_number)?;
+        let snapshot_reason = Some(format!("Restored version {}", version_number));
+        self.update_character(character_id, character_data, snapshot_reason)
+    }
+
     /// Level up a character
     pub fn level_up_character(
         &mut self,
@@ -434,12 +514,9 @@ impl<'a> CharacterService<'a> {
         // Validate HP gain
         options.validate_hp_gain(class_info.hit_die_value)?;
 
-        // Calculate HP gain
+        // Calculate HP gain; a level always adds at least 1 HP
         let con_modifier = char_data.abilities.con_modifier();
-        let hp_gain = match options.hp_method {
-            HpGainMethod::Roll(value) => value + con_modifier,
-            HpGainMethod::Average => class_info.average_hp_gain() + con_modifier,
-        };
+        let hp_gain = (class_info.hp_gain(&options.hp_method) + con_modifier).max(1);
 
         // Update HP
         char_data.max_hp += hp_gain;
@@ -511,8 +588,16 @@ impl<'a> CharacterService<'a> {
             }
         }
 
-        // Update spell slots if character is a spellcaster
-        // For now, we'll skip this complex logic and leave it for future enhancement
+        // Record optional feature choices against the new class level
+        optional_features::apply_optional_feature_choices(
+            self.conn,
+            &mut char_data,
+            &options.class_name,
+            &options.optional_features,
+        )?;
+
+        // Recalculate spell slots, pact magic and class resources for the new level
+        resources::refresh_resources(self.conn, &mut char_data)?;
 
         // Update snapshot reason
         let snapshot_reason = options
@@ -523,6 +608,40 @@ impl<'a> CharacterService<'a> {
         self.update_character(character_id, char_data, snapshot_reason)
     }
 
+    /// Get the optional features a character can choose when leveling up in a class
+    ///
+    /// Evaluated as if the character had already gained the level, and the
+    /// subclass when one is chosen at that level. Only kinds with open choices
+    /// are returned.
+    pub fn get_level_up_optional_features(
+        &mut self,
+        character_id: i32,
+        class_name: &str,
+        subclass: Option<&str>,
+    ) -> Result<Vec<OptionalFeatureGain>> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+
+        char_data.level += 1;
+        match char_data.get_class_mut(class_name) {
+            Some(class_level) => class_level.level += 1,
+            None => char_data.classes.push(ClassLevel {
+                class_name: class_name.to_string(),
+                level: 1,
+                subclass: None,
+                hit_dice_type: String::new(),
+                hit_dice_remaining: 1,
+            }),
+        }
+        if let Some(subclass) = subclass {
+            if let Some(class_level) = char_data.get_class_mut(class_name) {
+                class_level.subclass = Some(subclass.to_string());
+            }
+        }
+
+        let gains = optional_features::optional_feature_gains(self.conn, &char_data, class_name)?;
+        Ok(gains.into_iter().filter(|g| g.remaining > 0).collect())
+    }
+
     // Helper method to apply ability score increase
     fn apply_ability_increase(
         &self,
@@ -671,6 +790,9 @@ impl<'a> CharacterService<'a> {
     }
 
     /// Cast a spell, consuming the appropriate spell slot
+    ///
+    /// Casting a concentration spell replaces whatever the character was
+    /// concentrating on.
     pub fn cast_spell(
         &mut self,
         character_id: i32,
@@ -680,6 +802,10 @@ impl<'a> CharacterService<'a> {
     ) -> Result<CharacterVersion> {
         let (_character, mut char_data) = self.get_character(character_id)?;
 
+        if requires_concentration(self.conn, &char_data, spell_name)? {
+            char_data.concentration = Some(spell_name.to_string());
+        }
+
         // Ritual casting doesn't consume slots
         if is_ritual {
             let snapshot_reason = Some(format!("Cast {} as ritual", spell_name));
@@ -689,20 +815,19 @@ impl<'a> CharacterService<'a> {
         // Check if character has prepared the spell (or if it's a known spell for spontaneous casters)
         // For now, just check if spell is in known_spells or prepared_spells
 
-        // Consume spell slot
-        if let Some(slots) = char_data.spells.spell_slots.get_mut(&spell_level) {
-            if !slots.expend(1) {
-                return Err(DbError::InvalidData(format!(
-                    "No level {} spell slots remaining",
-                    spell_level
-                )));
-            }
-        } else {
+        // Consume spell slot, falling back to pact magic slots
+        if !char_data.has_spell_slot_level(spell_level) {
             return Err(DbError::InvalidData(format!(
                 "Character has no level {} spell slots",
                 spell_level
             )));
         }
+        if !char_data.expend_spell_slot(spell_level) {
+            return Err(DbError::InvalidData(format!(
+                "No level {} spell slots remaining",
+                spell_level
+            )));
+        }
 
         // Create new version
         let snapshot_reason = Some(format!("Cast {} (level {})", spell_name, spell_level));
@@ -710,42 +835,38 @@ impl<'a> CharacterService<'a> {
     }
 
     /// Rest and restore spell slots
+    ///
+    /// A long rest restores HP through healing, so a character resting from
+    /// 0 HP wakes up. Dead characters can't rest.
     pub fn rest(&mut self, character_id: i32, rest_type: RestType) -> Result<CharacterVersion> {
         let (_character, mut char_data) = self.get_character(character_id)?;
+        if char_data.death_saves.is_dead() {
+            return Err(DbError::InvalidData(format!(
+                "{} is dead and can't rest",
+                char_data.character_name
+            )));
+        }
 
-        match rest_type {
-            RestType::Short => {
-                // Short rest restores warlock pact magic slots
-                if char_data.has_class("Warlock") {
-                    for slots in char_data.spells.spell_slots.values_mut() {
-                        slots.recover_all();
-                    }
-                }
-                // Note: Class-specific short rest features (Arcane Recovery, etc.) are listed
-                // on character sheets but actual resource tracking is handled at the table/VTT.
-            }
-            RestType::Long => {
-                // Long rest restores all spell slots for all classes
-                for slots in char_data.spells.spell_slots.values_mut() {
-                    slots.recover_all();
-                }
-
-                // Also restore HP
-                char_data.current_hp = char_data.max_hp;
-
-                // Restore hit dice (restore half of max for each class, minimum 1 total)
-                let total_restored = (char_data.level / 2).max(1);
-                let mut remaining_to_restore = total_restored;
-
-                for class_level in &mut char_data.classes {
-                    let max_for_class = class_level.level;
-                    let can_restore = max_for_class - class_level.hit_dice_remaining;
-                    let to_restore = can_restore.min(remaining_to_restore);
-                    class_level.hit_dice_remaining += to_restore;
-                    remaining_to_restore -= to_restore;
-                    if remaining_to_restore <= 0 {
-                        break;
-                    }
+        // Short rests restore pact magic and short-rest class resources;
+        // long rests restore every slot and resource
+        char_data.recover_resources(rest_type);
+
+        if rest_type == RestType::Long {
+            // Also restore HP, ending unconsciousness at 0 HP
+            char_data.heal(char_data.max_hp);
+
+            // Restore hit dice (restore half of max for each class, minimum 1 total)
+            let total_restored = (char_data.level / 2).max(1);
+            let mut remaining_to_restore = total_restored;
+
+            for class_level in &mut char_data.classes {
+                let max_for_class = class_level.level;
+                let can_restore = max_for_class - class_level.hit_dice_remaining;
+                let to_restore = can_restore.min(remaining_to_restore);
+                class_level.hit_dice_remaining += to_restore;
+                remaining_to_restore -= to_restore;
+                if remaining_to_restore <= 0 {
+                    break;
                 }
             }
         }
@@ -755,6 +876,298 @@ impl<'a> CharacterService<'a> {
         self.update_character(character_id, char_data, snapshot_reason)
     }
 
+    /// Spend uses of a class resource (e.g., Ki Points, Rage)
+    ///
+    /// Fails without changing the character if fewer than `amount` uses remain.
+    pub fn use_class_resource(
+        &mut self,
+        character_id: i32,
+        resource_name: &str,
+        amount: i32,
+    ) -> Result<CharacterVersion> {
+        if amount < 1 {
+            return Err(DbError::InvalidData(
+                "Amount must be at least 1".to_string(),
+            ));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+
+        let resource = char_data.get_resource_mut(resource_name).ok_or_else(|| {
+            DbError::InvalidData(format!(
+                "Character has no class resource named '{}'",
+                resource_name
+            ))
+        })?;
+        if !resource.expend(amount) {
+            return Err(DbError::InvalidData(format!(
+                "Not enough {} remaining ({}/{}, need {})",
+                resource.name, resource.current, resource.max, amount
+            )));
+        }
+
+        let snapshot_reason = Some(format!(
+            "Used {} {} ({}/{} remaining)",
+            amount, resource.name, resource.current, resource.max
+        ));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Restore uses of a class resource outside of a rest
+    ///
+    /// Restores `amount` uses, or all of them if `amount` is `None`.
+    pub fn restore_class_resource(
+        &mut self,
+        character_id: i32,
+        resource_name: &str,
+        amount: Option<i32>,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+
+        let resource = char_data.get_resource_mut(resource_name).ok_or_else(|| {
+            DbError::InvalidData(format!(
+                "Character has no class resource named '{}'",
+                resource_name
+            ))
+        })?;
+        match amount {
+            Some(amount) if amount < 1 => {
+                return Err(DbError::InvalidData(
+                    "Amount must be at least 1".to_string(),
+                ));
+            }
+            Some(amount) => resource.recover(amount),
+            None => resource.recover_all(),
+        }
+
+        let snapshot_reason = Some(format!(
+            "Restored {} ({}/{})",
+            resource.name, resource.current, resource.max
+        ));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    // ===== Combat State Methods =====
+
+    /// Apply damage to a character
+    ///
+    /// Temporary HP absorbs damage first. The returned outcome reports whether
+    /// the character dropped to 0 HP or died, and the DC of any concentration
+    /// save they now need to make.
+    pub fn apply_damage(
+        &mut self,
+        character_id: i32,
+        amount: i32,
+        reason: Option<String>,
+    ) -> Result<(CharacterVersion, DamageOutcome)> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Damage cannot be negative".to_string(),
+            ));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        let outcome = char_data.take_damage(amount);
+
+        let snapshot_reason = reason.unwrap_or_else(|| {
+            let mut reason = format!(
+                "Took {} damage ({}/{} HP)",
+                amount, char_data.current_hp, char_data.max_hp
+            );
+            if outcome.temp_hp_absorbed > 0 {
+                reason.push_str(&format!(
+                    ", {} absorbed by temporary HP",
+                    outcome.temp_hp_absorbed
+                ));
+            }
+            if outcome.killed {
+                reason.push_str(", killed");
+            } else if outcome.dropped_to_zero {
+                reason.push_str(", dropped to 0 HP");
+            }
+            reason
+        });
+
+        let version = self.update_character(character_id, char_data, Some(snapshot_reason))?;
+        Ok((version, outcome))
+    }
+
+    /// Heal a character, up to their max HP
+    pub fn heal(
+        &mut self,
+        character_id: i32,
+        amount: i32,
+        reason: Option<String>,
+    ) -> Result<CharacterVersion> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Healing cannot be negative".to_string(),
+            ));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        if char_data.death_saves.is_dead() {
+            return Err(DbError::InvalidData(format!(
+                "{} is dead and can't be healed",
+                char_data.character_name
+            )));
+        }
+        char_data.heal(amount);
+
+        let snapshot_reason = reason.unwrap_or_else(|| {
+            format!(
+                "Healed {} HP ({}/{} HP)",
+                amount, char_data.current_hp, char_data.max_hp
+            )
+        });
+        self.update_character(character_id, char_data, Some(snapshot_reason))
+    }
+
+    /// Set a character's current HP
+    ///
+    /// Lowering HP is applied as damage, so temporary HP absorbs it first and
+    /// concentration and death saves are handled; raising HP is healing.
+    pub fn update_hp(
+        &mut self,
+        character_id: i32,
+        new_hp: i32,
+        reason: Option<String>,
+    ) -> Result<(CharacterVersion, DamageOutcome)> {
+        let (_character, char_data) = self.get_character(character_id)?;
+        let change = new_hp.clamp(0, char_data.max_hp) - char_data.current_hp;
+
+        if change < 0 {
+            self.apply_damage(character_id, -change, reason)
+        } else {
+            let version = self.heal(character_id, change, reason)?;
+            Ok((version, DamageOutcome::default()))
+        }
+    }
+
+    /// Grant temporary HP, keeping the existing amount if it is higher
+    pub fn set_temp_hp(&mut self, character_id: i32, amount: i32) -> Result<CharacterVersion> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Temporary HP cannot be negative".to_string(),
+            ));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        if !char_data.grant_temp_hp(amount) {
+            return Err(DbError::InvalidData(format!(
+                "{} already has {} temporary HP; temporary HP doesn't stack",
+                char_data.character_name, char_data.temp_hp
+            )));
+        }
+
+        let snapshot_reason = Some(format!("Gained {} temporary HP", amount));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Record a death saving throw from its d20 roll
+    pub fn record_death_save(&mut self, character_id: i32, roll: i32) -> Result<CharacterVersion> {
+        if !(1..=20).contains(&roll) {
+            return Err(DbError::InvalidData(format!(
+                "Death save roll must be between 1 and 20, got {}",
+                roll
+            )));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        if char_data.current_hp > 0 {
+            return Err(DbError::InvalidData(format!(
+                "{} is not at 0 HP",
+                char_data.character_name
+            )));
+        }
+        if char_data.death_saves.is_stable() || char_data.death_saves.is_dead() {
+            return Err(DbError::InvalidData(format!(
+                "{} is no longer making death saves",
+                char_data.character_name
+            )));
+        }
+        char_data.record_death_save(roll);
+
+        let saves = char_data.death_saves;
+        let snapshot_reason = Some(if char_data.current_hp > 0 {
+            format!("Rolled {} on a death save, regained 1 HP", roll)
+        } else {
+            format!(
+                "Rolled {} on a death save ({} successes, {} failures)",
+                roll, saves.successes, saves.failures
+            )
+        });
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Set a character's exhaustion level (0-6)
+    pub fn set_exhaustion(&mut self, character_id: i32, level: i32) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        char_data.set_exhaustion(level);
+
+        let snapshot_reason = Some(format!(
+            "Exhaustion level set to {}",
+            char_data.exhaustion_level
+        ));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Add a condition (e.g., "Poisoned", "Prone") to a character
+    pub fn add_condition(
+        &mut self,
+        character_id: i32,
+        condition: &str,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        if !char_data.add_condition(condition) {
+            return Err(DbError::InvalidData(format!(
+                "{} is already {}",
+                char_data.character_name, condition
+            )));
+        }
+
+        let snapshot_reason = Some(format!("Became {}", condition));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Remove a condition from a character
+    pub fn remove_condition(
+        &mut self,
+        character_id: i32,
+        condition: &str,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        if !char_data.remove_condition(condition) {
+            return Err(DbError::InvalidData(format!(
+                "{} is not {}",
+                char_data.character_name, condition
+            )));
+        }
+
+        let snapshot_reason = Some(format!("No longer {}", condition));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Set the spell a character is concentrating on, or end concentration with `None`
+    pub fn set_concentration(
+        &mut self,
+        character_id: i32,
+        spell_name: Option<&str>,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        let previous = std::mem::replace(
+            &mut char_data.concentration,
+            spell_name.map(|s| s.to_string()),
+        );
+
+        let snapshot_reason = Some(match (spell_name, previous) {
+            (Some(spell), _) => format!("Concentrating on {}", spell),
+            (None, Some(previous)) => format!("Ended concentration on {}", previous),
+            (None, None) => "Not concentrating".to_string(),
+        });
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
     // ===== Inventory Management Methods =====
 
     /// Add an item to character's inventory
@@ -785,6 +1198,7 @@ impl<'a> CharacterService<'a> {
         // Get weight and value from item
         let weight = item.weight.map(|w| w as f64).unwrap_or(0.0);
         let value = item.value.unwrap_or(0.0);
+        let charges = ItemCharges::from_catalog(&item);
 
         // Check if item already exists in inventory
         let existing_item = char_data.inventory.iter_mut().find(|i| i.name == item_name);
@@ -803,6 +1217,9 @@ impl<'a> CharacterService<'a> {
                     weight,
                     value,
                     notes,
+                    requires_attunement: item.requires_attunement.is_some(),
+                    attuned: false,
+                    charges,
                 });
         }
 
@@ -847,6 +1264,126 @@ impl<'a> CharacterService<'a> {
         self.update_character(character_id, char_data, snapshot_reason)
     }
 
+    /// Attune to or end attunement with an inventory item
+    ///
+    /// Only items whose catalog entry requires attunement can be attuned, and
+    /// a character can be attuned to at most three items at once.
+    pub fn set_item_attunement(
+        &mut self,
+        character_id: i32,
+        item_name: &str,
+        attuned: bool,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+        let attuned_count = char_data.attuned_count();
+
+        let item = char_data.get_item_mut(item_name).ok_or_else(|| {
+            DbError::InvalidData(format!("Item '{}' not found in inventory", item_name))
+        })?;
+        if item.attuned == attuned {
+            return Err(DbError::InvalidData(format!(
+                "Already {} {}",
+                if attuned { "attuned to" } else { "not attuned to" },
+                item.name
+            )));
+        }
+        if attuned {
+            if !item.requires_attunement {
+                return Err(DbError::InvalidData(format!(
+                    "{} does not require attunement",
+                    item.name
+                )));
+            }
+            if attuned_count >= MAX_ATTUNED_ITEMS {
+                return Err(DbError::InvalidData(format!(
+                    "Already attuned to {} items; end attunement with one first",
+                    MAX_ATTUNED_ITEMS
+                )));
+            }
+        }
+        item.attuned = attuned;
+
+        let snapshot_reason = Some(if attuned {
+            format!("Attuned to {}", item.name)
+        } else {
+            format!("Ended attunement with {}", item.name)
+        });
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Spend charges from an inventory item (e.g., a wand or staff)
+    ///
+    /// Fails without changing the character if fewer than `amount` charges remain.
+    pub fn use_item_charges(
+        &mut self,
+        character_id: i32,
+        item_name: &str,
+        amount: i32,
+    ) -> Result<CharacterVersion> {
+        if amount < 1 {
+            return Err(DbError::InvalidData(
+                "Amount must be at least 1".to_string(),
+            ));
+        }
+
+        let (_character, mut char_data) = self.get_character(character_id)?;
+
+        let item = char_data.get_item_mut(item_name).ok_or_else(|| {
+            DbError::InvalidData(format!("Item '{}' not found in inventory", item_name))
+        })?;
+        let item_name = item.name.clone();
+        let charges = item.charges.as_mut().ok_or_else(|| {
+            DbError::InvalidData(format!("{} has no charges", item_name))
+        })?;
+        if !charges.expend(amount) {
+            return Err(DbError::InvalidData(format!(
+                "Not enough charges on {} ({}/{}, need {})",
+                item_name, charges.current, charges.max, amount
+            )));
+        }
+
+        let snapshot_reason = Some(format!(
+            "Used {} charge(s) of {} ({}/{} remaining)",
+            amount, item_name, charges.current, charges.max
+        ));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
+    /// Restore charges to an inventory item outside of a rest
+    ///
+    /// Restores `amount` charges, or all of them if `amount` is `None`.
+    pub fn restore_item_charges(
+        &mut self,
+        character_id: i32,
+        item_name: &str,
+        amount: Option<i32>,
+    ) -> Result<CharacterVersion> {
+        let (_character, mut char_data) = self.get_character(character_id)?;
+
+        let item = char_data.get_item_mut(item_name).ok_or_else(|| {
+            DbError::InvalidData(format!("Item '{}' not found in inventory", item_name))
+        })?;
+        let item_name = item.name.clone();
+        let charges = item.charges.as_mut().ok_or_else(|| {
+            DbError::InvalidData(format!("{} has no charges", item_name))
+        })?;
+        match amount {
+            Some(amount) if amount < 1 => {
+                return Err(DbError::InvalidData(
+                    "Amount must be at least 1".to_string(),
+                ));
+            }
+            Some(amount) => charges.recover(amount),
+            None => charges.recover(charges.max),
+        }
+
+        let snapshot_reason = Some(format!(
+            "Recharged {} ({}/{})",
+            item_name, charges.current, charges.max
+        ));
+        self.update_character(character_id, char_data, snapshot_reason)
+    }
+
     /// Update character's currency
     ///
     /// Adds or subtracts currency. Use negative values to subtract.
@@ -1094,6 +1631,11 @@ mod tests {
             },
             max_hp: 12,
             current_hp: 12,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies {
                 skills: vec!["Athletics".to_string(), "Intimidation".to_string()],
                 saves: vec!["Strength".to_string(), "Constitution".to_string()],
@@ -1103,7 +1645,9 @@ mod tests {
                 languages: vec!["Common".to_string()],
             },
             class_features: vec![],
+            class_resources: vec![],
             feats: vec![],
+            optional_features: vec![],
             spells: SpellData::default(),
             inventory: vec![],
             currency: Currency::default(),
@@ -1426,6 +1970,97 @@ mod tests {
         assert_eq!(v2_data.level, 2);
     }
 
+    #[test]
+    fn test_diff_character_versions() {
+        let mut conn = setup_test_db();
+        let campaign_id = create_test_campaign(&mut conn);
+        let player_id = create_test_player(&mut conn);
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let campaign_dir = temp_dir.path().to_str().unwrap();
+
+        let mut service = CharacterService::new(&mut conn);
+        let mut character_data = create_test_character_data();
+        character_data.player_id = Some(player_id);
+
+        let created = service
+            .create_character(
+                Some(campaign_id),
+                Some(player_id),
+                false,
+                campaign_dir,
+                character_data.clone(),
+            )
+            .expect("Failed to create character");
+
+        character_data.current_hp -= 5;
+        service
+            .update_character(created.id, character_data, Some("Took damage".to_string()))
+            .unwrap();
+
+        let diff = service
+            .diff_character_versions(created.id, 1, 2)
+            .expect("Failed to diff versions");
+        assert_eq!(diff.fields.len(), 1);
+        assert_eq!(diff.fields[0].field, "current_hp");
+
+        let unchanged = service.diff_character_versions(created.id, 2, 2).unwrap();
+        assert!(unchanged.is_empty());
+
+        assert!(service.diff_character_versions(created.id, 1, 99).is_err());
+    }
+
+    #[test]
+    fn test_restore_character_version() {
+        let mut conn = setup_test_db();
+        let campaign_id = create_test_campaign(&mut conn);
+        let player_id = create_test_player(&mut conn);
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let campaign_dir = temp_dir.path().to_str().unwrap();
+
+        let mut service = CharacterService::new(&mut conn);
+        let mut character_data = create_test_character_data();
+        character_data.player_id = Some(player_id);
+
+        let created = service
+            .create_character(
+                Some(campaign_id),
+                Some(player_id),
+                false,
+                campaign_dir,
+                character_data.clone(),
+            )
+            .expect("Failed to create character");
+        let original = character_data.clone();
+
+        character_data.level = 2;
+        character_data.max_hp += 6;
+        service
+            .update_character(created.id, character_data, Some("Bad level up".to_string()))
+            .unwrap();
+
+        let restored = service
+            .restore_character_version(created.id, 1)
+            .expect("Failed to restore version");
+        assert_eq!(restored.version_number, 3);
+        assert_eq!(restored.level, 1);
+        assert_eq!(
+            restored.snapshot_reason.as_deref(),
+            Some("Restored version 1")
+        );
+
+        let (character, data) = service.get_character(created.id).unwrap();
+        assert_eq!(character.current_version, 3);
+        assert_eq!(character.current_level, 1);
+        assert_eq!(data.level, original.level);
+        assert_eq!(data.max_hp, original.max_hp);
+
+        // The bad version is still in history
+        assert_eq!(service.get_character_versions(created.id).unwrap().len(), 3);
+        assert!(service.restore_character_version(created.id, 42).is_err());
+    }
+
     #[test]
     fn test_get_character_version_not_found() {
         let mut conn = setup_test_db();
@@ -1472,6 +2107,7 @@ mod tests {
             hp_method: HpGainMethod::Roll(8),
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: Some("Test level up".to_string()),
         };
 
@@ -1491,6 +2127,44 @@ mod tests {
         assert_eq!(data.max_hp, 22);
     }
 
+    #[test]
+    fn test_level_up_gains_at_least_one_hp() {
+        let mut conn = setup_test_db();
+        let campaign_id = create_test_campaign(&mut conn);
+        let player_id = create_test_player(&mut conn);
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let campaign_dir = temp_dir.path().to_str().unwrap();
+
+        let mut service = CharacterService::new(&mut conn);
+        let mut character_data = create_test_character_data();
+        character_data.player_id = Some(player_id);
+        character_data.abilities.constitution = 6;
+
+        let created = service
+            .create_character(Some(campaign_id), Some(player_id), false, campaign_dir, character_data)
+            .expect("Failed to create character");
+
+        // A roll of 1 with a -2 CON modifier still adds 1 HP
+        let level_up_options = LevelUpOptions {
+            class_name: "Fighter".to_string(),
+            class_source: "PHB".to_string(),
+            hp_method: HpGainMethod::Roll(1),
+            asi_or_feat: None,
+            subclass_choice: None,
+            optional_features: Vec::new(),
+            snapshot_reason: None,
+        };
+        service
+            .level_up_character(created.id, level_up_options)
+            .expect("Failed to level up");
+
+        let (_character, data) = service
+            .get_character(created.id)
+            .expect("Failed to get character");
+        assert_eq!(data.max_hp, 13);
+    }
+
     #[test]
     fn test_level_up_with_average_hp() {
         let mut conn = setup_test_db();
@@ -1515,6 +2189,7 @@ mod tests {
             hp_method: HpGainMethod::Average,
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -1556,6 +2231,7 @@ mod tests {
                 hp_method: HpGainMethod::Average,
                 asi_or_feat: None,
                 subclass_choice: None,
+                optional_features: Vec::new(),
                 snapshot_reason: None,
             };
             service.level_up_character(created.id, options).unwrap();
@@ -1573,6 +2249,7 @@ mod tests {
                 increase2: None,
             }),
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: Some("Level 4 with ASI".to_string()),
         };
 
@@ -1614,6 +2291,7 @@ mod tests {
                 hp_method: HpGainMethod::Average,
                 asi_or_feat: None,
                 subclass_choice: None,
+                optional_features: Vec::new(),
                 snapshot_reason: None,
             };
             service.level_up_character(created.id, options).unwrap();
@@ -1625,6 +2303,7 @@ mod tests {
             hp_method: HpGainMethod::Average,
             asi_or_feat: Some(AsiOrFeat::Feat("Great Weapon Master".to_string())),
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -1666,6 +2345,7 @@ mod tests {
             hp_method: HpGainMethod::Average,
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: Some("Multiclass to Barbarian".to_string()),
         };
 
@@ -1709,6 +2389,7 @@ mod tests {
             hp_method: HpGainMethod::Average,
             asi_or_feat: None,
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
@@ -1743,6 +2424,7 @@ mod tests {
                 hp_method: HpGainMethod::Average,
                 asi_or_feat: None,
                 subclass_choice: None,
+                optional_features: Vec::new(),
                 snapshot_reason: None,
             };
             service.level_up_character(created.id, options).unwrap();
@@ -1759,6 +2441,7 @@ mod tests {
                 increase2: None,
             }),
             subclass_choice: None,
+            optional_features: Vec::new(),
             snapshot_reason: None,
         };
 
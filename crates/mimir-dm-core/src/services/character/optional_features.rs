@@ -0,0 +1,501 @@
+//! Optional class feature choices
+//!
+//! Eldritch Invocations, Fighting Styles, Metamagic and Battle Master
+//! maneuvers are chosen from `catalog_optional_features`. How many a class
+//! knows at each level is read from the class or subclass
+//! `optionalfeatureProgression` when it has been imported, with the PHB
+//! progressions as a fallback.
+
+use super::resources::find_class;
+use crate::connection::DbConnection;
+use crate::error::{DbError, Result};
+use crate::models::catalog::optionalfeature::{Prerequisite, PrerequisiteLevel};
+use crate::models::catalog::{OptionalFeature, OptionalFeatureSummary, Subclass};
+use crate::models::character::data::{CharacterData, ClassLevel, OptionalFeatureChoice};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// An optional feature picked during level up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionalFeatureSelection {
+    /// Feature name (case-insensitive)
+    pub name: String,
+    /// Source book, when the same name exists in several books
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// Optional features a class knows at its current level and the catalog
+/// entries that can fill the open choices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionalFeatureGain {
+    /// Progression name (e.g., "Eldritch Invocations")
+    pub name: String,
+    /// Class granting the choices
+    pub class_name: String,
+    /// 5etools feature type codes accepted (e.g., `["EI"]`)
+    pub feature_types: Vec<String>,
+    /// Number of features of this kind known at the class level
+    pub total: i32,
+    /// Choices not yet made
+    pub remaining: i32,
+    /// Catalog features whose prerequisites the character meets
+    pub available: Vec<OptionalFeatureSummary>,
+}
+
+/// A class or subclass `optionalfeatureProgression` entry
+struct FeatureProgression {
+    name: String,
+    feature_types: Vec<String>,
+    progression: Value,
+}
+
+impl FeatureProgression {
+    fn parse(value: &Value) -> Option<Self> {
+        let name = value.get("name")?.as_str()?.to_string();
+        let feature_types: Vec<String> = value
+            .get("featureType")?
+            .as_array()?
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect();
+        let progression = value.get("progression")?.clone();
+        if feature_types.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name,
+            feature_types,
+            progression,
+        })
+    }
+
+    fn fallback(name: &str, feature_type: &str, progression: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            feature_types: vec![feature_type.to_string()],
+            progression,
+        }
+    }
+
+    /// Number of features known at a class level
+    ///
+    /// Progressions are either an array indexed by level or an object of
+    /// `{"level": total}` breakpoints.
+    fn total_at(&self, level: i32) -> i32 {
+        match &self.progression {
+            Value::Array(totals) => usize::try_from(level - 1)
+                .ok()
+                .and_then(|i| totals.get(i))
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0) as i32,
+            Value::Object(breakpoints) => breakpoints
+                .iter()
+                .filter_map(|(lvl, total)| Some((lvl.parse::<i32>().ok()?, total.as_i64()?)))
+                .filter(|(lvl, _)| *lvl <= level)
+                .max_by_key(|(lvl, _)| *lvl)
+                .map_or(0, |(_, total)| total as i32),
+            _ => 0,
+        }
+    }
+}
+
+/// PHB progressions used when the class hasn't been imported
+fn fallback_progressions(class_level: &ClassLevel) -> Vec<FeatureProgression> {
+    match class_level.class_name.to_lowercase().as_str() {
+        "warlock" => vec![FeatureProgression::fallback(
+            "Eldritch Invocations",
+            "EI",
+            json!([0, 2, 2, 2, 3, 3, 4, 4, 5, 5, 5, 6, 6, 6, 7, 7, 7, 8, 8, 8]),
+        )],
+        "sorcerer" => vec![FeatureProgression::fallback(
+            "Metamagic",
+            "MM",
+            json!({"3": 2, "10": 3, "17": 4}),
+        )],
+        "fighter" => vec![FeatureProgression::fallback(
+            "Fighting Style",
+            "FS:F",
+            json!({"1": 1}),
+        )],
+        "paladin" => vec![FeatureProgression::fallback(
+            "Fighting Style",
+            "FS:P",
+            json!({"2": 1}),
+        )],
+        "ranger" => vec![FeatureProgression::fallback(
+            "Fighting Style",
+            "FS:R",
+            json!({"2": 1}),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// PHB subclass progressions used when the subclass hasn't been imported
+fn fallback_subclass_progressions(subclass: &str) -> Vec<FeatureProgression> {
+    if subclass.to_lowercase().contains("battle master") {
+        vec![FeatureProgression::fallback(
+            "Maneuvers",
+            "MV:B",
+            json!({"3": 3, "7": 5, "10": 7, "15": 9}),
+        )]
+    } else {
+        Vec::new()
+    }
+}
+
+fn parse_progressions(values: Option<&Vec<Value>>) -> Option<Vec<FeatureProgression>> {
+    let progressions: Vec<FeatureProgression> = values?
+        .iter()
+        .filter_map(FeatureProgression::parse)
+        .collect();
+    (!progressions.is_empty()).then_some(progressions)
+}
+
+/// Look up a subclass by name or short name
+fn find_subclass(conn: &mut DbConnection, class_name: &str, subclass: &str) -> Option<Subclass> {
+    use crate::schema::catalog_subclasses::dsl;
+
+    let records: Vec<String> = dsl::catalog_subclasses
+        .filter(dsl::class_name.eq(class_name))
+        .filter(dsl::name.eq(subclass).or(dsl::short_name.eq(subclass)))
+        .select(dsl::full_subclass_json)
+        .load(conn)
+        .ok()?;
+
+    records
+        .iter()
+        .find_map(|json| serde_json::from_str::<Subclass>(json).ok())
+}
+
+/// Optional feature progressions for a class, including its subclass
+fn class_progressions(
+    conn: &mut DbConnection,
+    class_level: &ClassLevel,
+) -> Vec<FeatureProgression> {
+    let class = find_class(conn, &class_level.class_name);
+    let mut progressions = parse_progressions(
+        class
+            .as_ref()
+            .and_then(|c| c.optionalfeature_progression.as_ref()),
+    )
+    .unwrap_or_else(|| fallback_progressions(class_level));
+
+    if let Some(subclass_name) = &class_level.subclass {
+        let subclass = find_subclass(conn, &class_level.class_name, subclass_name);
+        progressions.extend(
+            parse_progressions(
+                subclass
+                    .as_ref()
+                    .and_then(|s| s.optionalfeature_progression.as_ref()),
+            )
+            .unwrap_or_else(|| fallback_subclass_progressions(subclass_name)),
+        );
+    }
+
+    progressions
+}
+
+/// Load catalog optional features of any of the given types
+fn load_catalog_features(
+    conn: &mut DbConnection,
+    feature_types: &[String],
+) -> Result<Vec<OptionalFeature>> {
+    use crate::schema::catalog_optional_features::dsl;
+
+    let mut features: Vec<OptionalFeature> = Vec::new();
+    for feature_type in feature_types {
+        let records: Vec<String> = dsl::catalog_optional_features
+            .filter(dsl::feature_types.like(format!("%\"{}\"%", feature_type)))
+            .select(dsl::full_optional_feature_json)
+            .load(conn)?;
+
+        for json in records {
+            let Ok(feature) = serde_json::from_str::<OptionalFeature>(&json) else {
+                continue;
+            };
+            if !features
+                .iter()
+                .any(|f| f.name == feature.name && f.source == feature.source)
+            {
+                features.push(feature);
+            }
+        }
+    }
+
+    features.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.source.cmp(&b.source)));
+    Ok(features)
+}
+
+fn has_named_feature(character: &CharacterData, name: &str) -> bool {
+    let name = name.to_lowercase();
+    character
+        .class_features
+        .iter()
+        .map(|f| &f.name)
+        .chain(character.optional_features.iter().map(|f| &f.name))
+        .chain(character.feats.iter())
+        .any(|f| f.to_lowercase().contains(&name))
+}
+
+fn knows_spell(character: &CharacterData, spell: &str) -> bool {
+    // 5etools spell prerequisites look like "eldritch blast#c" or "hex|phb"
+    let name = spell.split(['#', '|']).next().unwrap_or(spell).trim();
+    let spells = &character.spells;
+    spells
+        .cantrips
+        .iter()
+        .chain(spells.known_spells.iter())
+        .chain(spells.prepared_spells.iter())
+        .any(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+fn subclass_matches(class_level: &ClassLevel, subclass: &str) -> bool {
+    class_level
+        .subclass
+        .as_ref()
+        .is_some_and(|s| s.to_lowercase().contains(&subclass.to_lowercase()))
+}
+
+fn prerequisite_met(prerequisite: &Prerequisite, character: &CharacterData) -> bool {
+    if let Some(level) = &prerequisite.level {
+        let met = match level {
+            PrerequisiteLevel::Simple(level) => character.level >= *level,
+            PrerequisiteLevel::Complex {
+                level,
+                class: Some(class),
+                subclass,
+            } => character.classes.iter().any(|c| {
+                c.class_name.eq_ignore_ascii_case(&class.name)
+                    && c.level >= *level
+                    && subclass.as_ref().is_none_or(|s| {
+                        subclass_matches(c, &s.name)
+                            || s.short_name
+                                .as_ref()
+                                .is_some_and(|n| subclass_matches(c, n))
+                    })
+            }),
+            PrerequisiteLevel::Complex { level, .. } => character.level >= *level,
+        };
+        if !met {
+            return false;
+        }
+    }
+
+    if let Some(pact) = &prerequisite.pact {
+        if !has_named_feature(character, &format!("pact of the {}", pact)) {
+            return false;
+        }
+    }
+
+    if let Some(patron) = &prerequisite.patron {
+        if !character
+            .classes
+            .iter()
+            .any(|c| subclass_matches(c, patron))
+        {
+            return false;
+        }
+    }
+
+    if let Some(spells) = &prerequisite.spell {
+        if !spells.iter().all(|s| knows_spell(character, s)) {
+            return false;
+        }
+    }
+
+    if let Some(features) = &prerequisite.feature {
+        if !features.iter().all(|f| has_named_feature(character, f)) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Check an optional feature's prerequisites against a character
+///
+/// Each prerequisite entry is an alternative; all conditions within an entry
+/// must hold. Item and free-text prerequisites are left to the DM.
+pub fn prerequisites_met(feature: &OptionalFeature, character: &CharacterData) -> bool {
+    match &feature.prerequisite {
+        Some(prerequisites) if !prerequisites.is_empty() => {
+            prerequisites.iter().any(|p| prerequisite_met(p, character))
+        }
+        _ => true,
+    }
+}
+
+/// Optional features a character's class knows at its current class level,
+/// with the open choices and the catalog features available to fill them
+///
+/// Returns an empty list for classes without optional features. Features the
+/// character already has or whose prerequisites aren't met are left out.
+pub fn optional_feature_gains(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+    class_name: &str,
+) -> Result<Vec<OptionalFeatureGain>> {
+    let Some(class_level) = character
+        .classes
+        .iter()
+        .find(|c| c.class_name.eq_ignore_ascii_case(class_name))
+    else {
+        return Err(DbError::InvalidData(format!(
+            "Character does not have class '{}'",
+            class_name
+        )));
+    };
+
+    let mut gains = Vec::new();
+    for progression in class_progressions(conn, class_level) {
+        let total = progression.total_at(class_level.level);
+        if total <= 0 {
+            continue;
+        }
+
+        let chosen = character
+            .optional_features
+            .iter()
+            .filter(|f| {
+                f.class_name.eq_ignore_ascii_case(&class_level.class_name)
+                    && progression.feature_types.contains(&f.feature_type)
+            })
+            .count() as i32;
+
+        let available = load_catalog_features(conn, &progression.feature_types)?
+            .iter()
+            .filter(|f| {
+                !character
+                    .optional_features
+                    .iter()
+                    .any(|c| c.name.eq_ignore_ascii_case(&f.name))
+            })
+            .filter(|f| prerequisites_met(f, character))
+            .map(OptionalFeatureSummary::from)
+            .collect();
+
+        gains.push(OptionalFeatureGain {
+            name: progression.name,
+            class_name: class_level.class_name.clone(),
+            feature_types: progression.feature_types,
+            total,
+            remaining: (total - chosen).max(0),
+            available,
+        });
+    }
+
+    Ok(gains)
+}
+
+/// Validate and record optional feature selections for a class
+///
+/// Each selection must fill an open choice at the character's current class
+/// level and be available per `optional_feature_gains`.
+pub fn apply_optional_feature_choices(
+    conn: &mut DbConnection,
+    character: &mut CharacterData,
+    class_name: &str,
+    selections: &[OptionalFeatureSelection],
+) -> Result<Vec<OptionalFeatureChoice>> {
+    if selections.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut gains = optional_feature_gains(conn, character, class_name)?;
+    let class_level = character
+        .classes
+        .iter()
+        .find(|c| c.class_name.eq_ignore_ascii_case(class_name))
+        .map_or(0, |c| c.level);
+
+    let mut choices = Vec::new();
+    for selection in selections {
+        let matches = |f: &OptionalFeatureSummary| {
+            f.name.eq_ignore_ascii_case(&selection.name)
+                && selection
+                    .source
+                    .as_ref()
+                    .is_none_or(|s| f.source.eq_ignore_ascii_case(s))
+        };
+
+        let Some(gain) = gains.iter_mut().find(|g| g.available.iter().any(matches)) else {
+            return Err(DbError::InvalidData(format!(
+                "'{}' is not an available optional feature for {} level {} \
+                 (unknown, already chosen, or prerequisites not met)",
+                selection.name, class_name, class_level
+            )));
+        };
+
+        if gain.remaining <= 0 {
+            return Err(DbError::InvalidData(format!(
+                "No {} choices remaining for {} level {}",
+                gain.name, class_name, class_level
+            )));
+        }
+
+        let index = gain.available.iter().position(matches).unwrap_or_default();
+        let feature = gain.available.remove(index);
+        gain.remaining -= 1;
+
+        let feature_type = feature
+            .feature_types
+            .iter()
+            .find(|t| gain.feature_types.contains(t))
+            .cloned()
+            .unwrap_or_default();
+
+        let choice = OptionalFeatureChoice {
+            name: feature.name,
+            source: feature.source,
+            feature_type,
+            class_name: gain.class_name.clone(),
+            level: class_level,
+        };
+        character.optional_features.push(choice.clone());
+        choices.push(choice);
+    }
+
+    Ok(choices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progression(value: Value) -> FeatureProgression {
+        FeatureProgression::fallback("Test", "EI", value)
+    }
+
+    #[test]
+    fn test_progression_totals() {
+        let array = progression(json!([0, 2, 2, 2, 3]));
+        assert_eq!(array.total_at(1), 0);
+        assert_eq!(array.total_at(2), 2);
+        assert_eq!(array.total_at(5), 3);
+        assert_eq!(array.total_at(6), 0);
+
+        let breakpoints = progression(json!({"3": 3, "7": 5, "10": 7, "15": 9}));
+        assert_eq!(breakpoints.total_at(2), 0);
+        assert_eq!(breakpoints.total_at(3), 3);
+        assert_eq!(breakpoints.total_at(9), 5);
+        assert_eq!(breakpoints.total_at(20), 9);
+    }
+
+    #[test]
+    fn test_parse_progression() {
+        let parsed = FeatureProgression::parse(&json!({
+            "name": "Eldritch Invocations",
+            "featureType": ["EI"],
+            "progression": [0, 2, 2]
+        }))
+        .unwrap();
+        assert_eq!(parsed.name, "Eldritch Invocations");
+        assert_eq!(parsed.feature_types, vec!["EI"]);
+        assert_eq!(parsed.total_at(3), 2);
+
+        assert!(FeatureProgression::parse(&json!({"name": "Broken"})).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 //! Character progression service for level up operations
 
 use super::{
-    AsiOrFeat, CharacterService, ClassInfo, HpGainMethod, LevelUpOptions, MulticlassPrerequisites,
+    AsiOrFeat, CharacterService, ClassInfo, LevelUpOptions, MulticlassPrerequisites,
 };
 use crate::{
     connection::DbConnection,
@@ -48,12 +48,9 @@ impl<'a> CharacterProgressionService<'a> {
         // Validate HP gain
         options.validate_hp_gain(class_info.hit_die_value)?;
 
-        // Calculate HP gain
+        // Calculate HP gain; a level always adds at least 1 HP
         let con_modifier = char_data.abilities.con_modifier();
-        let hp_gain = match options.hp_method {
-            HpGainMethod::Roll(value) => value + con_modifier,
-            HpGainMethod::Average => class_info.average_hp_gain() + con_modifier,
-        };
+        let hp_gain = (class_info.hp_gain(&options.hp_method) + con_modifier).max(1);
 
         // Update HP
         char_data.max_hp += hp_gain;
@@ -125,6 +122,17 @@ impl<'a> CharacterProgressionService<'a> {
             }
         }
 
+        // Record optional feature choices against the new class level
+        super::optional_features::apply_optional_feature_choices(
+            self.conn,
+            &mut char_data,
+            &options.class_name,
+            &options.optional_features,
+        )?;
+
+        // Recalculate spell slots, pact magic and class resources for the new level
+        super::resources::refresh_resources(self.conn, &mut char_data)?;
+
         // Update snapshot reason
         let snapshot_reason = options
             .snapshot_reason
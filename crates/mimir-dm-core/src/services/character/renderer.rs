@@ -2,8 +2,11 @@
 //!
 //! Generates human-readable markdown character sheets from CharacterData.
 
+use super::derived_stats::{calculate_derived_stats, DerivedStats};
+use crate::models::catalog::optionalfeature::format_feature_types;
 use crate::models::catalog::{Item, Spell};
-use crate::models::character::CharacterData;
+use crate::models::character::data::MAX_ATTUNED_ITEMS;
+use crate::models::character::{CharacterData, ItemRecharge, RestType};
 use std::collections::HashMap;
 
 /// Trait for rendering character sheets in various formats.
@@ -106,22 +109,13 @@ impl MarkdownRenderer {
         output
     }
 
-    fn render_combat_stats(&self, character: &CharacterData) -> String {
+    fn render_combat_stats(&self, character: &CharacterData, stats: &DerivedStats) -> String {
         let mut output = String::from("## Combat Stats\n\n");
 
         let dex_mod = character.abilities.dex_modifier();
         let wis_mod = character.abilities.wis_modifier();
         let prof_bonus = character.proficiency_bonus();
 
-        // Calculate AC (base 10 + DEX, note armor if equipped)
-        let base_ac = 10 + dex_mod;
-        let shield_bonus = if character.equipped.shield.is_some() {
-            2
-        } else {
-            0
-        };
-        let ac = base_ac + shield_bonus;
-
         // Calculate Passive Perception
         let perception_prof = character
             .proficiencies
@@ -135,19 +129,32 @@ impl MarkdownRenderer {
         output.push_str("|:--:|:----------:|:-----:|:------------------:|\n");
         output.push_str(&format!(
             "| {} | {:+} | 30 ft | {} |\n\n",
-            ac, dex_mod, passive_perception
+            stats.armor_class, dex_mod, passive_perception
         ));
 
-        // Note armor if equipped
-        if let Some(armor) = &character.equipped.armor {
-            output.push_str(&format!("*Armor: {}*\n\n", armor));
+        // Show where the AC comes from
+        if !stats.ac_breakdown.is_empty() {
+            output.push_str(&format!("*AC: {}*\n\n", stats.ac_breakdown.join(", ")));
         }
 
         // HP and Hit Dice
-        output.push_str(&format!(
-            "**HP:** {} / {}\n",
-            character.current_hp, character.max_hp
-        ));
+        if character.temp_hp > 0 {
+            output.push_str(&format!(
+                "**HP:** {} / {} (+{} temporary)\n",
+                character.current_hp, character.max_hp, character.temp_hp
+            ));
+        } else {
+            output.push_str(&format!(
+                "**HP:** {} / {}\n",
+                character.current_hp, character.max_hp
+            ));
+        }
+        if character.current_hp == 0 {
+            output.push_str(&format!(
+                "**Death Saves:** {} successes, {} failures\n",
+                character.death_saves.successes, character.death_saves.failures
+            ));
+        }
 
         // Render hit dice for each class
         let hit_dice_str = character
@@ -159,111 +166,63 @@ impl MarkdownRenderer {
         output.push_str(&format!("**Hit Dice:** {}\n", hit_dice_str));
         output.push_str(&format!("**Proficiency Bonus:** +{}\n\n", prof_bonus));
 
+        // Live play state
+        let mut status = Vec::new();
+        if !character.conditions.is_empty() {
+            status.push(format!(
+                "**Conditions:** {}",
+                character.conditions.join(", ")
+            ));
+        }
+        if character.exhaustion_level > 0 {
+            status.push(format!(
+                "**Exhaustion:** Level {}",
+                character.exhaustion_level
+            ));
+        }
+        if let Some(spell) = &character.concentration {
+            status.push(format!("**Concentrating on:** {}", spell));
+        }
+        if !status.is_empty() {
+            output.push_str(&status.join("\n"));
+            output.push_str("\n\n");
+        }
+
         output
     }
 
-    fn render_attacks(&self, character: &CharacterData) -> String {
-        let equipped = &character.equipped;
-
+    fn render_attacks(&self, stats: &DerivedStats) -> String {
         // Only render if character has weapons equipped
-        if equipped.main_hand.is_none() && equipped.off_hand.is_none() {
+        if stats.attacks.is_empty() {
             return String::new();
         }
 
         let mut output = String::from("## Attacks\n\n");
-        let prof_bonus = character.proficiency_bonus();
-        let str_mod = character.abilities.str_modifier();
-        let dex_mod = character.abilities.dex_modifier();
-
-        output.push_str("| Name | Attack Bonus | Damage/Type |\n");
-        output.push_str("|------|:------------:|-------------|\n");
-
-        // Main hand weapon
-        if let Some(weapon) = &equipped.main_hand {
-            let weapon_lower = weapon.to_lowercase();
-
-            // Determine if finesse or ranged (use DEX), otherwise STR
-            let is_finesse_or_ranged = weapon_lower.contains("rapier")
-                || weapon_lower.contains("dagger")
-                || weapon_lower.contains("shortsword")
-                || weapon_lower.contains("scimitar")
-                || weapon_lower.contains("whip")
-                || weapon_lower.contains("bow")
-                || weapon_lower.contains("crossbow")
-                || weapon_lower.contains("dart")
-                || weapon_lower.contains("sling");
-
-            // Ranged weapons always use DEX; finesse weapons use better of STR/DEX
-            let is_ranged = weapon_lower.contains("bow")
-                || weapon_lower.contains("crossbow")
-                || weapon_lower.contains("dart")
-                || weapon_lower.contains("sling");
-            let ability_mod = if is_ranged || (is_finesse_or_ranged && dex_mod > str_mod) {
-                dex_mod
-            } else {
-                str_mod
-            };
+        output.push_str("| Name | Attack Bonus | Damage/Type | Notes |\n");
+        output.push_str("|------|:------------:|-------------|-------|\n");
 
-            let attack_bonus = prof_bonus + ability_mod;
-
-            // Estimate damage based on common weapons
-            let damage = match weapon_lower.as_str() {
-                w if w.contains("greatsword") || w.contains("maul") => {
-                    format!("2d6{:+}", ability_mod)
-                }
-                w if w.contains("greataxe") => format!("1d12{:+}", ability_mod),
-                w if w.contains("longsword")
-                    || w.contains("warhammer")
-                    || w.contains("battleaxe") =>
-                {
-                    format!("1d8{:+}", ability_mod)
-                }
-                w if w.contains("rapier") => format!("1d8{:+}", ability_mod),
-                w if w.contains("shortsword") || w.contains("scimitar") => {
-                    format!("1d6{:+}", ability_mod)
-                }
-                w if w.contains("dagger") => format!("1d4{:+}", ability_mod),
-                w if w.contains("quarterstaff") || w.contains("spear") => {
-                    format!("1d6{:+}", ability_mod)
-                }
-                w if w.contains("longbow") => format!("1d8{:+}", ability_mod),
-                w if w.contains("shortbow") || w.contains("light crossbow") => {
-                    format!("1d6{:+}", ability_mod)
-                }
-                w if w.contains("heavy crossbow") => format!("1d10{:+}", ability_mod),
-                w if w.contains("handaxe") || w.contains("javelin") || w.contains("mace") => {
-                    format!("1d6{:+}", ability_mod)
-                }
-                _ => format!("1d6{:+}", ability_mod), // Default
-            };
+        for attack in &stats.attacks {
+            let mut notes = Vec::new();
+            if let Some(versatile) = &attack.versatile_damage {
+                notes.push(format!("Two-handed: {}", versatile));
+            }
+            if let Some(range) = &attack.range {
+                notes.push(format!("Range {}", range));
+            }
+            notes.extend(attack.properties.iter().cloned());
+            if !attack.from_catalog {
+                notes.push("Not in catalog".to_string());
+            }
 
             output.push_str(&format!(
-                "| {} | {:+} | {} |\n",
-                weapon, attack_bonus, damage
+                "| {} | {:+} | {} | {} |\n",
+                attack.name,
+                attack.attack_bonus,
+                attack.damage,
+                notes.join(", ")
             ));
         }
 
-        // Off hand weapon (if different from shield)
-        if let Some(weapon) = &equipped.off_hand {
-            let weapon_lower = weapon.to_lowercase();
-            if !weapon_lower.contains("shield") {
-                let _ability_mod = str_mod; // Off-hand typically doesn't add ability to damage
-                let attack_bonus = prof_bonus + str_mod;
-
-                let damage = match weapon_lower.as_str() {
-                    w if w.contains("dagger") => "1d4".to_string(),
-                    w if w.contains("shortsword") => "1d6".to_string(),
-                    w if w.contains("handaxe") => "1d6".to_string(),
-                    _ => "1d6".to_string(),
-                };
-
-                output.push_str(&format!(
-                    "| {} | {:+} | {} |\n",
-                    weapon, attack_bonus, damage
-                ));
-            }
-        }
-
         output.push('\n');
         output
     }
@@ -435,6 +394,47 @@ impl MarkdownRenderer {
         output
     }
 
+    fn render_optional_features(&self, character: &CharacterData) -> String {
+        if character.optional_features.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::from("## Optional Features\n\n");
+        for feature in &character.optional_features {
+            output.push_str(&format!(
+                "- **{}** ({}, {} {})\n",
+                feature.name,
+                format_feature_types(std::slice::from_ref(&feature.feature_type)),
+                feature.class_name,
+                feature.level
+            ));
+        }
+        output.push('\n');
+
+        output
+    }
+
+    fn render_class_resources(&self, character: &CharacterData) -> String {
+        if character.class_resources.is_empty() {
+            return String::new();
+        }
+
+        let mut output = String::from("## Class Resources\n\n");
+        for resource in &character.class_resources {
+            let recovery = match resource.recovery {
+                RestType::Short => "short rest",
+                RestType::Long => "long rest",
+            };
+            output.push_str(&format!(
+                "- **{}** ({}): {} / {} - recovers on a {}\n",
+                resource.name, resource.class_name, resource.current, resource.max, recovery
+            ));
+        }
+        output.push('\n');
+
+        output
+    }
+
     fn render_feats(&self, character: &CharacterData) -> String {
         if character.feats.is_empty() {
             return String::new();
@@ -460,6 +460,7 @@ impl MarkdownRenderer {
         if spells.cantrips.is_empty()
             && spells.known_spells.is_empty()
             && spells.spell_slots.is_empty()
+            && spells.pact_slots.is_none()
         {
             return String::new();
         }
@@ -483,6 +484,14 @@ impl MarkdownRenderer {
             output.push('\n');
         }
 
+        // Pact magic slots
+        if let Some(pact) = &spells.pact_slots {
+            output.push_str(&format!(
+                "**Pact Magic:** {} / {} slots (level {})\n\n",
+                pact.current, pact.max, pact.slot_level
+            ));
+        }
+
         // Cantrips with full details
         if !spells.cantrips.is_empty() {
             output.push_str("### Cantrips\n\n");
@@ -702,6 +711,14 @@ impl MarkdownRenderer {
 
         let mut output = String::from("## Inventory\n\n");
 
+        if character.inventory.iter().any(|i| i.requires_attunement) {
+            output.push_str(&format!(
+                "**Attuned Items:** {} / {}\n\n",
+                character.attuned_count(),
+                MAX_ATTUNED_ITEMS
+            ));
+        }
+
         for item in &character.inventory {
             // Item header with quantity
             if item.quantity > 1 {
@@ -710,6 +727,31 @@ impl MarkdownRenderer {
                 output.push_str(&format!("### {}\n\n", item.name));
             }
 
+            // Magic item state
+            let mut state = Vec::new();
+            if item.attuned {
+                state.push("Attuned".to_string());
+            } else if item.requires_attunement {
+                state.push("Requires attunement".to_string());
+            }
+            if let Some(charges) = &item.charges {
+                let recharge = match charges.recharge {
+                    Some(ItemRecharge::Dawn) => " (recharges at dawn)",
+                    Some(ItemRecharge::Dusk) => " (recharges at dusk)",
+                    Some(ItemRecharge::Midnight) => " (recharges at midnight)",
+                    Some(ItemRecharge::ShortRest) => " (recharges on a short rest)",
+                    Some(ItemRecharge::LongRest) => " (recharges on a long rest)",
+                    Some(ItemRecharge::Special) | None => "",
+                };
+                state.push(format!(
+                    "Charges: {} / {}{}",
+                    charges.current, charges.max, recharge
+                ));
+            }
+            if !state.is_empty() {
+                output.push_str(&format!("**{}**\n\n", state.join(" · ")));
+            }
+
             // Custom notes (flavor text) - displayed prominently
             if let Some(notes) = &item.notes {
                 output.push_str(&format!("> **Notes:** {}\n\n", notes));
@@ -925,6 +967,7 @@ impl CharacterRenderer for MarkdownRenderer {
         item_details: &HashMap<String, Item>,
     ) -> String {
         let mut output = String::new();
+        let stats = calculate_derived_stats(character, item_details);
 
         output.push_str(&self.render_header(character));
         output.push_str(&self.render_metadata(character));
@@ -932,12 +975,14 @@ impl CharacterRenderer for MarkdownRenderer {
         output.push_str(&self.render_npc_info(character));
         output.push_str(&self.render_legendary_actions(character));
         output.push_str(&self.render_ability_scores(character));
-        output.push_str(&self.render_combat_stats(character));
-        output.push_str(&self.render_attacks(character));
+        output.push_str(&self.render_combat_stats(character, &stats));
+        output.push_str(&self.render_attacks(&stats));
         output.push_str(&self.render_saving_throws(character));
         output.push_str(&self.render_skills(character));
         output.push_str(&self.render_proficiencies(character));
         output.push_str(&self.render_class_features(character));
+        output.push_str(&self.render_optional_features(character));
+        output.push_str(&self.render_class_resources(character));
         output.push_str(&self.render_feats(character));
         output.push_str(&self.render_spells(character, spell_details));
         output.push_str(&self.render_equipment(character));
@@ -954,8 +999,8 @@ mod tests {
     use super::*;
     use crate::models::character::data::{ClassLevel, Currency, LegendaryAction};
     use crate::models::character::{
-        AbilityScores, EquippedItems, FeatureReference, InventoryItem, Personality, Proficiencies,
-        SpellData, SpellReference, SpellSlots,
+        AbilityScores, DeathSaves, EquippedItems, FeatureReference, InventoryItem, Personality,
+        Proficiencies, SpellData, SpellReference, SpellSlots,
     };
 
     fn create_sample_fighter() -> CharacterData {
@@ -988,6 +1033,11 @@ mod tests {
             },
             max_hp: 28,
             current_hp: 28,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies {
                 skills: vec!["Athletics".to_string(), "Intimidation".to_string()],
                 saves: vec!["Strength".to_string(), "Constitution".to_string()],
@@ -1019,7 +1069,9 @@ mod tests {
                     level: 2,
                 },
             ],
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: vec![
                 InventoryItem {
@@ -1029,6 +1081,9 @@ mod tests {
                     weight: 20.0,
                     value: 5.0,
                     notes: None,
+                    requires_attunement: false,
+                    attuned: false,
+                    charges: None,
                 },
                 InventoryItem {
                     name: "Healing Potion".to_string(),
@@ -1037,6 +1092,9 @@ mod tests {
                     weight: 1.0,
                     value: 50.0,
                     notes: Some("Greater healing".to_string()),
+                    requires_attunement: false,
+                    attuned: false,
+                    charges: None,
                 },
             ],
             currency: Currency::default(),
@@ -1098,6 +1156,11 @@ mod tests {
             },
             max_hp: 15,
             current_hp: 15,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies {
                 skills: vec![
                     "Arcana".to_string(),
@@ -1137,7 +1200,9 @@ mod tests {
                     level: 2,
                 },
             ],
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData {
                 cantrips: vec![
                     SpellReference::new("Fire Bolt", "PHB"),
@@ -1157,6 +1222,7 @@ mod tests {
                     SpellReference::new("Fireball", "PHB"),
                 ],
                 spell_slots,
+                pact_slots: None,
             },
             inventory: vec![InventoryItem {
                 name: "Spellbook".to_string(),
@@ -1165,6 +1231,9 @@ mod tests {
                 weight: 3.0,
                 value: 50.0,
                 notes: Some("Contains all known spells".to_string()),
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             }],
             currency: Currency::default(),
             speed: 30, // Elf speed
@@ -1284,9 +1353,16 @@ mod tests {
             },
             max_hp: 12,
             current_hp: 12,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies::default(),
             class_features: Vec::new(),
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: Vec::new(),
             currency: Currency::default(),
@@ -1315,6 +1391,137 @@ mod tests {
         assert!(markdown.contains("## Combat Stats"));
     }
 
+    #[test]
+    fn test_render_combat_state() {
+        let renderer = MarkdownRenderer::new();
+        let mut character = create_sample_fighter();
+
+        let markdown = renderer.render(&character);
+        assert!(!markdown.contains("**Conditions:**"));
+        assert!(!markdown.contains("**Death Saves:**"));
+
+        character.temp_hp = 5;
+        character.conditions = vec!["Poisoned".to_string(), "Prone".to_string()];
+        character.exhaustion_level = 2;
+        character.concentration = Some("Bless".to_string());
+
+        let markdown = renderer.render(&character);
+        assert!(markdown.contains("**HP:** 28 / 28 (+5 temporary)"));
+        assert!(markdown.contains("**Conditions:** Poisoned, Prone"));
+        assert!(markdown.contains("**Exhaustion:** Level 2"));
+        assert!(markdown.contains("**Concentrating on:** Bless"));
+
+        character.current_hp = 0;
+        character.death_saves.failures = 2;
+        let markdown = renderer.render(&character);
+        assert!(markdown.contains("**Death Saves:** 0 successes, 2 failures"));
+    }
+
+    #[test]
+    fn test_render_magic_item_state() {
+        use crate::models::character::ItemCharges;
+
+        let renderer = MarkdownRenderer::new();
+        let mut fighter = create_sample_fighter();
+        let markdown = renderer.render(&fighter);
+        assert!(!markdown.contains("**Attuned Items:**"));
+
+        fighter.inventory.push(InventoryItem {
+            name: "Wand of Web".to_string(),
+            source: Some("DMG".to_string()),
+            quantity: 1,
+            weight: 1.0,
+            value: 0.0,
+            notes: None,
+            requires_attunement: true,
+            attuned: true,
+            charges: Some(ItemCharges {
+                current: 4,
+                max: 7,
+                recharge: Some(ItemRecharge::Dawn),
+                recharge_amount: Some("1d6+1".to_string()),
+            }),
+        });
+
+        let markdown = renderer.render(&fighter);
+        assert!(markdown.contains("**Attuned Items:** 1 / 3"));
+        assert!(markdown.contains("**Attuned · Charges: 4 / 7 (recharges at dawn)**"));
+    }
+
+    #[test]
+    fn test_render_optional_features() {
+        use crate::models::character::OptionalFeatureChoice;
+
+        let renderer = MarkdownRenderer::new();
+        let mut fighter = create_sample_fighter();
+        assert!(!renderer.render(&fighter).contains("## Optional Features"));
+
+        fighter.optional_features.push(OptionalFeatureChoice {
+            name: "Archery".to_string(),
+            source: "PHB".to_string(),
+            feature_type: "FS:F".to_string(),
+            class_name: "Fighter".to_string(),
+            level: 1,
+        });
+
+        let markdown = renderer.render(&fighter);
+        assert!(markdown.contains("## Optional Features"));
+        assert!(markdown.contains("- **Archery** (Fighting Style (Fighter), Fighter 1)"));
+    }
+
+    #[test]
+    fn test_render_derived_ac_and_attacks() {
+        let renderer = MarkdownRenderer::new();
+        let fighter = create_sample_fighter();
+        let item = |name: &str, item_type: &str, ac: Option<u8>, dmg1: Option<&str>| Item {
+            name: name.to_string(),
+            source: "PHB".to_string(),
+            page: None,
+            item_type: Some(item_type.to_string()),
+            rarity: None,
+            weight: None,
+            value: None,
+            ac,
+            dmg1: dmg1.map(str::to_string),
+            dmg2: Some("1d10".to_string()),
+            dmg_type: Some("B".to_string()),
+            property: Some(vec!["V".to_string()]),
+            range: None,
+            reload: None,
+            weapon_category: Some("martial".to_string()),
+            bonus_ac: None,
+            bonus_weapon: None,
+            bonus_weapon_attack: None,
+            bonus_weapon_damage: None,
+            requires_attunement: None,
+            charges: None,
+            recharge: None,
+            recharge_amount: None,
+            entries: None,
+            srd: None,
+            pack_contents: None,
+        };
+        let item_details: HashMap<String, Item> = [
+            item("Chain Mail", "HA", Some(16), None),
+            item("Shield", "S", Some(2), None),
+            item("Warhammer", "M", None, Some("1d8")),
+        ]
+        .into_iter()
+        .map(|i| (format!("{}:{}", i.name, i.source), i))
+        .collect();
+
+        // Without catalog data the armor can't be resolved
+        let markdown = renderer.render(&fighter);
+        assert!(markdown.contains("| 14 | +1 | 30 ft |"));
+        assert!(markdown.contains("| Warhammer | +5 | — | Not in catalog |"));
+
+        // Chain Mail 16 + Shield 2 + Defense fighting style 1
+        let markdown = renderer.render_with_details(&fighter, &HashMap::new(), &item_details);
+        assert!(markdown.contains("| 19 | +1 | 30 ft |"));
+        assert!(markdown.contains("*AC: Chain Mail 16, Shield +2, Defense +1*"));
+        assert!(markdown.contains("| Warhammer | +5 | 1d8+3 bludgeoning | Versatile |"));
+    }
+
     #[test]
     fn test_render_npc_info() {
         let renderer = MarkdownRenderer::new();
@@ -1349,9 +1556,16 @@ mod tests {
             },
             max_hp: 8,
             current_hp: 8,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies::default(),
             class_features: Vec::new(),
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: Vec::new(),
             currency: Currency::default(),
@@ -1421,9 +1635,16 @@ mod tests {
             },
             max_hp: 546,
             current_hp: 546,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies::default(),
             class_features: Vec::new(),
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: Vec::new(),
             currency: Currency::default(),
@@ -0,0 +1,510 @@
+//! Pact magic and class resource tracking
+//!
+//! Warlock pact slots and limited-use class features (Ki, Rage, Channel
+//! Divinity, ...) are derived from class levels. Values are read from the
+//! class table columns in `catalog_classes` when the class has been imported,
+//! with the PHB progressions as a fallback.
+
+use super::spell_management::calculate_spell_slots;
+use crate::connection::DbConnection;
+use crate::error::DbError;
+use crate::models::catalog::Class;
+use crate::models::character::data::{CharacterData, ClassResource, PactSlots, RestType};
+use crate::services::ClassService;
+use crate::text::strip_tags;
+
+type Result<T> = std::result::Result<T, DbError>;
+
+/// Warlock pact slots by Warlock level as (slot count, slot level)
+const PACT_SLOT_TABLE: [(i32, i32); 20] = [
+    (1, 1), // Level 1
+    (2, 1), // Level 2
+    (2, 2), // Level 3
+    (2, 2), // Level 4
+    (2, 3), // Level 5
+    (2, 3), // Level 6
+    (2, 4), // Level 7
+    (2, 4), // Level 8
+    (2, 5), // Level 9
+    (2, 5), // Level 10
+    (3, 5), // Level 11
+    (3, 5), // Level 12
+    (3, 5), // Level 13
+    (3, 5), // Level 14
+    (3, 5), // Level 15
+    (3, 5), // Level 16
+    (4, 5), // Level 17
+    (4, 5), // Level 18
+    (4, 5), // Level 19
+    (4, 5), // Level 20
+];
+
+/// A class resource and where its maximum comes from
+struct ResourceRule {
+    name: &'static str,
+    /// Class table column holding the maximum, if the class has one
+    column: Option<&'static str>,
+    /// Maximum used when the class table doesn't provide one
+    fallback_max: i32,
+    recovery: RestType,
+}
+
+impl ResourceRule {
+    fn new(name: &'static str, fallback_max: i32, recovery: RestType) -> Self {
+        Self {
+            name,
+            column: None,
+            fallback_max,
+            recovery,
+        }
+    }
+
+    fn from_column(
+        name: &'static str,
+        column: &'static str,
+        fallback_max: i32,
+        recovery: RestType,
+    ) -> Self {
+        Self {
+            name,
+            column: Some(column),
+            fallback_max,
+            recovery,
+        }
+    }
+}
+
+/// Resources granted by a class at the given class level
+fn resource_rules(class_name: &str, level: i32, character: &CharacterData) -> Vec<ResourceRule> {
+    let cha_mod = character.abilities.cha_modifier();
+
+    match class_name.to_lowercase().as_str() {
+        "barbarian" => {
+            // Unlimited rages at level 20 aren't tracked
+            let rages = match level {
+                1..=2 => 2,
+                3..=5 => 3,
+                6..=11 => 4,
+                12..=16 => 5,
+                17..=19 => 6,
+                _ => 0,
+            };
+            vec![ResourceRule::from_column(
+                "Rage",
+                "Rages",
+                rages,
+                RestType::Long,
+            )]
+        }
+        "bard" => {
+            // Font of Inspiration moves recovery to short rests at level 5
+            let recovery = if level >= 5 {
+                RestType::Short
+            } else {
+                RestType::Long
+            };
+            vec![ResourceRule::new(
+                "Bardic Inspiration",
+                cha_mod.max(1),
+                recovery,
+            )]
+        }
+        "cleric" if level >= 2 => {
+            let uses = match level {
+                2..=5 => 1,
+                6..=17 => 2,
+                _ => 3,
+            };
+            vec![ResourceRule::new("Channel Divinity", uses, RestType::Short)]
+        }
+        "druid" if level >= 2 => vec![ResourceRule::new("Wild Shape", 2, RestType::Short)],
+        "fighter" => {
+            let mut rules = vec![ResourceRule::new("Second Wind", 1, RestType::Short)];
+            if level >= 2 {
+                let uses = if level >= 17 { 2 } else { 1 };
+                rules.push(ResourceRule::new("Action Surge", uses, RestType::Short));
+            }
+            rules
+        }
+        "monk" if level >= 2 => vec![ResourceRule::from_column(
+            "Ki Points",
+            "Ki Points",
+            level,
+            RestType::Short,
+        )],
+        "paladin" => {
+            let mut rules = vec![
+                ResourceRule::new("Divine Sense", (1 + cha_mod).max(1), RestType::Long),
+                ResourceRule::new("Lay on Hands", 5 * level, RestType::Long),
+            ];
+            if level >= 3 {
+                rules.push(ResourceRule::new("Channel Divinity", 1, RestType::Short));
+            }
+            rules
+        }
+        "sorcerer" if level >= 2 => vec![ResourceRule::from_column(
+            "Sorcery Points",
+            "Sorcery Points",
+            level,
+            RestType::Long,
+        )],
+        "wizard" => vec![ResourceRule::new("Arcane Recovery", 1, RestType::Long)],
+        _ => Vec::new(),
+    }
+}
+
+/// Read a numeric cell from a class table column at the given class level
+///
+/// Column labels and cells may carry 5etools markup (e.g. `{@filter Ki Points|...}`
+/// or `{@filter 3rd|spells|level=3}`); cells may also be bonus objects.
+fn class_table_value(class: &Class, column: &str, level: i32) -> Option<i32> {
+    let row_index = usize::try_from(level - 1).ok()?;
+
+    for group in class.class_table_groups.iter().flatten() {
+        let Some(labels) = group.get("colLabels").and_then(|l| l.as_array()) else {
+            continue;
+        };
+        let Some(col_index) = labels.iter().position(|label| {
+            label
+                .as_str()
+                .is_some_and(|l| strip_tags(l).trim().eq_ignore_ascii_case(column))
+        }) else {
+            continue;
+        };
+
+        let cell = group
+            .get("rows")
+            .and_then(|rows| rows.get(row_index))
+            .and_then(|row| row.get(col_index))?;
+
+        return match cell {
+            serde_json::Value::Number(n) => n.as_i64().map(|n| n as i32),
+            serde_json::Value::String(s) => {
+                let text = strip_tags(s);
+                let digits: String = text
+                    .trim()
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect();
+                digits.parse().ok()
+            }
+            serde_json::Value::Object(obj) => {
+                obj.get("value").and_then(|v| v.as_i64()).map(|n| n as i32)
+            }
+            _ => None,
+        };
+    }
+
+    None
+}
+
+/// Look up class data for table columns, treating lookup failures as missing
+pub(super) fn find_class(conn: &mut DbConnection, class_name: &str) -> Option<Class> {
+    ClassService::new(conn)
+        .get_class_by_name(class_name)
+        .ok()
+        .flatten()
+}
+
+/// Calculate pact magic slots from a character's Warlock levels
+///
+/// Pact slots are separate from the multiclass spellcaster slots returned by
+/// `calculate_spell_slots`. Returns `None` for characters without Warlock levels.
+pub fn calculate_pact_slots(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+) -> Result<Option<PactSlots>> {
+    let Some(warlock) = character
+        .classes
+        .iter()
+        .find(|c| c.class_name.eq_ignore_ascii_case("warlock"))
+    else {
+        return Ok(None);
+    };
+
+    let level = warlock.level.clamp(1, 20);
+    let (fallback_count, fallback_level) = PACT_SLOT_TABLE[(level - 1) as usize];
+
+    let class = find_class(conn, &warlock.class_name);
+    let count = class
+        .as_ref()
+        .and_then(|c| class_table_value(c, "Spell Slots", level))
+        .unwrap_or(fallback_count);
+    let slot_level = class
+        .as_ref()
+        .and_then(|c| class_table_value(c, "Slot Level", level))
+        .unwrap_or(fallback_level);
+
+    Ok(Some(PactSlots::new(slot_level, count)))
+}
+
+/// Calculate the limited-use class resources for every class a character has
+///
+/// Returned resources are at full uses.
+pub fn calculate_class_resources(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+) -> Result<Vec<ClassResource>> {
+    let mut resources = Vec::new();
+
+    for class_level in &character.classes {
+        let rules = resource_rules(&class_level.class_name, class_level.level, character);
+        if rules.is_empty() {
+            continue;
+        }
+
+        let class = if rules.iter().any(|r| r.column.is_some()) {
+            find_class(conn, &class_level.class_name)
+        } else {
+            None
+        };
+
+        for rule in rules {
+            let max = rule
+                .column
+                .zip(class.as_ref())
+                .and_then(|(column, class)| class_table_value(class, column, class_level.level))
+                .unwrap_or(rule.fallback_max);
+            if max <= 0 {
+                continue;
+            }
+
+            resources.push(ClassResource {
+                name: rule.name.to_string(),
+                class_name: class_level.class_name.clone(),
+                max,
+                current: max,
+                recovery: rule.recovery,
+            });
+        }
+    }
+
+    Ok(resources)
+}
+
+/// Recalculate spell slots, pact slots and class resources after a change to
+/// a character's classes, levels or abilities
+///
+/// Maximums are recalculated while uses already spent are carried over, so
+/// leveling up mid-adventure doesn't refill anything.
+pub fn refresh_resources(conn: &mut DbConnection, character: &mut CharacterData) -> Result<()> {
+    let mut spell_slots = calculate_spell_slots(conn, character)?;
+    for (level, slots) in spell_slots.iter_mut() {
+        if let Some(old) = character.spells.spell_slots.get(level) {
+            slots.current = (slots.max - (old.max - old.current)).max(0);
+        }
+    }
+
+    let mut pact_slots = calculate_pact_slots(conn, character)?;
+    if let (Some(new), Some(old)) = (&mut pact_slots, &character.spells.pact_slots) {
+        new.current = (new.max - (old.max - old.current)).max(0);
+    }
+
+    let mut class_resources = calculate_class_resources(conn, character)?;
+    for resource in &mut class_resources {
+        if let Some(old) = character
+            .class_resources
+            .iter()
+            .find(|r| r.name == resource.name && r.class_name == resource.class_name)
+        {
+            resource.current = (resource.max - (old.max - old.current)).max(0);
+        }
+    }
+
+    character.spells.spell_slots = spell_slots;
+    character.spells.pact_slots = pact_slots;
+    character.class_resources = class_resources;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::character::data::*;
+    use diesel::prelude::*;
+
+    fn setup_test_db() -> DbConnection {
+        let mut conn = crate::establish_connection(":memory:").unwrap();
+        crate::run_migrations(&mut conn).unwrap();
+
+        // Monk with a tagged Ki Points column, as in 5etools data
+        let monk_json = r#"{
+            "name": "Monk",
+            "source": "PHB",
+            "hd": {"number": 1, "faces": 8},
+            "classTableGroups": [{
+                "colLabels": [
+                    "Martial Arts",
+                    "{@filter Ki Points|classes|search=ki}",
+                    "Unarmored Movement"
+                ],
+                "rows": [
+                    ["1d4", 0, {"type": "bonusSpeed", "value": 0}],
+                    ["1d4", 2, {"type": "bonusSpeed", "value": 10}],
+                    ["1d4", 3, {"type": "bonusSpeed", "value": 10}]
+                ]
+            }]
+        }"#;
+
+        let warlock_json = r#"{
+            "name": "Warlock",
+            "source": "PHB",
+            "hd": {"number": 1, "faces": 8},
+            "casterProgression": "pact",
+            "classTableGroups": [{
+                "colLabels": [
+                    "{@filter Spell Slots|spells|class=warlock}",
+                    "{@filter Slot Level|spells|class=warlock}"
+                ],
+                "rows": [
+                    [1, "{@filter 1st|spells|level=1|class=Warlock}"],
+                    [2, "{@filter 1st|spells|level=1|class=Warlock}"],
+                    [2, "{@filter 2nd|spells|level=2|class=Warlock}"]
+                ]
+            }]
+        }"#;
+
+        for (name, hit_dice, progression, json) in [
+            ("Monk", "d8", None, monk_json),
+            ("Warlock", "d8", Some("pact"), warlock_json),
+        ] {
+            diesel::insert_into(crate::schema::catalog_classes::table)
+                .values((
+                    crate::schema::catalog_classes::name.eq(name),
+                    crate::schema::catalog_classes::source.eq("PHB"),
+                    crate::schema::catalog_classes::hit_dice.eq(hit_dice),
+                    crate::schema::catalog_classes::caster_progression.eq(progression),
+                    crate::schema::catalog_classes::full_class_json.eq(json),
+                ))
+                .execute(&mut conn)
+                .expect("Failed to insert class");
+        }
+
+        conn
+    }
+
+    fn create_test_character(classes: &[(&str, i32)]) -> CharacterData {
+        let level = classes.iter().map(|(_, level)| level).sum();
+        CharacterData {
+            character_name: "Test".to_string(),
+            player_id: None,
+            level,
+            experience_points: 0,
+            version: 1,
+            snapshot_reason: None,
+            created_at: "2025-01-01".to_string(),
+            race: "Human".to_string(),
+            subrace: None,
+            classes: classes
+                .iter()
+                .map(|(name, level)| ClassLevel {
+                    class_name: name.to_string(),
+                    level: *level,
+                    subclass: None,
+                    hit_dice_type: "d8".to_string(),
+                    hit_dice_remaining: *level,
+                })
+                .collect(),
+            background: "Hermit".to_string(),
+            alignment: None,
+            abilities: AbilityScores {
+                strength: 10,
+                dexterity: 16,
+                constitution: 12,
+                intelligence: 10,
+                wisdom: 14,
+                charisma: 16,
+            },
+            max_hp: 20,
+            current_hp: 20,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
+            speed: 30,
+            proficiencies: Proficiencies::default(),
+            class_features: Vec::new(),
+            class_resources: Vec::new(),
+            feats: Vec::new(),
+            optional_features: Vec::new(),
+            spells: SpellData::default(),
+            inventory: Vec::new(),
+            currency: Currency::default(),
+            equipped: EquippedItems::default(),
+            personality: Personality::default(),
+            npc_role: None,
+            npc_location: None,
+            npc_faction: None,
+            npc_notes: None,
+            legendary_actions: Vec::new(),
+            legendary_action_count: None,
+        }
+    }
+
+    #[test]
+    fn test_resources_read_from_class_table() {
+        let mut conn = setup_test_db();
+        let character = create_test_character(&[("Monk", 3)]);
+
+        let resources = calculate_class_resources(&mut conn, &character).unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].name, "Ki Points");
+        assert_eq!(resources[0].max, 3);
+        assert_eq!(resources[0].recovery, RestType::Short);
+    }
+
+    #[test]
+    fn test_resources_fall_back_without_catalog_class() {
+        let mut conn = setup_test_db();
+        let character = create_test_character(&[("Bard", 5), ("Barbarian", 3)]);
+
+        let resources = calculate_class_resources(&mut conn, &character).unwrap();
+        let inspiration = resources
+            .iter()
+            .find(|r| r.name == "Bardic Inspiration")
+            .unwrap();
+        assert_eq!(inspiration.max, 3); // Charisma 16
+        assert_eq!(inspiration.recovery, RestType::Short); // Font of Inspiration
+        let rage = resources.iter().find(|r| r.name == "Rage").unwrap();
+        assert_eq!(rage.max, 3);
+        assert_eq!(rage.recovery, RestType::Long);
+    }
+
+    #[test]
+    fn test_pact_slots_for_multiclass_warlock() {
+        let mut conn = setup_test_db();
+        let character = create_test_character(&[("Warlock", 3), ("Monk", 2)]);
+
+        let pact = calculate_pact_slots(&mut conn, &character)
+            .unwrap()
+            .unwrap();
+        assert_eq!(pact.max, 2);
+        assert_eq!(pact.slot_level, 2);
+
+        // Pact magic doesn't add to multiclass spellcaster slots
+        let slots = calculate_spell_slots(&mut conn, &character).unwrap();
+        assert!(slots.is_empty());
+
+        let no_warlock = create_test_character(&[("Monk", 3)]);
+        assert!(calculate_pact_slots(&mut conn, &no_warlock)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_refresh_keeps_spent_uses() {
+        let mut conn = setup_test_db();
+        let mut character = create_test_character(&[("Monk", 2)]);
+        refresh_resources(&mut conn, &mut character).unwrap();
+        assert!(character.get_resource_mut("ki points").unwrap().expend(1));
+
+        character.classes[0].level = 3;
+        character.level = 3;
+        refresh_resources(&mut conn, &mut character).unwrap();
+
+        let ki = &character.class_resources[0];
+        assert_eq!(ki.max, 3);
+        assert_eq!(ki.current, 2);
+    }
+}
@@ -6,6 +6,7 @@ use crate::connection::DbConnection;
 use crate::error::DbError;
 use crate::models::catalog::Spell;
 use crate::models::character::data::{CharacterData, SpellSlots};
+pub use crate::models::character::data::RestType;
 use crate::services::ClassService;
 use std::collections::HashMap;
 
@@ -182,15 +183,36 @@ pub fn validate_spell_for_class_and_subclass(
     Ok(false)
 }
 
-/// Rest type for spell slot restoration
-#[derive(Debug, Clone, PartialEq)]
-pub enum RestType {
-    /// Short rest - typically 1 hour
-    Short,
-    /// Long rest - typically 8 hours
-    Long,
+/// Check whether casting a spell requires concentration
+///
+/// The spell's source is taken from the character's spell lists (PHB if the
+/// spell isn't listed). Spells missing from the catalog are treated as not
+/// needing concentration.
+pub fn requires_concentration(
+    conn: &mut DbConnection,
+    character: &CharacterData,
+    spell_name: &str,
+) -> Result<bool> {
+    let source = character
+        .spells
+        .known_spells
+        .iter()
+        .chain(&character.spells.prepared_spells)
+        .chain(&character.spells.cantrips)
+        .find(|spell| spell.name.eq_ignore_ascii_case(spell_name))
+        .map(|spell| spell.source.as_str())
+        .unwrap_or("PHB");
+
+    let spell = crate::services::SpellService::get_spell_details(conn, spell_name, source)?;
+    Ok(spell.is_some_and(|spell| {
+        spell
+            .duration
+            .iter()
+            .any(|d| d.concentration.unwrap_or(false))
+    }))
 }
 
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,9 +303,16 @@ mod tests {
             },
             max_hp: 6 + (level - 1) * 4,
             current_hp: 6 + (level - 1) * 4,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
             proficiencies: Proficiencies::default(),
             class_features: Vec::new(),
+            class_resources: Vec::new(),
             feats: Vec::new(),
+            optional_features: Vec::new(),
             spells: SpellData::default(),
             inventory: Vec::new(),
             currency: Currency::default(),
@@ -141,6 +141,9 @@ impl<'a> CharacterSpellService<'a> {
     }
 
     /// Cast a spell, consuming the appropriate spell slot
+    ///
+    /// Casting a concentration spell replaces whatever the character was
+    /// concentrating on.
     pub fn cast_spell(
         &mut self,
         character_id: i32,
@@ -151,6 +154,10 @@ impl<'a> CharacterSpellService<'a> {
         let mut char_service = CharacterService::new(self.conn);
         let (_character, mut char_data) = char_service.get_character(character_id)?;
 
+        if spell_management::requires_concentration(self.conn, &char_data, spell_name)? {
+            char_data.concentration = Some(spell_name.to_string());
+        }
+
         // Ritual casting doesn't consume slots
         if is_ritual {
             let snapshot_reason = Some(format!("Cast {} as ritual", spell_name));
@@ -158,20 +165,19 @@ impl<'a> CharacterSpellService<'a> {
             return char_service.update_character(character_id, char_data, snapshot_reason);
         }
 
-        // Consume spell slot
-        if let Some(slots) = char_data.spells.spell_slots.get_mut(&spell_level) {
-            if !slots.expend(1) {
-                return Err(DbError::InvalidData(format!(
-                    "No level {} spell slots remaining",
-                    spell_level
-                )));
-            }
-        } else {
+        // Consume spell slot, falling back to pact magic slots
+        if !char_data.has_spell_slot_level(spell_level) {
             return Err(DbError::InvalidData(format!(
                 "Character has no level {} spell slots",
                 spell_level
             )));
         }
+        if !char_data.expend_spell_slot(spell_level) {
+            return Err(DbError::InvalidData(format!(
+                "No level {} spell slots remaining",
+                spell_level
+            )));
+        }
 
         // Create new version
         let snapshot_reason = Some(format!("Cast {} (level {})", spell_name, spell_level));
@@ -184,40 +190,26 @@ impl<'a> CharacterSpellService<'a> {
         let mut char_service = CharacterService::new(self.conn);
         let (_character, mut char_data) = char_service.get_character(character_id)?;
 
-        match rest_type {
-            RestType::Short => {
-                // Short rest restores warlock spell slots
-                // Check if character has warlock levels
-                if char_data.has_class("Warlock") {
-                    // Restore all spell slots for warlock
-                    for slots in char_data.spells.spell_slots.values_mut() {
-                        slots.recover_all();
-                    }
-                }
-                // Other classes may have features that restore on short rest (e.g., Arcane Recovery)
-            }
-            RestType::Long => {
-                // Long rest restores all spell slots for all classes
-                for slots in char_data.spells.spell_slots.values_mut() {
-                    slots.recover_all();
-                }
-
-                // Also restore HP
-                char_data.current_hp = char_data.max_hp;
-
-                // Restore hit dice (restore half of max for each class, minimum 1 total)
-                let total_restored = (char_data.level / 2).max(1);
-                let mut remaining_to_restore = total_restored;
-
-                for class_level in &mut char_data.classes {
-                    let max_for_class = class_level.level;
-                    let can_restore = max_for_class - class_level.hit_dice_remaining;
-                    let to_restore = can_restore.min(remaining_to_restore);
-                    class_level.hit_dice_remaining += to_restore;
-                    remaining_to_restore -= to_restore;
-                    if remaining_to_restore <= 0 {
-                        break;
-                    }
+        // Short rests restore pact magic and short-rest class resources;
+        // long rests restore every slot and resource
+        char_data.recover_resources(rest_type);
+
+        if rest_type == RestType::Long {
+            // Also restore HP
+            char_data.current_hp = char_data.max_hp;
+
+            // Restore hit dice (restore half of max for each class, minimum 1 total)
+            let total_restored = (char_data.level / 2).max(1);
+            let mut remaining_to_restore = total_restored;
+
+            for class_level in &mut char_data.classes {
+                let max_for_class = class_level.level;
+                let can_restore = max_for_class - class_level.hit_dice_remaining;
+                let to_restore = can_restore.min(remaining_to_restore);
+                class_level.hit_dice_remaining += to_restore;
+                remaining_to_restore -= to_restore;
+                if remaining_to_restore <= 0 {
+                    break;
                 }
             }
         }
@@ -0,0 +1,642 @@
+//! Starting equipment for new characters
+//!
+//! Classes and backgrounds list their starting gear as groups of (a)/(b)
+//! options in the 5etools `startingEquipment` data. This module turns those
+//! groups into something a player can choose from, and resolves the choices
+//! into concrete [`InventoryItem`]s from `catalog_items` (expanding equipment
+//! packs into their contents) plus starting coins. Players may instead take
+//! the class's starting gold and no equipment at all.
+
+use crate::connection::DbConnection;
+use crate::domain::dice::DiceRoller;
+use crate::error::{DbError, Result};
+use crate::models::catalog::{Background, Item, StartingEquipment};
+use crate::models::character::data::{Currency, InventoryItem, ItemCharges};
+use crate::services::{BackgroundService, ClassService};
+use crate::text::{strip_tags, titlecase};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Source assumed for item references that don't name one
+const DEFAULT_ITEM_SOURCE: &str = "PHB";
+
+/// One entry of a starting equipment option
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EquipmentEntry {
+    /// A catalog item, e.g. `longsword|phb`
+    Item {
+        /// Item name as written in the reference (usually lowercase)
+        name: String,
+        /// Source book, if the reference names one
+        source: Option<String>,
+        /// Number of items
+        quantity: i32,
+        /// Label to show instead of the name (e.g. "20 arrows")
+        display_name: Option<String>,
+        /// Coins carried inside the item, in copper pieces
+        contains_value: Option<i32>,
+    },
+    /// A free-text item that has no catalog entry (e.g. "insignia of rank")
+    Special {
+        /// Item description
+        name: String,
+        /// Number of items
+        quantity: i32,
+    },
+    /// Any item of a category; the player picks which one
+    EquipmentType {
+        /// 5etools category code (e.g. `weaponMartial`)
+        equipment_type: String,
+        /// Number of items
+        quantity: i32,
+    },
+    /// Coins, in copper pieces
+    Currency {
+        /// Value in copper pieces
+        value: i32,
+    },
+}
+
+/// One lettered option of a choice group
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EquipmentOption {
+    /// Option key as used in the source data ("a", "b", ...)
+    pub key: String,
+    /// Everything granted by this option
+    pub entries: Vec<EquipmentEntry>,
+}
+
+/// A group of mutually exclusive options: "(a) ... or (b) ..."
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EquipmentChoiceGroup {
+    /// The options to choose between
+    pub options: Vec<EquipmentOption>,
+}
+
+/// Starting equipment offered by a class and background
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct StartingEquipmentOptions {
+    /// Class equipment granted without a choice
+    pub class_fixed: Vec<EquipmentEntry>,
+    /// Class choice groups, in order
+    pub class_choices: Vec<EquipmentChoiceGroup>,
+    /// The class's starting equipment as written in the rulebook
+    pub class_description: Vec<String>,
+    /// Background equipment granted without a choice
+    pub background_fixed: Vec<EquipmentEntry>,
+    /// Background choice groups, in order
+    pub background_choices: Vec<EquipmentChoiceGroup>,
+    /// Starting gold formula offered instead of equipment (e.g. "5d4×10")
+    pub gold_alternative: Option<String>,
+}
+
+/// How a new character is equipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StartingEquipmentChoice {
+    /// Take the class and background equipment
+    Equipment {
+        /// Option key for each class choice group, in order
+        #[serde(default)]
+        class_choices: Vec<String>,
+        /// Option key for each background choice group, in order
+        #[serde(default)]
+        background_choices: Vec<String>,
+        /// Specific items for each "any martial weapon"-style entry, in the
+        /// order they appear (class before background), as `name` or
+        /// `name|source`
+        #[serde(default)]
+        equipment_picks: Vec<String>,
+    },
+    /// Take the class's starting gold instead of any class or background
+    /// equipment. The gold is rolled when no amount is given.
+    Gold {
+        /// Gold pieces to start with
+        #[serde(default)]
+        gold: Option<i32>,
+    },
+}
+
+/// Items and coins resolved from a [`StartingEquipmentChoice`]
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedStartingEquipment {
+    /// Inventory items, with packs expanded and duplicates merged
+    pub items: Vec<InventoryItem>,
+    /// Starting coins
+    pub currency: Currency,
+}
+
+/// Load the starting equipment options for a class and (optionally) a background
+pub fn starting_equipment_options(
+    conn: &mut DbConnection,
+    class_name: &str,
+    class_source: &str,
+    background: Option<(&str, &str)>,
+) -> Result<StartingEquipmentOptions> {
+    let class = ClassService::new(conn)
+        .get_class_by_name_and_source(class_name, class_source)?
+        .ok_or_else(|| DbError::NotFound {
+            entity_type: "Class".to_string(),
+            id: format!("{} ({})", class_name, class_source),
+        })?;
+
+    let mut options = StartingEquipmentOptions::default();
+
+    if let Some(equipment) = class
+        .starting_equipment
+        .and_then(|v| serde_json::from_value::<StartingEquipment>(v).ok())
+    {
+        let (fixed, choices) =
+            parse_equipment_groups(equipment.default_data.as_deref().unwrap_or(&[]));
+        options.class_fixed = fixed;
+        options.class_choices = choices;
+        options.class_description = equipment
+            .default
+            .unwrap_or_default()
+            .iter()
+            .map(|line| strip_tags(line))
+            .collect();
+        options.gold_alternative = equipment
+            .gold_alternative
+            .as_deref()
+            .and_then(parse_gold_alternative);
+    }
+
+    if let Some((background_name, background_source)) = background {
+        let catalog_bg = BackgroundService::new(conn)
+            .get_background_by_name_and_source(background_name, background_source)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Background".to_string(),
+                id: format!("{} ({})", background_name, background_source),
+            })?;
+        let background: Background = serde_json::from_str(&catalog_bg.full_background_json)?;
+        let (fixed, choices) = parse_equipment_groups(&background.starting_equipment);
+        options.background_fixed = fixed;
+        options.background_choices = choices;
+    }
+
+    Ok(options)
+}
+
+/// Resolve a starting equipment choice into inventory items and coins
+pub fn resolve_starting_equipment(
+    conn: &mut DbConnection,
+    options: &StartingEquipmentOptions,
+    choice: &StartingEquipmentChoice,
+    roller: &mut DiceRoller,
+) -> Result<ResolvedStartingEquipment> {
+    let mut resolved = ResolvedStartingEquipment::default();
+
+    match choice {
+        StartingEquipmentChoice::Gold { gold } => {
+            let gold = match gold {
+                Some(amount) if *amount < 0 => {
+                    return Err(DbError::InvalidData(
+                        "Starting gold cannot be negative".to_string(),
+                    ))
+                }
+                Some(amount) => *amount,
+                None => {
+                    let formula = options.gold_alternative.as_deref().ok_or_else(|| {
+                        DbError::InvalidData(
+                            "This class has no starting gold alternative".to_string(),
+                        )
+                    })?;
+                    roll_starting_gold(formula, roller)?
+                }
+            };
+            resolved.currency.gold = gold;
+        }
+        StartingEquipmentChoice::Equipment {
+            class_choices,
+            background_choices,
+            equipment_picks,
+        } => {
+            let mut entries: Vec<&EquipmentEntry> = options.class_fixed.iter().collect();
+            entries.extend(select_options(
+                &options.class_choices,
+                class_choices,
+                "class",
+            )?);
+            entries.extend(options.background_fixed.iter());
+            entries.extend(select_options(
+                &options.background_choices,
+                background_choices,
+                "background",
+            )?);
+
+            let mut picks = equipment_picks.iter();
+            let mut copper = 0;
+            for entry in entries {
+                match entry {
+                    EquipmentEntry::Item {
+                        name,
+                        source,
+                        quantity,
+                        contains_value,
+                        ..
+                    } => {
+                        for item in resolve_item(conn, name, source.as_deref(), *quantity)? {
+                            merge_item(&mut resolved.items, item);
+                        }
+                        copper += contains_value.unwrap_or(0);
+                    }
+                    EquipmentEntry::Special { name, quantity } => merge_item(
+                        &mut resolved.items,
+                        plain_item(&titlecase(name), None, *quantity),
+                    ),
+                    EquipmentEntry::EquipmentType {
+                        equipment_type,
+                        quantity,
+                    } => {
+                        let pick = picks.next().ok_or_else(|| {
+                            DbError::InvalidData(format!(
+                                "Choose a specific {} for the starting equipment",
+                                equipment_type_label(equipment_type)
+                            ))
+                        })?;
+                        let (name, source) = split_reference(pick);
+                        for item in resolve_item(conn, &name, source.as_deref(), *quantity)? {
+                            merge_item(&mut resolved.items, item);
+                        }
+                    }
+                    EquipmentEntry::Currency { value } => copper += value,
+                }
+            }
+
+            resolved.currency.gold = copper / 100;
+            resolved.currency.silver = (copper % 100) / 10;
+            resolved.currency.copper = copper % 10;
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Split 5etools starting equipment groups into fixed entries and choice groups
+pub fn parse_equipment_groups(
+    groups: &[Value],
+) -> (Vec<EquipmentEntry>, Vec<EquipmentChoiceGroup>) {
+    let mut fixed = Vec::new();
+    let mut choices = Vec::new();
+
+    for group in groups {
+        let Some(obj) = group.as_object() else {
+            continue;
+        };
+
+        let mut options = Vec::new();
+        for (key, value) in obj {
+            let entries: Vec<EquipmentEntry> = value
+                .as_array()
+                .map(|items| items.iter().filter_map(parse_entry).collect())
+                .unwrap_or_default();
+            if key == "_" {
+                fixed.extend(entries);
+            } else {
+                options.push(EquipmentOption {
+                    key: key.clone(),
+                    entries,
+                });
+            }
+        }
+
+        if !options.is_empty() {
+            options.sort_by(|a, b| a.key.cmp(&b.key));
+            choices.push(EquipmentChoiceGroup { options });
+        }
+    }
+
+    (fixed, choices)
+}
+
+/// Parse one entry of a starting equipment list
+fn parse_entry(value: &Value) -> Option<EquipmentEntry> {
+    if let Some(reference) = value.as_str() {
+        let (name, source) = split_reference(reference);
+        return Some(EquipmentEntry::Item {
+            name,
+            source,
+            quantity: 1,
+            display_name: None,
+            contains_value: None,
+        });
+    }
+
+    let obj = value.as_object()?;
+    let quantity = obj.get("quantity").and_then(|q| q.as_i64()).unwrap_or(1) as i32;
+
+    if let Some(reference) = obj.get("item").and_then(|i| i.as_str()) {
+        let (name, source) = split_reference(reference);
+        Some(EquipmentEntry::Item {
+            name,
+            source,
+            quantity,
+            display_name: obj
+                .get("displayName")
+                .and_then(|d| d.as_str())
+                .map(String::from),
+            contains_value: obj
+                .get("containsValue")
+                .and_then(|v| v.as_i64())
+                .map(|v| v as i32),
+        })
+    } else if let Some(special) = obj.get("special").and_then(|s| s.as_str()) {
+        Some(EquipmentEntry::Special {
+            name: special.to_string(),
+            quantity,
+        })
+    } else if let Some(equipment_type) = obj.get("equipmentType").and_then(|t| t.as_str()) {
+        Some(EquipmentEntry::EquipmentType {
+            equipment_type: equipment_type.to_string(),
+            quantity,
+        })
+    } else {
+        obj.get("value")
+            .and_then(|v| v.as_i64())
+            .map(|value| EquipmentEntry::Currency {
+                value: value as i32,
+            })
+    }
+}
+
+/// Pick the chosen option out of each choice group
+fn select_options<'o>(
+    groups: &'o [EquipmentChoiceGroup],
+    keys: &[String],
+    label: &str,
+) -> Result<Vec<&'o EquipmentEntry>> {
+    if keys.len() != groups.len() {
+        return Err(DbError::InvalidData(format!(
+            "Expected {} {} equipment choice(s), got {}",
+            groups.len(),
+            label,
+            keys.len()
+        )));
+    }
+
+    let mut entries = Vec::new();
+    for (index, (group, key)) in groups.iter().zip(keys).enumerate() {
+        let key = key.trim().trim_matches(|c| c == '(' || c == ')');
+        let option = group
+            .options
+            .iter()
+            .find(|o| o.key.eq_ignore_ascii_case(key))
+            .ok_or_else(|| {
+                let valid: Vec<&str> = group.options.iter().map(|o| o.key.as_str()).collect();
+                DbError::InvalidData(format!(
+                    "Invalid option '{}' for {} equipment choice {} (expected one of: {})",
+                    key,
+                    label,
+                    index + 1,
+                    valid.join(", ")
+                ))
+            })?;
+        entries.extend(option.entries.iter());
+    }
+    Ok(entries)
+}
+
+/// Resolve an item reference into inventory items, expanding equipment packs
+fn resolve_item(
+    conn: &mut DbConnection,
+    name: &str,
+    source: Option<&str>,
+    quantity: i32,
+) -> Result<Vec<InventoryItem>> {
+    let Some(item) = find_catalog_item(conn, name, source)? else {
+        return Ok(vec![plain_item(&titlecase(name), source, quantity)]);
+    };
+
+    if let Some(contents) = &item.pack_contents {
+        let mut items = Vec::new();
+        for entry in contents.iter().filter_map(parse_entry) {
+            match entry {
+                EquipmentEntry::Item {
+                    name,
+                    source,
+                    quantity: count,
+                    ..
+                } => {
+                    if let Some(content) = find_catalog_item(conn, &name, source.as_deref())? {
+                        items.push(catalog_inventory_item(&content, count * quantity));
+                    } else {
+                        items.push(plain_item(
+                            &titlecase(&name),
+                            source.as_deref(),
+                            count * quantity,
+                        ));
+                    }
+                }
+                EquipmentEntry::Special {
+                    name,
+                    quantity: count,
+                } => items.push(plain_item(&titlecase(&name), None, count * quantity)),
+                EquipmentEntry::EquipmentType { .. } | EquipmentEntry::Currency { .. } => {}
+            }
+        }
+        return Ok(items);
+    }
+
+    Ok(vec![catalog_inventory_item(&item, quantity)])
+}
+
+/// Look up a catalog item by case-insensitive name, preferring the given source
+fn find_catalog_item(
+    conn: &mut DbConnection,
+    item_name: &str,
+    item_source: Option<&str>,
+) -> Result<Option<Item>> {
+    use crate::schema::catalog_items::dsl::*;
+
+    let wanted_source = item_source
+        .filter(|s| !s.is_empty())
+        .unwrap_or(DEFAULT_ITEM_SOURCE)
+        .to_uppercase();
+
+    let candidates: Vec<(String, String)> = catalog_items
+        .filter(name.like(item_name))
+        .select((source, full_item_json))
+        .load(conn)?;
+
+    let json = candidates
+        .iter()
+        .find(|(s, _)| s.eq_ignore_ascii_case(&wanted_source))
+        .or_else(|| candidates.first())
+        .map(|(_, json)| json);
+
+    match json {
+        Some(json) => Ok(Some(serde_json::from_str(json)?)),
+        None => Ok(None),
+    }
+}
+
+fn catalog_inventory_item(item: &Item, quantity: i32) -> InventoryItem {
+    InventoryItem {
+        name: item.name.clone(),
+        source: Some(item.source.clone()),
+        quantity,
+        weight: item.weight.map(|w| w as f64).unwrap_or(0.0),
+        value: item.value.unwrap_or(0.0),
+        notes: None,
+        requires_attunement: item.requires_attunement.is_some(),
+        attuned: false,
+        charges: ItemCharges::from_catalog(item),
+    }
+}
+
+fn plain_item(name: &str, source: Option<&str>, quantity: i32) -> InventoryItem {
+    InventoryItem {
+        name: name.to_string(),
+        source: source.map(|s| s.to_uppercase()),
+        quantity,
+        weight: 0.0,
+        value: 0.0,
+        notes: None,
+        requires_attunement: false,
+        attuned: false,
+        charges: None,
+    }
+}
+
+/// Add an item to a list, stacking it onto an existing entry with the same name
+fn merge_item(items: &mut Vec<InventoryItem>, item: InventoryItem) {
+    match items
+        .iter_mut()
+        .find(|i| i.name.eq_ignore_ascii_case(&item.name))
+    {
+        Some(existing) => existing.quantity += item.quantity,
+        None => items.push(item),
+    }
+}
+
+/// Split a `name|source` reference
+fn split_reference(reference: &str) -> (String, Option<String>) {
+    let mut parts = reference.split('|');
+    let name = parts.next().unwrap_or_default().trim().to_string();
+    let source = parts
+        .next()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+    (name, source)
+}
+
+/// Extract the dice formula from a `goldAlternative` string such as
+/// `{@dice 5d4 × 10|5d4 × 10|Starting Gold}`
+pub fn parse_gold_alternative(text: &str) -> Option<String> {
+    let formula = match text.find("{@dice ") {
+        Some(start) => {
+            let rest = &text[start + "{@dice ".len()..];
+            let end = rest.find(['|', '}']).unwrap_or(rest.len());
+            &rest[..end]
+        }
+        None => text.trim_end_matches("gp").trim(),
+    };
+
+    let formula: String = formula.chars().filter(|c| !c.is_whitespace()).collect();
+    (!formula.is_empty()).then_some(formula)
+}
+
+/// Roll a starting gold formula such as "5d4×10"
+pub fn roll_starting_gold(formula: &str, roller: &mut DiceRoller) -> Result<i32> {
+    let (dice, multiplier) = match formula.split_once(['×', 'x', '*']) {
+        Some((dice, multiplier)) => {
+            let multiplier = multiplier.trim().parse::<i32>().map_err(|_| {
+                DbError::InvalidData(format!("Invalid starting gold formula: {}", formula))
+            })?;
+            (dice, multiplier)
+        }
+        None => (formula, 1),
+    };
+    Ok(roller.roll(dice.trim())?.total * multiplier)
+}
+
+/// Human-readable name for a 5etools `equipmentType` code
+pub fn equipment_type_label(equipment_type: &str) -> String {
+    match equipment_type {
+        "weaponSimple" => "simple weapon",
+        "weaponSimpleMelee" => "simple melee weapon",
+        "weaponSimpleRanged" => "simple ranged weapon",
+        "weaponMartial" => "martial weapon",
+        "weaponMartialMelee" => "martial melee weapon",
+        "weaponMartialRanged" => "martial ranged weapon",
+        "instrumentMusical" => "musical instrument",
+        "toolArtisan" => "set of artisan's tools",
+        "setGaming" => "gaming set",
+        "focusSpellcastingArcane" => "arcane focus",
+        "focusSpellcastingDruidic" => "druidic focus",
+        "focusSpellcastingHoly" => "holy symbol",
+        other => other,
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_equipment_groups() {
+        let groups = vec![
+            json!({"a": ["chain mail|phb"], "b": ["leather armor|phb", {"item": "arrow|phb", "quantity": 20}]}),
+            json!({"_": [{"special": "insignia of rank"}, {"item": "pouch|phb", "containsValue": 1000}]}),
+            json!({"a": [{"equipmentType": "weaponMartial", "quantity": 2}], "b": [{"value": 500}]}),
+        ];
+
+        let (fixed, choices) = parse_equipment_groups(&groups);
+        assert_eq!(fixed.len(), 2);
+        assert_eq!(
+            fixed[1],
+            EquipmentEntry::Item {
+                name: "pouch".to_string(),
+                source: Some("phb".to_string()),
+                quantity: 1,
+                display_name: None,
+                contains_value: Some(1000),
+            }
+        );
+        assert_eq!(choices.len(), 2);
+        assert_eq!(choices[0].options[1].key, "b");
+        assert_eq!(choices[0].options[1].entries.len(), 2);
+        assert_eq!(
+            choices[1].options[0].entries[0],
+            EquipmentEntry::EquipmentType {
+                equipment_type: "weaponMartial".to_string(),
+                quantity: 2,
+            }
+        );
+        assert_eq!(
+            choices[1].options[1].entries[0],
+            EquipmentEntry::Currency { value: 500 }
+        );
+    }
+
+    #[test]
+    fn test_gold_alternative() {
+        assert_eq!(
+            parse_gold_alternative("{@dice 5d4 × 10|5d4 × 10|Starting Gold}").as_deref(),
+            Some("5d4×10")
+        );
+        assert_eq!(
+            parse_gold_alternative("{@dice 5d4|5d4|Starting Gold}").as_deref(),
+            Some("5d4")
+        );
+
+        let mut roller = DiceRoller::seeded(3);
+        let gold = roll_starting_gold("5d4×10", &mut roller).unwrap();
+        assert!((50..=200).contains(&gold));
+        assert_eq!(gold % 10, 0);
+    }
+
+    #[test]
+    fn test_class_description_keeps_display_text() {
+        assert_eq!(
+            strip_tags("(a) two {@item handaxe|phb|handaxes} or (b) any {@filter simple weapon|items|type=simple weapon}"),
+            "(a) two handaxes or (b) any simple weapon"
+        );
+    }
+}
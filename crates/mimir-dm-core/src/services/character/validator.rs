@@ -0,0 +1,1216 @@
+//! Rules linter for whole characters
+//!
+//! [`CharacterValidator`] checks a [`CharacterData`] against the race, class,
+//! background, feat, optional feature and spell catalogs and reports what
+//! breaks the rules. Findings are either errors (the character could not
+//! have been built this way) or warnings (unusual, but possibly granted by
+//! something the catalog data doesn't describe, such as a racial cantrip).
+
+use super::optional_features::prerequisites_met;
+use super::resources::find_class;
+use super::spell_management::validate_spell_for_class_and_subclass;
+use crate::connection::DbConnection;
+use crate::error::Result;
+use crate::models::catalog::{Background, Class, Feat, OptionalFeature, Race, Spell};
+use crate::models::character::data::{CharacterData, MAX_ATTUNED_ITEMS};
+use crate::models::character::SpellReference;
+use crate::services::SpellService;
+use crate::text::capitalize;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FindingSeverity {
+    /// The character breaks the rules
+    Error,
+    /// Unusual, but could be explained by something outside the catalog data
+    Warning,
+}
+
+/// A single rules problem found on a character
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationFinding {
+    /// Error or warning
+    pub severity: FindingSeverity,
+    /// Character data field the finding is about (e.g. `proficiencies.skills`)
+    pub field: String,
+    /// Explanation of what is wrong
+    pub message: String,
+}
+
+/// Result of validating a character
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    /// True when there are no errors (warnings are allowed)
+    pub valid: bool,
+    /// Number of error findings
+    pub error_count: usize,
+    /// Number of warning findings
+    pub warning_count: usize,
+    /// All findings, errors first
+    pub findings: Vec<ValidationFinding>,
+}
+
+impl ValidationReport {
+    fn from_findings(mut findings: Vec<ValidationFinding>) -> Self {
+        findings.sort_by_key(|f| f.severity != FindingSeverity::Error);
+        let error_count = findings
+            .iter()
+            .filter(|f| f.severity == FindingSeverity::Error)
+            .count();
+        Self {
+            valid: error_count == 0,
+            error_count,
+            warning_count: findings.len() - error_count,
+            findings,
+        }
+    }
+
+    /// Findings with error severity
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == FindingSeverity::Error)
+    }
+}
+
+/// Validates characters against catalog data
+pub struct CharacterValidator<'a> {
+    conn: &'a mut DbConnection,
+    findings: Vec<ValidationFinding>,
+}
+
+/// Skills a race, class, background or feat grants or lets the player choose
+#[derive(Debug, Default)]
+struct SkillAllowance {
+    fixed: Vec<String>,
+    choices: Vec<(Vec<String>, usize)>,
+    any: usize,
+}
+
+impl SkillAllowance {
+    /// Add a 5etools `skillProficiencies` block
+    fn add_block(&mut self, values: &[Value]) {
+        // Alternatives (several objects) are rare; the first one is the default
+        let Some(obj) = values.first().and_then(|v| v.as_object()) else {
+            return;
+        };
+        for (key, value) in obj {
+            match key.as_str() {
+                "choose" => {
+                    let from = value
+                        .get("from")
+                        .and_then(|f| f.as_array())
+                        .map(|f| {
+                            f.iter()
+                                .filter_map(|s| s.as_str())
+                                .map(|s| s.to_lowercase())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    let count = value.get("count").and_then(|c| c.as_u64()).unwrap_or(1);
+                    self.choices.push((from, count as usize));
+                }
+                "any" => self.any += value.as_u64().unwrap_or(1) as usize,
+                skill => {
+                    if value.as_bool().unwrap_or(false) {
+                        let skill = skill.to_lowercase();
+                        // A skill granted twice lets the player pick any other skill
+                        if self.fixed.contains(&skill) {
+                            self.any += 1;
+                        } else {
+                            self.fixed.push(skill);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn offers(&self, skill: &str) -> bool {
+        let skill = skill.to_lowercase();
+        self.any > 0
+            || self.fixed.contains(&skill)
+            || self.choices.iter().any(|(from, _)| from.contains(&skill))
+    }
+
+    fn budget(&self) -> usize {
+        self.fixed.len() + self.choices.iter().map(|(_, n)| n).sum::<usize>() + self.any
+    }
+}
+
+impl<'a> CharacterValidator<'a> {
+    /// Create a validator using the given catalog connection
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self {
+            conn,
+            findings: Vec::new(),
+        }
+    }
+
+    /// Check a character against the catalogs
+    pub fn validate(mut self, character: &CharacterData) -> Result<ValidationReport> {
+        let classes: Vec<(String, i32, Option<Class>)> = character
+            .classes
+            .iter()
+            .map(|c| {
+                (
+                    c.class_name.clone(),
+                    c.level,
+                    find_class(self.conn, &c.class_name),
+                )
+            })
+            .collect();
+
+        self.check_levels(character);
+        self.check_abilities(character);
+        self.check_hit_points(character);
+        self.check_classes(character, &classes);
+
+        let race = self.load_race(&character.race)?;
+        if race.is_none() && !character.race.is_empty() {
+            self.warning(
+                "race",
+                format!(
+                    "Race '{}' is not in the catalog; racial rules were not checked",
+                    character.race
+                ),
+            );
+        }
+        let background = self.load_background(&character.background)?;
+        if background.is_none() && !character.background.is_empty() {
+            self.warning(
+                "background",
+                format!(
+                    "Background '{}' is not in the catalog; background rules were not checked",
+                    character.background
+                ),
+            );
+        }
+
+        let feats = self.check_feats(character)?;
+        self.check_skills(
+            character,
+            race.as_ref(),
+            &classes,
+            background.as_ref(),
+            &feats,
+        );
+        self.check_optional_features(character)?;
+        self.check_spells(character, &classes)?;
+        self.check_attunement(character);
+
+        Ok(ValidationReport::from_findings(self.findings))
+    }
+
+    fn error(&mut self, field: &str, message: String) {
+        self.findings.push(ValidationFinding {
+            severity: FindingSeverity::Error,
+            field: field.to_string(),
+            message,
+        });
+    }
+
+    fn warning(&mut self, field: &str, message: String) {
+        self.findings.push(ValidationFinding {
+            severity: FindingSeverity::Warning,
+            field: field.to_string(),
+            message,
+        });
+    }
+
+    fn check_levels(&mut self, character: &CharacterData) {
+        if !(1..=20).contains(&character.level) {
+            self.error(
+                "level",
+                format!("Level {} is outside the 1-20 range", character.level),
+            );
+        }
+
+        if character.classes.is_empty() {
+            self.error("classes", "Character has no class".to_string());
+            return;
+        }
+
+        let class_total: i32 = character.classes.iter().map(|c| c.level).sum();
+        if class_total != character.level {
+            self.error(
+                "classes",
+                format!(
+                    "Class levels add up to {} but the character is level {}",
+                    class_total, character.level
+                ),
+            );
+        }
+        for class in &character.classes {
+            if class.level < 1 {
+                self.error(
+                    "classes",
+                    format!("{} has level {}", class.class_name, class.level),
+                );
+            }
+        }
+    }
+
+    fn check_abilities(&mut self, character: &CharacterData) {
+        let a = &character.abilities;
+        for (name, score) in [
+            ("strength", a.strength),
+            ("dexterity", a.dexterity),
+            ("constitution", a.constitution),
+            ("intelligence", a.intelligence),
+            ("wisdom", a.wisdom),
+            ("charisma", a.charisma),
+        ] {
+            let field = format!("abilities.{}", name);
+            if !(1..=30).contains(&score) {
+                self.error(
+                    &field,
+                    format!("{} {} is outside the 1-30 range", capitalize(name), score),
+                );
+            } else if score > 20 {
+                self.warning(
+                    &field,
+                    format!(
+                        "{} {} is above the normal maximum of 20",
+                        capitalize(name),
+                        score
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_hit_points(&mut self, character: &CharacterData) {
+        if character.max_hp < 1 {
+            self.error(
+                "max_hp",
+                format!("Maximum HP {} must be at least 1", character.max_hp),
+            );
+        }
+        if character.current_hp > character.max_hp {
+            self.warning(
+                "current_hp",
+                format!(
+                    "Current HP {} is above maximum HP {}",
+                    character.current_hp, character.max_hp
+                ),
+            );
+        }
+    }
+
+    /// Classes must exist and multiclassed characters must meet every
+    /// class's multiclassing ability requirements
+    fn check_classes(
+        &mut self,
+        character: &CharacterData,
+        classes: &[(String, i32, Option<Class>)],
+    ) {
+        for (name, _, class) in classes {
+            let Some(class) = class else {
+                self.warning(
+                    "classes",
+                    format!(
+                        "Class '{}' is not in the catalog; class rules were not checked",
+                        name
+                    ),
+                );
+                continue;
+            };
+
+            if classes.len() < 2 {
+                continue;
+            }
+            let Some(requirements) = class
+                .multiclassing
+                .as_ref()
+                .and_then(|m| m.get("requirements"))
+            else {
+                continue;
+            };
+            if let Some(unmet) = unmet_ability_requirement(requirements, character) {
+                self.error(
+                    "classes",
+                    format!(
+                        "Multiclassing into or out of {} requires {}",
+                        class.name, unmet
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Every feat must exist and its prerequisites must be met
+    fn check_feats(&mut self, character: &CharacterData) -> Result<Vec<Feat>> {
+        let mut feats = Vec::new();
+        for name in &character.feats {
+            let Some(feat) = self.load_feat(name)? else {
+                self.warning("feats", format!("Feat '{}' is not in the catalog", name));
+                continue;
+            };
+
+            if let Some(prerequisites) = feat.prerequisite.as_ref().filter(|p| !p.is_empty()) {
+                let reasons: Vec<String> = prerequisites
+                    .iter()
+                    .filter_map(|p| unmet_feat_prerequisite(p, character))
+                    .collect();
+                if reasons.len() == prerequisites.len() {
+                    self.error(
+                        "feats",
+                        format!("{} requires {}", feat.name, reasons.join(", or ")),
+                    );
+                }
+            }
+            feats.push(feat);
+        }
+        Ok(feats)
+    }
+
+    /// Skill proficiencies must be offered by the race, class, background or
+    /// a feat, and there must not be more of them than those sources grant
+    fn check_skills(
+        &mut self,
+        character: &CharacterData,
+        race: Option<&Race>,
+        classes: &[(String, i32, Option<Class>)],
+        background: Option<&Background>,
+        feats: &[Feat],
+    ) {
+        // Without the full set of sources the allowance would be incomplete
+        if race.is_none() || background.is_none() || classes.iter().any(|(_, _, c)| c.is_none()) {
+            return;
+        }
+
+        let mut allowance = SkillAllowance::default();
+        if let Some(skills) = race.and_then(|r| r.skill_proficiencies.as_ref()) {
+            allowance.add_block(skills);
+        }
+        if let Some(background) = background {
+            allowance.add_block(&background.skill_proficiencies);
+        }
+        for (index, (_, _, class)) in classes.iter().enumerate() {
+            let Some(class) = class else { continue };
+            // The first class grants its full starting proficiencies, later
+            // classes only what multiclassing grants
+            let block = if index == 0 {
+                class
+                    .starting_proficiencies
+                    .as_ref()
+                    .and_then(|p| p.get("skills"))
+            } else {
+                class
+                    .multiclassing
+                    .as_ref()
+                    .and_then(|m| m.get("proficienciesGained"))
+                    .and_then(|p| p.get("skills"))
+            };
+            if let Some(values) = block.and_then(|b| b.as_array()) {
+                allowance.add_block(values);
+            }
+        }
+        for feat in feats {
+            if let Some(skills) = &feat.skill_proficiencies {
+                allowance.add_block(skills);
+            }
+        }
+
+        let skills = &character.proficiencies.skills;
+        for skill in skills {
+            if !allowance.offers(skill) {
+                self.error(
+                    "proficiencies.skills",
+                    format!(
+                        "{} is not offered by the character's race, class, background or feats",
+                        skill
+                    ),
+                );
+            }
+        }
+        if skills.len() > allowance.budget() {
+            self.warning(
+                "proficiencies.skills",
+                format!(
+                    "{} skill proficiencies, but race, class, background and feats grant {}",
+                    skills.len(),
+                    allowance.budget()
+                ),
+            );
+        }
+    }
+
+    /// Chosen invocations, fighting styles and the like must meet their
+    /// prerequisites
+    fn check_optional_features(&mut self, character: &CharacterData) -> Result<()> {
+        use crate::schema::catalog_optional_features::dsl;
+
+        for choice in &character.optional_features {
+            let json: Option<String> = dsl::catalog_optional_features
+                .filter(dsl::name.eq(&choice.name))
+                .filter(dsl::source.eq(&choice.source))
+                .select(dsl::full_optional_feature_json)
+                .first(self.conn)
+                .optional()?;
+            let Some(feature) = json.and_then(|j| serde_json::from_str::<OptionalFeature>(&j).ok())
+            else {
+                self.warning(
+                    "optional_features",
+                    format!("{} ({}) is not in the catalog", choice.name, choice.source),
+                );
+                continue;
+            };
+            if !prerequisites_met(&feature, character) {
+                self.error(
+                    "optional_features",
+                    format!("{} prerequisites are not met", feature.name),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Spell counts against class limits, and spell levels against slots
+    fn check_spells(
+        &mut self,
+        character: &CharacterData,
+        classes: &[(String, i32, Option<Class>)],
+    ) -> Result<()> {
+        let spells = &character.spells;
+
+        // Cantrips known
+        let cantrip_limit: i32 = classes
+            .iter()
+            .filter_map(|(_, level, class)| {
+                progression_at(class.as_ref()?.cantrip_progression.as_ref()?, *level)
+            })
+            .sum();
+        if spells.cantrips.len() as i32 > cantrip_limit {
+            self.warning(
+                "spells.cantrips",
+                format!(
+                    "{} cantrips known, but the character's classes grant {}",
+                    spells.cantrips.len(),
+                    cantrip_limit
+                ),
+            );
+        }
+
+        // Prepared spells: ability modifier + class level (half for half casters)
+        let preparers: Vec<(String, i32)> = classes
+            .iter()
+            .filter_map(|(_, level, class)| {
+                let class = class.as_ref()?;
+                let formula = class.prepared_spells.as_ref()?;
+                let ability = class.spellcasting_ability.as_deref().unwrap_or("int");
+                let class_level = if formula.contains("/ 2") {
+                    level / 2
+                } else {
+                    *level
+                };
+                let max = (ability_modifier(character, ability) + class_level).max(1);
+                Some((class.name.clone(), max))
+            })
+            .collect();
+        let prepared_count = spells
+            .prepared_spells
+            .iter()
+            .filter(|s| !contains_spell(&spells.cantrips, s))
+            .count() as i32;
+        if !preparers.is_empty() {
+            let max: i32 = preparers.iter().map(|(_, m)| m).sum();
+            if prepared_count > max {
+                let breakdown: Vec<String> = preparers
+                    .iter()
+                    .map(|(name, m)| format!("{} {}", name, m))
+                    .collect();
+                self.error(
+                    "spells.prepared_spells",
+                    format!(
+                        "{} spells prepared, but the maximum is {} ({})",
+                        prepared_count,
+                        max,
+                        breakdown.join(", ")
+                    ),
+                );
+            }
+        }
+
+        // Spells known, for characters whose casting classes all learn a fixed number
+        let casters: Vec<&Class> = classes
+            .iter()
+            .filter_map(|(_, _, c)| c.as_ref())
+            .filter(|c| c.caster_progression.is_some())
+            .collect();
+        if !casters.is_empty() && casters.iter().all(|c| c.prepared_spells.is_none()) {
+            let known_limit: i32 = classes
+                .iter()
+                .filter_map(|(_, level, class)| {
+                    progression_at(class.as_ref()?.spells_known_progression.as_ref()?, *level)
+                })
+                .sum();
+            if known_limit > 0 && spells.known_spells.len() as i32 > known_limit {
+                self.warning(
+                    "spells.known_spells",
+                    format!(
+                        "{} spells known, but the character's classes grant {}",
+                        spells.known_spells.len(),
+                        known_limit
+                    ),
+                );
+            }
+        }
+
+        // Prepared spells come from the spellbook or known list when one is kept
+        for spell in &spells.prepared_spells {
+            if !spells.known_spells.is_empty()
+                && !contains_spell(&spells.known_spells, spell)
+                && !contains_spell(&spells.cantrips, spell)
+            {
+                self.warning(
+                    "spells.prepared_spells",
+                    format!("{} is prepared but not known", spell.name),
+                );
+            }
+        }
+
+        // Catalog checks: cantrips are level 0, leveled spells fit the slots,
+        // and every spell is on one of the character's class lists
+        let max_slot_level = spells
+            .spell_slots
+            .iter()
+            .filter(|(_, slots)| slots.max > 0)
+            .map(|(level, _)| *level)
+            .chain(spells.pact_slots.iter().map(|p| p.slot_level))
+            .max()
+            .unwrap_or(0);
+
+        let mut checked: Vec<&SpellReference> = Vec::new();
+        for (field, list) in [
+            ("spells.cantrips", &spells.cantrips),
+            ("spells.known_spells", &spells.known_spells),
+            ("spells.prepared_spells", &spells.prepared_spells),
+        ] {
+            for reference in list {
+                if checked
+                    .iter()
+                    .any(|s| s.name == reference.name && s.source == reference.source)
+                {
+                    continue;
+                }
+                checked.push(reference);
+                self.check_spell(character, field, reference, max_slot_level)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_spell(
+        &mut self,
+        character: &CharacterData,
+        field: &str,
+        reference: &SpellReference,
+        max_slot_level: i32,
+    ) -> Result<()> {
+        let Some(spell) =
+            SpellService::get_spell_details(self.conn, &reference.name, &reference.source)?
+        else {
+            self.warning(
+                field,
+                format!(
+                    "{} ({}) is not in the catalog",
+                    reference.name, reference.source
+                ),
+            );
+            return Ok(());
+        };
+
+        let level = spell.level as i32;
+        if field == "spells.cantrips" && level != 0 {
+            self.error(
+                field,
+                format!("{} is a level {} spell, not a cantrip", spell.name, level),
+            );
+        } else if field != "spells.cantrips" && level > max_slot_level {
+            self.error(
+                field,
+                format!(
+                    "{} is level {}, but the character's highest spell slot is level {}",
+                    spell.name, level, max_slot_level
+                ),
+            );
+        }
+
+        if !on_class_list(self.conn, &spell, character)? {
+            self.warning(
+                field,
+                format!(
+                    "{} is not on the spell list of any of the character's classes",
+                    spell.name
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    fn check_attunement(&mut self, character: &CharacterData) {
+        let attuned = character.inventory.iter().filter(|i| i.attuned).count();
+        if attuned > MAX_ATTUNED_ITEMS {
+            self.error(
+                "inventory",
+                format!(
+                    "{} items attuned, but the maximum is {}",
+                    attuned, MAX_ATTUNED_ITEMS
+                ),
+            );
+        }
+        for item in character.inventory.iter().filter(|i| i.attuned) {
+            if !item.requires_attunement {
+                self.warning(
+                    "inventory",
+                    format!("{} is attuned but does not require attunement", item.name),
+                );
+            }
+        }
+    }
+
+    fn load_race(&mut self, race_name: &str) -> Result<Option<Race>> {
+        use crate::schema::catalog_races::dsl::*;
+
+        let records: Vec<(String, String)> = catalog_races
+            .filter(name.eq(race_name))
+            .select((source, full_race_json))
+            .load(self.conn)?;
+        Ok(prefer_phb(records).and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    fn load_background(&mut self, background_name: &str) -> Result<Option<Background>> {
+        use crate::schema::catalog_backgrounds::dsl::*;
+
+        let records: Vec<(String, String)> = catalog_backgrounds
+            .filter(name.eq(background_name))
+            .select((source, full_background_json))
+            .load(self.conn)?;
+        Ok(prefer_phb(records).and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    fn load_feat(&mut self, feat_name: &str) -> Result<Option<Feat>> {
+        use crate::schema::catalog_feats::dsl::*;
+
+        let records: Vec<(String, String)> = catalog_feats
+            .filter(name.like(feat_name))
+            .select((source, full_feat_json))
+            .load(self.conn)?;
+        Ok(prefer_phb(records).and_then(|json| serde_json::from_str(&json).ok()))
+    }
+}
+
+/// Pick the PHB record when a name exists in several books
+fn prefer_phb(records: Vec<(String, String)>) -> Option<String> {
+    let index = records.iter().position(|(s, _)| s == "PHB").unwrap_or(0);
+    records.into_iter().nth(index).map(|(_, json)| json)
+}
+
+fn contains_spell(list: &[SpellReference], spell: &SpellReference) -> bool {
+    list.iter()
+        .any(|s| s.name.eq_ignore_ascii_case(&spell.name) && s.source == spell.source)
+}
+
+fn progression_at(progression: &[u8], level: i32) -> Option<i32> {
+    let index = (level.clamp(1, 20) - 1) as usize;
+    progression.get(index).map(|v| *v as i32)
+}
+
+fn on_class_list(
+    conn: &mut DbConnection,
+    spell: &Spell,
+    character: &CharacterData,
+) -> Result<bool> {
+    // Spells without class data can't be checked
+    if spell.classes.is_none() {
+        return Ok(true);
+    }
+    for class in &character.classes {
+        if validate_spell_for_class_and_subclass(
+            conn,
+            spell,
+            &class.class_name,
+            class.subclass.as_deref(),
+        )? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn ability_score(character: &CharacterData, ability: &str) -> Option<i32> {
+    let a = &character.abilities;
+    match ability.to_lowercase().as_str() {
+        "str" | "strength" => Some(a.strength),
+        "dex" | "dexterity" => Some(a.dexterity),
+        "con" | "constitution" => Some(a.constitution),
+        "int" | "intelligence" => Some(a.intelligence),
+        "wis" | "wisdom" => Some(a.wisdom),
+        "cha" | "charisma" => Some(a.charisma),
+        _ => None,
+    }
+}
+
+fn ability_modifier(character: &CharacterData, ability: &str) -> i32 {
+    ability_score(character, ability)
+        .map(|score| (score - 10).div_euclid(2))
+        .unwrap_or(0)
+}
+
+fn any_ability_met(options: &[(String, i32)], character: &CharacterData) -> bool {
+    options
+        .iter()
+        .any(|(ability, min)| ability_score(character, ability).is_some_and(|s| s >= *min))
+}
+
+fn describe_ability_options(options: &[(String, i32)]) -> String {
+    options
+        .iter()
+        .map(|(ability, min)| format!("{} {}", ability.to_uppercase(), min))
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Describe the unmet parts of a multiclassing `{"str": 13, "or": [...]}` block
+fn unmet_ability_requirement(requirements: &Value, character: &CharacterData) -> Option<String> {
+    let obj = requirements.as_object()?;
+    let mut unmet = Vec::new();
+    for (key, value) in obj {
+        if key == "or" {
+            // Fighter-style `{"or": [{"str": 13, "dex": 13}]}`: any one score is enough
+            let options: Vec<(String, i32)> = value
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|alt| alt.as_object())
+                .flat_map(|alt| alt.iter())
+                .filter_map(|(ability, min)| Some((ability.clone(), min.as_i64()? as i32)))
+                .collect();
+            if !options.is_empty() && !any_ability_met(&options, character) {
+                unmet.push(describe_ability_options(&options));
+            }
+        } else if let Some(min) = value.as_i64() {
+            if ability_score(character, key).is_some_and(|score| score < min as i32) {
+                unmet.push(format!("{} {}", key.to_uppercase(), min));
+            }
+        }
+    }
+    (!unmet.is_empty()).then(|| unmet.join(" and "))
+}
+
+/// Describe why a single feat prerequisite entry is not met, or `None` if it is
+fn unmet_feat_prerequisite(prerequisite: &Value, character: &CharacterData) -> Option<String> {
+    let obj = prerequisite.as_object()?;
+    let mut unmet = Vec::new();
+
+    if let Some(level) = obj.get("level") {
+        let (min, class) = match level {
+            Value::Object(l) => (
+                l.get("level").and_then(|v| v.as_i64()).unwrap_or(1) as i32,
+                l.get("class")
+                    .and_then(|c| c.get("name"))
+                    .and_then(|n| n.as_str()),
+            ),
+            other => (other.as_i64().unwrap_or(1) as i32, None),
+        };
+        let met = match class {
+            Some(class) => character
+                .classes
+                .iter()
+                .any(|c| c.class_name.eq_ignore_ascii_case(class) && c.level >= min),
+            None => character.level >= min,
+        };
+        if !met {
+            unmet.push(match class {
+                Some(class) => format!("{} level {}", class, min),
+                None => format!("level {}", min),
+            });
+        }
+    }
+
+    if let Some(abilities) = obj.get("ability").and_then(|a| a.as_array()) {
+        // Each entry is an alternative
+        let options: Vec<(String, i32)> = abilities
+            .iter()
+            .filter_map(|entry| entry.as_object())
+            .flat_map(|entry| entry.iter())
+            .filter_map(|(ability, min)| Some((ability.clone(), min.as_i64()? as i32)))
+            .collect();
+        if !options.is_empty() && !any_ability_met(&options, character) {
+            unmet.push(describe_ability_options(&options));
+        }
+    }
+
+    if let Some(races) = obj.get("race").and_then(|r| r.as_array()) {
+        let names: Vec<&str> = races
+            .iter()
+            .filter_map(|r| r.get("name").and_then(|n| n.as_str()))
+            .collect();
+        let race = character.race.to_lowercase();
+        if !names.is_empty() && !names.iter().any(|n| race.contains(&n.to_lowercase())) {
+            unmet.push(format!("race {}", names.join(" or ")));
+        }
+    }
+
+    let needs_spellcasting = ["spellcasting", "spellcasting2020", "spellcastingFeature"]
+        .iter()
+        .any(|k| obj.get(*k).and_then(|v| v.as_bool()).unwrap_or(false));
+    if needs_spellcasting && !can_cast_spells(character) {
+        unmet.push("the ability to cast at least one spell".to_string());
+    }
+
+    if let Some(proficiencies) = obj.get("proficiency").and_then(|p| p.as_array()) {
+        for proficiency in proficiencies.iter().filter_map(|p| p.as_object()) {
+            for (kind, value) in proficiency {
+                let Some(wanted) = value.as_str() else {
+                    continue;
+                };
+                let list = match kind.as_str() {
+                    "armor" => &character.proficiencies.armor,
+                    "weapon" | "weaponGroup" => &character.proficiencies.weapons,
+                    _ => continue,
+                };
+                let wanted = wanted.to_lowercase();
+                if !list.iter().any(|p| p.to_lowercase().contains(&wanted)) {
+                    unmet.push(format!("proficiency with {} {}", wanted, kind));
+                }
+            }
+        }
+    }
+
+    if let Some(required) = obj.get("feat").and_then(|f| f.as_array()) {
+        for feat in required.iter().filter_map(|f| f.as_str()) {
+            let feat_name = feat.split('|').next().unwrap_or(feat);
+            if !character
+                .feats
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(feat_name))
+            {
+                unmet.push(format!("the {} feat", capitalize(feat_name)));
+            }
+        }
+    }
+
+    (!unmet.is_empty()).then(|| unmet.join(" and "))
+}
+
+fn can_cast_spells(character: &CharacterData) -> bool {
+    let spells = &character.spells;
+    !spells.cantrips.is_empty()
+        || spells.spell_slots.values().any(|s| s.max > 0)
+        || spells.pact_slots.as_ref().is_some_and(|p| p.max > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::establish_connection;
+    use crate::models::character::data::{
+        AbilityScores, ClassLevel, Currency, DeathSaves, EquippedItems, InventoryItem, Personality,
+        Proficiencies, SpellData, SpellSlots,
+    };
+
+    fn setup_test_db() -> DbConnection {
+        let mut conn = establish_connection(":memory:").expect("Failed to create in-memory DB");
+        crate::run_migrations(&mut conn).expect("Failed to run migrations");
+
+        let fighter = r#"{
+            "name": "Fighter", "source": "PHB",
+            "hd": {"number": 1, "faces": 10},
+            "startingProficiencies": {
+                "skills": [{"choose": {"from": ["acrobatics", "athletics", "perception", "survival"], "count": 2}}]
+            },
+            "multiclassing": {"requirements": {"or": [{"str": 13, "dex": 13}]}}
+        }"#;
+        let wizard = r#"{
+            "name": "Wizard", "source": "PHB",
+            "hd": {"number": 1, "faces": 6},
+            "casterProgression": "full",
+            "preparedSpells": "<$level$> + <$int_mod$>",
+            "spellcastingAbility": "int",
+            "cantripProgression": [3, 3, 3, 4],
+            "startingProficiencies": {
+                "skills": [{"choose": {"from": ["arcana", "history"], "count": 2}}]
+            },
+            "multiclassing": {"requirements": {"int": 13}, "proficienciesGained": {}}
+        }"#;
+        for (name, json) in [("Fighter", fighter), ("Wizard", wizard)] {
+            diesel::insert_into(crate::schema::catalog_classes::table)
+                .values((
+                    crate::schema::catalog_classes::name.eq(name),
+                    crate::schema::catalog_classes::source.eq("PHB"),
+                    crate::schema::catalog_classes::full_class_json.eq(json),
+                ))
+                .execute(&mut conn)
+                .expect("Failed to insert class");
+        }
+
+        diesel::insert_into(crate::schema::catalog_races::table)
+            .values((
+                crate::schema::catalog_races::name.eq("Human"),
+                crate::schema::catalog_races::source.eq("PHB"),
+                crate::schema::catalog_races::full_race_json
+                    .eq(r#"{"name": "Human", "source": "PHB", "speed": 30}"#),
+            ))
+            .execute(&mut conn)
+            .expect("Failed to insert race");
+
+        diesel::insert_into(crate::schema::catalog_backgrounds::table)
+            .values((
+                crate::schema::catalog_backgrounds::name.eq("Soldier"),
+                crate::schema::catalog_backgrounds::source.eq("PHB"),
+                crate::schema::catalog_backgrounds::skills.eq("Athletics, Intimidation"),
+                crate::schema::catalog_backgrounds::languages.eq(""),
+                crate::schema::catalog_backgrounds::tools.eq(""),
+                crate::schema::catalog_backgrounds::feature.eq("Military Rank"),
+                crate::schema::catalog_backgrounds::full_background_json
+                    .eq(r#"{"name": "Soldier", "source": "PHB",
+                        "skillProficiencies": [{"athletics": true, "intimidation": true}]}"#),
+            ))
+            .execute(&mut conn)
+            .expect("Failed to insert background");
+
+        for (name, json) in [
+            (
+                "Grappler",
+                r#"{"name": "Grappler", "source": "PHB", "prerequisite": [{"ability": [{"str": 13}]}]}"#,
+            ),
+            (
+                "Heavy Armor Master",
+                r#"{"name": "Heavy Armor Master", "source": "PHB", "prerequisite": [{"proficiency": [{"armor": "heavy"}]}]}"#,
+            ),
+        ] {
+            diesel::insert_into(crate::schema::catalog_feats::table)
+                .values((
+                    crate::schema::catalog_feats::name.eq(name),
+                    crate::schema::catalog_feats::source.eq("PHB"),
+                    crate::schema::catalog_feats::full_feat_json.eq(json),
+                ))
+                .execute(&mut conn)
+                .expect("Failed to insert feat");
+        }
+
+        for (name, level) in [("Magic Missile", 1), ("Shield", 1), ("Fireball", 3)] {
+            let json = format!(
+                r#"{{"name": "{}", "source": "PHB", "level": {}, "school": "V",
+                    "time": [{{"number": 1, "unit": "action"}}],
+                    "range": {{"type": "point", "distance": {{"type": "feet", "amount": 120}}}},
+                    "components": {{"v": true, "s": true}},
+                    "duration": [{{"type": "instant"}}],
+                    "entries": ["Test spell."],
+                    "classes": {{"fromClassList": [{{"name": "Wizard", "source": "PHB"}}]}}}}"#,
+                name, level
+            );
+            diesel::insert_into(crate::schema::catalog_spells::table)
+                .values((
+                    crate::schema::catalog_spells::name.eq(name),
+                    crate::schema::catalog_spells::level.eq(level),
+                    crate::schema::catalog_spells::school.eq("V"),
+                    crate::schema::catalog_spells::cast_time.eq("1 action"),
+                    crate::schema::catalog_spells::range.eq("120 feet"),
+                    crate::schema::catalog_spells::components.eq("V, S"),
+                    crate::schema::catalog_spells::tags.eq(""),
+                    crate::schema::catalog_spells::source.eq("PHB"),
+                    crate::schema::catalog_spells::full_spell_json.eq(json),
+                ))
+                .execute(&mut conn)
+                .expect("Failed to insert spell");
+        }
+
+        conn
+    }
+
+    fn create_test_fighter() -> CharacterData {
+        CharacterData {
+            character_name: "Test Fighter".to_string(),
+            player_id: Some(1),
+            level: 1,
+            experience_points: 0,
+            version: 1,
+            snapshot_reason: None,
+            created_at: "2025-01-01".to_string(),
+            race: "Human".to_string(),
+            subrace: None,
+            classes: vec![ClassLevel {
+                class_name: "Fighter".to_string(),
+                level: 1,
+                subclass: None,
+                hit_dice_type: "d10".to_string(),
+                hit_dice_remaining: 1,
+            }],
+            background: "Soldier".to_string(),
+            alignment: None,
+            abilities: AbilityScores {
+                strength: 16,
+                dexterity: 12,
+                constitution: 14,
+                intelligence: 10,
+                wisdom: 10,
+                charisma: 8,
+            },
+            max_hp: 12,
+            current_hp: 12,
+            temp_hp: 0,
+            death_saves: DeathSaves::default(),
+            exhaustion_level: 0,
+            conditions: Vec::new(),
+            concentration: None,
+            proficiencies: Proficiencies {
+                skills: vec![
+                    "Athletics".to_string(),
+                    "Intimidation".to_string(),
+                    "Perception".to_string(),
+                    "Survival".to_string(),
+                ],
+                armor: vec![
+                    "Light".to_string(),
+                    "Medium".to_string(),
+                    "Heavy".to_string(),
+                ],
+                ..Default::default()
+            },
+            class_features: Vec::new(),
+            class_resources: Vec::new(),
+            feats: vec!["Grappler".to_string()],
+            optional_features: Vec::new(),
+            spells: SpellData::default(),
+            inventory: Vec::new(),
+            currency: Currency::default(),
+            speed: 30,
+            equipped: EquippedItems::default(),
+            personality: Personality::default(),
+            npc_role: None,
+            npc_location: None,
+            npc_faction: None,
+            npc_notes: None,
+            legendary_actions: Vec::new(),
+            legendary_action_count: None,
+        }
+    }
+
+    fn spell(name: &str) -> SpellReference {
+        SpellReference {
+            name: name.to_string(),
+            source: "PHB".to_string(),
+        }
+    }
+
+    fn has_error(report: &ValidationReport, field: &str, text: &str) -> bool {
+        report
+            .errors()
+            .any(|f| f.field == field && f.message.contains(text))
+    }
+
+    #[test]
+    fn test_valid_character_has_no_errors() {
+        let mut conn = setup_test_db();
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&create_test_fighter())
+            .unwrap();
+
+        assert!(report.valid, "unexpected findings: {:?}", report.findings);
+        assert_eq!(report.error_count, 0);
+    }
+
+    #[test]
+    fn test_rules_violations_are_reported() {
+        let mut conn = setup_test_db();
+        let mut character = create_test_fighter();
+        character.level = 2;
+        character.abilities.strength = 12;
+        character.abilities.intelligence = 12;
+        character.classes.push(ClassLevel {
+            class_name: "Wizard".to_string(),
+            level: 1,
+            subclass: None,
+            hit_dice_type: "d6".to_string(),
+            hit_dice_remaining: 1,
+        });
+        character.proficiencies.skills.push("Arcana".to_string());
+        character.feats.push("Heavy Armor Master".to_string());
+        character.spells.spell_slots.insert(1, SpellSlots::new(2));
+        character.spells.known_spells =
+            vec![spell("Magic Missile"), spell("Shield"), spell("Fireball")];
+        character.spells.prepared_spells = vec![spell("Magic Missile"), spell("Shield")];
+        character.inventory = (0..4)
+            .map(|i| InventoryItem {
+                name: format!("Ring {}", i),
+                source: None,
+                quantity: 1,
+                weight: 0.0,
+                value: 0.0,
+                notes: None,
+                requires_attunement: true,
+                attuned: true,
+                charges: None,
+            })
+            .collect();
+
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&character)
+            .unwrap();
+        assert!(!report.valid);
+
+        // INT 12 is below the Wizard's multiclassing requirement
+        assert!(has_error(&report, "classes", "INT 13"));
+        // STR 12 no longer qualifies for Grappler; Heavy Armor Master is fine
+        assert!(has_error(&report, "feats", "Grappler requires STR 13"));
+        assert!(!has_error(&report, "feats", "Heavy Armor Master"));
+        // A multiclassed Wizard gains no skills
+        assert!(has_error(&report, "proficiencies.skills", "Arcana"));
+        // INT +1 and Wizard level 1 allow two prepared spells, but only level 1 slots
+        assert!(!has_error(&report, "spells.prepared_spells", "maximum"));
+        assert!(has_error(
+            &report,
+            "spells.known_spells",
+            "Fireball is level 3"
+        ));
+        assert!(has_error(&report, "inventory", "4 items attuned"));
+
+        character.spells.prepared_spells.push(spell("Fireball"));
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&character)
+            .unwrap();
+        assert!(has_error(&report, "spells.prepared_spells", "maximum is 2"));
+    }
+
+    #[test]
+    fn test_level_and_ability_sanity_checks() {
+        let mut conn = setup_test_db();
+        let mut character = create_test_fighter();
+        character.level = 3;
+        character.abilities.charisma = 0;
+        character.abilities.strength = 22;
+
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&character)
+            .unwrap();
+        assert!(has_error(&report, "classes", "add up to 1"));
+        assert!(has_error(
+            &report,
+            "abilities.charisma",
+            "outside the 1-30 range"
+        ));
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.field == "abilities.strength" && f.severity == FindingSeverity::Warning));
+
+        // Unknown race and background are warnings only
+        character = create_test_fighter();
+        character.race = "Goblin".to_string();
+        character.background = "Haunted One".to_string();
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&character)
+            .unwrap();
+        assert!(report.valid);
+        assert_eq!(report.warning_count, 2);
+    }
+}
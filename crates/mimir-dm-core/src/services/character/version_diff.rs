@@ -0,0 +1,610 @@
+//! Structured comparison of two character snapshots
+//!
+//! Every change to a character is stored as a new row in `character_versions`.
+//! This module compares two of those snapshots and reports what changed in
+//! terms a DM cares about (hit points, spell slots, inventory, spells and
+//! features) so a bad level-up or tool call can be reviewed before it is
+//! rolled back with [`CharacterService::restore_character_version`].
+//!
+//! [`CharacterService::restore_character_version`]: super::CharacterService::restore_character_version
+
+use crate::models::character::data::{CharacterData, InventoryItem, SpellReference, SpellSlots};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A single value that differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Dotted path of the changed value (e.g. "abilities.strength")
+    pub field: String,
+    /// Value in the older snapshot
+    pub before: Value,
+    /// Value in the newer snapshot
+    pub after: Value,
+}
+
+/// Entries added to or removed from a list (spells, feats, features, ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListChange {
+    /// Name of the list (e.g. "spells.prepared_spells")
+    pub field: String,
+    /// Entries only present in the newer snapshot
+    pub added: Vec<String>,
+    /// Entries only present in the older snapshot
+    pub removed: Vec<String>,
+}
+
+/// Change to the spell slots of a single spell level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellSlotChange {
+    /// Spell level of the slots
+    pub level: i32,
+    /// Slots in the older snapshot, if the level existed
+    pub before: Option<SpellSlots>,
+    /// Slots in the newer snapshot, if the level exists
+    pub after: Option<SpellSlots>,
+}
+
+/// Kind of inventory change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InventoryChangeKind {
+    /// Item was gained
+    Added,
+    /// Item was lost
+    Removed,
+    /// Item is still carried but its quantity, attunement, charges or notes changed
+    Changed,
+}
+
+/// Change to a single inventory entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryChange {
+    /// Item name
+    pub name: String,
+    /// Item source book, if recorded
+    pub source: Option<String>,
+    /// What happened to the item
+    pub kind: InventoryChangeKind,
+    /// Entry in the older snapshot
+    pub before: Option<InventoryItem>,
+    /// Entry in the newer snapshot
+    pub after: Option<InventoryItem>,
+}
+
+/// Everything that differs between two character snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CharacterDiff {
+    /// Scalar and structured values that changed (HP, abilities, classes, ...)
+    pub fields: Vec<FieldChange>,
+    /// Spell slot changes by spell level
+    pub spell_slots: Vec<SpellSlotChange>,
+    /// Inventory entries gained, lost or modified
+    pub inventory: Vec<InventoryChange>,
+    /// Additions and removals for spells, feats, features and proficiencies
+    pub lists: Vec<ListChange>,
+}
+
+impl CharacterDiff {
+    /// Whether the two snapshots are identical in every compared value
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && self.spell_slots.is_empty()
+            && self.inventory.is_empty()
+            && self.lists.is_empty()
+    }
+}
+
+/// Compare two snapshots of the same character.
+///
+/// `before` is treated as the older snapshot. Version metadata
+/// (`version`, `snapshot_reason`, `created_at`) is ignored.
+pub fn diff_character_data(before: &CharacterData, after: &CharacterData) -> CharacterDiff {
+    let mut diff = CharacterDiff::default();
+    let fields = &mut diff.fields;
+
+    // Identity and progression
+    push_field(
+        fields,
+        "character_name",
+        &before.character_name,
+        &after.character_name,
+    );
+    push_field(fields, "level", &before.level, &after.level);
+    push_field(
+        fields,
+        "experience_points",
+        &before.experience_points,
+        &after.experience_points,
+    );
+    push_field(fields, "race", &before.race, &after.race);
+    push_field(fields, "subrace", &before.subrace, &after.subrace);
+    push_field(fields, "background", &before.background, &after.background);
+    push_field(fields, "alignment", &before.alignment, &after.alignment);
+    diff_classes(fields, before, after);
+
+    // Abilities
+    let (a, b) = (&before.abilities, &after.abilities);
+    let abilities = [
+        ("strength", a.strength, b.strength),
+        ("dexterity", a.dexterity, b.dexterity),
+        ("constitution", a.constitution, b.constitution),
+        ("intelligence", a.intelligence, b.intelligence),
+        ("wisdom", a.wisdom, b.wisdom),
+        ("charisma", a.charisma, b.charisma),
+    ];
+    for (name, old, new) in abilities {
+        push_field(fields, &format!("abilities.{}", name), &old, &new);
+    }
+
+    // Hit points and combat state
+    push_field(fields, "max_hp", &before.max_hp, &after.max_hp);
+    push_field(fields, "current_hp", &before.current_hp, &after.current_hp);
+    push_field(fields, "temp_hp", &before.temp_hp, &after.temp_hp);
+    push_field(
+        fields,
+        "death_saves",
+        &before.death_saves,
+        &after.death_saves,
+    );
+    push_field(
+        fields,
+        "exhaustion_level",
+        &before.exhaustion_level,
+        &after.exhaustion_level,
+    );
+    push_field(
+        fields,
+        "concentration",
+        &before.concentration,
+        &after.concentration,
+    );
+    push_field(fields, "speed", &before.speed, &after.speed);
+
+    // Resources
+    diff_class_resources(fields, before, after);
+    push_field(
+        fields,
+        "spells.pact_slots",
+        &before.spells.pact_slots,
+        &after.spells.pact_slots,
+    );
+    push_field(fields, "currency", &before.currency, &after.currency);
+    push_field(fields, "equipped", &before.equipped, &after.equipped);
+
+    diff.spell_slots = diff_spell_slots(before, after);
+    diff.inventory = diff_inventory(&before.inventory, &after.inventory);
+
+    // Lists
+    let lists = &mut diff.lists;
+    push_list(
+        lists,
+        "spells.cantrips",
+        spell_names(&before.spells.cantrips),
+        spell_names(&after.spells.cantrips),
+    );
+    push_list(
+        lists,
+        "spells.known_spells",
+        spell_names(&before.spells.known_spells),
+        spell_names(&after.spells.known_spells),
+    );
+    push_list(
+        lists,
+        "spells.prepared_spells",
+        spell_names(&before.spells.prepared_spells),
+        spell_names(&after.spells.prepared_spells),
+    );
+    push_list(
+        lists,
+        "class_features",
+        before
+            .class_features
+            .iter()
+            .map(|f| format!("{} ({} {})", f.name, f.class_name, f.level)),
+        after
+            .class_features
+            .iter()
+            .map(|f| format!("{} ({} {})", f.name, f.class_name, f.level)),
+    );
+    push_list(
+        lists,
+        "feats",
+        before.feats.iter().cloned(),
+        after.feats.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "optional_features",
+        before
+            .optional_features
+            .iter()
+            .map(|f| format!("{} ({})", f.name, f.feature_type)),
+        after
+            .optional_features
+            .iter()
+            .map(|f| format!("{} ({})", f.name, f.feature_type)),
+    );
+    push_list(
+        lists,
+        "conditions",
+        before.conditions.iter().cloned(),
+        after.conditions.iter().cloned(),
+    );
+
+    let (old, new) = (&before.proficiencies, &after.proficiencies);
+    push_list(
+        lists,
+        "proficiencies.skills",
+        old.skills.iter().cloned(),
+        new.skills.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "proficiencies.saves",
+        old.saves.iter().cloned(),
+        new.saves.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "proficiencies.armor",
+        old.armor.iter().cloned(),
+        new.armor.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "proficiencies.weapons",
+        old.weapons.iter().cloned(),
+        new.weapons.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "proficiencies.tools",
+        old.tools.iter().cloned(),
+        new.tools.iter().cloned(),
+    );
+    push_list(
+        lists,
+        "proficiencies.languages",
+        old.languages.iter().cloned(),
+        new.languages.iter().cloned(),
+    );
+
+    diff
+}
+
+/// Record a field change when the serialized values differ
+fn push_field<T: Serialize>(changes: &mut Vec<FieldChange>, field: &str, before: &T, after: &T) {
+    let before = serde_json::to_value(before).unwrap_or(Value::Null);
+    let after = serde_json::to_value(after).unwrap_or(Value::Null);
+    if before != after {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        });
+    }
+}
+
+/// Record added and removed entries when two lists differ
+fn push_list(
+    changes: &mut Vec<ListChange>,
+    field: &str,
+    before: impl IntoIterator<Item = String>,
+    after: impl IntoIterator<Item = String>,
+) {
+    let before: BTreeSet<String> = before.into_iter().collect();
+    let after: BTreeSet<String> = after.into_iter().collect();
+
+    let added: Vec<String> = after.difference(&before).cloned().collect();
+    let removed: Vec<String> = before.difference(&after).cloned().collect();
+    if !added.is_empty() || !removed.is_empty() {
+        changes.push(ListChange {
+            field: field.to_string(),
+            added,
+            removed,
+        });
+    }
+}
+
+fn spell_names(spells: &[SpellReference]) -> impl Iterator<Item = String> + '_ {
+    spells.iter().map(|s| s.name.clone())
+}
+
+/// Compare class levels, subclasses and hit dice per class
+fn diff_classes(changes: &mut Vec<FieldChange>, before: &CharacterData, after: &CharacterData) {
+    let old: BTreeMap<&str, _> = before
+        .classes
+        .iter()
+        .map(|c| (c.class_name.as_str(), c))
+        .collect();
+    let new: BTreeMap<&str, _> = after
+        .classes
+        .iter()
+        .map(|c| (c.class_name.as_str(), c))
+        .collect();
+
+    let names: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+    for name in names {
+        match (old.get(name), new.get(name)) {
+            (Some(o), Some(n)) => {
+                push_field(
+                    changes,
+                    &format!("classes.{}.level", name),
+                    &o.level,
+                    &n.level,
+                );
+                push_field(
+                    changes,
+                    &format!("classes.{}.subclass", name),
+                    &o.subclass,
+                    &n.subclass,
+                );
+                push_field(
+                    changes,
+                    &format!("classes.{}.hit_dice_remaining", name),
+                    &o.hit_dice_remaining,
+                    &n.hit_dice_remaining,
+                );
+            }
+            (o, n) => push_field(changes, &format!("classes.{}", name), &o, &n),
+        }
+    }
+}
+
+/// Compare class resources (Ki, Rage, Channel Divinity, ...) by name
+fn diff_class_resources(
+    changes: &mut Vec<FieldChange>,
+    before: &CharacterData,
+    after: &CharacterData,
+) {
+    let old: BTreeMap<&str, _> = before
+        .class_resources
+        .iter()
+        .map(|r| (r.name.as_str(), r))
+        .collect();
+    let new: BTreeMap<&str, _> = after
+        .class_resources
+        .iter()
+        .map(|r| (r.name.as_str(), r))
+        .collect();
+
+    let names: BTreeSet<&str> = old.keys().chain(new.keys()).copied().collect();
+    for name in names {
+        push_field(
+            changes,
+            &format!("class_resources.{}", name),
+            &old.get(name),
+            &new.get(name),
+        );
+    }
+}
+
+fn diff_spell_slots(before: &CharacterData, after: &CharacterData) -> Vec<SpellSlotChange> {
+    let levels: BTreeSet<i32> = before
+        .spells
+        .spell_slots
+        .keys()
+        .chain(after.spells.spell_slots.keys())
+        .copied()
+        .collect();
+
+    levels
+        .into_iter()
+        .filter_map(|level| {
+            let old = before.spells.spell_slots.get(&level);
+            let new = after.spells.spell_slots.get(&level);
+            (old != new).then(|| SpellSlotChange {
+                level,
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Compare inventory entries keyed by name and source
+fn diff_inventory(before: &[InventoryItem], after: &[InventoryItem]) -> Vec<InventoryChange> {
+    let key = |item: &InventoryItem| (item.name.to_lowercase(), item.source.clone());
+    let old: BTreeMap<_, &InventoryItem> = before.iter().map(|i| (key(i), i)).collect();
+    let new: BTreeMap<_, &InventoryItem> = after.iter().map(|i| (key(i), i)).collect();
+
+    let keys: BTreeSet<_> = old.keys().chain(new.keys()).cloned().collect();
+    keys.into_iter()
+        .filter_map(|k| {
+            let o = old.get(&k).copied();
+            let n = new.get(&k).copied();
+            let kind = match (o, n) {
+                (None, Some(_)) => InventoryChangeKind::Added,
+                (Some(_), None) => InventoryChangeKind::Removed,
+                (Some(o), Some(n)) if o != n => InventoryChangeKind::Changed,
+                _ => return None,
+            };
+            let item = n.or(o)?;
+            Some(InventoryChange {
+                name: item.name.clone(),
+                source: item.source.clone(),
+                kind,
+                before: o.cloned(),
+                after: n.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::character::data::{
+        AbilityScores, ClassLevel, Currency, FeatureReference, Proficiencies, SpellData,
+    };
+    use serde_json::json;
+
+    fn base_character() -> CharacterData {
+        let mut spell_slots = std::collections::HashMap::new();
+        spell_slots.insert(1, SpellSlots::new(2));
+
+        CharacterData {
+            character_name: "Elara".to_string(),
+            player_id: Some(1),
+            level: 1,
+            experience_points: 0,
+            version: 1,
+            snapshot_reason: None,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            race: "Elf".to_string(),
+            subrace: None,
+            classes: vec![ClassLevel {
+                class_name: "Wizard".to_string(),
+                level: 1,
+                subclass: None,
+                hit_dice_type: "d6".to_string(),
+                hit_dice_remaining: 1,
+            }],
+            background: "Sage".to_string(),
+            alignment: None,
+            abilities: AbilityScores {
+                strength: 8,
+                dexterity: 14,
+                constitution: 13,
+                intelligence: 16,
+                wisdom: 12,
+                charisma: 10,
+            },
+            max_hp: 7,
+            current_hp: 7,
+            temp_hp: 0,
+            death_saves: Default::default(),
+            exhaustion_level: 0,
+            conditions: vec![],
+            concentration: None,
+            speed: 30,
+            proficiencies: Proficiencies::default(),
+            class_features: vec![],
+            class_resources: vec![],
+            feats: vec![],
+            optional_features: vec![],
+            spells: SpellData {
+                known_spells: vec![SpellReference::new("Magic Missile", "PHB")],
+                prepared_spells: vec![],
+                cantrips: vec![SpellReference::new("Fire Bolt", "PHB")],
+                spell_slots,
+                pact_slots: None,
+            },
+            inventory: vec![InventoryItem {
+                name: "Dagger".to_string(),
+                source: Some("PHB".to_string()),
+                quantity: 2,
+                weight: 1.0,
+                value: 2.0,
+                notes: None,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
+            }],
+            currency: Currency::default(),
+            equipped: Default::default(),
+            personality: Default::default(),
+            npc_role: None,
+            npc_location: None,
+            npc_faction: None,
+            npc_notes: None,
+            legendary_actions: vec![],
+            legendary_action_count: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_empty_diff() {
+        let character = base_character();
+        let mut later = character.clone();
+        later.version = 5;
+        later.snapshot_reason = Some("No-op".to_string());
+
+        assert!(diff_character_data(&character, &later).is_empty());
+    }
+
+    #[test]
+    fn test_level_up_diff() {
+        let before = base_character();
+        let mut after = before.clone();
+        after.level = 2;
+        after.classes[0].level = 2;
+        after.classes[0].hit_dice_remaining = 2;
+        after.max_hp = 12;
+        after.current_hp = 12;
+        after.spells.spell_slots.insert(1, SpellSlots::new(3));
+        after
+            .spells
+            .known_spells
+            .push(SpellReference::new("Shield", "PHB"));
+        after
+            .class_features
+            .push(FeatureReference::new("Arcane Recovery", "Wizard", "PHB", 2));
+
+        let diff = diff_character_data(&before, &after);
+
+        let field = |name: &str| diff.fields.iter().find(|c| c.field == name);
+        assert_eq!(field("level").unwrap().after, json!(2));
+        assert_eq!(field("classes.Wizard.level").unwrap().before, json!(1));
+        assert_eq!(field("max_hp").unwrap().after, json!(12));
+        assert!(field("abilities.strength").is_none());
+
+        assert_eq!(diff.spell_slots.len(), 1);
+        assert_eq!(diff.spell_slots[0].level, 1);
+        assert_eq!(diff.spell_slots[0].after.as_ref().unwrap().max, 3);
+
+        let known = diff
+            .lists
+            .iter()
+            .find(|l| l.field == "spells.known_spells")
+            .unwrap();
+        assert_eq!(known.added, vec!["Shield".to_string()]);
+        assert!(known.removed.is_empty());
+        assert!(diff.lists.iter().any(|l| l.field == "class_features"));
+        assert!(diff.inventory.is_empty());
+    }
+
+    #[test]
+    fn test_inventory_diff() {
+        let before = base_character();
+        let mut after = before.clone();
+        after.inventory[0].quantity = 1;
+        after.inventory.push(InventoryItem {
+            name: "Spellbook".to_string(),
+            source: Some("PHB".to_string()),
+            quantity: 1,
+            weight: 3.0,
+            value: 50.0,
+            notes: None,
+            requires_attunement: false,
+            attuned: false,
+            charges: None,
+        });
+
+        let diff = diff_character_data(&before, &after);
+        assert_eq!(diff.inventory.len(), 2);
+
+        let dagger = diff.inventory.iter().find(|c| c.name == "Dagger").unwrap();
+        assert_eq!(dagger.kind, InventoryChangeKind::Changed);
+        assert_eq!(dagger.before.as_ref().unwrap().quantity, 2);
+        assert_eq!(dagger.after.as_ref().unwrap().quantity, 1);
+
+        let book = diff
+            .inventory
+            .iter()
+            .find(|c| c.name == "Spellbook")
+            .unwrap();
+        assert_eq!(book.kind, InventoryChangeKind::Added);
+
+        // Reversing the comparison reports the item as removed
+        let reverse = diff_character_data(&after, &before);
+        let book = reverse
+            .inventory
+            .iter()
+            .find(|c| c.name == "Spellbook")
+            .unwrap();
+        assert_eq!(book.kind, InventoryChangeKind::Removed);
+    }
+}
@@ -258,6 +258,34 @@ impl<'a> ClassService<'a> {
         Ok(result.flatten())
     }
 
+    /// Get a class by name when its source is unknown.
+    ///
+    /// Returns the first matching class, like `get_caster_progression_by_name`.
+    /// Used to read class table columns for characters, which don't record
+    /// the source of their classes.
+    ///
+    /// # Arguments
+    /// * `class_name` - Name of the class (case-insensitive)
+    ///
+    /// # Returns
+    /// * `Ok(Some(Class))` - The parsed class data if found
+    /// * `Ok(None)` - If no matching class exists
+    /// * `Err(DbError)` - If the query fails or the stored JSON can't be parsed
+    pub fn get_class_by_name(&mut self, class_name_param: &str) -> Result<Option<Class>> {
+        use crate::schema::catalog_classes::dsl::*;
+
+        let class_json = catalog_classes
+            .filter(name.like(class_name_param))
+            .select(full_class_json)
+            .first::<String>(self.conn)
+            .optional()?;
+
+        match class_json {
+            Some(json) => Ok(Some(serde_json::from_str(&json)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Get a specific subclass by name, class name, and source.
     ///
     /// Includes fluff data and introductory description if available.
@@ -0,0 +1,1066 @@
+//! Combat encounter service for the initiative tracker.
+//!
+//! Starts encounters from module encounter tags or from the tokens on a map,
+//! rolls initiative, and tracks rounds, turn order and per-combatant HP.
+//! Damage is adjusted for the resistances, immunities and vulnerabilities in
+//! the monster's catalog data and every HP change is written to a log.
+//! All state is written through to the database on every change so that a
+//! session can be resumed after the application is closed.
+
+use crate::connection::DbConnection;
+use crate::domain::dice::DiceRoller;
+use crate::error::{DbError, Result};
+use crate::models::campaign::{
+    CombatCondition, CombatEncounter, CombatState, CombatStatus, Combatant, CombatantType,
+    DamageDefenses, HpEvent, HpEventType, HpMode, Map, Module, NewCombatCondition,
+    NewCombatEncounter, NewCombatant, NewHpEvent, Token, TokenType, UpdateCombatEncounter,
+    UpdateCombatant,
+};
+use crate::models::catalog::monster::CatalogMonster;
+use crate::models::character::data::AbilityScores;
+use crate::schema::{
+    catalog_conditions, catalog_monsters, combat_combatants, combat_conditions, combat_encounters,
+    combat_hp_events, maps, modules, tokens,
+};
+use crate::services::{CharacterService, ModuleMonsterService};
+use diesel::prelude::*;
+use serde_json::Value;
+
+/// Service for running combat encounters
+pub struct CombatService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> CombatService<'a> {
+    /// Create a new combat service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Start an encounter from a module's encounter tag.
+    ///
+    /// Creates one combatant per monster instance (a quantity of 3 goblins
+    /// becomes "Goblin 1", "Goblin 2" and "Goblin 3"), optionally adds the
+    /// campaign's player characters, rolls initiative for everyone and sets
+    /// the turn marker on the first combatant.
+    /// Everything happens in one transaction, so a failure part-way through
+    /// leaves no half-built encounter behind.
+    ///
+    /// # Arguments
+    /// * `module_id` - Database ID of the module
+    /// * `encounter_tag` - Encounter tag to pull monsters from (`None` for untagged monsters)
+    /// * `include_party` - Whether to add the campaign's PCs as combatants
+    /// * `hp_mode` - Whether monsters get average or rolled max HP
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The new encounter with combatants in initiative order
+    pub fn start_from_encounter(
+        &mut self,
+        module_id: i32,
+        encounter_tag: Option<&str>,
+        include_party: bool,
+        hp_mode: HpMode,
+    ) -> Result<CombatState> {
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            CombatService::new(conn).build_from_encounter(
+                module_id,
+                encounter_tag,
+                include_party,
+                hp_mode,
+            )
+        })
+    }
+
+    fn build_from_encounter(
+        &mut self,
+        module_id: i32,
+        encounter_tag: Option<&str>,
+        include_party: bool,
+        hp_mode: HpMode,
+    ) -> Result<CombatState> {
+        let module: Module = modules::table
+            .find(module_id)
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Module".to_string(),
+                id: module_id.to_string(),
+            })?;
+
+        let group = {
+            let mut mm_service = ModuleMonsterService::new(self.conn);
+            mm_service
+                .get_monsters_grouped_by_encounter(module_id)?
+                .into_iter()
+                .find(|g| g.encounter_tag.as_deref() == encounter_tag)
+        };
+
+        let group = group.ok_or_else(|| DbError::NotFound {
+            entity_type: "Encounter".to_string(),
+            id: encounter_tag.unwrap_or("(untagged)").to_string(),
+        })?;
+
+        let name = encounter_tag
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| format!("{} encounter", module.name));
+        let new_encounter = NewCombatEncounter::new(module.campaign_id, name)
+            .with_module(module_id, encounter_tag.map(|t| t.to_string()));
+        let encounter = self.create_encounter(new_encounter)?;
+
+        for entry in group.monsters {
+            for index in 0..entry.quantity.max(0) {
+                let name = if entry.quantity > 1 {
+                    format!("{} {}", entry.monster_name, index + 1)
+                } else {
+                    entry.monster_name.clone()
+                };
+                let mut combatant = NewCombatant::monster(
+                    encounter.id,
+                    name,
+                    entry.monster_name.clone(),
+                    entry.monster_source.clone(),
+                );
+                if let Some(ref data) = entry.monster_data {
+                    combatant = apply_monster_stats(combatant, data, hp_mode);
+                }
+                self.add_combatant(combatant, true)?;
+            }
+        }
+
+        if include_party {
+            let pcs = {
+                let mut char_service = CharacterService::new(self.conn);
+                char_service.list_pcs_for_campaign(module.campaign_id)?
+            };
+            for pc in pcs {
+                let combatant = self.character_combatant(encounter.id, pc.id, pc.character_name, true)?;
+                self.add_combatant(combatant, true)?;
+            }
+        }
+
+        self.begin(encounter.id)
+    }
+
+    /// Start an encounter from the tokens placed on a map.
+    ///
+    /// Monster, PC and NPC tokens become combatants; traps and markers are
+    /// ignored. Monster stats come from the linked catalog monster and
+    /// character stats from the linked character's latest version. As with
+    /// [`Self::start_from_encounter`], the encounter is built atomically.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    /// * `hp_mode` - Whether monsters get average or rolled max HP
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The new encounter with combatants in initiative order
+    pub fn start_from_map(&mut self, map_id: i32, hp_mode: HpMode) -> Result<CombatState> {
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            CombatService::new(conn).build_from_map(map_id, hp_mode)
+        })
+    }
+
+    fn build_from_map(&mut self, map_id: i32, hp_mode: HpMode) -> Result<CombatState> {
+        let map: Map = maps::table
+            .find(map_id)
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Map".to_string(),
+                id: map_id.to_string(),
+            })?;
+
+        let map_tokens: Vec<Token> = tokens::table
+            .filter(tokens::map_id.eq(map_id))
+            .order(tokens::name.asc())
+            .load(self.conn)?;
+
+        let mut new_encounter = NewCombatEncounter::new(map.campaign_id, map.name.clone()).with_map(map_id);
+        new_encounter.module_id = map.module_id;
+        let encounter = self.create_encounter(new_encounter)?;
+
+        for token in map_tokens {
+            let combatant = match token.token_type_enum() {
+                TokenType::Monster => {
+                    let catalog: Option<CatalogMonster> = match token.monster_id {
+                        Some(monster_id) => catalog_monsters::table
+                            .find(monster_id)
+                            .first(self.conn)
+                            .optional()?,
+                        None => None,
+                    };
+                    match catalog {
+                        Some(catalog) => {
+                            let combatant = NewCombatant::monster(
+                                encounter.id,
+                                token.name.clone(),
+                                catalog.name.clone(),
+                                catalog.source.clone(),
+                            );
+                            match serde_json::from_str::<Value>(&catalog.full_monster_json) {
+                                Ok(data) => apply_monster_stats(combatant, &data, hp_mode),
+                                Err(_) => combatant,
+                            }
+                        }
+                        None => NewCombatant::new(
+                            encounter.id,
+                            token.name.clone(),
+                            CombatantType::Monster,
+                        ),
+                    }
+                }
+                TokenType::PC | TokenType::NPC => {
+                    let is_pc = token.token_type_enum() == TokenType::PC;
+                    match token.character_id {
+                        Some(character_id) => self.character_combatant(
+                            encounter.id,
+                            character_id,
+                            token.name.clone(),
+                            is_pc,
+                        )?,
+                        None => {
+                            let combatant_type = if is_pc {
+                                CombatantType::PC
+                            } else {
+                                CombatantType::NPC
+                            };
+                            NewCombatant::new(encounter.id, token.name.clone(), combatant_type)
+                        }
+                    }
+                }
+                TokenType::Trap | TokenType::Marker => continue,
+            };
+
+            self.add_combatant(combatant.with_token(token.id), true)?;
+        }
+
+        self.begin(encounter.id)
+    }
+
+    /// Create an empty encounter.
+    ///
+    /// # Arguments
+    /// * `new_encounter` - The encounter to insert
+    ///
+    /// # Returns
+    /// * `Ok(CombatEncounter)` - The created encounter record
+    pub fn create_encounter(&mut self, new_encounter: NewCombatEncounter) -> Result<CombatEncounter> {
+        diesel::insert_into(combat_encounters::table)
+            .values(&new_encounter)
+            .returning(CombatEncounter::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get an encounter by ID.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(Some(CombatEncounter))` - If found
+    /// * `Ok(None)` - If no encounter exists with that ID
+    pub fn get_encounter(&mut self, id: i32) -> Result<Option<CombatEncounter>> {
+        combat_encounters::table
+            .find(id)
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Get the full state of an encounter with combatants in initiative order.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The encounter and its combatants
+    pub fn get_combat_state(&mut self, encounter_id: i32) -> Result<CombatState> {
+        let encounter = self.require_encounter(encounter_id)?;
+        let combatants = self.list_combatants(encounter_id)?;
+        let combatant_ids: Vec<i32> = combatants.iter().map(|c| c.id).collect();
+        let conditions = combat_conditions::table
+            .filter(combat_conditions::combatant_id.eq_any(combatant_ids))
+            .order(combat_conditions::id.asc())
+            .load(self.conn)?;
+        Ok(CombatState {
+            encounter,
+            combatants,
+            conditions,
+        })
+    }
+
+    /// Get the most recent active encounter for a campaign.
+    ///
+    /// Used to resume a fight after the application restarts.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    ///
+    /// # Returns
+    /// * `Ok(Some(CombatState))` - The active encounter, if any
+    pub fn get_active_combat(&mut self, campaign_id: i32) -> Result<Option<CombatState>> {
+        let encounter: Option<CombatEncounter> = combat_encounters::table
+            .filter(combat_encounters::campaign_id.eq(campaign_id))
+            .filter(combat_encounters::status.eq(CombatStatus::Active.as_str()))
+            .order(combat_encounters::id.desc())
+            .first(self.conn)
+            .optional()?;
+
+        match encounter {
+            Some(encounter) => self.get_combat_state(encounter.id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// List all encounters for a campaign, newest first.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CombatEncounter>)` - All encounters in the campaign
+    pub fn list_encounters(&mut self, campaign_id: i32) -> Result<Vec<CombatEncounter>> {
+        combat_encounters::table
+            .filter(combat_encounters::campaign_id.eq(campaign_id))
+            .order(combat_encounters::id.desc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// List the combatants of an encounter in initiative order.
+    ///
+    /// Ties are broken by initiative modifier, then by the order the
+    /// combatants were added.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Combatant>)` - Combatants sorted highest initiative first
+    pub fn list_combatants(&mut self, encounter_id: i32) -> Result<Vec<Combatant>> {
+        combat_combatants::table
+            .filter(combat_combatants::encounter_id.eq(encounter_id))
+            .order((
+                combat_combatants::initiative.desc(),
+                combat_combatants::initiative_modifier.desc(),
+                combat_combatants::id.asc(),
+            ))
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get a combatant by ID.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    ///
+    /// # Returns
+    /// * `Ok(Some(Combatant))` - If found
+    pub fn get_combatant(&mut self, id: i32) -> Result<Option<Combatant>> {
+        combat_combatants::table
+            .find(id)
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Add a combatant to an encounter.
+    ///
+    /// # Arguments
+    /// * `new_combatant` - The combatant to add
+    /// * `roll_initiative` - Roll d20 + initiative modifier instead of using the given initiative
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The created combatant
+    pub fn add_combatant(
+        &mut self,
+        mut new_combatant: NewCombatant,
+        roll_initiative: bool,
+    ) -> Result<Combatant> {
+        if roll_initiative {
+            new_combatant.initiative = roll_initiative_for(new_combatant.initiative_modifier);
+        }
+
+        diesel::insert_into(combat_combatants::table)
+            .values(&new_combatant)
+            .returning(Combatant::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Update a combatant.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `update` - Fields to update
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The updated combatant
+    pub fn update_combatant(&mut self, id: i32, mut update: UpdateCombatant) -> Result<Combatant> {
+        update.updated_at = Some(chrono::Utc::now().to_rfc3339());
+
+        diesel::update(combat_combatants::table.find(id))
+            .set(&update)
+            .returning(Combatant::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Remove a combatant from an encounter.
+    ///
+    /// If it is currently that combatant's turn, the turn passes to the next
+    /// combatant first.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The encounter state after removal
+    pub fn remove_combatant(&mut self, id: i32) -> Result<CombatState> {
+        let combatant = self.require_combatant(id)?;
+        let encounter = self.require_encounter(combatant.encounter_id)?;
+
+        if encounter.current_combatant_id == Some(id) {
+            self.next_turn(encounter.id)?;
+        }
+
+        diesel::delete(combat_combatants::table.find(id)).execute(self.conn)?;
+
+        // If the removed combatant was the only one left, clear the turn marker
+        let state = self.get_combat_state(encounter.id)?;
+        if state.encounter.current_combatant_id == Some(id) {
+            self.set_turn(encounter.id, state.encounter.round, None)?;
+            return self.get_combat_state(encounter.id);
+        }
+        Ok(state)
+    }
+
+    /// Set a combatant's initiative explicitly (e.g. a player's physical roll).
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `initiative` - The initiative total
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The updated combatant
+    pub fn set_initiative(&mut self, id: i32, initiative: i32) -> Result<Combatant> {
+        self.update_combatant(id, UpdateCombatant::initiative(initiative))
+    }
+
+    /// Reroll initiative for every combatant and restart at round 1.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The encounter in its new initiative order
+    pub fn reroll_initiative(&mut self, encounter_id: i32) -> Result<CombatState> {
+        let combatants = self.list_combatants(encounter_id)?;
+        for combatant in combatants {
+            let roll = roll_initiative_for(combatant.initiative_modifier);
+            self.update_combatant(combatant.id, UpdateCombatant::initiative(roll))?;
+        }
+        self.begin(encounter_id)
+    }
+
+    /// Advance to the next active combatant.
+    ///
+    /// Inactive (defeated or removed) combatants are skipped. Wrapping past
+    /// the last combatant starts a new round.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The encounter with the turn marker moved
+    pub fn next_turn(&mut self, encounter_id: i32) -> Result<CombatState> {
+        let state = self.get_combat_state(encounter_id)?;
+        let order: Vec<i32> = state
+            .combatants
+            .iter()
+            .filter(|c| c.is_active)
+            .map(|c| c.id)
+            .collect();
+
+        if order.is_empty() {
+            return Ok(state);
+        }
+
+        let mut round = state.encounter.round;
+        let next = match state.encounter.current_combatant_id {
+            None => order[0],
+            Some(current_id) => {
+                // Find the next active combatant after the current one in the full order,
+                // so the turn still advances correctly if the current one was deactivated
+                let position = state.combatants.iter().position(|c| c.id == current_id);
+                let after = position
+                    .map(|pos| {
+                        state.combatants[pos + 1..]
+                            .iter()
+                            .find(|c| c.is_active)
+                            .map(|c| c.id)
+                    })
+                    .unwrap_or(None);
+                match after {
+                    Some(id) => id,
+                    None => {
+                        round += 1;
+                        order[0]
+                    }
+                }
+            }
+        };
+
+        self.set_turn(encounter_id, round, Some(next))?;
+        if round > state.encounter.round {
+            self.expire_conditions(&state.conditions, round)?;
+        }
+        self.get_combat_state(encounter_id)
+    }
+
+    /// Step back to the previous active combatant.
+    ///
+    /// Stepping back past the first combatant returns to the previous round
+    /// (never before round 1).
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(CombatState)` - The encounter with the turn marker moved
+    pub fn previous_turn(&mut self, encounter_id: i32) -> Result<CombatState> {
+        let state = self.get_combat_state(encounter_id)?;
+        let order: Vec<i32> = state
+            .combatants
+            .iter()
+            .filter(|c| c.is_active)
+            .map(|c| c.id)
+            .collect();
+
+        let (Some(current_id), Some(&last)) = (state.encounter.current_combatant_id, order.last()) else {
+            return Ok(state);
+        };
+
+        let mut round = state.encounter.round;
+        let position = state.combatants.iter().position(|c| c.id == current_id);
+        let before = position.and_then(|pos| {
+            state.combatants[..pos]
+                .iter()
+                .rev()
+                .find(|c| c.is_active)
+                .map(|c| c.id)
+        });
+
+        let previous = match before {
+            Some(id) => id,
+            None if round > 1 => {
+                round -= 1;
+                last
+            }
+            None => return Ok(state),
+        };
+
+        self.set_turn(encounter_id, round, Some(previous))?;
+        self.get_combat_state(encounter_id)
+    }
+
+    /// Set a combatant's current HP.
+    ///
+    /// HP is clamped between 0 and the combatant's maximum (if known).
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `current_hp` - The new HP value
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The updated combatant
+    pub fn set_combatant_hp(&mut self, id: i32, current_hp: i32) -> Result<Combatant> {
+        let combatant = self.require_combatant(id)?;
+        let clamped = match combatant.max_hp {
+            Some(max) => current_hp.clamp(0, max),
+            None => current_hp.max(0),
+        };
+        self.update_combatant(id, UpdateCombatant::hp(clamped))
+    }
+
+    /// Adjust a combatant's HP by a delta (negative for damage, positive for healing).
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `delta` - Amount to add to current HP
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The updated combatant
+    pub fn adjust_combatant_hp(&mut self, id: i32, delta: i32) -> Result<Combatant> {
+        let combatant = self.require_combatant(id)?;
+        let current = combatant.current_hp.or(combatant.max_hp).unwrap_or(0);
+        self.set_combatant_hp(id, current + delta)
+    }
+
+    /// Take a combatant in or out of the turn order (e.g. when defeated).
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `is_active` - Whether the combatant still takes turns
+    ///
+    /// # Returns
+    /// * `Ok(Combatant)` - The updated combatant
+    pub fn set_combatant_active(&mut self, id: i32, is_active: bool) -> Result<Combatant> {
+        self.update_combatant(id, UpdateCombatant::active(is_active))
+    }
+
+    /// Apply damage to a combatant.
+    ///
+    /// For monster combatants, the damage is first adjusted by the
+    /// resistances, immunities and vulnerabilities in the catalog monster's
+    /// data. Temporary HP absorbs damage before current HP. The change is
+    /// recorded in the combatant's HP log.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `amount` - Damage as rolled
+    /// * `damage_type` - Damage type (e.g. "fire"), if known
+    /// * `magical` - Whether the damage comes from a magical attack
+    ///
+    /// # Returns
+    /// * `Ok(HpEvent)` - The log entry describing what was applied
+    pub fn apply_damage(
+        &mut self,
+        id: i32,
+        amount: i32,
+        damage_type: Option<&str>,
+        magical: bool,
+    ) -> Result<HpEvent> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Damage amount cannot be negative".to_string(),
+            ));
+        }
+
+        let combatant = self.require_combatant(id)?;
+        let defense = match damage_type {
+            Some(damage_type) => self
+                .monster_defenses(&combatant)?
+                .and_then(|d| d.defense_for(damage_type, magical)),
+            None => None,
+        };
+        let applied = defense.map(|d| d.apply(amount)).unwrap_or(amount);
+
+        let absorbed = applied.min(combatant.temp_hp);
+        let temp_hp_after = combatant.temp_hp - absorbed;
+        let hp_after = combatant
+            .current_hp
+            .or(combatant.max_hp)
+            .map(|hp| (hp - (applied - absorbed)).max(0));
+
+        let mut event = self.new_hp_event(&combatant, HpEventType::Damage, amount, applied)?;
+        event.damage_type = damage_type.map(|t| t.to_lowercase());
+        event.defense = defense.map(|d| d.as_str().to_string());
+        event.hp_after = hp_after;
+        event.temp_hp_after = temp_hp_after;
+
+        self.record_hp_event(event)
+    }
+
+    /// Heal a combatant.
+    ///
+    /// Healing never raises HP above the combatant's maximum and does not
+    /// restore temporary HP.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `amount` - HP regained
+    ///
+    /// # Returns
+    /// * `Ok(HpEvent)` - The log entry describing what was applied
+    pub fn apply_healing(&mut self, id: i32, amount: i32) -> Result<HpEvent> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Healing amount cannot be negative".to_string(),
+            ));
+        }
+
+        let combatant = self.require_combatant(id)?;
+        let hp_before = combatant.current_hp.or(combatant.max_hp);
+        let hp_after = hp_before.map(|hp| match combatant.max_hp {
+            Some(max) => (hp + amount).min(max),
+            None => hp + amount,
+        });
+        let applied = match (hp_before, hp_after) {
+            (Some(before), Some(after)) => after - before,
+            _ => 0,
+        };
+
+        let mut event = self.new_hp_event(&combatant, HpEventType::Healing, amount, applied)?;
+        event.hp_after = hp_after;
+
+        self.record_hp_event(event)
+    }
+
+    /// Grant temporary HP to a combatant.
+    ///
+    /// Temporary HP doesn't stack: the combatant keeps whichever is higher,
+    /// their current temporary HP or the new amount.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the combatant
+    /// * `amount` - Temporary HP granted
+    ///
+    /// # Returns
+    /// * `Ok(HpEvent)` - The log entry describing what was applied
+    pub fn grant_temp_hp(&mut self, id: i32, amount: i32) -> Result<HpEvent> {
+        if amount < 0 {
+            return Err(DbError::InvalidData(
+                "Temporary HP cannot be negative".to_string(),
+            ));
+        }
+
+        let combatant = self.require_combatant(id)?;
+        let temp_hp_after = combatant.temp_hp.max(amount);
+
+        let mut event = self.new_hp_event(
+            &combatant,
+            HpEventType::TempHp,
+            amount,
+            temp_hp_after - combatant.temp_hp,
+        )?;
+        event.temp_hp_after = temp_hp_after;
+
+        self.record_hp_event(event)
+    }
+
+    /// Get a combatant's HP log, oldest first.
+    ///
+    /// # Arguments
+    /// * `combatant_id` - Database ID of the combatant
+    ///
+    /// # Returns
+    /// * `Ok(Vec<HpEvent>)` - Damage, healing and temp HP events
+    pub fn list_hp_events(&mut self, combatant_id: i32) -> Result<Vec<HpEvent>> {
+        combat_hp_events::table
+            .filter(combat_hp_events::combatant_id.eq(combatant_id))
+            .order(combat_hp_events::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get the HP log for every combatant in an encounter, oldest first.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(Vec<HpEvent>)` - Damage, healing and temp HP events
+    pub fn list_encounter_hp_events(&mut self, encounter_id: i32) -> Result<Vec<HpEvent>> {
+        combat_hp_events::table
+            .inner_join(combat_combatants::table)
+            .filter(combat_combatants::encounter_id.eq(encounter_id))
+            .select(HpEvent::as_select())
+            .order(combat_hp_events::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Apply a catalog condition to a combatant.
+    ///
+    /// The condition must exist in the condition catalog (conditions and
+    /// diseases). Monsters immune to
+    /// the condition (per their catalog data) are rejected.
+    ///
+    /// # Arguments
+    /// * `combatant_id` - Database ID of the combatant
+    /// * `condition_name` - Catalog condition name (case-insensitive)
+    /// * `duration_rounds` - How many rounds the condition lasts (`None` until removed)
+    ///
+    /// # Returns
+    /// * `Ok(CombatCondition)` - The applied condition
+    pub fn add_condition(
+        &mut self,
+        combatant_id: i32,
+        condition_name: &str,
+        duration_rounds: Option<i32>,
+    ) -> Result<CombatCondition> {
+        let combatant = self.require_combatant(combatant_id)?;
+        let encounter = self.require_encounter(combatant.encounter_id)?;
+
+        let (name, source): (String, String) = catalog_conditions::table
+            // LIKE without wildcards is a case-insensitive match in SQLite
+            .filter(catalog_conditions::name.like(condition_name))
+            .select((catalog_conditions::name, catalog_conditions::source))
+            .first(self.conn)
+            .optional()?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Condition".to_string(),
+                id: condition_name.to_string(),
+            })?;
+
+        if let Some(defenses) = self.monster_defenses(&combatant)? {
+            if defenses.is_immune_to_condition(&name) {
+                return Err(DbError::InvalidData(format!(
+                    "{} is immune to the {} condition",
+                    combatant.name, name
+                )));
+            }
+        }
+
+        let mut new_condition = NewCombatCondition::new(combatant_id, name, source);
+        new_condition.applied_round = encounter.round;
+        if let Some(duration) = duration_rounds {
+            new_condition = new_condition.with_duration(duration);
+        }
+
+        diesel::insert_into(combat_conditions::table)
+            .values(&new_condition)
+            .returning(CombatCondition::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Remove a condition from a combatant.
+    ///
+    /// # Arguments
+    /// * `condition_id` - Database ID of the applied condition
+    ///
+    /// # Returns
+    /// * `Ok(())` - If removal succeeds
+    pub fn remove_condition(&mut self, condition_id: i32) -> Result<()> {
+        diesel::delete(combat_conditions::table.find(condition_id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// List the conditions applied to a combatant.
+    ///
+    /// # Arguments
+    /// * `combatant_id` - Database ID of the combatant
+    ///
+    /// # Returns
+    /// * `Ok(Vec<CombatCondition>)` - Applied conditions, oldest first
+    pub fn list_conditions(&mut self, combatant_id: i32) -> Result<Vec<CombatCondition>> {
+        combat_conditions::table
+            .filter(combat_conditions::combatant_id.eq(combatant_id))
+            .order(combat_conditions::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Mark an encounter as completed.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(CombatEncounter)` - The completed encounter
+    pub fn end_combat(&mut self, encounter_id: i32) -> Result<CombatEncounter> {
+        diesel::update(combat_encounters::table.find(encounter_id))
+            .set(&UpdateCombatEncounter::status(CombatStatus::Completed))
+            .returning(CombatEncounter::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Delete an encounter and all its combatants.
+    ///
+    /// # Arguments
+    /// * `encounter_id` - Database ID of the encounter
+    ///
+    /// # Returns
+    /// * `Ok(())` - If deletion succeeds
+    pub fn delete_encounter(&mut self, encounter_id: i32) -> Result<()> {
+        diesel::delete(combat_encounters::table.find(encounter_id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// Reset to round 1 with the turn marker on the highest initiative.
+    fn begin(&mut self, encounter_id: i32) -> Result<CombatState> {
+        let first = self
+            .list_combatants(encounter_id)?
+            .into_iter()
+            .find(|c| c.is_active)
+            .map(|c| c.id);
+        self.set_turn(encounter_id, 1, first)?;
+        self.get_combat_state(encounter_id)
+    }
+
+    fn set_turn(&mut self, encounter_id: i32, round: i32, current: Option<i32>) -> Result<()> {
+        diesel::update(combat_encounters::table.find(encounter_id))
+            .set(&UpdateCombatEncounter::turn(round, current))
+            .execute(self.conn)?;
+        Ok(())
+    }
+
+    /// Remove conditions whose duration has run out by the given round.
+    fn expire_conditions(&mut self, conditions: &[CombatCondition], round: i32) -> Result<()> {
+        let expired: Vec<i32> = conditions
+            .iter()
+            .filter(|c| c.is_expired(round))
+            .map(|c| c.id)
+            .collect();
+        if !expired.is_empty() {
+            diesel::delete(combat_conditions::table.filter(combat_conditions::id.eq_any(expired)))
+                .execute(self.conn)?;
+        }
+        Ok(())
+    }
+
+    /// Load the damage defenses of a monster combatant's catalog entry.
+    fn monster_defenses(&mut self, combatant: &Combatant) -> Result<Option<DamageDefenses>> {
+        let (Some(name), Some(source)) = (&combatant.monster_name, &combatant.monster_source) else {
+            return Ok(None);
+        };
+
+        let catalog: Option<CatalogMonster> = catalog_monsters::table
+            .filter(catalog_monsters::name.eq(name))
+            .filter(catalog_monsters::source.eq(source))
+            .first(self.conn)
+            .optional()?;
+
+        match catalog {
+            Some(catalog) => {
+                let data: Value = serde_json::from_str(&catalog.full_monster_json)?;
+                Ok(Some(DamageDefenses::from_monster_json(&data)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Start an HP log entry with the combatant's current values.
+    fn new_hp_event(
+        &mut self,
+        combatant: &Combatant,
+        event_type: HpEventType,
+        amount: i32,
+        applied_amount: i32,
+    ) -> Result<NewHpEvent> {
+        let encounter = self.require_encounter(combatant.encounter_id)?;
+        let hp_before = combatant.current_hp.or(combatant.max_hp);
+        Ok(NewHpEvent {
+            combatant_id: combatant.id,
+            round: encounter.round,
+            event_type: event_type.as_str().to_string(),
+            amount,
+            damage_type: None,
+            applied_amount,
+            defense: None,
+            hp_before,
+            hp_after: hp_before,
+            temp_hp_before: combatant.temp_hp,
+            temp_hp_after: combatant.temp_hp,
+        })
+    }
+
+    /// Write an HP log entry and apply its resulting HP to the combatant.
+    fn record_hp_event(&mut self, event: NewHpEvent) -> Result<HpEvent> {
+        self.conn.transaction(|conn| {
+            diesel::update(combat_combatants::table.find(event.combatant_id))
+                .set(&UpdateCombatant {
+                    current_hp: event.hp_after.map(Some),
+                    temp_hp: Some(event.temp_hp_after),
+                    updated_at: Some(chrono::Utc::now().to_rfc3339()),
+                    ..Default::default()
+                })
+                .execute(conn)?;
+
+            diesel::insert_into(combat_hp_events::table)
+                .values(&event)
+                .returning(HpEvent::as_returning())
+                .get_result(conn)
+        })
+        .map_err(Into::into)
+    }
+
+    fn require_encounter(&mut self, id: i32) -> Result<CombatEncounter> {
+        self.get_encounter(id)?.ok_or_else(|| DbError::NotFound {
+            entity_type: "CombatEncounter".to_string(),
+            id: id.to_string(),
+        })
+    }
+
+    fn require_combatant(&mut self, id: i32) -> Result<Combatant> {
+        self.get_combatant(id)?.ok_or_else(|| DbError::NotFound {
+            entity_type: "Combatant".to_string(),
+            id: id.to_string(),
+        })
+    }
+
+    /// Build a combatant from a character's latest version.
+    fn character_combatant(
+        &mut self,
+        encounter_id: i32,
+        character_id: i32,
+        name: String,
+        is_pc: bool,
+    ) -> Result<NewCombatant> {
+        let combatant = NewCombatant::character(encounter_id, name, character_id, is_pc);
+        let mut char_service = CharacterService::new(self.conn);
+        match char_service.get_character(character_id) {
+            Ok((_, data)) => Ok(combatant
+                .with_initiative_modifier(data.abilities.dex_modifier())
+                .with_hp(data.current_hp, data.max_hp)),
+            Err(e) if e.is_not_found() => Ok(combatant),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Roll d20 + modifier for initiative.
+fn roll_initiative_for(modifier: i32) -> i32 {
+    DiceRoller::new().roll_die(20) as i32 + modifier
+}
+
+/// Fill in initiative modifier, HP and AC from a catalog monster's JSON.
+fn apply_monster_stats(combatant: NewCombatant, data: &Value, hp_mode: HpMode) -> NewCombatant {
+    let dex_mod = data
+        .get("dex")
+        .and_then(|d| d.as_i64())
+        .map(|dex| AbilityScores::modifier(dex as i32))
+        .unwrap_or(0);
+    let mut combatant = combatant.with_initiative_modifier(dex_mod);
+
+    if let Some(max_hp) = monster_max_hp(data, hp_mode) {
+        combatant = combatant.with_hp(max_hp, max_hp);
+    }
+    if let Some(ac) = monster_armor_class(data) {
+        combatant = combatant.with_armor_class(ac);
+    }
+    combatant
+}
+
+/// Get a monster's max HP: the catalog average, or a roll of its hit dice formula.
+fn monster_max_hp(data: &Value, hp_mode: HpMode) -> Option<i32> {
+    let hp = data.get("hp")?;
+    if let Some(n) = hp.as_i64() {
+        return Some(n as i32);
+    }
+
+    let average = hp.get("average").and_then(|a| a.as_i64()).map(|a| a as i32);
+    match hp_mode {
+        HpMode::Average => average,
+        HpMode::Rolled => hp
+            .get("formula")
+            .and_then(|f| f.as_str())
+            .and_then(roll_hit_dice)
+            .or(average),
+    }
+}
+
+/// Get a monster's AC from either a plain number or the first AC entry.
+fn monster_armor_class(data: &Value) -> Option<i32> {
+    let ac = data.get("ac")?;
+    let first = match ac.as_array() {
+        Some(entries) => entries.first()?,
+        None => ac,
+    };
+    first
+        .as_i64()
+        .or_else(|| first.get("ac").and_then(|a| a.as_i64()))
+        .map(|a| a as i32)
+}
+
+/// Roll a hit dice formula such as "7d10 + 21" (minimum 1).
+fn roll_hit_dice(formula: &str) -> Option<i32> {
+    DiceRoller::new()
+        .roll(formula)
+        .ok()
+        .map(|result| result.total.max(1))
+}
@@ -0,0 +1,383 @@
+//! Random encounter generator.
+//!
+//! Builds encounters from catalog monsters matching a set of `MonsterFilters`
+//! so that their adjusted XP lands in the party's budget for a requested
+//! difficulty. Encounters can be generated for a campaign (restricted to its
+//! enabled sources) and written straight into a module as a new encounter group.
+
+use crate::connection::DbConnection;
+use crate::dal::campaign::modules::ModuleRepository;
+use crate::domain::dice::DiceRoller;
+use crate::domain::encounter::{
+    encounter_multiplier, xp_for_cr, Difficulty, EncounterDifficulty, EncounterMonster,
+    XpThresholds,
+};
+use crate::error::{DbError, Result};
+use crate::models::catalog::monster::{MonsterFilters, MonsterSummary};
+use crate::services::{CampaignService, CharacterService, ModuleMonsterService, MonsterService};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Default cap on the total number of monsters in a generated encounter
+const DEFAULT_MAX_MONSTERS: i32 = 12;
+
+/// Default cap on the number of distinct monster types
+const DEFAULT_MAX_MONSTER_TYPES: usize = 3;
+
+/// Number of random encounters tried before settling for the closest one
+const GENERATION_ATTEMPTS: usize = 25;
+
+/// Options for generating a random encounter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterGenerationOptions {
+    /// Level of each party member (taken from the campaign when empty)
+    #[serde(default)]
+    pub party_levels: Vec<i32>,
+    /// Difficulty to aim for
+    pub difficulty: Difficulty,
+    /// Catalog filters for candidate monsters (environment, type, CR, sources)
+    #[serde(default)]
+    pub filters: MonsterFilters,
+    /// Maximum total number of monsters
+    pub max_monsters: Option<i32>,
+    /// Maximum number of distinct monster types
+    pub max_monster_types: Option<usize>,
+}
+
+impl EncounterGenerationOptions {
+    /// Create options for a difficulty with no filters.
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            party_levels: Vec::new(),
+            difficulty,
+            filters: MonsterFilters::default(),
+            max_monsters: None,
+            max_monster_types: None,
+        }
+    }
+
+    /// Set the party levels.
+    pub fn with_party(mut self, party_levels: Vec<i32>) -> Self {
+        self.party_levels = party_levels;
+        self
+    }
+
+    /// Set the monster filters.
+    pub fn with_filters(mut self, filters: MonsterFilters) -> Self {
+        self.filters = filters;
+        self
+    }
+}
+
+/// A generated encounter and how it rates against the party
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedEncounter {
+    /// Difficulty that was requested
+    pub requested: Difficulty,
+    /// Lowest adjusted XP that rates as the requested difficulty
+    pub target_min_xp: i32,
+    /// Highest adjusted XP that rates as the requested difficulty
+    pub target_max_xp: i32,
+    /// The generated monsters and their difficulty breakdown
+    pub encounter: EncounterDifficulty,
+    /// Encounter tag the monsters were saved under, if added to a module
+    pub encounter_tag: Option<String>,
+}
+
+/// A catalog monster that can be placed in an encounter
+struct Candidate {
+    summary: MonsterSummary,
+    xp: i32,
+}
+
+/// Service for generating random encounters from the monster catalog.
+pub struct EncounterGeneratorService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> EncounterGeneratorService<'a> {
+    /// Create a new encounter generator service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Generate an encounter for the party in the options.
+    ///
+    /// # Arguments
+    /// * `options` - Party, difficulty, filters and size limits
+    /// * `roller` - Dice roller used for random picks (seed it for repeatable results)
+    ///
+    /// # Returns
+    /// * `Ok(GeneratedEncounter)` - The encounter closest to the requested difficulty
+    /// * `Err(DbError::InvalidData)` - If there is no party or no monster matches the filters
+    pub fn generate(
+        &mut self,
+        options: &EncounterGenerationOptions,
+        roller: &mut DiceRoller,
+    ) -> Result<GeneratedEncounter> {
+        if options.party_levels.is_empty() {
+            return Err(DbError::InvalidData(
+                "Cannot generate an encounter without party levels".to_string(),
+            ));
+        }
+
+        let party_size = options.party_levels.len();
+        let thresholds = XpThresholds::for_party(&options.party_levels);
+        let (min_xp, max_xp) = thresholds.xp_range(options.difficulty);
+        let max_monsters = options.max_monsters.unwrap_or(DEFAULT_MAX_MONSTERS).max(1);
+        let max_types = options
+            .max_monster_types
+            .unwrap_or(DEFAULT_MAX_MONSTER_TYPES)
+            .max(1);
+
+        let mut monster_service = MonsterService::new(self.conn);
+        let candidates: Vec<Candidate> = monster_service
+            .search_monsters(options.filters.clone())?
+            .into_iter()
+            .filter_map(|summary| {
+                let xp = xp_for_cr(&summary.cr)?;
+                // A single monster must fit the budget on its own
+                let fits = (xp as f64 * encounter_multiplier(1, party_size)) as i32 <= max_xp;
+                fits.then_some(Candidate { summary, xp })
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return Err(DbError::InvalidData(format!(
+                "No monsters match the filters for a {} encounter",
+                options.difficulty.as_str()
+            )));
+        }
+
+        debug!(
+            "Generating {} encounter ({}-{} XP) from {} candidates",
+            options.difficulty.as_str(),
+            min_xp,
+            max_xp,
+            candidates.len()
+        );
+
+        let mut best: Option<(i32, Vec<(usize, i32)>)> = None;
+        for _ in 0..GENERATION_ATTEMPTS {
+            let picks = build_encounter(
+                &candidates,
+                roller,
+                party_size,
+                (min_xp, max_xp),
+                max_monsters,
+                max_types,
+            );
+            if picks.is_empty() {
+                continue;
+            }
+            let adjusted = adjusted_xp(&candidates, &picks, party_size);
+            let distance = if adjusted < min_xp {
+                min_xp - adjusted
+            } else {
+                (adjusted - max_xp).max(0)
+            };
+            if best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                best = Some((distance, picks));
+            }
+            if distance == 0 {
+                break;
+            }
+        }
+
+        let (_, picks) = best.ok_or_else(|| {
+            DbError::InvalidData("Could not build an encounter from the matching monsters".into())
+        })?;
+
+        let monsters = picks
+            .iter()
+            .map(|&(index, quantity)| {
+                let candidate = &candidates[index];
+                EncounterMonster {
+                    name: candidate.summary.name.clone(),
+                    source: candidate.summary.source.clone(),
+                    quantity,
+                    cr: Some(candidate.summary.cr.clone()),
+                    xp: candidate.xp,
+                }
+            })
+            .collect();
+
+        Ok(GeneratedEncounter {
+            requested: options.difficulty,
+            target_min_xp: min_xp,
+            target_max_xp: max_xp,
+            encounter: EncounterDifficulty::calculate(&options.party_levels, monsters),
+            encounter_tag: None,
+        })
+    }
+
+    /// Generate an encounter for a campaign.
+    ///
+    /// Candidate monsters are limited to the campaign's enabled sources, and
+    /// the campaign's player characters are used when no party is given.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - The campaign to generate for
+    /// * `options` - Difficulty, filters and size limits
+    /// * `roller` - Dice roller used for random picks
+    pub fn generate_for_campaign(
+        &mut self,
+        campaign_id: i32,
+        options: &EncounterGenerationOptions,
+        roller: &mut DiceRoller,
+    ) -> Result<GeneratedEncounter> {
+        let mut options = options.clone();
+
+        let enabled = CampaignService::new(self.conn).get_enabled_sources(campaign_id)?;
+        if !enabled.is_empty() {
+            let sources = match options.filters.sources.take() {
+                Some(requested) if !requested.is_empty() => {
+                    let allowed: Vec<String> = requested
+                        .into_iter()
+                        .filter(|s| enabled.contains(s))
+                        .collect();
+                    if allowed.is_empty() {
+                        return Err(DbError::InvalidData(
+                            "None of the requested sources are enabled for this campaign"
+                                .to_string(),
+                        ));
+                    }
+                    allowed
+                }
+                _ => enabled,
+            };
+            options.filters.sources = Some(sources);
+        }
+
+        if options.party_levels.is_empty() {
+            options.party_levels =
+                CharacterService::new(self.conn).get_party_levels(campaign_id)?;
+        }
+
+        self.generate(&options, roller)
+    }
+
+    /// Generate an encounter and add it to a module under a new encounter tag.
+    ///
+    /// # Arguments
+    /// * `module_id` - The module to add the encounter to
+    /// * `encounter_tag` - Tag for the new encounter group (must not already be used)
+    /// * `options` - Difficulty, filters and size limits
+    /// * `roller` - Dice roller used for random picks
+    ///
+    /// # Returns
+    /// * `Ok(GeneratedEncounter)` - The encounter that was saved
+    /// * `Err(DbError::NotFound)` - If the module doesn't exist
+    /// * `Err(DbError::InvalidData)` - If the tag is empty or already used in the module
+    pub fn generate_for_module(
+        &mut self,
+        module_id: i32,
+        encounter_tag: &str,
+        options: &EncounterGenerationOptions,
+        roller: &mut DiceRoller,
+    ) -> Result<GeneratedEncounter> {
+        let encounter_tag = encounter_tag.trim();
+        if encounter_tag.is_empty() {
+            return Err(DbError::InvalidData(
+                "Encounter tag cannot be empty".to_string(),
+            ));
+        }
+
+        let module = ModuleRepository::new(self.conn)
+            .find_by_id(module_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Module".to_string(),
+                id: module_id.to_string(),
+            })?;
+
+        let existing_tags = ModuleMonsterService::new(self.conn).get_encounter_tags(module_id)?;
+        if existing_tags
+            .iter()
+            .any(|t| t.as_deref() == Some(encounter_tag))
+        {
+            return Err(DbError::InvalidData(format!(
+                "Module already has an encounter tagged '{}'",
+                encounter_tag
+            )));
+        }
+
+        let mut generated = self.generate_for_campaign(module.campaign_id, options, roller)?;
+
+        let mut mm_service = ModuleMonsterService::new(self.conn);
+        for monster in &generated.encounter.monsters {
+            mm_service.add_monster(
+                module_id,
+                monster.name.clone(),
+                monster.source.clone(),
+                monster.quantity,
+                Some(encounter_tag.to_string()),
+            )?;
+        }
+
+        generated.encounter_tag = Some(encounter_tag.to_string());
+        Ok(generated)
+    }
+}
+
+/// Adjusted XP of a set of picks (candidate index, quantity).
+fn adjusted_xp(candidates: &[Candidate], picks: &[(usize, i32)], party_size: usize) -> i32 {
+    let count: i32 = picks.iter().map(|(_, quantity)| quantity).sum();
+    if count == 0 {
+        return 0;
+    }
+    let base: i32 = picks
+        .iter()
+        .map(|&(index, quantity)| candidates[index].xp * quantity)
+        .sum();
+    (base as f64 * encounter_multiplier(count, party_size)).round() as i32
+}
+
+/// Randomly add monsters until the adjusted XP reaches the budget.
+///
+/// Each step either adds another copy of a monster already in the encounter
+/// or (while under the type limit) a new monster, choosing only additions
+/// that keep the adjusted XP within the budget's upper bound.
+fn build_encounter(
+    candidates: &[Candidate],
+    roller: &mut DiceRoller,
+    party_size: usize,
+    (min_xp, max_xp): (i32, i32),
+    max_monsters: i32,
+    max_types: usize,
+) -> Vec<(usize, i32)> {
+    let mut picks: Vec<(usize, i32)> = Vec::new();
+
+    loop {
+        let count: i32 = picks.iter().map(|(_, quantity)| quantity).sum();
+        if count >= max_monsters
+            || (count > 0 && adjusted_xp(candidates, &picks, party_size) >= min_xp)
+        {
+            break;
+        }
+
+        let mut options: Vec<usize> = picks.iter().map(|(index, _)| *index).collect();
+        if picks.len() < max_types {
+            options.extend((0..candidates.len()).filter(|i| !picks.iter().any(|(p, _)| p == i)));
+        }
+        options.retain(|&index| {
+            let mut next = picks.clone();
+            match next.iter_mut().find(|(p, _)| *p == index) {
+                Some(pick) => pick.1 += 1,
+                None => next.push((index, 1)),
+            }
+            adjusted_xp(candidates, &next, party_size) <= max_xp
+        });
+
+        if options.is_empty() {
+            break;
+        }
+
+        let index = options[roller.roll_die(options.len() as u32) as usize - 1];
+        match picks.iter_mut().find(|(p, _)| *p == index) {
+            Some(pick) => pick.1 += 1,
+            None => picks.push((index, 1)),
+        }
+    }
+
+    picks
+}
@@ -1,12 +1,16 @@
 //! Fog of War service for Visual Display System.
 //!
-//! Manages fog of war state on maps - tracking revealed areas
-//! and toggling fog visibility.
+//! Manages fog of war state on maps - tracking areas revealed by the DM,
+//! remembering areas explored by PC tokens, and toggling fog visibility.
 
 use crate::connection::DbConnection;
-use crate::error::Result;
-use crate::models::campaign::{FogRevealedArea, FogRevealedAreaSummary, NewFogRevealedArea, UpdateMap};
+use crate::domain::visibility::{Point, SeenRegion};
+use crate::error::{DbError, Result};
+use crate::models::campaign::{
+    FogAreaSource, FogRevealedArea, FogRevealedAreaSummary, Map, NewFogRevealedArea, UpdateMap,
+};
 use crate::schema::{fog_revealed_areas, maps};
+use crate::services::VisibilityService;
 use diesel::prelude::*;
 
 /// Service for managing fog of war
@@ -143,7 +147,7 @@ impl<'a> FogOfWarService<'a> {
         self.reveal_area(new_area)
     }
 
-    /// Reveal a circular area (stored as bounding box).
+    /// Reveal a circular area.
     ///
     /// # Arguments
     /// * `map_id` - Database ID of the map
@@ -164,7 +168,26 @@ impl<'a> FogOfWarService<'a> {
         self.reveal_area(new_area)
     }
 
-    /// Get all revealed areas for a map.
+    /// Reveal a polygon area.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    /// * `polygon` - Outline of the area (pixels)
+    ///
+    /// # Returns
+    /// * `Ok(FogRevealedArea)` - The created revealed area
+    /// * `Err(DbError::InvalidData)` - If the outline has fewer than 3 points
+    pub fn reveal_polygon(&mut self, map_id: i32, polygon: &[Point]) -> Result<FogRevealedArea> {
+        if polygon.len() < 3 {
+            return Err(DbError::InvalidData(
+                "A revealed polygon needs at least 3 points".to_string(),
+            ));
+        }
+        let new_area = NewFogRevealedArea::from_polygon(map_id, polygon);
+        self.reveal_area(new_area)
+    }
+
+    /// Get all areas revealed by the DM on a map.
     ///
     /// # Arguments
     /// * `map_id` - Database ID of the map
@@ -172,11 +195,7 @@ impl<'a> FogOfWarService<'a> {
     /// # Returns
     /// * `Ok(Vec<FogRevealedArea>)` - All revealed areas on the map
     pub fn get_revealed_areas(&mut self, map_id: i32) -> Result<Vec<FogRevealedArea>> {
-        fog_revealed_areas::table
-            .filter(fog_revealed_areas::map_id.eq(map_id))
-            .order(fog_revealed_areas::id.asc())
-            .load(self.conn)
-            .map_err(Into::into)
+        self.get_areas(map_id, FogAreaSource::Manual)
     }
 
     /// Get revealed area summaries for a map.
@@ -191,6 +210,111 @@ impl<'a> FogOfWarService<'a> {
         Ok(areas.into_iter().map(FogRevealedAreaSummary::from).collect())
     }
 
+    /// Get all areas PC tokens have explored on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FogRevealedArea>)` - All explored areas on the map
+    pub fn get_explored_areas(&mut self, map_id: i32) -> Result<Vec<FogRevealedArea>> {
+        self.get_areas(map_id, FogAreaSource::Explored)
+    }
+
+    /// Get explored area summaries for a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FogRevealedAreaSummary>)` - Summaries of all explored areas
+    pub fn get_explored_area_summaries(&mut self, map_id: i32) -> Result<Vec<FogRevealedAreaSummary>> {
+        let areas = self.get_explored_areas(map_id)?;
+        Ok(areas.into_iter().map(FogRevealedAreaSummary::from).collect())
+    }
+
+    /// Remember what the PC tokens on a map can currently see.
+    ///
+    /// Explored areas accumulate as tokens move, so the players keep seeing
+    /// the layout of places they have been even once they are out of sight.
+    /// Regions already explored to within half a grid square are skipped,
+    /// and recorded areas a new region covers are replaced by it, so moving
+    /// around explored ground doesn't add rows.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<FogRevealedArea>)` - The newly recorded explored areas
+    pub fn record_explored(&mut self, map_id: i32) -> Result<Vec<FogRevealedArea>> {
+        let visibility = VisibilityService::new(self.conn).get_pc_visibility(map_id)?;
+        let map: Map = maps::table.find(map_id).first(self.conn)?;
+        let tolerance = map.grid_size_px_or_default() / 2.0;
+
+        let mut explored: Vec<(Option<i32>, SeenRegion)> = self
+            .get_explored_areas(map_id)?
+            .into_iter()
+            .filter_map(|area| area.seen_region().map(|region| (Some(area.id), region)))
+            .collect();
+        let mut superseded = Vec::new();
+
+        for region in visibility.seen_regions() {
+            let samples = region.sample_points();
+            let already_explored = samples.iter().all(|point| {
+                explored
+                    .iter()
+                    .any(|(_, seen)| seen.contains_near(*point, tolerance))
+            });
+            if already_explored {
+                continue;
+            }
+
+            explored.retain(|(id, seen)| {
+                let covered = seen
+                    .sample_points()
+                    .iter()
+                    .all(|point| region.contains_near(*point, tolerance));
+                if covered {
+                    superseded.extend(*id);
+                }
+                !covered
+            });
+            explored.push((None, region));
+        }
+
+        let new_areas: Vec<NewFogRevealedArea> = explored
+            .iter()
+            .filter(|(id, _)| id.is_none())
+            .map(|(_, region)| NewFogRevealedArea::explored(map_id, region))
+            .collect();
+
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            diesel::delete(
+                fog_revealed_areas::table.filter(fog_revealed_areas::id.eq_any(&superseded)),
+            )
+            .execute(conn)?;
+            new_areas
+                .iter()
+                .map(|area| {
+                    diesel::insert_into(fog_revealed_areas::table)
+                        .values(area)
+                        .returning(FogRevealedArea::as_returning())
+                        .get_result(conn)
+                        .map_err(Into::into)
+                })
+                .collect()
+        })
+    }
+
+    fn get_areas(&mut self, map_id: i32, source: FogAreaSource) -> Result<Vec<FogRevealedArea>> {
+        fog_revealed_areas::table
+            .filter(fog_revealed_areas::map_id.eq(map_id))
+            .filter(fog_revealed_areas::source.eq(source.as_str()))
+            .order(fog_revealed_areas::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
     /// Delete a specific revealed area (re-fog that area).
     ///
     /// # Arguments
@@ -204,13 +328,13 @@ impl<'a> FogOfWarService<'a> {
         Ok(())
     }
 
-    /// Clear all revealed areas for a map (reset fog to full coverage).
+    /// Clear all revealed and explored areas for a map (reset fog to full coverage).
     ///
     /// # Arguments
     /// * `map_id` - Database ID of the map
     ///
     /// # Returns
-    /// * `Ok(usize)` - Number of revealed areas deleted
+    /// * `Ok(usize)` - Number of revealed and explored areas deleted
     pub fn reset_fog(&mut self, map_id: i32) -> Result<usize> {
         let count = diesel::delete(
             fog_revealed_areas::table.filter(fog_revealed_areas::map_id.eq(map_id)),
@@ -242,7 +366,7 @@ impl<'a> FogOfWarService<'a> {
         self.reveal_rect(map_id, 0.0, 0.0, map_width, map_height)
     }
 
-    /// Count revealed areas on a map.
+    /// Count areas revealed by the DM on a map.
     ///
     /// # Arguments
     /// * `map_id` - Database ID of the map
@@ -252,6 +376,7 @@ impl<'a> FogOfWarService<'a> {
     pub fn count_revealed_areas(&mut self, map_id: i32) -> Result<i64> {
         fog_revealed_areas::table
             .filter(fog_revealed_areas::map_id.eq(map_id))
+            .filter(fog_revealed_areas::source.eq(FogAreaSource::Manual.as_str()))
             .count()
             .get_result(self.conn)
             .map_err(Into::into)
@@ -0,0 +1,347 @@
+//! Lore index service for semantic search.
+//!
+//! Stores embedded chunks of book content and campaign documents and runs
+//! nearest-neighbour queries against them. Embedding itself happens outside
+//! this crate; the service only persists vectors produced by a provider and
+//! searches them with sqlite-vec.
+
+use crate::connection::DbConnection;
+use crate::error::{DbError, Result};
+use crate::models::campaign::{
+    EmbeddedChunk, LoreIndexInfo, LoreSearchFilter, LoreSearchResult, LoreSource, LoreSourceType,
+    NewLoreChunk, NewLoreSource,
+};
+use crate::schema::{lore_chunks, lore_index_info, lore_sources};
+use diesel::prelude::*;
+use diesel::sql_types::{Binary, Integer, Text};
+use sha2::{Digest, Sha256};
+use zerocopy::AsBytes;
+
+/// Largest `k` sqlite-vec accepts for a KNN query.
+const MAX_KNN: i64 = 4096;
+
+/// How many extra neighbours to fetch so filtered searches still fill `limit`.
+const FILTER_OVERFETCH: i64 = 5;
+
+/// Service for storing and searching the lore embedding index
+pub struct LoreIndexService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> LoreIndexService<'a> {
+    /// Create a new lore index service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Hash text the same way indexed sources are hashed.
+    ///
+    /// Callers compare this against `LoreSource::content_hash` to decide
+    /// whether a book or document needs re-embedding.
+    pub fn content_hash(text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(text.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Get the embedding model and dimensions the index was built with.
+    ///
+    /// # Returns
+    /// * `Ok(None)` - If nothing has been indexed yet
+    pub fn index_info(&mut self) -> Result<Option<LoreIndexInfo>> {
+        lore_index_info::table
+            .find(1)
+            .select(LoreIndexInfo::as_select())
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Find an indexed source by type and key.
+    pub fn find_source(
+        &mut self,
+        source_type: LoreSourceType,
+        source_key: &str,
+    ) -> Result<Option<LoreSource>> {
+        lore_sources::table
+            .filter(lore_sources::source_type.eq(source_type.as_str()))
+            .filter(lore_sources::source_key.eq(source_key))
+            .select(LoreSource::as_select())
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Check whether a source is already indexed with the given content.
+    ///
+    /// # Arguments
+    /// * `source_type` - Book or document
+    /// * `source_key` - Book ID or document ID
+    /// * `content_hash` - Hash of the current text, from [`Self::content_hash`]
+    pub fn is_current(
+        &mut self,
+        source_type: LoreSourceType,
+        source_key: &str,
+        content_hash: &str,
+    ) -> Result<bool> {
+        Ok(self
+            .find_source(source_type, source_key)?
+            .is_some_and(|s| s.content_hash == content_hash))
+    }
+
+    /// List indexed sources.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - When set, only books and this campaign's documents
+    pub fn list_sources(&mut self, campaign_id: Option<i32>) -> Result<Vec<LoreSource>> {
+        let mut query = lore_sources::table
+            .select(LoreSource::as_select())
+            .order_by((lore_sources::source_type, lore_sources::title))
+            .into_boxed();
+
+        if let Some(campaign_id) = campaign_id {
+            query = query.filter(
+                lore_sources::campaign_id
+                    .is_null()
+                    .or(lore_sources::campaign_id.eq(campaign_id)),
+            );
+        }
+
+        query.load(self.conn).map_err(Into::into)
+    }
+
+    /// Store a source and its embedded chunks, replacing any previous version.
+    ///
+    /// The first source stored fixes the index's embedding model and vector
+    /// width. Storing vectors from a different model is rejected; call
+    /// [`Self::reset_index`] and re-index everything to switch models.
+    ///
+    /// # Arguments
+    /// * `source` - Source metadata; `chunk_count` is filled in from `chunks`
+    /// * `chunks` - Chunks with their embeddings, in document order
+    /// * `embedding_model` - Name of the model that produced the embeddings
+    ///
+    /// # Returns
+    /// * `Ok(LoreSource)` - The stored source
+    pub fn store_source(
+        &mut self,
+        mut source: NewLoreSource,
+        chunks: Vec<EmbeddedChunk>,
+        embedding_model: &str,
+    ) -> Result<LoreSource> {
+        if LoreSourceType::parse(&source.source_type).is_none() {
+            return Err(DbError::InvalidData(format!(
+                "Unknown lore source type: {}",
+                source.source_type
+            )));
+        }
+
+        let dimensions = match chunks.first() {
+            Some(chunk) => chunk.embedding.len(),
+            None => 0,
+        };
+        if chunks.iter().any(|c| c.embedding.len() != dimensions) {
+            return Err(DbError::InvalidData(
+                "All chunk embeddings must have the same length".to_string(),
+            ));
+        }
+        if !chunks.is_empty() && dimensions == 0 {
+            return Err(DbError::InvalidData(
+                "Embeddings must not be empty".to_string(),
+            ));
+        }
+
+        let existing_info = self.index_info()?;
+        if let Some(info) = &existing_info {
+            if !chunks.is_empty()
+                && (info.embedding_model != embedding_model
+                    || info.dimensions as usize != dimensions)
+            {
+                return Err(DbError::InvalidData(format!(
+                    "Index was built with {} ({} dimensions) but got {} ({} dimensions); rebuild the index to switch models",
+                    info.embedding_model, info.dimensions, embedding_model, dimensions
+                )));
+            }
+        }
+
+        source.chunk_count = chunks.len() as i32;
+
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            if existing_info.is_none() && !chunks.is_empty() {
+                diesel::insert_into(lore_index_info::table)
+                    .values((
+                        lore_index_info::id.eq(1),
+                        lore_index_info::embedding_model.eq(embedding_model),
+                        lore_index_info::dimensions.eq(dimensions as i32),
+                    ))
+                    .execute(conn)?;
+                diesel::sql_query(format!(
+                    "CREATE VIRTUAL TABLE IF NOT EXISTS lore_vectors USING vec0(embedding float[{}])",
+                    dimensions
+                ))
+                .execute(conn)?;
+            } else if existing_info.is_some() {
+                // Chunk ids freed by a cascade may be reused below
+                prune_orphaned_vectors(conn)?;
+            }
+
+            let source_type = source.source_type.clone();
+            let source_key = source.source_key.clone();
+            delete_source(conn, &source_type, &source_key)?;
+
+            let stored = diesel::insert_into(lore_sources::table)
+                .values(&source)
+                .returning(LoreSource::as_returning())
+                .get_result(conn)?;
+
+            for (index, chunk) in chunks.into_iter().enumerate() {
+                let chunk_id: i32 = diesel::insert_into(lore_chunks::table)
+                    .values(&NewLoreChunk {
+                        source_id: stored.id,
+                        chunk_index: index as i32,
+                        heading: chunk.heading,
+                        content: chunk.content,
+                    })
+                    .returning(lore_chunks::id)
+                    .get_result(conn)?;
+
+                diesel::sql_query("INSERT INTO lore_vectors(rowid, embedding) VALUES (?, ?)")
+                    .bind::<Integer, _>(chunk_id)
+                    .bind::<Binary, _>(chunk.embedding.as_bytes())
+                    .execute(conn)?;
+            }
+
+            Ok(stored)
+        })
+    }
+
+    /// Remove a source and its vectors from the index.
+    ///
+    /// # Returns
+    /// * `Ok(bool)` - Whether the source was indexed
+    pub fn remove_source(&mut self, source_type: LoreSourceType, source_key: &str) -> Result<bool> {
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            delete_source(conn, source_type.as_str(), source_key)
+        })
+    }
+
+    /// Clear the whole index, including the recorded embedding model.
+    pub fn reset_index(&mut self) -> Result<()> {
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            diesel::sql_query("DROP TABLE IF EXISTS lore_vectors").execute(conn)?;
+            diesel::delete(lore_sources::table).execute(conn)?;
+            diesel::delete(lore_index_info::table).execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Find the chunks closest to a query embedding.
+    ///
+    /// # Arguments
+    /// * `query_embedding` - Embedding of the search text, from the index's model
+    /// * `limit` - Maximum number of chunks to return
+    /// * `filter` - Optional campaign and source type restrictions
+    ///
+    /// # Returns
+    /// * `Ok(Vec<LoreSearchResult>)` - Matches ordered from most to least similar
+    pub fn search(
+        &mut self,
+        query_embedding: &[f32],
+        limit: i64,
+        filter: &LoreSearchFilter,
+    ) -> Result<Vec<LoreSearchResult>> {
+        let Some(info) = self.index_info()? else {
+            return Ok(Vec::new());
+        };
+
+        if query_embedding.len() != info.dimensions as usize {
+            return Err(DbError::InvalidData(format!(
+                "Query embedding has {} dimensions but the index uses {}",
+                query_embedding.len(),
+                info.dimensions
+            )));
+        }
+
+        // Orphaned vectors would otherwise take up places among the k nearest
+        prune_orphaned_vectors(self.conn)?;
+
+        let limit = limit.clamp(1, MAX_KNN);
+        let filtered = filter.campaign_id.is_some() || filter.source_type.is_some();
+        let k = if filtered {
+            (limit * FILTER_OVERFETCH).min(MAX_KNN)
+        } else {
+            limit
+        };
+
+        let mut sql = String::from(
+            "WITH matches AS (
+                SELECT rowid, distance FROM lore_vectors WHERE embedding MATCH ? AND k = ?
+             )
+             SELECT c.id AS chunk_id, s.id AS source_id, s.source_type, s.source_key, s.title,
+                    s.campaign_id, c.heading, c.content, m.distance
+             FROM matches m
+             JOIN lore_chunks c ON c.id = m.rowid
+             JOIN lore_sources s ON s.id = c.source_id
+             WHERE 1 = 1",
+        );
+        if filter.campaign_id.is_some() {
+            sql.push_str(" AND (s.campaign_id IS NULL OR s.campaign_id = ?)");
+        }
+        if filter.source_type.is_some() {
+            sql.push_str(" AND s.source_type = ?");
+        }
+        sql.push_str(" ORDER BY m.distance LIMIT ?");
+
+        let mut query = diesel::sql_query(sql)
+            .into_boxed()
+            .bind::<Binary, _>(query_embedding.as_bytes().to_vec())
+            .bind::<diesel::sql_types::BigInt, _>(k);
+        if let Some(campaign_id) = filter.campaign_id {
+            query = query.bind::<Integer, _>(campaign_id);
+        }
+        if let Some(source_type) = filter.source_type {
+            query = query.bind::<Text, _>(source_type.as_str());
+        }
+        query
+            .bind::<diesel::sql_types::BigInt, _>(limit)
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+}
+
+/// Delete vectors whose chunks are gone.
+///
+/// `lore_vectors` is a virtual table without foreign keys, so chunks removed
+/// by a cascade (for example when their campaign is deleted) leave their
+/// vectors behind.
+fn prune_orphaned_vectors(conn: &mut DbConnection) -> Result<usize> {
+    diesel::sql_query("DELETE FROM lore_vectors WHERE rowid NOT IN (SELECT id FROM lore_chunks)")
+        .execute(conn)
+        .map_err(Into::into)
+}
+
+/// Delete a source's vectors, chunks and row. Returns whether it existed.
+fn delete_source(conn: &mut DbConnection, source_type: &str, source_key: &str) -> Result<bool> {
+    let existing: Option<i32> = lore_sources::table
+        .filter(lore_sources::source_type.eq(source_type))
+        .filter(lore_sources::source_key.eq(source_key))
+        .select(lore_sources::id)
+        .first(conn)
+        .optional()?;
+
+    let Some(source_id) = existing else {
+        return Ok(false);
+    };
+
+    let has_vectors = lore_index_info::table.count().get_result::<i64>(conn)? > 0;
+    if has_vectors {
+        diesel::sql_query(
+            "DELETE FROM lore_vectors WHERE rowid IN (SELECT id FROM lore_chunks WHERE source_id = ?)",
+        )
+        .bind::<Integer, _>(source_id)
+        .execute(conn)?;
+    }
+
+    diesel::delete(lore_sources::table.find(source_id)).execute(conn)?;
+    Ok(true)
+}
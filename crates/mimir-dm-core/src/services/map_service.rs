@@ -4,9 +4,10 @@
 //! regional maps for visual display during in-person play sessions.
 
 use crate::connection::DbConnection;
-use crate::error::Result;
-use crate::models::campaign::{Map, MapSummary, NewMap, UpdateMap};
-use crate::schema::maps;
+use crate::domain::measurement::GridMeasure;
+use crate::error::{DbError, Result};
+use crate::models::campaign::{DiagonalRule, Map, MapSummary, NewMap, UpdateMap};
+use crate::schema::{campaigns, maps};
 use diesel::prelude::*;
 
 /// Service for managing maps
@@ -51,6 +52,31 @@ impl<'a> MapService<'a> {
             .map_err(Into::into)
     }
 
+    /// Get how distances are measured on a map.
+    ///
+    /// Uses the map's grid and its campaign's diagonal rule.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(GridMeasure)` - The map's measuring rules
+    /// * `Err(DbError::NotFound)` - If the map doesn't exist
+    pub fn get_grid_measure(&mut self, id: i32) -> Result<GridMeasure> {
+        let map = self.get_map(id)?.ok_or_else(|| DbError::NotFound {
+            entity_type: "Map".to_string(),
+            id: id.to_string(),
+        })?;
+        let diagonal_rule: String = campaigns::table
+            .find(map.campaign_id)
+            .select(campaigns::diagonal_rule)
+            .first(self.conn)?;
+        Ok(GridMeasure::for_map(
+            &map,
+            DiagonalRule::from_str(&diagonal_rule),
+        ))
+    }
+
     /// List all campaign-level maps (not tied to a module).
     ///
     /// # Arguments
@@ -0,0 +1,213 @@
+//! Area of effect template service for Visual Display System.
+//!
+//! Manages spell templates placed on maps and works out which tokens
+//! they cover.
+
+use crate::connection::DbConnection;
+use crate::domain::measurement::{spell_area, Footprint};
+use crate::error::{DbError, Result};
+use crate::models::campaign::{MapTemplate, NewMapTemplate, Token, UpdateMapTemplate};
+use crate::schema::map_templates;
+use crate::services::{MapService, SpellService, TokenService};
+use diesel::prelude::*;
+
+/// Service for managing map templates
+pub struct MapTemplateService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> MapTemplateService<'a> {
+    /// Create a new map template service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Place a template on a map.
+    ///
+    /// # Arguments
+    /// * `new_template` - The template to create
+    ///
+    /// # Returns
+    /// * `Ok(MapTemplate)` - The created template
+    /// * `Err(DbError::InvalidData)` - If the template has no size
+    pub fn create_template(&mut self, new_template: NewMapTemplate) -> Result<MapTemplate> {
+        if new_template.size_ft <= 0.0 || new_template.width_ft <= 0.0 {
+            return Err(DbError::InvalidData(format!(
+                "Template '{}' must have a positive size",
+                new_template.name
+            )));
+        }
+
+        diesel::insert_into(map_templates::table)
+            .values(&new_template)
+            .returning(MapTemplate::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Place a catalog spell's area of effect on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    /// * `spell_name` - Name of the catalog spell
+    /// * `spell_source` - Source book of the spell (e.g., "PHB")
+    /// * `x` - X coordinate of the point of origin in pixels
+    /// * `y` - Y coordinate of the point of origin in pixels
+    /// * `direction_deg` - Direction for cones, cubes and lines, clockwise from east
+    ///
+    /// # Returns
+    /// * `Ok(MapTemplate)` - The created template
+    /// * `Err(DbError::NotFound)` - If the spell isn't in the catalog
+    /// * `Err(DbError::InvalidData)` - If the spell has no area of effect
+    pub fn create_spell_template(
+        &mut self,
+        map_id: i32,
+        spell_name: &str,
+        spell_source: &str,
+        x: f32,
+        y: f32,
+        direction_deg: f32,
+    ) -> Result<MapTemplate> {
+        let spell = SpellService::get_spell_details(self.conn, spell_name, spell_source)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Spell".to_string(),
+                id: format!("{} ({})", spell_name, spell_source),
+            })?;
+        let area = spell_area(&spell).ok_or_else(|| {
+            DbError::InvalidData(format!("Spell {} has no area of effect", spell.name))
+        })?;
+
+        self.create_template(
+            NewMapTemplate::new(map_id, spell.name.clone(), area.shape, x, y, area.size_ft)
+                .with_direction(direction_deg)
+                .with_width(area.width_ft)
+                .with_spell(spell.name, spell.source),
+        )
+    }
+
+    /// Get a template by ID.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the template
+    ///
+    /// # Returns
+    /// * `Ok(Some(MapTemplate))` - If found
+    /// * `Ok(None)` - If no template exists with that ID
+    pub fn get_template(&mut self, id: i32) -> Result<Option<MapTemplate>> {
+        map_templates::table
+            .find(id)
+            .first(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// Get all templates on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<MapTemplate>)` - Templates in placement order
+    pub fn get_templates_for_map(&mut self, map_id: i32) -> Result<Vec<MapTemplate>> {
+        map_templates::table
+            .filter(map_templates::map_id.eq(map_id))
+            .order(map_templates::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Update a template.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the template
+    /// * `update` - Fields to update
+    ///
+    /// # Returns
+    /// * `Ok(MapTemplate)` - The updated template
+    pub fn update_template(
+        &mut self,
+        id: i32,
+        mut update: UpdateMapTemplate,
+    ) -> Result<MapTemplate> {
+        if update.updated_at.is_none() {
+            update.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+
+        diesel::update(map_templates::table.find(id))
+            .set(&update)
+            .returning(MapTemplate::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Move and turn a template.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the template
+    /// * `x` - New X coordinate of the point of origin in pixels
+    /// * `y` - New Y coordinate of the point of origin in pixels
+    /// * `direction_deg` - New direction, clockwise from east
+    ///
+    /// # Returns
+    /// * `Ok(MapTemplate)` - The updated template
+    pub fn move_template(
+        &mut self,
+        id: i32,
+        x: f32,
+        y: f32,
+        direction_deg: f32,
+    ) -> Result<MapTemplate> {
+        self.update_template(id, UpdateMapTemplate::placement(x, y, direction_deg))
+    }
+
+    /// Find the tokens a template covers.
+    ///
+    /// A token is covered when the template reaches the center of any grid
+    /// square it occupies.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the template
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Token>)` - Covered tokens, sorted by name
+    /// * `Err(DbError::NotFound)` - If the template doesn't exist
+    pub fn get_tokens_in_template(&mut self, id: i32) -> Result<Vec<Token>> {
+        let template = self.get_template(id)?.ok_or_else(|| DbError::NotFound {
+            entity_type: "MapTemplate".to_string(),
+            id: id.to_string(),
+        })?;
+        let grid = MapService::new(self.conn).get_grid_measure(template.map_id)?;
+        let area = template.area();
+
+        Ok(TokenService::new(self.conn)
+            .list_tokens_for_map(template.map_id)?
+            .into_iter()
+            .filter(|token| area.covers(&Footprint::of(token), &grid))
+            .collect())
+    }
+
+    /// Delete a template.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the template
+    ///
+    /// # Returns
+    /// * `Ok(())` - If deletion succeeds
+    pub fn delete_template(&mut self, id: i32) -> Result<()> {
+        diesel::delete(map_templates::table.find(id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// Delete every template on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of templates deleted
+    pub fn delete_all_for_map(&mut self, map_id: i32) -> Result<usize> {
+        diesel::delete(map_templates::table.filter(map_templates::map_id.eq(map_id)))
+            .execute(self.conn)
+            .map_err(Into::into)
+    }
+}
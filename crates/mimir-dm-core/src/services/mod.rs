@@ -10,19 +10,24 @@ pub mod action_service;
 pub mod background_service;
 pub mod campaign_service;
 pub mod campaign_summary_service;
+pub mod catalog_search_service;
 pub mod catalog_trait;
 pub mod character;
 pub mod class_service;
+pub mod combat_service;
 pub mod condition_service;
 pub mod cult_service;
 pub mod deity_service;
 pub mod document_service;
+pub mod encounter_generator_service;
 pub mod feat_service;
 pub mod fog_service;
 pub mod item_service;
 pub mod light_source_service;
 pub mod language_service;
+pub mod lore_index_service;
 pub mod map_service;
+pub mod map_template_service;
 pub mod module_monster_service;
 pub mod module_service;
 pub mod monster_renderer;
@@ -33,13 +38,18 @@ pub mod player_service;
 pub mod psionic_service;
 pub mod race_service;
 pub mod reward_service;
+pub mod session_service;
 pub mod spell_service;
 pub mod table_service;
 pub mod template_service;
 pub mod token_service;
+pub mod tool_journal_service;
 pub mod trap_service;
+pub mod uvtt_import_service;
 pub mod variant_rule_service;
 pub mod vehicle_service;
+pub mod visibility_service;
+pub mod wall_service;
 pub mod reference_service;
 
 // Re-export services
@@ -48,23 +58,30 @@ pub use background_service::BackgroundService;
 pub use campaign_service::CampaignService;
 pub use campaign_summary_service::{
     CampaignSummary, CampaignSummaryService, ModuleSummaryInfo, SessionNoteInfo,
-    SummarySourceMaterial, format_source_for_llm,
+    SessionSummaryInfo, SummarySourceMaterial, format_source_for_llm,
 };
+pub use catalog_search_service::CatalogSearchService;
 pub use catalog_trait::CatalogService;
 pub use character::{
     CharacterProgressionService, CharacterService, CharacterSpellService,
 };
 pub use class_service::ClassService;
+pub use combat_service::CombatService;
 pub use condition_service::ConditionService;
 pub use cult_service::CultService;
 pub use deity_service::DeityService;
 pub use document_service::DocumentService;
+pub use encounter_generator_service::{
+    EncounterGenerationOptions, EncounterGeneratorService, GeneratedEncounter,
+};
 pub use feat_service::FeatService;
 pub use fog_service::FogOfWarService;
 pub use item_service::ItemService;
 pub use light_source_service::LightSourceService;
 pub use language_service::LanguageService;
+pub use lore_index_service::LoreIndexService;
 pub use map_service::MapService;
+pub use map_template_service::MapTemplateService;
 pub use module_monster_service::ModuleMonsterService;
 pub use module_service::ModuleService;
 pub use monster_service::MonsterService;
@@ -74,11 +91,16 @@ pub use player_service::PlayerService;
 pub use psionic_service::PsionicService;
 pub use race_service::RaceService;
 pub use reward_service::RewardService;
+pub use session_service::SessionService;
 pub use spell_service::{SpellService, SpellServiceStateful};
 pub use table_service::TableService;
 pub use template_service::TemplateService;
 pub use token_service::TokenService;
+pub use tool_journal_service::ToolJournalService;
 pub use trap_service::TrapService;
+pub use uvtt_import_service::{UvttImport, UvttImportService};
 pub use variant_rule_service::VariantRuleService;
 pub use vehicle_service::VehicleService;
+pub use visibility_service::VisibilityService;
+pub use wall_service::WallService;
 pub use reference_service::{ReferenceData, ReferenceService};
@@ -6,12 +6,15 @@
 
 use crate::connection::DbConnection;
 use crate::dal::campaign::module_monsters::ModuleMonsterRepository;
-use crate::error::Result;
+use crate::dal::campaign::modules::ModuleRepository;
+use crate::domain::encounter::{EncounterDifficulty, EncounterMonster};
+use crate::error::{DbError, Result};
 use crate::models::campaign::module_monsters::{
-    EncounterGroup, ModuleMonster, ModuleMonsterWithData, NewModuleMonster, UpdateModuleMonster,
+    EncounterGroup, ModuleEncounterDifficulty, ModuleMonster, ModuleMonsterWithData,
+    NewModuleMonster, UpdateModuleMonster,
 };
 use crate::services::monster_renderer::{render_monsters_file, MonsterData};
-use crate::services::MonsterService;
+use crate::services::{CharacterService, MonsterService};
 use std::fs;
 use std::path::PathBuf;
 
@@ -164,6 +167,79 @@ impl<'a> ModuleMonsterService<'a> {
         Ok(result)
     }
 
+    /// Calculate the difficulty of each of a module's encounters.
+    ///
+    /// The party is the campaign's player characters, with each character's
+    /// level summed across their classes. Monster XP comes from the catalog CR.
+    ///
+    /// # Arguments
+    /// * `module_id` - The module whose encounters to rate
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ModuleEncounterDifficulty>)` - One entry per encounter group
+    /// * `Err(DbError::NotFound)` - If the module doesn't exist
+    pub fn get_encounter_difficulties(
+        &mut self,
+        module_id: i32,
+    ) -> Result<Vec<ModuleEncounterDifficulty>> {
+        let party_levels = self.party_levels(module_id)?;
+        let groups = self.get_monsters_grouped_by_encounter(module_id)?;
+
+        Ok(groups
+            .into_iter()
+            .map(|group| {
+                let monsters = group
+                    .monsters
+                    .iter()
+                    .map(|m| {
+                        EncounterMonster::from_monster_json(
+                            m.monster_name.clone(),
+                            m.monster_source.clone(),
+                            m.quantity,
+                            m.monster_data.as_ref(),
+                        )
+                    })
+                    .collect();
+                ModuleEncounterDifficulty {
+                    encounter_tag: group.encounter_tag,
+                    difficulty: EncounterDifficulty::calculate(&party_levels, monsters),
+                }
+            })
+            .collect())
+    }
+
+    /// Calculate the difficulty of a single encounter in a module.
+    ///
+    /// # Arguments
+    /// * `module_id` - The module containing the encounter
+    /// * `encounter_tag` - The encounter tag (`None` for untagged monsters)
+    pub fn get_encounter_difficulty(
+        &mut self,
+        module_id: i32,
+        encounter_tag: Option<&str>,
+    ) -> Result<EncounterDifficulty> {
+        self.get_encounter_difficulties(module_id)?
+            .into_iter()
+            .find(|e| e.encounter_tag.as_deref() == encounter_tag)
+            .map(|e| e.difficulty)
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Encounter".to_string(),
+                id: encounter_tag.unwrap_or("(untagged)").to_string(),
+            })
+    }
+
+    /// Levels of the player characters in the module's campaign.
+    fn party_levels(&mut self, module_id: i32) -> Result<Vec<i32>> {
+        let module = ModuleRepository::new(self.conn)
+            .find_by_id(module_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Module".to_string(),
+                id: module_id.to_string(),
+            })?;
+
+        CharacterService::new(self.conn).get_party_levels(module.campaign_id)
+    }
+
     /// Get distinct encounter tags for a module.
     pub fn get_encounter_tags(&mut self, module_id: i32) -> Result<Vec<Option<String>>> {
         let mut repo = ModuleMonsterRepository::new(self.conn);
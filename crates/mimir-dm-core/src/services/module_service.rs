@@ -496,7 +496,9 @@ impl<'a> ModuleService<'a> {
 
     /// Increment session count for a module.
     ///
-    /// Call after each session to track actual vs expected sessions.
+    /// Call after each session to track actual vs expected sessions. Sessions
+    /// tracked on the session board are counted automatically when marked
+    /// played via `SessionService::transition_session_stage`.
     ///
     /// # Arguments
     /// * `module_id` - Database ID of the module
@@ -37,7 +37,7 @@ impl<'a> MonsterService<'a> {
     /// Results are limited to 1000 to prevent memory issues.
     ///
     /// # Arguments
-    /// * `filters` - Search criteria including name, size, type, CR, HP, etc.
+    /// * `filters` - Search criteria including name, size, type, CR, HP, environment, etc.
     ///
     /// # Returns
     /// * `Ok(Vec<MonsterSummary>)` - List of matching monster summaries
@@ -98,6 +98,22 @@ impl<'a> MonsterService<'a> {
             query = query.filter(hp.le(max_hp_filter));
         }
 
+        // Filter by environment (stored in the monster JSON as an array)
+        if let Some(environment_filters) = &filters.environment {
+            if !environment_filters.is_empty() {
+                let values = environment_filters
+                    .iter()
+                    .map(|e| format!("'{}'", e.to_lowercase().replace('\'', "''")))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                query = query.filter(diesel::dsl::sql::<diesel::sql_types::Bool>(&format!(
+                    "EXISTS (SELECT 1 FROM json_each(full_monster_json, '$.environment') \
+                     WHERE LOWER(json_each.value) IN ({}))",
+                    values
+                )));
+            }
+        }
+
         let monsters = query
             .limit(super::DEFAULT_QUERY_LIMIT)
             .load::<CatalogMonster>(self.conn)?;
@@ -152,6 +168,33 @@ impl<'a> MonsterService<'a> {
         }
     }
 
+    /// Get the catalog ID of a monster by name and source.
+    ///
+    /// Tokens link to catalog monsters by this ID.
+    ///
+    /// # Arguments
+    /// * `monster_name` - Exact monster name
+    /// * `monster_source` - Source book code (e.g., "MM", "VGM")
+    ///
+    /// # Returns
+    /// * `Ok(Some(i32))` - Catalog ID if found
+    /// * `Ok(None)` - If no monster matches name and source
+    pub fn get_monster_id_by_name_and_source(
+        &mut self,
+        monster_name: &str,
+        monster_source: &str,
+    ) -> Result<Option<i32>> {
+        use crate::schema::catalog_monsters::dsl::*;
+
+        catalog_monsters
+            .filter(name.eq(monster_name))
+            .filter(source.eq(monster_source))
+            .select(id)
+            .first::<i32>(self.conn)
+            .optional()
+            .map_err(Into::into)
+    }
+
     /// Get all unique sizes for filtering.
     ///
     /// # Returns
@@ -0,0 +1,417 @@
+//! Session management service.
+//!
+//! Provides business logic for game sessions within modules. Sessions move
+//! through the session board (planned → prepping → ready → played), are
+//! numbered in play order across the campaign, and own their prep outline and
+//! session notes documents.
+
+use crate::connection::DbConnection;
+use crate::dal::campaign::{
+    campaigns::CampaignRepository, documents::DocumentRepository, modules::ModuleRepository,
+    sessions::SessionRepository, template_documents::TemplateRepository,
+};
+use crate::domain::{BoardCompletionStatus, BoardRegistry};
+use crate::error::{DbError, Result};
+use crate::models::campaign::{
+    documents::{Document, NewDocument},
+    modules::Module,
+    sessions::{NewSession, Session, UpdateSession},
+};
+use crate::text::capitalize;
+use chrono::Utc;
+use diesel::Connection;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+/// Service for managing game sessions
+pub struct SessionService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> SessionService<'a> {
+    /// Create a new session service.
+    ///
+    /// # Arguments
+    /// * `conn` - Mutable reference to the database connection
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a new session for a module.
+    ///
+    /// The session starts in the "planned" stage and is given the next session
+    /// number in the campaign, so numbers follow play order across modules.
+    ///
+    /// # Arguments
+    /// * `module_id` - Database ID of the module the session belongs to
+    /// * `scheduled_date` - Optional ISO date (YYYY-MM-DD) the session is scheduled for
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The created session record
+    /// * `Err(DbError::NotFound)` - If the module does not exist
+    pub fn create_session(
+        &mut self,
+        module_id: i32,
+        scheduled_date: Option<String>,
+    ) -> Result<Session> {
+        let module = self.find_module(module_id)?;
+
+        let mut repo = SessionRepository::new(self.conn);
+        let session_number = repo.get_next_session_number(module.campaign_id)?;
+
+        repo.create(NewSession {
+            campaign_id: module.campaign_id,
+            module_id: Some(module_id),
+            session_number,
+            status: "planned".to_string(),
+            scheduled_date,
+        })
+    }
+
+    /// Get a session by ID.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the session
+    ///
+    /// # Returns
+    /// * `Ok(Some(Session))` - If found
+    /// * `Ok(None)` - If no session exists with that ID
+    pub fn get_session(&mut self, id: i32) -> Result<Option<Session>> {
+        SessionRepository::new(self.conn).find_by_id(id)
+    }
+
+    /// List all sessions for a campaign.
+    ///
+    /// # Arguments
+    /// * `campaign_id` - Database ID of the campaign
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Session>)` - All sessions in the campaign, ordered by session_number
+    pub fn list_campaign_sessions(&mut self, campaign_id: i32) -> Result<Vec<Session>> {
+        SessionRepository::new(self.conn).list_by_campaign(campaign_id)
+    }
+
+    /// List all sessions for a module.
+    ///
+    /// # Arguments
+    /// * `module_id` - Database ID of the module
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Session>)` - The module's sessions, ordered by session_number
+    pub fn list_module_sessions(&mut self, module_id: i32) -> Result<Vec<Session>> {
+        SessionRepository::new(self.conn).list_by_module(module_id)
+    }
+
+    /// Set or clear the scheduled date of a session.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the session
+    /// * `scheduled_date` - ISO date (YYYY-MM-DD), or None to unschedule
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The updated session record
+    /// * `Err(DbError::NotFound)` - If the session does not exist
+    pub fn schedule_session(&mut self, id: i32, scheduled_date: Option<String>) -> Result<Session> {
+        self.find_session(id)?;
+
+        SessionRepository::new(self.conn).update(
+            id,
+            UpdateSession {
+                scheduled_date: Some(scheduled_date),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Transition a session to a new stage.
+    ///
+    /// Validates the transition against the session board, records prep and
+    /// play timestamps, and creates the documents required by the new stage.
+    /// Marking a session played also counts it towards its module's
+    /// actual sessions. The database changes commit together or not at all.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the session
+    /// * `new_stage` - Target stage ("planned", "prepping", "ready" or "played")
+    ///
+    /// # Returns
+    /// * `Ok(Session)` - The updated session with new status
+    /// * `Err(DbError::NotFound)` - If the session does not exist
+    /// * `Err(DbError::InvalidData)` - If the transition is not allowed
+    pub fn transition_session_stage(&mut self, id: i32, new_stage: &str) -> Result<Session> {
+        let session = self.find_session(id)?;
+
+        let board_registry = BoardRegistry::new();
+        let board = board_registry
+            .get("session")
+            .ok_or_else(|| DbError::InvalidData("Session board not registered".to_string()))?;
+
+        if !board.can_transition(&session.status, new_stage) {
+            return Err(DbError::InvalidData(format!(
+                "Cannot transition session from {} to {}",
+                session.status, new_stage
+            )));
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut update = UpdateSession {
+            status: Some(new_stage.to_string()),
+            ..Default::default()
+        };
+
+        match new_stage {
+            "prepping" if session.prep_started_at.is_none() => {
+                update.prep_started_at = Some(Some(now));
+            }
+            "ready" => {
+                update.prep_completed_at = Some(Some(now));
+            }
+            "played" => {
+                update.completed_at = Some(Some(now));
+            }
+            _ => {}
+        }
+
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            let updated = SessionRepository::new(conn).update(id, update)?;
+
+            if new_stage == "played" {
+                if let Some(module_id) = updated.module_id {
+                    ModuleRepository::new(conn).increment_sessions(module_id)?;
+                }
+            }
+
+            SessionService::new(conn).initialize_session_documents(id)?;
+
+            Ok(updated)
+        })
+    }
+
+    /// Initialize documents for a session stage.
+    ///
+    /// Creates the required documents for the session's current stage from
+    /// templates in the module's `sessions/session_NN` directory. Skips
+    /// documents that already exist.
+    ///
+    /// # Arguments
+    /// * `session_id` - Database ID of the session
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - List of created document file names
+    /// * `Err(DbError::NotFound)` - If the session, its module or campaign is missing
+    pub fn initialize_session_documents(&mut self, session_id: i32) -> Result<Vec<String>> {
+        let session = self.find_session(session_id)?;
+
+        let board_registry = BoardRegistry::new();
+        let board = board_registry
+            .get("session")
+            .ok_or_else(|| DbError::InvalidData("Session board not registered".to_string()))?;
+
+        let required_docs = board.required_documents(&session.status);
+        if required_docs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let module = match session.module_id {
+            Some(module_id) => Some(self.find_module(module_id)?),
+            None => None,
+        };
+        let session_dir = self.session_directory(&session, module.as_ref())?;
+
+        if !session_dir.exists() {
+            fs::create_dir_all(&session_dir)?;
+        }
+
+        let existing = DocumentRepository::find_by_session(self.conn, session_id)?;
+        let mut created_files = Vec::new();
+
+        for doc_template_id in required_docs {
+            let file_name = format!("{}.md", doc_template_id.replace('_', "-"));
+            let file_path = session_dir.join(&file_name);
+
+            if existing.iter().any(|d| d.template_id == doc_template_id) || file_path.exists() {
+                continue;
+            }
+
+            let template = TemplateRepository::get_latest(self.conn, doc_template_id)?;
+
+            // Create context with template defaults, then add session-specific variables
+            let mut context = template.create_context();
+            context.insert("session_number", &json!(session.session_number));
+            if let Some(ref module) = module {
+                context.insert("module_name", &json!(module.name));
+                context.insert("module_number", &json!(module.module_number));
+            }
+            if let Some(ref date) = session.scheduled_date {
+                context.insert("session_date", &json!(date));
+            }
+
+            let mut tera = tera::Tera::default();
+            tera.add_raw_template(&template.document_id, &template.document_content)
+                .map_err(|e| DbError::InvalidData(format!("Failed to add template: {}", e)))?;
+
+            let content = tera
+                .render(&template.document_id, &context)
+                .map_err(|e| DbError::InvalidData(format!("Failed to render template: {}", e)))?;
+
+            let title = format!(
+                "Session {} - {}",
+                session.session_number,
+                template
+                    .document_id
+                    .trim_start_matches("session_")
+                    .split('_')
+                    .map(capitalize)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+
+            let content_with_frontmatter = format!(
+                "---\ntitle: \"{}\"\ntype: {}\n---\n\n{}",
+                title, doc_template_id, content
+            );
+            fs::write(&file_path, content_with_frontmatter)?;
+
+            DocumentRepository::create(
+                self.conn,
+                NewDocument {
+                    campaign_id: session.campaign_id,
+                    module_id: session.module_id,
+                    session_id: Some(session_id),
+                    template_id: doc_template_id.to_string(),
+                    document_type: doc_template_id.to_string(),
+                    title,
+                    file_path: file_path.to_string_lossy().to_string(),
+                },
+            )?;
+            created_files.push(file_name);
+        }
+
+        Ok(created_files)
+    }
+
+    /// Get session documents.
+    ///
+    /// # Arguments
+    /// * `session_id` - Database ID of the session
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Document>)` - All documents tied to the session
+    pub fn get_session_documents(&mut self, session_id: i32) -> Result<Vec<Document>> {
+        DocumentRepository::find_by_session(self.conn, session_id)
+    }
+
+    /// Check session completion status.
+    ///
+    /// Evaluates the current stage's required documents to determine if the
+    /// stage is complete and the session can progress.
+    ///
+    /// # Arguments
+    /// * `session_id` - Database ID of the session
+    ///
+    /// # Returns
+    /// * `Ok(BoardCompletionStatus)` - Completion metrics
+    /// * `Err(DbError::NotFound)` - If the session does not exist
+    pub fn check_session_completion(&mut self, session_id: i32) -> Result<BoardCompletionStatus> {
+        let session = self.find_session(session_id)?;
+        let documents = DocumentRepository::find_by_session(self.conn, session_id)?;
+
+        let board_registry = BoardRegistry::new();
+        let board = board_registry
+            .get("session")
+            .ok_or_else(|| DbError::InvalidData("Session board not registered".to_string()))?;
+
+        let required_docs = board.required_documents(&session.status);
+        let optional_docs = board.optional_documents(&session.status);
+
+        let is_completed = |template_id: &str| {
+            documents
+                .iter()
+                .any(|d| d.template_id == template_id && d.completed_at.is_some())
+        };
+
+        let missing_required: Vec<String> = required_docs
+            .iter()
+            .filter(|doc| !is_completed(doc))
+            .map(|doc| doc.to_string())
+            .collect();
+        let completed_optional = optional_docs.iter().filter(|doc| is_completed(doc)).count();
+
+        let is_stage_complete = missing_required.is_empty();
+        let next_stage = board.next_stage(&session.status).map(|s| s.to_string());
+        let can_progress = is_stage_complete && next_stage.is_some();
+
+        Ok(BoardCompletionStatus {
+            board_type: "session".to_string(),
+            current_stage: session.status.clone(),
+            total_required_documents: required_docs.len(),
+            completed_required_documents: required_docs.len() - missing_required.len(),
+            total_optional_documents: optional_docs.len(),
+            completed_optional_documents: completed_optional,
+            missing_required_documents: missing_required,
+            is_stage_complete,
+            can_progress,
+            next_stage,
+            stage_metadata: board.stage_metadata(&session.status),
+        })
+    }
+
+    /// Delete a session.
+    ///
+    /// Removes the session record; its documents are removed by cascade.
+    /// Files on disk are left in place.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the session
+    ///
+    /// # Returns
+    /// * `Ok(())` - If deletion succeeds
+    pub fn delete_session(&mut self, id: i32) -> Result<()> {
+        SessionRepository::new(self.conn).delete(id)
+    }
+
+    /// Load a session, mapping a missing row to `DbError::NotFound`
+    fn find_session(&mut self, id: i32) -> Result<Session> {
+        SessionRepository::new(self.conn)
+            .find_by_id(id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Session".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    /// Load a module, mapping a missing row to `DbError::NotFound`
+    fn find_module(&mut self, id: i32) -> Result<Module> {
+        ModuleRepository::new(self.conn)
+            .find_by_id(id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Module".to_string(),
+                id: id.to_string(),
+            })
+    }
+
+    /// Directory holding a session's documents.
+    ///
+    /// Module sessions live under `modules/module_NN/sessions/session_NN`;
+    /// sessions without a module fall back to the campaign's `sessions` folder.
+    fn session_directory(&mut self, session: &Session, module: Option<&Module>) -> Result<PathBuf> {
+        let campaign = CampaignRepository::new(self.conn)
+            .find_by_id(session.campaign_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Campaign".to_string(),
+                id: session.campaign_id.to_string(),
+            })?;
+
+        let mut dir = PathBuf::from(&campaign.directory_path);
+        if let Some(module) = module {
+            dir = dir
+                .join("modules")
+                .join(format!("module_{:02}", module.module_number));
+        }
+
+        Ok(dir
+            .join("sessions")
+            .join(format!("session_{:02}", session.session_number)))
+    }
+}
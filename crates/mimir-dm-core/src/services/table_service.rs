@@ -1,18 +1,31 @@
 //! Table catalog service.
 //!
 //! Provides database-backed table search, retrieval, and import functionality.
-//! Supports filtering by name, category, and source.
+//! Supports filtering by name, category, and source, and rolling on tables
+//! with nested `{@table}` references resolved recursively.
 
-use crate::error::Result;
+use crate::domain::dice::DiceRoller;
+use crate::error::{DbError, Result};
 use crate::models::catalog::table::{
-    CatalogTable, NewCatalogTable, Table, TableData, TableFilters, TableSummary,
+    CatalogTable, NewCatalogTable, Table, TableData, TableFilters, TableRollResult, TableSummary,
 };
 use crate::services::CatalogService;
 use diesel::prelude::*;
+use regex::Regex;
 use std::fs;
 use std::path::Path;
+use std::sync::LazyLock;
 use tracing::{debug, info};
 
+/// Maximum depth of nested `{@table}` references followed when rolling
+const MAX_TABLE_DEPTH: usize = 5;
+
+/// Default source for `{@table}` tags that don't name one (5etools convention)
+const DEFAULT_TABLE_SOURCE: &str = "DMG";
+
+static TABLE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{@table ([^|}]+)(?:\|([^|}]*))?[^}]*\}").unwrap());
+
 /// Service for searching and managing tables in the catalog.
 pub struct TableService<'a> {
     /// Database connection reference.
@@ -98,6 +111,149 @@ impl<'a> TableService<'a> {
         }
     }
 
+    /// Rolls on a table and resolves nested table references.
+    ///
+    /// Names match case-insensitively; if the source doesn't have the table,
+    /// any source that does is used.
+    ///
+    /// # Arguments
+    /// * `table_name` - Name of the table
+    /// * `table_source` - Source book of the table
+    ///
+    /// # Returns
+    /// * `Ok(TableRollResult)` - The selected row and any nested rolls
+    /// * `Err(DbError::NotFound)` - If the table doesn't exist
+    pub fn roll_table(&mut self, table_name: &str, table_source: &str) -> Result<TableRollResult> {
+        self.roll_table_with(table_name, table_source, &mut DiceRoller::new())
+    }
+
+    /// Rolls on a table using the given dice roller (seed it for repeatable results).
+    pub fn roll_table_with(
+        &mut self,
+        table_name: &str,
+        table_source: &str,
+        roller: &mut DiceRoller,
+    ) -> Result<TableRollResult> {
+        let table =
+            self.find_table(table_name, table_source)?
+                .ok_or_else(|| DbError::NotFound {
+                    entity_type: "Table".to_string(),
+                    id: format!("{} ({})", table_name, table_source),
+                })?;
+        self.roll_on(&table, roller, 0)
+    }
+
+    fn roll_on(
+        &mut self,
+        table: &Table,
+        roller: &mut DiceRoller,
+        depth: usize,
+    ) -> Result<TableRollResult> {
+        if table.rows.is_empty() {
+            return Err(DbError::InvalidData(format!(
+                "Table '{}' has no rows to roll on",
+                table.name
+            )));
+        }
+
+        // Only tables with a dice roll column select rows by range
+        let dice = table.roll_column_dice();
+        let ranges = dice.as_ref().and_then(|_| table.row_ranges());
+
+        let (roll, row_index) = match (&dice, &ranges) {
+            (Some(dice), Some(ranges)) => {
+                let roll = roller.roll(dice)?.total;
+                let index = ranges
+                    .iter()
+                    .position(|r| r.contains(roll))
+                    .ok_or_else(|| {
+                        DbError::InvalidData(format!(
+                            "Roll of {} on {} matches no row of table '{}'",
+                            roll, dice, table.name
+                        ))
+                    })?;
+                (roll, index)
+            }
+            _ => {
+                let index = roller.roll_die(table.rows.len() as u32) as usize - 1;
+                (index as i32 + 1, index)
+            }
+        };
+
+        // The first column holds the roll ranges when the table is rollable by range
+        let skip = if ranges.is_some() { 1 } else { 0 };
+        let columns = table
+            .col_labels
+            .as_ref()
+            .map(|labels| labels.iter().skip(skip).cloned().collect())
+            .unwrap_or_default();
+        let result: Vec<String> = table.rows[row_index]
+            .iter()
+            .skip(skip)
+            .map(cell_text)
+            .collect();
+
+        let mut nested = Vec::new();
+        let mut unresolved = Vec::new();
+        if depth < MAX_TABLE_DEPTH {
+            for text in &result {
+                for captures in TABLE_TAG.captures_iter(text) {
+                    let name = captures[1].trim();
+                    let source = captures
+                        .get(2)
+                        .map(|s| s.as_str().trim())
+                        .filter(|s| !s.is_empty())
+                        .unwrap_or(DEFAULT_TABLE_SOURCE);
+                    match self.find_table(name, source)? {
+                        Some(nested_table) => {
+                            nested.push(self.roll_on(&nested_table, roller, depth + 1)?)
+                        }
+                        None => unresolved.push(format!("{}|{}", name, source)),
+                    }
+                }
+            }
+        }
+
+        Ok(TableRollResult {
+            table_name: table.name.clone(),
+            source: table.source.clone(),
+            dice: ranges.as_ref().and(dice),
+            roll,
+            row_index,
+            columns,
+            result,
+            nested,
+            unresolved,
+        })
+    }
+
+    /// Find a table referenced by a `{@table}` tag.
+    ///
+    /// Tag names are often lowercase, so matching is case-insensitive. Falls
+    /// back to any source if the named source doesn't have the table.
+    /// `%` and `_` in the names match literally.
+    fn find_table(&mut self, table_name: &str, table_source: &str) -> Result<Option<Table>> {
+        use crate::schema::catalog_tables::dsl::*;
+
+        let table_name = escape_like(table_name);
+        let mut catalog_table = catalog_tables
+            .filter(name.like(&table_name).escape('\\'))
+            .filter(source.like(escape_like(table_source)).escape('\\'))
+            .first::<CatalogTable>(self.conn)
+            .optional()?;
+        if catalog_table.is_none() {
+            catalog_table = catalog_tables
+                .filter(name.like(&table_name).escape('\\'))
+                .first::<CatalogTable>(self.conn)
+                .optional()?;
+        }
+
+        match catalog_table {
+            Some(t) => Ok(Some(serde_json::from_str(&t.full_table_json)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Gets all unique table categories.
     pub fn get_table_categories(&mut self) -> Result<Vec<String>> {
         use crate::schema::catalog_tables::dsl::*;
@@ -240,6 +396,36 @@ impl<'a> TableService<'a> {
     }
 }
 
+/// Escape `LIKE` wildcards so a name only matches itself (used with `ESCAPE '\'`).
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Flatten a table cell (string, number or 5etools entry object) to text.
+fn cell_text(cell: &serde_json::Value) -> String {
+    match cell {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::Array(items) => {
+            items.iter().map(cell_text).collect::<Vec<_>>().join(" ")
+        }
+        serde_json::Value::Object(obj) => {
+            if let Some(entry) = obj.get("entry") {
+                cell_text(entry)
+            } else if let Some(entries) = obj.get("entries").or(obj.get("items")) {
+                cell_text(entries)
+            } else if let Some(name) = obj.get("name") {
+                cell_text(name)
+            } else {
+                String::new()
+            }
+        }
+        _ => String::new(),
+    }
+}
+
 impl<'a> CatalogService for TableService<'a> {
     type Filters = TableFilters;
     type Summary = TableSummary;
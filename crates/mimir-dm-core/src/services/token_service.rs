@@ -4,10 +4,18 @@
 //! traps, and markers placed on battle maps.
 
 use crate::connection::DbConnection;
-use crate::error::Result;
-use crate::models::campaign::{NewToken, Token, TokenSummary, UpdateToken};
+use crate::domain::formation::{
+    layout_formation, token_size_from_monster_json, vision_from_monster_json, Formation,
+    PlacementGrid,
+};
+use crate::domain::measurement::Footprint;
+use crate::domain::visibility::Point;
+use crate::error::{DbError, Result};
+use crate::models::campaign::{GridType, NewToken, Token, TokenSummary, UpdateToken};
 use crate::schema::tokens;
+use crate::services::{MapService, ModuleMonsterService, MonsterService};
 use diesel::prelude::*;
+use std::collections::HashMap;
 
 /// Service for managing map tokens
 pub struct TokenService<'a> {
@@ -35,6 +43,109 @@ impl<'a> TokenService<'a> {
             .map_err(Into::into)
     }
 
+    /// Place tokens for every monster in one of a module's encounters.
+    ///
+    /// The encounter comes from the module the map belongs to. Each monster
+    /// instance gets its own token, linked to the catalog monster with its
+    /// catalog size and the vision of its best special sense. Tokens start
+    /// hidden from players and are laid out in `formation` around `center`,
+    /// lined up with the grid on square-grid maps.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    /// * `encounter_tag` - The encounter tag (`None` for untagged monsters)
+    /// * `center` - Where to place the group (pixels)
+    /// * `formation` - How to arrange the tokens
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Token>)` - The created tokens
+    /// * `Err(DbError::NotFound)` - If the map or encounter doesn't exist
+    /// * `Err(DbError::InvalidData)` - If the map doesn't belong to a module
+    pub fn place_encounter(
+        &mut self,
+        map_id: i32,
+        encounter_tag: Option<&str>,
+        center: Point,
+        formation: Formation,
+    ) -> Result<Vec<Token>> {
+        let map = MapService::new(self.conn)
+            .get_map(map_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Map".to_string(),
+                id: map_id.to_string(),
+            })?;
+        let module_id = map.module_id.ok_or_else(|| {
+            DbError::InvalidData(format!("Map {} does not belong to a module", map_id))
+        })?;
+
+        let group = ModuleMonsterService::new(self.conn)
+            .get_monsters_grouped_by_encounter(module_id)?
+            .into_iter()
+            .find(|g| g.encounter_tag.as_deref() == encounter_tag)
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Encounter".to_string(),
+                id: encounter_tag.unwrap_or("(untagged)").to_string(),
+            })?;
+
+        // Repeated monsters are numbered so they can be told apart
+        let mut totals: HashMap<&str, i32> = HashMap::new();
+        for monster in &group.monsters {
+            *totals.entry(monster.monster_name.as_str()).or_default() += monster.quantity;
+        }
+        let mut numbers: HashMap<&str, i32> = HashMap::new();
+
+        let mut new_tokens = Vec::new();
+        let mut sizes = Vec::new();
+        for monster in &group.monsters {
+            let name = monster.monster_name.as_str();
+            let monster_id = MonsterService::new(self.conn)
+                .get_monster_id_by_name_and_source(name, &monster.monster_source)?;
+            let data = monster.monster_data.as_ref();
+            let size = token_size_from_monster_json(data);
+            let (vision_type, vision_range_ft) = vision_from_monster_json(data);
+
+            for _ in 0..monster.quantity {
+                let token_name = if totals[name] > 1 {
+                    let number = numbers.entry(name).or_default();
+                    *number += 1;
+                    format!("{} {}", name, number)
+                } else {
+                    name.to_string()
+                };
+                let mut token = NewToken::new(map_id, token_name, center.x, center.y)
+                    .with_size(size)
+                    .with_visibility(false)
+                    .with_vision(vision_type, vision_range_ft);
+                token.monster_id = monster_id;
+                new_tokens.push(token);
+                sizes.push(size);
+            }
+        }
+
+        let grid = PlacementGrid {
+            size_px: map.grid_size_px_or_default(),
+            offset: Point::new(map.grid_offset_x as f32, map.grid_offset_y as f32),
+            snap: map.grid_type_enum() == GridType::Square,
+        };
+        let positions = layout_formation(formation, &sizes, center, &grid);
+
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            new_tokens
+                .into_iter()
+                .zip(positions)
+                .map(|(mut token, position)| {
+                    token.x = position.x;
+                    token.y = position.y;
+                    diesel::insert_into(tokens::table)
+                        .values(&token)
+                        .returning(Token::as_returning())
+                        .get_result(conn)
+                        .map_err(Into::into)
+                })
+                .collect()
+        })
+    }
+
     /// Get a token by ID.
     ///
     /// # Arguments
@@ -51,6 +162,39 @@ impl<'a> TokenService<'a> {
             .map_err(Into::into)
     }
 
+    /// Measure the distance between two tokens on the same map.
+    ///
+    /// Counts from the nearest squares each token occupies, using the map's
+    /// grid and its campaign's diagonal rule.
+    ///
+    /// # Arguments
+    /// * `from_id` - Database ID of the first token
+    /// * `to_id` - Database ID of the second token
+    ///
+    /// # Returns
+    /// * `Ok(f32)` - Distance in feet
+    /// * `Err(DbError::NotFound)` - If either token doesn't exist
+    /// * `Err(DbError::InvalidData)` - If the tokens are on different maps
+    pub fn distance_between(&mut self, from_id: i32, to_id: i32) -> Result<f32> {
+        let mut find = |id: i32| {
+            self.get_token(id)?.ok_or_else(|| DbError::NotFound {
+                entity_type: "Token".to_string(),
+                id: id.to_string(),
+            })
+        };
+        let from = find(from_id)?;
+        let to = find(to_id)?;
+        if from.map_id != to.map_id {
+            return Err(DbError::InvalidData(format!(
+                "Tokens {} and {} are on different maps",
+                from_id, to_id
+            )));
+        }
+
+        let grid = MapService::new(self.conn).get_grid_measure(from.map_id)?;
+        Ok(grid.token_distance_ft(&Footprint::of(&from), &Footprint::of(&to)))
+    }
+
     /// List all tokens for a map.
     ///
     /// # Arguments
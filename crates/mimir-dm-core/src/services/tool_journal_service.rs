@@ -0,0 +1,171 @@
+//! Tool action journal service.
+//!
+//! Records mutating tool calls made by the assistant together with the state
+//! needed to revert them, and undoes the most recent actions of a chat
+//! session on request.
+
+use crate::connection::DbConnection;
+use crate::error::{DbError, Result};
+use crate::models::campaign::modules::UpdateModule;
+use crate::models::chat::{NewToolAction, RevertAction, ToolAction};
+use crate::schema::tool_action_journal;
+use crate::services::{CharacterService, ModuleService};
+use diesel::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Service for journaling and undoing assistant tool actions
+pub struct ToolJournalService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> ToolJournalService<'a> {
+    /// Create a new tool journal service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Record a tool call that can later be undone.
+    ///
+    /// # Arguments
+    /// * `session_id` - Chat session the tool was called from
+    /// * `tool_name` - Name of the tool that ran
+    /// * `arguments` - Arguments the tool was called with
+    /// * `summary` - Human-readable description of the action
+    /// * `revert` - How to put things back
+    ///
+    /// # Returns
+    /// * `Ok(ToolAction)` - The journal entry
+    pub fn record(
+        &mut self,
+        session_id: &str,
+        tool_name: &str,
+        arguments: &serde_json::Value,
+        summary: &str,
+        revert: &RevertAction,
+    ) -> Result<ToolAction> {
+        let new_action = NewToolAction::new(session_id, tool_name, arguments, summary, revert)?;
+
+        diesel::insert_into(tool_action_journal::table)
+            .values(&new_action)
+            .returning(ToolAction::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// List journaled actions for a chat session, most recent first.
+    ///
+    /// # Arguments
+    /// * `session_id` - Chat session ID
+    /// * `include_undone` - Whether to include actions that were already reverted
+    pub fn list_for_session(
+        &mut self,
+        session_id: &str,
+        include_undone: bool,
+    ) -> Result<Vec<ToolAction>> {
+        let mut query = tool_action_journal::table
+            .filter(tool_action_journal::session_id.eq(session_id))
+            .into_boxed();
+
+        if !include_undone {
+            query = query.filter(tool_action_journal::undone_at.is_null());
+        }
+
+        query
+            .order_by(tool_action_journal::id.desc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Undo the most recent actions of a chat session.
+    ///
+    /// Actions are reverted newest first. Each action is marked as undone as
+    /// soon as its revert succeeds, so if a revert fails the earlier reverts
+    /// stay in effect and the failing action can be retried.
+    ///
+    /// # Arguments
+    /// * `session_id` - Chat session ID
+    /// * `count` - Number of actions to undo
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ToolAction>)` - The actions that were undone, newest first
+    pub fn undo_last(&mut self, session_id: &str, count: usize) -> Result<Vec<ToolAction>> {
+        let pending: Vec<ToolAction> = tool_action_journal::table
+            .filter(tool_action_journal::session_id.eq(session_id))
+            .filter(tool_action_journal::undone_at.is_null())
+            .order_by(tool_action_journal::id.desc())
+            .limit(count as i64)
+            .load(self.conn)?;
+
+        let mut undone = Vec::with_capacity(pending.len());
+        for action in pending {
+            let revert = action.revert()?;
+            self.apply_revert(&revert)?;
+
+            let undone_action = diesel::update(tool_action_journal::table.find(action.id))
+                .set(tool_action_journal::undone_at.eq(Some(chrono::Utc::now().to_rfc3339())))
+                .returning(ToolAction::as_returning())
+                .get_result(self.conn)?;
+            undone.push(undone_action);
+        }
+
+        Ok(undone)
+    }
+
+    /// Delete the journal of a chat session.
+    pub fn clear_session(&mut self, session_id: &str) -> Result<usize> {
+        diesel::delete(
+            tool_action_journal::table.filter(tool_action_journal::session_id.eq(session_id)),
+        )
+        .execute(self.conn)
+        .map_err(Into::into)
+    }
+
+    fn apply_revert(&mut self, revert: &RevertAction) -> Result<()> {
+        match revert {
+            RevertAction::RestoreCharacterVersion {
+                character_id,
+                version_number,
+            } => {
+                CharacterService::new(self.conn)
+                    .restore_character_version(*character_id, *version_number)?;
+            }
+            RevertAction::DeleteCharacter { character_id } => {
+                // Nothing to revert if the character is already gone
+                match CharacterService::new(self.conn).delete_character(*character_id) {
+                    Ok(()) | Err(DbError::NotFound { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            RevertAction::DeleteModule { module_id } => {
+                ModuleService::new(self.conn).delete_module(*module_id)?;
+            }
+            RevertAction::RestoreModuleStatus {
+                module_id,
+                status,
+                started_at,
+                completed_at,
+            } => {
+                let update = UpdateModule {
+                    status: Some(status.clone()),
+                    started_at: Some(started_at.clone()),
+                    completed_at: Some(completed_at.clone()),
+                    ..Default::default()
+                };
+                ModuleService::new(self.conn).update_module(*module_id, update)?;
+            }
+            RevertAction::RestoreFile {
+                path,
+                previous_contents,
+            } => match previous_contents {
+                Some(contents) => fs::write(path, contents)?,
+                None => {
+                    if Path::new(path).exists() {
+                        fs::remove_file(path)?;
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! Universal VTT import service.
+//!
+//! Creates a map from a Universal VTT file (`.dd2vtt`, `.uvtt`) with its grid
+//! aligned, and stores the file's walls, doors and lights alongside it.
+
+use crate::connection::DbConnection;
+use crate::domain::uvtt::UvttMap;
+use crate::error::{DbError, Result};
+use crate::models::campaign::{
+    DoorState, GridType, LightSource, LightType, Map, NewLightSource, NewMap, NewMapDoor,
+    NewMapWall,
+};
+use crate::services::{LightSourceService, MapService, WallService};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Result of importing a Universal VTT map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UvttImport {
+    /// The created map
+    pub map: Map,
+    /// Number of wall segments created
+    pub wall_count: usize,
+    /// Number of doors created
+    pub door_count: usize,
+    /// Light sources created from the file's lights
+    pub light_sources: Vec<LightSource>,
+}
+
+/// Service for importing Universal VTT maps
+pub struct UvttImportService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> UvttImportService<'a> {
+    /// Create a new Universal VTT import service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a map with the walls, doors and lights of a Universal VTT file.
+    ///
+    /// `new_map` describes the stored map image. If the image was resized
+    /// when it was stored, its width determines how the file's coordinates
+    /// are scaled. The grid and ambient light are taken from the file.
+    /// Lights are dim out to their full range and bright for half of it.
+    ///
+    /// # Arguments
+    /// * `uvtt` - The parsed Universal VTT file
+    /// * `new_map` - The map record for the stored image
+    ///
+    /// # Returns
+    /// * `Ok(UvttImport)` - The created map and what was imported with it
+    pub fn import(&mut self, uvtt: &UvttMap, new_map: NewMap) -> Result<UvttImport> {
+        let (file_width, _) = uvtt.image_size_px();
+        if uvtt.resolution.pixels_per_grid <= 0.0 || file_width <= 0.0 {
+            return Err(DbError::InvalidData(
+                "Universal VTT file has no grid resolution".to_string(),
+            ));
+        }
+
+        let scale = new_map.width_px as f64 / file_width;
+        let grid_size_px = (uvtt.resolution.pixels_per_grid * scale).round() as i32;
+        let new_map = new_map
+            .with_grid(GridType::Square, grid_size_px, 0, 0)
+            .with_ambient_light(uvtt.ambient_light());
+
+        self.conn.transaction::<_, DbError, _>(|conn| {
+            let map = MapService::new(conn).create_map(new_map)?;
+
+            let walls: Vec<NewMapWall> = uvtt
+                .wall_segments(scale)
+                .into_iter()
+                .map(|s| NewMapWall::new(map.id, s.x1, s.y1, s.x2, s.y2))
+                .collect();
+            let wall_count = WallService::new(conn).create_walls(&walls)?;
+
+            let doors = uvtt.doors(scale);
+            for door in &doors {
+                let s = door.segment;
+                let state = if door.closed {
+                    DoorState::Closed
+                } else {
+                    DoorState::Open
+                };
+                WallService::new(conn).create_door(
+                    NewMapDoor::new(map.id, s.x1, s.y1, s.x2, s.y2).with_state(state),
+                )?;
+            }
+
+            let mut light_sources = Vec::new();
+            for (index, light) in uvtt.light_placements(scale).into_iter().enumerate() {
+                let name = format!("Light {}", index + 1);
+                let mut new_light =
+                    NewLightSource::new(map.id, name, LightType::Custom, light.x, light.y)
+                        .with_radii(light.radius_ft / 2.0, light.radius_ft);
+                if let Some(color) = light.color {
+                    new_light = new_light.with_color(color);
+                }
+                light_sources.push(LightSourceService::new(conn).create_light_source(new_light)?);
+            }
+
+            Ok(UvttImport {
+                map,
+                wall_count,
+                door_count: doors.len(),
+                light_sources,
+            })
+        })
+    }
+}
@@ -0,0 +1,142 @@
+//! Visibility service for Vision and Lighting System.
+//!
+//! Loads a map's walls, doors, light sources and tokens and computes what
+//! tokens can see, for dynamic vision on the player display.
+
+use crate::connection::DbConnection;
+use crate::domain::visibility::{
+    feet_to_pixels, lit_area, token_visibility, Light, MapVisibility, Point, SightBlockers, Viewer,
+};
+use crate::error::{DbError, Result};
+use crate::models::campaign::{Map, Token, TokenType};
+use crate::services::{LightSourceService, MapService, TokenService, WallService};
+
+/// Service for computing token visibility
+pub struct VisibilityService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> VisibilityService<'a> {
+    /// Create a new visibility service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Compute what the party can see on a map.
+    ///
+    /// The party is every PC and NPC token that is visible to players.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(MapVisibility)` - Lit areas and each party token's sight
+    pub fn get_party_visibility(&mut self, map_id: i32) -> Result<MapVisibility> {
+        let map = self.find_map(map_id)?;
+        let viewers: Vec<Token> = TokenService::new(self.conn)
+            .list_visible_tokens_for_map(map_id)?
+            .into_iter()
+            .filter(|t| matches!(t.token_type_enum(), TokenType::PC | TokenType::NPC))
+            .collect();
+        self.compute(&map, &viewers)
+    }
+
+    /// Compute what the player characters can see on a map.
+    ///
+    /// Only PC tokens that are visible to players are included.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(MapVisibility)` - Lit areas and each PC token's sight
+    pub fn get_pc_visibility(&mut self, map_id: i32) -> Result<MapVisibility> {
+        let map = self.find_map(map_id)?;
+        let viewers: Vec<Token> = TokenService::new(self.conn)
+            .list_visible_tokens_for_map(map_id)?
+            .into_iter()
+            .filter(|t| t.token_type_enum() == TokenType::PC)
+            .collect();
+        self.compute(&map, &viewers)
+    }
+
+    /// Compute what a single token can see.
+    ///
+    /// # Arguments
+    /// * `token_id` - Database ID of the token
+    ///
+    /// # Returns
+    /// * `Ok(MapVisibility)` - Lit areas and the token's sight
+    pub fn get_token_visibility(&mut self, token_id: i32) -> Result<MapVisibility> {
+        let token = TokenService::new(self.conn)
+            .get_token(token_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Token".to_string(),
+                id: token_id.to_string(),
+            })?;
+        let map = self.find_map(token.map_id)?;
+        self.compute(&map, &[token])
+    }
+
+    fn find_map(&mut self, map_id: i32) -> Result<Map> {
+        MapService::new(self.conn)
+            .get_map(map_id)?
+            .ok_or_else(|| DbError::NotFound {
+                entity_type: "Map".to_string(),
+                id: map_id.to_string(),
+            })
+    }
+
+    fn compute(&mut self, map: &Map, viewers: &[Token]) -> Result<MapVisibility> {
+        let grid_size_px = map.grid_size_px_or_default();
+
+        let segments = WallService::new(self.conn).get_sight_blocking_segments(map.id)?;
+        let blockers =
+            SightBlockers::new(map.width_px as f32, map.height_px as f32).with_segments(segments);
+
+        // Lights attached to a token follow it
+        let tokens = TokenService::new(self.conn).list_tokens_for_map(map.id)?;
+        let lit_areas = LightSourceService::new(self.conn)
+            .get_active_light_sources(map.id)?
+            .into_iter()
+            .map(|light| {
+                let position = light
+                    .token_id
+                    .and_then(|id| tokens.iter().find(|t| t.id == id))
+                    .map_or(Point::new(light.x, light.y), |t| Point::new(t.x, t.y));
+                lit_area(
+                    &blockers,
+                    &Light {
+                        light_source_id: light.id,
+                        position,
+                        bright_radius_px: feet_to_pixels(light.bright_radius_ft, grid_size_px),
+                        dim_radius_px: feet_to_pixels(light.dim_radius_ft, grid_size_px),
+                    },
+                )
+            })
+            .collect();
+
+        let ambient_light = map.ambient_light_enum();
+        let tokens = viewers
+            .iter()
+            .map(|token| {
+                let viewer = Viewer {
+                    token_id: token.id,
+                    position: Point::new(token.x, token.y),
+                    vision_type: token.vision_type_enum(),
+                    vision_range_px: feet_to_pixels(
+                        token.vision_range_ft.unwrap_or(0.0),
+                        grid_size_px,
+                    ),
+                };
+                token_visibility(&blockers, &viewer, ambient_light)
+            })
+            .collect();
+
+        Ok(MapVisibility {
+            map_id: map.id,
+            lit_areas,
+            tokens,
+        })
+    }
+}
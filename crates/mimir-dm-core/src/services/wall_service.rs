@@ -0,0 +1,209 @@
+//! Wall and door service for Vision and Lighting System.
+//!
+//! Manages the line-of-sight geometry of maps: wall segments and doors.
+
+use crate::connection::DbConnection;
+use crate::domain::Segment;
+use crate::error::Result;
+use crate::models::campaign::{
+    DoorState, MapDoor, MapWall, NewMapDoor, NewMapWall, UpdateMapDoor, UpdateMapWall,
+};
+use crate::schema::{map_doors, map_walls};
+use diesel::prelude::*;
+
+/// Service for managing walls and doors
+pub struct WallService<'a> {
+    conn: &'a mut DbConnection,
+}
+
+impl<'a> WallService<'a> {
+    /// Create a new wall service.
+    pub fn new(conn: &'a mut DbConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Create a wall segment on a map.
+    ///
+    /// # Arguments
+    /// * `new_wall` - The wall segment to create
+    ///
+    /// # Returns
+    /// * `Ok(MapWall)` - The created wall
+    pub fn create_wall(&mut self, new_wall: NewMapWall) -> Result<MapWall> {
+        diesel::insert_into(map_walls::table)
+            .values(&new_wall)
+            .returning(MapWall::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Create many wall segments at once.
+    ///
+    /// # Arguments
+    /// * `new_walls` - The wall segments to create
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of walls created
+    pub fn create_walls(&mut self, new_walls: &[NewMapWall]) -> Result<usize> {
+        diesel::insert_into(map_walls::table)
+            .values(new_walls)
+            .execute(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Create a door on a map.
+    ///
+    /// # Arguments
+    /// * `new_door` - The door to create
+    ///
+    /// # Returns
+    /// * `Ok(MapDoor)` - The created door
+    pub fn create_door(&mut self, new_door: NewMapDoor) -> Result<MapDoor> {
+        diesel::insert_into(map_doors::table)
+            .values(&new_door)
+            .returning(MapDoor::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get all wall segments on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<MapWall>)` - Walls in creation order
+    pub fn get_walls_for_map(&mut self, map_id: i32) -> Result<Vec<MapWall>> {
+        map_walls::table
+            .filter(map_walls::map_id.eq(map_id))
+            .order(map_walls::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Get all doors on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<MapDoor>)` - Doors in creation order
+    pub fn get_doors_for_map(&mut self, map_id: i32) -> Result<Vec<MapDoor>> {
+        map_doors::table
+            .filter(map_doors::map_id.eq(map_id))
+            .order(map_doors::id.asc())
+            .load(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Update a wall segment.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the wall
+    /// * `update` - Fields to update
+    ///
+    /// # Returns
+    /// * `Ok(MapWall)` - The updated wall
+    pub fn update_wall(&mut self, id: i32, update: UpdateMapWall) -> Result<MapWall> {
+        diesel::update(map_walls::table.find(id))
+            .set(&update)
+            .returning(MapWall::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Update a door.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the door
+    /// * `update` - Fields to update
+    ///
+    /// # Returns
+    /// * `Ok(MapDoor)` - The updated door
+    pub fn update_door(&mut self, id: i32, mut update: UpdateMapDoor) -> Result<MapDoor> {
+        if update.updated_at.is_none() {
+            update.updated_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+
+        diesel::update(map_doors::table.find(id))
+            .set(&update)
+            .returning(MapDoor::as_returning())
+            .get_result(self.conn)
+            .map_err(Into::into)
+    }
+
+    /// Open, close or lock a door.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the door
+    /// * `state` - The new door state
+    ///
+    /// # Returns
+    /// * `Ok(MapDoor)` - The updated door
+    pub fn set_door_state(&mut self, id: i32, state: DoorState) -> Result<MapDoor> {
+        self.update_door(id, UpdateMapDoor::state(state))
+    }
+
+    /// Delete a wall segment.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the wall
+    ///
+    /// # Returns
+    /// * `Ok(())` - If deletion succeeds
+    pub fn delete_wall(&mut self, id: i32) -> Result<()> {
+        diesel::delete(map_walls::table.find(id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// Delete a door.
+    ///
+    /// # Arguments
+    /// * `id` - Database ID of the door
+    ///
+    /// # Returns
+    /// * `Ok(())` - If deletion succeeds
+    pub fn delete_door(&mut self, id: i32) -> Result<()> {
+        diesel::delete(map_doors::table.find(id)).execute(self.conn)?;
+        Ok(())
+    }
+
+    /// Get the segments that currently block sight on a map.
+    ///
+    /// Includes every wall and each door that is closed or locked.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Segment>)` - Sight-blocking segments
+    pub fn get_sight_blocking_segments(&mut self, map_id: i32) -> Result<Vec<Segment>> {
+        let walls = self.get_walls_for_map(map_id)?;
+        let doors = self.get_doors_for_map(map_id)?;
+
+        let wall_segments = walls.iter().map(|w| (w.x1, w.y1, w.x2, w.y2));
+        let door_segments = doors
+            .iter()
+            .filter(|d| d.state_enum().blocks_sight())
+            .map(|d| (d.x1, d.y1, d.x2, d.y2));
+        Ok(wall_segments
+            .chain(door_segments)
+            .map(|(x1, y1, x2, y2)| Segment { x1, y1, x2, y2 })
+            .collect())
+    }
+
+    /// Delete all walls and doors on a map.
+    ///
+    /// # Arguments
+    /// * `map_id` - Database ID of the map
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of walls and doors deleted
+    pub fn delete_all_for_map(&mut self, map_id: i32) -> Result<usize> {
+        let walls = diesel::delete(map_walls::table.filter(map_walls::map_id.eq(map_id)))
+            .execute(self.conn)?;
+        let doors = diesel::delete(map_doors::table.filter(map_doors::map_id.eq(map_id)))
+            .execute(self.conn)?;
+        Ok(walls + doors)
+    }
+}
@@ -0,0 +1,122 @@
+//! Small string helpers shared across services
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Innermost `{@tag text|source|...}` markup
+static INLINE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{@(\w+)\s*([^{}]*)\}").unwrap());
+
+/// Tags written `name|source|display`, whose display text replaces the name
+const NAME_SOURCE_DISPLAY_TAGS: &[&str] = &[
+    "action",
+    "background",
+    "boon",
+    "charoption",
+    "class",
+    "condition",
+    "creature",
+    "cult",
+    "disease",
+    "feat",
+    "hazard",
+    "item",
+    "language",
+    "legroup",
+    "object",
+    "optfeature",
+    "psionic",
+    "race",
+    "recipe",
+    "reward",
+    "sense",
+    "skill",
+    "spell",
+    "status",
+    "table",
+    "trap",
+    "variantrule",
+    "vehicle",
+];
+
+/// Reduce 5etools `{@tag ...}` markup to the text a reader would see,
+/// innermost tags first.
+///
+/// Reference tags show their display text when they have one, so
+/// `{@item handaxe|phb|handaxes}` becomes "handaxes"; every other tag keeps
+/// its first field (`{@damage 2d6}` becomes "2d6").
+pub fn strip_tags(text: &str) -> String {
+    let mut text = text.to_string();
+    while INLINE_TAG.is_match(&text) {
+        text = INLINE_TAG
+            .replace_all(&text, |caps: &regex::Captures| {
+                let fields: Vec<&str> = caps[2].split('|').collect();
+                let display = if NAME_SOURCE_DISPLAY_TAGS.contains(&&caps[1]) {
+                    fields.get(2).filter(|d| !d.trim().is_empty())
+                } else {
+                    None
+                };
+                display.unwrap_or(&fields[0]).to_string()
+            })
+            .into_owned();
+    }
+    text
+}
+
+/// Uppercase the first character, leaving the rest untouched
+pub(crate) fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+    }
+}
+
+/// Capitalize every whitespace-separated word ("chain mail" -> "Chain Mail")
+pub(crate) fn titlecase(s: &str) -> String {
+    s.split_whitespace()
+        .map(capitalize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_tags_keeps_display_text() {
+        assert_eq!(
+            strip_tags("(a) {@item chain mail|phb} or (b) {@item arrow|phb|20 arrows}"),
+            "(a) chain mail or (b) 20 arrows"
+        );
+        assert_eq!(
+            strip_tags("Four {@creature goblin|mm|goblins} attack {@area Phandalin|123|x}."),
+            "Four goblins attack Phandalin."
+        );
+        assert_eq!(
+            strip_tags("{@damage 2d6} and {@item dagger||}"),
+            "2d6 and dagger"
+        );
+    }
+
+    #[test]
+    fn test_strip_nested_and_unclosed_tags() {
+        assert_eq!(
+            strip_tags("{@note see {@spell fireball|phb}} now"),
+            "see fireball now"
+        );
+        assert_eq!(
+            strip_tags("broken {@item sword and more"),
+            "broken {@item sword and more"
+        );
+    }
+
+    #[test]
+    fn test_capitalize_and_titlecase() {
+        assert_eq!(capitalize("chain mail"), "Chain mail");
+        assert_eq!(capitalize(""), "");
+        assert_eq!(titlecase("chain  mail"), "Chain Mail");
+        assert_eq!(titlecase("explorer's pack"), "Explorer's Pack");
+    }
+}
@@ -284,3 +284,31 @@ fn test_document_check_exists() {
         .count();
     assert_eq!(final_count, initial_count + 1);
 }
+
+#[test]
+fn test_campaign_enabled_sources() {
+    let (mut conn, temp_dir) = setup_test_db();
+
+    let mut service = CampaignService::new(&mut conn);
+    let campaign = service
+        .create_campaign("Sources Campaign", None, temp_dir.path().to_str().unwrap())
+        .unwrap();
+
+    // No sources configured means every source is allowed
+    assert!(service.get_enabled_sources(campaign.id).unwrap().is_empty());
+
+    let sources = service
+        .set_enabled_sources(
+            campaign.id,
+            &["VGM".to_string(), "MM".to_string(), "MM".to_string()],
+        )
+        .unwrap();
+    assert_eq!(sources, vec!["MM".to_string(), "VGM".to_string()]);
+
+    let sources = service
+        .set_enabled_sources(campaign.id, &["PHB".to_string()])
+        .unwrap();
+    assert_eq!(sources, vec!["PHB".to_string()]);
+
+    assert!(service.set_enabled_sources(campaign.id + 1, &[]).is_err());
+}
@@ -0,0 +1,191 @@
+//! Integration tests for CatalogSearchService
+
+use diesel::prelude::*;
+use mimir_dm_core::models::catalog::{CatalogKind, CatalogSearchFilter};
+use mimir_dm_core::schema::{catalog_items, catalog_monsters, catalog_spells};
+use mimir_dm_core::services::CatalogSearchService;
+use mimir_dm_core::{establish_connection, run_migrations};
+use tempfile::TempDir;
+
+fn setup_test_db() -> (SqliteConnection, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("test.db");
+    let mut conn = establish_connection(db_path.to_str().unwrap()).unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    seed_catalog(&mut conn);
+    (conn, temp_dir)
+}
+
+fn seed_catalog(conn: &mut SqliteConnection) {
+    let spells = [
+        (
+            "Fireball",
+            "PHB",
+            r#"{"name":"Fireball","source":"PHB","entries":["A bright streak flashes from your pointing finger. Each creature in a 20-foot-radius sphere takes {@damage 8d6} fire damage on a failed save."]}"#,
+        ),
+        (
+            "Shield",
+            "PHB",
+            r#"{"name":"Shield","source":"PHB","entries":["An invisible barrier of magical force appears and protects you."]}"#,
+        ),
+        (
+            "Chaos Bolt",
+            "XGE",
+            r#"{"name":"Chaos Bolt","source":"XGE","entries":["You hurl an undulating mass of chaotic energy. The target takes {@damage 2d8 + 1d6} damage."]}"#,
+        ),
+    ];
+    for (name, source, json) in spells {
+        diesel::insert_into(catalog_spells::table)
+            .values((
+                catalog_spells::name.eq(name),
+                catalog_spells::level.eq(1),
+                catalog_spells::school.eq("Evocation"),
+                catalog_spells::cast_time.eq("1 action"),
+                catalog_spells::range.eq("Self"),
+                catalog_spells::components.eq("V, S"),
+                catalog_spells::tags.eq("[]"),
+                catalog_spells::source.eq(source),
+                catalog_spells::full_spell_json.eq(json),
+            ))
+            .execute(conn)
+            .expect("Failed to insert spell");
+    }
+
+    diesel::insert_into(catalog_monsters::table)
+        .values((
+            catalog_monsters::name.eq("Wolf"),
+            catalog_monsters::source.eq("MM"),
+            catalog_monsters::full_monster_json.eq(r#"{"name":"Wolf","source":"MM","type":"beast","trait":[{"name":"Pack Tactics","entries":["The wolf has advantage on an attack roll against a creature if at least one of the wolf's allies is within 5 feet of the creature."]}]}"#),
+        ))
+        .execute(conn)
+        .expect("Failed to insert monster");
+
+    diesel::insert_into(catalog_items::table)
+        .values((
+            catalog_items::name.eq("Flame Tongue"),
+            catalog_items::source.eq("DMG"),
+            catalog_items::full_item_json.eq(r#"{"name":"Flame Tongue","source":"DMG","entries":["While ablaze, the sword deals an extra {@damage 2d6} fire damage to any target it hits."]}"#),
+        ))
+        .execute(conn)
+        .expect("Failed to insert item");
+}
+
+fn index_all(conn: &mut SqliteConnection) {
+    let mut service = CatalogSearchService::new(conn);
+    for source in ["PHB", "XGE", "MM", "DMG"] {
+        service.index_source(source).unwrap();
+    }
+}
+
+#[test]
+fn test_search_matches_entry_text_across_kinds() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    index_all(&mut conn);
+    let mut service = CatalogSearchService::new(&mut conn);
+
+    let hits = service
+        .search("fire damage", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    let names: Vec<&str> = hits.iter().map(|h| h.name.as_str()).collect();
+    assert_eq!(hits.len(), 2);
+    assert!(names.contains(&"Fireball"));
+    assert!(names.contains(&"Flame Tongue"));
+
+    let wolf = service
+        .search("\"pack tactics\"", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert_eq!(wolf.len(), 1);
+    assert_eq!(wolf[0].kind, CatalogKind::Monster);
+    assert_eq!(wolf[0].source, "MM");
+    assert!(wolf[0].snippet.contains("**Pack Tactics**"));
+}
+
+#[test]
+fn test_search_matches_monster_creature_type() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    index_all(&mut conn);
+    let mut service = CatalogSearchService::new(&mut conn);
+
+    let hits = service
+        .search("beast", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].name, "Wolf");
+    assert_eq!(hits[0].kind, CatalogKind::Monster);
+}
+
+#[test]
+fn test_name_matches_rank_first() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    index_all(&mut conn);
+    let mut service = CatalogSearchService::new(&mut conn);
+
+    // "fire" appears in Flame Tongue's text, but Fireball's name starts with it
+    let hits = service
+        .search("fire", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert_eq!(hits[0].name, "Fireball");
+    assert!(hits.windows(2).all(|w| w[0].rank <= w[1].rank));
+}
+
+#[test]
+fn test_search_filters_by_kind_and_source() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    index_all(&mut conn);
+    let mut service = CatalogSearchService::new(&mut conn);
+
+    let filter = CatalogSearchFilter {
+        kinds: vec![CatalogKind::Item],
+        sources: Vec::new(),
+    };
+    let hits = service.search("damage", &filter, 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].name, "Flame Tongue");
+
+    let filter = CatalogSearchFilter {
+        kinds: Vec::new(),
+        sources: vec!["XGE".to_string()],
+    };
+    let hits = service.search("damage", &filter, 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].name, "Chaos Bolt");
+    assert_eq!(hits[0].kind, CatalogKind::Spell);
+}
+
+#[test]
+fn test_remove_and_reindex_source() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    index_all(&mut conn);
+    let mut service = CatalogSearchService::new(&mut conn);
+    assert_eq!(service.indexed_count().unwrap(), 5);
+
+    // Re-indexing replaces rather than duplicates
+    assert_eq!(service.index_source("PHB").unwrap(), 2);
+    assert_eq!(service.indexed_count().unwrap(), 5);
+
+    assert_eq!(service.remove_source("PHB").unwrap(), 2);
+    let hits = service
+        .search("fireball", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert!(hits.is_empty());
+}
+
+#[test]
+fn test_ensure_index_builds_from_existing_catalog() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    let mut service = CatalogSearchService::new(&mut conn);
+
+    assert!(service.ensure_index().unwrap());
+    assert_eq!(service.indexed_count().unwrap(), 5);
+    assert!(!service.ensure_index().unwrap());
+
+    // Operator characters in the query don't break FTS5 parsing
+    let hits = service
+        .search("shield OR (", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert!(hits.is_empty());
+    let hits = service
+        .search("shie", &CatalogSearchFilter::default(), 10)
+        .unwrap();
+    assert_eq!(hits[0].name, "Shield");
+}
@@ -472,3 +472,614 @@ fn test_proficiency_bonus_calculation() {
     char_data_level_17.level = 17;
     assert_eq!(char_data_level_17.proficiency_bonus(), 6);
 }
+
+#[test]
+fn test_class_resources_use_and_rest() {
+    use mimir_dm_core::models::character::RestType;
+
+    let (mut conn, temp_dir) = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Boromir".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Fighter", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 16,
+            dexterity: 12,
+            constitution: 14,
+            intelligence: 8,
+            wisdom: 10,
+            charisma: 10,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(char_data.class_resources.len(), 1);
+    assert_eq!(char_data.class_resources[0].name, "Second Wind");
+    assert!(char_data.spells.pact_slots.is_none());
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    char_service
+        .use_class_resource(character.id, "second wind", 1)
+        .unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.class_resources[0].current, 0);
+
+    // No uses left
+    assert!(char_service
+        .use_class_resource(character.id, "Second Wind", 1)
+        .is_err());
+    assert!(char_service
+        .use_class_resource(character.id, "Ki Points", 1)
+        .is_err());
+
+    char_service.rest(character.id, RestType::Short).unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.class_resources[0].current, 1);
+}
+
+#[test]
+fn test_combat_state_is_versioned() {
+    use mimir_dm_core::models::character::RestType;
+
+    let (mut conn, temp_dir) = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Faramir".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Fighter", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 16,
+            dexterity: 12,
+            constitution: 14,
+            intelligence: 8,
+            wisdom: 10,
+            charisma: 10,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let max_hp = char_data.max_hp;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    char_service.set_temp_hp(character.id, 5).unwrap();
+    assert!(char_service.set_temp_hp(character.id, 2).is_err());
+    char_service
+        .set_concentration(character.id, Some("Hunter's Mark"))
+        .unwrap();
+    char_service
+        .add_condition(character.id, "Frightened")
+        .unwrap();
+    assert!(char_service
+        .add_condition(character.id, "frightened")
+        .is_err());
+
+    let (_, outcome) = char_service.apply_damage(character.id, 7, None).unwrap();
+    assert_eq!(outcome.temp_hp_absorbed, 5);
+    assert_eq!(outcome.concentration_save_dc, Some(10));
+
+    // Dropping to 0 HP ends concentration and starts death saves
+    let (_, outcome) = char_service.update_hp(character.id, 0, None).unwrap();
+    assert!(outcome.dropped_to_zero);
+    assert_eq!(
+        outcome.concentration_ended.as_deref(),
+        Some("Hunter's Mark")
+    );
+    char_service.record_death_save(character.id, 4).unwrap();
+    assert!(char_service.record_death_save(character.id, 21).is_err());
+
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.current_hp, 0);
+    assert_eq!(data.death_saves.failures, 1);
+    assert!(data.conditions.iter().any(|c| c == "Unconscious"));
+
+    char_service.heal(character.id, 3, None).unwrap();
+    char_service.set_exhaustion(character.id, 2).unwrap();
+    char_service.rest(character.id, RestType::Long).unwrap();
+
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.current_hp, max_hp);
+    assert_eq!(data.death_saves.failures, 0);
+    assert_eq!(data.exhaustion_level, 1);
+    assert_eq!(data.conditions, vec!["Frightened".to_string()]);
+    assert!(char_service.record_death_save(character.id, 10).is_err());
+
+    let versions = char_service.get_character_versions(character.id).unwrap();
+    assert!(versions
+        .iter()
+        .any(|v| v.snapshot_reason.as_deref() == Some("Became Frightened")));
+}
+
+#[test]
+fn test_long_rest_from_zero_hp_and_from_death() {
+    use mimir_dm_core::models::character::RestType;
+
+    let (mut conn, temp_dir) = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Boromir".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Fighter", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 16,
+            dexterity: 12,
+            constitution: 14,
+            intelligence: 8,
+            wisdom: 10,
+            charisma: 10,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+    let max_hp = char_data.max_hp;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    // Resting at 0 HP wakes the character
+    char_service.update_hp(character.id, 0, None).unwrap();
+    char_service.record_death_save(character.id, 4).unwrap();
+    char_service.rest(character.id, RestType::Long).unwrap();
+
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.current_hp, max_hp);
+    assert_eq!(data.death_saves.failures, 0);
+    assert!(!data.conditions.iter().any(|c| c == "Unconscious"));
+
+    // A dead character stays dead
+    char_service.update_hp(character.id, 0, None).unwrap();
+    char_service.record_death_save(character.id, 1).unwrap();
+    char_service.record_death_save(character.id, 1).unwrap();
+    assert!(char_service.rest(character.id, RestType::Long).is_err());
+
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.current_hp, 0);
+    assert!(data.death_saves.is_dead());
+}
+
+fn seed_test_magic_items(conn: &mut SqliteConnection) {
+    let items = [
+        (
+            "Wand of Magic Missiles",
+            r#"{"name": "Wand of Magic Missiles", "source": "DMG", "type": "WD", "rarity": "uncommon", "charges": 7, "recharge": "dawn", "rechargeAmount": "{@dice 1d6 + 1}"}"#,
+        ),
+        (
+            "Ring of Protection",
+            r#"{"name": "Ring of Protection", "source": "DMG", "type": "RG", "rarity": "rare", "reqAttune": true}"#,
+        ),
+        (
+            "Cloak of Protection",
+            r#"{"name": "Cloak of Protection", "source": "DMG", "type": "W", "rarity": "uncommon", "reqAttune": true}"#,
+        ),
+        (
+            "Amulet of Health",
+            r#"{"name": "Amulet of Health", "source": "DMG", "type": "W", "rarity": "rare", "reqAttune": true}"#,
+        ),
+        (
+            "Staff of Power",
+            r#"{"name": "Staff of Power", "source": "DMG", "type": "ST", "rarity": "very rare", "reqAttune": "by a sorcerer, warlock, or wizard", "charges": 20, "recharge": "dawn", "rechargeAmount": "{@dice 2d8 + 4}"}"#,
+        ),
+    ];
+
+    for (name, json) in items {
+        diesel::sql_query(
+            "INSERT INTO catalog_items (name, source, full_item_json) VALUES (?, ?, ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>("DMG")
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_magic_item_attunement_and_charges() {
+    use mimir_dm_core::models::character::{ItemRecharge, RestType};
+
+    let (mut conn, temp_dir) = setup_test_db();
+    seed_test_magic_items(&mut conn);
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Gandalf".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Wizard", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 10,
+            dexterity: 12,
+            constitution: 14,
+            intelligence: 16,
+            wisdom: 12,
+            charisma: 10,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    for item in [
+        "Wand of Magic Missiles",
+        "Ring of Protection",
+        "Cloak of Protection",
+        "Amulet of Health",
+        "Staff of Power",
+    ] {
+        char_service
+            .add_item(character.id, item, "DMG", 1, None)
+            .unwrap();
+    }
+
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    let wand = &data.inventory[0];
+    assert!(!wand.requires_attunement);
+    let charges = wand.charges.as_ref().unwrap();
+    assert_eq!((charges.current, charges.max), (7, 7));
+    assert_eq!(charges.recharge, Some(ItemRecharge::Dawn));
+    assert_eq!(charges.recharge_amount.as_deref(), Some("1d6+1"));
+
+    // Attunement respects reqAttune and the three-item limit
+    assert!(char_service
+        .set_item_attunement(character.id, "Wand of Magic Missiles", true)
+        .is_err());
+    for item in [
+        "Ring of Protection",
+        "Cloak of Protection",
+        "Amulet of Health",
+    ] {
+        char_service
+            .set_item_attunement(character.id, item, true)
+            .unwrap();
+    }
+    assert!(char_service
+        .set_item_attunement(character.id, "Staff of Power", true)
+        .is_err());
+    char_service
+        .set_item_attunement(character.id, "amulet of health", false)
+        .unwrap();
+    char_service
+        .set_item_attunement(character.id, "Staff of Power", true)
+        .unwrap();
+
+    // Charges are spent, refused when short, and recharged at dawn
+    char_service
+        .use_item_charges(character.id, "Wand of Magic Missiles", 7)
+        .unwrap();
+    assert!(char_service
+        .use_item_charges(character.id, "Wand of Magic Missiles", 1)
+        .is_err());
+    assert!(char_service
+        .use_item_charges(character.id, "Ring of Protection", 1)
+        .is_err());
+
+    char_service.rest(character.id, RestType::Short).unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.inventory[0].charges.as_ref().unwrap().current, 0);
+
+    char_service.rest(character.id, RestType::Long).unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    let current = data.inventory[0].charges.as_ref().unwrap().current;
+    assert!((2..=7).contains(&current));
+    assert_eq!(data.attuned_count(), 3);
+
+    // Charges can be restored outside a rest, but only by a positive amount
+    char_service
+        .restore_item_charges(character.id, "Wand of Magic Missiles", None)
+        .unwrap();
+    char_service
+        .use_item_charges(character.id, "Wand of Magic Missiles", 3)
+        .unwrap();
+    assert!(char_service
+        .restore_item_charges(character.id, "Wand of Magic Missiles", Some(0))
+        .is_err());
+    assert!(char_service
+        .restore_item_charges(character.id, "Wand of Magic Missiles", Some(-2))
+        .is_err());
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.inventory[0].charges.as_ref().unwrap().current, 4);
+
+    char_service
+        .restore_item_charges(character.id, "Wand of Magic Missiles", Some(2))
+        .unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.inventory[0].charges.as_ref().unwrap().current, 6);
+}
+
+fn seed_test_warlock_data(conn: &mut SqliteConnection) {
+    let warlock_json = r#"{
+        "name": "Warlock",
+        "source": "PHB",
+        "hd": {"number": 1, "faces": 8},
+        "casterProgression": "pact",
+        "spellcastingAbility": "cha",
+        "optionalfeatureProgression": [{
+            "name": "Eldritch Invocations",
+            "featureType": ["EI"],
+            "progression": [0, 2, 2, 2, 3, 3, 4, 4, 5, 5, 5, 6, 6, 6, 7, 7, 7, 8, 8, 8]
+        }]
+    }"#;
+
+    diesel::sql_query(
+        "INSERT INTO catalog_classes (name, source, hit_dice, caster_progression, full_class_json) VALUES (?, ?, ?, ?, ?)",
+    )
+    .bind::<diesel::sql_types::Text, _>("Warlock")
+    .bind::<diesel::sql_types::Text, _>("PHB")
+    .bind::<diesel::sql_types::Text, _>("d8")
+    .bind::<diesel::sql_types::Text, _>("pact")
+    .bind::<diesel::sql_types::Text, _>(warlock_json)
+    .execute(conn)
+    .unwrap();
+
+    let invocations = [
+        (
+            "Agonizing Blast",
+            r#"{"name": "Agonizing Blast", "source": "PHB", "featureType": ["EI"], "prerequisite": [{"spell": ["eldritch blast#c"]}], "entries": []}"#,
+        ),
+        (
+            "Armor of Shadows",
+            r#"{"name": "Armor of Shadows", "source": "PHB", "featureType": ["EI"], "entries": []}"#,
+        ),
+        (
+            "Devil's Sight",
+            r#"{"name": "Devil's Sight", "source": "PHB", "featureType": ["EI"], "entries": []}"#,
+        ),
+        (
+            "Mask of Many Faces",
+            r#"{"name": "Mask of Many Faces", "source": "PHB", "featureType": ["EI"], "entries": []}"#,
+        ),
+        (
+            "Thirsting Blade",
+            r#"{"name": "Thirsting Blade", "source": "PHB", "featureType": ["EI"], "prerequisite": [{"level": {"level": 5, "class": {"name": "Warlock"}}, "pact": "Blade"}], "entries": []}"#,
+        ),
+    ];
+
+    for (name, json) in invocations {
+        diesel::sql_query(
+            "INSERT INTO catalog_optional_features (name, feature_types, source, full_optional_feature_json) VALUES (?, ?, ?, ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(r#"["EI"]"#)
+        .bind::<diesel::sql_types::Text, _>("PHB")
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_level_up_recalculates_pact_slots() {
+    use mimir_dm_core::services::character::{HpGainMethod, LevelUpOptions};
+
+    let (mut conn, temp_dir) = setup_test_db();
+    seed_test_warlock_data(&mut conn);
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Hexley".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Warlock", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 8,
+            dexterity: 14,
+            constitution: 14,
+            intelligence: 10,
+            wisdom: 12,
+            charisma: 16,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    let options = || LevelUpOptions {
+        class_name: "Warlock".to_string(),
+        class_source: "PHB".to_string(),
+        hp_method: HpGainMethod::Average,
+        asi_or_feat: None,
+        subclass_choice: None,
+        optional_features: vec![],
+        snapshot_reason: None,
+    };
+    let pact_slots = |service: &mut CharacterService| {
+        let (_, data) = service.get_character(character.id).unwrap();
+        let slots = data.spells.pact_slots.unwrap();
+        (slots.slot_level, slots.max)
+    };
+    assert_eq!(pact_slots(&mut char_service), (1, 1));
+
+    // Level 2 gains a second slot; invocations are left for later
+    char_service
+        .level_up_character(character.id, options())
+        .unwrap();
+    assert_eq!(pact_slots(&mut char_service), (1, 2));
+
+    // Level 3 raises both slots to 2nd level
+    char_service
+        .level_up_character(character.id, options())
+        .unwrap();
+    assert_eq!(pact_slots(&mut char_service), (2, 2));
+}
+
+#[test]
+fn test_level_up_optional_features() {
+    use mimir_dm_core::services::character::{
+        HpGainMethod, LevelUpOptions, OptionalFeatureSelection,
+    };
+
+    let (mut conn, temp_dir) = setup_test_db();
+    seed_test_warlock_data(&mut conn);
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let player_id = create_test_player(&mut conn);
+
+    let char_data = CharacterBuilder::new(&mut conn)
+        .set_identity("Hexley".to_string(), Some(player_id))
+        .set_race("Human", "PHB", None)
+        .unwrap()
+        .set_class("Warlock", "PHB", None)
+        .unwrap()
+        .set_background("Sage", "PHB")
+        .unwrap()
+        .set_ability_scores(AbilityScoreMethod::Manual {
+            strength: 8,
+            dexterity: 14,
+            constitution: 14,
+            intelligence: 10,
+            wisdom: 12,
+            charisma: 16,
+        })
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let character = char_service
+        .create_character(
+            Some(campaign_id),
+            Some(player_id),
+            false,
+            temp_dir.path().to_str().unwrap(),
+            char_data,
+        )
+        .unwrap();
+
+    // Level 2 opens two invocations; prerequisites filter the catalog
+    let gains = char_service
+        .get_level_up_optional_features(character.id, "Warlock", None)
+        .unwrap();
+    assert_eq!(gains.len(), 1);
+    assert_eq!(gains[0].name, "Eldritch Invocations");
+    assert_eq!((gains[0].total, gains[0].remaining), (2, 2));
+    let available: Vec<&str> = gains[0].available.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+        available,
+        vec!["Armor of Shadows", "Devil's Sight", "Mask of Many Faces"]
+    );
+
+    let options = |choices: &[&str]| LevelUpOptions {
+        class_name: "Warlock".to_string(),
+        class_source: "PHB".to_string(),
+        hp_method: HpGainMethod::Average,
+        asi_or_feat: None,
+        subclass_choice: None,
+        optional_features: choices
+            .iter()
+            .map(|name| OptionalFeatureSelection {
+                name: name.to_string(),
+                source: None,
+            })
+            .collect(),
+        snapshot_reason: None,
+    };
+
+    // Unmet prerequisites are rejected
+    assert!(char_service
+        .level_up_character(
+            character.id,
+            options(&["Armor of Shadows", "Agonizing Blast"])
+        )
+        .is_err());
+
+    char_service
+        .level_up_character(
+            character.id,
+            options(&["armor of shadows", "Devil's Sight"]),
+        )
+        .unwrap();
+    let (_, data) = char_service.get_character(character.id).unwrap();
+    assert_eq!(data.level, 2);
+    let chosen: Vec<(&str, &str, i32)> = data
+        .optional_features
+        .iter()
+        .map(|f| (f.name.as_str(), f.feature_type.as_str(), f.level))
+        .collect();
+    assert_eq!(
+        chosen,
+        vec![("Armor of Shadows", "EI", 2), ("Devil's Sight", "EI", 2)]
+    );
+
+    // Level 3 adds no invocations, so further choices are refused
+    assert!(char_service
+        .get_level_up_optional_features(character.id, "Warlock", None)
+        .unwrap()
+        .is_empty());
+    assert!(char_service
+        .level_up_character(character.id, options(&["Mask of Many Faces"]))
+        .is_err());
+}
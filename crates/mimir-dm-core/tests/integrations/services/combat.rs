@@ -0,0 +1,577 @@
+//! Integration tests for CombatService
+
+use diesel::prelude::*;
+use mimir_dm_core::models::campaign::{
+    CombatantType, DamageDefense, HpEventType, HpMode, NewCombatEncounter, NewCombatant, NewMap,
+    NewToken, TokenSize,
+};
+use mimir_dm_core::models::character::CharacterData;
+use mimir_dm_core::services::{
+    CampaignService, CharacterService, CombatService, MapService, ModuleMonsterService,
+    ModuleService, TokenService,
+};
+use mimir_dm_core::{establish_connection, run_migrations};
+use tempfile::TempDir;
+
+fn setup_test_db() -> (SqliteConnection, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("test.db");
+    let mut conn = establish_connection(db_path.to_str().unwrap()).unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    mimir_dm_core::seed::template_seeder::seed_templates(&mut conn).unwrap();
+    seed_test_monsters(&mut conn);
+    seed_test_conditions(&mut conn);
+    (conn, temp_dir)
+}
+
+fn seed_test_monsters(conn: &mut SqliteConnection) {
+    let monsters = vec![
+        (
+            "Goblin",
+            "S",
+            "1/4",
+            0.25,
+            7,
+            15,
+            r#"{"name":"Goblin","source":"MM","size":["S"],"type":"humanoid","ac":[{"ac":15}],"hp":{"average":7,"formula":"2d6"},"dex":14,"cr":"1/4"}"#,
+        ),
+        (
+            "Ogre",
+            "L",
+            "2",
+            2.0,
+            59,
+            11,
+            r#"{"name":"Ogre","source":"MM","size":["L"],"type":"giant","ac":[{"ac":11}],"hp":{"average":59,"formula":"7d10 + 21"},"dex":8,"cr":"2"}"#,
+        ),
+        (
+            "Zombie",
+            "M",
+            "1/4",
+            0.25,
+            22,
+            8,
+            r#"{"name":"Zombie","source":"MM","size":["M"],"type":"undead","ac":[{"ac":8}],"hp":{"average":22,"formula":"3d8 + 9"},"dex":6,"cr":"1/4","resist":["cold",{"resist":["bludgeoning","piercing","slashing"],"note":"from nonmagical attacks","cond":true}],"immune":["poison"],"vulnerable":["radiant"],"conditionImmune":["poisoned"]}"#,
+        ),
+    ];
+
+    for (name, size, cr, cr_numeric, hp, ac, json) in monsters {
+        diesel::sql_query(
+            "INSERT INTO catalog_monsters (name, size, cr, cr_numeric, hp, ac, source, full_monster_json) VALUES (?, ?, ?, ?, ?, ?, 'MM', ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(size)
+        .bind::<diesel::sql_types::Text, _>(cr)
+        .bind::<diesel::sql_types::Double, _>(cr_numeric)
+        .bind::<diesel::sql_types::Integer, _>(hp)
+        .bind::<diesel::sql_types::Integer, _>(ac)
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+fn seed_test_conditions(conn: &mut SqliteConnection) {
+    for name in ["Poisoned", "Prone", "Stunned"] {
+        diesel::sql_query(
+            "INSERT INTO catalog_conditions (name, item_type, description, source, full_condition_json) VALUES (?, 'condition', '', 'PHB', '{}')",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+fn create_test_module(conn: &mut SqliteConnection, temp_dir: &TempDir) -> (i32, i32) {
+    let dir_path = temp_dir.path().join("campaign").to_string_lossy().to_string();
+
+    let mut campaign_service = CampaignService::new(conn);
+    let campaign = campaign_service
+        .create_campaign("Combat Campaign", None, &dir_path)
+        .unwrap();
+
+    let mut module_service = ModuleService::new(conn);
+    let module = module_service
+        .create_module(campaign.id, "Goblin Ambush".to_string(), 2)
+        .unwrap();
+
+    (campaign.id, module.id)
+}
+
+fn create_test_pc(conn: &mut SqliteConnection, campaign_id: i32, name: &str, dex: i32) -> i32 {
+    let yaml = format!(
+        r#"
+character_name: {name}
+player_id: null
+level: 3
+version: 1
+snapshot_reason: null
+created_at: "2025-01-01T00:00:00Z"
+race: Human
+subrace: null
+classes:
+  - class_name: Fighter
+    level: 3
+    subclass: null
+    hit_dice_type: d10
+    hit_dice_remaining: 3
+background: Soldier
+alignment: null
+abilities:
+  strength: 16
+  dexterity: {dex}
+  constitution: 14
+  intelligence: 10
+  wisdom: 12
+  charisma: 8
+max_hp: 28
+current_hp: 24
+proficiencies: {{}}
+"#
+    );
+    let data: CharacterData = serde_yaml::from_str(&yaml).unwrap();
+
+    let mut char_service = CharacterService::new(conn);
+    char_service
+        .create_character(Some(campaign_id), None, false, "", data)
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_start_from_encounter_creates_instances() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (_, module_id) = create_test_module(&mut conn, &temp_dir);
+
+    let mut mm_service = ModuleMonsterService::new(&mut conn);
+    mm_service
+        .add_monster(module_id, "Goblin".to_string(), "MM".to_string(), 3, Some("Ambush".to_string()))
+        .unwrap();
+    mm_service
+        .add_monster(module_id, "Ogre".to_string(), "MM".to_string(), 1, Some("Ambush".to_string()))
+        .unwrap();
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service
+        .start_from_encounter(module_id, Some("Ambush"), false, HpMode::Average)
+        .unwrap();
+
+    assert_eq!(state.encounter.round, 1);
+    assert_eq!(state.encounter.encounter_tag, Some("Ambush".to_string()));
+    assert_eq!(state.combatants.len(), 4);
+
+    let names: Vec<&str> = state.combatants.iter().map(|c| c.name.as_str()).collect();
+    assert!(names.contains(&"Goblin 1"));
+    assert!(names.contains(&"Goblin 3"));
+    assert!(names.contains(&"Ogre"));
+
+    let goblin = state
+        .combatants
+        .iter()
+        .find(|c| c.name == "Goblin 2")
+        .unwrap();
+    assert_eq!(goblin.initiative_modifier, 2);
+    assert_eq!(goblin.max_hp, Some(7));
+    assert_eq!(goblin.current_hp, Some(7));
+    assert_eq!(goblin.armor_class, Some(15));
+    assert!(goblin.initiative >= 3 && goblin.initiative <= 22);
+
+    let ogre = state.combatants.iter().find(|c| c.name == "Ogre").unwrap();
+    assert_eq!(ogre.initiative_modifier, -1);
+
+    // Turn marker starts on the highest initiative
+    assert_eq!(
+        state.encounter.current_combatant_id,
+        Some(state.combatants[0].id)
+    );
+}
+
+#[test]
+fn test_start_from_encounter_includes_party() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (campaign_id, module_id) = create_test_module(&mut conn, &temp_dir);
+    create_test_pc(&mut conn, campaign_id, "Aria", 16);
+
+    let mut mm_service = ModuleMonsterService::new(&mut conn);
+    mm_service
+        .add_monster(module_id, "Goblin".to_string(), "MM".to_string(), 1, None)
+        .unwrap();
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service.start_from_encounter(module_id, None, true, HpMode::Average).unwrap();
+
+    assert_eq!(state.combatants.len(), 2);
+    let pc = state
+        .combatants
+        .iter()
+        .find(|c| c.combatant_type_enum() == CombatantType::PC)
+        .unwrap();
+    assert_eq!(pc.name, "Aria");
+    assert_eq!(pc.initiative_modifier, 3);
+    assert_eq!(pc.current_hp, Some(24));
+    assert_eq!(pc.max_hp, Some(28));
+}
+
+#[test]
+fn test_start_from_unknown_encounter_fails() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (_, module_id) = create_test_module(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let result = service.start_from_encounter(module_id, Some("Nowhere"), false, HpMode::Average);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_start_from_map_tokens() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (campaign_id, _) = create_test_module(&mut conn, &temp_dir);
+    let pc_id = create_test_pc(&mut conn, campaign_id, "Brom", 10);
+
+    let mut map_service = MapService::new(&mut conn);
+    let map = map_service
+        .create_map(NewMap::new(
+            campaign_id,
+            "Cave".to_string(),
+            "cave.png".to_string(),
+            1000,
+            1000,
+            1000,
+            1000,
+        ))
+        .unwrap();
+
+    let ogre_id: i32 = diesel::sql_query("SELECT id FROM catalog_monsters WHERE name = 'Ogre'")
+        .get_result::<MonsterId>(&mut conn)
+        .unwrap()
+        .id;
+
+    let mut token_service = TokenService::new(&mut conn);
+    token_service
+        .create_token(NewToken::monster(map.id, "Ogre".to_string(), ogre_id, TokenSize::Large, 5.0, 5.0))
+        .unwrap();
+    token_service
+        .create_token(NewToken::character(map.id, "Brom".to_string(), pc_id, true, TokenSize::Medium, 1.0, 1.0))
+        .unwrap();
+    token_service
+        .create_token(NewToken::trap(map.id, "Pit".to_string(), 3.0, 3.0))
+        .unwrap();
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service.start_from_map(map.id, HpMode::Average).unwrap();
+
+    assert_eq!(state.encounter.map_id, Some(map.id));
+    assert_eq!(state.combatants.len(), 2);
+
+    let ogre = state.combatants.iter().find(|c| c.name == "Ogre").unwrap();
+    assert_eq!(ogre.monster_name, Some("Ogre".to_string()));
+    assert_eq!(ogre.max_hp, Some(59));
+    assert!(ogre.token_id.is_some());
+
+    let brom = state.combatants.iter().find(|c| c.name == "Brom").unwrap();
+    assert_eq!(brom.character_id, Some(pc_id));
+    assert_eq!(brom.initiative_modifier, 0);
+}
+
+#[derive(QueryableByName)]
+struct MonsterId {
+    #[diesel(sql_type = diesel::sql_types::Integer)]
+    id: i32,
+}
+
+fn create_manual_encounter(conn: &mut SqliteConnection, temp_dir: &TempDir) -> i32 {
+    let (campaign_id, _) = create_test_module(conn, temp_dir);
+
+    let mut service = CombatService::new(conn);
+    let encounter = service
+        .create_encounter(NewCombatEncounter::new(campaign_id, "Skirmish".to_string()))
+        .unwrap();
+
+    // Fixed initiatives keep the turn order deterministic
+    for (name, initiative) in [("Fast", 20), ("Middle", 12), ("Slow", 5)] {
+        let mut combatant =
+            NewCombatant::new(encounter.id, name.to_string(), CombatantType::Monster)
+                .with_hp(10, 10);
+        combatant.initiative = initiative;
+        service.add_combatant(combatant, false).unwrap();
+    }
+
+    encounter.id
+}
+
+#[test]
+fn test_turn_order_and_rounds() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service.get_combat_state(encounter_id).unwrap();
+    let names: Vec<&str> = state.combatants.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Fast", "Middle", "Slow"]);
+    assert!(state.current_combatant().is_none());
+
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Fast");
+    assert_eq!(state.encounter.round, 1);
+
+    // Can't step back before the first turn of round 1
+    let state = service.previous_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Fast");
+
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Middle");
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Slow");
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Fast");
+    assert_eq!(state.encounter.round, 2);
+
+    let state = service.previous_turn(encounter_id).unwrap();
+    assert_eq!(state.current_combatant().unwrap().name, "Slow");
+    assert_eq!(state.encounter.round, 1);
+}
+
+#[test]
+fn test_set_initiative_reorders() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let slow = service.list_combatants(encounter_id).unwrap()[2].id;
+    service.set_initiative(slow, 25).unwrap();
+
+    let combatants = service.list_combatants(encounter_id).unwrap();
+    assert_eq!(combatants[0].name, "Slow");
+}
+
+#[test]
+fn test_next_turn_skips_inactive() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service.reroll_initiative(encounter_id).unwrap();
+    let first = state.combatants[0].id;
+    let second = state.combatants[1].id;
+    let third = state.combatants[2].id;
+    assert_eq!(state.encounter.current_combatant_id, Some(first));
+
+    service.set_combatant_active(second, false).unwrap();
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.encounter.current_combatant_id, Some(third));
+}
+
+#[test]
+fn test_combatant_hp_is_clamped() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let combatant_id = service.list_combatants(encounter_id).unwrap()[0].id;
+
+    let c = service.adjust_combatant_hp(combatant_id, -4).unwrap();
+    assert_eq!(c.current_hp, Some(6));
+
+    let c = service.adjust_combatant_hp(combatant_id, -20).unwrap();
+    assert_eq!(c.current_hp, Some(0));
+    assert!(c.is_down());
+
+    let c = service.set_combatant_hp(combatant_id, 50).unwrap();
+    assert_eq!(c.current_hp, Some(10));
+}
+
+#[test]
+fn test_resume_active_combat_and_end() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+    let campaign_id = {
+        let mut service = CombatService::new(&mut conn);
+        service.get_encounter(encounter_id).unwrap().unwrap().campaign_id
+    };
+
+    let mut service = CombatService::new(&mut conn);
+    service.next_turn(encounter_id).unwrap();
+    let before = service.get_combat_state(encounter_id).unwrap();
+
+    // A fresh service (as after a restart) sees the same state
+    let mut resumed_service = CombatService::new(&mut conn);
+    let resumed = resumed_service
+        .get_active_combat(campaign_id)
+        .unwrap()
+        .expect("active combat");
+    assert_eq!(resumed.encounter.id, encounter_id);
+    assert_eq!(resumed.encounter.round, before.encounter.round);
+    assert_eq!(
+        resumed.encounter.current_combatant_id,
+        before.encounter.current_combatant_id
+    );
+
+    let ended = resumed_service.end_combat(encounter_id).unwrap();
+    assert!(!ended.is_active());
+    assert!(resumed_service.get_active_combat(campaign_id).unwrap().is_none());
+    assert_eq!(resumed_service.list_encounters(campaign_id).unwrap().len(), 1);
+}
+
+#[test]
+fn test_remove_current_combatant_passes_turn() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service.reroll_initiative(encounter_id).unwrap();
+    let first = state.combatants[0].id;
+    let second = state.combatants[1].id;
+
+    let state = service.remove_combatant(first).unwrap();
+    assert_eq!(state.combatants.len(), 2);
+    assert_eq!(state.encounter.current_combatant_id, Some(second));
+}
+
+#[test]
+fn test_delete_encounter_removes_combatants() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let encounter_id = create_manual_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+    service.delete_encounter(encounter_id).unwrap();
+    assert!(service.get_encounter(encounter_id).unwrap().is_none());
+    assert!(service.list_combatants(encounter_id).unwrap().is_empty());
+}
+
+fn start_zombie_encounter(conn: &mut SqliteConnection, temp_dir: &TempDir) -> (i32, i32) {
+    let (_, module_id) = create_test_module(conn, temp_dir);
+    let mut mm_service = ModuleMonsterService::new(conn);
+    mm_service
+        .add_monster(module_id, "Zombie".to_string(), "MM".to_string(), 1, Some("Crypt".to_string()))
+        .unwrap();
+
+    let mut service = CombatService::new(conn);
+    let state = service
+        .start_from_encounter(module_id, Some("Crypt"), false, HpMode::Average)
+        .unwrap();
+    (state.encounter.id, state.combatants[0].id)
+}
+
+#[test]
+fn test_rolled_hp_within_formula_range() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (_, module_id) = create_test_module(&mut conn, &temp_dir);
+
+    let mut mm_service = ModuleMonsterService::new(&mut conn);
+    mm_service
+        .add_monster(module_id, "Ogre".to_string(), "MM".to_string(), 4, Some("Bridge".to_string()))
+        .unwrap();
+
+    let mut service = CombatService::new(&mut conn);
+    let state = service
+        .start_from_encounter(module_id, Some("Bridge"), false, HpMode::Rolled)
+        .unwrap();
+
+    for ogre in &state.combatants {
+        // 7d10 + 21
+        let max_hp = ogre.max_hp.unwrap();
+        assert!((28..=91).contains(&max_hp), "rolled {}", max_hp);
+        assert_eq!(ogre.current_hp, Some(max_hp));
+    }
+}
+
+#[test]
+fn test_damage_applies_monster_defenses() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (_, zombie_id) = start_zombie_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+
+    let event = service.apply_damage(zombie_id, 7, Some("cold"), false).unwrap();
+    assert_eq!(event.defense_enum(), Some(DamageDefense::Resistance));
+    assert_eq!(event.applied_amount, 3);
+    assert_eq!(event.hp_before, Some(22));
+    assert_eq!(event.hp_after, Some(19));
+
+    let event = service.apply_damage(zombie_id, 10, Some("Poison"), false).unwrap();
+    assert_eq!(event.defense_enum(), Some(DamageDefense::Immunity));
+    assert_eq!(event.applied_amount, 0);
+
+    let event = service.apply_damage(zombie_id, 4, Some("radiant"), false).unwrap();
+    assert_eq!(event.defense_enum(), Some(DamageDefense::Vulnerability));
+    assert_eq!(event.applied_amount, 8);
+    assert_eq!(event.hp_after, Some(11));
+
+    // Resistance to slashing only covers nonmagical attacks
+    let event = service.apply_damage(zombie_id, 6, Some("slashing"), false).unwrap();
+    assert_eq!(event.applied_amount, 3);
+    let event = service.apply_damage(zombie_id, 6, Some("slashing"), true).unwrap();
+    assert_eq!(event.defense, None);
+    assert_eq!(event.applied_amount, 6);
+
+    let zombie = service.get_combatant(zombie_id).unwrap().unwrap();
+    assert_eq!(zombie.current_hp, Some(2));
+
+    let event = service.apply_damage(zombie_id, 20, None, false).unwrap();
+    assert_eq!(event.event_type_enum(), HpEventType::Damage);
+    assert_eq!(event.hp_after, Some(0));
+
+    assert_eq!(service.list_hp_events(zombie_id).unwrap().len(), 6);
+}
+
+#[test]
+fn test_temp_hp_and_healing() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (encounter_id, zombie_id) = start_zombie_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+
+    let event = service.grant_temp_hp(zombie_id, 5).unwrap();
+    assert_eq!(event.temp_hp_after, 5);
+
+    // Temp HP doesn't stack
+    let event = service.grant_temp_hp(zombie_id, 3).unwrap();
+    assert_eq!(event.applied_amount, 0);
+    assert_eq!(event.temp_hp_after, 5);
+
+    let event = service.apply_damage(zombie_id, 8, Some("fire"), false).unwrap();
+    assert_eq!(event.temp_hp_before, 5);
+    assert_eq!(event.temp_hp_after, 0);
+    assert_eq!(event.hp_after, Some(19));
+
+    let event = service.apply_healing(zombie_id, 10).unwrap();
+    assert_eq!(event.applied_amount, 3);
+    assert_eq!(event.hp_after, Some(22));
+
+    assert!(service.apply_healing(zombie_id, -1).is_err());
+    assert_eq!(service.list_encounter_hp_events(encounter_id).unwrap().len(), 4);
+}
+
+#[test]
+fn test_conditions_from_catalog() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (encounter_id, zombie_id) = start_zombie_encounter(&mut conn, &temp_dir);
+
+    let mut service = CombatService::new(&mut conn);
+
+    let prone = service.add_condition(zombie_id, "prone", None).unwrap();
+    assert_eq!(prone.condition_name, "Prone");
+    assert_eq!(prone.condition_source, "PHB");
+
+    // Not in the catalog
+    assert!(service.add_condition(zombie_id, "Sparkly", None).is_err());
+    // Zombies are immune to being poisoned
+    assert!(service.add_condition(zombie_id, "Poisoned", None).is_err());
+
+    service.add_condition(zombie_id, "Stunned", Some(1)).unwrap();
+    let state = service.get_combat_state(encounter_id).unwrap();
+    assert_eq!(state.conditions_for(zombie_id).len(), 2);
+
+    // Moving into round 2 expires the one-round stun
+    let state = service.next_turn(encounter_id).unwrap();
+    assert_eq!(state.encounter.round, 2);
+    let names: Vec<&str> = state
+        .conditions
+        .iter()
+        .map(|c| c.condition_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Prone"]);
+
+    service.remove_condition(prone.id).unwrap();
+    assert!(service.list_conditions(zombie_id).unwrap().is_empty());
+}
@@ -0,0 +1,271 @@
+//! Integration tests for the random encounter generator
+
+use diesel::prelude::*;
+use mimir_dm_core::domain::dice::DiceRoller;
+use mimir_dm_core::domain::encounter::Difficulty;
+use mimir_dm_core::models::catalog::monster::MonsterFilters;
+use mimir_dm_core::models::character::CharacterData;
+use mimir_dm_core::services::{
+    CampaignService, CharacterService, EncounterGenerationOptions, EncounterGeneratorService,
+    ModuleMonsterService, ModuleService,
+};
+use mimir_dm_core::{establish_connection, run_migrations};
+use tempfile::TempDir;
+
+fn setup_test_db() -> (SqliteConnection, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db_path = temp_dir.path().join("test.db");
+    let mut conn = establish_connection(db_path.to_str().unwrap()).unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    mimir_dm_core::seed::template_seeder::seed_templates(&mut conn).unwrap();
+    seed_test_monsters(&mut conn);
+    (conn, temp_dir)
+}
+
+fn seed_test_monsters(conn: &mut SqliteConnection) {
+    // name, creature_type, cr, cr_numeric, source, environment
+    let monsters = [
+        (
+            "Goblin",
+            "humanoid",
+            "1/4",
+            0.25,
+            "MM",
+            r#"["forest","hill"]"#,
+        ),
+        ("Bugbear", "humanoid", "1", 1.0, "MM", r#"["forest"]"#),
+        ("Owlbear", "monstrosity", "3", 3.0, "MM", r#"["forest"]"#),
+        ("Troll", "giant", "5", 5.0, "MM", r#"["forest","swamp"]"#),
+        ("Ogre", "giant", "2", 2.0, "MM", r#"["hill"]"#),
+        (
+            "Forest Gnome Warband",
+            "humanoid",
+            "2",
+            2.0,
+            "VGM",
+            r#"["forest"]"#,
+        ),
+    ];
+
+    for (name, creature_type, cr, cr_numeric, source, environment) in monsters {
+        let json = format!(
+            r#"{{"name":"{}","source":"{}","type":"{}","cr":"{}","environment":{}}}"#,
+            name, source, creature_type, cr, environment
+        );
+        diesel::sql_query(
+            "INSERT INTO catalog_monsters (name, creature_type, cr, cr_numeric, source, full_monster_json) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(creature_type)
+        .bind::<diesel::sql_types::Text, _>(cr)
+        .bind::<diesel::sql_types::Double, _>(cr_numeric)
+        .bind::<diesel::sql_types::Text, _>(source)
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+fn create_test_module(conn: &mut SqliteConnection, temp_dir: &TempDir) -> (i32, i32) {
+    let campaign = CampaignService::new(conn)
+        .create_campaign(
+            "Encounter Campaign",
+            None,
+            temp_dir.path().to_str().unwrap(),
+        )
+        .unwrap();
+    let module = ModuleService::new(conn)
+        .create_module(campaign.id, "Into the Woods".to_string(), 4)
+        .unwrap();
+    (campaign.id, module.id)
+}
+
+fn create_test_pc(conn: &mut SqliteConnection, campaign_id: i32, name: &str, level: i32) {
+    let yaml = format!(
+        r#"
+character_name: {name}
+player_id: null
+level: {level}
+version: 1
+snapshot_reason: null
+created_at: "2025-01-01T00:00:00Z"
+race: Human
+subrace: null
+classes:
+  - class_name: Fighter
+    level: {level}
+    subclass: null
+    hit_dice_type: d10
+    hit_dice_remaining: {level}
+background: Soldier
+alignment: null
+abilities:
+  strength: 10
+  dexterity: 10
+  constitution: 10
+  intelligence: 10
+  wisdom: 10
+  charisma: 10
+max_hp: 30
+current_hp: 30
+proficiencies: {{}}
+"#
+    );
+    let data: CharacterData = serde_yaml::from_str(&yaml).unwrap();
+
+    CharacterService::new(conn)
+        .create_character(Some(campaign_id), None, false, "", data)
+        .unwrap();
+}
+
+fn forest_filters() -> MonsterFilters {
+    MonsterFilters {
+        environment: Some(vec!["forest".to_string()]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_generate_hard_forest_encounter() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    let mut service = EncounterGeneratorService::new(&mut conn);
+
+    let options = EncounterGenerationOptions::new(Difficulty::Hard)
+        .with_party(vec![5, 5, 5, 5])
+        .with_filters(forest_filters());
+
+    for seed in 0..5 {
+        let generated = service
+            .generate(&options, &mut DiceRoller::seeded(seed))
+            .expect("Generation should succeed");
+
+        assert_eq!(generated.requested, Difficulty::Hard);
+        assert_eq!(generated.target_min_xp, 3000);
+        assert_eq!(generated.target_max_xp, 4399);
+        assert_eq!(generated.encounter.difficulty, Some(Difficulty::Hard));
+        assert!(generated.encounter.adjusted_xp >= 3000 && generated.encounter.adjusted_xp <= 4399);
+        assert!(generated.encounter.monster_count <= 12);
+        assert!(generated.encounter.monsters.len() <= 3);
+        for monster in &generated.encounter.monsters {
+            assert_ne!(monster.name, "Ogre", "Ogres don't live in forests");
+        }
+    }
+}
+
+#[test]
+fn test_generate_respects_cr_and_type_filters() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    let mut service = EncounterGeneratorService::new(&mut conn);
+
+    let options = EncounterGenerationOptions::new(Difficulty::Medium)
+        .with_party(vec![3, 3, 3, 3])
+        .with_filters(MonsterFilters {
+            creature_types: Some(vec!["humanoid".to_string()]),
+            max_cr: Some(1.0),
+            ..Default::default()
+        });
+
+    let generated = service
+        .generate(&options, &mut DiceRoller::seeded(3))
+        .expect("Generation should succeed");
+
+    for monster in &generated.encounter.monsters {
+        assert!(monster.name == "Goblin" || monster.name == "Bugbear");
+    }
+}
+
+#[test]
+fn test_generate_without_matching_monsters_fails() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    let mut service = EncounterGeneratorService::new(&mut conn);
+
+    let options = EncounterGenerationOptions::new(Difficulty::Easy)
+        .with_party(vec![1, 1, 1, 1])
+        .with_filters(MonsterFilters {
+            environment: Some(vec!["arctic".to_string()]),
+            ..Default::default()
+        });
+    assert!(service
+        .generate(&options, &mut DiceRoller::seeded(1))
+        .is_err());
+
+    let no_party = EncounterGenerationOptions::new(Difficulty::Easy);
+    assert!(service
+        .generate(&no_party, &mut DiceRoller::seeded(1))
+        .is_err());
+}
+
+#[test]
+fn test_generate_for_campaign_uses_enabled_sources_and_party() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (campaign_id, _) = create_test_module(&mut conn, &temp_dir);
+    for name in ["Aria", "Brom", "Cass"] {
+        create_test_pc(&mut conn, campaign_id, name, 4);
+    }
+    CampaignService::new(&mut conn)
+        .set_enabled_sources(campaign_id, &["MM".to_string()])
+        .unwrap();
+
+    let mut service = EncounterGeneratorService::new(&mut conn);
+    let options =
+        EncounterGenerationOptions::new(Difficulty::Medium).with_filters(forest_filters());
+
+    for seed in 0..5 {
+        let generated = service
+            .generate_for_campaign(campaign_id, &options, &mut DiceRoller::seeded(seed))
+            .expect("Generation should succeed");
+        assert_eq!(generated.encounter.party_levels, vec![4, 4, 4]);
+        assert!(generated
+            .encounter
+            .monsters
+            .iter()
+            .all(|m| m.source == "MM"));
+    }
+
+    // Asking only for a source the campaign doesn't use is an error
+    let vgm_only =
+        EncounterGenerationOptions::new(Difficulty::Medium).with_filters(MonsterFilters {
+            sources: Some(vec!["VGM".to_string()]),
+            ..Default::default()
+        });
+    assert!(service
+        .generate_for_campaign(campaign_id, &vgm_only, &mut DiceRoller::seeded(1))
+        .is_err());
+}
+
+#[test]
+fn test_generate_for_module_adds_encounter_group() {
+    let (mut conn, temp_dir) = setup_test_db();
+    let (campaign_id, module_id) = create_test_module(&mut conn, &temp_dir);
+    for name in ["Aria", "Brom", "Cass", "Dax"] {
+        create_test_pc(&mut conn, campaign_id, name, 3);
+    }
+
+    let options = EncounterGenerationOptions::new(Difficulty::Easy).with_filters(forest_filters());
+    let generated = EncounterGeneratorService::new(&mut conn)
+        .generate_for_module(
+            module_id,
+            "Forest Ambush",
+            &options,
+            &mut DiceRoller::seeded(7),
+        )
+        .expect("Generation should succeed");
+    assert_eq!(generated.encounter_tag.as_deref(), Some("Forest Ambush"));
+
+    let groups = ModuleMonsterService::new(&mut conn)
+        .get_monsters_grouped_by_encounter(module_id)
+        .unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].encounter_tag.as_deref(), Some("Forest Ambush"));
+    let saved: i32 = groups[0].monsters.iter().map(|m| m.quantity).sum();
+    assert_eq!(saved, generated.encounter.monster_count);
+
+    // The tag must be new
+    let again = EncounterGeneratorService::new(&mut conn).generate_for_module(
+        module_id,
+        "Forest Ambush",
+        &options,
+        &mut DiceRoller::seeded(8),
+    );
+    assert!(again.is_err());
+}
@@ -0,0 +1,168 @@
+//! Integration tests for placing module encounters on maps
+
+use diesel::prelude::*;
+use mimir_dm_core::domain::{Formation, Point};
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{GridType, NewMap, TokenSize, TokenType, VisionType};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{
+    CampaignService, MapService, ModuleMonsterService, ModuleService, TokenService,
+};
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    mimir_dm_core::seed::template_seeder::seed_templates(&mut conn).unwrap();
+    conn
+}
+
+fn seed_catalog_monsters(conn: &mut mimir_dm_core::connection::DbConnection) {
+    let monsters = [
+        (
+            "Goblin",
+            r#"{"name":"Goblin","source":"MM","size":["S"],"senses":["darkvision 60 ft."]}"#,
+        ),
+        (
+            "Owlbear",
+            r#"{"name":"Owlbear","source":"MM","size":["L"],"senses":["darkvision 60 ft."]}"#,
+        ),
+        (
+            "Giant Bat",
+            r#"{"name":"Giant Bat","source":"MM","size":["L"],"senses":["blindsight 60 ft."]}"#,
+        ),
+    ];
+
+    for (name, json) in monsters {
+        diesel::sql_query(
+            "INSERT INTO catalog_monsters (name, source, full_monster_json) VALUES (?, 'MM', ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+/// A module with a "Cave" encounter, and a square-grid map (50px squares) in it
+fn create_module_map(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> i32 {
+    seed_catalog_monsters(conn);
+    let campaign_id = CampaignService::new(conn)
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id;
+    let module_id = ModuleService::new(conn)
+        .create_module(campaign_id, "Test Module".to_string(), 4)
+        .unwrap()
+        .id;
+
+    let mut monsters = ModuleMonsterService::new(conn);
+    let cave = Some("Cave".to_string());
+    monsters
+        .add_monster(module_id, "Goblin".into(), "MM".into(), 3, cave.clone())
+        .unwrap();
+    monsters
+        .add_monster(module_id, "Owlbear".into(), "MM".into(), 1, cave)
+        .unwrap();
+    monsters
+        .add_monster(module_id, "Giant Bat".into(), "MM".into(), 2, None)
+        .unwrap();
+
+    MapService::new(conn)
+        .create_map(
+            NewMap::new(
+                campaign_id,
+                "Cave".to_string(),
+                "cave.png".to_string(),
+                1000,
+                1000,
+                1000,
+                1000,
+            )
+            .with_module(module_id)
+            .with_grid(GridType::Square, 50, 0, 0),
+        )
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_place_encounter_creates_hidden_monster_tokens() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_module_map(&mut conn, &dir);
+
+    let tokens = TokenService::new(&mut conn)
+        .place_encounter(
+            map_id,
+            Some("Cave"),
+            Point::new(500.0, 500.0),
+            Formation::Cluster,
+        )
+        .unwrap();
+
+    let names: Vec<&str> = tokens.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Goblin 1", "Goblin 2", "Goblin 3", "Owlbear"]);
+    assert!(tokens.iter().all(|t| !t.visible_to_players
+        && t.monster_id.is_some()
+        && t.token_type_enum() == TokenType::Monster
+        && t.vision_type_enum() == VisionType::Darkvision
+        && t.vision_range_ft == Some(60.0)));
+    assert_eq!(tokens[0].size_enum(), TokenSize::Small);
+    assert_eq!(tokens[3].size_enum(), TokenSize::Large);
+    assert_ne!(tokens[0].monster_id, tokens[3].monster_id);
+
+    // Tokens sit on the grid without overlapping
+    for (i, a) in tokens.iter().enumerate() {
+        assert_eq!((a.x % 25.0, a.y % 25.0), (0.0, 0.0));
+        for b in &tokens[i + 1..] {
+            assert!((a.x - b.x).abs() >= 50.0 || (a.y - b.y).abs() >= 50.0);
+        }
+    }
+    assert_eq!(
+        TokenService::new(&mut conn)
+            .count_tokens_for_map(map_id)
+            .unwrap(),
+        4
+    );
+}
+
+#[test]
+fn test_place_untagged_monsters_in_a_ring() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_module_map(&mut conn, &dir);
+
+    let center = Point::new(300.0, 300.0);
+    let tokens = TokenService::new(&mut conn)
+        .place_encounter(map_id, None, center, Formation::Ring)
+        .unwrap();
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].name, "Giant Bat 1");
+    assert_eq!(tokens[0].vision_type_enum(), VisionType::Blindsight);
+    let distance =
+        |t: &mimir_dm_core::models::campaign::Token| Point::new(t.x, t.y).distance_to(center);
+    assert!((distance(&tokens[0]) - distance(&tokens[1])).abs() < 0.01);
+}
+
+#[test]
+fn test_place_encounter_errors() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_module_map(&mut conn, &dir);
+    let mut service = TokenService::new(&mut conn);
+
+    assert!(service
+        .place_encounter(
+            map_id,
+            Some("Throne Room"),
+            Point::new(0.0, 0.0),
+            Formation::Line
+        )
+        .is_err());
+    assert!(service
+        .place_encounter(999, Some("Cave"), Point::new(0.0, 0.0), Formation::Line)
+        .is_err());
+    assert_eq!(service.count_tokens_for_map(map_id).unwrap(), 0);
+}
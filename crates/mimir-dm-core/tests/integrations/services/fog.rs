@@ -0,0 +1,182 @@
+//! Integration tests for fog of war revealed and explored areas
+
+use mimir_dm_core::domain::Point;
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{
+    AmbientLight, FogAreaShape, FogAreaSource, GridType, NewMap, NewMapWall, NewToken, TokenType,
+    VisionType,
+};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{
+    CampaignService, FogOfWarService, MapService, TokenService, WallService,
+};
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+/// A dark 1000x1000 map (5 ft squares of 50px) with a wall at x = 500
+/// that has a gap from y = 400 to 600
+fn create_cave(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> i32 {
+    let campaign_id = CampaignService::new(conn)
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id;
+
+    let map = MapService::new(conn)
+        .create_map(
+            NewMap::new(
+                campaign_id,
+                "Cave".to_string(),
+                "cave.png".to_string(),
+                1000,
+                1000,
+                1000,
+                1000,
+            )
+            .with_grid(GridType::Square, 50, 0, 0)
+            .with_ambient_light(AmbientLight::Darkness),
+        )
+        .unwrap();
+
+    let mut walls = WallService::new(conn);
+    walls
+        .create_wall(NewMapWall::new(map.id, 500.0, 0.0, 500.0, 400.0))
+        .unwrap();
+    walls
+        .create_wall(NewMapWall::new(map.id, 500.0, 600.0, 500.0, 1000.0))
+        .unwrap();
+
+    map.id
+}
+
+#[test]
+fn test_reveal_shapes() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_cave(&mut conn, &dir);
+    let mut fog = FogOfWarService::new(&mut conn);
+
+    let circle = fog.reveal_circle(map_id, 100.0, 100.0, 50.0).unwrap();
+    assert_eq!(circle.shape_enum(), FogAreaShape::Circle);
+    assert!(circle.contains_point(130.0, 130.0));
+    assert!(!circle.contains_point(145.0, 145.0));
+
+    // An L-shaped passage
+    let passage = [
+        Point::new(0.0, 0.0),
+        Point::new(300.0, 0.0),
+        Point::new(300.0, 100.0),
+        Point::new(100.0, 100.0),
+        Point::new(100.0, 300.0),
+        Point::new(0.0, 300.0),
+    ];
+    let polygon = fog.reveal_polygon(map_id, &passage).unwrap();
+    assert_eq!(polygon.shape_enum(), FogAreaShape::Polygon);
+    assert_eq!((polygon.width, polygon.height), (300.0, 300.0));
+    assert!(polygon.contains_point(50.0, 250.0));
+    assert!(!polygon.contains_point(200.0, 200.0));
+    assert!(polygon.overlaps(150.0, 150.0, 10.0, 10.0));
+
+    assert!(fog.reveal_polygon(map_id, &passage[..2]).is_err());
+
+    let summaries = fog.get_revealed_area_summaries(map_id).unwrap();
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(summaries[1].points.as_deref(), Some(&passage[..]));
+    assert!(summaries.iter().all(|a| a.source == FogAreaSource::Manual));
+}
+
+#[test]
+fn test_exploration_accumulates_until_reset() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_cave(&mut conn, &dir);
+    let mut tokens = TokenService::new(&mut conn);
+    let dwarf = tokens
+        .create_token(
+            NewToken::new(map_id, "Dwarf".to_string(), 250.0, 500.0)
+                .with_type(TokenType::PC)
+                .with_vision(VisionType::Darkvision, Some(30.0)),
+        )
+        .unwrap();
+    // Monsters do not explore
+    tokens
+        .create_token(
+            NewToken::new(map_id, "Grick".to_string(), 750.0, 100.0)
+                .with_vision(VisionType::Darkvision, Some(60.0)),
+        )
+        .unwrap();
+
+    let mut fog = FogOfWarService::new(&mut conn);
+    fog.reveal_rect(map_id, 0.0, 0.0, 100.0, 100.0).unwrap();
+    assert_eq!(fog.record_explored(map_id).unwrap().len(), 1);
+    // Standing still records nothing new
+    assert!(fog.record_explored(map_id).unwrap().is_empty());
+
+    TokenService::new(&mut conn)
+        .update_token_position(dwarf.id, 750.0, 500.0)
+        .unwrap();
+    let mut fog = FogOfWarService::new(&mut conn);
+    assert_eq!(fog.record_explored(map_id).unwrap().len(), 1);
+
+    // Where the dwarf has been stays explored, and is kept apart from reveals
+    let explored = fog.get_explored_areas(map_id).unwrap();
+    assert_eq!(explored.len(), 2);
+    assert!(explored
+        .iter()
+        .all(|a| a.source_enum() == FogAreaSource::Explored));
+    assert!(explored.iter().any(|a| a.contains_point(300.0, 500.0)));
+    assert!(explored.iter().any(|a| a.contains_point(700.0, 500.0)));
+    assert!(!explored.iter().any(|a| a.contains_point(750.0, 100.0)));
+    assert_eq!(fog.count_revealed_areas(map_id).unwrap(), 1);
+
+    assert_eq!(fog.reset_fog(map_id).unwrap(), 3);
+    assert!(fog.get_explored_areas(map_id).unwrap().is_empty());
+    assert!(fog.get_revealed_areas(map_id).unwrap().is_empty());
+}
+
+#[test]
+fn test_moving_over_explored_ground_adds_no_rows() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let map_id = create_cave(&mut conn, &dir);
+    let dwarf = TokenService::new(&mut conn)
+        .create_token(
+            NewToken::new(map_id, "Dwarf".to_string(), 250.0, 500.0)
+                .with_type(TokenType::PC)
+                .with_vision(VisionType::Darkvision, Some(30.0)),
+        )
+        .unwrap();
+
+    let mut explored_rows = Vec::new();
+    for x in [250.0, 300.0, 250.0, 300.0, 275.0, 260.0, 300.0] {
+        TokenService::new(&mut conn)
+            .update_token_position(dwarf.id, x, 500.0)
+            .unwrap();
+        let mut fog = FogOfWarService::new(&mut conn);
+        fog.record_explored(map_id).unwrap();
+        explored_rows.push(fog.get_explored_areas(map_id).unwrap().len());
+    }
+    // The second spot sees further east through the gap; pacing back and
+    // forth between the two after that is already explored
+    assert_eq!(explored_rows, vec![1, 2, 2, 2, 2, 2, 2]);
+
+    // A wider view replaces the areas it covers
+    TokenService::new(&mut conn)
+        .update_token(
+            dwarf.id,
+            mimir_dm_core::models::campaign::UpdateToken {
+                vision_range_ft: Some(Some(90.0)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let mut fog = FogOfWarService::new(&mut conn);
+    assert_eq!(fog.record_explored(map_id).unwrap().len(), 1);
+    let explored = fog.get_explored_areas(map_id).unwrap();
+    assert_eq!(explored.len(), 1);
+    assert!(explored[0].contains_point(260.0, 250.0));
+}
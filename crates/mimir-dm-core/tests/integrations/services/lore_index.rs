@@ -0,0 +1,293 @@
+//! Integration tests for the lore index service
+
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{
+    EmbeddedChunk, LoreSearchFilter, LoreSourceType, NewLoreSource,
+};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{CampaignService, LoreIndexService};
+use tempfile::TempDir;
+
+const MODEL: &str = "test-embed";
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+fn chunk(heading: &str, content: &str, embedding: [f32; 3]) -> EmbeddedChunk {
+    EmbeddedChunk {
+        heading: Some(heading.to_string()),
+        content: content.to_string(),
+        embedding: embedding.to_vec(),
+    }
+}
+
+fn book(key: &str, title: &str) -> NewLoreSource {
+    NewLoreSource::new(LoreSourceType::Book, key, None, title, "hash-1")
+}
+
+#[test]
+fn test_store_and_search_nearest_chunks() {
+    let mut conn = setup_test_db();
+    let mut service = LoreIndexService::new(&mut conn);
+
+    // Nothing indexed yet
+    assert!(service.index_info().unwrap().is_none());
+    assert!(service
+        .search(&[1.0, 0.0, 0.0], 5, &LoreSearchFilter::default())
+        .unwrap()
+        .is_empty());
+
+    let source = service
+        .store_source(
+            book("PHB", "Player's Handbook"),
+            vec![
+                chunk("Fireball", "A bright streak flashes...", [1.0, 0.0, 0.0]),
+                chunk("Cure Wounds", "A creature you touch...", [0.0, 1.0, 0.0]),
+                chunk("Shield", "An invisible barrier...", [0.0, 0.0, 1.0]),
+            ],
+            MODEL,
+        )
+        .unwrap();
+    assert_eq!(source.chunk_count, 3);
+
+    let info = service.index_info().unwrap().unwrap();
+    assert_eq!(info.embedding_model, MODEL);
+    assert_eq!(info.dimensions, 3);
+
+    let results = service
+        .search(&[0.1, 0.9, 0.0], 2, &LoreSearchFilter::default())
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].heading.as_deref(), Some("Cure Wounds"));
+    assert_eq!(results[0].source_key, "PHB");
+    assert_eq!(results[0].title, "Player's Handbook");
+    assert!(results[0].distance <= results[1].distance);
+
+    // Wrong query width is rejected
+    assert!(service
+        .search(&[1.0, 0.0], 2, &LoreSearchFilter::default())
+        .is_err());
+}
+
+#[test]
+fn test_reindex_replaces_chunks_and_rejects_other_models() {
+    let mut conn = setup_test_db();
+    let mut service = LoreIndexService::new(&mut conn);
+
+    service
+        .store_source(
+            book("DMG", "Dungeon Master's Guide"),
+            vec![chunk("Traps", "Old trap text", [1.0, 0.0, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+
+    let hash = LoreIndexService::content_hash("new text");
+    assert!(!service
+        .is_current(LoreSourceType::Book, "DMG", &hash)
+        .unwrap());
+
+    let mut updated = book("DMG", "Dungeon Master's Guide");
+    updated.content_hash = hash.clone();
+    service
+        .store_source(
+            updated,
+            vec![
+                chunk("Traps", "New trap text", [1.0, 0.0, 0.0]),
+                chunk("Poisons", "Poison text", [0.0, 1.0, 0.0]),
+            ],
+            MODEL,
+        )
+        .unwrap();
+    assert!(service
+        .is_current(LoreSourceType::Book, "DMG", &hash)
+        .unwrap());
+
+    let results = service
+        .search(&[1.0, 0.0, 0.0], 10, &LoreSearchFilter::default())
+        .unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].content, "New trap text");
+
+    // A different model or width can't be mixed into the index
+    assert!(service
+        .store_source(
+            book("MM", "Monster Manual"),
+            vec![chunk("Goblin", "Goblin text", [1.0, 0.0, 0.0])],
+            "other-model",
+        )
+        .is_err());
+    assert!(service
+        .store_source(
+            book("MM", "Monster Manual"),
+            vec![EmbeddedChunk {
+                heading: None,
+                content: "Goblin text".to_string(),
+                embedding: vec![1.0, 0.0],
+            }],
+            MODEL,
+        )
+        .is_err());
+
+    assert!(service.remove_source(LoreSourceType::Book, "DMG").unwrap());
+    assert!(!service.remove_source(LoreSourceType::Book, "DMG").unwrap());
+    assert!(service
+        .search(&[1.0, 0.0, 0.0], 10, &LoreSearchFilter::default())
+        .unwrap()
+        .is_empty());
+
+    // Resetting allows a new model
+    service.reset_index().unwrap();
+    assert!(service.index_info().unwrap().is_none());
+    service
+        .store_source(
+            book("MM", "Monster Manual"),
+            vec![EmbeddedChunk {
+                heading: None,
+                content: "Goblin text".to_string(),
+                embedding: vec![1.0, 0.0],
+            }],
+            "other-model",
+        )
+        .unwrap();
+    assert_eq!(service.index_info().unwrap().unwrap().dimensions, 2);
+}
+
+#[test]
+fn test_search_filters_by_campaign_and_source_type() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().to_string_lossy().to_string();
+    let mut campaigns = CampaignService::new(&mut conn);
+    let ours = campaigns.create_campaign("Ours", None, &dir).unwrap().id;
+    let theirs = campaigns
+        .create_campaign("Theirs", None, &format!("{}/other", dir))
+        .unwrap()
+        .id;
+
+    let mut service = LoreIndexService::new(&mut conn);
+    service
+        .store_source(
+            book("PHB", "Player's Handbook"),
+            vec![chunk("Dragons", "Book dragons", [1.0, 0.0, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+    service
+        .store_source(
+            NewLoreSource::new(LoreSourceType::Document, "1", Some(ours), "Our Notes", "h"),
+            vec![chunk("Dragon", "Our dragon", [0.9, 0.1, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+    service
+        .store_source(
+            NewLoreSource::new(
+                LoreSourceType::Document,
+                "2",
+                Some(theirs),
+                "Their Notes",
+                "h",
+            ),
+            vec![chunk("Dragon", "Their dragon", [0.95, 0.05, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+
+    let query = [1.0, 0.0, 0.0];
+    let all = service
+        .search(&query, 10, &LoreSearchFilter::default())
+        .unwrap();
+    assert_eq!(all.len(), 3);
+
+    let campaign_only = service
+        .search(
+            &query,
+            10,
+            &LoreSearchFilter {
+                campaign_id: Some(ours),
+                source_type: None,
+            },
+        )
+        .unwrap();
+    let contents: Vec<&str> = campaign_only.iter().map(|r| r.content.as_str()).collect();
+    assert_eq!(contents, vec!["Book dragons", "Our dragon"]);
+
+    let documents = service
+        .search(
+            &query,
+            10,
+            &LoreSearchFilter {
+                campaign_id: Some(ours),
+                source_type: Some(LoreSourceType::Document),
+            },
+        )
+        .unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].campaign_id, Some(ours));
+
+    assert_eq!(service.list_sources(Some(ours)).unwrap().len(), 2);
+    assert_eq!(service.list_sources(None).unwrap().len(), 3);
+}
+
+#[test]
+fn test_deleted_campaign_vectors_leave_search() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let dir = temp_dir.path().to_string_lossy().to_string();
+    let campaign_id = CampaignService::new(&mut conn)
+        .create_campaign("Doomed", None, &dir)
+        .unwrap()
+        .id;
+
+    let mut service = LoreIndexService::new(&mut conn);
+    service
+        .store_source(
+            book("PHB", "Player's Handbook"),
+            vec![chunk("Dragons", "Book dragons", [0.0, 1.0, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+    service
+        .store_source(
+            NewLoreSource::new(
+                LoreSourceType::Document,
+                "1",
+                Some(campaign_id),
+                "Notes",
+                "h",
+            ),
+            vec![chunk("Dragon", "Campaign dragon", [1.0, 0.0, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+
+    let mut campaigns = CampaignService::new(&mut conn);
+    campaigns.archive_campaign(campaign_id).unwrap();
+    campaigns.delete_campaign(campaign_id, false).unwrap();
+
+    // The deleted campaign's vector no longer takes the only nearest place
+    let mut service = LoreIndexService::new(&mut conn);
+    let results = service
+        .search(&[1.0, 0.0, 0.0], 1, &LoreSearchFilter::default())
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].content, "Book dragons");
+
+    // Reusing the freed chunk id doesn't collide with a leftover vector
+    service
+        .store_source(
+            book("MM", "Monster Manual"),
+            vec![chunk("Goblins", "Book goblins", [1.0, 0.0, 0.0])],
+            MODEL,
+        )
+        .unwrap();
+    let results = service
+        .search(&[1.0, 0.0, 0.0], 2, &LoreSearchFilter::default())
+        .unwrap();
+    let contents: Vec<&str> = results.iter().map(|r| r.content.as_str()).collect();
+    assert_eq!(contents, vec!["Book goblins", "Book dragons"]);
+}
@@ -0,0 +1,172 @@
+//! Integration tests for distance measurement and area of effect templates
+
+use diesel::prelude::*;
+use mimir_dm_core::error::DbError;
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{
+    DiagonalRule, GridType, NewMap, NewMapTemplate, NewToken, TemplateShape, TokenSize,
+};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{CampaignService, MapService, MapTemplateService, TokenService};
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+fn seed_catalog_spells(conn: &mut mimir_dm_core::connection::DbConnection) {
+    let spells = [
+        (
+            "Fireball",
+            r#"{"name":"Fireball","source":"PHB","level":3,"school":"V","time":[{"number":1,"unit":"action"}],"range":{"type":"point","distance":{"type":"feet","amount":150}},"components":{"v":true,"s":true},"duration":[{"type":"instant"}],"entries":["A bright streak flashes from your pointing finger to a point you choose within range and then blossoms with a low roar into an explosion of flame. Each creature in a 20-foot-radius sphere centered on that point must make a Dexterity saving throw."]}"#,
+        ),
+        (
+            "Magic Missile",
+            r#"{"name":"Magic Missile","source":"PHB","level":1,"school":"V","time":[{"number":1,"unit":"action"}],"range":{"type":"point","distance":{"type":"feet","amount":120}},"components":{"v":true,"s":true},"duration":[{"type":"instant"}],"entries":["You create three glowing darts of magical force."]}"#,
+        ),
+    ];
+
+    for (name, json) in spells {
+        diesel::sql_query(
+            "INSERT INTO catalog_spells (name, level, school, cast_time, range, components, tags, source, full_spell_json) \
+             VALUES (?, 1, 'Evocation', '1 action', '150 feet', 'V, S', '[]', 'PHB', ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+/// A 2000x2000 map with 50px squares; returns (campaign id, map id)
+fn create_map(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> (i32, i32) {
+    let campaign_id = CampaignService::new(conn)
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id;
+    let map = MapService::new(conn)
+        .create_map(
+            NewMap::new(
+                campaign_id,
+                "Battlefield".to_string(),
+                "battlefield.png".to_string(),
+                2000,
+                2000,
+                2000,
+                2000,
+            )
+            .with_grid(GridType::Square, 50, 0, 0),
+        )
+        .unwrap();
+    (campaign_id, map.id)
+}
+
+#[test]
+fn test_token_distance_follows_campaign_rule() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let (campaign_id, map_id) = create_map(&mut conn, &dir);
+
+    let mut tokens = TokenService::new(&mut conn);
+    let fighter = tokens
+        .create_token(NewToken::new(map_id, "Fighter".to_string(), 25.0, 25.0))
+        .unwrap();
+    // Four squares diagonally away
+    let orc = tokens
+        .create_token(NewToken::new(map_id, "Orc".to_string(), 225.0, 225.0))
+        .unwrap();
+    assert_eq!(tokens.distance_between(fighter.id, orc.id).unwrap(), 30.0);
+
+    let campaign = CampaignService::new(&mut conn)
+        .set_diagonal_rule(campaign_id, DiagonalRule::Euclidean)
+        .unwrap();
+    assert_eq!(campaign.diagonal_rule_enum(), DiagonalRule::Euclidean);
+    let euclidean = TokenService::new(&mut conn)
+        .distance_between(fighter.id, orc.id)
+        .unwrap();
+    assert!((euclidean - 28.28).abs() < 0.01);
+
+    assert!(matches!(
+        TokenService::new(&mut conn).distance_between(fighter.id, 999),
+        Err(DbError::NotFound { .. })
+    ));
+}
+
+#[test]
+fn test_spell_template_covers_tokens() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    seed_catalog_spells(&mut conn);
+    let (_, map_id) = create_map(&mut conn, &dir);
+
+    let mut tokens = TokenService::new(&mut conn);
+    for (name, x, y, size) in [
+        ("Goblin", 525.0, 675.0, TokenSize::Small),
+        ("Hobgoblin", 875.0, 525.0, TokenSize::Medium),
+        // Its center is out of reach, but its nearest square is inside
+        ("Ogre", 700.0, 550.0, TokenSize::Large),
+        ("Wolf", 500.0, 775.0, TokenSize::Medium),
+    ] {
+        tokens
+            .create_token(NewToken::new(map_id, name.to_string(), x, y).with_size(size))
+            .unwrap();
+    }
+
+    let mut templates = MapTemplateService::new(&mut conn);
+    let fireball = templates
+        .create_spell_template(map_id, "Fireball", "PHB", 500.0, 500.0, 0.0)
+        .unwrap();
+    assert_eq!(fireball.shape_enum(), TemplateShape::Sphere);
+    assert_eq!(fireball.size_ft, 20.0);
+    assert_eq!(fireball.spell_name.as_deref(), Some("Fireball"));
+
+    let names: Vec<String> = templates
+        .get_tokens_in_template(fireball.id)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["Goblin", "Ogre"]);
+
+    // A cone pointing west misses everyone; turned south it catches the
+    // wolf and the goblin in front of it
+    let cone = templates
+        .create_template(
+            NewMapTemplate::new(
+                map_id,
+                "Cone of Cold".to_string(),
+                TemplateShape::Cone,
+                500.0,
+                500.0,
+                30.0,
+            )
+            .with_direction(180.0),
+        )
+        .unwrap();
+    assert!(templates
+        .get_tokens_in_template(cone.id)
+        .unwrap()
+        .is_empty());
+    templates
+        .move_template(cone.id, 500.0, 500.0, 90.0)
+        .unwrap();
+    let caught = templates.get_tokens_in_template(cone.id).unwrap();
+    assert_eq!(caught.len(), 2);
+    assert!(caught
+        .iter()
+        .all(|t| t.name == "Goblin" || t.name == "Wolf"));
+
+    assert!(matches!(
+        templates.create_spell_template(map_id, "Magic Missile", "PHB", 0.0, 0.0, 0.0),
+        Err(DbError::InvalidData(_))
+    ));
+    assert!(matches!(
+        templates.create_spell_template(map_id, "Wish", "PHB", 0.0, 0.0, 0.0),
+        Err(DbError::NotFound { .. })
+    ));
+
+    assert_eq!(templates.get_templates_for_map(map_id).unwrap().len(), 2);
+    assert_eq!(templates.delete_all_for_map(map_id).unwrap(), 2);
+}
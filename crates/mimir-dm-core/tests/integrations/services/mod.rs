@@ -1,15 +1,22 @@
 mod action;
 mod background;
 mod campaigns;
+mod catalog_search;
 mod character;
 mod class;
+mod combat;
 mod document;
 mod condition;
 mod cult;
 mod deity;
+mod encounter_generator;
+mod encounter_tokens;
 mod feat;
+mod fog;
 mod item;
 mod language;
+mod lore_index;
+mod map_templates;
 mod module_monsters;
 mod modules;
 mod monster;
@@ -19,8 +26,12 @@ mod player;
 mod psionic;
 mod race;
 mod reward;
+mod session;
 mod spell;
 mod table;
+mod tool_journal;
 mod trap;
+mod uvtt_import;
 mod variant_rule;
 mod vehicle;
+mod visibility;
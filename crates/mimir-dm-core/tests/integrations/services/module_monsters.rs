@@ -1,8 +1,13 @@
 //! Integration tests for module monster service
 
+use diesel::prelude::*;
+use mimir_dm_core::domain::encounter::Difficulty;
 use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::character::CharacterData;
 use mimir_dm_core::run_migrations;
-use mimir_dm_core::services::{CampaignService, ModuleMonsterService, ModuleService};
+use mimir_dm_core::services::{
+    CampaignService, CharacterService, ModuleMonsterService, ModuleService,
+};
 use tempfile::TempDir;
 
 fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
@@ -469,3 +474,169 @@ fn test_monsters_isolated_between_modules() {
     assert_eq!(module2_monsters.len(), 1);
     assert_eq!(module2_monsters[0].monster_name, "Dragon");
 }
+
+fn seed_encounter_monsters(conn: &mut mimir_dm_core::connection::DbConnection) {
+    let monsters = [
+        (
+            "Bugbear",
+            "1",
+            r#"{"name":"Bugbear","source":"MM","cr":"1"}"#,
+        ),
+        (
+            "Hobgoblin",
+            "1/2",
+            r#"{"name":"Hobgoblin","source":"MM","cr":"1/2"}"#,
+        ),
+    ];
+
+    for (name, cr, json) in monsters {
+        diesel::sql_query(
+            "INSERT INTO catalog_monsters (name, cr, source, full_monster_json) VALUES (?, ?, 'MM', ?)",
+        )
+        .bind::<diesel::sql_types::Text, _>(name)
+        .bind::<diesel::sql_types::Text, _>(cr)
+        .bind::<diesel::sql_types::Text, _>(json)
+        .execute(conn)
+        .unwrap();
+    }
+}
+
+fn create_test_pc(
+    conn: &mut mimir_dm_core::connection::DbConnection,
+    campaign_id: i32,
+    name: &str,
+    classes: &[(&str, i32)],
+) {
+    let class_yaml: String = classes
+        .iter()
+        .map(|(class, level)| {
+            format!(
+                "  - class_name: {class}\n    level: {level}\n    subclass: null\n    hit_dice_type: d8\n    hit_dice_remaining: {level}\n"
+            )
+        })
+        .collect();
+    let total_level: i32 = classes.iter().map(|(_, level)| level).sum();
+    let yaml = format!(
+        r#"
+character_name: {name}
+player_id: null
+level: {total_level}
+version: 1
+snapshot_reason: null
+created_at: "2025-01-01T00:00:00Z"
+race: Human
+subrace: null
+classes:
+{class_yaml}background: Soldier
+alignment: null
+abilities:
+  strength: 10
+  dexterity: 10
+  constitution: 10
+  intelligence: 10
+  wisdom: 10
+  charisma: 10
+max_hp: 20
+current_hp: 20
+proficiencies: {{}}
+"#
+    );
+    let data: CharacterData = serde_yaml::from_str(&yaml).unwrap();
+
+    CharacterService::new(conn)
+        .create_character(Some(campaign_id), None, false, "", data)
+        .unwrap();
+}
+
+#[test]
+fn test_encounter_difficulty_for_party() {
+    let mut conn = setup_test_db();
+    seed_encounter_monsters(&mut conn);
+    let (campaign_id, module_id) = create_test_module(&mut conn);
+    create_test_pc(&mut conn, campaign_id, "Fighter", &[("Fighter", 3)]);
+    create_test_pc(&mut conn, campaign_id, "Multiclass", &[("Fighter", 2), ("Wizard", 1)]);
+
+    let mut service = ModuleMonsterService::new(&mut conn);
+    let tag = Some("Ambush".to_string());
+    service
+        .add_monster(module_id, "Bugbear".to_string(), "MM".to_string(), 1, tag.clone())
+        .unwrap();
+    service
+        .add_monster(module_id, "Hobgoblin".to_string(), "MM".to_string(), 3, tag)
+        .unwrap();
+
+    let difficulty = service
+        .get_encounter_difficulty(module_id, Some("Ambush"))
+        .unwrap();
+
+    assert_eq!(difficulty.party_levels, vec![3, 3]);
+    assert_eq!(difficulty.thresholds.deadly, 800);
+    assert_eq!(difficulty.monster_count, 4);
+    assert_eq!(difficulty.base_xp, 500);
+    // Four monsters is x2, stepped up to x2.5 for a party of two
+    assert_eq!(difficulty.multiplier, 2.5);
+    assert_eq!(difficulty.adjusted_xp, 1250);
+    assert_eq!(difficulty.difficulty, Some(Difficulty::Deadly));
+    assert_eq!(difficulty.xp_per_character, 250);
+}
+
+#[test]
+fn test_encounter_difficulties_per_group() {
+    let mut conn = setup_test_db();
+    seed_encounter_monsters(&mut conn);
+    let (campaign_id, module_id) = create_test_module(&mut conn);
+    for name in ["A", "B", "C", "D"] {
+        create_test_pc(&mut conn, campaign_id, name, &[("Fighter", 1)]);
+    }
+
+    let mut service = ModuleMonsterService::new(&mut conn);
+    service
+        .add_monster(
+            module_id,
+            "Hobgoblin".to_string(),
+            "MM".to_string(),
+            1,
+            Some("Patrol".to_string()),
+        )
+        .unwrap();
+    service
+        .add_monster(module_id, "Homebrew Horror".to_string(), "HB".to_string(), 1, None)
+        .unwrap();
+
+    let difficulties = service.get_encounter_difficulties(module_id).unwrap();
+    assert_eq!(difficulties.len(), 2);
+
+    let patrol = difficulties
+        .iter()
+        .find(|d| d.encounter_tag.as_deref() == Some("Patrol"))
+        .unwrap();
+    assert_eq!(patrol.difficulty.base_xp, 100);
+    assert_eq!(patrol.difficulty.difficulty, Some(Difficulty::Easy));
+
+    // Monsters missing from the catalog contribute no XP
+    let untagged = difficulties.iter().find(|d| d.encounter_tag.is_none()).unwrap();
+    assert_eq!(untagged.difficulty.monsters[0].cr, None);
+    assert_eq!(untagged.difficulty.base_xp, 0);
+    assert_eq!(untagged.difficulty.difficulty, Some(Difficulty::Trivial));
+}
+
+#[test]
+fn test_encounter_difficulty_without_party() {
+    let mut conn = setup_test_db();
+    seed_encounter_monsters(&mut conn);
+    let (_, module_id) = create_test_module(&mut conn);
+
+    let mut service = ModuleMonsterService::new(&mut conn);
+    service
+        .add_monster(module_id, "Bugbear".to_string(), "MM".to_string(), 2, None)
+        .unwrap();
+
+    let difficulty = service.get_encounter_difficulty(module_id, None).unwrap();
+    assert!(difficulty.party_levels.is_empty());
+    assert_eq!(difficulty.base_xp, 400);
+    assert_eq!(difficulty.difficulty, None);
+
+    assert!(service
+        .get_encounter_difficulty(module_id, Some("Missing"))
+        .is_err());
+}
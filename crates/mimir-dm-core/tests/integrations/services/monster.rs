@@ -28,7 +28,7 @@ fn seed_test_monster_data(conn: &mut SqliteConnection) {
             7,
             15,
             "MM",
-            r#"{"name":"Goblin","source":"MM","size":["S"],"type":"humanoid","alignment":["N","E"],"ac":[{"ac":15}],"hp":{"average":7},"cr":"1/4"}"#,
+            r#"{"name":"Goblin","source":"MM","size":["S"],"type":"humanoid","alignment":["N","E"],"ac":[{"ac":15}],"hp":{"average":7},"cr":"1/4","environment":["forest","hill"]}"#,
         ),
         (
             "Orc",
@@ -40,7 +40,7 @@ fn seed_test_monster_data(conn: &mut SqliteConnection) {
             15,
             13,
             "MM",
-            r#"{"name":"Orc","source":"MM","size":["M"],"type":"humanoid","alignment":["C","E"],"ac":[{"ac":13}],"hp":{"average":15},"cr":"1/2"}"#,
+            r#"{"name":"Orc","source":"MM","size":["M"],"type":"humanoid","alignment":["C","E"],"ac":[{"ac":13}],"hp":{"average":15},"cr":"1/2","environment":["hill","mountain"]}"#,
         ),
         (
             "Skeleton",
@@ -486,3 +486,29 @@ fn test_get_monster_count_by_source() {
     let mm_count = counts.iter().find(|(s, _)| s == "MM").map(|(_, c)| *c);
     assert_eq!(mm_count, Some(10), "MM should have 10 monsters");
 }
+
+#[test]
+fn test_search_monsters_by_environment() {
+    let (mut conn, _temp_dir) = setup_test_db();
+
+    let filters = MonsterFilters {
+        environment: Some(vec!["Forest".to_string()]),
+        ..Default::default()
+    };
+    let results = MonsterService::new(&mut conn)
+        .search_monsters(filters)
+        .expect("Search should succeed");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].name, "Goblin");
+
+    let filters = MonsterFilters {
+        environment: Some(vec!["hill".to_string(), "mountain".to_string()]),
+        ..Default::default()
+    };
+    let results = MonsterService::new(&mut conn)
+        .search_monsters(filters)
+        .expect("Search should succeed");
+    let mut names: Vec<_> = results.iter().map(|m| m.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["Goblin", "Orc"]);
+}
@@ -0,0 +1,220 @@
+//! Integration tests for session service
+
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{
+    CampaignService, CampaignSummaryService, DocumentService, ModuleService, SessionService,
+};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+
+    // Seed templates
+    mimir_dm_core::seed::template_seeder::seed_templates(&mut conn).unwrap();
+
+    conn
+}
+
+fn create_test_campaign(
+    conn: &mut mimir_dm_core::connection::DbConnection,
+    temp_dir: &TempDir,
+) -> i32 {
+    let dir_path = temp_dir.path().to_string_lossy().to_string();
+    CampaignService::new(conn)
+        .create_campaign("Session Campaign", None, &dir_path)
+        .unwrap()
+        .id
+}
+
+fn create_test_module(
+    conn: &mut mimir_dm_core::connection::DbConnection,
+    campaign_id: i32,
+    name: &str,
+) -> i32 {
+    ModuleService::new(conn)
+        .create_module(campaign_id, name.to_string(), 3)
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_create_session_numbers_across_modules() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let first_module = create_test_module(&mut conn, campaign_id, "Goblin Arrows");
+    let second_module = create_test_module(&mut conn, campaign_id, "Phandalin");
+
+    let mut service = SessionService::new(&mut conn);
+    let s1 = service
+        .create_session(first_module, Some("2025-03-01".to_string()))
+        .unwrap();
+    let s2 = service.create_session(first_module, None).unwrap();
+    let s3 = service.create_session(second_module, None).unwrap();
+
+    assert_eq!(s1.session_number, 1);
+    assert_eq!(s2.session_number, 2);
+    assert_eq!(s3.session_number, 3);
+    assert_eq!(s1.status, "planned");
+    assert_eq!(s1.campaign_id, campaign_id);
+    assert_eq!(s1.scheduled_date.as_deref(), Some("2025-03-01"));
+
+    let module_sessions = service.list_module_sessions(first_module).unwrap();
+    assert_eq!(module_sessions.len(), 2);
+    assert_eq!(
+        service.list_campaign_sessions(campaign_id).unwrap().len(),
+        3
+    );
+
+    assert!(service.create_session(9999, None).is_err());
+}
+
+#[test]
+fn test_session_board_flow() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let module_id = create_test_module(&mut conn, campaign_id, "Cragmaw Hideout");
+
+    let mut service = SessionService::new(&mut conn);
+    let session = service.create_session(module_id, None).unwrap();
+
+    // Can't skip prep
+    assert!(service
+        .transition_session_stage(session.id, "played")
+        .is_err());
+
+    let prepping = service
+        .transition_session_stage(session.id, "prepping")
+        .unwrap();
+    assert_eq!(prepping.status, "prepping");
+    assert!(prepping.prep_started_at.is_some());
+
+    // The outline is created on entering prep, in the module's session folder
+    let docs = service.get_session_documents(session.id).unwrap();
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].template_id, "session_outline");
+    assert_eq!(docs[0].module_id, Some(module_id));
+    assert!(docs[0]
+        .file_path
+        .ends_with("modules/module_01/sessions/session_01/session-outline.md"));
+    let outline = std::fs::read_to_string(&docs[0].file_path).unwrap();
+    assert!(outline.contains("# Session 1:"));
+
+    let status = service.check_session_completion(session.id).unwrap();
+    assert!(!status.can_progress);
+    assert_eq!(status.missing_required_documents, vec!["session_outline"]);
+
+    let outline_id = docs[0].id;
+    DocumentService::new(&mut conn)
+        .complete_document(outline_id)
+        .unwrap();
+
+    let mut service = SessionService::new(&mut conn);
+    let status = service.check_session_completion(session.id).unwrap();
+    assert!(status.can_progress);
+    assert_eq!(status.next_stage.as_deref(), Some("ready"));
+
+    let ready = service
+        .transition_session_stage(session.id, "ready")
+        .unwrap();
+    assert!(ready.prep_completed_at.is_some());
+
+    let played = service
+        .transition_session_stage(session.id, "played")
+        .unwrap();
+    assert!(played.is_played());
+    assert!(played.completed_at.is_some());
+
+    // Playing creates the notes document and counts the session for the module
+    let docs = service.get_session_documents(session.id).unwrap();
+    assert_eq!(docs.len(), 2);
+    let notes = docs
+        .iter()
+        .find(|d| d.template_id == "session_notes")
+        .unwrap();
+    assert!(Path::new(&notes.file_path).exists());
+
+    let module = ModuleService::new(&mut conn)
+        .get_module(module_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(module.actual_sessions, 1);
+    assert!(module.started_at.is_some());
+
+    // Played is final
+    assert!(SessionService::new(&mut conn)
+        .transition_session_stage(session.id, "ready")
+        .is_err());
+}
+
+#[test]
+fn test_schedule_and_delete_session() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let module_id = create_test_module(&mut conn, campaign_id, "Wave Echo Cave");
+
+    let mut service = SessionService::new(&mut conn);
+    let session = service.create_session(module_id, None).unwrap();
+
+    let scheduled = service
+        .schedule_session(session.id, Some("2025-04-12".to_string()))
+        .unwrap();
+    assert_eq!(scheduled.scheduled_date.as_deref(), Some("2025-04-12"));
+
+    let cleared = service.schedule_session(session.id, None).unwrap();
+    assert!(cleared.scheduled_date.is_none());
+
+    service
+        .transition_session_stage(session.id, "prepping")
+        .unwrap();
+    assert_eq!(service.get_session_documents(session.id).unwrap().len(), 1);
+
+    service.delete_session(session.id).unwrap();
+    assert!(service.get_session(session.id).unwrap().is_none());
+    assert!(service
+        .get_session_documents(session.id)
+        .unwrap()
+        .is_empty());
+    assert!(service.schedule_session(session.id, None).is_err());
+}
+
+#[test]
+fn test_summary_sources_include_session_records() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+    let module_id = create_test_module(&mut conn, campaign_id, "Goblin Arrows");
+
+    let mut service = SessionService::new(&mut conn);
+    let first = service.create_session(module_id, None).unwrap();
+    service.create_session(module_id, None).unwrap();
+    for stage in ["prepping", "ready", "played"] {
+        service.transition_session_stage(first.id, stage).unwrap();
+    }
+
+    let dir = temp_dir.path().to_string_lossy().to_string();
+    let source = CampaignSummaryService::new(&mut conn)
+        .gather_source_materials(campaign_id, &dir)
+        .unwrap();
+
+    assert_eq!(source.sessions.len(), 2);
+    assert_eq!(source.sessions[0].session_number, 1);
+    assert_eq!(source.sessions[0].status, "played");
+    assert_eq!(
+        source.sessions[0].module_name.as_deref(),
+        Some("Goblin Arrows")
+    );
+    assert_eq!(source.sessions[1].status, "planned");
+
+    // The notes document created on play is picked up with its session number
+    assert_eq!(source.session_notes.len(), 1);
+    assert_eq!(source.session_notes[0].session_number, Some(1));
+
+    let prompt = mimir_dm_core::services::format_source_for_llm(&source);
+    assert!(prompt.contains("Session 1 (Goblin Arrows)"));
+}
@@ -1,6 +1,7 @@
 //! Integration tests for TableService
 
 use diesel::prelude::*;
+use mimir_dm_core::domain::dice::DiceRoller;
 use mimir_dm_core::models::catalog::table::TableFilters;
 use mimir_dm_core::services::TableService;
 use mimir_dm_core::{establish_connection, run_migrations};
@@ -140,6 +141,21 @@ fn seed_test_table_data(conn: &mut SqliteConnection) {
         ),
     ];
 
+    insert_tables(conn, tables);
+}
+
+type TableRow<'a> = (
+    &'a str,
+    Option<&'a str>,
+    &'a str,
+    &'a str,
+    Option<i32>,
+    i32,
+    i32,
+    &'a str,
+);
+
+fn insert_tables(conn: &mut SqliteConnection, tables: Vec<TableRow>) {
     for (name, caption, category, source, page, columns, rows, json) in tables {
         diesel::sql_query(
             "INSERT INTO catalog_tables (name, caption, category, source, page, columns_count, rows_count, full_table_json) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
@@ -386,3 +402,193 @@ fn test_table_summary_fields() {
     assert_eq!(summary.columns, 2);
     assert_eq!(summary.rows, 50);
 }
+
+fn seed_rollable_tables(conn: &mut SqliteConnection) {
+    insert_tables(
+        conn,
+        vec![
+            (
+                "Treasure Roll",
+                None,
+                "Treasure",
+                "DMG",
+                None,
+                2,
+                2,
+                r#"{"name":"Treasure Roll","source":"DMG","colLabels":["d100","Result"],"rows":[["01-50","Roll on {@table Gem Table|DMG|the gem table}"],["51–00","Nothing"]]}"#,
+            ),
+            (
+                "Gem Table",
+                None,
+                "Treasure",
+                "DMG",
+                None,
+                2,
+                2,
+                r#"{"name":"Gem Table","source":"DMG","colLabels":["d4","Gem"],"rows":[["1","Agate"],["2-4",{"type":"entries","entries":["Jasper"]}]]}"#,
+            ),
+            (
+                "Weather",
+                None,
+                "Miscellaneous",
+                "XGE",
+                None,
+                2,
+                3,
+                r#"{"name":"Weather","source":"XGE","colLabels":["{@dice 2d6}","Weather"],"rows":[["2-6","Calm"],["7-11","Rain"],["12","Storm"]]}"#,
+            ),
+            (
+                "Names",
+                None,
+                "Names",
+                "XGE",
+                None,
+                1,
+                3,
+                r#"{"name":"Names","source":"XGE","colLabels":["Name"],"rows":[["Alpha"],["Beta"],["Gamma"]]}"#,
+            ),
+            (
+                "Broken Reference",
+                None,
+                "Miscellaneous",
+                "DMG",
+                None,
+                2,
+                1,
+                r#"{"name":"Broken Reference","source":"DMG","colLabels":["d4","Result"],"rows":[["1-4","See {@table Missing Table}"]]}"#,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_roll_table_resolves_nested_tables() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+    let mut service = TableService::new(&mut conn);
+
+    for seed in 0..40 {
+        let result = service
+            .roll_table_with("Treasure Roll", "DMG", &mut DiceRoller::seeded(seed))
+            .expect("Roll should succeed");
+
+        assert_eq!(result.dice.as_deref(), Some("d100"));
+        assert!((1..=100).contains(&result.roll));
+        assert_eq!(result.columns, vec!["Result".to_string()]);
+
+        if result.roll <= 50 {
+            assert_eq!(result.row_index, 0);
+            assert_eq!(result.nested.len(), 1);
+            let gem = &result.nested[0];
+            assert_eq!(gem.table_name, "Gem Table");
+            let expected = if gem.roll == 1 { "Agate" } else { "Jasper" };
+            assert_eq!(gem.result, vec![expected.to_string()]);
+        } else {
+            assert_eq!(result.row_index, 1);
+            assert_eq!(result.result, vec!["Nothing".to_string()]);
+            assert!(result.nested.is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_roll_table_dice_label() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+    let mut service = TableService::new(&mut conn);
+
+    for seed in 0..20 {
+        let result = service
+            .roll_table_with("Weather", "XGE", &mut DiceRoller::seeded(seed))
+            .expect("Roll should succeed");
+
+        assert_eq!(result.dice.as_deref(), Some("2d6"));
+        let expected = match result.roll {
+            2..=6 => "Calm",
+            7..=11 => "Rain",
+            12 => "Storm",
+            roll => panic!("2d6 rolled {}", roll),
+        };
+        assert_eq!(result.result, vec![expected.to_string()]);
+    }
+}
+
+#[test]
+fn test_roll_table_without_dice_column() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+    let mut service = TableService::new(&mut conn);
+
+    let names = ["Alpha", "Beta", "Gamma"];
+    for seed in 0..10 {
+        let result = service
+            .roll_table_with("Names", "XGE", &mut DiceRoller::seeded(seed))
+            .expect("Roll should succeed");
+
+        assert!(result.dice.is_none());
+        assert_eq!(result.roll, result.row_index as i32 + 1);
+        assert_eq!(result.result, vec![names[result.row_index].to_string()]);
+    }
+}
+
+#[test]
+fn test_roll_table_is_repeatable_with_seed() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+    let mut service = TableService::new(&mut conn);
+
+    let first = service
+        .roll_table_with("Treasure Roll", "DMG", &mut DiceRoller::seeded(7))
+        .unwrap();
+    let second = service
+        .roll_table_with("Treasure Roll", "DMG", &mut DiceRoller::seeded(7))
+        .unwrap();
+
+    assert_eq!(first.roll, second.roll);
+    assert_eq!(first.result, second.result);
+}
+
+#[test]
+fn test_roll_table_case_insensitive_name() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+
+    let result = TableService::new(&mut conn)
+        .roll_table("weather", "xge")
+        .expect("Roll should succeed");
+
+    assert_eq!(result.table_name, "Weather");
+}
+
+#[test]
+fn test_roll_table_unresolved_reference() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+
+    let result = TableService::new(&mut conn)
+        .roll_table("Broken Reference", "DMG")
+        .expect("Roll should succeed");
+
+    assert!(result.nested.is_empty());
+    assert_eq!(result.unresolved, vec!["Missing Table|DMG".to_string()]);
+}
+
+#[test]
+fn test_roll_table_not_found() {
+    let (mut conn, _temp_dir) = setup_test_db();
+
+    let result = TableService::new(&mut conn).roll_table("Nonexistent", "DMG");
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_roll_table_name_wildcards_match_literally() {
+    let (mut conn, _temp_dir) = setup_test_db();
+    seed_rollable_tables(&mut conn);
+    let mut service = TableService::new(&mut conn);
+
+    assert!(service.roll_table("%", "DMG").is_err());
+    assert!(service.roll_table("Weathe_", "XGE").is_err());
+    assert!(service.roll_table("Weather", "%").is_ok());
+}
@@ -0,0 +1,189 @@
+//! Integration tests for the tool action journal service
+
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::chat::RevertAction;
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{CampaignService, ModuleService, ToolJournalService};
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+fn create_test_campaign(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> i32 {
+    let mut campaign_service = CampaignService::new(conn);
+    campaign_service
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_record_and_list_actions() {
+    let mut conn = setup_test_db();
+    let mut service = ToolJournalService::new(&mut conn);
+
+    let revert = RevertAction::RestoreCharacterVersion {
+        character_id: 1,
+        version_number: 3,
+    };
+    service
+        .record(
+            "session-a",
+            "update_character_hp",
+            &json!({"character_id": 1, "new_hp": 5}),
+            "Set HP to 5",
+            &revert,
+        )
+        .unwrap();
+    service
+        .record(
+            "session-b",
+            "update_currency",
+            &json!({"character_id": 2}),
+            "Spend gold",
+            &RevertAction::RestoreCharacterVersion {
+                character_id: 2,
+                version_number: 1,
+            },
+        )
+        .unwrap();
+
+    let actions = service.list_for_session("session-a", true).unwrap();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0].tool_name, "update_character_hp");
+    assert_eq!(actions[0].summary, "Set HP to 5");
+    assert_eq!(actions[0].revert().unwrap(), revert);
+    assert!(!actions[0].is_undone());
+
+    assert_eq!(service.clear_session("session-a").unwrap(), 1);
+    assert!(service
+        .list_for_session("session-a", true)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        service.list_for_session("session-b", true).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn test_undo_file_actions_newest_first() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("notes.md");
+    let path_str = path.to_str().unwrap().to_string();
+
+    let mut service = ToolJournalService::new(&mut conn);
+
+    // write_file created the file, then edit_file changed it
+    fs::write(&path, "first draft").unwrap();
+    service
+        .record(
+            "session",
+            "write_file",
+            &json!({"file_path": path_str}),
+            "Create notes.md",
+            &RevertAction::RestoreFile {
+                path: path_str.clone(),
+                previous_contents: None,
+            },
+        )
+        .unwrap();
+
+    fs::write(&path, "second draft").unwrap();
+    service
+        .record(
+            "session",
+            "edit_file",
+            &json!({"file_path": path_str}),
+            "Edit notes.md",
+            &RevertAction::RestoreFile {
+                path: path_str.clone(),
+                previous_contents: Some("first draft".to_string()),
+            },
+        )
+        .unwrap();
+
+    let undone = service.undo_last("session", 1).unwrap();
+    assert_eq!(undone.len(), 1);
+    assert_eq!(undone[0].tool_name, "edit_file");
+    assert!(undone[0].is_undone());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "first draft");
+
+    // Only the remaining action is undone; asking for more is fine
+    let undone = service.undo_last("session", 5).unwrap();
+    assert_eq!(undone.len(), 1);
+    assert_eq!(undone[0].tool_name, "write_file");
+    assert!(!path.exists());
+
+    assert!(service.undo_last("session", 1).unwrap().is_empty());
+    assert!(service
+        .list_for_session("session", false)
+        .unwrap()
+        .is_empty());
+    assert_eq!(service.list_for_session("session", true).unwrap().len(), 2);
+}
+
+#[test]
+fn test_undo_module_actions() {
+    let mut conn = setup_test_db();
+    let temp_dir = TempDir::new().unwrap();
+    let campaign_id = create_test_campaign(&mut conn, &temp_dir);
+
+    let (created, transitioned) = {
+        let mut modules = ModuleService::new(&mut conn);
+        let created = modules
+            .create_module(campaign_id, "Created Module".to_string(), 2)
+            .unwrap();
+        let transitioned = modules
+            .create_module(campaign_id, "Transitioned Module".to_string(), 3)
+            .unwrap();
+        (created, transitioned)
+    };
+
+    let mut service = ToolJournalService::new(&mut conn);
+    service
+        .record(
+            "session",
+            "create_module",
+            &json!({"campaign_id": campaign_id, "name": "Created Module"}),
+            "Create module",
+            &RevertAction::DeleteModule {
+                module_id: created.id,
+            },
+        )
+        .unwrap();
+    service
+        .record(
+            "session",
+            "update_module_status",
+            &json!({"module_id": transitioned.id, "new_status": "development"}),
+            "Move module to development",
+            &RevertAction::RestoreModuleStatus {
+                module_id: transitioned.id,
+                status: transitioned.status.clone(),
+                started_at: transitioned.started_at.clone(),
+                completed_at: transitioned.completed_at.clone(),
+            },
+        )
+        .unwrap();
+
+    ModuleService::new(&mut conn)
+        .transition_module_stage(transitioned.id, "development")
+        .unwrap();
+
+    let undone = ToolJournalService::new(&mut conn)
+        .undo_last("session", 2)
+        .unwrap();
+    assert_eq!(undone.len(), 2);
+
+    let mut modules = ModuleService::new(&mut conn);
+    assert!(modules.get_module(created.id).unwrap().is_none());
+    let restored = modules.get_module(transitioned.id).unwrap().unwrap();
+    assert_eq!(restored.status, "planning");
+}
@@ -0,0 +1,157 @@
+//! Integration tests for Universal VTT map import
+
+use mimir_dm_core::domain::UvttMap;
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{AmbientLight, DoorState, GridType, NewMap};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{CampaignService, MapService, UvttImportService, WallService};
+use tempfile::TempDir;
+
+const DUNGEON: &str = r#"{
+    "format": 0.3,
+    "resolution": {
+        "map_origin": {"x": 0, "y": 0},
+        "map_size": {"x": 20, "y": 10},
+        "pixels_per_grid": 140
+    },
+    "line_of_sight": [
+        [{"x": 0, "y": 0}, {"x": 20, "y": 0}, {"x": 20, "y": 10}, {"x": 0, "y": 10}, {"x": 0, "y": 0}],
+        [{"x": 10, "y": 0}, {"x": 10, "y": 4}]
+    ],
+    "objects_line_of_sight": [],
+    "portals": [
+        {"position": {"x": 10, "y": 5}, "bounds": [{"x": 10, "y": 4}, {"x": 10, "y": 6}], "rotation": 1.5708, "closed": true, "freestanding": false},
+        {"position": {"x": 15, "y": 10}, "bounds": [{"x": 14.5, "y": 10}, {"x": 15.5, "y": 10}], "rotation": 0, "closed": false, "freestanding": false}
+    ],
+    "environment": {"baked_lighting": false, "ambient_light": "ff808080"},
+    "lights": [
+        {"position": {"x": 5, "y": 5}, "range": 8, "intensity": 1, "color": "ffff9933", "shadows": true}
+    ],
+    "image": ""
+}"#;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+fn create_test_campaign(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> i32 {
+    let mut campaign_service = CampaignService::new(conn);
+    campaign_service
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id
+}
+
+#[test]
+fn test_import_creates_map_with_grid_walls_doors_and_lights() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &dir);
+    let uvtt = UvttMap::from_json(DUNGEON).unwrap();
+
+    // The 2800x1400 image was stored at half size
+    let new_map = NewMap::new(
+        campaign_id,
+        "Dungeon".to_string(),
+        "dungeon.jpg".to_string(),
+        1400,
+        700,
+        2800,
+        1400,
+    );
+    let import = UvttImportService::new(&mut conn)
+        .import(&uvtt, new_map)
+        .unwrap();
+
+    assert_eq!(import.map.grid_type_enum(), GridType::Square);
+    assert_eq!(import.map.grid_size_px, Some(70));
+    assert_eq!((import.map.grid_offset_x, import.map.grid_offset_y), (0, 0));
+    assert_eq!(import.map.ambient_light_enum(), AmbientLight::Dim);
+
+    // Four segments around the room plus the dividing wall
+    assert_eq!(import.wall_count, 5);
+    assert_eq!(import.door_count, 2);
+
+    let mut walls = WallService::new(&mut conn);
+    let stored_walls = walls.get_walls_for_map(import.map.id).unwrap();
+    assert_eq!(stored_walls.len(), 5);
+    assert_eq!(
+        (
+            stored_walls[4].x1,
+            stored_walls[4].y1,
+            stored_walls[4].x2,
+            stored_walls[4].y2
+        ),
+        (700.0, 0.0, 700.0, 280.0)
+    );
+
+    let doors = walls.get_doors_for_map(import.map.id).unwrap();
+    assert_eq!(doors[0].state_enum(), DoorState::Closed);
+    assert_eq!((doors[0].y1, doors[0].y2), (280.0, 420.0));
+    assert_eq!(doors[1].state_enum(), DoorState::Open);
+
+    assert_eq!(import.light_sources.len(), 1);
+    let light = &import.light_sources[0];
+    assert_eq!((light.x, light.y), (350.0, 350.0));
+    assert_eq!(light.dim_radius_ft, 40.0);
+    assert_eq!(light.bright_radius_ft, 20.0);
+    assert_eq!(light.color.as_deref(), Some("#ff9933"));
+}
+
+#[test]
+fn test_import_rejects_missing_resolution() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &dir);
+
+    let mut uvtt = UvttMap::from_json(DUNGEON).unwrap();
+    uvtt.resolution.pixels_per_grid = 0.0;
+
+    let new_map = NewMap::new(
+        campaign_id,
+        "Broken".to_string(),
+        "broken.jpg".to_string(),
+        100,
+        100,
+        100,
+        100,
+    );
+    assert!(UvttImportService::new(&mut conn)
+        .import(&uvtt, new_map)
+        .is_err());
+    assert!(MapService::new(&mut conn)
+        .list_campaign_maps(campaign_id)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_walls_deleted_with_map() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let campaign_id = create_test_campaign(&mut conn, &dir);
+    let uvtt = UvttMap::from_json(DUNGEON).unwrap();
+
+    let new_map = NewMap::new(
+        campaign_id,
+        "Dungeon".to_string(),
+        "dungeon.jpg".to_string(),
+        2800,
+        1400,
+        2800,
+        1400,
+    );
+    let map_id = UvttImportService::new(&mut conn)
+        .import(&uvtt, new_map)
+        .unwrap()
+        .map
+        .id;
+
+    MapService::new(&mut conn).delete_map(map_id).unwrap();
+
+    let mut walls = WallService::new(&mut conn);
+    assert!(walls.get_walls_for_map(map_id).unwrap().is_empty());
+    assert!(walls.get_doors_for_map(map_id).unwrap().is_empty());
+}
@@ -0,0 +1,173 @@
+//! Integration tests for walls, doors and token visibility
+
+use mimir_dm_core::domain::{LightLevel, Point};
+use mimir_dm_core::establish_connection;
+use mimir_dm_core::models::campaign::{
+    AmbientLight, DoorState, GridType, NewMap, NewMapDoor, NewMapWall, NewToken, TokenType,
+    UpdateMapWall, VisionType,
+};
+use mimir_dm_core::run_migrations;
+use mimir_dm_core::services::{
+    CampaignService, LightSourceService, MapService, TokenService, VisibilityService, WallService,
+};
+use tempfile::TempDir;
+
+fn setup_test_db() -> mimir_dm_core::connection::DbConnection {
+    let mut conn = establish_connection(":memory:").unwrap();
+    run_migrations(&mut conn).expect("Failed to run migrations");
+    conn
+}
+
+struct Room {
+    map_id: i32,
+    upper_wall_id: i32,
+    door_id: i32,
+}
+
+/// A dark 1000x1000 map (5 ft squares of 50px) split by a wall at x = 500
+/// with a closed door from y = 400 to 600. A dwarf with 60 ft darkvision
+/// stands west of the wall and a torch burns east of it.
+fn create_room(conn: &mut mimir_dm_core::connection::DbConnection, dir: &TempDir) -> Room {
+    let campaign_id = CampaignService::new(conn)
+        .create_campaign("Test Campaign", None, dir.path().to_str().unwrap())
+        .unwrap()
+        .id;
+
+    let map = MapService::new(conn)
+        .create_map(
+            NewMap::new(
+                campaign_id,
+                "Crypt".to_string(),
+                "crypt.png".to_string(),
+                1000,
+                1000,
+                1000,
+                1000,
+            )
+            .with_grid(GridType::Square, 50, 0, 0)
+            .with_ambient_light(AmbientLight::Darkness),
+        )
+        .unwrap();
+
+    let mut walls = WallService::new(conn);
+    let upper_wall_id = walls
+        .create_wall(NewMapWall::new(map.id, 500.0, 0.0, 500.0, 400.0))
+        .unwrap()
+        .id;
+    walls
+        .create_wall(NewMapWall::new(map.id, 500.0, 600.0, 500.0, 1000.0))
+        .unwrap();
+    let door_id = walls
+        .create_door(NewMapDoor::new(map.id, 500.0, 400.0, 500.0, 600.0))
+        .unwrap()
+        .id;
+
+    let mut tokens = TokenService::new(conn);
+    tokens
+        .create_token(
+            NewToken::new(map.id, "Dwarf".to_string(), 250.0, 500.0)
+                .with_type(TokenType::PC)
+                .with_vision(VisionType::Darkvision, Some(60.0)),
+        )
+        .unwrap();
+    tokens
+        .create_token(NewToken::new(map.id, "Ghoul".to_string(), 300.0, 300.0))
+        .unwrap();
+
+    LightSourceService::new(conn)
+        .create_torch(map.id, 800.0, 500.0)
+        .unwrap();
+
+    Room {
+        map_id: map.id,
+        upper_wall_id,
+        door_id,
+    }
+}
+
+#[test]
+fn test_party_visibility_respects_doors() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let room = create_room(&mut conn, &dir);
+
+    let visibility = VisibilityService::new(&mut conn)
+        .get_party_visibility(room.map_id)
+        .unwrap();
+
+    // Only the PC counts as party, and the torch is lit even unseen
+    assert_eq!(visibility.tokens.len(), 1);
+    assert_eq!(visibility.lit_areas.len(), 1);
+    assert_eq!(
+        visibility.light_level_at(Point::new(300.0, 500.0)),
+        Some(LightLevel::Dim)
+    );
+    assert_eq!(visibility.light_level_at(Point::new(800.0, 500.0)), None);
+
+    let mut walls = WallService::new(&mut conn);
+    let door = walls.set_door_state(room.door_id, DoorState::Open).unwrap();
+    assert_eq!(door.state_enum(), DoorState::Open);
+
+    let visibility = VisibilityService::new(&mut conn)
+        .get_party_visibility(room.map_id)
+        .unwrap();
+    assert_eq!(
+        visibility.light_level_at(Point::new(800.0, 500.0)),
+        Some(LightLevel::Bright)
+    );
+
+    WallService::new(&mut conn)
+        .set_door_state(room.door_id, DoorState::Locked)
+        .unwrap();
+    let visibility = VisibilityService::new(&mut conn)
+        .get_party_visibility(room.map_id)
+        .unwrap();
+    assert_eq!(visibility.light_level_at(Point::new(800.0, 500.0)), None);
+}
+
+#[test]
+fn test_editing_walls_changes_visibility() {
+    let dir = TempDir::new().unwrap();
+    let mut conn = setup_test_db();
+    let room = create_room(&mut conn, &dir);
+    WallService::new(&mut conn)
+        .set_door_state(room.door_id, DoorState::Open)
+        .unwrap();
+
+    // Dimly lit by the torch, but hidden behind the upper wall
+    let corner = Point::new(800.0, 150.0);
+    let visibility = VisibilityService::new(&mut conn)
+        .get_party_visibility(room.map_id)
+        .unwrap();
+    assert_eq!(visibility.light_level_at(corner), None);
+
+    // Shortening the wall opens a wider gap
+    let mut walls = WallService::new(&mut conn);
+    let wall = walls
+        .update_wall(
+            room.upper_wall_id,
+            UpdateMapWall::endpoints(500.0, 0.0, 500.0, 200.0),
+        )
+        .unwrap();
+    assert_eq!(wall.y2, 200.0);
+    let visibility = VisibilityService::new(&mut conn)
+        .get_party_visibility(room.map_id)
+        .unwrap();
+    assert_eq!(visibility.light_level_at(corner), Some(LightLevel::Dim));
+
+    let mut walls = WallService::new(&mut conn);
+    walls.delete_wall(room.upper_wall_id).unwrap();
+    walls.delete_door(room.door_id).unwrap();
+    assert_eq!(walls.get_walls_for_map(room.map_id).unwrap().len(), 1);
+    assert!(walls.get_doors_for_map(room.map_id).unwrap().is_empty());
+    let segments = walls.get_sight_blocking_segments(room.map_id).unwrap();
+    assert_eq!(segments.len(), 1);
+}
+
+#[test]
+fn test_token_visibility_for_missing_token() {
+    let mut conn = setup_test_db();
+    assert!(VisibilityService::new(&mut conn)
+        .get_token_visibility(999)
+        .is_err());
+}
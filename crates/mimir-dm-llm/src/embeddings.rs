@@ -1 +1,309 @@
-// Embedding generation and management
\ No newline at end of file
+//! Embedding generation and management
+//!
+//! Helpers for turning long documents into embeddable chunks and for
+//! embedding those chunks through any [`LlmProvider`].
+//!
+//! Chunks are built from markdown: YAML frontmatter is dropped, the nearest
+//! heading is kept alongside each chunk, and paragraphs are packed together
+//! up to a size limit. Paragraphs that are too long on their own are split on
+//! sentence or word boundaries with a small overlap so that context isn't
+//! lost at the cut.
+
+use crate::traits::provider::{LlmError, LlmProvider};
+
+/// Options controlling how text is split into chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOptions {
+    /// Target maximum chunk length in characters
+    pub max_chars: usize,
+    /// Characters repeated between consecutive pieces of an oversized paragraph
+    pub overlap_chars: usize,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            max_chars: 1200,
+            overlap_chars: 200,
+        }
+    }
+}
+
+/// A chunk of a larger document, ready to be embedded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    /// Position of the chunk within its document
+    pub index: usize,
+    /// Nearest markdown heading above the chunk, if any
+    pub heading: Option<String>,
+    /// Chunk text
+    pub text: String,
+}
+
+impl TextChunk {
+    /// Text to send to the embedding model.
+    ///
+    /// The heading is prepended so that chunks deep inside a section still
+    /// carry the section's subject.
+    pub fn embedding_text(&self) -> String {
+        match &self.heading {
+            Some(heading) => format!("{}\n\n{}", heading, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Split markdown text into chunks.
+///
+/// Frontmatter is skipped and headings start a new chunk. Each chunk
+/// records the heading it appeared under.
+pub fn chunk_markdown(text: &str, options: &ChunkOptions) -> Vec<TextChunk> {
+    let max_chars = options.max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut heading: Option<String> = None;
+    let mut buffer = String::new();
+
+    for block in split_blocks(strip_frontmatter(text)) {
+        if let Some(title) = heading_text(&block) {
+            flush(&mut chunks, &heading, &mut buffer);
+            heading = Some(title);
+            continue;
+        }
+
+        if block.chars().count() > max_chars {
+            flush(&mut chunks, &heading, &mut buffer);
+            for piece in split_long(&block, max_chars, options.overlap_chars) {
+                push_chunk(&mut chunks, &heading, piece);
+            }
+            continue;
+        }
+
+        let joined_len = buffer.chars().count() + block.chars().count() + 2;
+        if !buffer.is_empty() && joined_len > max_chars {
+            flush(&mut chunks, &heading, &mut buffer);
+        }
+        if !buffer.is_empty() {
+            buffer.push_str("\n\n");
+        }
+        buffer.push_str(&block);
+    }
+    flush(&mut chunks, &heading, &mut buffer);
+
+    chunks
+}
+
+/// Embed each text with the provider, one request per text.
+///
+/// Some providers (Ollama included) combine every input of a single
+/// `embed` call into one vector, so texts are sent individually.
+///
+/// # Errors
+/// Returns the provider's error if any request fails, or
+/// [`LlmError::ProviderError`] if the returned vectors differ in length.
+pub async fn embed_texts<P>(provider: &P, texts: &[String]) -> Result<Vec<Vec<f32>>, LlmError>
+where
+    P: LlmProvider + ?Sized,
+{
+    let mut embeddings: Vec<Vec<f32>> = Vec::with_capacity(texts.len());
+
+    for text in texts {
+        let response = provider.embed(vec![text.clone()], None).await?;
+
+        if response.embedding.is_empty() {
+            return Err(LlmError::ProviderError(
+                "Provider returned an empty embedding".to_string(),
+            ));
+        }
+        if let Some(first) = embeddings.first() {
+            if first.len() != response.embedding.len() {
+                return Err(LlmError::ProviderError(format!(
+                    "Embedding size changed from {} to {}",
+                    first.len(),
+                    response.embedding.len()
+                )));
+            }
+        }
+
+        embeddings.push(response.embedding);
+    }
+
+    Ok(embeddings)
+}
+
+fn strip_frontmatter(text: &str) -> &str {
+    let trimmed = text.trim_start();
+    let Some(rest) = trimmed.strip_prefix("---") else {
+        return text;
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let after = &rest[end + 4..];
+            after.split_once('\n').map(|(_, body)| body).unwrap_or("")
+        }
+        None => text,
+    }
+}
+
+/// Split text into paragraphs, keeping each heading line as its own block.
+fn split_blocks(text: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() || heading_text(line).is_some() {
+            if !current.trim().is_empty() {
+                blocks.push(current.trim().to_string());
+            }
+            current.clear();
+            if !line.trim().is_empty() {
+                blocks.push(line.trim().to_string());
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.trim().is_empty() {
+        blocks.push(current.trim().to_string());
+    }
+
+    blocks
+}
+
+fn heading_text(block: &str) -> Option<String> {
+    if block.contains('\n') {
+        return None;
+    }
+    let hashes = block.chars().take_while(|c| *c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+    let title = block[hashes..].strip_prefix(' ')?.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Split a paragraph longer than `max_chars` into overlapping pieces.
+///
+/// Cuts prefer a sentence end, then whitespace, within the last third of
+/// each window.
+fn split_long(text: &str, max_chars: usize, overlap_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let overlap = overlap_chars.min(max_chars / 2);
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let hard_end = (start + max_chars).min(chars.len());
+        let end = if hard_end == chars.len() {
+            hard_end
+        } else {
+            let floor = start + max_chars * 2 / 3;
+            let window = &chars[floor..hard_end];
+            let sentence = window
+                .windows(2)
+                .rposition(|w| matches!(w[0], '.' | '!' | '?') && w[1].is_whitespace())
+                .map(|i| floor + i + 1);
+            let space = window
+                .iter()
+                .rposition(|c| c.is_whitespace())
+                .map(|i| floor + i);
+            sentence.or(space).unwrap_or(hard_end)
+        };
+
+        let piece: String = chars[start..end].iter().collect();
+        if !piece.trim().is_empty() {
+            pieces.push(piece.trim().to_string());
+        }
+        if end == chars.len() {
+            break;
+        }
+        start = if end > start + overlap {
+            end - overlap
+        } else {
+            end
+        };
+    }
+
+    pieces
+}
+
+fn push_chunk(chunks: &mut Vec<TextChunk>, heading: &Option<String>, text: String) {
+    chunks.push(TextChunk {
+        index: chunks.len(),
+        heading: heading.clone(),
+        text,
+    });
+}
+
+fn flush(chunks: &mut Vec<TextChunk>, heading: &Option<String>, buffer: &mut String) {
+    if !buffer.trim().is_empty() {
+        push_chunk(chunks, heading, std::mem::take(buffer));
+    }
+    buffer.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_follow_headings() {
+        let text = "---\ntitle: Notes\n---\n# Goblins\n\nThey ambush travellers.\n\nThey like traps.\n\n## Klarg\n\nA bugbear boss.";
+        let chunks = chunk_markdown(text, &ChunkOptions::default());
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].heading.as_deref(), Some("Goblins"));
+        assert_eq!(
+            chunks[0].text,
+            "They ambush travellers.\n\nThey like traps."
+        );
+        assert_eq!(chunks[1].heading.as_deref(), Some("Klarg"));
+        assert_eq!(chunks[1].index, 1);
+        assert_eq!(chunks[1].embedding_text(), "Klarg\n\nA bugbear boss.");
+        assert!(!chunks.iter().any(|c| c.text.contains("title:")));
+    }
+
+    #[test]
+    fn test_paragraphs_are_packed_up_to_limit() {
+        let options = ChunkOptions {
+            max_chars: 30,
+            overlap_chars: 0,
+        };
+        let text = "First paragraph.\n\nSecond one.\n\nThird paragraph here.";
+        let chunks = chunk_markdown(text, &options);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "First paragraph.\n\nSecond one.");
+        assert_eq!(chunks[1].text, "Third paragraph here.");
+        assert!(chunks[0].heading.is_none());
+    }
+
+    #[test]
+    fn test_long_paragraph_is_split_with_overlap() {
+        let options = ChunkOptions {
+            max_chars: 60,
+            overlap_chars: 15,
+        };
+        let sentence = "The dragon sleeps on a hoard of gold. ";
+        let text = sentence.repeat(6);
+        let chunks = chunk_markdown(&text, &options);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.text.chars().count() <= 60);
+        }
+        // Consecutive pieces share some text
+        let tail: String = chunks[0].text.chars().rev().take(8).collect();
+        let tail: String = tail.chars().rev().collect();
+        assert!(chunks[1].text.contains(tail.trim()));
+    }
+
+    #[test]
+    fn test_empty_text_has_no_chunks() {
+        assert!(chunk_markdown("", &ChunkOptions::default()).is_empty());
+        assert!(chunk_markdown("---\na: b\n---\n", &ChunkOptions::default()).is_empty());
+    }
+}
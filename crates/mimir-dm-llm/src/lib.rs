@@ -11,6 +11,7 @@
 //! - **Multiple endpoints**: Support for chat, completion, and embedding endpoints
 //! - **Configuration**: YAML-based configuration system
 //! - **Async support**: Full async/await support with tokio
+//! - **Streaming**: Chat replies can be streamed token by token
 //!
 //! ## Quick Start
 //!
@@ -55,8 +56,10 @@
 #![warn(missing_docs)]
 
 pub mod config;
+pub mod embeddings;
 /// LLM provider implementations.
 pub mod providers;
+mod streaming;
 /// Tool implementations for LLM function calling.
 pub mod tools;
 pub mod traits;
@@ -68,11 +71,14 @@ pub use config::{
 
 // Re-export provider trait and types
 pub use traits::provider::{
-    ChatResponse, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider, Message, ModelInfo,
-    ModelPullProgress, RateLimitState, Timing, Tool, ToolCall, ToolCallFunction, ToolFunction,
-    Usage,
+    ChatResponse, ChatStreamEvent, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider,
+    Message, ModelInfo, ModelPullProgress, RateLimitState, Timing, Tool, ToolCall,
+    ToolCallFunction, ToolFunction, Usage,
 };
 
+// Re-export embedding helpers
+pub use embeddings::{chunk_markdown, embed_texts, ChunkOptions, TextChunk};
+
 // Re-export tool trait
 pub use traits::ToolTrait;
 
@@ -49,8 +49,8 @@ use tracing::debug;
 use crate::config::{EndpointType, ModelConfig};
 use crate::providers::openai_compat::{OpenAiChatRequest, OpenAiCompatClient, OpenAiMessage};
 use crate::traits::{
-    ChatResponse, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider, Message,
-    ModelInfo, RateLimitState, Tool,
+    ChatResponse, ChatStreamEvent, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider,
+    Message, ModelInfo, RateLimitState, Tool,
 };
 
 // Note: Groq now uses the shared OpenAI-compatible client (OpenAiCompatClient).
@@ -146,6 +146,7 @@ impl LlmProvider for GroqProvider {
             stop,
             tools,
             stream: false,
+            stream_options: None,
         };
 
         debug!(
@@ -158,6 +159,52 @@ impl LlmProvider for GroqProvider {
         self.openai_client.chat(request, cancellation_token).await
     }
 
+    async fn chat_stream<F>(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<Tool>>,
+        _n: Option<u32>,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+        stop: Option<Vec<String>>,
+        _extra_config: Option<HashMap<String, String>>,
+        cancellation_token: Option<CancellationToken>,
+        on_event: F,
+    ) -> Result<ChatResponse, LlmError>
+    where
+        F: Fn(ChatStreamEvent) + Send + Sync + 'static,
+    {
+        if !self.supports_endpoint(EndpointType::Chat) {
+            return Err(LlmError::UnsupportedEndpoint("chat".to_string()));
+        }
+
+        self.check_rate_limit().await?;
+
+        let openai_messages: Vec<OpenAiMessage> =
+            messages.into_iter().map(OpenAiMessage::from).collect();
+
+        let request = OpenAiChatRequest {
+            model: self.config.model.clone(),
+            messages: openai_messages,
+            temperature,
+            max_tokens,
+            stop,
+            tools,
+            stream: true,
+            stream_options: None,
+        };
+
+        debug!(
+            "Groq streaming chat request via OpenAI-compat: model={} messages={}",
+            request.model,
+            request.messages.len()
+        );
+
+        self.openai_client
+            .chat_stream(request, cancellation_token, on_event)
+            .await
+    }
+
     async fn complete(
         &self,
         prompt: String,
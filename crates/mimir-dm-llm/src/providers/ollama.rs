@@ -2,7 +2,8 @@
 //!
 //! This module provides an implementation of the [`LlmProvider`] trait for the Ollama API.
 //! Uses OpenAI-compatible endpoints for chat and completion, with Ollama-specific endpoints
-//! for model management (list, pull, check).
+//! for model management (list, pull, check). Streaming chat uses the native `/api/chat`
+//! endpoint, which sends newline-delimited JSON.
 //!
 //! ## Configuration
 //!
@@ -30,13 +31,88 @@ use url::Url;
 
 use crate::config::{EndpointType, ModelConfig};
 use crate::providers::openai_compat::{OpenAiChatRequest, OpenAiCompatClient, OpenAiMessage};
+use crate::streaming::{next_chunk, LineBuffer, StreamAccumulator};
 use crate::traits::{
-    ChatResponse, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider, Message, ModelInfo,
-    ModelPullProgress, RateLimitState, Tool, Usage,
+    ChatResponse, ChatStreamEvent, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider,
+    Message, ModelInfo, ModelPullProgress, RateLimitState, Timing, Tool, Usage,
 };
 
 // Note: Chat and completion now use OpenAI-compatible endpoint via OpenAiCompatClient.
-// The following types are only used for Ollama-specific endpoints (embeddings, model management).
+// The following types are only used for Ollama-specific endpoints (streaming chat,
+// embeddings, model management).
+
+/// Ollama native chat request (/api/chat), used for streaming
+#[derive(Debug, Serialize)]
+struct OllamaChatRequest {
+    model: String,
+    messages: Vec<OllamaChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<Tool>>,
+    stream: bool,
+    options: OllamaChatOptions,
+}
+
+/// Message in Ollama's native chat format
+#[derive(Debug, Serialize)]
+struct OllamaChatMessage {
+    role: String,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+/// Sampling options for Ollama's native chat endpoint
+#[derive(Debug, Serialize)]
+struct OllamaChatOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+/// One line of a streamed /api/chat response
+#[derive(Debug, Deserialize)]
+struct OllamaChatChunk {
+    #[serde(default)]
+    model: String,
+    message: Option<OllamaChunkMessage>,
+    #[serde(default)]
+    done: bool,
+    error: Option<String>,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+    total_duration: Option<u64>,
+    load_duration: Option<u64>,
+    prompt_eval_duration: Option<u64>,
+    eval_duration: Option<u64>,
+}
+
+/// Message content added by one chunk
+#[derive(Debug, Deserialize)]
+struct OllamaChunkMessage {
+    #[serde(default)]
+    content: String,
+    /// Reasoning from thinking models, sent separately from the content
+    #[serde(default)]
+    thinking: Option<String>,
+    tool_calls: Option<Vec<OllamaToolCall>>,
+}
+
+/// Tool call in Ollama's native format; arguments arrive complete
+#[derive(Debug, Deserialize)]
+struct OllamaToolCall {
+    id: Option<String>,
+    function: OllamaToolCallFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaToolCallFunction {
+    name: String,
+    #[serde(default)]
+    arguments: serde_json::Value,
+}
 
 /// Ollama embedding request (Ollama-specific /api/embeddings endpoint)
 #[derive(Debug, Serialize)]
@@ -144,6 +220,64 @@ impl OllamaProvider {
             base_url,
         })
     }
+
+    /// Apply one NDJSON line of a streamed chat to the response being built
+    ///
+    /// Returns `true` once Ollama reports the reply is done.
+    fn apply_chat_chunk<F>(
+        line: &str,
+        acc: &mut StreamAccumulator,
+        on_event: &F,
+    ) -> Result<bool, LlmError>
+    where
+        F: Fn(ChatStreamEvent),
+    {
+        let chunk: OllamaChatChunk = serde_json::from_str(line)
+            .map_err(|e| LlmError::ProviderError(format!("Failed to parse stream chunk: {}", e)))?;
+
+        if let Some(error) = chunk.error {
+            return Err(LlmError::ProviderError(format!("Ollama error: {}", error)));
+        }
+        if !chunk.model.is_empty() {
+            acc.model = chunk.model;
+        }
+
+        if let Some(message) = chunk.message {
+            if let Some(thinking) = message.thinking.as_deref() {
+                if let Some(event) = acc.push_thinking(thinking) {
+                    on_event(event);
+                }
+            }
+            if let Some(event) = acc.push_content(&message.content) {
+                on_event(event);
+            }
+            for call in message.tool_calls.unwrap_or_default() {
+                on_event(acc.push_tool_call(call.id, call.function.name, call.function.arguments));
+            }
+        }
+
+        if !chunk.done {
+            return Ok(false);
+        }
+
+        let prompt_tokens = chunk.prompt_eval_count.unwrap_or(0);
+        let completion_tokens = chunk.eval_count.unwrap_or(0);
+        acc.usage = Some(Usage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        });
+        // Ollama reports durations in nanoseconds
+        let to_ms = |ns: Option<u64>| ns.unwrap_or(0) / 1_000_000;
+        acc.timing = Some(Timing {
+            total_duration_ms: to_ms(chunk.total_duration),
+            load_duration_ms: to_ms(chunk.load_duration),
+            prompt_eval_duration_ms: to_ms(chunk.prompt_eval_duration),
+            completion_duration_ms: to_ms(chunk.eval_duration),
+        });
+
+        Ok(true)
+    }
 }
 
 #[async_trait]
@@ -185,6 +319,7 @@ impl LlmProvider for OllamaProvider {
             stop,
             tools,
             stream: false,
+            stream_options: None,
         };
 
         debug!(
@@ -197,6 +332,102 @@ impl LlmProvider for OllamaProvider {
         self.openai_client.chat(request, cancellation_token).await
     }
 
+    async fn chat_stream<F>(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<Tool>>,
+        _n: Option<u32>,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+        stop: Option<Vec<String>>,
+        _extra_config: Option<HashMap<String, String>>,
+        cancellation_token: Option<CancellationToken>,
+        on_event: F,
+    ) -> Result<ChatResponse, LlmError>
+    where
+        F: Fn(ChatStreamEvent) + Send + Sync + 'static,
+    {
+        if !self.supports_endpoint(EndpointType::Chat) {
+            return Err(LlmError::UnsupportedEndpoint("chat".to_string()));
+        }
+
+        self.check_rate_limit().await?;
+
+        let request = OllamaChatRequest {
+            model: self.config.model.clone(),
+            messages: messages
+                .into_iter()
+                .map(|m| OllamaChatMessage {
+                    role: m.role,
+                    content: m.content,
+                    tool_call_id: m.tool_call_id,
+                })
+                .collect(),
+            tools,
+            stream: true,
+            options: OllamaChatOptions {
+                temperature,
+                num_predict: max_tokens,
+                stop,
+            },
+        };
+
+        debug!(
+            "Ollama streaming chat request: model={} messages={}",
+            request.model,
+            request.messages.len()
+        );
+
+        let send = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&request)
+            .send();
+        let response = match &cancellation_token {
+            Some(token) => {
+                tokio::select! {
+                    result = send => result,
+                    _ = token.cancelled() => {
+                        debug!("Ollama chat request cancelled");
+                        return Err(LlmError::Cancelled);
+                    }
+                }
+            }
+            None => send.await,
+        }
+        .map_err(|e| LlmError::ProviderError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(LlmError::ProviderError(format!(
+                "Ollama API error (status {}): {}",
+                status, error_text
+            )));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut lines = LineBuffer::default();
+        let mut acc = StreamAccumulator::default();
+        acc.model = self.config.model.clone();
+
+        'read: while let Some(chunk) = next_chunk(&mut stream, cancellation_token.as_ref()).await? {
+            for line in lines.push(&chunk) {
+                if Self::apply_chat_chunk(&line, &mut acc, &on_event)? {
+                    break 'read;
+                }
+            }
+        }
+        if let Some(line) = lines.finish() {
+            Self::apply_chat_chunk(&line, &mut acc, &on_event)?;
+        }
+
+        Ok(acc.finish())
+    }
+
     async fn complete(
         &self,
         prompt: String,
@@ -603,6 +834,53 @@ mod tests {
         assert!(provider.is_err());
     }
 
+    #[test]
+    fn test_apply_chat_chunks() {
+        use std::sync::Mutex;
+
+        let events = Mutex::new(Vec::new());
+        let on_event = |event: ChatStreamEvent| events.lock().unwrap().push(event);
+        let mut acc = StreamAccumulator::default();
+
+        let lines = [
+            r#"{"model":"llama3","message":{"role":"assistant","content":"Rolling"},"done":false}"#,
+            r#"{"model":"llama3","message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"roll_dice","arguments":{"dice":"1d20"}}}]},"done":false}"#,
+            r#"{"model":"llama3","message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":12,"eval_count":5,"total_duration":2000000000,"eval_duration":500000000}"#,
+        ];
+        let done: Vec<bool> = lines
+            .iter()
+            .map(|line| OllamaProvider::apply_chat_chunk(line, &mut acc, &on_event).unwrap())
+            .collect();
+        assert_eq!(done, vec![false, false, true]);
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(
+            events[0],
+            ChatStreamEvent::ContentDelta("Rolling".to_string())
+        );
+        assert!(
+            matches!(&events[1], ChatStreamEvent::ToolCallStarted { name, .. } if name == "roll_dice")
+        );
+        assert_eq!(events.len(), 2);
+
+        let response = acc.finish();
+        assert_eq!(response.content, "Rolling");
+        assert_eq!(response.usage.unwrap().total_tokens, 17);
+        assert_eq!(response.timing.unwrap().total_duration_ms, 2000);
+        assert_eq!(
+            response.tool_calls.unwrap()[0].function.arguments["dice"],
+            "1d20"
+        );
+    }
+
+    #[test]
+    fn test_apply_chat_chunk_error() {
+        let mut acc = StreamAccumulator::default();
+        let result =
+            OllamaProvider::apply_chat_chunk(r#"{"error":"model not found"}"#, &mut acc, &|_| {});
+        assert!(matches!(result, Err(LlmError::ProviderError(_))));
+    }
+
     #[test]
     fn test_supported_endpoints() {
         let config = create_test_config();
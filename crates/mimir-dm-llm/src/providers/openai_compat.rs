@@ -6,6 +6,8 @@
 //! - Groq (via /openai/v1/chat/completions)
 //! - OpenAI (via /v1/chat/completions)
 //! - vLLM, LM Studio, and other compatible providers
+//!
+//! Chat requests can be sent whole or streamed as server-sent events.
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -13,7 +15,10 @@ use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, warn};
 
-use crate::traits::{ChatResponse, CompletionResponse, LlmError, Message, Tool, ToolCall, Usage};
+use crate::streaming::{next_chunk, sse_data, LineBuffer, StreamAccumulator};
+use crate::traits::{
+    ChatResponse, ChatStreamEvent, CompletionResponse, LlmError, Message, Tool, ToolCall, Usage,
+};
 
 /// Configuration for rate limit retry behavior
 #[derive(Debug, Clone)]
@@ -58,6 +63,16 @@ pub struct OpenAiChatRequest {
     /// Whether to stream the response
     #[serde(default)]
     pub stream: bool,
+    /// Options for streamed responses
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_options: Option<OpenAiStreamOptions>,
+}
+
+/// Options for streamed chat responses
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenAiStreamOptions {
+    /// Send token usage in a final chunk
+    pub include_usage: bool,
 }
 
 /// OpenAI-compatible message format
@@ -131,6 +146,73 @@ pub struct OpenAiUsage {
     pub total_tokens: u32,
 }
 
+/// One server-sent event of a streamed chat response
+#[derive(Debug, Deserialize)]
+pub struct OpenAiStreamChunk {
+    /// Model used
+    #[serde(default)]
+    pub model: String,
+    /// Incremental choices
+    #[serde(default)]
+    pub choices: Vec<OpenAiStreamChoice>,
+    /// Token usage, sent with the last chunk when requested
+    pub usage: Option<OpenAiUsage>,
+    /// Groq-specific metadata, which carries usage on Groq
+    pub x_groq: Option<GroqStreamMetadata>,
+    /// Error reported in the middle of a stream
+    pub error: Option<OpenAiErrorDetail>,
+}
+
+/// Groq metadata attached to stream chunks
+#[derive(Debug, Deserialize)]
+pub struct GroqStreamMetadata {
+    /// Token usage for the whole response
+    pub usage: Option<OpenAiUsage>,
+}
+
+/// Incremental choice in a stream chunk
+#[derive(Debug, Deserialize)]
+pub struct OpenAiStreamChoice {
+    /// New content for this choice
+    #[serde(default)]
+    pub delta: OpenAiDelta,
+    /// Reason for stopping, on the last chunk
+    #[allow(dead_code)]
+    pub finish_reason: Option<String>,
+}
+
+/// Content added by one stream chunk
+#[derive(Debug, Default, Deserialize)]
+pub struct OpenAiDelta {
+    /// Reply text
+    pub content: Option<String>,
+    /// Reasoning text, for providers that stream it separately
+    pub reasoning: Option<String>,
+    /// Tool call fragments
+    pub tool_calls: Option<Vec<OpenAiToolCallDelta>>,
+}
+
+/// Fragment of a tool call in a stream chunk
+#[derive(Debug, Deserialize)]
+pub struct OpenAiToolCallDelta {
+    /// Which tool call the fragment belongs to
+    #[serde(default)]
+    pub index: usize,
+    /// Tool call ID, on the first fragment
+    pub id: Option<String>,
+    /// Function name and argument text
+    pub function: Option<OpenAiFunctionDelta>,
+}
+
+/// Function part of a tool call fragment
+#[derive(Debug, Deserialize)]
+pub struct OpenAiFunctionDelta {
+    /// Function name, on the first fragment
+    pub name: Option<String>,
+    /// Next piece of the JSON argument string
+    pub arguments: Option<String>,
+}
+
 /// OpenAI-compatible error response
 #[derive(Debug, Deserialize)]
 pub struct OpenAiErrorResponse {
@@ -243,12 +325,12 @@ impl OpenAiCompatClient {
         Duration::from_millis(delay_ms.min(self.retry_config.max_delay_ms))
     }
 
-    /// Send a chat completion request with automatic retry on rate limits
-    pub async fn chat(
+    /// Send a chat request, retrying on rate limits, and return the successful response
+    async fn send_chat_request(
         &self,
-        request: OpenAiChatRequest,
-        cancellation_token: Option<CancellationToken>,
-    ) -> Result<ChatResponse, LlmError> {
+        request: &OpenAiChatRequest,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<reqwest::Response, LlmError> {
         let url = format!("{}/chat/completions", self.base_url);
         let mut attempt = 0;
 
@@ -262,8 +344,9 @@ impl OpenAiCompatClient {
             );
 
             // Build the request (need to rebuild for each attempt)
-            let request_json = serde_json::to_string(&request)
-                .map_err(|e| LlmError::ProviderError(format!("Failed to serialize request: {}", e)))?;
+            let request_json = serde_json::to_string(request).map_err(|e| {
+                LlmError::ProviderError(format!("Failed to serialize request: {}", e))
+            })?;
 
             let mut req_builder = self
                 .client
@@ -277,7 +360,7 @@ impl OpenAiCompatClient {
             }
 
             // Execute with optional cancellation
-            let response = if let Some(ref token) = cancellation_token {
+            let response = if let Some(token) = cancellation_token {
                 tokio::select! {
                     result = req_builder.send() => {
                         result.map_err(|e| LlmError::ProviderError(format!("Request failed: {}", e)))?
@@ -316,7 +399,7 @@ impl OpenAiCompatClient {
                         );
 
                         // Wait with cancellation support
-                        if let Some(ref token) = cancellation_token {
+                        if let Some(token) = cancellation_token {
                             tokio::select! {
                                 _ = tokio::time::sleep(backoff) => {}
                                 _ = token.cancelled() => {
@@ -361,55 +444,181 @@ impl OpenAiCompatClient {
                 )));
             }
 
-            // Read response with optional cancellation
-            let response_text = if let Some(ref token) = cancellation_token {
-                tokio::select! {
-                    result = response.text() => {
-                        result.map_err(|e| LlmError::ProviderError(format!("Failed to read response: {}", e)))?
-                    }
-                    _ = token.cancelled() => {
-                        debug!("Response reading cancelled");
-                        return Err(LlmError::Cancelled);
-                    }
+            return Ok(response);
+        }
+    }
+
+    /// Send a chat completion request with automatic retry on rate limits
+    pub async fn chat(
+        &self,
+        request: OpenAiChatRequest,
+        cancellation_token: Option<CancellationToken>,
+    ) -> Result<ChatResponse, LlmError> {
+        let response = self
+            .send_chat_request(&request, cancellation_token.as_ref())
+            .await?;
+
+        // Read response with optional cancellation
+        let response_text = if let Some(ref token) = cancellation_token {
+            tokio::select! {
+                result = response.text() => {
+                    result.map_err(|e| LlmError::ProviderError(format!("Failed to read response: {}", e)))?
                 }
-            } else {
-                response
-                    .text()
-                    .await
-                    .map_err(|e| LlmError::ProviderError(format!("Failed to read response: {}", e)))?
-            };
+                _ = token.cancelled() => {
+                    debug!("Response reading cancelled");
+                    return Err(LlmError::Cancelled);
+                }
+            }
+        } else {
+            response
+                .text()
+                .await
+                .map_err(|e| LlmError::ProviderError(format!("Failed to read response: {}", e)))?
+        };
 
-            debug!("Response size: {} bytes", response_text.len());
+        debug!("Response size: {} bytes", response_text.len());
 
-            let api_response: OpenAiChatResponse =
-                serde_json::from_str(&response_text).map_err(|e| {
-                    error!("Failed to parse response: {}", e);
-                    if response_text.len() > 500 {
-                        error!("Response preview: {}...", &response_text[..500]);
-                    } else {
-                        error!("Full response: {}", response_text);
-                    }
-                    LlmError::ProviderError(format!("Failed to parse response: {}", e))
-                })?;
-
-            // Extract first choice
-            let choice = api_response
-                .choices
-                .first()
-                .ok_or_else(|| LlmError::ProviderError("No choices in response".to_string()))?;
-
-            return Ok(ChatResponse {
-                content: choice.message.content.clone().unwrap_or_default(),
-                usage: Some(Usage {
-                    prompt_tokens: api_response.usage.prompt_tokens,
-                    completion_tokens: api_response.usage.completion_tokens,
-                    total_tokens: api_response.usage.total_tokens,
-                }),
-                timing: None, // OpenAI format doesn't include detailed timing
-                model: api_response.model,
-                tool_calls: choice.message.tool_calls.clone(),
+        let api_response: OpenAiChatResponse =
+            serde_json::from_str(&response_text).map_err(|e| {
+                error!("Failed to parse response: {}", e);
+                if response_text.len() > 500 {
+                    error!("Response preview: {}...", &response_text[..500]);
+                } else {
+                    error!("Full response: {}", response_text);
+                }
+                LlmError::ProviderError(format!("Failed to parse response: {}", e))
+            })?;
+
+        // Extract first choice
+        let choice = api_response
+            .choices
+            .first()
+            .ok_or_else(|| LlmError::ProviderError("No choices in response".to_string()))?;
+
+        Ok(ChatResponse {
+            content: choice.message.content.clone().unwrap_or_default(),
+            usage: Some(Usage {
+                prompt_tokens: api_response.usage.prompt_tokens,
+                completion_tokens: api_response.usage.completion_tokens,
+                total_tokens: api_response.usage.total_tokens,
+            }),
+            timing: None, // OpenAI format doesn't include detailed timing
+            model: api_response.model,
+            tool_calls: choice.message.tool_calls.clone(),
+        })
+    }
+
+    /// Send a chat request and stream the reply as server-sent events
+    ///
+    /// Calls `on_event` for each content delta and each new tool call, and
+    /// returns the assembled response once the stream ends.
+    pub async fn chat_stream<F>(
+        &self,
+        mut request: OpenAiChatRequest,
+        cancellation_token: Option<CancellationToken>,
+        on_event: F,
+    ) -> Result<ChatResponse, LlmError>
+    where
+        F: Fn(ChatStreamEvent),
+    {
+        request.stream = true;
+        request.stream_options = Some(OpenAiStreamOptions {
+            include_usage: true,
+        });
+
+        let response = self
+            .send_chat_request(&request, cancellation_token.as_ref())
+            .await?;
+
+        let mut stream = response.bytes_stream();
+        let mut lines = LineBuffer::default();
+        let mut acc = StreamAccumulator::default();
+        acc.model = request.model.clone();
+
+        'read: while let Some(chunk) = next_chunk(&mut stream, cancellation_token.as_ref()).await? {
+            for line in lines.push(&chunk) {
+                if Self::apply_stream_line(&line, &mut acc, &on_event)? {
+                    break 'read;
+                }
+            }
+        }
+        if let Some(line) = lines.finish() {
+            Self::apply_stream_line(&line, &mut acc, &on_event)?;
+        }
+
+        Ok(acc.finish())
+    }
+
+    /// Apply one SSE line to the response being built
+    ///
+    /// Returns `true` once the `[DONE]` marker is seen.
+    fn apply_stream_line<F>(
+        line: &str,
+        acc: &mut StreamAccumulator,
+        on_event: &F,
+    ) -> Result<bool, LlmError>
+    where
+        F: Fn(ChatStreamEvent),
+    {
+        let Some(data) = sse_data(line) else {
+            return Ok(false);
+        };
+        if data.trim() == "[DONE]" {
+            return Ok(true);
+        }
+
+        let chunk: OpenAiStreamChunk = serde_json::from_str(data).map_err(|e| {
+            error!("Failed to parse stream chunk: {}", e);
+            LlmError::ProviderError(format!("Failed to parse stream chunk: {}", e))
+        })?;
+
+        if let Some(error) = chunk.error {
+            error!("OpenAI-compat stream error: {}", error.message);
+            return Err(LlmError::ProviderError(format!(
+                "API error: {}",
+                error.message
+            )));
+        }
+        if !chunk.model.is_empty() {
+            acc.model = chunk.model;
+        }
+        if let Some(usage) = chunk.usage.or(chunk.x_groq.and_then(|x| x.usage)) {
+            acc.usage = Some(Usage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
             });
         }
+
+        // Only the first choice is used, as with non-streamed responses
+        let Some(choice) = chunk.choices.into_iter().next() else {
+            return Ok(false);
+        };
+        let delta = choice.delta;
+
+        if let Some(reasoning) = delta.reasoning.as_deref() {
+            if let Some(event) = acc.push_thinking(reasoning) {
+                on_event(event);
+            }
+        }
+        if let Some(content) = delta.content.as_deref() {
+            if let Some(event) = acc.push_content(content) {
+                on_event(event);
+            }
+        }
+        for call in delta.tool_calls.unwrap_or_default() {
+            let (name, arguments) = match &call.function {
+                Some(function) => (function.name.as_deref(), function.arguments.as_deref()),
+                None => (None, None),
+            };
+            if let Some(event) =
+                acc.push_tool_fragment(call.index, call.id.as_deref(), name, arguments)
+            {
+                on_event(event);
+            }
+        }
+
+        Ok(false)
     }
 
     /// Send a completion request (uses chat endpoint with single user message)
@@ -437,6 +646,7 @@ impl OpenAiCompatClient {
             stop,
             tools: None,
             stream: false,
+            stream_options: None,
         };
 
         let chat_response = self.chat(request, None).await?;
@@ -530,6 +740,56 @@ mod tests {
         assert!(response.choices[0].message.tool_calls.is_some());
     }
 
+    #[test]
+    fn test_apply_stream_lines() {
+        use std::sync::Mutex;
+
+        let events = Mutex::new(Vec::new());
+        let on_event = |event: ChatStreamEvent| events.lock().unwrap().push(event);
+        let mut acc = StreamAccumulator::default();
+
+        let lines = [
+            r#"data: {"model":"llama-3.3-70b","choices":[{"index":0,"delta":{"role":"assistant","content":"Let me "},"finish_reason":null}]}"#,
+            r#"data: {"model":"llama-3.3-70b","choices":[{"index":0,"delta":{"content":"check."},"finish_reason":null}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"search_monsters","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"name\":\"goblin\"}"}}]}}]}"#,
+            r#"data: {"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}],"x_groq":{"usage":{"prompt_tokens":40,"completion_tokens":12,"total_tokens":52}}}"#,
+            "data: [DONE]",
+        ];
+        let done: Vec<bool> = lines
+            .iter()
+            .map(|line| OpenAiCompatClient::apply_stream_line(line, &mut acc, &on_event).unwrap())
+            .collect();
+        assert_eq!(done, vec![false, false, false, false, false, true]);
+
+        let events = events.into_inner().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            ChatStreamEvent::ContentDelta("Let me ".to_string())
+        );
+
+        let response = acc.finish();
+        assert_eq!(response.content, "Let me check.");
+        assert_eq!(response.model, "llama-3.3-70b");
+        assert_eq!(response.usage.unwrap().total_tokens, 52);
+        let calls = response.tool_calls.unwrap();
+        assert_eq!(calls[0].id, "call_1");
+        assert_eq!(calls[0].function.name, "search_monsters");
+        assert_eq!(calls[0].function.arguments, "{\"name\":\"goblin\"}");
+    }
+
+    #[test]
+    fn test_apply_stream_line_error() {
+        let mut acc = StreamAccumulator::default();
+        let result = OpenAiCompatClient::apply_stream_line(
+            r#"data: {"error":{"message":"overloaded","type":"server_error","code":null}}"#,
+            &mut acc,
+            &|_| {},
+        );
+        assert!(matches!(result, Err(LlmError::ProviderError(_))));
+    }
+
     #[test]
     fn test_parse_retry_delay_ms() {
         let msg = "Rate limit reached. Please try again in 970ms.";
@@ -0,0 +1,353 @@
+//! Streaming chat helpers
+//!
+//! Providers stream chat replies either as newline-delimited JSON (Ollama's
+//! native API) or as server-sent events (OpenAI-compatible APIs). Both are
+//! line based, so a [`LineBuffer`] turns raw body chunks into complete
+//! lines, and a [`StreamAccumulator`] collects content and tool-call
+//! fragments into the final [`ChatResponse`] while reporting progress as
+//! [`ChatStreamEvent`]s.
+
+use futures::{Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::traits::{
+    ChatResponse, ChatStreamEvent, LlmError, Timing, ToolCall, ToolCallFunction, Usage,
+};
+
+/// Opening tag used to keep separately streamed reasoning inside the content
+const THINK_OPEN: &str = "<think>";
+/// Closing tag for streamed reasoning
+const THINK_CLOSE: &str = "</think>";
+
+/// Collects bytes and yields complete lines.
+#[derive(Debug, Default)]
+pub(crate) struct LineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Add a body chunk and return every line it completed.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if !line.is_empty() {
+                lines.push(line.to_string());
+            }
+        }
+        lines
+    }
+
+    /// Return whatever is left once the body has ended.
+    pub(crate) fn finish(&mut self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.buffer))
+            .trim()
+            .to_string();
+        (!rest.is_empty()).then_some(rest)
+    }
+}
+
+/// Payload of an SSE `data:` line; other fields and comments are ignored.
+pub(crate) fn sse_data(line: &str) -> Option<&str> {
+    let data = line.strip_prefix("data:")?;
+    Some(data.strip_prefix(' ').unwrap_or(data))
+}
+
+/// Wait for the next body chunk, giving up if the request is cancelled.
+pub(crate) async fn next_chunk<S, B>(
+    stream: &mut S,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Option<B>, LlmError>
+where
+    S: Stream<Item = Result<B, reqwest::Error>> + Unpin,
+{
+    let next = match cancellation_token {
+        Some(token) => {
+            tokio::select! {
+                next = stream.next() => next,
+                _ = token.cancelled() => {
+                    debug!("Chat stream cancelled");
+                    return Err(LlmError::Cancelled);
+                }
+            }
+        }
+        None => stream.next().await,
+    };
+
+    next.transpose()
+        .map_err(|e| LlmError::ProviderError(format!("Stream error: {}", e)))
+}
+
+/// A tool call whose pieces are still arriving
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+    /// Complete arguments, for providers that send them in one piece
+    parsed_arguments: Option<serde_json::Value>,
+    announced: bool,
+}
+
+/// Builds a [`ChatResponse`] from streamed fragments.
+#[derive(Debug, Default)]
+pub(crate) struct StreamAccumulator {
+    content: String,
+    thinking: bool,
+    tool_calls: Vec<PartialToolCall>,
+    /// Model reported by the provider
+    pub(crate) model: String,
+    /// Token usage, usually sent with the last chunk
+    pub(crate) usage: Option<Usage>,
+    /// Timing information, usually sent with the last chunk
+    pub(crate) timing: Option<Timing>,
+}
+
+impl StreamAccumulator {
+    /// Append reply text.
+    ///
+    /// Empty deltas leave an open think block alone; Ollama sends an empty
+    /// `content` alongside every thinking chunk.
+    pub(crate) fn push_content(&mut self, delta: &str) -> Option<ChatStreamEvent> {
+        if delta.is_empty() {
+            return None;
+        }
+
+        let mut text = String::new();
+        if self.thinking {
+            self.thinking = false;
+            text.push_str(THINK_CLOSE);
+        }
+        text.push_str(delta);
+        self.emit_text(text)
+    }
+
+    /// Append reasoning text sent separately from the reply.
+    ///
+    /// It is wrapped in `<think>` tags so it is handled like models that
+    /// reason inline.
+    pub(crate) fn push_thinking(&mut self, delta: &str) -> Option<ChatStreamEvent> {
+        let mut text = String::new();
+        if !self.thinking {
+            self.thinking = true;
+            text.push_str(THINK_OPEN);
+        }
+        text.push_str(delta);
+        self.emit_text(text)
+    }
+
+    fn emit_text(&mut self, text: String) -> Option<ChatStreamEvent> {
+        if text.is_empty() {
+            return None;
+        }
+        self.content.push_str(&text);
+        Some(ChatStreamEvent::ContentDelta(text))
+    }
+
+    /// Add a fragment of a tool call.
+    ///
+    /// OpenAI-style streams send the ID and name once and the arguments as
+    /// string pieces, all keyed by the call's index. Returns a
+    /// [`ChatStreamEvent::ToolCallStarted`] the first time the name is known.
+    pub(crate) fn push_tool_fragment(
+        &mut self,
+        index: usize,
+        id: Option<&str>,
+        name: Option<&str>,
+        arguments: Option<&str>,
+    ) -> Option<ChatStreamEvent> {
+        if self.tool_calls.len() <= index {
+            self.tool_calls
+                .resize_with(index + 1, PartialToolCall::default);
+        }
+        let call = &mut self.tool_calls[index];
+
+        if let Some(id) = id.filter(|id| !id.is_empty()) {
+            call.id = Some(id.to_string());
+        }
+        if let Some(name) = name {
+            call.name.push_str(name);
+        }
+        if let Some(arguments) = arguments {
+            call.arguments.push_str(arguments);
+        }
+
+        if call.announced || call.name.is_empty() {
+            return None;
+        }
+        call.announced = true;
+        Some(ChatStreamEvent::ToolCallStarted {
+            index,
+            id: call.id.clone().unwrap_or_else(|| fallback_id(index)),
+            name: call.name.clone(),
+        })
+    }
+
+    /// Add a tool call that arrived in one piece.
+    pub(crate) fn push_tool_call(
+        &mut self,
+        id: Option<String>,
+        name: String,
+        arguments: serde_json::Value,
+    ) -> ChatStreamEvent {
+        let index = self.tool_calls.len();
+        let id = id.filter(|id| !id.is_empty());
+        let event = ChatStreamEvent::ToolCallStarted {
+            index,
+            id: id.clone().unwrap_or_else(|| fallback_id(index)),
+            name: name.clone(),
+        };
+        self.tool_calls.push(PartialToolCall {
+            id,
+            name,
+            arguments: String::new(),
+            parsed_arguments: Some(arguments),
+            announced: true,
+        });
+        event
+    }
+
+    /// Close any open reasoning block and build the final response.
+    pub(crate) fn finish(mut self) -> ChatResponse {
+        if self.thinking {
+            self.content.push_str(THINK_CLOSE);
+        }
+
+        let tool_calls: Vec<ToolCall> = self
+            .tool_calls
+            .into_iter()
+            .enumerate()
+            .filter(|(_, call)| !call.name.is_empty())
+            .map(|(index, call)| {
+                let arguments = match call.parsed_arguments {
+                    Some(arguments) => arguments,
+                    None if call.arguments.trim().is_empty() => {
+                        serde_json::Value::String("{}".to_string())
+                    }
+                    None => serde_json::Value::String(call.arguments),
+                };
+                ToolCall {
+                    id: call.id.unwrap_or_else(|| fallback_id(index)),
+                    function: ToolCallFunction {
+                        name: call.name,
+                        arguments,
+                    },
+                }
+            })
+            .collect();
+
+        ChatResponse {
+            content: self.content,
+            usage: self.usage,
+            timing: self.timing,
+            model: self.model,
+            tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+        }
+    }
+}
+
+/// ID for tool calls from providers that don't assign one
+fn fallback_id(index: usize) -> String {
+    format!("call_{}", index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_joins_split_lines() {
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.push(b"{\"a\":").is_empty());
+        assert_eq!(buffer.push(b"1}\r\n\n{\"b\""), vec!["{\"a\":1}"]);
+        assert_eq!(buffer.push(b":2}\n"), vec!["{\"b\":2}"]);
+        assert!(buffer.push(b"tail").is_empty());
+        assert_eq!(buffer.finish().as_deref(), Some("tail"));
+        assert!(buffer.finish().is_none());
+    }
+
+    #[test]
+    fn test_sse_data() {
+        assert_eq!(sse_data("data: {\"x\":1}"), Some("{\"x\":1}"));
+        assert_eq!(sse_data("data:[DONE]"), Some("[DONE]"));
+        assert_eq!(sse_data(": keep-alive"), None);
+        assert_eq!(sse_data("event: message"), None);
+    }
+
+    #[test]
+    fn test_tool_call_fragments_are_assembled() {
+        let mut acc = StreamAccumulator::default();
+
+        let started = acc.push_tool_fragment(0, Some("call_a"), Some("get_weather"), Some(""));
+        assert!(matches!(
+            started,
+            Some(ChatStreamEvent::ToolCallStarted { index: 0, ref name, .. }) if name == "get_weather"
+        ));
+        assert!(acc
+            .push_tool_fragment(0, None, None, Some("{\"city\":"))
+            .is_none());
+        assert!(acc
+            .push_tool_fragment(0, None, None, Some("\"Neverwinter\"}"))
+            .is_none());
+        acc.push_tool_fragment(1, Some("call_b"), Some("roll_dice"), None);
+
+        let response = acc.finish();
+        let calls = response.tool_calls.unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(
+            calls[0].function.arguments,
+            serde_json::Value::String("{\"city\":\"Neverwinter\"}".to_string())
+        );
+        assert_eq!(calls[1].function.name, "roll_dice");
+        assert_eq!(calls[1].function.arguments, "{}");
+    }
+
+    #[test]
+    fn test_thinking_is_wrapped_in_tags() {
+        let mut acc = StreamAccumulator::default();
+        acc.push_thinking("Let me ");
+        acc.push_thinking("think.");
+        let event = acc.push_content("Hello");
+        assert!(
+            matches!(event, Some(ChatStreamEvent::ContentDelta(ref t)) if t == "</think>Hello")
+        );
+        acc.push_content("!");
+
+        let response = acc.finish();
+        assert_eq!(response.content, "<think>Let me think.</think>Hello!");
+        assert!(response.tool_calls.is_none());
+    }
+
+    #[test]
+    fn test_empty_content_keeps_think_block_open() {
+        let mut acc = StreamAccumulator::default();
+        acc.push_thinking("a");
+        assert!(acc.push_content("").is_none());
+        acc.push_thinking("b");
+        assert!(acc.push_content("").is_none());
+        acc.push_content("Done");
+
+        let response = acc.finish();
+        assert_eq!(response.content, "<think>ab</think>Done");
+    }
+
+    #[test]
+    fn test_complete_tool_call_gets_fallback_id() {
+        let mut acc = StreamAccumulator::default();
+        acc.push_tool_call(
+            None,
+            "roll_dice".to_string(),
+            serde_json::json!({"dice": "1d20"}),
+        );
+
+        let calls = acc.finish().tool_calls.unwrap();
+        assert_eq!(calls[0].id, "call_0");
+        assert_eq!(calls[0].function.arguments["dice"], "1d20");
+    }
+}
@@ -6,9 +6,9 @@ pub mod tool;
 
 // Re-export commonly used types
 pub use provider::{
-    ChatResponse, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider, Message, ModelInfo,
-    ModelPullProgress, RateLimitState, Timing, Tool, ToolCall, ToolCallFunction, ToolFunction,
-    Usage,
+    ChatResponse, ChatStreamEvent, CompletionResponse, EmbeddingResponse, LlmError, LlmProvider,
+    Message, ModelInfo, ModelPullProgress, RateLimitState, Timing, Tool, ToolCall,
+    ToolCallFunction, ToolFunction, Usage,
 };
 
 pub use context::ToolContext;
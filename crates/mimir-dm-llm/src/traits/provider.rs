@@ -9,6 +9,7 @@
 //! - `ModelConfig`: Configuration for a specific model, including rate limits
 //! - `RateLimitState`: Internal state for tracking rate limiting
 //! - Response types: `ChatResponse`, `CompletionResponse`, `EmbeddingResponse`
+//! - `ChatStreamEvent`: Progress reported while a chat reply is streamed
 //!
 //! ## Rate Limiting
 //!
@@ -97,6 +98,22 @@ pub struct ChatResponse {
     pub tool_calls: Option<Vec<ToolCall>>,
 }
 
+/// Progress reported while a chat reply is streamed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ChatStreamEvent {
+    /// A piece of the reply text
+    ContentDelta(String),
+    /// The model has started a tool call; its arguments may still be arriving
+    ToolCallStarted {
+        /// Position of the call within the reply
+        index: usize,
+        /// Tool call ID
+        id: String,
+        /// Name of the tool being called
+        name: String,
+    },
+}
+
 /// Response from completion endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompletionResponse {
@@ -329,6 +346,55 @@ pub trait LlmProvider: Send + Sync {
         Err(LlmError::NotImplemented("chat".to_string()))
     }
 
+    /// Streaming chat endpoint
+    ///
+    /// Reports the reply through `on_event` as it is generated and returns the
+    /// assembled response, including any tool calls, once the stream ends.
+    /// The default implementation makes a regular [`LlmProvider::chat`] call
+    /// and reports the whole reply at once.
+    #[allow(clippy::too_many_arguments)]
+    async fn chat_stream<F>(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<Tool>>,
+        n: Option<u32>,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+        stop: Option<Vec<String>>,
+        extra_config: Option<HashMap<String, String>>,
+        cancellation_token: Option<CancellationToken>,
+        on_event: F,
+    ) -> Result<ChatResponse, LlmError>
+    where
+        F: Fn(ChatStreamEvent) + Send + Sync + 'static,
+    {
+        let response = self
+            .chat(
+                messages,
+                tools,
+                n,
+                temperature,
+                max_tokens,
+                stop,
+                extra_config,
+                cancellation_token,
+            )
+            .await?;
+
+        for (index, call) in response.tool_calls.iter().flatten().enumerate() {
+            on_event(ChatStreamEvent::ToolCallStarted {
+                index,
+                id: call.id.clone(),
+                name: call.function.name.clone(),
+            });
+        }
+        if !response.content.is_empty() {
+            on_event(ChatStreamEvent::ContentDelta(response.content.clone()));
+        }
+
+        Ok(response)
+    }
+
     /// Completion endpoint with default "not supported" implementation
     async fn complete(
         &self,
@@ -412,6 +412,64 @@ This is a test document.
         assert!(pdf_bytes.len() > 5000, "Character sheet PDF seems too small");
         assert_eq!(&pdf_bytes[0..4], b"%PDF", "Output is not a valid PDF");
 
+        // Sheet with combat state (dying, conditions, concentration)
+        let mut in_combat = data.clone();
+        in_combat["current_hp"] = serde_json::json!(0);
+        in_combat["temp_hp"] = serde_json::json!(4);
+        in_combat["death_saves"] = serde_json::json!({"successes": 1, "failures": 2});
+        in_combat["exhaustion_level"] = serde_json::json!(1);
+        in_combat["conditions"] = serde_json::json!(["Unconscious", "Prone"]);
+        in_combat["concentration"] = serde_json::json!("Bless");
+        let result = service.render_to_pdf("character/sheet.typ", in_combat);
+        assert!(
+            result.is_ok(),
+            "Character sheet with combat state render failed: {:?}",
+            result.err()
+        );
+
+        // Sheets with AC and attacks derived from catalog items, plus
+        // optional features chosen at level up
+        let derived_stats = serde_json::json!({
+            "armor_class": 19,
+            "ac_breakdown": ["Chain Mail 16", "Shield +2", "Defense +1"],
+            "attacks": [{
+                "name": "Warhammer",
+                "attack_bonus": 6,
+                "damage": "1d8+3 bludgeoning",
+                "versatile_damage": null,
+                "range": null,
+                "properties": ["Versatile"],
+                "from_catalog": true
+            }]
+        });
+        let mut with_derived = data.clone();
+        with_derived["derived_stats"] = derived_stats.clone();
+        with_derived["optional_features"] = serde_json::json!([{
+            "name": "Defense",
+            "source": "PHB",
+            "feature_type": "FS:F",
+            "class_name": "Fighter",
+            "level": 1
+        }]);
+        let result = service.render_to_pdf("character/sheet.typ", with_derived.clone());
+        assert!(
+            result.is_ok(),
+            "Character sheet with derived stats render failed: {:?}",
+            result.err()
+        );
+
+        let combined = serde_json::json!({
+            "character": with_derived,
+            "derived_stats": derived_stats,
+            "include_spell_cards": false
+        });
+        let result = service.render_to_pdf("character/sheet-with-spells.typ", combined);
+        assert!(
+            result.is_ok(),
+            "Combined character sheet render failed: {:?}",
+            result.err()
+        );
+
         // Also test summary template
         let result = service.render_to_pdf("character/summary.typ", data);
         assert!(result.is_ok(), "Character summary render failed: {:?}", result.err());
@@ -609,7 +667,17 @@ This is a test document.
         let encounter_data = serde_json::json!({
             "title": "Goblin Ambush",
             "monsters": [goblin.clone(), goblin.clone(), goblin.clone()],
-            "notes": "The goblins attack from hiding in the trees. They flee if two are killed."
+            "notes": "The goblins attack from hiding in the trees. They flee if two are killed.",
+            "difficulty": {
+                "party_levels": [1, 1, 1, 1],
+                "thresholds": { "easy": 100, "medium": 200, "hard": 300, "deadly": 400 },
+                "monster_count": 3,
+                "base_xp": 150,
+                "multiplier": 2.0,
+                "adjusted_xp": 300,
+                "difficulty": "hard",
+                "xp_per_character": 37
+            }
         });
 
         let result = service.render_to_pdf("monsters/encounter.typ", encounter_data);
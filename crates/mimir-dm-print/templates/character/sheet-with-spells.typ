@@ -151,14 +151,17 @@
 #let proficiencies = get(char-data, "proficiencies", default: ())
 #let prof-saves = get(proficiencies, "saves", default: ())
 
-// Calculate AC (basic formula: 10 + dex mod, or from equipped armor)
+// AC and attacks derived from equipped catalog items, when supplied
+#let derived = get(data, "derived_stats", default: none)
+#let attacks = get(derived, "attacks", default: ())
+
 #let equipped = get(char-data, "equipped", default: ())
 #let has-armor = get(equipped, "armor", default: none) != none
 #let has-shield = get(equipped, "shield", default: none) != none
-// Simplified AC calculation - would need armor data for real calculation
+// Fallback without derived stats: 10 + dex mod, +2 for a shield
 #let base-ac = 10 + calc.floor((dex-score - 10) / 2)
 #let shield-bonus = if has-shield { 2 } else { 0 }
-#let ac = base-ac + shield-bonus
+#let ac = get(derived, "armor_class", default: base-ac + shield-bonus)
 
 #grid(
   columns: (2fr, 1fr),
@@ -192,6 +195,30 @@
 
     v(spacing.sm)
 
+    // Attacks
+    if attacks.len() > 0 {
+      info-box(title: "Attacks")[
+        #for attack in attacks {
+          let bonus = get(attack, "attack_bonus", default: 0)
+          let versatile = get(attack, "versatile_damage", default: none)
+          let range = get(attack, "range", default: none)
+          text(size: sizes.sm)[
+            #text(weight: "bold")[#get(attack, "name", default: "")]
+            #h(1fr)
+            #if bonus >= 0 [+]#bonus
+          ]
+          linebreak()
+          text(size: sizes.xs)[
+            #get(attack, "damage", default: "—")
+            #if versatile != none [ (two-handed #versatile)]
+            #if range != none [ · #range]
+          ]
+          linebreak()
+        }
+      ]
+      v(spacing.sm)
+    }
+
     // Saving Throws
     info-box(title: "Saving Throws")[
       #let saves = (
@@ -345,6 +372,7 @@
   {
     let class-features = get(char-data, "class_features", default: ())
     let feats = get(char-data, "feats", default: ())
+    let optional-features = get(char-data, "optional_features", default: ())
 
     info-box(title: "Features & Traits")[
       #if type(class-features) == array and class-features.len() > 0 [
@@ -377,6 +405,16 @@
           #linebreak()
         ]
       ]
+      #if type(optional-features) == array and optional-features.len() > 0 [
+        #v(spacing.sm)
+        #label-text("Optional Features")
+        #linebreak()
+        #for feature in optional-features [
+          #text(size: sizes.sm)[- #get(feature, "name", default: "?")]
+          #text(size: sizes.xs, fill: colors.text-secondary)[ (#get(feature, "class_name", default: "") Lv#get(feature, "level", default: 0))]
+          #linebreak()
+        ]
+      ]
     ]
   },
 
@@ -169,19 +169,29 @@
 
 #let max-hp = get(data, "max_hp", default: 10)
 #let current-hp = get(data, "current_hp", default: 10)
+#let temp-hp = get(data, "temp_hp", default: 0)
 #let speed = get(data, "speed", default: 30)
 
+// Live play state
+#let conditions = get(data, "conditions", default: ())
+#let exhaustion = get(data, "exhaustion_level", default: 0)
+#let concentration = get(data, "concentration", default: none)
+#let death-saves = get(data, "death_saves", default: (:))
+
 #let proficiencies = get(data, "proficiencies", default: ())
 #let prof-saves = get(proficiencies, "saves", default: ())
 
-// Calculate AC (basic formula: 10 + dex mod, or from equipped armor)
+// AC and attacks derived from equipped catalog items, when supplied
+#let derived = get(data, "derived_stats", default: none)
+#let attacks = get(derived, "attacks", default: ())
+
 #let equipped = get(data, "equipped", default: ())
 #let has-armor = get(equipped, "armor", default: none) != none
 #let has-shield = get(equipped, "shield", default: none) != none
-// Simplified AC calculation - would need armor data for real calculation
+// Fallback without derived stats: 10 + dex mod, +2 for a shield
 #let base-ac = 10 + calc.floor((dex-score - 10) / 2)
 #let shield-bonus = if has-shield { 2 } else { 0 }
-#let ac = base-ac + shield-bonus
+#let ac = get(derived, "armor_class", default: base-ac + shield-bonus)
 
 #grid(
   columns: (2fr, 1fr),
@@ -206,15 +216,56 @@
         row-gutter: spacing.sm,
         column-gutter: spacing.md,
 
-        labeled-value("HP", [#current-hp / #max-hp]),
+        labeled-value("HP", if temp-hp > 0 [#current-hp / #max-hp (+#temp-hp)] else [#current-hp / #max-hp]),
         labeled-value("AC", str(ac)),
         labeled-value("Speed", [#speed ft]),
         labeled-value("Prof", [+#prof-bonus(level)]),
       )
+
+      #if current-hp == 0 [
+        #v(spacing.sm)
+        #inline-labeled("Death Saves", [#get(death-saves, "successes", default: 0) successes, #get(death-saves, "failures", default: 0) failures])
+      ]
+      #if conditions.len() > 0 [
+        #v(spacing.sm)
+        #inline-labeled("Conditions", conditions.join(", "))
+      ]
+      #if exhaustion > 0 [
+        #v(spacing.sm)
+        #inline-labeled("Exhaustion", [Level #exhaustion])
+      ]
+      #if concentration != none [
+        #v(spacing.sm)
+        #inline-labeled("Concentrating", concentration)
+      ]
     ]
 
     v(spacing.sm)
 
+    // Attacks
+    if attacks.len() > 0 {
+      info-box(title: "Attacks")[
+        #for attack in attacks {
+          let bonus = get(attack, "attack_bonus", default: 0)
+          let versatile = get(attack, "versatile_damage", default: none)
+          let range = get(attack, "range", default: none)
+          text(size: sizes.sm)[
+            #text(weight: "bold")[#get(attack, "name", default: "")]
+            #h(1fr)
+            #if bonus >= 0 [+]#bonus
+          ]
+          linebreak()
+          text(size: sizes.xs)[
+            #get(attack, "damage", default: "—")
+            #if versatile != none [ (two-handed #versatile)]
+            #if range != none [ · #range]
+          ]
+          linebreak()
+        }
+      ]
+      v(spacing.sm)
+    }
+
     // Saving Throws
     info-box(title: "Saving Throws")[
       #let saves = (
@@ -368,6 +419,7 @@
   {
     let class-features = get(data, "class_features", default: ())
     let feats = get(data, "feats", default: ())
+    let optional-features = get(data, "optional_features", default: ())
 
     info-box(title: "Features & Traits")[
       #for feature in class-features {
@@ -383,6 +435,16 @@
           linebreak()
         }
       ]
+      #if optional-features.len() > 0 [
+        #v(spacing.sm)
+        #label-text("Optional Features")
+        #linebreak()
+        #for feature in optional-features {
+          text(size: sizes.sm)[- #get(feature, "name", default: "?")]
+          text(size: sizes.xs, fill: colors.text-secondary)[ (#get(feature, "class_name", default: "") Lv#get(feature, "level", default: 0))]
+          linebreak()
+        }
+      ]
     ]
   },
 
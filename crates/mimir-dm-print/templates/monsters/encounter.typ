@@ -26,6 +26,7 @@
 #let encounter-title = get(data, "title", default: "Encounter")
 #let monsters = get(data, "monsters", default: ())
 #let notes = get(data, "notes", default: none)
+#let difficulty = get(data, "difficulty", default: none)
 
 // =============================================================================
 // COMPACT MONSTER STAT BLOCK
@@ -245,6 +246,32 @@
     #text(weight: "bold")[Encounter Summary]
     #h(1fr)
     #text(size: sizes.sm)[#monsters.len() creature#if monsters.len() != 1 [s]]
+
+    // Difficulty (DMG XP budget)
+    #if difficulty != none [
+      #let rating = get(difficulty, "difficulty", default: none)
+      #let thresholds = get(difficulty, "thresholds", default: (:))
+      #let party = get(difficulty, "party_levels", default: ())
+      #v(spacing.xs)
+      #set text(size: sizes.xs)
+      #grid(
+        columns: (auto, 1fr),
+        column-gutter: spacing.md,
+        row-gutter: spacing.xs,
+        [*Difficulty*],
+        [#if rating != none { upper(rating) } else [No party]],
+        [*XP*],
+        [#get(difficulty, "base_xp", default: 0) × #get(difficulty, "multiplier", default: 1) = #get(difficulty, "adjusted_xp", default: 0) adjusted (#get(difficulty, "xp_per_character", default: 0) per character)],
+        ..if party.len() > 0 {
+          (
+            [*Party*],
+            [#party.len() characters (levels #party.map(str).join(", "))],
+            [*Thresholds*],
+            [Easy #get(thresholds, "easy", default: 0) · Medium #get(thresholds, "medium", default: 0) · Hard #get(thresholds, "hard", default: 0) · Deadly #get(thresholds, "deadly", default: 0)],
+          )
+        } else { () },
+      )
+    ]
   ]
 ]
 
@@ -1,6 +1,7 @@
 /**
  * Composable for managing fog of war on maps.
- * Provides fog state management and reveal operations.
+ * Provides fog state management, reveal operations and the explored layer
+ * that PC tokens build up as they move.
  */
 import { ref, computed } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
@@ -11,19 +12,35 @@ interface ApiResponse<T> {
   error?: string
 }
 
+export type FogAreaShape = 'rect' | 'circle' | 'polygon'
+export type FogAreaSource = 'manual' | 'explored'
+
+export interface FogPoint {
+  x: number
+  y: number
+}
+
 export interface FogRevealedArea {
   id: number
   map_id: number
+  // Bounding box of the area
   x: number
   y: number
   width: number
   height: number
+  shape: FogAreaShape
+  // Polygon outline, for polygon areas
+  points: FogPoint[] | null
+  // Only the part of the polygon inside this outline is revealed
+  clip_points: FogPoint[] | null
+  source: FogAreaSource
 }
 
 export interface FogState {
   map_id: number
   fog_enabled: boolean
   revealed_areas: FogRevealedArea[]
+  explored_areas: FogRevealedArea[]
 }
 
 export interface RevealRectRequest {
@@ -41,9 +58,46 @@ export interface RevealCircleRequest {
   radius: number
 }
 
+export interface RevealPolygonRequest {
+  map_id: number
+  points: FogPoint[]
+}
+
+// Whether a point lies inside a polygon (even-odd rule)
+function polygonContains(polygon: FogPoint[], px: number, py: number): boolean {
+  let inside = false
+  for (let i = 0, j = polygon.length - 1; i < polygon.length; j = i++) {
+    const a = polygon[i]
+    const b = polygon[j]
+    if ((a.y > py) !== (b.y > py) && px < ((b.x - a.x) * (py - a.y)) / (b.y - a.y) + a.x) {
+      inside = !inside
+    }
+  }
+  return inside
+}
+
+// Whether a point lies inside a revealed or explored area
+export function fogAreaContains(area: FogRevealedArea, px: number, py: number): boolean {
+  if (px < area.x || px > area.x + area.width || py < area.y || py > area.y + area.height) {
+    return false
+  }
+  switch (area.shape) {
+    case 'circle': {
+      const radius = area.width / 2
+      return Math.hypot(px - (area.x + radius), py - (area.y + radius)) <= radius
+    }
+    case 'polygon':
+      return !!area.points && polygonContains(area.points, px, py) &&
+        (!area.clip_points || polygonContains(area.clip_points, px, py))
+    default:
+      return true
+  }
+}
+
 export function useFog(mapId: number) {
   const fogEnabled = ref(false)
   const revealedAreas = ref<FogRevealedArea[]>([])
+  const exploredAreas = ref<FogRevealedArea[]>([])
   const loading = ref(false)
   const error = ref<string | null>(null)
 
@@ -59,6 +113,7 @@ export function useFog(mapId: number) {
       if (response.success && response.data) {
         fogEnabled.value = response.data.fog_enabled
         revealedAreas.value = response.data.revealed_areas
+        exploredAreas.value = response.data.explored_areas
       } else {
         error.value = response.error || 'Failed to load fog state'
       }
@@ -154,6 +209,22 @@ export function useFog(mapId: number) {
     }
   }
 
+  // Reveal a polygon area, e.g. a cave passage
+  async function revealPolygon(points: FogPoint[]): Promise<FogRevealedArea | null> {
+    try {
+      const request: RevealPolygonRequest = { map_id: mapId, points }
+      const response = await invoke<ApiResponse<FogRevealedArea>>('reveal_polygon', { request })
+      if (response.success && response.data) {
+        revealedAreas.value.push(response.data)
+        return response.data
+      }
+      return null
+    } catch (e) {
+      console.error('Failed to reveal polygon:', e)
+      return null
+    }
+  }
+
   // Reveal entire map
   async function revealAll(mapWidth: number, mapHeight: number): Promise<FogRevealedArea | null> {
     try {
@@ -185,12 +256,13 @@ export function useFog(mapId: number) {
     }
   }
 
-  // Reset fog (clear all revealed areas)
+  // Reset fog (clear all revealed and explored areas)
   async function resetFog(): Promise<boolean> {
     try {
       const response = await invoke<ApiResponse<number>>('reset_fog', { mapId })
       if (response.success) {
         revealedAreas.value = []
+        exploredAreas.value = []
         return true
       }
       return false
@@ -202,16 +274,19 @@ export function useFog(mapId: number) {
 
   // Check if a point is in a revealed area
   function isPointRevealed(px: number, py: number): boolean {
-    return revealedAreas.value.some(area =>
-      px >= area.x && px <= area.x + area.width &&
-      py >= area.y && py <= area.y + area.height
-    )
+    return revealedAreas.value.some(area => fogAreaContains(area, px, py))
+  }
+
+  // Check if a point has been explored by PC tokens
+  function isPointExplored(px: number, py: number): boolean {
+    return exploredAreas.value.some(area => fogAreaContains(area, px, py))
   }
 
   return {
     // State
     fogEnabled,
     revealedAreas,
+    exploredAreas,
     loading,
     error,
     // Computed
@@ -223,9 +298,11 @@ export function useFog(mapId: number) {
     disableFog,
     revealRect,
     revealCircle,
+    revealPolygon,
     revealAll,
     deleteRevealedArea,
     resetFog,
-    isPointRevealed
+    isPointRevealed,
+    isPointExplored
   }
 }
@@ -56,6 +56,11 @@ function feetToPixels(feet: number, gridSizePx: number): number {
   return (feet / 5) * gridSizePx
 }
 
+/** Format polygon vertices for an SVG `points` attribute */
+export function toSvgPoints(polygon: { x: number; y: number }[]): string {
+  return polygon.map(p => `${p.x},${p.y}`).join(' ')
+}
+
 /**
  * Calculate visibility radii for a token based on its vision type
  * and the ambient light level.
@@ -163,12 +163,15 @@ If unsure whether tools are needed, respond conversationally first. Ask clarifyi
 - get_character - Get full character data by ID
 - list_campaign_characters - List all characters in a campaign
 - get_character_stats - Get ability scores, saves, skills
+- validate_character - Check a character against the rules and list errors and warnings
 - list_players - List all players in the database
-- create_character - Create new PC or NPC with full D&D 5e rules
+- create_character - Create new PC or NPC with full D&D 5e rules (optionally choose starting equipment or starting gold)
 - update_character - Update character attributes
+- level_up - Level up a character, including invocations, fighting styles, metamagic and maneuvers
 
 **Combat & Health:**
 - update_character_hp - Apply damage (negative) or healing (positive)
+- update_character_status - Set temp HP, conditions, exhaustion, death saves, concentration
 - take_rest - Apply short or long rest (restores HP, spell slots, hit dice)
 
 **Spellcasting:**
@@ -178,6 +181,7 @@ If unsure whether tools are needed, respond conversationally first. Ask clarifyi
 **Inventory & Equipment:**
 - add_inventory_item - Add item to inventory
 - remove_inventory_item - Remove item from inventory
+- update_inventory_item - Attune to magic items and spend or restore item charges
 - update_equipped - Change equipped items (armor, weapons)
 - update_currency - Add/remove gold, silver, copper, etc.
 
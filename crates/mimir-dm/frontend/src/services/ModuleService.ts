@@ -33,19 +33,16 @@ export interface Module {
 }
 
 export interface Session {
-  id: number // Changed from string | number to match components
-  module_id: number
-  name: string
-  date?: string
-  players?: string[]
-  status: 'planned' | 'active' | 'completed' | string
-  session_number: number // Made required with default
-  scheduled_date?: string
-  actual_date?: string
-  notes?: string
+  id: number
+  campaign_id: number
+  module_id: number | null
+  session_number: number
+  status: 'planned' | 'prepping' | 'ready' | 'played' | string
+  scheduled_date: string | null
+  prep_started_at: string | null
+  prep_completed_at: string | null
+  completed_at: string | null
   created_at: string
-  updated_at?: string
-  completed_at?: string | null
 }
 
 class ModuleServiceClass {
@@ -279,6 +276,34 @@ class ModuleServiceClass {
     }
   }
   
+  async createSession(moduleId: number, scheduledDate?: string): Promise<Session> {
+    try {
+      const response = await invoke<{ data: Session }>('create_session', {
+        request: {
+          module_id: moduleId,
+          scheduled_date: scheduledDate ?? null
+        }
+      })
+      return response.data
+    } catch (error) {
+      throw new Error(`Failed to create session for module ${moduleId}: ${error}`)
+    }
+  }
+
+  async transitionSession(sessionId: number, newStage: string): Promise<Session> {
+    try {
+      const response = await invoke<{ data: Session }>('transition_session_stage', {
+        request: {
+          session_id: sessionId,
+          new_stage: newStage
+        }
+      })
+      return response.data
+    } catch (error) {
+      throw new Error(`Failed to transition session ${sessionId}: ${error}`)
+    }
+  }
+
   async incrementSessionCount(id: number): Promise<void> {
     try {
       await invoke('increment_module_sessions', {
@@ -5,6 +5,7 @@
  */
 import { invoke } from '@tauri-apps/api/core'
 import { save } from '@tauri-apps/plugin-dialog'
+import type { EncounterDifficulty } from '@/types'
 
 // Types
 export interface PrintTemplateInfo {
@@ -39,6 +40,8 @@ export interface MonsterPrintOptions {
   title?: string
   notes?: string
   show_cut_lines?: boolean
+  /** Encounter difficulty summary (encounter template only) */
+  difficulty?: EncounterDifficulty
 }
 
 export interface SessionPrintData {
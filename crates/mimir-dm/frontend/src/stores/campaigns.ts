@@ -1,7 +1,7 @@
 import { defineStore } from 'pinia'
 import { ref } from 'vue'
 import { invoke } from '@tauri-apps/api/core'
-import type { ApiResponse, Campaign, NewCampaign } from '../types/api'
+import type { ApiResponse, Campaign, DiagonalRule, NewCampaign } from '../types/api'
 
 export const useCampaignStore = defineStore('campaigns', () => {
   const campaigns = ref<Campaign[]>([])
@@ -108,6 +108,31 @@ export const useCampaignStore = defineStore('campaigns', () => {
     }
   }
   
+  // Set how diagonal movement is measured on the campaign's maps
+  const setDiagonalRule = async (id: number, rule: DiagonalRule) => {
+    error.value = null
+    
+    try {
+      const response = await invoke<ApiResponse<Campaign>>('set_campaign_diagonal_rule', { campaignId: id, rule })
+      if (response.success && response.data) {
+        const index = campaigns.value.findIndex(c => c.id === id)
+        if (index !== -1) {
+          campaigns.value[index] = response.data
+        }
+        if (currentCampaign.value?.id === id) {
+          currentCampaign.value = response.data
+        }
+        return response.data
+      } else {
+        error.value = response.error || 'Failed to set diagonal rule'
+        return null
+      }
+    } catch (e) {
+      error.value = e instanceof Error ? e.message : 'Unknown error occurred'
+      return null
+    }
+  }
+  
   // Fetch archived campaigns
   const fetchArchivedCampaigns = async () => {
     loading.value = true
@@ -223,6 +248,7 @@ export const useCampaignStore = defineStore('campaigns', () => {
     getCampaign,
     createCampaign,
     updateCampaignStatus,
+    setDiagonalRule,
     archiveCampaign,
     unarchiveCampaign,
     deleteCampaign
@@ -4,6 +4,7 @@ import { invoke } from '@tauri-apps/api/core'
 import type {
   Character,
   CharacterData,
+  CharacterDiff,
   CharacterVersion,
   CharacterWithData,
   CreateCharacterRequest,
@@ -531,6 +532,63 @@ export const useCharacterStore = defineStore('characters', () => {
     }
   }
 
+  /**
+   * Compare two versions of a character
+   */
+  const diffCharacterVersions = async (
+    characterId: number,
+    fromVersion: number,
+    toVersion: number
+  ) => {
+    loading.value = true
+    error.value = null
+
+    try {
+      const result = await invoke<CharacterDiff>('diff_character_versions', {
+        characterId,
+        fromVersion,
+        toVersion
+      })
+      return result
+    } catch (e) {
+      error.value = e instanceof Error ? e.message : 'Failed to compare versions'
+      console.error('Error comparing versions:', e)
+      throw e
+    } finally {
+      loading.value = false
+    }
+  }
+
+  /**
+   * Restore a character to an earlier version (saved as a new version)
+   */
+  const restoreCharacterVersion = async (
+    characterId: number,
+    versionNumber: number
+  ) => {
+    loading.value = true
+    error.value = null
+
+    try {
+      const result = await invoke<CharacterVersion>('restore_character_version', {
+        characterId,
+        versionNumber
+      })
+
+      // Refresh the restored character and its history
+      await getCharacter(characterId)
+      await getCharacterVersions(characterId)
+
+      return result
+    } catch (e) {
+      error.value = e instanceof Error ? e.message : 'Failed to restore version'
+      console.error('Error restoring version:', e)
+      throw e
+    } finally {
+      loading.value = false
+    }
+  }
+
   /**
    * Set the current character
    */
@@ -581,6 +639,8 @@ export const useCharacterStore = defineStore('characters', () => {
     deleteCharacter,
     getCharacterVersions,
     getCharacterVersion,
+    diffCharacterVersions,
+    restoreCharacterVersion,
     setCurrentCharacter,
     reset
   }
@@ -4,6 +4,7 @@ import { createSessionStore } from './session'
 import { createMessagesStore } from './messages'
 import { createTodosStore } from './todos'
 import { createToolConfirmationsStore } from './tool-confirmations'
+import { createToolActionsStore } from './tool-actions'
 
 // Export all types and interfaces
 export * from './types'
@@ -11,6 +12,7 @@ export * from './messages'
 export * from './session'
 export * from './tokens'
 export * from './todos'
+export * from './tool-actions'
 
 export const useChatStore = defineStore('chat', () => {
   // Create all concern-specific stores
@@ -19,6 +21,7 @@ export const useChatStore = defineStore('chat', () => {
   const tokensStore = createTokensStore(() => messagesStore.messages.value)
   const todosStore = createTodosStore()
   const toolConfirmationsStore = createToolConfirmationsStore()
+  const toolActionsStore = createToolActionsStore()
 
   // Coordinated initialization
   const initialize = async () => {
@@ -117,6 +120,14 @@ export const useChatStore = defineStore('chat', () => {
     )
   }
 
+  const loadToolActions = async () => {
+    await toolActionsStore.loadToolActions(sessionStore.currentSessionId.value)
+  }
+
+  const undoToolActions = async (count = 1) => {
+    return await toolActionsStore.undoToolActions(sessionStore.currentSessionId.value, count)
+  }
+
   const clearHistory = async () => {
     if (sessionStore.currentSessionId.value) {
       // Create a new session to replace the current one
@@ -182,6 +193,7 @@ export const useChatStore = defineStore('chat', () => {
     ...tokensStore,
     ...todosStore,
     ...toolConfirmationsStore,
+    ...toolActionsStore,
 
     // Coordinated actions (override individual store actions where coordination is needed)
     initialize,
@@ -197,6 +209,8 @@ export const useChatStore = defineStore('chat', () => {
     saveCurrentSession,
     createNewSession,
     deleteSession,
-    switchToSession
+    switchToSession,
+    loadToolActions,
+    undoToolActions
   }
 })
@@ -5,6 +5,7 @@ import type {
   ChatMessage,
   ChatResponseWithUsage,
   IntermediateMessage,
+  StreamDeltaMessage,
   ToolResultMessage
 } from './types'
 
@@ -21,6 +22,7 @@ export type {
   LineEdit,
   DiffPreview,
   IntermediateMessage,
+  StreamDeltaMessage,
   ToolResultMessage
 } from './types'
 
@@ -89,11 +91,43 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
     return messages.value[messages.value.length - 1] || null
   })
 
+  // Drop the placeholder that shows a reply while it streams in
+  const removeStreamingMessage = () => {
+    messages.value = messages.value.filter(m => !m.isStreaming)
+  }
+
   // Actions
   const initializeMessageListeners = async (
     currentSessionId: () => string | null,
     onTodosUpdate: (todos: any[]) => void
   ) => {
+    // Set up event listener for streamed reply text
+    await listen<StreamDeltaMessage>('llm-stream-delta', (event) => {
+      const delta = event.payload
+
+      // Only process if this is for the current session
+      if (delta.session_id && currentSessionId() !== delta.session_id) return
+
+      let message = messages.value.find(m => m.isStreaming)
+      if (!message) {
+        messages.value.push({
+          id: `streaming_${Date.now()}`,
+          role: 'assistant',
+          content: '',
+          timestamp: Date.now(),
+          isStreaming: true,
+          iteration: delta.iteration,
+          toolCalls: []
+        })
+        message = messages.value[messages.value.length - 1]
+      }
+
+      message.content += delta.content
+      if (delta.tool_call) {
+        message.toolCalls = [...(message.toolCalls || []), delta.tool_call]
+      }
+    })
+
     // Set up event listener for intermediate LLM messages
     await listen<IntermediateMessage>('llm-intermediate-message', (event) => {
       console.log('Received intermediate LLM message:', event.payload)
@@ -101,6 +135,7 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
 
       // Only process if this is for the current session
       if (!intermediateMsg.session_id || currentSessionId() === intermediateMsg.session_id) {
+        removeStreamingMessage()
         const message: ChatMessage = {
           id: `intermediate_${Date.now()}_${Math.random()}`,
           role: 'assistant',
@@ -241,6 +276,7 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
         }
       }
 
+      removeStreamingMessage()
       messages.value.push(assistantMessage)
 
       // Immediately save assistant message to session file
@@ -270,6 +306,7 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
       // User should see their message was sent even if LLM fails to respond
       console.log('User message preserved in UI despite LLM error')
     } finally {
+      removeStreamingMessage()
       isLoading.value = false
     }
   }
@@ -417,6 +454,7 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
         }
       }
 
+      removeStreamingMessage()
       messages.value.push(assistantMessage)
 
       try {
@@ -435,6 +473,7 @@ export function createMessagesStore(): MessagesState & MessagesComputed & Messag
       console.error('Failed to resend conversation:', err)
       error.value = String(err)
     } finally {
+      removeStreamingMessage()
       isLoading.value = false
     }
   }
@@ -0,0 +1,51 @@
+import { ref, type Ref } from 'vue'
+import { invoke } from '@tauri-apps/api/core'
+import type { ToolAction } from './types'
+
+interface ToolActionsState {
+  undoableActions: Ref<ToolAction[]>
+}
+
+interface ToolActionsActions {
+  loadToolActions: (sessionId: string | null) => Promise<void>
+  undoToolActions: (sessionId: string | null, count?: number) => Promise<ToolAction[]>
+  clearToolActions: () => void
+}
+
+export function createToolActionsStore(): ToolActionsState & ToolActionsActions {
+  // State
+  const undoableActions = ref<ToolAction[]>([])
+
+  // Actions
+  const loadToolActions = async (sessionId: string | null) => {
+    if (!sessionId) {
+      undoableActions.value = []
+      return
+    }
+
+    try {
+      undoableActions.value = await invoke<ToolAction[]>('list_tool_actions', { sessionId })
+    } catch (err) {
+      console.error('Failed to load tool actions:', err)
+    }
+  }
+
+  const undoToolActions = async (sessionId: string | null, count = 1) => {
+    if (!sessionId) return []
+
+    const undone = await invoke<ToolAction[]>('undo_tool_actions', { sessionId, count })
+    await loadToolActions(sessionId)
+    return undone
+  }
+
+  const clearToolActions = () => {
+    undoableActions.value = []
+  }
+
+  return {
+    undoableActions,
+    loadToolActions,
+    undoToolActions,
+    clearToolActions
+  }
+}
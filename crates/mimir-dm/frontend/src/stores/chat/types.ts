@@ -10,6 +10,8 @@ export interface ChatMessage {
     total: number
   }
   isIntermediate?: boolean
+  /** Reply still being streamed; replaced once the full response arrives */
+  isStreaming?: boolean
   iteration?: number
   toolName?: string
   toolCalls?: string[]
@@ -102,6 +104,13 @@ export interface IntermediateMessage {
   session_id?: string
 }
 
+export interface StreamDeltaMessage {
+  content: string
+  tool_call?: string | null
+  iteration: number
+  session_id?: string
+}
+
 export interface ToolResultMessage {
   tool_name: string
   result: string
@@ -118,3 +127,17 @@ export interface TodoItem {
   status: 'pending' | 'in_progress' | 'completed'
   activeForm: string
 }
+
+// Undo journal types
+export interface ToolAction {
+  id: number
+  session_id: string
+  tool_name: string
+  /** Tool arguments as JSON */
+  arguments: string
+  summary: string
+  /** JSON-encoded revert action */
+  revert_action: string
+  created_at: string
+  undone_at: string | null
+}
@@ -56,10 +56,14 @@ export function createMockDocument(overrides: any = {}) {
 export function createMockSession(overrides: any = {}) {
   return {
     id: 1,
+    campaign_id: 1,
     module_id: 1,
-    name: 'Test Session',
     status: 'planned',
     session_number: 1,
+    scheduled_date: null,
+    prep_started_at: null,
+    prep_completed_at: null,
+    completed_at: null,
     created_at: '2024-01-01T00:00:00Z',
     ...overrides
   }
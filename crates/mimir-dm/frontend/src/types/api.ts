@@ -23,8 +23,12 @@ export interface Campaign {
   first_session_date?: string
   last_activity_at: string
   archived_at?: string
+  diagonal_rule: DiagonalRule
 }
 
+// 'alternating' counts diagonals as 5/10/5 feet, 'euclidean' measures straight lines
+export type DiagonalRule = 'alternating' | 'euclidean'
+
 export interface NewCampaign {
   name: string
   description?: string
@@ -49,13 +53,14 @@ export interface Module {
 // Session types
 export interface Session {
   id: number
-  module_id: number
+  campaign_id: number
+  module_id: number | null
   session_number: number
-  name: string
-  status: string
-  scheduled_date?: string
-  actual_date?: string
-  notes?: string
+  status: 'planned' | 'prepping' | 'ready' | 'played' | string
+  scheduled_date: string | null
+  prep_started_at: string | null
+  prep_completed_at: string | null
+  completed_at: string | null
   created_at: string
 }
 
@@ -138,6 +143,17 @@ export interface CreateTokenRequest {
   vision_range_ft?: number | null
 }
 
+export type Formation = 'cluster' | 'line' | 'ring'
+
+// Places every monster in a module encounter (null tag = untagged monsters)
+export interface PlaceEncounterRequest {
+  map_id: number
+  encounter_tag: string | null
+  x: number
+  y: number
+  formation?: Formation
+}
+
 export interface UpdateTokenRequest {
   name?: string
   token_type?: string
@@ -190,4 +206,90 @@ export const VISION_PRESETS: { label: string; type: VisionType; range: number |
   { label: 'Tremorsense 60 ft.', type: 'tremorsense', range: 60 },
   { label: 'Truesight 120 ft.', type: 'truesight', range: 120 },
   { label: "Devil's Sight 120 ft.", type: 'devils_sight', range: 120 }
-]
\ No newline at end of file
+]
+
+// Walls, doors and line of sight
+export type DoorState = 'open' | 'closed' | 'locked'
+
+export interface MapWall {
+  id: number
+  map_id: number
+  x1: number
+  y1: number
+  x2: number
+  y2: number
+  created_at: string
+}
+
+export interface MapDoor {
+  id: number
+  map_id: number
+  x1: number
+  y1: number
+  x2: number
+  y2: number
+  state: DoorState
+  created_at: string
+  updated_at: string
+}
+
+export interface VisibilityPoint {
+  x: number
+  y: number
+}
+
+export interface SightArea {
+  polygon: VisibilityPoint[]
+  light: 'bright' | 'dim'
+}
+
+export interface LitArea {
+  light_source_id: number
+  center: VisibilityPoint
+  bright_radius_px: number
+  dim_radius_px: number
+  polygon: VisibilityPoint[]
+}
+
+export interface TokenVisibility {
+  token_id: number
+  line_of_sight: VisibilityPoint[]
+  sight: SightArea[]
+}
+
+// A token sees its sight areas plus the lit areas inside its line of sight
+export interface MapVisibility {
+  map_id: number
+  lit_areas: LitArea[]
+  tokens: TokenVisibility[]
+}
+
+export type TemplateShape = 'cone' | 'cube' | 'sphere' | 'line' | 'cylinder'
+
+// An area of effect placed on a map; direction is degrees clockwise from east
+export interface MapTemplate {
+  id: number
+  map_id: number
+  name: string
+  shape: TemplateShape
+  x: number
+  y: number
+  direction_deg: number
+  size_ft: number
+  width_ft: number
+  spell_name: string | null
+  spell_source: string | null
+  color: string | null
+  visible_to_players: boolean
+  created_at: string
+  updated_at: string
+}
+
+export interface CreateSpellTemplateRequest {
+  map_id: number
+  spell_name: string
+  spell_source: string
+  x: number
+  y: number
+  direction_deg?: number
+}
\ No newline at end of file
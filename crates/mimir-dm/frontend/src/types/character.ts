@@ -75,9 +75,16 @@ export interface CharacterData {
   abilities: AbilityScores
   max_hp: number
   current_hp: number
+  // Combat state
+  temp_hp: number
+  death_saves: DeathSaves
+  exhaustion_level: number
+  conditions: string[]
+  concentration: string | null
   speed: number
   proficiencies: Proficiencies
   class_features: FeatureReference[]
+  optional_features: OptionalFeatureChoice[]
   feats: string[]
   spells: SpellData
   inventory: InventoryItem[]
@@ -94,6 +101,11 @@ export interface CharacterData {
   legendary_action_count: number | null
 }
 
+export interface DeathSaves {
+  successes: number
+  failures: number
+}
+
 export interface AbilityScores {
   strength: number
   dexterity: number
@@ -125,6 +137,114 @@ export interface FeatureReference {
   level: number
 }
 
+export interface OptionalFeatureChoice {
+  name: string
+  source: string
+  feature_type: string  // 5etools type code, e.g. "EI", "FS:F", "MV:B"
+  class_name: string
+  level: number
+}
+
+export interface OptionalFeatureSelection {
+  name: string
+  source: string | null
+}
+
+export interface OptionalFeatureOption {
+  name: string
+  source: string
+  feature_types: string[]
+  feature_type_full: string
+  prerequisite_text: string
+  grants_spells: boolean
+}
+
+export interface OptionalFeatureGain {
+  name: string  // e.g. "Eldritch Invocations"
+  class_name: string
+  feature_types: string[]
+  total: number
+  remaining: number
+  available: OptionalFeatureOption[]
+}
+
+export type EquipmentEntry =
+  | { kind: 'item'; name: string; source: string | null; quantity: number; display_name: string | null; contains_value: number | null }
+  | { kind: 'special'; name: string; quantity: number }
+  | { kind: 'equipment_type'; equipment_type: string; quantity: number }  // e.g. "weaponMartial"
+  | { kind: 'currency'; value: number }  // copper pieces
+
+export interface EquipmentOption {
+  key: string  // "a", "b", ...
+  entries: EquipmentEntry[]
+}
+
+export interface EquipmentChoiceGroup {
+  options: EquipmentOption[]
+}
+
+export interface StartingEquipmentOptions {
+  class_fixed: EquipmentEntry[]
+  class_choices: EquipmentChoiceGroup[]
+  class_description: string[]
+  background_fixed: EquipmentEntry[]
+  background_choices: EquipmentChoiceGroup[]
+  gold_alternative: string | null  // e.g. "5d4×10"
+}
+
+export type StartingEquipmentChoice =
+  | { type: 'equipment'; class_choices: string[]; background_choices: string[]; equipment_picks: string[] }
+  | { type: 'gold'; gold: number | null }
+
+export type FindingSeverity = 'error' | 'warning'
+
+export interface ValidationFinding {
+  severity: FindingSeverity
+  field: string
+  message: string
+}
+
+export interface ValidationReport {
+  valid: boolean
+  error_count: number
+  warning_count: number
+  findings: ValidationFinding[]
+}
+
+// Structured comparison of two character versions
+export interface FieldChange {
+  field: string
+  before: unknown
+  after: unknown
+}
+
+export interface ListChange {
+  field: string
+  added: string[]
+  removed: string[]
+}
+
+export interface SpellSlotChange {
+  level: number
+  before: SpellSlots | null
+  after: SpellSlots | null
+}
+
+export interface InventoryChange {
+  name: string
+  source: string | null
+  kind: 'added' | 'removed' | 'changed'
+  before: InventoryItem | null
+  after: InventoryItem | null
+}
+
+export interface CharacterDiff {
+  fields: FieldChange[]
+  spell_slots: SpellSlotChange[]
+  inventory: InventoryChange[]
+  lists: ListChange[]
+}
+
 export interface FeatureDetail {
   name: string
   class_name: string
@@ -151,6 +271,15 @@ export interface SpellReferenceInput {
   source: string
 }
 
+export type ItemRecharge = 'dawn' | 'dusk' | 'midnight' | 'short_rest' | 'long_rest' | 'special'
+
+export interface ItemCharges {
+  current: number
+  max: number
+  recharge: ItemRecharge | null
+  recharge_amount: string | null
+}
+
 export interface InventoryItem {
   name: string
   source: string | null
@@ -158,6 +287,9 @@ export interface InventoryItem {
   weight: number
   value: number
   notes: string | null
+  requires_attunement: boolean
+  attuned: boolean
+  charges: ItemCharges | null
 }
 
 export interface Currency {
@@ -209,6 +341,7 @@ export interface CreateCharacterRequest {
   personality: PersonalityInput | null
   skill_proficiencies: string[] | null
   equipment: InventoryItemInput[] | null
+  starting_equipment: StartingEquipmentChoice | null
   cantrips: SpellReferenceInput[] | null
   known_spells: SpellReferenceInput[] | null
   // NPC-specific fields
@@ -259,6 +392,7 @@ export interface LevelUpRequest {
   new_spell_slots: string | null  // JSON string with spell slot updates
   new_known_spells: SpellReferenceInput[] | null  // Updated known spells list
   new_cantrips: SpellReferenceInput[] | null  // Updated cantrips list
+  optional_features: OptionalFeatureSelection[] | null  // Invocations, fighting styles, ...
 }
 
 export interface AsiOrFeat {
@@ -370,4 +504,13 @@ export interface CharacterCommands {
     character_id: number,
     version_number: number
   ): Promise<CharacterData>
+  diff_character_versions(
+    character_id: number,
+    from_version: number,
+    to_version: number
+  ): Promise<CharacterDiff>
+  restore_character_version(
+    character_id: number,
+    version_number: number
+  ): Promise<CharacterVersion>
 }
@@ -128,4 +128,23 @@ export interface DocumentTemplate {
   documentType: string
   required?: boolean
   defaultContent?: string
-}
\ No newline at end of file
+}
+// Encounter difficulty (DMG XP budget) for a module encounter
+export type EncounterDifficultyRating = 'trivial' | 'easy' | 'medium' | 'hard' | 'deadly'
+
+export interface EncounterDifficulty {
+  party_levels: number[]
+  thresholds: { easy: number; medium: number; hard: number; deadly: number }
+  monsters: { name: string; source: string; quantity: number; cr: string | null; xp: number }[]
+  monster_count: number
+  base_xp: number
+  multiplier: number
+  adjusted_xp: number
+  difficulty: EncounterDifficultyRating | null
+  xp_per_character: number
+}
+
+export interface ModuleEncounterDifficulty {
+  encounter_tag: string | null
+  difficulty: EncounterDifficulty
+}
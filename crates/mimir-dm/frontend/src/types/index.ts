@@ -10,7 +10,10 @@ export type {
   BoardDocumentType,
   Stage,
   StageInfo,
-  DocumentTemplate
+  DocumentTemplate,
+  EncounterDifficulty,
+  EncounterDifficultyRating,
+  ModuleEncounterDifficulty
 } from './domain'
 // Campaign, Module, and Session are already exported from api.ts
 
@@ -8,7 +8,7 @@ use crate::state::AppState;
 use crate::types::{ApiError, ApiResponse};
 use mimir_dm_core::{
     domain::{BoardCompletionStatus, TemplateInfo},
-    models::campaign::campaigns::Campaign as DbCampaign,
+    models::campaign::campaigns::{Campaign as DbCampaign, DiagonalRule},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -23,6 +23,7 @@ pub struct Campaign {
     pub status: String,
     pub directory_path: String,
     pub created_at: String,
+    pub diagonal_rule: String,
 }
 
 impl From<DbCampaign> for Campaign {
@@ -33,6 +34,7 @@ impl From<DbCampaign> for Campaign {
             status: db_campaign.status,
             directory_path: db_campaign.directory_path,
             created_at: db_campaign.created_at,
+            diagonal_rule: db_campaign.diagonal_rule,
         }
     }
 }
@@ -347,6 +349,80 @@ pub async fn unarchive_campaign(
     }
 }
 
+/// Get the source books enabled for a campaign (empty means all sources)
+#[tauri::command]
+pub async fn get_campaign_sources(
+    campaign_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    info!("Getting enabled sources for campaign {}", campaign_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = mimir_dm_core::services::CampaignService::new(&mut conn);
+
+    match service.get_enabled_sources(campaign_id) {
+        Ok(sources) => Ok(ApiResponse::success(sources)),
+        Err(e) => {
+            error!("Failed to get campaign sources: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to get campaign sources: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Set the source books enabled for a campaign (empty allows all sources)
+#[tauri::command]
+pub async fn set_campaign_sources(
+    campaign_id: i32,
+    sources: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<String>>, ApiError> {
+    info!(
+        "Setting enabled sources for campaign {}: {:?}",
+        campaign_id, sources
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = mimir_dm_core::services::CampaignService::new(&mut conn);
+
+    match service.set_enabled_sources(campaign_id, &sources) {
+        Ok(sources) => Ok(ApiResponse::success(sources)),
+        Err(e) => {
+            error!("Failed to set campaign sources: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to set campaign sources: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Set how grid distances count diagonals ("alternating" for 5/10/5, or "euclidean")
+#[tauri::command]
+pub async fn set_campaign_diagonal_rule(
+    campaign_id: i32,
+    rule: String,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Campaign>, ApiError> {
+    info!("Setting diagonal rule for campaign {}: {}", campaign_id, rule);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = mimir_dm_core::services::CampaignService::new(&mut conn);
+
+    match service.set_diagonal_rule(campaign_id, DiagonalRule::from_str(&rule)) {
+        Ok(campaign) => Ok(ApiResponse::success(campaign.into())),
+        Err(e) => {
+            error!("Failed to set campaign diagonal rule: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to set campaign diagonal rule: {}",
+                e
+            )))
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteCampaignRequest {
     pub campaign_id: i32,
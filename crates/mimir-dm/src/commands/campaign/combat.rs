@@ -0,0 +1,689 @@
+//! Combat tracker command handlers.
+//!
+//! Commands for running encounters - starting combat from a module encounter
+//! or a map's tokens, rolling initiative, advancing turns, and tracking HP,
+//! damage and conditions.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::models::campaign::{
+    CombatCondition, CombatEncounter, CombatState, Combatant, CombatantType, HpEvent, HpMode,
+    NewCombatant,
+};
+use mimir_dm_core::services::CombatService;
+use serde::Deserialize;
+use tauri::State;
+use tracing::{error, info};
+
+/// Request to start combat from a module encounter
+#[derive(Debug, Deserialize)]
+pub struct StartEncounterCombatRequest {
+    pub module_id: i32,
+    pub encounter_tag: Option<String>,
+    pub include_party: Option<bool>,
+    /// "average" (default) or "rolled" monster HP
+    pub hp_mode: Option<String>,
+}
+
+/// Request to deal damage to a combatant
+#[derive(Debug, Deserialize)]
+pub struct ApplyDamageRequest {
+    pub combatant_id: i32,
+    pub amount: i32,
+    pub damage_type: Option<String>,
+    pub magical: Option<bool>,
+}
+
+/// Request to add a combatant to a running encounter
+#[derive(Debug, Deserialize)]
+pub struct AddCombatantRequest {
+    pub encounter_id: i32,
+    pub name: String,
+    pub combatant_type: String,
+    pub initiative: Option<i32>,
+    pub initiative_modifier: Option<i32>,
+    pub max_hp: Option<i32>,
+    pub armor_class: Option<i32>,
+    pub character_id: Option<i32>,
+    pub token_id: Option<i32>,
+}
+
+/// Start combat from a module's encounter tag.
+///
+/// # Parameters
+/// - `request` - Module ID, encounter tag and whether to include the party
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the new `CombatState`.
+#[tauri::command]
+pub async fn start_combat_from_encounter(
+    request: StartEncounterCombatRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    info!(
+        "Starting combat for module {} encounter {:?}",
+        request.module_id, request.encounter_tag
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.start_from_encounter(
+        request.module_id,
+        request.encounter_tag.as_deref(),
+        request.include_party.unwrap_or(true),
+        request
+            .hp_mode
+            .as_deref()
+            .map(HpMode::from_str)
+            .unwrap_or_default(),
+    ) {
+        Ok(combat) => {
+            info!(
+                "Combat started with ID {} ({} combatants)",
+                combat.encounter.id,
+                combat.combatants.len()
+            );
+            Ok(ApiResponse::success(combat))
+        }
+        Err(e) => {
+            error!("Failed to start combat: {}", e);
+            Ok(ApiResponse::error(format!("Failed to start combat: {}", e)))
+        }
+    }
+}
+
+/// Start combat from the tokens on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `hp_mode` - "average" (default) or "rolled" monster HP
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the new `CombatState`.
+#[tauri::command]
+pub async fn start_combat_from_map(
+    map_id: i32,
+    hp_mode: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    info!("Starting combat from tokens on map {}", map_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+    let hp_mode = hp_mode.as_deref().map(HpMode::from_str).unwrap_or_default();
+
+    match service.start_from_map(map_id, hp_mode) {
+        Ok(combat) => {
+            info!(
+                "Combat started with ID {} ({} combatants)",
+                combat.encounter.id,
+                combat.combatants.len()
+            );
+            Ok(ApiResponse::success(combat))
+        }
+        Err(e) => {
+            error!("Failed to start combat: {}", e);
+            Ok(ApiResponse::error(format!("Failed to start combat: {}", e)))
+        }
+    }
+}
+
+/// Get the full state of an encounter.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `CombatState`.
+#[tauri::command]
+pub async fn get_combat_state(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.get_combat_state(encounter_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to get combat state: {}", e);
+            Ok(ApiResponse::error(format!("Failed to get combat state: {}", e)))
+        }
+    }
+}
+
+/// Get the active encounter for a campaign, if any (for resuming combat).
+///
+/// # Parameters
+/// - `campaign_id` - Database ID of the campaign
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the active `CombatState`, or `None`.
+#[tauri::command]
+pub async fn get_active_combat(
+    campaign_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Option<CombatState>>, ApiError> {
+    info!("Getting active combat for campaign {}", campaign_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.get_active_combat(campaign_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to get active combat: {}", e);
+            Ok(ApiResponse::error(format!("Failed to get active combat: {}", e)))
+        }
+    }
+}
+
+/// List all encounters for a campaign.
+///
+/// # Parameters
+/// - `campaign_id` - Database ID of the campaign
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of `CombatEncounter` objects.
+#[tauri::command]
+pub async fn list_combat_encounters(
+    campaign_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<CombatEncounter>>, ApiError> {
+    info!("Listing combat encounters for campaign {}", campaign_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.list_encounters(campaign_id) {
+        Ok(encounters) => {
+            info!("Found {} encounters", encounters.len());
+            Ok(ApiResponse::success(encounters))
+        }
+        Err(e) => {
+            error!("Failed to list combat encounters: {}", e);
+            Ok(ApiResponse::error(format!("Failed to list combat encounters: {}", e)))
+        }
+    }
+}
+
+/// Advance to the next combatant's turn.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `CombatState`.
+#[tauri::command]
+pub async fn next_combat_turn(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.next_turn(encounter_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to advance turn: {}", e);
+            Ok(ApiResponse::error(format!("Failed to advance turn: {}", e)))
+        }
+    }
+}
+
+/// Step back to the previous combatant's turn.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `CombatState`.
+#[tauri::command]
+pub async fn previous_combat_turn(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.previous_turn(encounter_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to go back a turn: {}", e);
+            Ok(ApiResponse::error(format!("Failed to go back a turn: {}", e)))
+        }
+    }
+}
+
+/// Reroll initiative for every combatant and restart at round 1.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `CombatState`.
+#[tauri::command]
+pub async fn reroll_combat_initiative(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    info!("Rerolling initiative for encounter {}", encounter_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.reroll_initiative(encounter_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to reroll initiative: {}", e);
+            Ok(ApiResponse::error(format!("Failed to reroll initiative: {}", e)))
+        }
+    }
+}
+
+/// Set a combatant's initiative explicitly.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `initiative` - The initiative total
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `Combatant`.
+#[tauri::command]
+pub async fn set_combatant_initiative(
+    combatant_id: i32,
+    initiative: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Combatant>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.set_initiative(combatant_id, initiative) {
+        Ok(combatant) => Ok(ApiResponse::success(combatant)),
+        Err(e) => {
+            error!("Failed to set initiative: {}", e);
+            Ok(ApiResponse::error(format!("Failed to set initiative: {}", e)))
+        }
+    }
+}
+
+/// Add a combatant to a running encounter.
+///
+/// Initiative is rolled unless an explicit value is provided.
+///
+/// # Parameters
+/// - `request` - Combatant details
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `Combatant`.
+#[tauri::command]
+pub async fn add_combatant(
+    request: AddCombatantRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Combatant>, ApiError> {
+    info!(
+        "Adding combatant '{}' to encounter {}",
+        request.name, request.encounter_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    let mut new_combatant = NewCombatant::new(
+        request.encounter_id,
+        request.name,
+        CombatantType::from_str(&request.combatant_type),
+    )
+    .with_initiative_modifier(request.initiative_modifier.unwrap_or(0));
+
+    if let Some(max_hp) = request.max_hp {
+        new_combatant = new_combatant.with_hp(max_hp, max_hp);
+    }
+    if let Some(armor_class) = request.armor_class {
+        new_combatant = new_combatant.with_armor_class(armor_class);
+    }
+    if let Some(token_id) = request.token_id {
+        new_combatant = new_combatant.with_token(token_id);
+    }
+    new_combatant.character_id = request.character_id;
+
+    let roll = request.initiative.is_none();
+    if let Some(initiative) = request.initiative {
+        new_combatant.initiative = initiative;
+    }
+
+    match service.add_combatant(new_combatant, roll) {
+        Ok(combatant) => {
+            info!("Combatant added with ID: {}", combatant.id);
+            Ok(ApiResponse::success(combatant))
+        }
+        Err(e) => {
+            error!("Failed to add combatant: {}", e);
+            Ok(ApiResponse::error(format!("Failed to add combatant: {}", e)))
+        }
+    }
+}
+
+/// Remove a combatant from an encounter.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `CombatState`.
+#[tauri::command]
+pub async fn remove_combatant(
+    combatant_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatState>, ApiError> {
+    info!("Removing combatant {}", combatant_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.remove_combatant(combatant_id) {
+        Ok(combat) => Ok(ApiResponse::success(combat)),
+        Err(e) => {
+            error!("Failed to remove combatant: {}", e);
+            Ok(ApiResponse::error(format!("Failed to remove combatant: {}", e)))
+        }
+    }
+}
+
+/// Set a combatant's current HP.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `current_hp` - The new HP value
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `Combatant`.
+#[tauri::command]
+pub async fn set_combatant_hp(
+    combatant_id: i32,
+    current_hp: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Combatant>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.set_combatant_hp(combatant_id, current_hp) {
+        Ok(combatant) => Ok(ApiResponse::success(combatant)),
+        Err(e) => {
+            error!("Failed to set combatant HP: {}", e);
+            Ok(ApiResponse::error(format!("Failed to set combatant HP: {}", e)))
+        }
+    }
+}
+
+/// Take a combatant in or out of the turn order.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `is_active` - Whether the combatant still takes turns
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `Combatant`.
+#[tauri::command]
+pub async fn set_combatant_active(
+    combatant_id: i32,
+    is_active: bool,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Combatant>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.set_combatant_active(combatant_id, is_active) {
+        Ok(combatant) => Ok(ApiResponse::success(combatant)),
+        Err(e) => {
+            error!("Failed to update combatant: {}", e);
+            Ok(ApiResponse::error(format!("Failed to update combatant: {}", e)))
+        }
+    }
+}
+
+/// Deal damage to a combatant, applying its resistances, immunities and vulnerabilities.
+///
+/// # Parameters
+/// - `request` - Combatant ID, damage amount, damage type and whether it is magical
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `HpEvent` log entry.
+#[tauri::command]
+pub async fn apply_combat_damage(
+    request: ApplyDamageRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<HpEvent>, ApiError> {
+    info!(
+        "Applying {} {} damage to combatant {}",
+        request.amount,
+        request.damage_type.as_deref().unwrap_or("untyped"),
+        request.combatant_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.apply_damage(
+        request.combatant_id,
+        request.amount,
+        request.damage_type.as_deref(),
+        request.magical.unwrap_or(false),
+    ) {
+        Ok(event) => Ok(ApiResponse::success(event)),
+        Err(e) => {
+            error!("Failed to apply damage: {}", e);
+            Ok(ApiResponse::error(format!("Failed to apply damage: {}", e)))
+        }
+    }
+}
+
+/// Heal a combatant.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `amount` - HP regained
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `HpEvent` log entry.
+#[tauri::command]
+pub async fn apply_combat_healing(
+    combatant_id: i32,
+    amount: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<HpEvent>, ApiError> {
+    info!("Healing combatant {} for {}", combatant_id, amount);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.apply_healing(combatant_id, amount) {
+        Ok(event) => Ok(ApiResponse::success(event)),
+        Err(e) => {
+            error!("Failed to apply healing: {}", e);
+            Ok(ApiResponse::error(format!("Failed to apply healing: {}", e)))
+        }
+    }
+}
+
+/// Grant temporary HP to a combatant.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `amount` - Temporary HP granted
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `HpEvent` log entry.
+#[tauri::command]
+pub async fn grant_combatant_temp_hp(
+    combatant_id: i32,
+    amount: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<HpEvent>, ApiError> {
+    info!("Granting {} temp HP to combatant {}", amount, combatant_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.grant_temp_hp(combatant_id, amount) {
+        Ok(event) => Ok(ApiResponse::success(event)),
+        Err(e) => {
+            error!("Failed to grant temp HP: {}", e);
+            Ok(ApiResponse::error(format!("Failed to grant temp HP: {}", e)))
+        }
+    }
+}
+
+/// Get the damage and healing log for an encounter.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `HpEvent` log entries, oldest first.
+#[tauri::command]
+pub async fn get_combat_hp_log(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<HpEvent>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.list_encounter_hp_events(encounter_id) {
+        Ok(events) => Ok(ApiResponse::success(events)),
+        Err(e) => {
+            error!("Failed to get HP log: {}", e);
+            Ok(ApiResponse::error(format!("Failed to get HP log: {}", e)))
+        }
+    }
+}
+
+/// Apply a catalog condition to a combatant.
+///
+/// # Parameters
+/// - `combatant_id` - Database ID of the combatant
+/// - `condition_name` - Catalog condition name
+/// - `duration_rounds` - Rounds the condition lasts (omit for until removed)
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the applied `CombatCondition`.
+#[tauri::command]
+pub async fn add_combatant_condition(
+    combatant_id: i32,
+    condition_name: String,
+    duration_rounds: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatCondition>, ApiError> {
+    info!("Applying {} to combatant {}", condition_name, combatant_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.add_condition(combatant_id, &condition_name, duration_rounds) {
+        Ok(condition) => Ok(ApiResponse::success(condition)),
+        Err(e) => {
+            error!("Failed to apply condition: {}", e);
+            Ok(ApiResponse::error(format!("Failed to apply condition: {}", e)))
+        }
+    }
+}
+
+/// Remove a condition from a combatant.
+///
+/// # Parameters
+/// - `condition_id` - Database ID of the applied condition
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` with success or error status.
+#[tauri::command]
+pub async fn remove_combatant_condition(
+    condition_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<()>, ApiError> {
+    info!("Removing combat condition {}", condition_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.remove_condition(condition_id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to remove condition: {}", e);
+            Ok(ApiResponse::error(format!("Failed to remove condition: {}", e)))
+        }
+    }
+}
+
+/// End an encounter.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the completed `CombatEncounter`.
+#[tauri::command]
+pub async fn end_combat(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<CombatEncounter>, ApiError> {
+    info!("Ending combat encounter {}", encounter_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.end_combat(encounter_id) {
+        Ok(encounter) => Ok(ApiResponse::success(encounter)),
+        Err(e) => {
+            error!("Failed to end combat: {}", e);
+            Ok(ApiResponse::error(format!("Failed to end combat: {}", e)))
+        }
+    }
+}
+
+/// Delete an encounter and its combatants.
+///
+/// # Parameters
+/// - `encounter_id` - Database ID of the encounter
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` with success or error status.
+#[tauri::command]
+pub async fn delete_combat_encounter(
+    encounter_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<()>, ApiError> {
+    info!("Deleting combat encounter {}", encounter_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = CombatService::new(&mut conn);
+
+    match service.delete_encounter(encounter_id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to delete combat encounter: {}", e);
+            Ok(ApiResponse::error(format!("Failed to delete combat encounter: {}", e)))
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Dice rolling command handlers.
+//!
+//! Commands for rolling dice expressions from the UI, optionally resolving
+//! ability modifiers from a character, and for rolling ability score sets.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::domain::dice::{DiceExpression, DiceRoller, RollResult};
+use mimir_dm_core::models::character::data::AbilityScores;
+use mimir_dm_core::services::CharacterService;
+use tauri::State;
+use tracing::{error, info};
+
+/// Roll a dice expression.
+///
+/// # Parameters
+/// - `expression` - Dice notation, e.g. `1d20+5`, `4d6kh3`, `1d8+STR`
+/// - `character_id` - Character whose ability modifiers resolve `STR`, `DEX`, etc.
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the `RollResult` with per-die breakdown.
+#[tauri::command]
+pub async fn roll_dice(
+    expression: String,
+    character_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<RollResult>, ApiError> {
+    info!("Rolling {}", expression);
+
+    let parsed = match DiceExpression::parse(&expression) {
+        Ok(parsed) => parsed,
+        Err(e) => return Ok(ApiResponse::error(e.to_string())),
+    };
+
+    let abilities = match character_id {
+        Some(character_id) if parsed.uses_abilities() => {
+            let mut conn = state.db.get_connection()?;
+            let mut char_service = CharacterService::new(&mut conn);
+            match char_service.get_character(character_id) {
+                Ok((_, data)) => Some(data.abilities),
+                Err(e) => {
+                    error!("Failed to load character for roll: {}", e);
+                    return Ok(ApiResponse::error(format!(
+                        "Failed to load character: {}",
+                        e
+                    )));
+                }
+            }
+        }
+        _ => None,
+    };
+
+    match DiceRoller::new().roll_expression(&parsed, abilities.as_ref()) {
+        Ok(result) => Ok(ApiResponse::success(result)),
+        Err(e) => {
+            error!("Failed to roll dice: {}", e);
+            Ok(ApiResponse::error(format!("Failed to roll dice: {}", e)))
+        }
+    }
+}
+
+/// Roll a set of ability scores (4d6, drop the lowest, six times).
+///
+/// # Returns
+/// `ApiResponse` containing the rolled `AbilityScores` in STR..CHA order.
+#[tauri::command]
+pub async fn roll_ability_scores() -> Result<ApiResponse<AbilityScores>, ApiError> {
+    match DiceRoller::new().roll_ability_scores() {
+        Ok(scores) => Ok(ApiResponse::success(scores)),
+        Err(e) => {
+            error!("Failed to roll ability scores: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to roll ability scores: {}",
+                e
+            )))
+        }
+    }
+}
@@ -1,12 +1,14 @@
 //! Fog of War command handlers.
 //!
 //! Commands for managing fog of war on maps - enabling/disabling fog,
-//! revealing areas, and resetting fog state.
+//! revealing areas, recording what PC tokens have explored, and resetting
+//! fog state.
 
 use crate::state::AppState;
 use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::domain::{MapVisibility, Point};
 use mimir_dm_core::models::campaign::{FogRevealedArea, FogRevealedAreaSummary};
-use mimir_dm_core::services::FogOfWarService;
+use mimir_dm_core::services::{FogOfWarService, VisibilityService};
 use serde::{Deserialize, Serialize};
 use tauri::State;
 use tracing::{error, info};
@@ -30,6 +32,13 @@ pub struct RevealCircleRequest {
     pub radius: f32,
 }
 
+/// Request to reveal a polygon area
+#[derive(Debug, Deserialize)]
+pub struct RevealPolygonRequest {
+    pub map_id: i32,
+    pub points: Vec<Point>,
+}
+
 /// Request to reveal the entire map
 #[derive(Debug, Deserialize)]
 pub struct RevealAllRequest {
@@ -44,6 +53,14 @@ pub struct FogState {
     pub map_id: i32,
     pub fog_enabled: bool,
     pub revealed_areas: Vec<FogRevealedAreaSummary>,
+    pub explored_areas: Vec<FogRevealedAreaSummary>,
+}
+
+/// What the party currently sees, and everywhere PC tokens have explored
+#[derive(Debug, Serialize)]
+pub struct FogExploration {
+    pub visibility: MapVisibility,
+    pub explored_areas: Vec<FogRevealedAreaSummary>,
 }
 
 /// Toggle fog of war on a map.
@@ -176,16 +193,29 @@ pub async fn get_fog_state(
         }
     };
 
+    let explored_areas = match service.get_explored_area_summaries(map_id) {
+        Ok(areas) => areas,
+        Err(e) => {
+            error!("Failed to get explored areas: {}", e);
+            return Ok(ApiResponse::error(format!(
+                "Failed to get fog state: {}",
+                e
+            )));
+        }
+    };
+
     let fog_state = FogState {
         map_id,
         fog_enabled,
         revealed_areas,
+        explored_areas,
     };
 
     info!(
-        "Fog state: enabled={}, {} revealed areas",
+        "Fog state: enabled={}, {} revealed areas, {} explored areas",
         fog_state.fog_enabled,
-        fog_state.revealed_areas.len()
+        fog_state.revealed_areas.len(),
+        fog_state.explored_areas.len()
     );
     Ok(ApiResponse::success(fog_state))
 }
@@ -267,6 +297,40 @@ pub async fn reveal_circle(
     }
 }
 
+/// Reveal a polygon area on the map.
+///
+/// # Parameters
+/// - `request` - Polygon outline
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a `FogRevealedAreaSummary` with the parsed outline.
+#[tauri::command]
+pub async fn reveal_polygon(
+    request: RevealPolygonRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<FogRevealedAreaSummary>, ApiError> {
+    info!(
+        "Revealing polygon with {} points on map {}",
+        request.points.len(),
+        request.map_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = FogOfWarService::new(&mut conn);
+
+    match service.reveal_polygon(request.map_id, &request.points) {
+        Ok(area) => {
+            info!("Revealed area created with ID: {}", area.id);
+            Ok(ApiResponse::success(area.into()))
+        }
+        Err(e) => {
+            error!("Failed to reveal polygon: {}", e);
+            Ok(ApiResponse::error(format!("Failed to reveal area: {}", e)))
+        }
+    }
+}
+
 /// Reveal the entire map (removes all fog).
 ///
 /// # Parameters
@@ -330,6 +394,81 @@ pub async fn get_revealed_areas(
     }
 }
 
+/// Get all areas PC tokens have explored on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of `FogRevealedAreaSummary`.
+#[tauri::command]
+pub async fn get_explored_areas(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<FogRevealedAreaSummary>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = FogOfWarService::new(&mut conn);
+
+    match service.get_explored_area_summaries(map_id) {
+        Ok(areas) => Ok(ApiResponse::success(areas)),
+        Err(e) => {
+            error!("Failed to get explored areas: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to get explored areas: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Record what PC tokens can see as explored, and compute party visibility.
+///
+/// Called whenever tokens move so the explored layer keeps up with the party.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the party's current visibility and all explored areas.
+#[tauri::command]
+pub async fn record_fog_exploration(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<FogExploration>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+
+    let mut service = FogOfWarService::new(&mut conn);
+    let explored_areas = match service
+        .record_explored(map_id)
+        .and_then(|_| service.get_explored_area_summaries(map_id))
+    {
+        Ok(areas) => areas,
+        Err(e) => {
+            error!("Failed to record fog exploration: {}", e);
+            return Ok(ApiResponse::error(format!(
+                "Failed to record fog exploration: {}",
+                e
+            )));
+        }
+    };
+
+    match VisibilityService::new(&mut conn).get_party_visibility(map_id) {
+        Ok(visibility) => Ok(ApiResponse::success(FogExploration {
+            visibility,
+            explored_areas,
+        })),
+        Err(e) => {
+            error!("Failed to compute party visibility: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to compute party visibility: {}",
+                e
+            )))
+        }
+    }
+}
+
 /// Delete a specific revealed area (re-fog that area).
 ///
 /// # Parameters
@@ -363,14 +502,14 @@ pub async fn delete_revealed_area(
     }
 }
 
-/// Reset fog on a map (remove all revealed areas).
+/// Reset fog on a map (remove all revealed and explored areas).
 ///
 /// # Parameters
 /// - `map_id` - Database ID of the map
 /// - `state` - Application state
 ///
 /// # Returns
-/// `ApiResponse` containing the number of revealed areas deleted.
+/// `ApiResponse` containing the number of areas deleted.
 #[tauri::command]
 pub async fn reset_fog(
     map_id: i32,
@@ -10,8 +10,9 @@ use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
 use image::ImageReader;
 use mimir_dm_core::{
+    domain::UvttMap,
     models::campaign::{Map, MapSummary, NewMap, UpdateMap},
-    services::MapService,
+    services::{MapService, UvttImport, UvttImportService},
 };
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -41,6 +42,16 @@ pub struct UploadMapRequest {
     pub height_px: i32,
 }
 
+/// Request to import a Universal VTT map file.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportUvttMapRequest {
+    pub campaign_id: i32,
+    pub module_id: Option<i32>,
+    pub name: String,
+    /// Contents of the .dd2vtt / .uvtt file (JSON with an embedded image)
+    pub uvtt_data: String,
+}
+
 /// Request to update map properties.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateMapRequest {
@@ -227,6 +238,112 @@ pub async fn upload_map(
     }
 }
 
+/// Import a Universal VTT map (.dd2vtt, .uvtt).
+///
+/// Stores the embedded image like an uploaded map and creates the map with
+/// its grid taken from the file, along with the file's walls, doors and
+/// lights.
+///
+/// # Parameters
+/// - `request` - Import request with the file contents and metadata
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `Map` and what was imported with it.
+#[tauri::command]
+pub async fn import_uvtt_map(
+    request: ImportUvttMapRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<UvttImport>, ApiError> {
+    info!(
+        "Importing Universal VTT map '{}' for campaign {} (module: {:?})",
+        request.name, request.campaign_id, request.module_id
+    );
+
+    let uvtt = match UvttMap::from_json(&request.uvtt_data) {
+        Ok(uvtt) => uvtt,
+        Err(e) => {
+            error!("Failed to parse Universal VTT file: {}", e);
+            return Ok(ApiResponse::error(format!(
+                "Invalid Universal VTT file: {}",
+                e
+            )));
+        }
+    };
+
+    // Create maps directory if it doesn't exist
+    let maps_dir = state.paths.data_dir.join("maps");
+    if let Err(e) = fs::create_dir_all(&maps_dir) {
+        error!("Failed to create maps directory: {}", e);
+        return Ok(ApiResponse::error(format!(
+            "Failed to create maps directory: {}",
+            e
+        )));
+    }
+
+    let raw_bytes = match STANDARD.decode(uvtt.image.trim()) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            error!("Failed to decode embedded map image: {}", e);
+            return Ok(ApiResponse::error(format!("Invalid image data: {}", e)));
+        }
+    };
+
+    let (processed_bytes, width, height) = match process_map_image(&raw_bytes, MAX_MAP_DIMENSION) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Failed to process image: {}", e);
+            return Ok(ApiResponse::error(format!(
+                "Failed to process image: {}",
+                e
+            )));
+        }
+    };
+
+    let stored_filename = format!("{}.jpg", Uuid::new_v4());
+    let image_path = maps_dir.join(&stored_filename);
+    if let Err(e) = fs::write(&image_path, &processed_bytes) {
+        error!("Failed to write image file: {}", e);
+        return Ok(ApiResponse::error(format!("Failed to save image: {}", e)));
+    }
+
+    let (original_width, original_height) = uvtt.image_size_px();
+    let new_map = NewMap::new(
+        request.campaign_id,
+        request.name,
+        stored_filename,
+        width as i32,
+        height as i32,
+        original_width.round() as i32,
+        original_height.round() as i32,
+    );
+    let new_map = if let Some(module_id) = request.module_id {
+        new_map.with_module(module_id)
+    } else {
+        new_map
+    };
+
+    let mut conn = state.db.get_connection()?;
+    match UvttImportService::new(&mut conn).import(&uvtt, new_map) {
+        Ok(import) => {
+            info!(
+                "Imported map {} with {} walls, {} doors and {} lights",
+                import.map.id,
+                import.wall_count,
+                import.door_count,
+                import.light_sources.len()
+            );
+            Ok(ApiResponse::success(import))
+        }
+        Err(e) => {
+            // Clean up the saved image on failure
+            let _ = fs::remove_file(&image_path);
+            error!("Failed to import map: {}", e);
+            Ok(ApiResponse::error(format!("Failed to import map: {}", e)))
+        }
+    }
+}
+
 /// Get a map by ID.
 ///
 /// # Parameters
@@ -1,24 +1,36 @@
 //! Campaign management command handlers.
 //!
-//! Contains commands for managing campaigns, modules,
+//! Contains commands for managing campaigns, modules, sessions,
 //! and stage transitions in the campaign workflow.
 
 pub mod campaigns;
+pub mod combat;
+pub mod dice;
 pub mod display_control;
 pub mod fog;
 pub mod light_sources;
 pub mod maps;
 pub mod module_monsters;
 pub mod modules;
+pub mod sessions;
 pub mod stage_transitions;
+pub mod templates;
 pub mod tokens;
+pub mod visibility;
+pub mod walls;
 
 pub use campaigns::*;
+pub use combat::*;
+pub use dice::*;
 pub use display_control::*;
 pub use fog::*;
 pub use light_sources::*;
 pub use maps::*;
 pub use module_monsters::*;
 pub use modules::*;
+pub use sessions::*;
 pub use stage_transitions::*;
+pub use templates::*;
 pub use tokens::*;
+pub use visibility::*;
+pub use walls::*;
@@ -6,8 +6,14 @@
 use crate::state::AppState;
 use crate::types::{ApiError, ApiResponse};
 use mimir_dm_core::{
-    models::campaign::module_monsters::{EncounterGroup, ModuleMonster, ModuleMonsterWithData},
-    services::ModuleMonsterService,
+    domain::dice::DiceRoller,
+    models::campaign::module_monsters::{
+        EncounterGroup, ModuleEncounterDifficulty, ModuleMonster, ModuleMonsterWithData,
+    },
+    services::{
+        EncounterGenerationOptions, EncounterGeneratorService, GeneratedEncounter,
+        ModuleMonsterService,
+    },
 };
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -255,6 +261,45 @@ pub async fn list_module_monsters_by_encounter(
     }
 }
 
+/// Calculate the difficulty of each encounter in a module.
+///
+/// Rates every encounter group against the campaign's player characters using
+/// the DMG XP thresholds and encounter multipliers.
+///
+/// # Parameters
+/// - `module_id` - The database ID of the module
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing a `ModuleEncounterDifficulty` per encounter group.
+#[tauri::command]
+pub async fn get_module_encounter_difficulties(
+    module_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<ModuleEncounterDifficulty>>, ApiError> {
+    info!(
+        "Calculating encounter difficulties for module: {}",
+        module_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = ModuleMonsterService::new(&mut conn);
+
+    match service.get_encounter_difficulties(module_id) {
+        Ok(difficulties) => {
+            info!("Rated {} encounters", difficulties.len());
+            Ok(ApiResponse::success(difficulties))
+        }
+        Err(e) => {
+            error!("Failed to calculate encounter difficulties: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to calculate encounter difficulties: {}",
+                e
+            )))
+        }
+    }
+}
+
 /// Get encounter tags for a module.
 ///
 /// Returns all distinct encounter tags used in the module.
@@ -376,3 +421,76 @@ pub async fn sync_module_monsters_to_file(
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenerateEncounterRequest {
+    /// Module to add the encounter to (omit to only preview)
+    pub module_id: Option<i32>,
+    /// Campaign to take the party and enabled sources from when previewing
+    pub campaign_id: Option<i32>,
+    /// Tag for the new encounter group (required with `module_id`)
+    pub encounter_tag: Option<String>,
+    pub options: EncounterGenerationOptions,
+}
+
+/// Generate a random encounter from the monster catalog.
+///
+/// Picks monsters matching the filters (environment, type, CR range, sources)
+/// until the adjusted XP fits the party's budget for the requested difficulty.
+/// With a `module_id` the monsters are saved to the module under the new
+/// encounter tag; otherwise the encounter is only returned as a preview.
+///
+/// # Parameters
+/// - `request` - Target module or campaign, encounter tag and generation options
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the `GeneratedEncounter`.
+#[tauri::command]
+pub async fn generate_random_encounter(
+    request: GenerateEncounterRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<GeneratedEncounter>, ApiError> {
+    info!(
+        "Generating {} encounter (module: {:?}, campaign: {:?})",
+        request.options.difficulty.as_str(),
+        request.module_id,
+        request.campaign_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = EncounterGeneratorService::new(&mut conn);
+    let mut roller = DiceRoller::new();
+
+    let result = match (request.module_id, request.campaign_id) {
+        (Some(module_id), _) => match request.encounter_tag.as_deref() {
+            Some(tag) => service.generate_for_module(module_id, tag, &request.options, &mut roller),
+            None => {
+                return Ok(ApiResponse::error(
+                    "An encounter tag is required to add an encounter to a module".to_string(),
+                ))
+            }
+        },
+        (None, Some(campaign_id)) => {
+            service.generate_for_campaign(campaign_id, &request.options, &mut roller)
+        }
+        (None, None) => service.generate(&request.options, &mut roller),
+    };
+
+    match result {
+        Ok(generated) => {
+            info!(
+                "Generated encounter with {} monsters ({} adjusted XP)",
+                generated.encounter.monster_count, generated.encounter.adjusted_xp
+            );
+            Ok(ApiResponse::success(generated))
+        }
+        Err(e) => {
+            error!("Failed to generate encounter: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to generate encounter: {}",
+                e
+            )))
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Session management commands.
+//!
+//! Provides Tauri commands for the session board: creating numbered sessions
+//! within modules, scheduling them, and moving them from planned through
+//! prepping and ready to played.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::{
+    domain::BoardCompletionStatus,
+    models::campaign::{documents::Document, sessions::Session},
+    services::SessionService,
+};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::{error, info};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateSessionRequest {
+    pub module_id: i32,
+    pub scheduled_date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListModuleSessionsRequest {
+    pub module_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransitionSessionRequest {
+    pub session_id: i32,
+    pub new_stage: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScheduleSessionRequest {
+    pub session_id: i32,
+    pub scheduled_date: Option<String>,
+}
+
+/// Create a new session.
+///
+/// Creates the next numbered session in the campaign for the given module,
+/// starting in the "planned" stage.
+///
+/// # Parameters
+/// - `request` - Session creation request with module ID and optional date
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the created `Session`.
+///
+/// # Errors
+/// Returns an error response if the module doesn't exist or database operations fail.
+#[tauri::command]
+pub async fn create_session(
+    request: CreateSessionRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Session>, ApiError> {
+    info!("Creating session for module: {}", request.module_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.create_session(request.module_id, request.scheduled_date) {
+        Ok(session) => {
+            info!("Session {} created", session.session_number);
+            Ok(ApiResponse::success(session))
+        }
+        Err(e) => {
+            error!("Failed to create session: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to create session: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Get a session by ID.
+///
+/// # Parameters
+/// - `id` - The database ID of the session
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the `Session` if found.
+///
+/// # Errors
+/// Returns an error response if the session is not found or database operations fail.
+#[tauri::command]
+pub async fn get_session(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Session>, ApiError> {
+    info!("Getting session with ID: {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.get_session(id) {
+        Ok(Some(session)) => Ok(ApiResponse::success(session)),
+        Ok(None) => Ok(ApiResponse::error(format!("Session not found: {}", id))),
+        Err(e) => {
+            error!("Failed to get session: {}", e);
+            Ok(ApiResponse::error(format!("Failed to get session: {}", e)))
+        }
+    }
+}
+
+/// List sessions for a module.
+///
+/// # Parameters
+/// - `request` - Request containing the module ID
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the module's sessions in play order.
+///
+/// # Errors
+/// Returns an error response if database operations fail.
+#[tauri::command]
+pub async fn list_module_sessions(
+    request: ListModuleSessionsRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Session>>, ApiError> {
+    info!("Listing sessions for module: {}", request.module_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.list_module_sessions(request.module_id) {
+        Ok(sessions) => {
+            info!("Found {} sessions", sessions.len());
+            Ok(ApiResponse::success(sessions))
+        }
+        Err(e) => {
+            error!("Failed to list module sessions: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to list module sessions: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// List sessions for a campaign.
+///
+/// # Parameters
+/// - `campaign_id` - The database ID of the campaign
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing all sessions in the campaign in play order.
+///
+/// # Errors
+/// Returns an error response if database operations fail.
+#[tauri::command]
+pub async fn list_campaign_sessions(
+    campaign_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Session>>, ApiError> {
+    info!("Listing sessions for campaign: {}", campaign_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.list_campaign_sessions(campaign_id) {
+        Ok(sessions) => Ok(ApiResponse::success(sessions)),
+        Err(e) => {
+            error!("Failed to list campaign sessions: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to list campaign sessions: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Schedule a session.
+///
+/// Sets or clears the date the session is scheduled for.
+///
+/// # Parameters
+/// - `request` - Request with session ID and ISO date (or null to unschedule)
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the updated `Session`.
+///
+/// # Errors
+/// Returns an error response if the session doesn't exist or database operations fail.
+#[tauri::command]
+pub async fn schedule_session(
+    request: ScheduleSessionRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Session>, ApiError> {
+    info!(
+        "Scheduling session {} for {:?}",
+        request.session_id, request.scheduled_date
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.schedule_session(request.session_id, request.scheduled_date) {
+        Ok(session) => Ok(ApiResponse::success(session)),
+        Err(e) => {
+            error!("Failed to schedule session: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to schedule session: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Transition a session to a new stage.
+///
+/// Moves the session along the session board and creates the documents the
+/// new stage needs (the outline when prepping, notes once played).
+///
+/// # Parameters
+/// - `request` - Transition request with session ID and target stage
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the updated `Session`.
+///
+/// # Errors
+/// Returns an error response if the transition is invalid or database operations fail.
+#[tauri::command]
+pub async fn transition_session_stage(
+    request: TransitionSessionRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Session>, ApiError> {
+    info!(
+        "Transitioning session {} to stage: {}",
+        request.session_id, request.new_stage
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.transition_session_stage(request.session_id, &request.new_stage) {
+        Ok(session) => {
+            info!("Session transitioned to: {}", session.status);
+            Ok(ApiResponse::success(session))
+        }
+        Err(e) => {
+            error!("Failed to transition session: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to transition session: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Get session documents.
+///
+/// # Parameters
+/// - `session_id` - The database ID of the session
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the session's documents.
+///
+/// # Errors
+/// Returns an error response if database operations fail.
+#[tauri::command]
+pub async fn get_session_documents(
+    session_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Document>>, ApiError> {
+    info!("Getting documents for session: {}", session_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.get_session_documents(session_id) {
+        Ok(documents) => Ok(ApiResponse::success(documents)),
+        Err(e) => {
+            error!("Failed to get session documents: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to get session documents: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Check session completion status.
+///
+/// # Parameters
+/// - `session_id` - The database ID of the session
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` containing the `BoardCompletionStatus` for the session's stage.
+///
+/// # Errors
+/// Returns an error response if the session doesn't exist or database operations fail.
+#[tauri::command]
+pub async fn check_session_completion(
+    session_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<BoardCompletionStatus>, ApiError> {
+    info!("Checking completion status for session: {}", session_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.check_session_completion(session_id) {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => {
+            error!("Failed to check session completion: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to check session completion: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Delete a session.
+///
+/// Removes the session and its document records. Files on disk are kept.
+///
+/// # Parameters
+/// - `id` - The database ID of the session to delete
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// `ApiResponse` with success or error status.
+///
+/// # Errors
+/// Returns an error response if database operations fail.
+#[tauri::command]
+pub async fn delete_session(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<()>, ApiError> {
+    info!("Deleting session with ID: {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = SessionService::new(&mut conn);
+
+    match service.delete_session(id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to delete session: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to delete session: {}",
+                e
+            )))
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Area of effect template command handlers.
+//!
+//! Commands for placing spell templates (cones, cubes, spheres, lines and
+//! cylinders) on maps and finding the tokens they cover.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::models::campaign::{
+    MapTemplate, NewMapTemplate, TemplateShape, Token, UpdateMapTemplate,
+};
+use mimir_dm_core::services::MapTemplateService;
+use serde::Deserialize;
+use tauri::State;
+use tracing::{error, info};
+
+/// Request to place a template on a map
+#[derive(Debug, Deserialize)]
+pub struct CreateTemplateRequest {
+    pub map_id: i32,
+    pub name: String,
+    /// "cone", "cube", "sphere", "line" or "cylinder"
+    pub shape: String,
+    pub x: f32,
+    pub y: f32,
+    /// Cone/line length, cube side or sphere/cylinder radius in feet
+    pub size_ft: f32,
+    /// Degrees clockwise from east (defaults to 0)
+    pub direction_deg: Option<f32>,
+    /// Line width in feet (defaults to 5)
+    pub width_ft: Option<f32>,
+    pub color: Option<String>,
+    pub visible_to_players: Option<bool>,
+}
+
+/// Request to place a catalog spell's area of effect on a map
+#[derive(Debug, Deserialize)]
+pub struct CreateSpellTemplateRequest {
+    pub map_id: i32,
+    pub spell_name: String,
+    pub spell_source: String,
+    pub x: f32,
+    pub y: f32,
+    pub direction_deg: Option<f32>,
+}
+
+/// Request to update a template
+#[derive(Debug, Deserialize)]
+pub struct UpdateTemplateRequest {
+    pub name: Option<String>,
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub direction_deg: Option<f32>,
+    pub size_ft: Option<f32>,
+    pub width_ft: Option<f32>,
+    pub color: Option<Option<String>>,
+    pub visible_to_players: Option<bool>,
+}
+
+/// Place a template on a map.
+///
+/// # Parameters
+/// - `request` - Template shape, size and position
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `MapTemplate`.
+#[tauri::command]
+pub async fn create_map_template(
+    request: CreateTemplateRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapTemplate>, ApiError> {
+    info!(
+        "Creating {} template on map {}",
+        request.shape, request.map_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    let mut new_template = NewMapTemplate::new(
+        request.map_id,
+        request.name,
+        TemplateShape::from_str(&request.shape),
+        request.x,
+        request.y,
+        request.size_ft,
+    )
+    .with_direction(request.direction_deg.unwrap_or(0.0));
+    if let Some(width_ft) = request.width_ft {
+        new_template = new_template.with_width(width_ft);
+    }
+    if let Some(color) = request.color {
+        new_template = new_template.with_color(color);
+    }
+    if let Some(visible) = request.visible_to_players {
+        new_template = new_template.with_visibility(visible);
+    }
+
+    match service.create_template(new_template) {
+        Ok(template) => {
+            info!("Template created with ID: {}", template.id);
+            Ok(ApiResponse::success(template))
+        }
+        Err(e) => {
+            error!("Failed to create template: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to create template: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Place a catalog spell's area of effect on a map.
+///
+/// # Parameters
+/// - `request` - Spell name and source, point of origin and direction
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `MapTemplate`.
+#[tauri::command]
+pub async fn create_spell_template(
+    request: CreateSpellTemplateRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapTemplate>, ApiError> {
+    info!(
+        "Creating template for spell {} ({}) on map {}",
+        request.spell_name, request.spell_source, request.map_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    match service.create_spell_template(
+        request.map_id,
+        &request.spell_name,
+        &request.spell_source,
+        request.x,
+        request.y,
+        request.direction_deg.unwrap_or(0.0),
+    ) {
+        Ok(template) => {
+            info!("Spell template created with ID: {}", template.id);
+            Ok(ApiResponse::success(template))
+        }
+        Err(e) => {
+            error!("Failed to create spell template: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to create spell template: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Get all templates on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of `MapTemplate`.
+#[tauri::command]
+pub async fn list_map_templates(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<MapTemplate>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    match service.get_templates_for_map(map_id) {
+        Ok(templates) => Ok(ApiResponse::success(templates)),
+        Err(e) => {
+            error!("Failed to list templates: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to list templates: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Update a template (move, turn, resize or hide it).
+///
+/// # Parameters
+/// - `id` - Database ID of the template
+/// - `request` - Fields to update
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `MapTemplate`.
+#[tauri::command]
+pub async fn update_map_template(
+    id: i32,
+    request: UpdateTemplateRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapTemplate>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    let update = UpdateMapTemplate {
+        name: request.name,
+        x: request.x,
+        y: request.y,
+        direction_deg: request.direction_deg,
+        size_ft: request.size_ft,
+        width_ft: request.width_ft,
+        color: request.color,
+        visible_to_players: request.visible_to_players,
+        updated_at: None,
+    };
+
+    match service.update_template(id, update) {
+        Ok(template) => Ok(ApiResponse::success(template)),
+        Err(e) => {
+            error!("Failed to update template: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to update template: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Get the tokens a template covers.
+///
+/// # Parameters
+/// - `id` - Database ID of the template
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of covered `Token`s.
+#[tauri::command]
+pub async fn get_tokens_in_template(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Token>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    match service.get_tokens_in_template(id) {
+        Ok(tokens) => Ok(ApiResponse::success(tokens)),
+        Err(e) => {
+            error!("Failed to get tokens in template: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to get tokens in template: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Delete a template.
+///
+/// # Parameters
+/// - `id` - Database ID of the template
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` indicating success.
+#[tauri::command]
+pub async fn delete_map_template(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<()>, ApiError> {
+    info!("Deleting template {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    match service.delete_template(id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to delete template: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to delete template: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Delete every template on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the number of templates deleted.
+#[tauri::command]
+pub async fn clear_map_templates(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<usize>, ApiError> {
+    info!("Clearing templates on map {}", map_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = MapTemplateService::new(&mut conn);
+
+    match service.delete_all_for_map(map_id) {
+        Ok(count) => Ok(ApiResponse::success(count)),
+        Err(e) => {
+            error!("Failed to clear templates: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to clear templates: {}",
+                e
+            )))
+        }
+    }
+}
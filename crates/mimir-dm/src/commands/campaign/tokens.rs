@@ -6,6 +6,7 @@
 use crate::state::AppState;
 use crate::types::{ApiError, ApiResponse};
 use tracing::{error, info};
+use mimir_dm_core::domain::{Formation, Point};
 use mimir_dm_core::models::campaign::{NewToken, Token, TokenSummary, TokenSize, TokenType, UpdateToken, VisionType};
 use mimir_dm_core::services::TokenService;
 use serde::Deserialize;
@@ -44,6 +45,19 @@ pub struct UpdateTokenRequest {
     pub vision_range_ft: Option<f32>,
 }
 
+/// Request to place a module encounter's monsters on a map
+#[derive(Debug, Deserialize)]
+pub struct PlaceEncounterRequest {
+    pub map_id: i32,
+    /// Encounter tag within the map's module (None for untagged monsters)
+    pub encounter_tag: Option<String>,
+    /// Where to center the group (pixels)
+    pub x: f32,
+    pub y: f32,
+    /// "cluster", "line" or "ring" (defaults to cluster)
+    pub formation: Option<String>,
+}
+
 /// Request to update multiple token positions
 #[derive(Debug, Deserialize)]
 pub struct BulkPositionUpdate {
@@ -106,6 +120,50 @@ pub async fn create_token(
     }
 }
 
+/// Place one hidden token per monster in a module encounter.
+///
+/// # Parameters
+/// - `request` - Map, encounter tag, position and formation
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `Token`s.
+#[tauri::command]
+pub async fn place_encounter_tokens(
+    request: PlaceEncounterRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<Token>>, ApiError> {
+    info!(
+        "Placing encounter {:?} on map {}",
+        request.encounter_tag, request.map_id
+    );
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = TokenService::new(&mut conn);
+
+    let formation = request
+        .formation
+        .as_deref()
+        .map(Formation::from_str)
+        .unwrap_or_default();
+
+    match service.place_encounter(
+        request.map_id,
+        request.encounter_tag.as_deref(),
+        Point::new(request.x, request.y),
+        formation,
+    ) {
+        Ok(tokens) => {
+            info!("Placed {} encounter tokens", tokens.len());
+            Ok(ApiResponse::success(tokens))
+        }
+        Err(e) => {
+            error!("Failed to place encounter: {}", e);
+            Ok(ApiResponse::error(format!("Failed to place encounter: {}", e)))
+        }
+    }
+}
+
 /// Get a token by ID.
 ///
 /// # Parameters
@@ -133,6 +191,35 @@ pub async fn get_token(
     }
 }
 
+/// Measure the distance between two tokens in feet.
+///
+/// Uses the map's grid and the campaign's diagonal rule.
+///
+/// # Parameters
+/// - `from_id` - Database ID of the first token
+/// - `to_id` - Database ID of the second token
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the distance in feet.
+#[tauri::command]
+pub async fn measure_token_distance(
+    from_id: i32,
+    to_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<f32>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = TokenService::new(&mut conn);
+
+    match service.distance_between(from_id, to_id) {
+        Ok(distance_ft) => Ok(ApiResponse::success(distance_ft)),
+        Err(e) => {
+            error!("Failed to measure token distance: {}", e);
+            Ok(ApiResponse::error(format!("Failed to measure token distance: {}", e)))
+        }
+    }
+}
+
 /// List all tokens for a map.
 ///
 /// # Parameters
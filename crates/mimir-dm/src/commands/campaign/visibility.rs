@@ -0,0 +1,69 @@
+//! Visibility command handlers.
+//!
+//! Commands for computing what tokens can see on a map, taking walls,
+//! doors, vision types and lighting into account.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::domain::MapVisibility;
+use mimir_dm_core::services::VisibilityService;
+use tauri::State;
+use tracing::error;
+
+/// Compute what the party can see on a map.
+///
+/// The party is every PC and NPC token visible to players.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the lit areas and each party token's sight.
+#[tauri::command]
+pub async fn get_party_visibility(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapVisibility>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = VisibilityService::new(&mut conn);
+
+    match service.get_party_visibility(map_id) {
+        Ok(visibility) => Ok(ApiResponse::success(visibility)),
+        Err(e) => {
+            error!("Failed to compute party visibility: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to compute party visibility: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Compute what a single token can see.
+///
+/// # Parameters
+/// - `token_id` - Database ID of the token
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the lit areas and the token's sight.
+#[tauri::command]
+pub async fn get_token_visibility(
+    token_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapVisibility>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = VisibilityService::new(&mut conn);
+
+    match service.get_token_visibility(token_id) {
+        Ok(visibility) => Ok(ApiResponse::success(visibility)),
+        Err(e) => {
+            error!("Failed to compute token visibility: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to compute token visibility: {}",
+                e
+            )))
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Wall and door command handlers.
+//!
+//! Commands for editing the line-of-sight geometry of maps - wall segments
+//! and doors that can be opened, closed or locked.
+
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::models::campaign::{
+    DoorState, MapDoor, MapWall, NewMapDoor, NewMapWall, UpdateMapDoor, UpdateMapWall,
+};
+use mimir_dm_core::services::WallService;
+use serde::Deserialize;
+use tauri::State;
+use tracing::{error, info};
+
+/// Request to create a wall segment
+#[derive(Debug, Deserialize)]
+pub struct CreateWallRequest {
+    pub map_id: i32,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+}
+
+/// Request to move a wall segment's end points
+#[derive(Debug, Deserialize)]
+pub struct UpdateWallRequest {
+    pub x1: Option<f32>,
+    pub y1: Option<f32>,
+    pub x2: Option<f32>,
+    pub y2: Option<f32>,
+}
+
+/// Request to create a door
+#[derive(Debug, Deserialize)]
+pub struct CreateDoorRequest {
+    pub map_id: i32,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    /// "open", "closed" or "locked" (defaults to closed)
+    pub state: Option<String>,
+}
+
+/// Request to update a door
+#[derive(Debug, Deserialize)]
+pub struct UpdateDoorRequest {
+    pub x1: Option<f32>,
+    pub y1: Option<f32>,
+    pub x2: Option<f32>,
+    pub y2: Option<f32>,
+    pub state: Option<String>,
+}
+
+/// Create a wall segment on a map.
+///
+/// # Parameters
+/// - `request` - Wall end points
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `MapWall`.
+#[tauri::command]
+pub async fn create_wall(
+    request: CreateWallRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapWall>, ApiError> {
+    info!("Creating wall on map {}", request.map_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    let new_wall = NewMapWall::new(
+        request.map_id,
+        request.x1,
+        request.y1,
+        request.x2,
+        request.y2,
+    );
+
+    match service.create_wall(new_wall) {
+        Ok(wall) => {
+            info!("Wall created with ID: {}", wall.id);
+            Ok(ApiResponse::success(wall))
+        }
+        Err(e) => {
+            error!("Failed to create wall: {}", e);
+            Ok(ApiResponse::error(format!("Failed to create wall: {}", e)))
+        }
+    }
+}
+
+/// Get all wall segments on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of `MapWall`.
+#[tauri::command]
+pub async fn list_walls(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<MapWall>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    match service.get_walls_for_map(map_id) {
+        Ok(walls) => Ok(ApiResponse::success(walls)),
+        Err(e) => {
+            error!("Failed to list walls: {}", e);
+            Ok(ApiResponse::error(format!("Failed to list walls: {}", e)))
+        }
+    }
+}
+
+/// Move a wall segment.
+///
+/// # Parameters
+/// - `id` - Database ID of the wall
+/// - `request` - End points to change
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `MapWall`.
+#[tauri::command]
+pub async fn update_wall(
+    id: i32,
+    request: UpdateWallRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapWall>, ApiError> {
+    info!("Updating wall {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    let update = UpdateMapWall {
+        x1: request.x1,
+        y1: request.y1,
+        x2: request.x2,
+        y2: request.y2,
+    };
+
+    match service.update_wall(id, update) {
+        Ok(wall) => Ok(ApiResponse::success(wall)),
+        Err(e) => {
+            error!("Failed to update wall: {}", e);
+            Ok(ApiResponse::error(format!("Failed to update wall: {}", e)))
+        }
+    }
+}
+
+/// Delete a wall segment.
+///
+/// # Parameters
+/// - `id` - Database ID of the wall
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` indicating success.
+#[tauri::command]
+pub async fn delete_wall(id: i32, state: State<'_, AppState>) -> Result<ApiResponse<()>, ApiError> {
+    info!("Deleting wall {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    match service.delete_wall(id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to delete wall: {}", e);
+            Ok(ApiResponse::error(format!("Failed to delete wall: {}", e)))
+        }
+    }
+}
+
+/// Create a door on a map.
+///
+/// # Parameters
+/// - `request` - Door end points and optional state
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the created `MapDoor`.
+#[tauri::command]
+pub async fn create_door(
+    request: CreateDoorRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapDoor>, ApiError> {
+    info!("Creating door on map {}", request.map_id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    let door_state = request
+        .state
+        .as_deref()
+        .map(DoorState::from_str)
+        .unwrap_or_default();
+    let new_door = NewMapDoor::new(
+        request.map_id,
+        request.x1,
+        request.y1,
+        request.x2,
+        request.y2,
+    )
+    .with_state(door_state);
+
+    match service.create_door(new_door) {
+        Ok(door) => {
+            info!("Door created with ID: {}", door.id);
+            Ok(ApiResponse::success(door))
+        }
+        Err(e) => {
+            error!("Failed to create door: {}", e);
+            Ok(ApiResponse::error(format!("Failed to create door: {}", e)))
+        }
+    }
+}
+
+/// Get all doors on a map.
+///
+/// # Parameters
+/// - `map_id` - Database ID of the map
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing a vector of `MapDoor`.
+#[tauri::command]
+pub async fn list_doors(
+    map_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<MapDoor>>, ApiError> {
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    match service.get_doors_for_map(map_id) {
+        Ok(doors) => Ok(ApiResponse::success(doors)),
+        Err(e) => {
+            error!("Failed to list doors: {}", e);
+            Ok(ApiResponse::error(format!("Failed to list doors: {}", e)))
+        }
+    }
+}
+
+/// Move a door or change its state.
+///
+/// # Parameters
+/// - `id` - Database ID of the door
+/// - `request` - Fields to change
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `MapDoor`.
+#[tauri::command]
+pub async fn update_door(
+    id: i32,
+    request: UpdateDoorRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapDoor>, ApiError> {
+    info!("Updating door {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    let update = UpdateMapDoor {
+        x1: request.x1,
+        y1: request.y1,
+        x2: request.x2,
+        y2: request.y2,
+        state: request
+            .state
+            .map(|s| DoorState::from_str(&s).as_str().to_string()),
+        updated_at: None,
+    };
+
+    match service.update_door(id, update) {
+        Ok(door) => Ok(ApiResponse::success(door)),
+        Err(e) => {
+            error!("Failed to update door: {}", e);
+            Ok(ApiResponse::error(format!("Failed to update door: {}", e)))
+        }
+    }
+}
+
+/// Open, close or lock a door.
+///
+/// # Parameters
+/// - `id` - Database ID of the door
+/// - `door_state` - "open", "closed" or "locked"
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` containing the updated `MapDoor`.
+#[tauri::command]
+pub async fn set_door_state(
+    id: i32,
+    door_state: String,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<MapDoor>, ApiError> {
+    info!("Setting door {} to {}", id, door_state);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    match service.set_door_state(id, DoorState::from_str(&door_state)) {
+        Ok(door) => Ok(ApiResponse::success(door)),
+        Err(e) => {
+            error!("Failed to set door state: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to set door state: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Delete a door.
+///
+/// # Parameters
+/// - `id` - Database ID of the door
+/// - `state` - Application state
+///
+/// # Returns
+/// `ApiResponse` indicating success.
+#[tauri::command]
+pub async fn delete_door(id: i32, state: State<'_, AppState>) -> Result<ApiResponse<()>, ApiError> {
+    info!("Deleting door {}", id);
+
+    let mut conn = state.db.get_connection()?;
+    let mut service = WallService::new(&mut conn);
+
+    match service.delete_door(id) {
+        Ok(()) => Ok(ApiResponse::success(())),
+        Err(e) => {
+            error!("Failed to delete door: {}", e);
+            Ok(ApiResponse::error(format!("Failed to delete door: {}", e)))
+        }
+    }
+}
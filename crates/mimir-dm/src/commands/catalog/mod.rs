@@ -26,6 +26,7 @@ pub mod optional_feature;
 pub mod psionic;
 pub mod race;
 pub mod reward;
+pub mod search;
 pub mod spell;
 pub mod table;
 pub mod trap;
@@ -39,5 +40,6 @@ pub use monster::*;
 pub use object::*;
 pub use optional_feature::*;
 pub use race::*;
+pub use search::*;
 pub use spell::*;
 pub use trap::*;
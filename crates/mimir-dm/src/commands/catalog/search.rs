@@ -0,0 +1,56 @@
+//! Full-text search across all catalog kinds.
+//!
+//! Provides a single Tauri command that searches names and entry text of
+//! every catalog table through the FTS5 index, so the frontend can offer one
+//! search box over spells, monsters, items and everything else.
+
+use crate::state::AppState;
+use mimir_dm_core::models::catalog::{CatalogKind, CatalogSearchFilter, CatalogSearchHit};
+use mimir_dm_core::services::CatalogSearchService;
+use tauri::State;
+use tracing::debug;
+
+/// Default number of hits returned
+const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+/// Search names and entry text across every catalog.
+///
+/// Builds the index on first use if catalog content was imported before
+/// the index existed.
+///
+/// # Parameters
+/// - `query` - Free text; double-quoted parts match as phrases
+/// - `kinds` - Restrict to these kinds (e.g., `["spell", "monster"]`)
+/// - `sources` - Restrict to these source books (e.g., `["PHB", "MM"]`)
+/// - `limit` - Maximum number of hits to return
+///
+/// # Returns
+/// List of `CatalogSearchHit` objects, best match first, each with its kind,
+/// catalog row ID and a highlighted snippet.
+///
+/// # Errors
+/// Returns an error string if the database connection or query fails.
+#[tauri::command]
+pub async fn search_catalog(
+    query: String,
+    kinds: Option<Vec<CatalogKind>>,
+    sources: Option<Vec<String>>,
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CatalogSearchHit>, String> {
+    debug!(
+        "Catalog full-text search - query: {:?}, kinds: {:?}, sources: {:?}",
+        query, kinds, sources
+    );
+
+    let filter = CatalogSearchFilter {
+        kinds: kinds.unwrap_or_default(),
+        sources: sources.unwrap_or_default(),
+    };
+
+    state.with_connection("catalog search", |conn| {
+        let mut service = CatalogSearchService::new(conn);
+        service.ensure_index()?;
+        service.search(&query, &filter, limit.unwrap_or(DEFAULT_SEARCH_LIMIT))
+    })
+}
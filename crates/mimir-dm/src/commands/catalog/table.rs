@@ -4,7 +4,7 @@
 //! from the 5e catalog database. Used for random generation and DM tools.
 
 use crate::state::AppState;
-use mimir_dm_core::models::catalog::table::{Table, TableFilters, TableSummary};
+use mimir_dm_core::models::catalog::table::{Table, TableFilters, TableRollResult, TableSummary};
 use mimir_dm_core::services::TableService;
 use tauri::State;
 use tracing::{debug, info};
@@ -85,6 +85,48 @@ pub async fn get_table(id: i32, state: State<'_, AppState>) -> Result<Option<Tab
         .map_err(|e| format!("Failed to get table: {}", e))
 }
 
+/// Roll on a table by name and source.
+///
+/// Rolls the table's dice (or picks a row uniformly when the table has no
+/// roll column) and recursively rolls any `{@table}` references in the result.
+///
+/// # Parameters
+/// - `name` - Table name
+/// - `source` - Source book abbreviation (e.g., "DMG", "XGE")
+///
+/// # Returns
+/// A `TableRollResult` with the roll, the selected row and any nested rolls.
+///
+/// # Errors
+/// Returns an error string if the table doesn't exist or can't be rolled.
+#[tauri::command]
+pub async fn roll_table(
+    name: String,
+    source: String,
+    state: State<'_, AppState>,
+) -> Result<TableRollResult, String> {
+    debug!("Rolling on table: {} from {}", name, source);
+
+    let mut conn = state
+        .db
+        .get_connection()
+        .map_err(|e| format!("Database connection failed: {}", e))?;
+
+    let mut service = TableService::new(&mut conn);
+
+    let result = service
+        .roll_table(&name, &source)
+        .map_err(|e| format!("Failed to roll table: {}", e))?;
+
+    info!(
+        "Rolled {} on {}: {}",
+        result.roll,
+        result.table_name,
+        result.result.join(" | ")
+    );
+    Ok(result)
+}
+
 /// Get complete table details by name and source.
 ///
 /// Retrieves the full table record including rows and dice expressions.
@@ -12,9 +12,16 @@ use mimir_dm_core::models::character::{
     Character, CharacterData, CharacterVersion, LegendaryAction, SpellReference,
 };
 use mimir_dm_core::services::character::creation::{AbilityScoreMethod, CharacterBuilder};
+use mimir_dm_core::services::character::derived_stats::resolve_equipped_items;
 use mimir_dm_core::services::character::level_up::{AsiOrFeat, HpGainMethod, LevelUpOptions};
+use mimir_dm_core::services::character::optional_features::OptionalFeatureGain;
 use mimir_dm_core::services::character::renderer::{CharacterRenderer, MarkdownRenderer};
 use mimir_dm_core::services::character::spell_management::RestType;
+use mimir_dm_core::services::character::starting_equipment::{
+    starting_equipment_options, StartingEquipmentOptions,
+};
+use mimir_dm_core::services::character::validator::{CharacterValidator, ValidationReport};
+use mimir_dm_core::services::character::version_diff::CharacterDiff;
 use mimir_dm_core::services::CharacterService;
 use mimir_dm_core::services::{ClassService, ItemService, SpellService};
 use std::collections::HashMap;
@@ -60,8 +67,8 @@ pub async fn create_minimal_character(
 ) -> Result<Character, String> {
     use chrono::Utc;
     use mimir_dm_core::models::character::data::{
-        AbilityScores, CharacterData, Currency, EquippedItems, Personality, Proficiencies,
-        SpellData,
+        AbilityScores, CharacterData, Currency, DeathSaves, EquippedItems, Personality,
+        Proficiencies, SpellData,
     };
 
     let mut conn = state.db.get_connection().map_err(|e| {
@@ -99,6 +106,11 @@ pub async fn create_minimal_character(
         },
         max_hp: 10,
         current_hp: 10,
+        temp_hp: 0,
+        death_saves: DeathSaves::default(),
+        exhaustion_level: 0,
+        conditions: Vec::new(),
+        concentration: None,
         speed: 30,
         proficiencies: Proficiencies {
             skills: vec![],
@@ -109,7 +121,9 @@ pub async fn create_minimal_character(
             languages: vec![],
         },
         class_features: vec![],
+        class_resources: vec![],
         feats: vec![],
+        optional_features: vec![],
         spells: SpellData::default(),
         inventory: vec![],
         currency: Currency::default(),
@@ -242,6 +256,12 @@ pub async fn create_character(
         }
     }
 
+    if let Some(choice) = request.starting_equipment {
+        builder = builder
+            .choose_starting_equipment(choice)
+            .map_err(|e| format!("Failed to choose starting equipment: {}", e))?;
+    }
+
     if let Some(equipment) = request.equipment {
         for item in equipment {
             builder = builder.add_equipment(InventoryItem {
@@ -251,6 +271,9 @@ pub async fn create_character(
                 weight: item.weight,
                 value: item.value,
                 notes: item.notes,
+                requires_attunement: false,
+                attuned: false,
+                charges: None,
             });
         }
     }
@@ -627,6 +650,72 @@ pub async fn get_character_version(
         .map_err(|e| format!("Failed to get character version: {}", e))
 }
 
+/// Compare two versions of a character.
+///
+/// Reports changed values (HP, abilities, classes, currency), spell slot
+/// changes, inventory changes and added or removed spells, feats and features.
+///
+/// # Parameters
+/// - `character_id` - The database ID of the character
+/// - `from_version` - The older version number
+/// - `to_version` - The newer version number
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// A `CharacterDiff` describing what changed from `from_version` to `to_version`.
+///
+/// # Errors
+/// Returns an error string if either version is not found or database operations fail.
+#[tauri::command]
+pub async fn diff_character_versions(
+    character_id: i32,
+    from_version: i32,
+    to_version: i32,
+    state: State<'_, AppState>,
+) -> Result<CharacterDiff, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    char_service
+        .diff_character_versions(character_id, from_version, to_version)
+        .map_err(|e| format!("Failed to diff character versions: {}", e))
+}
+
+/// Restore a character to an earlier version.
+///
+/// Saves the old snapshot as a new version, so history is preserved and the
+/// restore can itself be undone.
+///
+/// # Parameters
+/// - `character_id` - The database ID of the character
+/// - `version_number` - The version number to restore
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// The new `CharacterVersion` record created by the restore.
+///
+/// # Errors
+/// Returns an error string if the version is not found or database operations fail.
+#[tauri::command]
+pub async fn restore_character_version(
+    character_id: i32,
+    version_number: i32,
+    state: State<'_, AppState>,
+) -> Result<CharacterVersion, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    char_service
+        .restore_character_version(character_id, version_number)
+        .map_err(|e| format!("Failed to restore character version: {}", e))
+}
+
 /// Update character data directly.
 ///
 /// Replaces the character's data with the provided values, creating a new
@@ -784,6 +873,7 @@ pub async fn level_up_character(
         hp_method,
         asi_or_feat,
         subclass_choice: request.subclass,
+        optional_features: request.optional_features.unwrap_or_default(),
         snapshot_reason: None,
     };
 
@@ -818,6 +908,81 @@ pub async fn level_up_character(
     Ok(result)
 }
 
+/// Get the optional features available when leveling up.
+///
+/// Computes how many Eldritch Invocations, Fighting Styles, Metamagic options
+/// or maneuvers the class gains at its next level and lists the catalog
+/// options whose prerequisites the character meets.
+///
+/// # Parameters
+/// - `character_id` - The database ID of the character
+/// - `class_name` - Class being leveled up
+/// - `subclass` - Subclass chosen at this level, if any
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// One entry per kind of optional feature with open choices.
+///
+/// # Errors
+/// Returns an error string if the character is not found or database
+/// operations fail.
+#[tauri::command]
+pub async fn get_level_up_optional_features(
+    character_id: i32,
+    class_name: String,
+    subclass: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<OptionalFeatureGain>, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    char_service
+        .get_level_up_optional_features(character_id, &class_name, subclass.as_deref())
+        .map_err(|e| format!("Failed to get optional features: {}", e))
+}
+
+/// Get starting equipment options for character creation.
+///
+/// Lists the class's (a)/(b) equipment choice groups, the background's
+/// equipment and the starting gold that can be taken instead.
+///
+/// # Parameters
+/// - `class_name` - Name of the class
+/// - `class_source` - Source book of the class (e.g., "PHB")
+/// - `background_name` - Name of the background, if chosen
+/// - `background_source` - Source book of the background
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// The fixed items, choice groups and gold alternative.
+///
+/// # Errors
+/// Returns an error string if the class or background is not found or
+/// database operations fail.
+#[tauri::command]
+pub async fn get_starting_equipment_options(
+    class_name: String,
+    class_source: String,
+    background_name: Option<String>,
+    background_source: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<StartingEquipmentOptions, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let background = background_name
+        .as_deref()
+        .map(|name| (name, background_source.as_deref().unwrap_or("PHB")));
+
+    starting_equipment_options(&mut conn, &class_name, &class_source, background)
+        .map_err(|e| format!("Failed to get starting equipment options: {}", e))
+}
+
 /// Add a spell to known spells.
 ///
 /// Adds a spell to the character's known spells or cantrips list.
@@ -962,6 +1127,42 @@ pub async fn take_rest(
         .map_err(|e| format!("Failed to rest: {}", e))
 }
 
+/// Set a character's current HP.
+///
+/// Lowering HP is applied as damage: temporary HP absorbs it first, damage at
+/// 0 HP counts as a failed death save, and falling unconscious ends
+/// concentration. Raising HP is applied as healing.
+///
+/// # Parameters
+/// - `character_id` - The database ID of the character
+/// - `new_hp` - The new current HP (clamped to 0..=max HP)
+/// - `reason` - Optional snapshot reason for the new version
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// The new `CharacterVersion` record with updated HP.
+///
+/// # Errors
+/// Returns an error string if the character is not found or database operations fail.
+#[tauri::command]
+pub async fn update_character_hp(
+    character_id: i32,
+    new_hp: i32,
+    reason: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CharacterVersion, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    char_service
+        .update_hp(character_id, new_hp, reason)
+        .map(|(version, _outcome)| version)
+        .map_err(|e| format!("Failed to update HP: {}", e))
+}
+
 /// Add an item to character inventory.
 ///
 /// Adds an item to the character's inventory with the specified quantity.
@@ -1110,6 +1311,40 @@ pub async fn update_character_equipped(
         .map_err(|e| format!("Failed to update equipped items: {}", e))
 }
 
+/// Validate a character against the rules and catalog data.
+///
+/// Checks class levels, multiclass requirements, skill proficiencies, feat
+/// and optional feature prerequisites, spell limits and attunement.
+///
+/// # Parameters
+/// - `character_id` - The database ID of the character
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// A report listing every error and warning found.
+///
+/// # Errors
+/// Returns an error string if character lookup fails or database operations fail.
+#[tauri::command]
+pub async fn validate_character(
+    character_id: i32,
+    state: State<'_, AppState>,
+) -> Result<ValidationReport, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let mut char_service = CharacterService::new(&mut conn);
+    let (_character, char_data) = char_service
+        .get_character(character_id)
+        .map_err(|e| format!("Failed to get character: {}", e))?;
+
+    CharacterValidator::new(&mut conn)
+        .validate(&char_data)
+        .map_err(|e| format!("Failed to validate character: {}", e))
+}
+
 /// Render character sheet as markdown.
 ///
 /// Generates a complete markdown representation of the character sheet
@@ -1170,6 +1405,12 @@ pub async fn render_character_sheet(
         }
     }
 
+    // Equipped items drive the derived AC and attack lines
+    match resolve_equipped_items(&mut conn, &char_data) {
+        Ok(equipped) => item_details.extend(equipped),
+        Err(e) => error!("Failed to resolve equipped items: {}", e),
+    }
+
     let renderer = MarkdownRenderer::new();
     Ok(renderer.render_with_details(&char_data, &spell_details, &item_details))
 }
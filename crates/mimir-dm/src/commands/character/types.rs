@@ -3,6 +3,7 @@
 //! Frontend-friendly types for JSON serialization in character management commands.
 
 use mimir_dm_core::models::character::SpellReference;
+use mimir_dm_core::services::character::{OptionalFeatureSelection, StartingEquipmentChoice};
 use serde::{Deserialize, Serialize};
 
 /// Request type for creating a new character with full options.
@@ -24,6 +25,7 @@ pub struct CreateCharacterRequest {
     pub personality: Option<PersonalityInput>,
     pub skill_proficiencies: Option<Vec<String>>,
     pub equipment: Option<Vec<InventoryItemInput>>,
+    pub starting_equipment: Option<StartingEquipmentChoice>, // Packages or starting gold
     pub cantrips: Option<Vec<SpellReferenceInput>>,
     pub known_spells: Option<Vec<SpellReferenceInput>>,
     // NPC fields
@@ -102,6 +104,7 @@ pub struct LevelUpRequest {
     pub new_spell_slots: Option<String>, // JSON string with spell slot updates
     pub new_known_spells: Option<Vec<SpellReferenceInput>>, // Updated known spells list
     pub new_cantrips: Option<Vec<SpellReferenceInput>>, // Updated cantrips list
+    pub optional_features: Option<Vec<OptionalFeatureSelection>>, // Invocations, fighting styles, ...
 }
 
 /// Currency update request for character inventory.
@@ -5,11 +5,12 @@
 //! efficient listing.
 
 use anyhow::{Context, Result};
+use mimir_dm_core::services::ToolJournalService;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use tauri::State;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::app_init::AppPaths;
@@ -365,7 +366,8 @@ pub async fn create_chat_session(state: State<'_, AppState>) -> Result<ChatSessi
 
 /// Delete a chat session.
 ///
-/// Removes the session file and updates the sessions index.
+/// Removes the session file, updates the sessions index and drops the
+/// session's tool action journal.
 ///
 /// # Parameters
 /// - `session_id` - UUID of the session to delete
@@ -380,8 +382,20 @@ pub async fn delete_chat_session(
     state: State<'_, AppState>,
     session_id: String,
 ) -> Result<bool, String> {
-    state.sessions.delete_session(&session_id).map_err(|e| {
+    let deleted = state.sessions.delete_session(&session_id).map_err(|e| {
         error!("Failed to delete chat session {}: {}", session_id, e);
         format!("Failed to delete session: {}", e)
-    })
+    })?;
+
+    // Undo history is meaningless without the session
+    match state.db.get_connection() {
+        Ok(mut conn) => {
+            if let Err(e) = ToolJournalService::new(&mut conn).clear_session(&session_id) {
+                warn!("Failed to clear tool journal for {}: {}", session_id, e);
+            }
+        }
+        Err(e) => warn!("Failed to get database connection: {}", e),
+    }
+
+    Ok(deleted)
 }
@@ -1,10 +1,12 @@
 //! Chat and LLM session command handlers.
 //!
-//! Contains commands for managing chat sessions with the LLM
-//! and session-specific todo lists.
+//! Contains commands for managing chat sessions with the LLM,
+//! session-specific todo lists and undo of assistant tool actions.
 
 pub mod chat_sessions;
 pub mod session_todos;
+pub mod tool_actions;
 
 pub use chat_sessions::*;
 pub use session_todos::*;
+pub use tool_actions::*;
@@ -0,0 +1,83 @@
+//! Undo commands for assistant tool actions.
+//!
+//! Mutating tool calls made during a chat are journaled with the state
+//! needed to revert them. These commands list that journal and undo the
+//! most recent actions of a session.
+
+use crate::state::AppState;
+use mimir_dm_core::models::chat::ToolAction;
+use mimir_dm_core::services::ToolJournalService;
+use tauri::State;
+use tracing::{error, info};
+
+/// List the journaled tool actions of a chat session.
+///
+/// # Parameters
+/// - `session_id` - Unique identifier of the chat session
+/// - `include_undone` - Whether to include actions that were already undone
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// The journaled actions, most recent first.
+///
+/// # Errors
+/// Returns an error string if database operations fail.
+#[tauri::command]
+pub async fn list_tool_actions(
+    session_id: String,
+    include_undone: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ToolAction>, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    ToolJournalService::new(&mut conn)
+        .list_for_session(&session_id, include_undone.unwrap_or(false))
+        .map_err(|e| format!("Failed to list tool actions: {}", e))
+}
+
+/// Undo the last N assistant actions of a chat session.
+///
+/// Character changes are rolled back by restoring the character version from
+/// before the action, created characters and modules are deleted, module
+/// stages are reset and edited files get their previous contents back.
+///
+/// # Parameters
+/// - `session_id` - Unique identifier of the chat session
+/// - `count` - Number of actions to undo (defaults to 1)
+/// - `state` - Application state containing the database connection
+///
+/// # Returns
+/// The actions that were undone, most recent first.
+///
+/// # Errors
+/// Returns an error string if an action cannot be reverted. Actions undone
+/// before the failure stay undone.
+#[tauri::command]
+pub async fn undo_tool_actions(
+    session_id: String,
+    count: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ToolAction>, String> {
+    let mut conn = state.db.get_connection().map_err(|e| {
+        error!("Failed to get database connection: {}", e);
+        format!("Database connection failed: {}", e)
+    })?;
+
+    let count = count.unwrap_or(1);
+    let undone = ToolJournalService::new(&mut conn)
+        .undo_last(&session_id, count)
+        .map_err(|e| {
+            error!("Failed to undo tool actions for {}: {}", session_id, e);
+            format!("Failed to undo tool actions: {}", e)
+        })?;
+
+    info!(
+        "Undid {} tool action(s) in session {}",
+        undone.len(),
+        session_id
+    );
+    Ok(undone)
+}
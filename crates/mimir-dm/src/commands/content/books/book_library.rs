@@ -7,12 +7,13 @@
 use crate::state::AppState;
 use crate::types::{ApiError, ApiResponse};
 use diesel::prelude::*;
+use mimir_dm_core::models::campaign::LoreSourceType;
 use mimir_dm_core::models::catalog::UploadedBook;
 use mimir_dm_core::schema::uploaded_books;
 use mimir_dm_core::services::{
-    ActionService, BackgroundService, ClassService, ConditionService, DeityService, FeatService,
-    ItemService, LanguageService, MonsterService, ObjectService, RaceService, RewardService,
-    SpellService, TrapService, VehicleService,
+    ActionService, BackgroundService, CatalogSearchService, ClassService, ConditionService,
+    DeityService, FeatService, ItemService, LanguageService, LoreIndexService, MonsterService,
+    ObjectService, RaceService, RewardService, SpellService, TrapService, VehicleService,
 };
 use std::fs;
 use std::path::Path;
@@ -150,6 +151,9 @@ pub async fn remove_book_from_library(
                     let _ = DeityService::remove_deities_from_source(conn, &book_id);
                     let _ = VehicleService::remove_vehicles_from_source(conn, &book_id);
                     let _ = ClassService::remove_classes_from_source(conn, &book_id);
+                    let _ = CatalogSearchService::new(conn).remove_source(&book_id);
+                    let _ =
+                        LoreIndexService::new(conn).remove_source(LoreSourceType::Book, &book_id);
                     // We don't want catalog cleanup errors to fail the book removal
 
                     Ok(())
@@ -5,10 +5,10 @@
 
 use diesel::prelude::*;
 use mimir_dm_core::services::{
-    ActionService, BackgroundService, ClassService, ConditionService, CultService, DeityService,
-    FeatService, ItemService, LanguageService, MonsterService, ObjectService,
-    OptionalFeatureService, PsionicService, RaceService, RewardService, SpellService, TableService,
-    TrapService, VariantRuleService, VehicleService,
+    ActionService, BackgroundService, CatalogSearchService, ClassService, ConditionService,
+    CultService, DeityService, FeatService, ItemService, LanguageService, MonsterService,
+    ObjectService, OptionalFeatureService, PsionicService, RaceService, RewardService,
+    SpellService, TableService, TrapService, VariantRuleService, VehicleService,
 };
 use std::path::Path;
 use tracing::{info, warn};
@@ -40,6 +40,9 @@ use tracing::{info, warn};
 /// Each import is attempted independently. If an import fails, a warning is logged
 /// but the function continues to import the remaining catalog types.
 ///
+/// Once everything is imported, the book's entries are added to the
+/// full-text catalog search index.
+///
 /// # Arguments
 ///
 /// * `conn` - Mutable reference to the database connection
@@ -335,4 +338,20 @@ pub fn import_all_catalogs_from_book(conn: &mut SqliteConnection, book_dir: &Pat
             );
         }
     }
+
+    // Index the imported entries for full-text search
+    match CatalogSearchService::new(conn).index_source(book_id) {
+        Ok(indexed_count) => {
+            info!(
+                "Indexed {} catalog entries from book '{}' for search",
+                indexed_count, book_id
+            );
+        }
+        Err(e) => {
+            warn!(
+                "Book uploaded successfully but failed to index catalog for search: {}",
+                e
+            );
+        }
+    }
 }
@@ -0,0 +1,181 @@
+//! Lore index commands.
+//!
+//! Provides Tauri commands for building the semantic search index over
+//! uploaded books and campaign documents, and for searching it.
+
+use crate::services::lore_service::{self, LoreEmbedder, LoreIndexReport, LoreIndexStatus};
+use crate::state::AppState;
+use crate::types::{ApiError, ApiResponse};
+use mimir_dm_core::models::campaign::{LoreSearchFilter, LoreSearchResult, LoreSourceType};
+use mimir_dm_core::services::LoreIndexService;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tracing::{error, info};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchLoreRequest {
+    pub query: String,
+    pub campaign_id: Option<i32>,
+    pub source_type: Option<LoreSourceType>,
+    pub limit: Option<i64>,
+}
+
+/// Get the lore embedder from the LLM service, if one is configured.
+async fn get_embedder(state: &AppState) -> Option<LoreEmbedder> {
+    state
+        .llm
+        .lock()
+        .await
+        .as_ref()
+        .and_then(|llm| llm.lore_embedder())
+}
+
+/// Index books and campaign documents for lore search.
+///
+/// Embeds every uploaded book and, when a campaign is given, that
+/// campaign's documents. Sources that haven't changed are skipped.
+///
+/// # Parameters
+/// - `campaign_id` - Campaign whose documents should be indexed
+/// - `state` - Application state containing the database and LLM service
+///
+/// # Returns
+/// `ApiResponse` containing a `LoreIndexReport`.
+///
+/// # Errors
+/// Returns an error response if no embedding model is configured or indexing fails.
+#[tauri::command]
+pub async fn index_lore(
+    campaign_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<LoreIndexReport>, ApiError> {
+    info!("Indexing lore (campaign: {:?})", campaign_id);
+
+    let Some(embedder) = get_embedder(&state).await else {
+        return Ok(ApiResponse::error(
+            "Lore search requires an Ollama embedding model. Configure Ollama in settings."
+                .to_string(),
+        ));
+    };
+
+    match lore_service::index_lore(&state.db, &embedder, campaign_id).await {
+        Ok(report) => Ok(ApiResponse::success(report)),
+        Err(e) => {
+            error!("Failed to index lore: {}", e);
+            Ok(ApiResponse::error(format!("Failed to index lore: {}", e)))
+        }
+    }
+}
+
+/// Clear the lore index and index everything again.
+///
+/// Needed after changing the embedding model, since vectors from different
+/// models can't be mixed.
+///
+/// # Parameters
+/// - `campaign_id` - Campaign whose documents should be indexed
+/// - `state` - Application state containing the database and LLM service
+///
+/// # Returns
+/// `ApiResponse` containing a `LoreIndexReport`.
+///
+/// # Errors
+/// Returns an error response if no embedding model is configured or indexing fails.
+#[tauri::command]
+pub async fn rebuild_lore_index(
+    campaign_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<LoreIndexReport>, ApiError> {
+    info!("Rebuilding lore index (campaign: {:?})", campaign_id);
+
+    let Some(embedder) = get_embedder(&state).await else {
+        return Ok(ApiResponse::error(
+            "Lore search requires an Ollama embedding model. Configure Ollama in settings."
+                .to_string(),
+        ));
+    };
+
+    {
+        let mut conn = state.db.get_connection()?;
+        LoreIndexService::new(&mut conn).reset_index()?;
+    }
+
+    match lore_service::index_lore(&state.db, &embedder, campaign_id).await {
+        Ok(report) => Ok(ApiResponse::success(report)),
+        Err(e) => {
+            error!("Failed to rebuild lore index: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to rebuild lore index: {}",
+                e
+            )))
+        }
+    }
+}
+
+/// Search books and campaign documents by meaning.
+///
+/// # Parameters
+/// - `request` - Query text with optional campaign, source type and limit
+/// - `state` - Application state containing the database and LLM service
+///
+/// # Returns
+/// `ApiResponse` containing matching passages, closest first.
+///
+/// # Errors
+/// Returns an error response if no embedding model is configured or the search fails.
+#[tauri::command]
+pub async fn search_lore(
+    request: SearchLoreRequest,
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<Vec<LoreSearchResult>>, ApiError> {
+    info!("Searching lore: {}", request.query);
+
+    let Some(embedder) = get_embedder(&state).await else {
+        return Ok(ApiResponse::error(
+            "Lore search requires an Ollama embedding model. Configure Ollama in settings."
+                .to_string(),
+        ));
+    };
+
+    let filter = LoreSearchFilter {
+        campaign_id: request.campaign_id,
+        source_type: request.source_type,
+    };
+    let limit = request.limit.unwrap_or(10);
+
+    match lore_service::search_lore(&state.db, &embedder, &request.query, limit, &filter).await {
+        Ok(results) => Ok(ApiResponse::success(results)),
+        Err(e) => {
+            error!("Lore search failed: {}", e);
+            Ok(ApiResponse::error(format!("Lore search failed: {}", e)))
+        }
+    }
+}
+
+/// Get the lore index status.
+///
+/// # Parameters
+/// - `state` - Application state containing the database and LLM service
+///
+/// # Returns
+/// `ApiResponse` containing the `LoreIndexStatus`.
+///
+/// # Errors
+/// Returns an error response if database operations fail.
+#[tauri::command]
+pub async fn get_lore_index_status(
+    state: State<'_, AppState>,
+) -> Result<ApiResponse<LoreIndexStatus>, ApiError> {
+    let embedder = get_embedder(&state).await;
+
+    match lore_service::lore_index_status(&state.db, embedder.as_ref()) {
+        Ok(status) => Ok(ApiResponse::success(status)),
+        Err(e) => {
+            error!("Failed to get lore index status: {}", e);
+            Ok(ApiResponse::error(format!(
+                "Failed to get lore index status: {}",
+                e
+            )))
+        }
+    }
+}
@@ -1,14 +1,16 @@
 //! Content and document management command handlers.
 //!
 //! Contains commands for managing documents, boards, context,
-//! and book imports.
+//! book imports and the lore search index.
 
 pub mod boards;
 pub mod books;
 pub mod context;
 pub mod documents;
+pub mod lore;
 
 pub use boards::*;
 pub use books::*;
 pub use context::*;
 pub use documents::*;
+pub use lore::*;
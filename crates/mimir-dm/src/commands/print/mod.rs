@@ -211,7 +211,9 @@ pub async fn generate_character_sheet(
     use mimir_dm_core::models::catalog::item::Item;
     use mimir_dm_core::models::catalog::Spell;
     use mimir_dm_core::models::catalog::SpellFilters;
+    use mimir_dm_core::services::character::{calculate_derived_stats, derive_character_stats};
     use mimir_dm_core::services::{CharacterService, ClassService, ItemService, SpellService};
+    use std::collections::HashMap;
 
     info!("Generating character sheet for character {}", character_id);
 
@@ -413,6 +415,18 @@ pub async fn generate_character_sheet(
         );
     }
 
+    // Derive AC and attack lines from equipped catalog items
+    let derived_stats = {
+        let mut stats_conn = state
+            .db
+            .get_connection()
+            .map_err(|e| format!("Database error: {}", e))?;
+        derive_character_stats(&mut stats_conn, &character_data).unwrap_or_else(|e| {
+            error!("Failed to derive combat stats: {}", e);
+            calculate_derived_stats(&character_data, &HashMap::new())
+        })
+    };
+
     // Convert character to JSON
     let character_json = serde_json::to_value(&character_data)
         .map_err(|e| format!("Failed to serialize character: {}", e))?;
@@ -438,6 +452,7 @@ pub async fn generate_character_sheet(
         "class_features_details": class_features_json,
         "subclass_features_details": subclass_features_json,
         "item_details": item_details_json,
+        "derived_stats": derived_stats,
         "include_spell_cards": should_include_spells && !spell_details.is_empty()
     });
 
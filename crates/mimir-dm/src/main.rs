@@ -222,6 +222,9 @@ fn main() {
             transition_campaign_stage,
             archive_campaign,
             unarchive_campaign,
+            get_campaign_sources,
+            set_campaign_sources,
+            set_campaign_diagonal_rule,
             delete_campaign,
             list_archived_campaigns,
             get_campaign_summary,
@@ -241,6 +244,16 @@ fn main() {
             find_modules_needing_next,
             increment_module_sessions,
             delete_module,
+            // Session commands
+            create_session,
+            get_session,
+            list_module_sessions,
+            list_campaign_sessions,
+            schedule_session,
+            transition_session_stage,
+            get_session_documents,
+            check_session_completion,
+            delete_session,
             // Module monster commands
             add_module_monster,
             remove_module_monster,
@@ -249,10 +262,13 @@ fn main() {
             list_module_monsters_with_data,
             list_module_monsters_by_encounter,
             get_module_encounter_tags,
+            get_module_encounter_difficulties,
+            generate_random_encounter,
             clear_module_monsters,
             sync_module_monsters_to_file,
             // Map commands
             upload_map,
+            import_uvtt_map,
             get_map,
             list_maps,
             list_map_summaries,
@@ -262,7 +278,9 @@ fn main() {
             serve_map_image,
             // Token commands
             create_token,
+            place_encounter_tokens,
             get_token,
+            measure_token_distance,
             list_tokens,
             list_visible_tokens,
             list_token_summaries,
@@ -280,8 +298,11 @@ fn main() {
             get_fog_state,
             reveal_rect,
             reveal_circle,
+            reveal_polygon,
             reveal_all,
             get_revealed_areas,
+            get_explored_areas,
+            record_fog_exploration,
             delete_revealed_area,
             reset_fog,
             // Light source commands
@@ -296,6 +317,51 @@ fn main() {
             toggle_light_source,
             delete_light_source,
             delete_all_light_sources,
+            // Wall, door and visibility commands
+            create_wall,
+            list_walls,
+            update_wall,
+            delete_wall,
+            create_door,
+            list_doors,
+            update_door,
+            set_door_state,
+            delete_door,
+            get_party_visibility,
+            get_token_visibility,
+            // Area of effect template commands
+            create_map_template,
+            create_spell_template,
+            list_map_templates,
+            update_map_template,
+            get_tokens_in_template,
+            delete_map_template,
+            clear_map_templates,
+            // Combat tracker commands
+            start_combat_from_encounter,
+            start_combat_from_map,
+            get_combat_state,
+            get_active_combat,
+            list_combat_encounters,
+            next_combat_turn,
+            previous_combat_turn,
+            reroll_combat_initiative,
+            set_combatant_initiative,
+            add_combatant,
+            remove_combatant,
+            set_combatant_hp,
+            set_combatant_active,
+            apply_combat_damage,
+            apply_combat_healing,
+            grant_combatant_temp_hp,
+            get_combat_hp_log,
+            add_combatant_condition,
+            remove_combatant_condition,
+            end_combat,
+            delete_combat_encounter,
+            // Dice commands
+            roll_dice,
+            roll_ability_scores,
             // Display control commands
             send_map_to_display,
             update_display_viewport,
@@ -308,6 +374,11 @@ fn main() {
             get_book_content,
             serve_book_image,
             lookup_reference,
+            // Lore search commands
+            index_lore,
+            rebuild_lore_index,
+            search_lore,
+            get_lore_index_status,
             // Dev tools
             is_dev_mode,
             remove_dev_test_book,
@@ -441,6 +512,9 @@ fn main() {
             commands::catalog::table::get_table_details,
             commands::catalog::table::get_table_categories,
             commands::catalog::table::get_table_sources,
+            commands::catalog::table::roll_table,
+            // Cross-catalog full-text search
+            search_catalog,
             // Context commands
             update_context,
             get_full_context,
@@ -466,6 +540,8 @@ fn main() {
             llm::commands::list_available_models,
             llm::commands::get_provider_settings,
             llm::commands::save_provider_settings,
+            llm::commands::get_tool_policies,
+            llm::commands::save_tool_policies,
             llm::commands::reload_llm_service,
             // Chat session commands
             list_chat_sessions,
@@ -476,6 +552,9 @@ fn main() {
             // Session todo commands
             get_session_todos,
             configure_todo_storage,
+            // Tool action undo commands
+            list_tool_actions,
+            undo_tool_actions,
             // Log management commands
             logs::list_log_files,
             logs::read_log_file,
@@ -500,19 +579,25 @@ fn main() {
             create_npc,
             get_character_versions,
             get_character_version,
+            diff_character_versions,
+            restore_character_version,
             update_character,
             delete_character,
             assign_character_to_campaign,
             level_up_character,
+            get_level_up_optional_features,
+            get_starting_equipment_options,
             add_spell_to_known,
             prepare_spells,
             cast_spell,
             take_rest,
+            update_character_hp,
             add_item_to_inventory,
             remove_item_from_inventory,
             update_character_currency,
             update_character_equipped,
             render_character_sheet,
+            validate_character,
             write_text_file,
             get_feature_details,
             // Print commands
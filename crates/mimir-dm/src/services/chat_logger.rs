@@ -54,6 +54,9 @@ pub enum ChatLogEvent {
         success: bool,
         result: String,
         execution_time_ms: Option<u64>,
+        /// Permission policy (or tool default) that decided whether the call ran
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        policy: Option<String>,
     },
     /// Chat session error
     Error {
@@ -202,6 +205,7 @@ impl ChatLogger {
         success: bool,
         result: &str,
         execution_time_ms: Option<u64>,
+        policy: Option<&str>,
     ) {
         self.log_event(ChatLogEvent::ToolCall {
             tool_name: tool_name.to_string(),
@@ -209,6 +213,7 @@ impl ChatLogger {
             success,
             result: result.to_string(),
             execution_time_ms,
+            policy: policy.map(str::to_string),
         });
     }
 
@@ -0,0 +1,226 @@
+//! Undo support for mutating tool calls
+//!
+//! Before a mutating tool runs, the state it is about to change is captured
+//! (the character's current version, a module's stage, a file's contents).
+//! Once the tool succeeds, that before-state is turned into a
+//! [`RevertAction`] and written to the tool action journal for the chat
+//! session, so the assistant's last actions can be undone.
+
+use mimir_dm_core::models::chat::RevertAction;
+use mimir_dm_core::services::{CharacterService, ModuleService, ToolJournalService};
+use mimir_dm_core::DatabaseService;
+use serde_json::Value;
+use std::fs;
+use tracing::{debug, warn};
+
+/// Tools that create a character and report its ID in the result
+const CHARACTER_CREATION_TOOLS: [&str; 2] = ["create_character", "create_npc"];
+
+/// Tools that write files, identified by their `file_path` argument
+const FILE_TOOLS: [&str; 2] = ["write_file", "edit_file"];
+
+/// State captured before a mutating tool runs
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum BeforeState {
+    /// Character version current before the call
+    Character {
+        character_id: i32,
+        version_number: i32,
+    },
+    /// A character will be created; its ID comes from the tool result
+    NewCharacter,
+    /// A module will be created; its ID comes from the tool result
+    NewModule,
+    /// Module stage before the call
+    Module {
+        module_id: i32,
+        status: String,
+        started_at: Option<String>,
+        completed_at: Option<String>,
+    },
+    /// File contents before the call (None if the file did not exist)
+    File {
+        path: String,
+        previous_contents: Option<String>,
+    },
+}
+
+/// Capture the state a mutating tool is about to change.
+///
+/// Returns None for tools whose effects cannot be reverted.
+pub(super) fn capture_before_state(
+    db_service: &DatabaseService,
+    tool_name: &str,
+    arguments: &Value,
+) -> Option<BeforeState> {
+    if CHARACTER_CREATION_TOOLS.contains(&tool_name) {
+        return Some(BeforeState::NewCharacter);
+    }
+    if tool_name == "create_module" {
+        return Some(BeforeState::NewModule);
+    }
+
+    if FILE_TOOLS.contains(&tool_name) {
+        let path = arguments.get("file_path")?.as_str()?.to_string();
+        let previous_contents = fs::read_to_string(&path).ok();
+        return Some(BeforeState::File {
+            path,
+            previous_contents,
+        });
+    }
+
+    let mut conn = db_service.get_connection().ok()?;
+
+    if tool_name == "update_module_status" {
+        let module_id = arguments.get("module_id")?.as_i64()? as i32;
+        let module = ModuleService::new(&mut conn)
+            .get_module(module_id)
+            .ok()
+            .flatten()?;
+        return Some(BeforeState::Module {
+            module_id,
+            status: module.status,
+            started_at: module.started_at,
+            completed_at: module.completed_at,
+        });
+    }
+
+    // Every other character tool works on an existing character
+    let character_id = arguments.get("character_id")?.as_i64()? as i32;
+    let (character, _) = CharacterService::new(&mut conn)
+        .get_character(character_id)
+        .ok()?;
+    Some(BeforeState::Character {
+        character_id,
+        version_number: character.current_version,
+    })
+}
+
+/// Turn the captured before-state into a revert action once the tool succeeded
+pub(super) fn revert_action(before: BeforeState, tool_result: &str) -> Option<RevertAction> {
+    let created_id = |field: &str| -> Option<i32> {
+        let result: Value = serde_json::from_str(tool_result).ok()?;
+        result.get(field)?.as_i64().map(|id| id as i32)
+    };
+
+    match before {
+        BeforeState::Character {
+            character_id,
+            version_number,
+        } => Some(RevertAction::RestoreCharacterVersion {
+            character_id,
+            version_number,
+        }),
+        BeforeState::NewCharacter => Some(RevertAction::DeleteCharacter {
+            character_id: created_id("character_id")?,
+        }),
+        BeforeState::NewModule => Some(RevertAction::DeleteModule {
+            module_id: created_id("module_id")?,
+        }),
+        BeforeState::Module {
+            module_id,
+            status,
+            started_at,
+            completed_at,
+        } => Some(RevertAction::RestoreModuleStatus {
+            module_id,
+            status,
+            started_at,
+            completed_at,
+        }),
+        BeforeState::File {
+            path,
+            previous_contents,
+        } => Some(RevertAction::RestoreFile {
+            path,
+            previous_contents,
+        }),
+    }
+}
+
+/// Write a successful tool call to the session's action journal
+pub(super) fn record_action(
+    db_service: &DatabaseService,
+    session_id: &str,
+    tool_name: &str,
+    arguments: &Value,
+    summary: &str,
+    before: BeforeState,
+    tool_result: &str,
+) {
+    let Some(revert) = revert_action(before, tool_result) else {
+        warn!(
+            "Could not determine how to undo {}; not journaling it",
+            tool_name
+        );
+        return;
+    };
+
+    let recorded = db_service
+        .get_connection()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            ToolJournalService::new(&mut conn)
+                .record(session_id, tool_name, arguments, summary, &revert)
+                .map_err(|e| e.to_string())
+        });
+
+    match recorded {
+        Ok(action) => debug!(
+            "Journaled {} as action {} for session {}",
+            tool_name, action.id, session_id
+        ),
+        Err(e) => warn!("Failed to journal {} for undo: {}", tool_name, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revert_action_for_created_rows() {
+        let result = r#"{"success": true, "character_id": 7, "character_name": "Elara"}"#;
+        assert_eq!(
+            revert_action(BeforeState::NewCharacter, result),
+            Some(RevertAction::DeleteCharacter { character_id: 7 })
+        );
+
+        let result = r#"{"success": true, "module_id": 3}"#;
+        assert_eq!(
+            revert_action(BeforeState::NewModule, result),
+            Some(RevertAction::DeleteModule { module_id: 3 })
+        );
+
+        // Without an ID in the result there is nothing to delete
+        assert_eq!(revert_action(BeforeState::NewCharacter, "Created"), None);
+    }
+
+    #[test]
+    fn test_capture_file_before_state() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db_service =
+            DatabaseService::new(temp_dir.path().join("test.db").to_str().unwrap(), false).unwrap();
+
+        let path = temp_dir.path().join("notes.md");
+        let path_str = path.to_str().unwrap().to_string();
+        let arguments = serde_json::json!({ "file_path": path_str });
+
+        assert_eq!(
+            capture_before_state(&db_service, "write_file", &arguments),
+            Some(BeforeState::File {
+                path: path_str.clone(),
+                previous_contents: None,
+            })
+        );
+
+        fs::write(&path, "old notes").unwrap();
+        assert_eq!(
+            capture_before_state(&db_service, "edit_file", &arguments),
+            Some(BeforeState::File {
+                path: path_str,
+                previous_contents: Some("old notes".to_string()),
+            })
+        );
+    }
+}
@@ -8,7 +8,10 @@ use mimir_dm_core::services::{
     CampaignService, CampaignSummaryService, CharacterService, ModuleService, PlayerService,
 };
 use mimir_dm_core::DatabaseService;
-use mimir_dm_llm::{traits::ActionDescription, LlmProvider};
+use mimir_dm_llm::{
+    traits::{ActionDescription, ChangeDetail},
+    ChatStreamEvent, LlmProvider,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
@@ -18,7 +21,9 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 use crate::services::chat_logger::ChatTokenUsage;
+use crate::services::llm::action_journal;
 use crate::services::llm::LlmService;
+use crate::services::tool_policy::{ToolPermission, ToolPolicySettings};
 use crate::services::tools::ToolRegistry;
 
 // ============================================================================
@@ -474,6 +479,17 @@ pub struct IntermediateMessage {
     pub session_id: Option<String>,
 }
 
+/// Piece of an LLM reply streamed while it is being generated
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StreamDeltaMessage {
+    /// Text appended to the reply
+    pub content: String,
+    /// Name of a tool call the model has started, if any
+    pub tool_call: Option<String>,
+    pub iteration: usize,
+    pub session_id: Option<String>,
+}
+
 /// Tool result message
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ToolResultMessage {
@@ -524,6 +540,7 @@ impl<'a> ChatProcessor<'a> {
             &mut registry,
             self.llm.db_service.clone(),
             self.llm.todo_state_manager.clone(),
+            self.llm.lore_embedder.clone(),
             Some(campaign_dir),
         );
         registry
@@ -662,6 +679,7 @@ impl<'a> ChatProcessor<'a> {
                     max_tokens,
                     ollama_url,
                     tool_call_count,
+                    session_id,
                     &chat_logger,
                     &cancellation_token,
                 )
@@ -692,6 +710,7 @@ impl<'a> ChatProcessor<'a> {
                         tool_calls,
                         &mut provider_messages,
                         resolved_campaign_dir.as_deref(),
+                        campaign_id,
                         session_id,
                         tool_call_count,
                         &chat_logger,
@@ -894,6 +913,7 @@ impl<'a> ChatProcessor<'a> {
         max_tokens: Option<u32>,
         ollama_url: Option<&str>,
         iteration: usize,
+        session_id: &str,
         chat_logger: &Option<Arc<crate::services::chat_logger::ChatLogger>>,
         cancellation_token: &CancellationToken,
     ) -> Result<mimir_dm_llm::ChatResponse, String> {
@@ -949,9 +969,10 @@ impl<'a> ChatProcessor<'a> {
             tools.as_ref().map_or(0, |t| t.len())
         );
 
-        // Call the provider's chat method
+        // Stream the reply to the frontend as it is generated
+        let on_event = self.stream_delta_emitter(iteration, session_id);
         let response = provider
-            .chat(
+            .chat_stream(
                 provider_messages.to_vec(),
                 tools.clone(),
                 None,                             // n (number of completions)
@@ -960,6 +981,7 @@ impl<'a> ChatProcessor<'a> {
                 None,                      // stop sequences
                 None,                      // extra config
                 Some(cancellation_token.clone()), // cancellation token
+                on_event,
             )
             .await
             .map_err(|e| {
@@ -1113,6 +1135,41 @@ impl<'a> ChatProcessor<'a> {
         }
     }
 
+    /// Build the callback that forwards streamed reply text to the frontend
+    fn stream_delta_emitter(
+        &self,
+        iteration: usize,
+        session_id: &str,
+    ) -> impl Fn(ChatStreamEvent) + Send + Sync + 'static {
+        let app = self.llm.app_handle.clone();
+        let session_id = session_id.to_string();
+
+        move |event| {
+            let Some(ref app) = app else {
+                return;
+            };
+
+            let delta = match event {
+                ChatStreamEvent::ContentDelta(content) => StreamDeltaMessage {
+                    content,
+                    tool_call: None,
+                    iteration,
+                    session_id: Some(session_id.clone()),
+                },
+                ChatStreamEvent::ToolCallStarted { name, .. } => StreamDeltaMessage {
+                    content: String::new(),
+                    tool_call: Some(name),
+                    iteration,
+                    session_id: Some(session_id.clone()),
+                },
+            };
+
+            if let Err(e) = app.emit("llm-stream-delta", &delta) {
+                debug!("Failed to emit stream delta: {}", e);
+            }
+        }
+    }
+
     /// Emit intermediate message to frontend
     fn emit_intermediate_message(
         &self,
@@ -1149,11 +1206,13 @@ impl<'a> ChatProcessor<'a> {
         tool_calls: &[mimir_dm_llm::ToolCall],
         provider_messages: &mut Vec<mimir_dm_llm::Message>,
         campaign_directory_path: Option<&str>,
+        active_campaign_id: Option<i32>,
         session_id: &str,
         iteration: usize,
         chat_logger: &Option<Arc<crate::services::chat_logger::ChatLogger>>,
     ) -> Vec<ToolCallRecord> {
         let mut records = Vec::new();
+        let tool_policies = self.load_tool_policies();
         info!("=== Processing {} tool calls ===", tool_calls.len());
         for (idx, tool_call) in tool_calls.iter().enumerate() {
             let tool_name = &tool_call.function.name;
@@ -1208,8 +1267,54 @@ impl<'a> ChatProcessor<'a> {
             // Check if tool requires confirmation
             let (requires_confirmation, action_desc) =
                 self.check_tool_confirmation(tool_name, &tool_args, campaign_directory_path);
+            let action_summary = action_desc
+                .as_ref()
+                .map(|desc| desc.description.clone())
+                .unwrap_or_else(|| tool_name.clone());
+
+            // Let the configured permission policies decide how to proceed
+            let decision = tool_policies.evaluate(
+                tool_name,
+                active_campaign_id,
+                &tool_args,
+                requires_confirmation,
+            );
+            let decided_by = decision.decided_by();
+            info!("Tool {} permission: {}", tool_name, decided_by);
+
+            if decision.permission == ToolPermission::Deny {
+                let denial = format!("Action denied by tool policy: {}", tool_name);
+                if let Some(ref logger) = chat_logger {
+                    logger.log_tool_call(
+                        tool_name,
+                        &tool_args,
+                        false,
+                        &denial,
+                        None,
+                        Some(&decided_by),
+                    );
+                }
+                provider_messages.push(mimir_dm_llm::Message {
+                    role: "tool".to_string(),
+                    content: denial,
+                    tool_call_id: Some(tool_call.id.clone()),
+                });
+                continue;
+            }
+
+            if decision.permission == ToolPermission::Ask {
+                // Tools that normally run freely have no description of their own
+                let action_desc = match action_desc {
+                    None if decision.policy.is_some() => Some(ActionDescription {
+                        title: format!("Run {}", tool_name),
+                        description: format!("The assistant wants to run {}", tool_name),
+                        changes: ChangeDetail::Generic {
+                            items: vec![args_json.clone()],
+                        },
+                    }),
+                    action_desc => action_desc,
+                };
 
-            if requires_confirmation {
                 if let Some(action_desc) = action_desc {
                     info!(
                         "Tool {} requires confirmation, requesting from user",
@@ -1257,6 +1362,13 @@ impl<'a> ChatProcessor<'a> {
                 }
             }
 
+            // Capture what a mutating tool is about to change so it can be undone
+            let before_state = if requires_confirmation {
+                action_journal::capture_before_state(&self.llm.db_service, tool_name, &tool_args)
+            } else {
+                None
+            };
+
             // Execute the tool
             let tool_result = self
                 .execute_single_tool(
@@ -1264,6 +1376,7 @@ impl<'a> ChatProcessor<'a> {
                     tool_args.clone(),
                     campaign_directory_path,
                     chat_logger,
+                    &decided_by,
                 )
                 .await;
 
@@ -1282,6 +1395,19 @@ impl<'a> ChatProcessor<'a> {
             }
             debug_content!("Tool result content", tool_result, 200);
 
+            // Journal successful mutating calls for undo
+            if let (Some(before), false) = (before_state, is_error) {
+                action_journal::record_action(
+                    &self.llm.db_service,
+                    session_id,
+                    tool_name,
+                    &tool_args,
+                    &action_summary,
+                    before,
+                    &tool_result,
+                );
+            }
+
             // Record this tool call for tracking/testing
             records.push(ToolCallRecord {
                 name: tool_name.clone(),
@@ -1317,6 +1443,14 @@ impl<'a> ChatProcessor<'a> {
         records
     }
 
+    /// Load the persisted tool permission policies
+    ///
+    /// If the policies file is unreadable every tool call asks for
+    /// confirmation rather than falling back to the tools' defaults.
+    fn load_tool_policies(&self) -> ToolPolicySettings {
+        ToolPolicySettings::load_or_ask(&self.llm.app_paths.config_dir)
+    }
+
     /// Check if tool requires confirmation
     fn check_tool_confirmation(
         &self,
@@ -1347,6 +1481,7 @@ impl<'a> ChatProcessor<'a> {
         tool_args: serde_json::Value,
         campaign_directory_path: Option<&str>,
         chat_logger: &Option<Arc<crate::services::chat_logger::ChatLogger>>,
+        decided_by: &str,
     ) -> String {
         info!(
             "Executing tool: {} with {} bytes of arguments",
@@ -1409,6 +1544,7 @@ impl<'a> ChatProcessor<'a> {
                 success,
                 &tool_result,
                 Some(execution_time_ms),
+                Some(decided_by),
             );
         }
 
@@ -5,6 +5,7 @@
 
 use crate::services::llm::chat_processor::ChatProcessor;
 use crate::services::provider_settings::ProviderSettings;
+use crate::services::tool_policy::ToolPolicySettings;
 use crate::state::AppState;
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -298,6 +299,32 @@ pub async fn save_provider_settings(
     Ok(())
 }
 
+/// Tauri command to get the tool permission policies
+#[tauri::command]
+pub async fn get_tool_policies(state: State<'_, AppState>) -> Result<ToolPolicySettings, String> {
+    ToolPolicySettings::load(&state.paths.config_dir).map_err(|e| {
+        error!("Failed to load tool policies: {}", e);
+        format!("Failed to load tool policies: {}", e)
+    })
+}
+
+/// Tauri command to save the tool permission policies
+///
+/// Policies are read before every batch of tool calls, so changes apply to
+/// the next tool call without reloading the LLM service.
+#[tauri::command]
+pub async fn save_tool_policies(
+    state: State<'_, AppState>,
+    settings: ToolPolicySettings,
+) -> Result<(), String> {
+    info!("Saving {} tool policies", settings.policies.len());
+
+    settings.save(&state.paths.config_dir).map_err(|e| {
+        error!("Failed to save tool policies: {}", e);
+        format!("Failed to save tool policies: {}", e)
+    })
+}
+
 /// Tauri command to reload LLM service with new provider settings
 #[tauri::command]
 pub async fn reload_llm_service(
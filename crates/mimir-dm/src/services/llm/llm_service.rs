@@ -14,8 +14,8 @@ use mimir_dm_llm::{
     providers::groq::GroqProvider,
     providers::ollama::OllamaProvider,
     traits::ActionDescription,
-    ChatResponse, CompletionResponse, EmbeddingResponse, LlmProvider, Message, ModelPullProgress,
-    RateLimitState, TodoStateManager, Tool,
+    ChatResponse, ChatStreamEvent, CompletionResponse, EmbeddingResponse, LlmProvider, Message,
+    ModelPullProgress, RateLimitState, TodoStateManager, Tool,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,6 +30,7 @@ use uuid::Uuid;
 
 use crate::app_init::AppPaths;
 use crate::services::chat_logger::ChatLogger;
+use crate::services::lore_service::LoreEmbedder;
 use crate::services::tools::{register_all_tools, ToolRegistry};
 use mimir_dm_core::DatabaseService;
 
@@ -98,6 +99,53 @@ impl LlmProvider for Provider {
         }
     }
 
+    async fn chat_stream<F>(
+        &self,
+        messages: Vec<Message>,
+        tools: Option<Vec<Tool>>,
+        n: Option<u32>,
+        temperature: Option<f32>,
+        max_tokens: Option<u32>,
+        stop: Option<Vec<String>>,
+        extra_config: Option<HashMap<String, String>>,
+        cancellation_token: Option<CancellationToken>,
+        on_event: F,
+    ) -> Result<ChatResponse, mimir_dm_llm::LlmError>
+    where
+        F: Fn(ChatStreamEvent) + Send + Sync + 'static,
+    {
+        match self {
+            Provider::Ollama(p) => {
+                p.chat_stream(
+                    messages,
+                    tools,
+                    n,
+                    temperature,
+                    max_tokens,
+                    stop,
+                    extra_config,
+                    cancellation_token,
+                    on_event,
+                )
+                .await
+            }
+            Provider::Groq(p) => {
+                p.chat_stream(
+                    messages,
+                    tools,
+                    n,
+                    temperature,
+                    max_tokens,
+                    stop,
+                    extra_config,
+                    cancellation_token,
+                    on_event,
+                )
+                .await
+            }
+        }
+    }
+
     async fn complete(
         &self,
         prompt: String,
@@ -183,6 +231,7 @@ impl LlmProvider for Provider {
 pub const DEFAULT_OLLAMA_MODEL: &str = "gpt-oss:20b";
 pub const DEFAULT_GROQ_MODEL: &str = "qwen/qwen3-32b";
 pub const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+pub const DEFAULT_OLLAMA_EMBEDDING_MODEL: &str = "nomic-embed-text";
 
 /// Event emitted during model download progress
 #[derive(Clone, Serialize)]
@@ -213,6 +262,8 @@ pub struct LlmService {
     provider_type: ProviderType,
     pub(super) tool_registry: Arc<ToolRegistry>,
     pub(super) db_service: Arc<DatabaseService>,
+    /// Embedding model for lore search (None when no Ollama is configured)
+    pub(super) lore_embedder: Option<LoreEmbedder>,
     /// Channel senders for pending confirmations (shared globally)
     confirmation_receivers: ConfirmationReceivers,
     /// App handle for emitting events
@@ -222,7 +273,7 @@ pub struct LlmService {
     /// Chat loggers by session ID
     chat_loggers: Arc<Mutex<HashMap<String, Arc<ChatLogger>>>>,
     /// Application paths for file operations
-    app_paths: Arc<AppPaths>,
+    pub(super) app_paths: Arc<AppPaths>,
     /// Timeout for tool confirmation prompts
     tool_confirmation_timeout: Duration,
 }
@@ -246,6 +297,7 @@ impl LlmService {
         // Create provider based on settings
         let (provider, model_name, provider_type) = Self::create_provider_from_settings(&settings)?;
         let provider = Arc::new(provider);
+        let lore_embedder = Self::create_lore_embedder(&settings)?;
 
         // Get tool confirmation timeout from settings
         let tool_confirmation_timeout =
@@ -283,6 +335,7 @@ impl LlmService {
             &mut tool_registry,
             db_service.clone(),
             todo_state_manager.clone(),
+            lore_embedder.clone(),
         );
         info!("Tool registry initialized with all standard tools");
 
@@ -292,6 +345,7 @@ impl LlmService {
             provider_type,
             tool_registry: Arc::new(tool_registry),
             db_service,
+            lore_embedder,
             confirmation_receivers,
             app_handle,
             todo_state_manager,
@@ -377,6 +431,32 @@ impl LlmService {
         }
     }
 
+    /// Create the lore search embedder from settings
+    ///
+    /// Embeddings always come from Ollama since Groq has no embedding
+    /// endpoint. Returns None when no Ollama configuration is saved.
+    fn create_lore_embedder(settings: &ProviderSettings) -> Result<Option<LoreEmbedder>> {
+        let Some(ollama_config) = settings.ollama_config.as_ref() else {
+            info!("No Ollama configuration - lore search is unavailable");
+            return Ok(None);
+        };
+
+        let model_name = ollama_config
+            .embedding_model
+            .clone()
+            .unwrap_or_else(|| DEFAULT_OLLAMA_EMBEDDING_MODEL.to_string());
+
+        let config = Self::create_ollama_config(&ollama_config.base_url, &model_name);
+        let provider = OllamaProvider::new(config)
+            .context("Failed to create Ollama embedding provider")?;
+
+        info!("Created lore embedder with model: {}", model_name);
+        Ok(Some(LoreEmbedder::new(
+            Arc::new(Provider::Ollama(Arc::new(provider))),
+            model_name,
+        )))
+    }
+
     /// Create Groq model configuration
     fn create_groq_config(api_key: &str, model: &str) -> ModelConfig {
         let mut config_map = HashMap::new();
@@ -523,6 +603,11 @@ impl LlmService {
         &self.model_name
     }
 
+    /// Get the embedder used for lore search, if one is configured
+    pub fn lore_embedder(&self) -> Option<LoreEmbedder> {
+        self.lore_embedder.clone()
+    }
+
     /// Get todos for a session from the state manager
     pub fn get_session_todos(&self, session_id: &str) -> Vec<mimir_dm_llm::TodoItem> {
         self.todo_state_manager.get_todos(session_id)
@@ -5,8 +5,10 @@
 //!
 //! - `llm_service`: Core service for model management and initialization
 //! - `chat_processor`: Chat message processing and tool execution
+//! - `action_journal`: Undo journal for mutating tool calls
 //! - `commands`: Tauri command handlers for frontend integration
 
+mod action_journal;
 pub mod chat_processor;
 pub mod commands;
 mod llm_service;
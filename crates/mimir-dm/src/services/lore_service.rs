@@ -0,0 +1,475 @@
+//! Lore indexing for semantic search
+//!
+//! Turns uploaded books and campaign documents into embedded chunks stored
+//! in the lore index, and answers similarity queries against it. Embeddings
+//! come from the configured Ollama embedding model; the vectors are stored
+//! and searched by `mimir_dm_core::services::LoreIndexService`.
+
+use crate::commands::content::books::book_content::find_book_content_file;
+use crate::services::llm::Provider;
+use anyhow::{anyhow, Context, Result};
+use diesel::prelude::*;
+use mimir_dm_core::dal::campaign::documents::DocumentRepository;
+use mimir_dm_core::models::campaign::{
+    EmbeddedChunk, LoreSearchFilter, LoreSearchResult, LoreSourceType, NewLoreSource,
+};
+use mimir_dm_core::models::catalog::UploadedBook;
+use mimir_dm_core::schema::uploaded_books;
+use mimir_dm_core::services::LoreIndexService;
+use mimir_dm_core::text::strip_tags;
+use mimir_dm_core::DatabaseService;
+use mimir_dm_llm::{chunk_markdown, embed_texts, ChunkOptions, TextChunk};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Embeds text with the configured embedding model
+#[derive(Clone)]
+pub struct LoreEmbedder {
+    provider: Arc<Provider>,
+    model: String,
+}
+
+impl LoreEmbedder {
+    pub fn new(provider: Arc<Provider>, model: String) -> Self {
+        Self { provider, model }
+    }
+
+    /// Name of the embedding model
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Embed a search query
+    pub async fn embed_query(&self, query: &str) -> Result<Vec<f32>> {
+        let mut embeddings = embed_texts(self.provider.as_ref(), &[query.to_string()])
+            .await
+            .context("Failed to embed query")?;
+        embeddings
+            .pop()
+            .ok_or_else(|| anyhow!("Embedding model returned no vector"))
+    }
+
+    /// Embed chunks, pairing each with its vector
+    async fn embed_chunks(&self, chunks: Vec<TextChunk>) -> Result<Vec<EmbeddedChunk>> {
+        let texts: Vec<String> = chunks.iter().map(|c| c.embedding_text()).collect();
+        let embeddings = embed_texts(self.provider.as_ref(), &texts)
+            .await
+            .context("Failed to embed chunks")?;
+
+        Ok(chunks
+            .into_iter()
+            .zip(embeddings)
+            .map(|(chunk, embedding)| EmbeddedChunk {
+                heading: chunk.heading,
+                content: chunk.text,
+                embedding,
+            })
+            .collect())
+    }
+}
+
+/// Outcome of an indexing run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoreIndexReport {
+    /// Sources that were embedded and stored
+    pub indexed: Vec<String>,
+    /// Sources already up to date
+    pub skipped: usize,
+    /// Sources removed because the book or document no longer exists
+    pub removed: usize,
+    /// Sources that failed, with the reason
+    pub failed: Vec<String>,
+}
+
+/// Current state of the lore index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoreIndexStatus {
+    /// Model configured for new embeddings, if embeddings are available
+    pub configured_model: Option<String>,
+    /// Model the stored vectors were built with
+    pub indexed_model: Option<String>,
+    pub dimensions: Option<i32>,
+    pub book_count: usize,
+    pub document_count: usize,
+    pub chunk_count: i64,
+}
+
+/// Index every uploaded book and, if given, one campaign's documents.
+///
+/// Sources whose text hasn't changed since the last run are skipped.
+pub async fn index_lore(
+    db: &DatabaseService,
+    embedder: &LoreEmbedder,
+    campaign_id: Option<i32>,
+) -> Result<LoreIndexReport> {
+    let mut report = LoreIndexReport::default();
+
+    let books: Vec<UploadedBook> = {
+        let mut conn = db.get_connection()?;
+        uploaded_books::table.load(&mut conn)?
+    };
+
+    for book in &books {
+        let Some(text) = read_book_text(Path::new(&book.location))? else {
+            warn!("No book content found for {}", book.id);
+            continue;
+        };
+        let source = NewLoreSource::new(
+            LoreSourceType::Book,
+            &book.id,
+            None,
+            &book.name,
+            LoreIndexService::content_hash(&text),
+        );
+        index_source(db, embedder, source, &text, &mut report).await;
+    }
+
+    // Books removed from the library drop out of the index
+    let book_ids: HashSet<&str> = books.iter().map(|b| b.id.as_str()).collect();
+    report.removed += remove_missing(db, LoreSourceType::Book, None, |key| book_ids.contains(key))?;
+
+    if let Some(campaign_id) = campaign_id {
+        let documents = {
+            let mut conn = db.get_connection()?;
+            DocumentRepository::find_by_campaign(&mut conn, campaign_id)?
+        };
+
+        for document in &documents {
+            let text = match std::fs::read_to_string(&document.file_path) {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!("Skipping document {}: {}", document.file_path, e);
+                    continue;
+                }
+            };
+            let source = NewLoreSource::new(
+                LoreSourceType::Document,
+                document.id.to_string(),
+                Some(campaign_id),
+                &document.title,
+                LoreIndexService::content_hash(&text),
+            );
+            index_source(db, embedder, source, &text, &mut report).await;
+        }
+
+        let document_ids: HashSet<String> = documents.iter().map(|d| d.id.to_string()).collect();
+        report.removed += remove_missing(db, LoreSourceType::Document, Some(campaign_id), |key| {
+            document_ids.contains(key)
+        })?;
+    }
+
+    info!(
+        "Lore index updated: {} indexed, {} unchanged, {} removed, {} failed",
+        report.indexed.len(),
+        report.skipped,
+        report.removed,
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// Find the chunks most relevant to a query.
+pub async fn search_lore(
+    db: &DatabaseService,
+    embedder: &LoreEmbedder,
+    query: &str,
+    limit: i64,
+    filter: &LoreSearchFilter,
+) -> Result<Vec<LoreSearchResult>> {
+    let embedding = embedder.embed_query(query).await?;
+    let mut conn = db.get_connection()?;
+    let mut service = LoreIndexService::new(&mut conn);
+
+    if let Some(info) = service.index_info()? {
+        if info.embedding_model != embedder.model() {
+            return Err(anyhow!(
+                "Lore index was built with {} but {} is configured; rebuild the index",
+                info.embedding_model,
+                embedder.model()
+            ));
+        }
+    }
+
+    Ok(service.search(&embedding, limit, filter)?)
+}
+
+/// Describe the lore index.
+pub fn lore_index_status(
+    db: &DatabaseService,
+    embedder: Option<&LoreEmbedder>,
+) -> Result<LoreIndexStatus> {
+    let mut conn = db.get_connection()?;
+    let mut service = LoreIndexService::new(&mut conn);
+    let info = service.index_info()?;
+    let sources = service.list_sources(None)?;
+
+    Ok(LoreIndexStatus {
+        configured_model: embedder.map(|e| e.model().to_string()),
+        indexed_model: info.as_ref().map(|i| i.embedding_model.clone()),
+        dimensions: info.map(|i| i.dimensions),
+        book_count: sources
+            .iter()
+            .filter(|s| s.source_type == LoreSourceType::Book.as_str())
+            .count(),
+        document_count: sources
+            .iter()
+            .filter(|s| s.source_type == LoreSourceType::Document.as_str())
+            .count(),
+        chunk_count: sources.iter().map(|s| s.chunk_count as i64).sum(),
+    })
+}
+
+/// Embed and store one source unless it is already current.
+async fn index_source(
+    db: &DatabaseService,
+    embedder: &LoreEmbedder,
+    source: NewLoreSource,
+    text: &str,
+    report: &mut LoreIndexReport,
+) {
+    let label = format!("{} {}", source.source_type, source.title);
+    let source_type = LoreSourceType::parse(&source.source_type).unwrap_or(LoreSourceType::Book);
+
+    let current = db.get_connection().and_then(|mut conn| {
+        LoreIndexService::new(&mut conn)
+            .is_current(source_type, &source.source_key, &source.content_hash)
+            .map_err(Into::into)
+    });
+    match current {
+        Ok(true) => {
+            report.skipped += 1;
+            return;
+        }
+        Ok(false) => {}
+        Err(e) => {
+            report.failed.push(format!("{}: {}", label, e));
+            return;
+        }
+    }
+
+    let chunks = chunk_markdown(text, &ChunkOptions::default());
+    let result = async {
+        let embedded = embedder.embed_chunks(chunks).await?;
+        let mut conn = db.get_connection()?;
+        LoreIndexService::new(&mut conn).store_source(source, embedded, embedder.model())?;
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+
+    match result {
+        Ok(()) => {
+            info!("Indexed {}", label);
+            report.indexed.push(label);
+        }
+        Err(e) => {
+            warn!("Failed to index {}: {}", label, e);
+            report.failed.push(format!("{}: {}", label, e));
+        }
+    }
+}
+
+/// Remove indexed sources of one type whose key is no longer present.
+fn remove_missing(
+    db: &DatabaseService,
+    source_type: LoreSourceType,
+    campaign_id: Option<i32>,
+    exists: impl Fn(&str) -> bool,
+) -> Result<usize> {
+    let mut conn = db.get_connection()?;
+    let mut service = LoreIndexService::new(&mut conn);
+    let stale: Vec<String> = service
+        .list_sources(campaign_id)?
+        .into_iter()
+        .filter(|s| s.source_type == source_type.as_str() && !exists(&s.source_key))
+        .map(|s| s.source_key)
+        .collect();
+
+    for key in &stale {
+        service.remove_source(source_type, key)?;
+    }
+    Ok(stale.len())
+}
+
+/// Read a book's content file and render it as markdown.
+fn read_book_text(book_dir: &Path) -> Result<Option<String>> {
+    let Some(path) = find_book_content_file(book_dir)
+        .map_err(|e| anyhow!("Failed to locate book content: {}", e))?
+    else {
+        return Ok(None);
+    };
+
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+    let json: Value =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {:?}", path))?;
+
+    Ok(Some(book_to_markdown(&json)))
+}
+
+/// Render 5etools book data as markdown.
+///
+/// Named sections and entries become headings, lists become bullet points
+/// and tables become pipe-separated rows. Images and other non-text entries
+/// are dropped, and `{@tag ...}` markup is reduced to its display text.
+pub fn book_to_markdown(book: &Value) -> String {
+    let mut out = String::new();
+    match book.get("data").and_then(|d| d.as_array()) {
+        Some(sections) => {
+            for section in sections {
+                write_entry(&mut out, section, 1);
+            }
+        }
+        None => write_entry(&mut out, book, 1),
+    }
+    out
+}
+
+fn write_entry(out: &mut String, entry: &Value, depth: usize) {
+    match entry {
+        Value::String(text) => push_paragraph(out, &strip_tags(text)),
+        Value::Array(entries) => {
+            for entry in entries {
+                write_entry(out, entry, depth);
+            }
+        }
+        Value::Object(obj) => {
+            let entry_type = obj
+                .get("type")
+                .and_then(|t| t.as_str())
+                .unwrap_or("entries");
+            match entry_type {
+                "image" | "gallery" | "hr" | "statblock" | "flowchart" => {}
+                "list" => {
+                    if let Some(items) = obj.get("items").and_then(|i| i.as_array()) {
+                        let lines: Vec<String> = items
+                            .iter()
+                            .map(|item| format!("- {}", inline_text(item)))
+                            .collect();
+                        push_paragraph(out, &lines.join("\n"));
+                    }
+                }
+                "table" => {
+                    let mut lines = Vec::new();
+                    if let Some(caption) = obj.get("caption").and_then(|c| c.as_str()) {
+                        lines.push(strip_tags(caption));
+                    }
+                    if let Some(labels) = obj.get("colLabels").and_then(|l| l.as_array()) {
+                        lines.push(
+                            labels
+                                .iter()
+                                .map(inline_text)
+                                .collect::<Vec<_>>()
+                                .join(" | "),
+                        );
+                    }
+                    if let Some(rows) = obj.get("rows").and_then(|r| r.as_array()) {
+                        for row in rows {
+                            let cells = row
+                                .as_array()
+                                .map(|cells| cells.iter().map(inline_text).collect::<Vec<_>>())
+                                .unwrap_or_else(|| vec![inline_text(row)]);
+                            lines.push(cells.join(" | "));
+                        }
+                    }
+                    push_paragraph(out, &lines.join("\n"));
+                }
+                _ => {
+                    let mut child_depth = depth;
+                    if let Some(name) = obj.get("name").and_then(|n| n.as_str()) {
+                        let level = depth.min(6);
+                        push_paragraph(out, &format!("{} {}", "#".repeat(level), strip_tags(name)));
+                        child_depth = depth + 1;
+                    }
+                    for key in ["entries", "entry", "items"] {
+                        if let Some(children) = obj.get(key) {
+                            write_entry(out, children, child_depth);
+                        }
+                    }
+                    if let Some(by) = obj.get("by").and_then(|b| b.as_str()) {
+                        push_paragraph(out, &format!("— {}", strip_tags(by)));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Flatten an entry into a single line of text.
+fn inline_text(entry: &Value) -> String {
+    match entry {
+        Value::String(text) => strip_tags(text),
+        Value::Number(n) => n.to_string(),
+        Value::Array(items) => items.iter().map(inline_text).collect::<Vec<_>>().join(" "),
+        Value::Object(obj) => {
+            let mut parts = Vec::new();
+            if let Some(name) = obj.get("name").and_then(|n| n.as_str()) {
+                parts.push(format!("{}.", strip_tags(name)));
+            }
+            for key in ["entry", "entries", "items", "roll"] {
+                if let Some(child) = obj.get(key) {
+                    parts.push(inline_text(child));
+                }
+            }
+            parts.retain(|p| !p.is_empty());
+            parts.join(" ")
+        }
+        _ => String::new(),
+    }
+}
+
+fn push_paragraph(out: &mut String, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_book_to_markdown_renders_sections() {
+        let book = json!({
+            "data": [{
+                "type": "section",
+                "name": "Chapter 1: Goblin Arrows",
+                "entries": [
+                    "The adventurers are escorting a wagon to {@area Phandalin|123|x}.",
+                    {
+                        "type": "entries",
+                        "name": "Goblin Ambush",
+                        "entries": ["Four {@creature goblin|mm|goblins} attack."]
+                    },
+                    { "type": "image", "href": { "path": "map.png" } },
+                    { "type": "list", "items": ["Sildar", { "name": "Gundren", "entry": "Missing" }] },
+                    {
+                        "type": "table",
+                        "caption": "Treasure",
+                        "colLabels": ["d6", "Item"],
+                        "rows": [["1", "{@item Potion of Healing|dmg}"]]
+                    }
+                ]
+            }]
+        });
+
+        let markdown = book_to_markdown(&book);
+
+        assert!(markdown.starts_with("# Chapter 1: Goblin Arrows"));
+        assert!(markdown.contains("escorting a wagon to Phandalin."));
+        assert!(markdown.contains("## Goblin Ambush\n\nFour goblins attack."));
+        assert!(markdown.contains("- Sildar\n- Gundren. Missing"));
+        assert!(markdown.contains("Treasure\nd6 | Item\n1 | Potion of Healing"));
+        assert!(!markdown.contains("map.png"));
+    }
+}
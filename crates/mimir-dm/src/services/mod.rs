@@ -3,5 +3,7 @@
 pub mod chat_logger;
 pub mod context_service;
 pub mod llm;
+pub mod lore_service;
 pub mod provider_settings;
+pub mod tool_policy;
 pub mod tools;
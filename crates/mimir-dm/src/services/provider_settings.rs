@@ -23,6 +23,9 @@ pub struct OllamaConfig {
     /// Selected model name (e.g., "gpt-oss:20b", "qwen3:8b", "llama3.2")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
+    /// Model used to embed books and documents for lore search (e.g., "nomic-embed-text")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
 }
 
 impl Default for OllamaConfig {
@@ -30,6 +33,7 @@ impl Default for OllamaConfig {
         Self {
             base_url: "http://localhost:11434".to_string(),
             model: None,
+            embedding_model: None,
         }
     }
 }
@@ -245,6 +249,7 @@ mod tests {
             ollama_config: Some(OllamaConfig {
                 base_url: "http://localhost:11434".to_string(),
                 model: Some("gpt-oss:20b".to_string()),
+                embedding_model: None,
             }),
             groq_config: None,
             tool_confirmation_timeout_secs: 60,
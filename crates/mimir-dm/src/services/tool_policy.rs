@@ -0,0 +1,415 @@
+//! Tool permission policies and their persistence
+//!
+//! Policies decide, before a tool runs, whether the assistant may run it
+//! without asking, must ask the user first, or may not run it at all. A policy
+//! can be scoped to a campaign and to a path prefix of one of the tool's
+//! arguments (for example allowing `write_file` only below a campaign's notes
+//! directory). Policies are saved next to the provider settings.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Component, Path};
+use tracing::{debug, info, warn};
+
+/// Tool name that makes a policy apply to every tool
+pub const ANY_TOOL: &str = "*";
+
+const POLICIES_FILE: &str = "tool_policies.json";
+
+/// What to do with a tool call
+///
+/// Variants are ordered from least to most restrictive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolPermission {
+    /// Run without asking
+    Allow,
+    /// Ask the user to confirm the call
+    Ask,
+    /// Refuse the call
+    Deny,
+}
+
+impl ToolPermission {
+    fn as_str(self) -> &'static str {
+        match self {
+            ToolPermission::Allow => "allow",
+            ToolPermission::Ask => "ask",
+            ToolPermission::Deny => "deny",
+        }
+    }
+}
+
+/// Restricts a policy to calls whose argument lies below a path prefix
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArgumentScope {
+    /// Argument to inspect (e.g. "file_path")
+    pub argument: String,
+    /// Path the argument must start with, compared component by component
+    pub path_prefix: String,
+}
+
+impl ArgumentScope {
+    fn matches(&self, arguments: &Value) -> bool {
+        let Some(value) = arguments.get(&self.argument).and_then(|v| v.as_str()) else {
+            return false;
+        };
+        let path = Path::new(value);
+
+        // A path that climbs out with ".." cannot be trusted to stay below the prefix
+        if path.components().any(|c| c == Component::ParentDir) {
+            return false;
+        }
+
+        path.starts_with(&self.path_prefix)
+    }
+}
+
+/// A persisted permission rule for a tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ToolPolicy {
+    /// Tool the policy applies to, or "*" for every tool
+    pub tool_name: String,
+    pub permission: ToolPermission,
+    /// Only apply within this campaign
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign_id: Option<i32>,
+    /// Only apply when an argument lies below a path prefix
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument_scope: Option<ArgumentScope>,
+}
+
+impl ToolPolicy {
+    /// Whether the policy applies to a call
+    pub fn matches(&self, tool_name: &str, campaign_id: Option<i32>, arguments: &Value) -> bool {
+        if self.tool_name != ANY_TOOL && self.tool_name != tool_name {
+            return false;
+        }
+        if self.campaign_id.is_some() && self.campaign_id != campaign_id {
+            return false;
+        }
+        match &self.argument_scope {
+            Some(scope) => scope.matches(arguments),
+            None => true,
+        }
+    }
+
+    /// Number of scopes the policy narrows on; more specific policies win
+    fn specificity(&self) -> u8 {
+        u8::from(self.tool_name != ANY_TOOL)
+            + u8::from(self.campaign_id.is_some())
+            + u8::from(self.argument_scope.is_some())
+    }
+
+    /// Short description used in logs, e.g. "allow write_file in campaign 3 where file_path under /notes"
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} {}", self.permission.as_str(), self.tool_name);
+        if let Some(campaign_id) = self.campaign_id {
+            description.push_str(&format!(" in campaign {}", campaign_id));
+        }
+        if let Some(scope) = &self.argument_scope {
+            description.push_str(&format!(
+                " where {} under {}",
+                scope.argument, scope.path_prefix
+            ));
+        }
+        description
+    }
+}
+
+/// Outcome of evaluating the policies for a tool call
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyDecision {
+    pub permission: ToolPermission,
+    /// The policy that decided, or None when the tool's default applied
+    pub policy: Option<ToolPolicy>,
+}
+
+impl PolicyDecision {
+    /// Description of what decided the call, for logging
+    pub fn decided_by(&self) -> String {
+        match &self.policy {
+            Some(policy) => format!("policy: {}", policy.describe()),
+            None => format!("default: {}", self.permission.as_str()),
+        }
+    }
+}
+
+/// All configured tool policies
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ToolPolicySettings {
+    #[serde(default)]
+    pub policies: Vec<ToolPolicy>,
+}
+
+impl ToolPolicySettings {
+    /// Validate the policies
+    pub fn validate(&self) -> Result<()> {
+        for policy in &self.policies {
+            if policy.tool_name.trim().is_empty() {
+                anyhow::bail!("Tool policy must name a tool or use \"{}\"", ANY_TOOL);
+            }
+            if let Some(scope) = &policy.argument_scope {
+                if scope.argument.trim().is_empty() || scope.path_prefix.trim().is_empty() {
+                    anyhow::bail!(
+                        "Tool policy for {} needs both an argument and a path prefix",
+                        policy.tool_name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Decide what to do with a tool call.
+    ///
+    /// The most specific matching policy wins; among equally specific
+    /// policies the most restrictive one wins. Without a matching policy the
+    /// tool's own default applies: ask if it requires confirmation, otherwise
+    /// allow.
+    pub fn evaluate(
+        &self,
+        tool_name: &str,
+        campaign_id: Option<i32>,
+        arguments: &Value,
+        requires_confirmation: bool,
+    ) -> PolicyDecision {
+        let policy = self
+            .policies
+            .iter()
+            .filter(|policy| policy.matches(tool_name, campaign_id, arguments))
+            .max_by_key(|policy| (policy.specificity(), policy.permission));
+
+        match policy {
+            Some(policy) => PolicyDecision {
+                permission: policy.permission,
+                policy: Some(policy.clone()),
+            },
+            None => PolicyDecision {
+                permission: if requires_confirmation {
+                    ToolPermission::Ask
+                } else {
+                    ToolPermission::Allow
+                },
+                policy: None,
+            },
+        }
+    }
+
+    /// Load tool policies from file
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let config_path = config_dir.join(POLICIES_FILE);
+
+        if !config_path.exists() {
+            debug!("Tool policies file not found, using defaults");
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(&config_path)
+            .with_context(|| format!("Failed to read tool policies from {:?}", config_path))?;
+
+        let settings: ToolPolicySettings = serde_json::from_str(&contents)
+            .with_context(|| "Failed to parse tool policies JSON")?;
+
+        settings.validate()?;
+
+        debug!("Loaded {} tool policies", settings.policies.len());
+        Ok(settings)
+    }
+
+    /// Load tool policies, failing closed if the file cannot be used
+    ///
+    /// A missing file means no policies. A file that cannot be read or
+    /// parsed may have held deny rules, so every call is made to ask the
+    /// user instead of silently falling back to the tool defaults.
+    pub fn load_or_ask(config_dir: &Path) -> Self {
+        Self::load(config_dir).unwrap_or_else(|e| {
+            warn!(
+                "Failed to load tool policies, asking for every tool call: {:#}",
+                e
+            );
+            Self {
+                policies: vec![ToolPolicy {
+                    tool_name: ANY_TOOL.to_string(),
+                    permission: ToolPermission::Ask,
+                    campaign_id: None,
+                    argument_scope: None,
+                }],
+            }
+        })
+    }
+
+    /// Save tool policies to file
+    pub fn save(&self, config_dir: &Path) -> Result<()> {
+        self.validate()?;
+
+        let config_path = config_dir.join(POLICIES_FILE);
+
+        let json =
+            serde_json::to_string_pretty(self).context("Failed to serialize tool policies")?;
+
+        fs::write(&config_path, json)
+            .with_context(|| format!("Failed to write tool policies to {:?}", config_path))?;
+
+        info!("Saved {} tool policies", self.policies.len());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    fn policy(tool_name: &str, permission: ToolPermission) -> ToolPolicy {
+        ToolPolicy {
+            tool_name: tool_name.to_string(),
+            permission,
+            campaign_id: None,
+            argument_scope: None,
+        }
+    }
+
+    #[test]
+    fn test_default_follows_tool_confirmation() {
+        let settings = ToolPolicySettings::default();
+        let args = json!({});
+
+        let decision = settings.evaluate("write_file", None, &args, true);
+        assert_eq!(decision.permission, ToolPermission::Ask);
+        assert!(decision.policy.is_none());
+        assert_eq!(decision.decided_by(), "default: ask");
+
+        let decision = settings.evaluate("read_file", None, &args, false);
+        assert_eq!(decision.permission, ToolPermission::Allow);
+    }
+
+    #[test]
+    fn test_most_specific_policy_wins() {
+        let settings = ToolPolicySettings {
+            policies: vec![
+                policy(ANY_TOOL, ToolPermission::Deny),
+                policy("write_file", ToolPermission::Ask),
+                ToolPolicy {
+                    campaign_id: Some(3),
+                    argument_scope: Some(ArgumentScope {
+                        argument: "file_path".to_string(),
+                        path_prefix: "/campaigns/three/notes".to_string(),
+                    }),
+                    ..policy("write_file", ToolPermission::Allow)
+                },
+            ],
+        };
+
+        let in_notes = json!({ "file_path": "/campaigns/three/notes/session.md" });
+        let decision = settings.evaluate("write_file", Some(3), &in_notes, true);
+        assert_eq!(decision.permission, ToolPermission::Allow);
+        assert_eq!(
+            decision.decided_by(),
+            "policy: allow write_file in campaign 3 where file_path under /campaigns/three/notes"
+        );
+
+        // Same path in another campaign falls back to the tool-wide policy
+        let decision = settings.evaluate("write_file", Some(4), &in_notes, true);
+        assert_eq!(decision.permission, ToolPermission::Ask);
+
+        // Other tools hit the wildcard
+        let decision = settings.evaluate("delete_character", Some(3), &json!({}), true);
+        assert_eq!(decision.permission, ToolPermission::Deny);
+    }
+
+    #[test]
+    fn test_restrictive_policy_wins_ties() {
+        let settings = ToolPolicySettings {
+            policies: vec![
+                policy("create_module", ToolPermission::Allow),
+                policy("create_module", ToolPermission::Deny),
+            ],
+        };
+
+        let decision = settings.evaluate("create_module", None, &json!({}), true);
+        assert_eq!(decision.permission, ToolPermission::Deny);
+    }
+
+    #[test]
+    fn test_argument_scope_matching() {
+        let scope = ArgumentScope {
+            argument: "file_path".to_string(),
+            path_prefix: "/campaigns/three".to_string(),
+        };
+
+        assert!(scope.matches(&json!({ "file_path": "/campaigns/three/a.md" })));
+        assert!(!scope.matches(&json!({ "file_path": "/campaigns/threefold/a.md" })));
+        assert!(!scope.matches(&json!({ "file_path": "/campaigns/three/../four/a.md" })));
+        assert!(!scope.matches(&json!({ "path": "/campaigns/three/a.md" })));
+    }
+
+    #[test]
+    fn test_validate_rejects_incomplete_scope() {
+        let settings = ToolPolicySettings {
+            policies: vec![ToolPolicy {
+                argument_scope: Some(ArgumentScope {
+                    argument: "file_path".to_string(),
+                    path_prefix: " ".to_string(),
+                }),
+                ..policy("write_file", ToolPermission::Allow)
+            }],
+        };
+        assert!(settings.validate().is_err());
+        assert!(ToolPolicySettings {
+            policies: vec![policy("", ToolPermission::Allow)]
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().to_path_buf();
+
+        assert_eq!(
+            ToolPolicySettings::load(&config_dir).unwrap(),
+            ToolPolicySettings::default()
+        );
+
+        let settings = ToolPolicySettings {
+            policies: vec![ToolPolicy {
+                campaign_id: Some(1),
+                ..policy("update_module_status", ToolPermission::Allow)
+            }],
+        };
+        settings.save(&config_dir).unwrap();
+
+        let loaded = ToolPolicySettings::load(&config_dir).unwrap();
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn test_corrupt_policies_file_fails_closed() {
+        let temp_dir = tempdir().unwrap();
+        let config_dir = temp_dir.path().to_path_buf();
+        fs::write(
+            config_dir.join(POLICIES_FILE),
+            "{ \"policies\": [ { \"tool_na",
+        )
+        .unwrap();
+
+        assert!(ToolPolicySettings::load(&config_dir).is_err());
+
+        let settings = ToolPolicySettings::load_or_ask(&config_dir);
+        let decision = settings.evaluate("read_file", None, &json!({}), false);
+        assert_eq!(decision.permission, ToolPermission::Ask);
+        let decision = settings.evaluate("write_file", Some(1), &json!({}), true);
+        assert_eq!(decision.permission, ToolPermission::Ask);
+
+        // A missing file still means the tool defaults
+        fs::remove_file(config_dir.join(POLICIES_FILE)).unwrap();
+        let settings = ToolPolicySettings::load_or_ask(&config_dir);
+        let decision = settings.evaluate("read_file", None, &json!({}), false);
+        assert_eq!(decision.permission, ToolPermission::Allow);
+    }
+}
@@ -4,6 +4,7 @@
 
 use async_trait::async_trait;
 use mimir_dm_core::models::character::CharacterData;
+use mimir_dm_core::services::character::CharacterValidator;
 use mimir_dm_core::services::player_service::PlayerService;
 use mimir_dm_core::{services::CharacterService, DatabaseService};
 use mimir_dm_llm::ToolTrait;
@@ -179,6 +180,7 @@ Output includes:
                 "languages": char_data.proficiencies.languages
             },
             "class_features": char_data.class_features,
+            "optional_features": char_data.optional_features,
             "feats": char_data.feats,
             "spells": {
                 "known_spells": char_data.spells.known_spells,
@@ -647,7 +649,9 @@ When to use:
 Output format:
 - Object with spell levels as keys (1-9)
 - Each level shows current and maximum slots
-- Only includes levels the character has access to"
+- Only includes levels the character has access to
+- Warlock pact magic slots are listed separately under pact_slots
+- Limited-use class features (Ki, Rage, etc.) are listed under class_resources"
     }
 
     fn parameters_schema(&self) -> Value {
@@ -682,10 +686,13 @@ Output format:
             "class": char_data.primary_class_name(),
             "level": char_data.level,
             "spell_slots": char_data.spells.spell_slots,
+            "pact_slots": char_data.spells.pact_slots,
+            "class_resources": char_data.class_resources,
             "prepared_spells_count": char_data.spells.prepared_spells.len(),
             "prepared_spells": char_data.spells.prepared_spells,
             "cantrips": char_data.spells.cantrips,
             "is_spellcaster": !char_data.spells.spell_slots.is_empty()
+                || char_data.spells.pact_slots.is_some()
         });
 
         debug!(
@@ -696,6 +703,95 @@ Output format:
     }
 }
 
+/// Tool for checking a character against the rules and catalog data
+pub struct ValidateCharacterTool {
+    db_service: Arc<DatabaseService>,
+}
+
+impl ValidateCharacterTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self { db_service }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for ValidateCharacterTool {
+    fn name(&self) -> &str {
+        "validate_character"
+    }
+
+    fn description(&self) -> &str {
+        "Check a character against the D&D 5e rules and the imported catalogs.
+
+Usage:
+- Provide character_id
+- Read-only; nothing is changed
+
+When to use:
+- After creating or leveling up a character
+- When a player's sheet looks wrong or a DM asks whether a build is legal
+- Before fixing a character with update_character or level_up
+
+Checks include:
+- Class levels add up to the character level; multiclassing ability requirements
+- Skill proficiencies offered by race, class, background and feats
+- Feat and optional feature (invocation, fighting style) prerequisites
+- Cantrips, spells known and prepared spells against class limits
+- Spell levels against available spell slots and class spell lists
+- Ability score ranges, hit points and attunement limits
+
+Output:
+- valid (true when there are no errors), error_count, warning_count
+- findings: each with severity (error/warning), field and message"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "character_id": {
+                    "type": "integer",
+                    "description": "Numeric ID of the character"
+                }
+            },
+            "required": ["character_id"]
+        })
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let character_id = extract_i32_param(&arguments, "character_id")?;
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+        let mut char_service = CharacterService::new(&mut conn);
+        let (character, char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+
+        let report = CharacterValidator::new(&mut conn)
+            .validate(&char_data)
+            .map_err(|e| format!("Failed to validate character: {}", e))?;
+
+        let result = json!({
+            "character_id": character.id,
+            "character_name": char_data.character_name,
+            "valid": report.valid,
+            "error_count": report.error_count,
+            "warning_count": report.warning_count,
+            "findings": report.findings
+        });
+
+        debug!(
+            "Validated character: {} (ID: {}) - {} errors, {} warnings",
+            char_data.character_name, character.id, report.error_count, report.warning_count
+        );
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
 /// Tool for listing all players
 pub struct ListPlayersTool {
     db_service: Arc<DatabaseService>,
@@ -311,6 +311,30 @@ mod tests {
         assert!(spell_slots.contains_key("1"));
     }
 
+    #[tokio::test]
+    async fn test_validate_character_tool() {
+        let (db_service, temp_dir) = setup_test_db();
+        let campaign_id = create_test_campaign(&db_service, &temp_dir);
+        let player_id = create_test_player(&db_service);
+        let character_id = create_test_character(&db_service, campaign_id, player_id, &temp_dir);
+
+        let tool = ValidateCharacterTool::new(Arc::clone(&db_service));
+        assert!(!tool.requires_confirmation());
+
+        let arguments = json!({
+            "character_id": character_id
+        });
+
+        let result = tool.execute(arguments).await;
+        assert!(result.is_ok());
+
+        let response: serde_json::Value = serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(response["character_name"], "Gandalf");
+        assert_eq!(response["error_count"], 0);
+        assert_eq!(response["valid"], true);
+        assert!(response["findings"].is_array());
+    }
+
     #[tokio::test]
     async fn test_update_character_hp_tool() {
         let (db_service, temp_dir) = setup_test_db();
@@ -352,6 +376,47 @@ mod tests {
         assert_eq!(char_data.current_hp, 5);
     }
 
+    #[tokio::test]
+    async fn test_temp_hp_absorbs_damage_and_concentration_check() {
+        let (db_service, temp_dir) = setup_test_db();
+        let campaign_id = create_test_campaign(&db_service, &temp_dir);
+        let player_id = create_test_player(&db_service);
+        let character_id = create_test_character(&db_service, campaign_id, player_id, &temp_dir);
+
+        let status_tool = UpdateCharacterStatusTool::new(Arc::clone(&db_service));
+        let result = status_tool
+            .execute(json!({
+                "character_id": character_id,
+                "temp_hp": 3,
+                "add_conditions": ["Poisoned"],
+                "concentration": "Mage Armor"
+            }))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(response["temp_hp"], 3);
+        assert_eq!(response["conditions"], json!(["Poisoned"]));
+
+        let hp_tool = UpdateCharacterHpTool::new(Arc::clone(&db_service));
+        let result = hp_tool
+            .execute(json!({
+                "character_id": character_id,
+                "hp_change": -4
+            }))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(response["temp_hp_absorbed"], 3);
+        assert_eq!(response["temp_hp"], 0);
+        assert_eq!(response["concentration_check"]["dc"], 10);
+
+        let mut conn = db_service.get_connection().unwrap();
+        let mut char_service = CharacterService::new(&mut conn);
+        let (_, char_data) = char_service.get_character(character_id).unwrap();
+        assert_eq!(char_data.current_hp, char_data.max_hp - 1);
+        assert_eq!(char_data.concentration.as_deref(), Some("Mage Armor"));
+    }
+
     #[tokio::test]
     async fn test_add_inventory_item_tool() {
         let (db_service, temp_dir) = setup_test_db();
@@ -389,6 +454,101 @@ mod tests {
         assert_eq!(char_data.inventory[0].quantity, 3);
     }
 
+    #[tokio::test]
+    async fn test_magic_item_attunement_and_charges_tools() {
+        use diesel::prelude::*;
+
+        let (db_service, temp_dir) = setup_test_db();
+        let campaign_id = create_test_campaign(&db_service, &temp_dir);
+        let player_id = create_test_player(&db_service);
+        let character_id = create_test_character(&db_service, campaign_id, player_id, &temp_dir);
+
+        {
+            let mut conn = db_service.get_connection().unwrap();
+            diesel::sql_query(
+                "INSERT INTO catalog_items (name, source, full_item_json) VALUES (?, ?, ?)",
+            )
+            .bind::<diesel::sql_types::Text, _>("Wand of Web")
+            .bind::<diesel::sql_types::Text, _>("DMG")
+            .bind::<diesel::sql_types::Text, _>(r#"{"name":"Wand of Web","source":"DMG","type":"WD","reqAttune":"by a spellcaster","charges":7,"recharge":"dawn","rechargeAmount":"{@dice 1d6 + 1}"}"#)
+            .execute(&mut conn)
+            .unwrap();
+        }
+
+        let add_tool = AddInventoryItemTool::new(Arc::clone(&db_service));
+        let result = add_tool
+            .execute(json!({
+                "character_id": character_id,
+                "item_name": "Wand of Web",
+                "attuned": true
+            }))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(response["found_in_catalog"], true);
+        assert_eq!(response["attuned"], true);
+        assert_eq!(response["charges"]["max"], 7);
+
+        let item_tool = UpdateInventoryItemTool::new(Arc::clone(&db_service));
+        let result = item_tool
+            .execute(json!({
+                "character_id": character_id,
+                "item_name": "wand of web",
+                "use_charges": 5
+            }))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(response["charges"]["current"], 2);
+
+        assert!(item_tool
+            .execute(json!({
+                "character_id": character_id,
+                "item_name": "Wand of Web",
+                "use_charges": 3
+            }))
+            .await
+            .is_err());
+
+        let rest_tool = TakeRestTool::new(Arc::clone(&db_service));
+        let result = rest_tool
+            .execute(json!({"character_id": character_id, "rest_type": "long"}))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        let current = response["item_charges"][0]["current"].as_i64().unwrap();
+        assert!((4..=7).contains(&current));
+    }
+
+    #[tokio::test]
+    async fn test_level_up_tool_optional_features() {
+        let (db_service, temp_dir) = setup_test_db();
+        let campaign_id = create_test_campaign(&db_service, &temp_dir);
+        let player_id = create_test_player(&db_service);
+        let character_id = create_test_character(&db_service, campaign_id, player_id, &temp_dir);
+
+        let tool = LevelUpTool::new(Arc::clone(&db_service));
+
+        // Wizards have no optional feature choices to fill
+        assert!(tool
+            .execute(json!({
+                "character_id": character_id,
+                "target_level": 2,
+                "optional_features": ["Archery"]
+            }))
+            .await
+            .is_err());
+
+        let result = tool
+            .execute(json!({"character_id": character_id, "target_level": 2}))
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(response["new_level"], 2);
+        assert_eq!(response["optional_features_chosen"], json!([]));
+        assert_eq!(response["pending_optional_features"], json!([]));
+    }
+
     #[tokio::test]
     async fn test_cast_spell_tool() {
         let (db_service, temp_dir) = setup_test_db();
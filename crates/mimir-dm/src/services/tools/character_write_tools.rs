@@ -4,9 +4,18 @@
 
 use async_trait::async_trait;
 use mimir_dm_core::dal::campaign::campaigns::CampaignRepository;
-use mimir_dm_core::models::character::data::{InventoryItem, Personality};
+use mimir_dm_core::domain::dice::DiceRoller;
+use mimir_dm_core::models::character::data::{
+    CharacterData, DamageOutcome, InventoryItem, ItemCharges, Personality, MAX_ATTUNED_ITEMS,
+};
 use mimir_dm_core::services::character::creation::{AbilityScoreMethod, CharacterBuilder};
-use mimir_dm_core::services::character::spell_management::RestType;
+use mimir_dm_core::services::character::optional_features::{
+    apply_optional_feature_choices, optional_feature_gains, OptionalFeatureSelection,
+};
+use mimir_dm_core::services::character::resources::refresh_resources;
+use mimir_dm_core::services::character::spell_management::{requires_concentration, RestType};
+use mimir_dm_core::services::character::starting_equipment::StartingEquipmentChoice;
+use mimir_dm_core::services::ItemService;
 use mimir_dm_core::{services::CharacterService, DatabaseService};
 use mimir_dm_llm::traits::{ActionDescription, ChangeDetail};
 use mimir_dm_llm::ToolTrait;
@@ -36,11 +45,17 @@ impl ToolTrait for UpdateCharacterHpTool {
         "Update a character's current HP (apply damage or healing).
 
 Usage:
-- Provide character_id and new_hp value
+- Provide character_id and either hp_change (negative for damage, positive for healing) or new_hp
 - Optionally provide reason for HP change
 - Creates new character version snapshot
 - Respects max HP limits
 
+Rules applied:
+- Damage is taken from temporary HP first
+- Damage to a concentrating character requires a Constitution save (DC 10 or half the damage)
+- Dropping to 0 HP makes the character unconscious and ends concentration
+- Damage at 0 HP counts as a failed death save; healing at 0 HP resets death saves
+
 When to use:
 - After combat encounters
 - When healing spells or potions are used
@@ -48,7 +63,9 @@ When to use:
 - Tracking character health status
 
 Output:
-- Updated character with new HP value
+- Updated HP and temporary HP
+- Whether the character dropped to 0 HP or died
+- Concentration save DC, if one is needed
 - Character version created for history"
     }
 
@@ -60,16 +77,20 @@ Output:
                     "type": "integer",
                     "description": "ID of the character to update"
                 },
+                "hp_change": {
+                    "type": ["integer", "null"],
+                    "description": "Amount to change HP by: negative for damage, positive for healing"
+                },
                 "new_hp": {
-                    "type": "integer",
-                    "description": "New current HP value (0 to max_hp)"
+                    "type": ["integer", "null"],
+                    "description": "New current HP value (0 to max_hp), as an alternative to hp_change"
                 },
                 "reason": {
                     "type": ["string", "null"],
                     "description": "Reason for HP change (e.g., 'Took 10 damage from goblin', 'Healed 8 HP from potion')"
                 }
             },
-            "required": ["character_id", "new_hp"]
+            "required": ["character_id"]
         })
     }
 
@@ -79,7 +100,8 @@ Output:
 
     fn describe_action(&self, arguments: &Value) -> Option<ActionDescription> {
         let character_id = arguments.get("character_id")?.as_i64()?;
-        let new_hp = arguments.get("new_hp")?.as_i64()?;
+        let hp_change = arguments.get("hp_change").and_then(|v| v.as_i64());
+        let new_hp = arguments.get("new_hp").and_then(|v| v.as_i64());
         let reason = arguments
             .get("reason")
             .and_then(|v| v.as_str())
@@ -93,6 +115,7 @@ Output:
                     char_data.character_name.clone(),
                     char_data.current_hp,
                     char_data.max_hp,
+                    char_data.temp_hp,
                 ))
             } else {
                 None
@@ -101,24 +124,34 @@ Output:
             None
         };
 
-        let description = if let Some((name, current, max)) = current_hp_info {
-            let change = new_hp as i32 - current;
-            let change_desc = if change > 0 {
-                format!("heal {} HP", change)
-            } else if change < 0 {
-                format!("take {} damage", -change)
+        let change = match (hp_change, new_hp, &current_hp_info) {
+            (Some(change), _, _) => change as i32,
+            (None, Some(new_hp), Some((_, current, _, _))) => new_hp as i32 - current,
+            (None, Some(_), None) => 0,
+            (None, None, _) => return None,
+        };
+        let change_desc = if change > 0 {
+            format!("heal {} HP", change)
+        } else if change < 0 {
+            format!("take {} damage", -change)
+        } else {
+            "no change".to_string()
+        };
+
+        let description = if let Some((name, current, max, temp_hp)) = current_hp_info {
+            let temp_desc = if temp_hp > 0 && change < 0 {
+                format!(", {} temporary HP absorbs damage first", temp_hp)
             } else {
-                "no change".to_string()
+                String::new()
             };
-
             format!(
-                "Update {}'s HP from {}/{} to {}/{} ({})\nReason: {}",
-                name, current, max, new_hp, max, change_desc, reason
+                "Update {}'s HP ({}/{}): {}{}\nReason: {}",
+                name, current, max, change_desc, temp_desc, reason
             )
         } else {
             format!(
-                "Update character {} HP to {}\nReason: {}",
-                character_id, new_hp, reason
+                "Update character {} HP: {}\nReason: {}",
+                character_id, change_desc, reason
             )
         };
 
@@ -128,7 +161,7 @@ Output:
             changes: ChangeDetail::Generic {
                 items: vec![
                     format!("Character ID: {}", character_id),
-                    format!("New HP: {}", new_hp),
+                    format!("HP change: {:+}", change),
                     format!("Reason: {}", reason),
                 ],
             },
@@ -141,10 +174,14 @@ Output:
             .and_then(|v| v.as_i64())
             .ok_or("Missing 'character_id' parameter")? as i32;
 
+        let hp_change = arguments
+            .get("hp_change")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32);
         let new_hp = arguments
             .get("new_hp")
             .and_then(|v| v.as_i64())
-            .ok_or("Missing 'new_hp' parameter")? as i32;
+            .map(|v| v as i32);
 
         let reason = arguments
             .get("reason")
@@ -157,19 +194,64 @@ Output:
             .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
         let mut char_service = CharacterService::new(&mut conn);
-        let (_, mut char_data) = char_service
+        let (_, old_data) = char_service
             .get_character(character_id)
             .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+        let old_hp = old_data.current_hp;
+
+        let outcome = match (hp_change, new_hp) {
+            (Some(change), _) if change < 0 => {
+                char_service
+                    .apply_damage(character_id, -change, reason)
+                    .map_err(|e| format!("Failed to update character: {}", e))?
+                    .1
+            }
+            (Some(change), _) => {
+                char_service
+                    .heal(character_id, change, reason)
+                    .map_err(|e| format!("Failed to update character: {}", e))?;
+                DamageOutcome::default()
+            }
+            (None, Some(new_hp)) => {
+                char_service
+                    .update_hp(character_id, new_hp, reason)
+                    .map_err(|e| format!("Failed to update character: {}", e))?
+                    .1
+            }
+            (None, None) => return Err("Provide either 'hp_change' or 'new_hp'".into()),
+        };
 
-        let old_hp = char_data.current_hp;
-        char_data.current_hp = new_hp.max(0).min(char_data.max_hp);
-
-        let snapshot_reason = reason
-            .unwrap_or_else(|| format!("HP updated from {} to {}", old_hp, char_data.current_hp));
+        let (_, char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Failed to retrieve character: {}", e))?;
 
-        char_service
-            .update_character(character_id, char_data.clone(), Some(snapshot_reason))
-            .map_err(|e| format!("Failed to update character: {}", e))?;
+        let mut message = format!(
+            "Updated {} HP from {} to {}",
+            char_data.character_name, old_hp, char_data.current_hp
+        );
+        if outcome.temp_hp_absorbed > 0 {
+            message.push_str(&format!(
+                " ({} damage absorbed by temporary HP)",
+                outcome.temp_hp_absorbed
+            ));
+        }
+        if outcome.killed {
+            message.push_str(&format!(". {} has died", char_data.character_name));
+        } else if outcome.dropped_to_zero {
+            message.push_str(&format!(
+                ". {} is unconscious and must make death saves",
+                char_data.character_name
+            ));
+        }
+        if let Some(spell) = &outcome.concentration_ended {
+            message.push_str(&format!(". Concentration on {} ended", spell));
+        }
+        if let (Some(dc), Some(spell)) = (outcome.concentration_save_dc, &char_data.concentration) {
+            message.push_str(&format!(
+                ". {} must make a DC {} Constitution saving throw to keep concentrating on {}",
+                char_data.character_name, dc, spell
+            ));
+        }
 
         let result = json!({
             "success": true,
@@ -178,7 +260,18 @@ Output:
             "old_hp": old_hp,
             "new_hp": char_data.current_hp,
             "max_hp": char_data.max_hp,
-            "message": format!("Updated {} HP from {} to {}", char_data.character_name, old_hp, char_data.current_hp)
+            "temp_hp": char_data.temp_hp,
+            "temp_hp_absorbed": outcome.temp_hp_absorbed,
+            "dropped_to_zero": outcome.dropped_to_zero,
+            "killed": outcome.killed,
+            "death_saves": char_data.death_saves,
+            "concentration_check": outcome.concentration_save_dc.map(|dc| json!({
+                "spell": char_data.concentration,
+                "dc": dc,
+                "ability": "Constitution"
+            })),
+            "concentration_ended": outcome.concentration_ended,
+            "message": message
         });
 
         debug!(
@@ -189,6 +282,274 @@ Output:
     }
 }
 
+/// Tool for updating a character's combat state (temp HP, conditions, etc.)
+pub struct UpdateCharacterStatusTool {
+    db_service: Arc<DatabaseService>,
+}
+
+impl UpdateCharacterStatusTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self { db_service }
+    }
+}
+
+/// Read an optional list of strings from tool arguments
+fn string_list(arguments: &Value, key: &str) -> Vec<String> {
+    arguments
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl ToolTrait for UpdateCharacterStatusTool {
+    fn name(&self) -> &str {
+        "update_character_status"
+    }
+
+    fn description(&self) -> &str {
+        "Update a character's combat state: temporary HP, conditions, exhaustion, death saves and concentration.
+
+Usage:
+- Provide character_id and any of the optional fields to change
+- All changes are saved together as one character version
+
+Rules applied:
+- Temporary HP doesn't stack; the higher amount is kept
+- Exhaustion is clamped to levels 0-6
+- Death save rolls only apply at 0 HP: 1 counts as two failures, 10+ succeeds, 20 restores 1 HP
+- Setting concentration replaces any spell the character was concentrating on
+
+When to use:
+- A spell or feature grants temporary HP
+- A character gains or loses a condition (Poisoned, Prone, Frightened, ...)
+- Recording a death saving throw
+- A failed concentration save ends concentration
+
+Output:
+- Current temporary HP, conditions, exhaustion level, death saves and concentration"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "character_id": {
+                    "type": "integer",
+                    "description": "ID of the character to update"
+                },
+                "temp_hp": {
+                    "type": ["integer", "null"],
+                    "description": "Temporary HP to grant (0 to remove temporary HP)"
+                },
+                "add_conditions": {
+                    "type": ["array", "null"],
+                    "items": {"type": "string"},
+                    "description": "Conditions to add (e.g., ['Poisoned', 'Prone'])"
+                },
+                "remove_conditions": {
+                    "type": ["array", "null"],
+                    "items": {"type": "string"},
+                    "description": "Conditions to remove"
+                },
+                "exhaustion_level": {
+                    "type": ["integer", "null"],
+                    "description": "New exhaustion level (0-6)"
+                },
+                "death_save_roll": {
+                    "type": ["integer", "null"],
+                    "description": "d20 roll of a death saving throw, for a character at 0 HP"
+                },
+                "concentration": {
+                    "type": ["string", "null"],
+                    "description": "Spell the character starts concentrating on, or an empty string to end concentration"
+                }
+            },
+            "required": ["character_id"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        true
+    }
+
+    fn describe_action(&self, arguments: &Value) -> Option<ActionDescription> {
+        let character_id = arguments.get("character_id")?.as_i64()?;
+
+        let mut items = vec![format!("Character ID: {}", character_id)];
+        if let Some(temp_hp) = arguments.get("temp_hp").and_then(|v| v.as_i64()) {
+            items.push(format!("Temporary HP: {}", temp_hp));
+        }
+        for condition in string_list(arguments, "add_conditions") {
+            items.push(format!("Add condition: {}", condition));
+        }
+        for condition in string_list(arguments, "remove_conditions") {
+            items.push(format!("Remove condition: {}", condition));
+        }
+        if let Some(level) = arguments.get("exhaustion_level").and_then(|v| v.as_i64()) {
+            items.push(format!("Exhaustion level: {}", level));
+        }
+        if let Some(roll) = arguments.get("death_save_roll").and_then(|v| v.as_i64()) {
+            items.push(format!("Death save roll: {}", roll));
+        }
+        match arguments.get("concentration").and_then(|v| v.as_str()) {
+            Some("") => items.push("End concentration".to_string()),
+            Some(spell) => items.push(format!("Concentrate on: {}", spell)),
+            None => {}
+        }
+
+        let character_name = self
+            .db_service
+            .get_connection()
+            .ok()
+            .and_then(|mut conn| {
+                CharacterService::new(&mut conn)
+                    .get_character(character_id as i32)
+                    .ok()
+            })
+            .map(|(_, data)| data.character_name)
+            .unwrap_or_else(|| format!("character {}", character_id));
+
+        Some(ActionDescription {
+            title: "Update Character Status".to_string(),
+            description: format!("Update combat state for {}", character_name),
+            changes: ChangeDetail::Generic { items },
+        })
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let character_id = arguments
+            .get("character_id")
+            .and_then(|v| v.as_i64())
+            .ok_or("Missing 'character_id' parameter")? as i32;
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+        let mut char_service = CharacterService::new(&mut conn);
+        let (_, mut char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+
+        let mut changes = Vec::new();
+
+        if let Some(temp_hp) = arguments.get("temp_hp").and_then(|v| v.as_i64()) {
+            let temp_hp = temp_hp as i32;
+            if temp_hp <= 0 {
+                char_data.temp_hp = 0;
+                changes.push("removed temporary HP".to_string());
+            } else if char_data.grant_temp_hp(temp_hp) {
+                changes.push(format!("gained {} temporary HP", temp_hp));
+            } else {
+                changes.push(format!(
+                    "kept existing {} temporary HP (temporary HP doesn't stack)",
+                    char_data.temp_hp
+                ));
+            }
+        }
+
+        for condition in string_list(&arguments, "add_conditions") {
+            if char_data.add_condition(&condition) {
+                changes.push(format!("became {}", condition));
+            }
+        }
+        for condition in string_list(&arguments, "remove_conditions") {
+            if char_data.remove_condition(&condition) {
+                changes.push(format!("no longer {}", condition));
+            }
+        }
+
+        if let Some(level) = arguments.get("exhaustion_level").and_then(|v| v.as_i64()) {
+            char_data.set_exhaustion(level as i32);
+            changes.push(format!("exhaustion level {}", char_data.exhaustion_level));
+        }
+
+        if let Some(roll) = arguments.get("death_save_roll").and_then(|v| v.as_i64()) {
+            if char_data.current_hp > 0 {
+                return Err(format!(
+                    "{} is not at 0 HP and doesn't make death saves",
+                    char_data.character_name
+                )
+                .into());
+            }
+            if !(1..=20).contains(&roll) {
+                return Err(
+                    format!("Death save roll must be between 1 and 20, got {}", roll).into(),
+                );
+            }
+            char_data.record_death_save(roll as i32);
+            let saves = char_data.death_saves;
+            if char_data.current_hp > 0 {
+                changes.push(format!("rolled {} on a death save and regained 1 HP", roll));
+            } else if saves.is_dead() {
+                changes.push(format!("rolled {} on a death save and died", roll));
+            } else if saves.is_stable() {
+                changes.push(format!("rolled {} on a death save and is stable", roll));
+            } else {
+                changes.push(format!(
+                    "rolled {} on a death save ({} successes, {} failures)",
+                    roll, saves.successes, saves.failures
+                ));
+            }
+        }
+
+        match arguments.get("concentration").and_then(|v| v.as_str()) {
+            Some("") => {
+                if let Some(spell) = char_data.concentration.take() {
+                    changes.push(format!("stopped concentrating on {}", spell));
+                }
+            }
+            Some(spell) => {
+                char_data.concentration = Some(spell.to_string());
+                changes.push(format!("concentrating on {}", spell));
+            }
+            None => {}
+        }
+
+        if changes.is_empty() {
+            return Err("No status changes to apply".into());
+        }
+
+        let summary = changes.join(", ");
+        char_service
+            .update_character(
+                character_id,
+                char_data.clone(),
+                Some(format!("Status: {}", summary)),
+            )
+            .map_err(|e| format!("Failed to update character: {}", e))?;
+
+        let result = json!({
+            "success": true,
+            "character_id": character_id,
+            "character_name": char_data.character_name,
+            "current_hp": char_data.current_hp,
+            "max_hp": char_data.max_hp,
+            "temp_hp": char_data.temp_hp,
+            "conditions": char_data.conditions,
+            "exhaustion_level": char_data.exhaustion_level,
+            "death_saves": char_data.death_saves,
+            "concentration": char_data.concentration,
+            "message": format!("{}: {}", char_data.character_name, summary)
+        });
+
+        debug!("Updated character {} status: {}", character_id, summary);
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+/// Sources searched for an item when the caller doesn't give one
+const CATALOG_ITEM_SOURCES: [&str; 4] = ["PHB", "DMG", "XPHB", "XDMG"];
+
 /// Tool for adding items to character inventory
 pub struct AddInventoryItemTool {
     db_service: Arc<DatabaseService>,
@@ -212,6 +573,8 @@ impl ToolTrait for AddInventoryItemTool {
 Usage:
 - Provide character_id, item name, and quantity
 - Optionally provide weight, value, and notes
+- Items found in the catalog get their weight, value, attunement requirement and charges filled in
+- Set attuned=true to attune immediately (item must require attunement; max 3 attuned items)
 - Creates new character version snapshot
 
 When to use:
@@ -222,6 +585,7 @@ When to use:
 
 Output:
 - Updated character with new inventory item
+- Attunement and charge state of the item
 - Character version created for history"
     }
 
@@ -256,6 +620,10 @@ Output:
                 "notes": {
                     "type": ["string", "null"],
                     "description": "Additional notes about the item (optional)"
+                },
+                "attuned": {
+                    "type": ["boolean", "null"],
+                    "description": "Attune to the item as it is added (optional)"
                 }
             },
             "required": ["character_id", "item_name"]
@@ -288,6 +656,9 @@ Output:
         if let Some(v) = value {
             details.push(format!("Value: {} gp", v));
         }
+        if arguments.get("attuned").and_then(|v| v.as_bool()) == Some(true) {
+            details.push("Attune to item".to_string());
+        }
 
         Some(ActionDescription {
             title: "Add Inventory Item".to_string(),
@@ -317,44 +688,91 @@ Output:
             .and_then(|v| v.as_i64())
             .unwrap_or(1) as i32;
 
-        let weight = arguments
-            .get("weight")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+        let weight = arguments.get("weight").and_then(|v| v.as_f64());
 
-        let value = arguments
-            .get("value")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+        let value = arguments.get("value").and_then(|v| v.as_f64());
 
         let notes = arguments
             .get("notes")
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        let attune = arguments
+            .get("attuned")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
         let mut conn = self
             .db_service
             .get_connection()
             .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
+        // Look the item up in the catalog for attunement and charges
+        let catalog_item = {
+            let mut item_service = ItemService::new(&mut conn);
+            let sources = match &item_source {
+                Some(source) => vec![source.as_str()],
+                None => CATALOG_ITEM_SOURCES.to_vec(),
+            };
+            let mut found = None;
+            for source in sources {
+                if let Ok(Some(item)) = item_service.get_item_by_name_and_source(item_name, source)
+                {
+                    found = Some(item);
+                    break;
+                }
+            }
+            found
+        };
+
         let mut char_service = CharacterService::new(&mut conn);
         let (_, mut char_data) = char_service
             .get_character(character_id)
             .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+        let attuned_count = char_data.attuned_count();
 
         // Check if item already exists in inventory
-        if let Some(existing) = char_data.inventory.iter_mut().find(|i| i.name == item_name) {
-            existing.quantity += quantity;
-        } else {
-            char_data.inventory.push(InventoryItem {
-                name: item_name.to_string(),
-                source: item_source,
-                quantity,
-                weight,
-                value,
-                notes,
-            });
+        let index = match char_data.inventory.iter().position(|i| i.name == item_name) {
+            Some(index) => {
+                char_data.inventory[index].quantity += quantity;
+                index
+            }
+            None => {
+                let catalog = catalog_item.as_ref();
+                char_data.inventory.push(InventoryItem {
+                    name: item_name.to_string(),
+                    source: item_source.or_else(|| catalog.map(|i| i.source.clone())),
+                    quantity,
+                    weight: weight
+                        .or_else(|| catalog.and_then(|i| i.weight).map(f64::from))
+                        .unwrap_or(0.0),
+                    value: value
+                        .or_else(|| catalog.and_then(|i| i.value))
+                        .unwrap_or(0.0),
+                    notes,
+                    requires_attunement: catalog.is_some_and(|i| i.requires_attunement.is_some()),
+                    attuned: false,
+                    charges: catalog.and_then(ItemCharges::from_catalog),
+                });
+                char_data.inventory.len() - 1
+            }
+        };
+
+        let item = &mut char_data.inventory[index];
+        if attune && !item.attuned {
+            if !item.requires_attunement {
+                return Err(format!("{} does not require attunement", item.name).into());
+            }
+            if attuned_count >= MAX_ATTUNED_ITEMS {
+                return Err(format!(
+                    "Already attuned to {} items; end attunement with one first",
+                    MAX_ATTUNED_ITEMS
+                )
+                .into());
+            }
+            item.attuned = true;
         }
+        let item = item.clone();
 
         let snapshot_reason = format!("Added {} × {} to inventory", quantity, item_name);
         char_service
@@ -367,6 +785,11 @@ Output:
             "character_name": char_data.character_name,
             "item_added": item_name,
             "quantity": quantity,
+            "found_in_catalog": catalog_item.is_some(),
+            "requires_attunement": item.requires_attunement,
+            "attuned": item.attuned,
+            "attuned_items": char_data.attuned_count(),
+            "charges": item.charges,
             "message": format!("Added {} × {} to {}'s inventory", quantity, item_name, char_data.character_name)
         });
 
@@ -401,8 +824,10 @@ impl ToolTrait for CastSpellTool {
 Usage:
 - Provide character_id, spell_name, and spell_level
 - Automatically reduces available spell slots
+- Uses Warlock pact magic slots of that level once regular slots run out
 - Creates new character version snapshot
 - Validates character has available slots
+- Concentration spells replace any spell the character was concentrating on
 
 When to use:
 - During combat when spells are cast
@@ -449,11 +874,8 @@ Output:
         let slot_info = if let Ok(mut conn) = self.db_service.get_connection() {
             let mut char_service = CharacterService::new(&mut conn);
             if let Ok((_, char_data)) = char_service.get_character(character_id as i32) {
-                char_data
-                    .spells
-                    .spell_slots
-                    .get(&(spell_level as i32))
-                    .map(|slots| (char_data.character_name.clone(), slots.current, slots.max))
+                slot_counts(&char_data, spell_level as i32)
+                    .map(|(current, max)| (char_data.character_name.clone(), current, max))
             } else {
                 None
             }
@@ -529,45 +951,57 @@ Output:
             .get_connection()
             .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
-        let mut char_service = CharacterService::new(&mut conn);
-        let (_, mut char_data) = char_service
+        let (_, mut char_data) = CharacterService::new(&mut conn)
             .get_character(character_id)
             .map_err(|e| format!("Failed to retrieve character: {}", e))?;
 
         // Check if character has spell slots for this level
-        let slots = char_data
-            .spells
-            .spell_slots
-            .get_mut(&spell_level)
-            .ok_or(format!(
-                "Character has no level {} spell slots",
-                spell_level
-            ))?;
-
-        if slots.current <= 0 {
+        let (_, slots_max) = slot_counts(&char_data, spell_level).ok_or(format!(
+            "Character has no level {} spell slots",
+            spell_level
+        ))?;
+
+        // Consume spell slot, falling back to pact magic slots
+        if !char_data.expend_spell_slot(spell_level) {
             return Err(format!(
                 "No level {} spell slots remaining (0/{})",
-                spell_level, slots.max
+                spell_level, slots_max
             )
             .into());
         }
 
-        // Consume spell slot
-        slots.current -= 1;
+        let (slots_remaining, slots_max) =
+            slot_counts(&char_data, spell_level).unwrap_or((0, slots_max));
 
-        // Capture values before the borrow ends
-        let slots_remaining = slots.current;
-        let slots_max = slots.max;
+        // Concentration spells replace whatever the caster was concentrating on
+        let concentration = requires_concentration(&mut conn, &char_data, spell_name)
+            .map_err(|e| format!("Failed to look up spell: {}", e))?;
+        let previous_concentration = if concentration {
+            char_data.concentration.replace(spell_name.to_string())
+        } else {
+            None
+        };
 
         let snapshot_reason = format!(
             "Cast {} (level {}) - {} slots remaining",
             spell_name, spell_level, slots_remaining
         );
 
-        char_service
+        CharacterService::new(&mut conn)
             .update_character(character_id, char_data.clone(), Some(snapshot_reason))
             .map_err(|e| format!("Failed to update character: {}", e))?;
 
+        let mut message = format!(
+            "{} cast {} (level {}). {} slots remaining.",
+            char_data.character_name, spell_name, spell_level, slots_remaining
+        );
+        if concentration {
+            message.push_str(&format!(" Concentrating on {}.", spell_name));
+        }
+        if let Some(previous) = &previous_concentration {
+            message.push_str(&format!(" Concentration on {} ended.", previous));
+        }
+
         let result = json!({
             "success": true,
             "character_id": character_id,
@@ -576,10 +1010,9 @@ Output:
             "spell_level": spell_level,
             "slots_remaining": slots_remaining,
             "slots_max": slots_max,
-            "message": format!(
-                "{} cast {} (level {}). {} slots remaining.",
-                char_data.character_name, spell_name, spell_level, slots_remaining
-            )
+            "concentration": char_data.concentration,
+            "concentration_ended": previous_concentration,
+            "message": message
         });
 
         debug!(
@@ -590,6 +1023,24 @@ Output:
     }
 }
 
+/// Current and maximum slots of a spell level, counting pact magic slots
+fn slot_counts(char_data: &CharacterData, spell_level: i32) -> Option<(i32, i32)> {
+    let regular = char_data.spells.spell_slots.get(&spell_level);
+    let pact = char_data
+        .spells
+        .pact_slots
+        .as_ref()
+        .filter(|pact| pact.slot_level == spell_level);
+
+    match (regular, pact) {
+        (None, None) => None,
+        (regular, pact) => Some((
+            regular.map_or(0, |s| s.current) + pact.map_or(0, |p| p.current),
+            regular.map_or(0, |s| s.max) + pact.map_or(0, |p| p.max),
+        )),
+    }
+}
+
 /// Tool for creating a new character
 pub struct CreateCharacterTool {
     db_service: Arc<DatabaseService>,
@@ -617,6 +1068,7 @@ Usage:
 - Provide player_id, character_name, race, race_source, class, class_source, background, background_source
 - Provide ability_scores as object with strength, dexterity, constitution, intelligence, wisdom, charisma
 - Optionally provide campaign_id, subrace, subclass, alignment, personality traits
+- Optionally provide starting_equipment: {type: 'equipment', class_choices: ['a', 'b', ...], background_choices: [...], equipment_picks: ['longsword|phb']} to take the class (a)/(b) packages and background gear, or {type: 'gold', gold: 120} to take starting gold instead (rolled when gold is omitted)
 - Source is typically 'PHB' for Player's Handbook content
 - If campaign_id is not provided, the character is created in the general character pool
 
@@ -630,6 +1082,7 @@ Character creation includes:
 - Starting HP calculated from class hit dice + CON modifier (level 1)
 - Spell slots calculated for spellcasting classes
 - Speed and other racial attributes
+- Starting equipment resolved from the catalog, with equipment packs expanded
 
 Output:
 - Created character with database ID
@@ -711,6 +1164,36 @@ Output:
                         "bonds": { "type": ["string", "null"] },
                         "flaws": { "type": ["string", "null"] }
                     }
+                },
+                "starting_equipment": {
+                    "type": ["object", "null"],
+                    "description": "Starting equipment choice: class/background packages or starting gold",
+                    "properties": {
+                        "type": {
+                            "type": "string",
+                            "enum": ["equipment", "gold"]
+                        },
+                        "class_choices": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Option key ('a', 'b', ...) for each class choice group, in order"
+                        },
+                        "background_choices": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Option key for each background choice group, in order"
+                        },
+                        "equipment_picks": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Specific items for 'any martial weapon'-style entries, in order (e.g., 'longsword|phb')"
+                        },
+                        "gold": {
+                            "type": ["integer", "null"],
+                            "description": "Gold pieces when type is 'gold' (rolled from the class formula if omitted)"
+                        }
+                    },
+                    "required": ["type"]
                 }
             },
             "required": ["player_id", "character_name", "race", "race_source", "class", "class_source", "background", "background_source", "ability_scores"]
@@ -833,6 +1316,13 @@ Output:
                 .map(|s| s.to_string()),
         });
 
+        let starting_equipment = arguments
+            .get("starting_equipment")
+            .filter(|v| !v.is_null())
+            .map(|v| serde_json::from_value::<StartingEquipmentChoice>(v.clone()))
+            .transpose()
+            .map_err(|e| format!("Invalid starting_equipment: {}", e))?;
+
         let mut conn = self
             .db_service
             .get_connection()
@@ -898,6 +1388,12 @@ Output:
             builder = builder.set_personality(pers);
         }
 
+        if let Some(choice) = starting_equipment {
+            builder = builder
+                .choose_starting_equipment(choice)
+                .map_err(|e| format!("Failed to choose starting equipment: {}", e))?;
+        }
+
         let char_data = builder
             .build()
             .map_err(|e| format!("Failed to create character: {}", e))?;
@@ -915,6 +1411,11 @@ Output:
             "level": char_data.level,
             "race": char_data.race,
             "class": char_data.classes[0].class_name,
+            "inventory": char_data.inventory.iter().map(|i| json!({
+                "name": i.name,
+                "quantity": i.quantity
+            })).collect::<Vec<_>>(),
+            "currency": char_data.currency,
             "message": format!("Created {} - Level {} {} {}",
                 char_data.character_name,
                 char_data.level,
@@ -1411,8 +1912,10 @@ impl ToolTrait for LevelUpTool {
 
 Usage:
 - Provide character_id and target_level
-- Optionally provide hp_increase_method: 'average' (default) or 'max'
+- Optionally provide hp_increase_method: 'average' (default), 'max' or 'roll'
 - Optionally provide custom max_hp to override calculated HP
+- Optionally provide optional_features: Eldritch Invocations, Fighting Styles,
+  Metamagic or maneuvers gained by the primary class at the new level
 - Creates version snapshot for history tracking
 
 Level up effects:
@@ -1420,6 +1923,7 @@ Level up effects:
 - Calculates HP gain based on class hit dice + CON modifier
 - Updates hit dice pool
 - For multiclass characters, uses primary (first) class hit die
+- Records optional feature choices after checking open choices and prerequisites
 
 HP calculation per level:
 - Average: (hit_die / 2 + 1) + CON modifier per level
@@ -1434,7 +1938,8 @@ When to use:
 Output:
 - New level confirmed
 - HP before and after
-- Hit dice updated"
+- Hit dice updated
+- Optional features chosen, plus any open choices with the options available"
     }
 
     fn parameters_schema(&self) -> Value {
@@ -1451,12 +1956,17 @@ Output:
                 },
                 "hp_increase_method": {
                     "type": ["string", "null"],
-                    "enum": ["average", "max"],
-                    "description": "How to calculate HP gain: 'average' (default) or 'max'"
+                    "enum": ["average", "max", "roll"],
+                    "description": "How to calculate HP gain: 'average' (default), 'max' or 'roll' the hit die"
                 },
                 "max_hp": {
                     "type": ["integer", "null"],
                     "description": "Override calculated max HP with this value (optional)"
+                },
+                "optional_features": {
+                    "type": ["array", "null"],
+                    "items": { "type": "string" },
+                    "description": "Names of optional features to take (e.g. ['Agonizing Blast', 'Devil's Sight'] for Eldritch Invocations)"
                 }
             },
             "required": ["character_id", "target_level"]
@@ -1475,19 +1985,28 @@ Output:
             .and_then(|v| v.as_str())
             .unwrap_or("average");
 
+        let mut items = vec![
+            format!("Character ID: {}", character_id),
+            format!("Target level: {}", target_level),
+            format!("HP method: {}", hp_method),
+        ];
+        if let Some(features) = arguments
+            .get("optional_features")
+            .and_then(|v| v.as_array())
+        {
+            let names: Vec<&str> = features.iter().filter_map(|f| f.as_str()).collect();
+            if !names.is_empty() {
+                items.push(format!("Optional features: {}", names.join(", ")));
+            }
+        }
+
         Some(ActionDescription {
             title: "Level Up Character".to_string(),
             description: format!(
                 "Level up character {} to level {} (HP method: {})",
                 character_id, target_level, hp_method
             ),
-            changes: ChangeDetail::Generic {
-                items: vec![
-                    format!("Character ID: {}", character_id),
-                    format!("Target level: {}", target_level),
-                    format!("HP method: {}", hp_method),
-                ],
-            },
+            changes: ChangeDetail::Generic { items },
         })
     }
 
@@ -1512,6 +2031,21 @@ Output:
             .and_then(|v| v.as_i64())
             .map(|v| v as i32);
 
+        let feature_selections: Vec<OptionalFeatureSelection> = arguments
+            .get("optional_features")
+            .and_then(|v| v.as_array())
+            .map(|features| {
+                features
+                    .iter()
+                    .filter_map(|f| f.as_str())
+                    .map(|name| OptionalFeatureSelection {
+                        name: name.to_string(),
+                        source: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         if target_level < 1 || target_level > 20 {
             return Err("Target level must be between 1 and 20".into());
         }
@@ -1549,14 +2083,18 @@ Output:
         // Calculate CON modifier
         let con_mod = (char_data.abilities.constitution - 10) / 2;
 
-        // Calculate HP gain
-        let hp_per_level = match hp_method {
-            "max" => hit_die + con_mod,
-            _ => (hit_die / 2 + 1) + con_mod, // average
+        // Calculate HP gain; each level adds at least 1 HP
+        let hp_gain = match hp_method {
+            "max" => (hit_die + con_mod).max(1) * levels_gained,
+            "roll" => {
+                let mut roller = DiceRoller::new();
+                (0..levels_gained)
+                    .map(|_| (roller.roll_die(hit_die as u32) as i32 + con_mod).max(1))
+                    .sum()
+            }
+            _ => ((hit_die / 2 + 1) + con_mod).max(1) * levels_gained, // average
         };
 
-        let hp_gain = hp_per_level * levels_gained;
-
         // Update character
         char_data.level = target_level;
 
@@ -1575,11 +2113,43 @@ Output:
             class.hit_dice_remaining = target_level;
         }
 
+        // Record optional feature choices and report any still open
+        let mut chosen_features = Vec::new();
+        let mut pending_features = Vec::new();
+        if let Some(class_name) = char_data.classes.first().map(|c| c.class_name.clone()) {
+            chosen_features = apply_optional_feature_choices(
+                &mut conn,
+                &mut char_data,
+                &class_name,
+                &feature_selections,
+            )
+            .map_err(|e| format!("Invalid optional feature choice: {}", e))?;
+
+            pending_features = optional_feature_gains(&mut conn, &char_data, &class_name)
+                .map_err(|e| format!("Failed to check optional features: {}", e))?
+                .into_iter()
+                .filter(|g| g.remaining > 0)
+                .map(|g| {
+                    json!({
+                        "name": g.name,
+                        "remaining": g.remaining,
+                        "available": g.available.iter().map(|f| &f.name).collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+        } else if !feature_selections.is_empty() {
+            return Err("Character has no class to take optional features for".into());
+        }
+
+        // Recalculate spell slots, pact magic and class resources for the new level
+        refresh_resources(&mut conn, &mut char_data)
+            .map_err(|e| format!("Failed to update class resources: {}", e))?;
+
         let snapshot_reason = format!(
             "Leveled up from {} to {} (HP: {} -> {})",
             current_level, target_level, old_hp, char_data.max_hp
         );
-        char_service
+        CharacterService::new(&mut conn)
             .update_character(character_id, char_data.clone(), Some(snapshot_reason))
             .map_err(|e| format!("Failed to update character: {}", e))?;
 
@@ -1593,6 +2163,8 @@ Output:
             "old_hp": old_hp,
             "new_hp": char_data.max_hp,
             "hp_gain": char_data.max_hp - old_hp,
+            "optional_features_chosen": chosen_features.iter().map(|f| &f.name).collect::<Vec<_>>(),
+            "pending_optional_features": pending_features,
             "message": format!(
                 "{} leveled up from {} to {}! HP: {} -> {}",
                 char_data.character_name, current_level, target_level, old_hp, char_data.max_hp
@@ -1632,14 +2204,20 @@ Usage:
 - Creates version snapshot for history tracking
 
 Short rest effects:
-- Currently records the rest (hit dice spending can be done manually)
-- Certain class features may restore on short rest
+- Restores Warlock pact magic slots
+- Restores short-rest class resources (Ki, Channel Divinity, Action Surge, etc.)
+- Recharges magic items that recharge on a short rest
+- Hit dice spending can be done manually
 
 Long rest effects:
 - Restores all HP to maximum
 - Restores all hit dice
-- Restores all spell slots to maximum
-- Resets daily abilities
+- Restores all spell slots and pact magic slots to maximum
+- Restores all class resources (Rage, Sorcery Points, etc.)
+- Ends temporary HP and concentration, resets death saves
+- Reduces exhaustion by one level
+- Recharges magic items that recharge at dawn, dusk, midnight or on a long rest
+  (rolling the item's recharge dice when it has them)
 
 When to use:
 - After combat encounters when party rests
@@ -1725,26 +2303,33 @@ Output:
 
         let old_hp = char_data.current_hp;
 
-        match rest_type {
-            RestType::Short => {
-                // Short rest: can spend hit dice (simplified - just note the rest)
-            }
-            RestType::Long => {
-                // Long rest: restore all HP
-                char_data.current_hp = char_data.max_hp;
+        // Spell slots, pact magic and class resources
+        char_data.recover_resources(rest_type);
 
-                // Restore all hit dice
-                for class in &mut char_data.classes {
-                    class.hit_dice_remaining = class.level;
-                }
+        if rest_type == RestType::Long {
+            // Long rest: restore all HP
+            char_data.current_hp = char_data.max_hp;
 
-                // Restore all spell slots
-                for slots in char_data.spells.spell_slots.values_mut() {
-                    slots.current = slots.max;
-                }
+            // Restore all hit dice
+            for class in &mut char_data.classes {
+                class.hit_dice_remaining = class.level;
             }
         }
 
+        let item_charges: Vec<Value> = char_data
+            .inventory
+            .iter()
+            .filter_map(|item| {
+                item.charges.as_ref().map(|charges| {
+                    json!({
+                        "item": item.name,
+                        "current": charges.current,
+                        "max": charges.max,
+                    })
+                })
+            })
+            .collect();
+
         let snapshot_reason = format!(
             "{} rest",
             if rest_type == RestType::Long {
@@ -1764,6 +2349,12 @@ Output:
             "rest_type": rest_type_str,
             "hp_before": old_hp,
             "hp_after": char_data.current_hp,
+            "pact_slots": char_data.spells.pact_slots,
+            "class_resources": char_data.class_resources,
+            "temp_hp": char_data.temp_hp,
+            "exhaustion_level": char_data.exhaustion_level,
+            "concentration": char_data.concentration,
+            "item_charges": item_charges,
             "message": format!("{} completed {} rest. HP: {}/{}",
                 char_data.character_name,
                 rest_type_str,
@@ -1777,41 +2368,45 @@ Output:
     }
 }
 
-/// Tool for updating equipped items (armor, shield, weapons)
-pub struct UpdateEquippedTool {
+/// Tool for spending uses of a class resource (Ki, Rage, Channel Divinity, etc.)
+pub struct UseClassResourceTool {
     db_service: Arc<DatabaseService>,
 }
 
-impl UpdateEquippedTool {
+impl UseClassResourceTool {
     pub fn new(db_service: Arc<DatabaseService>) -> Self {
         Self { db_service }
     }
 }
 
 #[async_trait]
-impl ToolTrait for UpdateEquippedTool {
+impl ToolTrait for UseClassResourceTool {
     fn name(&self) -> &str {
-        "update_equipped"
+        "use_class_resource"
     }
 
     fn description(&self) -> &str {
-        "Update a character's equipped items (armor, shield, weapons).
+        "Spend uses of a limited-use class feature.
 
 Usage:
-- Provide character_id (required)
-- Provide any slots to update: armor, shield, main_hand, off_hand
-- Set to null/empty string to unequip a slot
+- Provide character_id, resource_name, and optionally amount (default 1)
+- Resource names match the character's class_resources (e.g., 'Ki Points', 'Rage',
+  'Channel Divinity', 'Bardic Inspiration', 'Sorcery Points', 'Lay on Hands')
+- Fails if not enough uses remain
 - Creates version snapshot for history tracking
 
 When to use:
-- Character equips new armor or weapons
-- Swapping weapons during combat
-- Unequipping items before rest
-- Setting up character loadout
+- A monk spends ki on Flurry of Blows
+- A barbarian enters a rage
+- A paladin spends Lay on Hands hit points
+- Any class feature with limited uses is used
 
 Output:
-- Updated equipped items confirmed
-- Character version snapshot created"
+- Remaining and maximum uses of the resource
+- Version snapshot created for session history
+
+Use check_spell_slots to see a character's resources. They are restored
+by take_rest according to their recovery (short or long rest)."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -1820,11 +2415,278 @@ Output:
             "properties": {
                 "character_id": {
                     "type": "integer",
-                    "description": "ID of the character"
+                    "description": "ID of the character using the resource"
                 },
-                "armor": {
-                    "type": ["string", "null"],
-                    "description": "Armor to equip (null to unequip)"
+                "resource_name": {
+                    "type": "string",
+                    "description": "Name of the class resource (e.g., 'Ki Points', 'Rage')"
+                },
+                "amount": {
+                    "type": "integer",
+                    "description": "Number of uses to spend (default 1)"
+                }
+            },
+            "required": ["character_id", "resource_name"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        true
+    }
+
+    fn describe_action(&self, arguments: &Value) -> Option<ActionDescription> {
+        let character_id = arguments.get("character_id")?.as_i64()?;
+        let resource_name = arguments.get("resource_name")?.as_str()?;
+        let amount = arguments.get("amount").and_then(|v| v.as_i64()).unwrap_or(1);
+
+        Some(ActionDescription {
+            title: "Use Class Resource".to_string(),
+            description: format!(
+                "Character {} spends {} {}",
+                character_id, amount, resource_name
+            ),
+            changes: ChangeDetail::Generic {
+                items: vec![
+                    format!("Character ID: {}", character_id),
+                    format!("Resource: {}", resource_name),
+                    format!("Amount: {}", amount),
+                ],
+            },
+        })
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let character_id = arguments
+            .get("character_id")
+            .and_then(|v| v.as_i64())
+            .ok_or("Missing 'character_id' parameter")? as i32;
+
+        let resource_name = arguments
+            .get("resource_name")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing 'resource_name' parameter")?;
+
+        let amount = arguments
+            .get("amount")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(1) as i32;
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+        let mut char_service = CharacterService::new(&mut conn);
+        let version = char_service
+            .use_class_resource(character_id, resource_name, amount)
+            .map_err(|e| format!("Failed to use class resource: {}", e))?;
+
+        let (_, char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+        let resource = char_data
+            .class_resources
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(resource_name));
+
+        let result = json!({
+            "success": true,
+            "character_id": character_id,
+            "character_name": char_data.character_name,
+            "resource": resource,
+            "version": version.version_number,
+            "message": version.snapshot_reason
+        });
+
+        debug!(
+            "Character {} used {} {}",
+            character_id, amount, resource_name
+        );
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+/// Tool for restoring uses of a class resource outside of a rest
+pub struct RestoreClassResourceTool {
+    db_service: Arc<DatabaseService>,
+}
+
+impl RestoreClassResourceTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self { db_service }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for RestoreClassResourceTool {
+    fn name(&self) -> &str {
+        "restore_class_resource"
+    }
+
+    fn description(&self) -> &str {
+        "Restore uses of a limited-use class feature without resting.
+
+Usage:
+- Provide character_id and resource_name
+- Optionally provide amount; omit it to restore all uses
+- Never restores above the resource's maximum
+- Creates version snapshot for history tracking
+
+When to use:
+- A feature restores uses (e.g., a sorcerer regaining sorcery points)
+- Correcting a resource that was spent by mistake
+- Rests should use take_rest instead
+
+Output:
+- Remaining and maximum uses of the resource
+- Version snapshot created for session history"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "character_id": {
+                    "type": "integer",
+                    "description": "ID of the character"
+                },
+                "resource_name": {
+                    "type": "string",
+                    "description": "Name of the class resource (e.g., 'Ki Points', 'Rage')"
+                },
+                "amount": {
+                    "type": ["integer", "null"],
+                    "description": "Number of uses to restore (omit to restore all)"
+                }
+            },
+            "required": ["character_id", "resource_name"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        true
+    }
+
+    fn describe_action(&self, arguments: &Value) -> Option<ActionDescription> {
+        let character_id = arguments.get("character_id")?.as_i64()?;
+        let resource_name = arguments.get("resource_name")?.as_str()?;
+        let amount = arguments
+            .get("amount")
+            .and_then(|v| v.as_i64())
+            .map_or("all".to_string(), |a| a.to_string());
+
+        Some(ActionDescription {
+            title: "Restore Class Resource".to_string(),
+            description: format!(
+                "Character {} regains {} {}",
+                character_id, amount, resource_name
+            ),
+            changes: ChangeDetail::Generic {
+                items: vec![
+                    format!("Character ID: {}", character_id),
+                    format!("Resource: {}", resource_name),
+                    format!("Amount: {}", amount),
+                ],
+            },
+        })
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let character_id = arguments
+            .get("character_id")
+            .and_then(|v| v.as_i64())
+            .ok_or("Missing 'character_id' parameter")? as i32;
+
+        let resource_name = arguments
+            .get("resource_name")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing 'resource_name' parameter")?;
+
+        let amount = arguments
+            .get("amount")
+            .and_then(|v| v.as_i64())
+            .map(|a| a as i32);
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+        let mut char_service = CharacterService::new(&mut conn);
+        let version = char_service
+            .restore_class_resource(character_id, resource_name, amount)
+            .map_err(|e| format!("Failed to restore class resource: {}", e))?;
+
+        let (_, char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Failed to retrieve character: {}", e))?;
+        let resource = char_data
+            .class_resources
+            .iter()
+            .find(|r| r.name.eq_ignore_ascii_case(resource_name));
+
+        let result = json!({
+            "success": true,
+            "character_id": character_id,
+            "character_name": char_data.character_name,
+            "resource": resource,
+            "version": version.version_number,
+            "message": version.snapshot_reason
+        });
+
+        debug!("Character {} restored {}", character_id, resource_name);
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+/// Tool for updating equipped items (armor, shield, weapons)
+pub struct UpdateEquippedTool {
+    db_service: Arc<DatabaseService>,
+}
+
+impl UpdateEquippedTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self { db_service }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for UpdateEquippedTool {
+    fn name(&self) -> &str {
+        "update_equipped"
+    }
+
+    fn description(&self) -> &str {
+        "Update a character's equipped items (armor, shield, weapons).
+
+Usage:
+- Provide character_id (required)
+- Provide any slots to update: armor, shield, main_hand, off_hand
+- Set to null/empty string to unequip a slot
+- Creates version snapshot for history tracking
+
+When to use:
+- Character equips new armor or weapons
+- Swapping weapons during combat
+- Unequipping items before rest
+- Setting up character loadout
+
+Output:
+- Updated equipped items confirmed
+- Character version snapshot created"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "character_id": {
+                    "type": "integer",
+                    "description": "ID of the character"
+                },
+                "armor": {
+                    "type": ["string", "null"],
+                    "description": "Armor to equip (null to unequip)"
                 },
                 "shield": {
                     "type": ["string", "null"],
@@ -2129,6 +2991,213 @@ Output:
     }
 }
 
+/// Tool for magic item state on inventory entries (attunement and charges)
+pub struct UpdateInventoryItemTool {
+    db_service: Arc<DatabaseService>,
+}
+
+impl UpdateInventoryItemTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self { db_service }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for UpdateInventoryItemTool {
+    fn name(&self) -> &str {
+        "update_inventory_item"
+    }
+
+    fn description(&self) -> &str {
+        "Attune to magic items and spend or restore their charges.
+
+Usage:
+- Provide character_id, item_name and any of: attuned, use_charges, restore_charges
+- All changes are saved together as one character version
+
+Rules applied:
+- Only items that require attunement can be attuned
+- A character can be attuned to at most 3 items at once
+- Charges can't be spent below 0 or restored above the item's maximum
+
+When to use:
+- A character attunes to a new magic item during a short rest
+- Ending attunement to make room for another item
+- A wand, staff or other charged item is used
+- An item regains charges outside of a normal rest
+
+Output:
+- The item's attunement and charge state
+- Number of items the character is attuned to"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "character_id": {
+                    "type": "integer",
+                    "description": "ID of the character"
+                },
+                "item_name": {
+                    "type": "string",
+                    "description": "Name of the inventory item"
+                },
+                "attuned": {
+                    "type": ["boolean", "null"],
+                    "description": "true to attune to the item, false to end attunement"
+                },
+                "use_charges": {
+                    "type": ["integer", "null"],
+                    "description": "Number of charges to spend"
+                },
+                "restore_charges": {
+                    "type": ["integer", "null"],
+                    "description": "Number of charges to restore"
+                }
+            },
+            "required": ["character_id", "item_name"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        true
+    }
+
+    fn describe_action(&self, arguments: &Value) -> Option<ActionDescription> {
+        let character_id = arguments.get("character_id")?.as_i64()?;
+        let item_name = arguments.get("item_name")?.as_str()?;
+
+        let mut items = vec![
+            format!("Character ID: {}", character_id),
+            format!("Item: {}", item_name),
+        ];
+        match arguments.get("attuned").and_then(|v| v.as_bool()) {
+            Some(true) => items.push("Attune to item".to_string()),
+            Some(false) => items.push("End attunement".to_string()),
+            None => {}
+        }
+        if let Some(used) = arguments.get("use_charges").and_then(|v| v.as_i64()) {
+            items.push(format!("Spend {} charge(s)", used));
+        }
+        if let Some(restored) = arguments.get("restore_charges").and_then(|v| v.as_i64()) {
+            items.push(format!("Restore {} charge(s)", restored));
+        }
+
+        Some(ActionDescription {
+            title: "Update Inventory Item".to_string(),
+            description: format!("Update {} for character {}", item_name, character_id),
+            changes: ChangeDetail::Generic { items },
+        })
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let character_id = arguments
+            .get("character_id")
+            .and_then(|v| v.as_i64())
+            .ok_or("Missing character_id")? as i32;
+
+        let item_name = arguments
+            .get("item_name")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing item_name")?;
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Database error: {}", e))?;
+
+        let mut char_service = CharacterService::new(&mut conn);
+        let (_character, mut char_data) = char_service
+            .get_character(character_id)
+            .map_err(|e| format!("Character not found: {}", e))?;
+        let attuned_count = char_data.attuned_count();
+
+        let item = char_data
+            .get_item_mut(item_name)
+            .ok_or_else(|| format!("Item '{}' not found in inventory", item_name))?;
+        let mut changes = Vec::new();
+
+        match arguments.get("attuned").and_then(|v| v.as_bool()) {
+            Some(true) if !item.attuned => {
+                if !item.requires_attunement {
+                    return Err(format!("{} does not require attunement", item.name).into());
+                }
+                if attuned_count >= MAX_ATTUNED_ITEMS {
+                    return Err(format!(
+                        "Already attuned to {} items; end attunement with one first",
+                        MAX_ATTUNED_ITEMS
+                    )
+                    .into());
+                }
+                item.attuned = true;
+                changes.push(format!("attuned to {}", item.name));
+            }
+            Some(false) if item.attuned => {
+                item.attuned = false;
+                changes.push(format!("ended attunement with {}", item.name));
+            }
+            _ => {}
+        }
+
+        let use_charges = arguments.get("use_charges").and_then(|v| v.as_i64());
+        let restore_charges = arguments.get("restore_charges").and_then(|v| v.as_i64());
+        if use_charges.is_some() || restore_charges.is_some() {
+            let name = item.name.clone();
+            let charges = item
+                .charges
+                .as_mut()
+                .ok_or_else(|| format!("{} has no charges", name))?;
+            if let Some(used) = use_charges {
+                if !charges.expend(used as i32) {
+                    return Err(format!(
+                        "Not enough charges on {} ({}/{}, need {})",
+                        name, charges.current, charges.max, used
+                    )
+                    .into());
+                }
+                changes.push(format!("used {} charge(s) of {}", used, name));
+            }
+            if let Some(restored) = restore_charges {
+                charges.recover(restored as i32);
+                changes.push(format!("restored {} charge(s) to {}", restored, name));
+            }
+        }
+
+        if changes.is_empty() {
+            return Err("No item changes to apply".into());
+        }
+
+        let item = item.clone();
+        let summary = changes.join(", ");
+        char_service
+            .update_character(
+                character_id,
+                char_data.clone(),
+                Some(format!("Item: {}", summary)),
+            )
+            .map_err(|e| format!("Failed to update character: {}", e))?;
+
+        let result = json!({
+            "success": true,
+            "character_id": character_id,
+            "character_name": char_data.character_name,
+            "item": item.name,
+            "requires_attunement": item.requires_attunement,
+            "attuned": item.attuned,
+            "attuned_items": char_data.attuned_count(),
+            "charges": item.charges,
+            "message": format!("{}: {}", char_data.character_name, summary)
+        });
+
+        debug!(
+            "Updated item {} for character {}: {}",
+            item.name, character_id, summary
+        );
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
 /// Tool for updating character currency
 pub struct UpdateCurrencyTool {
     db_service: Arc<DatabaseService>,
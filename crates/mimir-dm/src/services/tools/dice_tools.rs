@@ -0,0 +1,244 @@
+//! Dice rolling tools for LLM interactions
+//!
+//! These tools let the LLM make rolls with the shared dice engine so the DM
+//! can see exactly which dice were rolled and kept.
+
+use async_trait::async_trait;
+use mimir_dm_core::domain::dice::{DiceExpression, DiceRoller};
+use mimir_dm_core::services::{CharacterService, TableService};
+use mimir_dm_core::DatabaseService;
+use mimir_dm_llm::ToolTrait;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Number of recent rolls returned when the LLM asks for the roll log
+const RECENT_ROLL_LIMIT: usize = 20;
+
+/// Tool for rolling dice expressions
+pub struct RollDiceTool {
+    db_service: Arc<DatabaseService>,
+    roller: Mutex<DiceRoller>,
+}
+
+impl RollDiceTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self {
+            db_service,
+            roller: Mutex::new(DiceRoller::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for RollDiceTool {
+    fn name(&self) -> &str {
+        "roll_dice"
+    }
+
+    fn description(&self) -> &str {
+        "Roll dice using standard notation and get a full breakdown.
+
+Usage:
+- Provide an expression like '1d20+5', '4d6kh3', '2d20kl1', '8d6', '3d6!' or 'd%'
+- kh/kl keep the highest/lowest dice, dh/dl drop them, ! makes dice explode on their max
+- Ability modifiers (STR, DEX, CON, INT, WIS, CHA) require a character_id, e.g. '1d8+STR'
+- Optionally provide a reason (e.g. 'Goblin attack roll') to label the roll
+- Set show_log to true to also return recent rolls made with this tool
+
+When to use:
+- Attack rolls, damage, saving throws and ability checks
+- Random outcomes the DM asks you to roll for
+- Rolling ability scores or hit points
+
+Output:
+- Total, per-die results (dropped dice in parentheses) and a readable breakdown"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "expression": {
+                    "type": "string",
+                    "description": "Dice expression, e.g. '1d20+5', '4d6kh3', '1d8+STR'"
+                },
+                "character_id": {
+                    "type": ["integer", "null"],
+                    "description": "Character whose ability modifiers are used for STR/DEX/etc."
+                },
+                "reason": {
+                    "type": ["string", "null"],
+                    "description": "What the roll is for"
+                },
+                "show_log": {
+                    "type": ["boolean", "null"],
+                    "description": "Include recent rolls made with this tool"
+                }
+            },
+            "required": ["expression"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let expression = arguments
+            .get("expression")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing expression")?;
+        let character_id = arguments
+            .get("character_id")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32);
+        let reason = arguments.get("reason").and_then(|v| v.as_str());
+        let show_log = arguments
+            .get("show_log")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let parsed = DiceExpression::parse(expression).map_err(|e| e.to_string())?;
+
+        let abilities = if parsed.uses_abilities() {
+            let character_id = character_id
+                .ok_or("This expression uses ability modifiers - provide a character_id")?;
+            let mut conn = self
+                .db_service
+                .get_connection()
+                .map_err(|e| format!("Database error: {}", e))?;
+            let mut char_service = CharacterService::new(&mut conn);
+            let (_character, data) = char_service
+                .get_character(character_id)
+                .map_err(|e| format!("Character not found: {}", e))?;
+            Some(data.abilities)
+        } else {
+            None
+        };
+
+        let mut roller = self
+            .roller
+            .lock()
+            .map_err(|_| "Dice roller is unavailable")?;
+        let roll = roller
+            .roll_expression(&parsed, abilities.as_ref())
+            .map_err(|e| e.to_string())?;
+
+        debug!("Rolled {}: {}", roll.expression, roll.breakdown);
+
+        let mut result = json!({
+            "expression": roll.expression,
+            "reason": reason,
+            "total": roll.total,
+            "breakdown": roll.breakdown,
+            "terms": roll.terms,
+        });
+
+        if show_log {
+            let log = roller.log();
+            let recent: Vec<_> = log
+                .iter()
+                .skip(log.len().saturating_sub(RECENT_ROLL_LIMIT))
+                .map(|r| json!({ "expression": r.expression, "total": r.total, "breakdown": r.breakdown }))
+                .collect();
+            result["recent_rolls"] = json!(recent);
+        }
+
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
+
+/// Tool for rolling on catalog random tables
+pub struct RollTableTool {
+    db_service: Arc<DatabaseService>,
+    roller: Mutex<DiceRoller>,
+}
+
+impl RollTableTool {
+    pub fn new(db_service: Arc<DatabaseService>) -> Self {
+        Self {
+            db_service,
+            roller: Mutex::new(DiceRoller::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for RollTableTool {
+    fn name(&self) -> &str {
+        "roll_table"
+    }
+
+    fn description(&self) -> &str {
+        "Roll on a random table from the catalog.
+
+Usage:
+- Provide the table name (e.g. 'Trinkets', 'Treasure Hoard: Challenge 0-4')
+- Provide the source book abbreviation (defaults to 'DMG')
+- Tables referenced from the result (e.g. 'roll on Magic Item Table A') are rolled automatically
+
+When to use:
+- Treasure, trinkets, random encounters, wild magic surges and other random outcomes
+- When the DM asks for something rolled from a specific table
+
+Output:
+- The dice rolled, the selected row and the results of any nested table rolls"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Table name, e.g. 'Trinkets'"
+                },
+                "source": {
+                    "type": ["string", "null"],
+                    "description": "Source book abbreviation, e.g. 'DMG', 'XGE' (default 'DMG')"
+                }
+            },
+            "required": ["name"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let name = arguments
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or("Missing name")?;
+        let source = arguments
+            .get("source")
+            .and_then(|v| v.as_str())
+            .unwrap_or("DMG");
+
+        let mut conn = self
+            .db_service
+            .get_connection()
+            .map_err(|e| format!("Database error: {}", e))?;
+        let mut roller = self
+            .roller
+            .lock()
+            .map_err(|_| "Dice roller is unavailable")?;
+
+        let mut table_service = TableService::new(&mut conn);
+        let result = table_service
+            .roll_table_with(name, source, &mut roller)
+            .map_err(|e| format!("Failed to roll table: {}", e))?;
+
+        debug!(
+            "Rolled {} on {}: {}",
+            result.roll,
+            result.table_name,
+            result.result.join(" | ")
+        );
+
+        Ok(serde_json::to_string_pretty(&result)?)
+    }
+}
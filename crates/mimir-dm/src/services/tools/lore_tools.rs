@@ -0,0 +1,156 @@
+//! Lore search tool for LLM interactions
+//!
+//! Lets the LLM retrieve passages from uploaded books and campaign documents
+//! by meaning rather than exact name, so answers can be grounded in the
+//! source text.
+
+use crate::services::lore_service::{search_lore, LoreEmbedder};
+use async_trait::async_trait;
+use mimir_dm_core::models::campaign::{LoreSearchFilter, LoreSourceType};
+use mimir_dm_core::DatabaseService;
+use mimir_dm_llm::ToolTrait;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Default number of passages returned
+const DEFAULT_LORE_LIMIT: i64 = 5;
+
+/// Most passages returned in one call
+const MAX_LORE_LIMIT: i64 = 20;
+
+/// Tool for semantic search over books and campaign documents
+pub struct SearchLoreTool {
+    db_service: Arc<DatabaseService>,
+    embedder: LoreEmbedder,
+}
+
+impl SearchLoreTool {
+    pub fn new(db_service: Arc<DatabaseService>, embedder: LoreEmbedder) -> Self {
+        Self {
+            db_service,
+            embedder,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolTrait for SearchLoreTool {
+    fn name(&self) -> &str {
+        "search_lore"
+    }
+
+    fn description(&self) -> &str {
+        "Search uploaded rulebooks/adventures and campaign documents by meaning.
+
+Usage:
+- Ask a natural language question or describe a topic, e.g. 'how does grappling work' or 'what do we know about the Black Spider'
+- Provide campaign_id to include that campaign's documents (books are always included)
+- Set source_type to 'book' or 'document' to search only one kind of source
+- Only books and documents that have been indexed are searchable
+
+When to use:
+- Rules questions that need the exact book wording
+- Setting and adventure lore that isn't a catalog entry (places, factions, history)
+- Recalling what happened in earlier sessions or what was planned in campaign notes
+
+Output:
+- Matching passages with their source title, section heading and similarity distance (lower is closer)"
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "What to search for, in natural language"
+                },
+                "campaign_id": {
+                    "type": ["integer", "null"],
+                    "description": "Include this campaign's documents in the search"
+                },
+                "source_type": {
+                    "type": ["string", "null"],
+                    "enum": ["book", "document", null],
+                    "description": "Restrict results to books or campaign documents"
+                },
+                "limit": {
+                    "type": ["integer", "null"],
+                    "description": "Number of passages to return (default 5, max 20)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+
+    async fn execute(&self, arguments: Value) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let query = arguments
+            .get("query")
+            .and_then(|v| v.as_str())
+            .filter(|q| !q.trim().is_empty())
+            .ok_or("Missing query")?;
+        let campaign_id = arguments
+            .get("campaign_id")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32);
+        let source_type = match arguments.get("source_type").and_then(|v| v.as_str()) {
+            Some(s) => Some(
+                LoreSourceType::parse(s).ok_or_else(|| format!("Unknown source_type '{}'", s))?,
+            ),
+            None => None,
+        };
+        let limit = arguments
+            .get("limit")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(DEFAULT_LORE_LIMIT)
+            .clamp(1, MAX_LORE_LIMIT);
+
+        let filter = LoreSearchFilter {
+            campaign_id,
+            source_type,
+        };
+        let results = search_lore(&self.db_service, &self.embedder, query, limit, &filter)
+            .await
+            .map_err(|e| format!("Lore search failed: {}", e))?;
+
+        debug!(
+            "search_lore '{}' returned {} passages",
+            query,
+            results.len()
+        );
+
+        if results.is_empty() {
+            return Ok(json!({
+                "query": query,
+                "results": [],
+                "message": "No indexed passages matched. The books or documents may not be indexed yet."
+            })
+            .to_string());
+        }
+
+        let passages: Vec<Value> = results
+            .iter()
+            .map(|r| {
+                json!({
+                    "source": r.title,
+                    "source_type": r.source_type,
+                    "source_key": r.source_key,
+                    "heading": r.heading,
+                    "text": r.content,
+                    "distance": r.distance,
+                })
+            })
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&json!({
+            "query": query,
+            "results": passages,
+        }))?)
+    }
+}
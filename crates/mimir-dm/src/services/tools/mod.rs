@@ -325,7 +325,7 @@ impl ToolRegistry {
             guidance.push_str("- **list_players**: List all players registered in the database\n");
         }
         if self.has_tool("create_character") {
-            guidance.push_str("- **create_character**: Create a new player character (PC) with full stats and details\n");
+            guidance.push_str("- **create_character**: Create a new player character (PC) with full stats and details, including starting equipment packages or starting gold\n");
         }
         if self.has_tool("create_npc") {
             guidance.push_str("- **create_npc**: Create a new NPC - USE THIS for all NPC creation, NOT file templates\n");
@@ -334,7 +334,7 @@ impl ToolRegistry {
             guidance.push_str("- **update_character**: Update character attributes, background, or other details\n");
         }
         if self.has_tool("level_up") {
-            guidance.push_str("- **level_up**: Level up a character to a target level (calculates HP based on class hit dice + CON)\n");
+            guidance.push_str("- **level_up**: Level up a character to a target level (calculates HP based on class hit dice + CON, records invocations, fighting styles, metamagic or maneuvers passed as optional_features)\n");
         }
         guidance.push_str("\n");
 
@@ -342,10 +342,25 @@ impl ToolRegistry {
         guidance.push_str("#### Combat & Health\n");
         guidance.push_str("Use these for combat tracking and health management:\n\n");
         if self.has_tool("update_character_hp") {
-            guidance.push_str("- **update_character_hp**: Apply damage or healing to a character (use negative for damage)\n");
+            guidance.push_str("- **update_character_hp**: Apply damage or healing to a character (use negative for damage); temp HP absorbs damage first and concentration save DCs are reported\n");
+        }
+        if self.has_tool("update_character_status") {
+            guidance.push_str("- **update_character_status**: Set temporary HP, conditions, exhaustion, death save rolls and concentration\n");
         }
         if self.has_tool("take_rest") {
-            guidance.push_str("- **take_rest**: Apply short or long rest mechanics (restore HP, spell slots, hit dice)\n");
+            guidance.push_str("- **take_rest**: Apply short or long rest mechanics (restore HP, spell slots, hit dice, class resources, item charges)\n");
+        }
+        if self.has_tool("use_class_resource") {
+            guidance.push_str("- **use_class_resource**: Spend uses of a class feature (Ki Points, Rage, Channel Divinity, etc.)\n");
+        }
+        if self.has_tool("restore_class_resource") {
+            guidance.push_str("- **restore_class_resource**: Restore uses of a class feature outside of a rest\n");
+        }
+        if self.has_tool("roll_dice") {
+            guidance.push_str("- **roll_dice**: Roll dice expressions (1d20+5, 4d6kh3, 2d20kl1, 1d8+STR) with a full breakdown\n");
+        }
+        if self.has_tool("roll_table") {
+            guidance.push_str("- **roll_table**: Roll on a catalog random table (treasure, encounters, trinkets); nested tables are rolled too\n");
         }
         guidance.push_str("\n");
 
@@ -353,7 +368,10 @@ impl ToolRegistry {
         guidance.push_str("#### Spellcasting\n");
         guidance.push_str("Use these for spell management:\n\n");
         if self.has_tool("check_spell_slots") {
-            guidance.push_str("- **check_spell_slots**: Check available spell slots for spellcasting characters\n");
+            guidance.push_str("- **check_spell_slots**: Check available spell slots, pact magic slots and class resources\n");
+        }
+        if self.has_tool("validate_character") {
+            guidance.push_str("- **validate_character**: Check a character against the rules (skills, feats, spells, multiclassing) and list errors and warnings\n");
         }
         if self.has_tool("cast_spell") {
             guidance.push_str("- **cast_spell**: Cast a spell, consuming the appropriate spell slot\n");
@@ -369,6 +387,9 @@ impl ToolRegistry {
         if self.has_tool("remove_inventory_item") {
             guidance.push_str("- **remove_inventory_item**: Remove an item from inventory\n");
         }
+        if self.has_tool("update_inventory_item") {
+            guidance.push_str("- **update_inventory_item**: Attune to magic items (max 3) and spend or restore item charges\n");
+        }
         if self.has_tool("update_equipped") {
             guidance.push_str("- **update_equipped**: Change what items are equipped (armor, weapons, shield)\n");
         }
@@ -389,6 +410,9 @@ impl ToolRegistry {
         if self.has_tool("search_items") {
             guidance.push_str("- **search_items**: Search equipment, weapons, armor, and magic items\n");
         }
+        if self.has_tool("search_lore") {
+            guidance.push_str("- **search_lore**: Search book text and campaign documents by meaning - use it to ground answers about rules, setting lore or past sessions, and cite the returned titles\n");
+        }
         guidance.push_str("\n");
 
         // Module/Adventure Tools
@@ -558,6 +582,12 @@ pub mod character_write_tools;
 // Catalog query tools
 pub mod catalog_tools;
 
+// Dice rolling tools
+pub mod dice_tools;
+
+// Lore search tools
+pub mod lore_tools;
+
 // Module management tools
 pub mod module_tools;
 
@@ -569,17 +599,21 @@ mod character_tools_test;
 
 // Re-exports for convenience
 pub use catalog_tools::{SearchItemsTool, SearchMonstersTool, SearchSpellsTool};
+pub use dice_tools::{RollDiceTool, RollTableTool};
 pub use character_tools::{
     CheckSpellSlotsTool, GetCharacterStatsTool, GetCharacterTool, ListCampaignCharactersTool,
-    ListNpcsTool, ListPcsTool, ListPlayersTool,
+    ListNpcsTool, ListPcsTool, ListPlayersTool, ValidateCharacterTool,
 };
 pub use character_write_tools::{
     AddInventoryItemTool, CastSpellTool, CreateCharacterTool, CreateNpcTool, LevelUpTool,
-    RemoveInventoryItemTool, TakeRestTool, UpdateCharacterHpTool, UpdateCharacterTool,
-    UpdateCurrencyTool, UpdateEquippedTool,
+    RemoveInventoryItemTool, RestoreClassResourceTool, TakeRestTool, UpdateCharacterHpTool,
+    UpdateCharacterStatusTool, UpdateCharacterTool, UpdateCurrencyTool, UpdateEquippedTool,
+    UpdateInventoryItemTool, UseClassResourceTool,
 };
+pub use lore_tools::SearchLoreTool;
 pub use module_tools::{CreateModuleTool, GetModuleTool, ListModulesTool, UpdateModuleStatusTool};
 
+use crate::services::lore_service::LoreEmbedder;
 use mimir_dm_core::DatabaseService;
 use mimir_dm_llm::tools::{EditFileTool, ListFilesTool, ReadFileTool, WriteFileTool};
 use mimir_dm_llm::FileToolsConfig;
@@ -592,12 +626,20 @@ use std::path::PathBuf;
 /// Both production and tests should use this function to ensure consistency.
 ///
 /// If `campaign_dir` is provided, file tools will be registered with that directory as root.
+/// Lore search is only registered when a `lore_embedder` is available.
 pub fn register_all_tools(
     registry: &mut ToolRegistry,
     db_service: Arc<DatabaseService>,
     todo_state_manager: TodoStateManager,
+    lore_embedder: Option<LoreEmbedder>,
 ) {
-    register_all_tools_with_file_config(registry, db_service, todo_state_manager, None);
+    register_all_tools_with_file_config(
+        registry,
+        db_service,
+        todo_state_manager,
+        lore_embedder,
+        None,
+    );
 }
 
 /// Register all tools with optional campaign-specific file tools
@@ -607,6 +649,7 @@ pub fn register_all_tools_with_file_config(
     registry: &mut ToolRegistry,
     db_service: Arc<DatabaseService>,
     todo_state_manager: TodoStateManager,
+    lore_embedder: Option<LoreEmbedder>,
     campaign_dir: Option<&str>,
 ) {
     // File tools (only if campaign directory is provided)
@@ -630,17 +673,22 @@ pub fn register_all_tools_with_file_config(
     registry.register(Arc::new(ListPcsTool::new(db_service.clone())));
     registry.register(Arc::new(GetCharacterStatsTool::new(db_service.clone())));
     registry.register(Arc::new(CheckSpellSlotsTool::new(db_service.clone())));
+    registry.register(Arc::new(ValidateCharacterTool::new(db_service.clone())));
 
     // Character write tools
     registry.register(Arc::new(CreateCharacterTool::new(db_service.clone())));
     registry.register(Arc::new(CreateNpcTool::new(db_service.clone())));
     registry.register(Arc::new(UpdateCharacterHpTool::new(db_service.clone())));
+    registry.register(Arc::new(UpdateCharacterStatusTool::new(db_service.clone())));
     registry.register(Arc::new(AddInventoryItemTool::new(db_service.clone())));
     registry.register(Arc::new(RemoveInventoryItemTool::new(db_service.clone())));
+    registry.register(Arc::new(UpdateInventoryItemTool::new(db_service.clone())));
     registry.register(Arc::new(UpdateCharacterTool::new(db_service.clone())));
     registry.register(Arc::new(LevelUpTool::new(db_service.clone())));
     registry.register(Arc::new(CastSpellTool::new(db_service.clone())));
     registry.register(Arc::new(TakeRestTool::new(db_service.clone())));
+    registry.register(Arc::new(UseClassResourceTool::new(db_service.clone())));
+    registry.register(Arc::new(RestoreClassResourceTool::new(db_service.clone())));
     registry.register(Arc::new(UpdateEquippedTool::new(db_service.clone())));
     registry.register(Arc::new(UpdateCurrencyTool::new(db_service.clone())));
 
@@ -653,7 +701,16 @@ pub fn register_all_tools_with_file_config(
     // Catalog search tools
     registry.register(Arc::new(SearchMonstersTool::new(db_service.clone())));
     registry.register(Arc::new(SearchSpellsTool::new(db_service.clone())));
-    registry.register(Arc::new(SearchItemsTool::new(db_service)));
+    registry.register(Arc::new(SearchItemsTool::new(db_service.clone())));
+
+    // Lore search (needs an embedding model)
+    if let Some(embedder) = lore_embedder {
+        registry.register(Arc::new(SearchLoreTool::new(db_service.clone(), embedder)));
+    }
+
+    // Dice tools
+    registry.register(Arc::new(RollDiceTool::new(db_service.clone())));
+    registry.register(Arc::new(RollTableTool::new(db_service)));
 
     // Note: Campaign summary is NOT registered as an LLM tool.
     // Story summaries are auto-generated and injected as context during chat processing.